@@ -8,27 +8,11 @@ fn main() -> Result<(), Box<dyn std::error::Error>> {
     ];
 
     // Actually invoke code generation on our search paths.
-    let (source, dependent_paths) =
-        roslibrust::codegen::find_and_generate_ros_messages_without_ros_package_path(p)?;
-    // This returns two things:
-    // 1) A TokenStream which is the rust code we want to generate
-    // 2) A list of paths that if modified would require the code to be regenerated. We use this to inform Cargo
-    //    of when to re-run our build script.
-
-    // It is important for build scripts to only output files to OUT_DIR which is an environment variable set by Cargo.
-    let out_dir = std::env::var_os("OUT_DIR").unwrap();
-    // Name of the file in out_dir we want to write our generated code to
-    let dest_path = std::path::Path::new(&out_dir).join("messages.rs");
-    // Write the generated code to disk
-    std::fs::write(dest_path, source.to_string())?;
-
-    // If we stopped at this point, our code would still work, but Cargo would not know to rebuild
-    // our package when a message file changed.
-    // Cargo recognizes certain command line strings that build scripts print out:
-    for path in &dependent_paths {
-        // Tell cargo to re-run our build script if any of these files change
-        println!("cargo:rerun-if-changed={}", path.display());
-    }
+    // This writes the generated code to messages.rs in OUT_DIR, the only place build scripts should write files,
+    // where src/main.rs include!s it from.
+    // It also prints the "cargo:rerun-if-changed" lines that tell Cargo to re-run this build script
+    // when any of the message files change, without them our package wouldn't be rebuilt when a message changed.
+    roslibrust::codegen::build_helper::generate_to_out_dir_without_ros_package_path(p)?;
 
     Ok(())
 }
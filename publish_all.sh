@@ -8,6 +8,7 @@ PACKAGES=(
     roslibrust_codegen
     roslibrust_codegen_macro
     roslibrust_mock
+    roslibrust_test_util
     roslibrust_ros1
     roslibrust_rosbridge
     roslibrust_zenoh
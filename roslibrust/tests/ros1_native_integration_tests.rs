@@ -45,6 +45,41 @@ mod tests {
         assert_eq!(msg.data, "test");
     }
 
+    #[test_log::test(tokio::test)]
+    async fn test_advertise_by_type_name() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_advertise_by_type_name")
+            .await
+            .unwrap();
+
+        let mut registry = roslibrust::ros1::TypeRegistry::new();
+        registry.register::<std_msgs::String>();
+
+        let err = nh
+            .advertise_by_type_name(&registry, "std_msgs/Bogus", "/test_by_type_name", 1, true)
+            .await;
+        assert!(matches!(err, Err(NodeError::UnknownType(_))));
+
+        let publisher = nh
+            .advertise_by_type_name(&registry, "std_msgs/String", "/test_by_type_name", 1, true)
+            .await
+            .unwrap();
+        let mut subscriber = nh
+            .subscribe::<std_msgs::String>("/test_by_type_name", 1)
+            .await
+            .unwrap();
+
+        publisher
+            .publish(serde_json::json!({ "data": "dynamic" }))
+            .await
+            .unwrap();
+        let msg = timeout(tokio::time::Duration::from_millis(250), subscriber.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.data, "dynamic");
+    }
+
     #[test_log::test(tokio::test)]
     async fn test_subscribe_any() {
         // get a single message in raw bytes and test the bytes are as expected
@@ -73,18 +108,115 @@ mod tests {
     }
 
     #[test_log::test(tokio::test)]
-    async fn test_latching() {
-        let nh = NodeHandle::new("http://localhost:11311", "test_latching")
+    async fn test_fork_subscriber() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_fork_subscriber")
             .await
             .unwrap();
 
-        // Create a publisher that is latching
         let publisher = nh
-            .advertise::<std_msgs::String>("/test_latching", 1, true)
+            .advertise::<std_msgs::String>("/test_fork_subscriber", 1, true)
+            .await
+            .unwrap();
+        let mut subscriber = nh
+            .subscribe::<std_msgs::String>("/test_fork_subscriber", 2)
+            .await
+            .unwrap();
+        let mut fork = subscriber.fork();
+
+        let timeout = tokio::time::Duration::from_millis(250);
+        let msg = |data: &str| std_msgs::String {
+            data: data.to_owned(),
+        };
+        publisher.publish(&msg("first")).await.unwrap();
+        for subscriber in [&mut subscriber, &mut fork] {
+            let res = tokio::time::timeout(timeout, subscriber.next()).await;
+            assert_eq!(res.unwrap().unwrap().unwrap().data, "first");
+        }
+
+        // The fork keeps the subscription alive on its own
+        drop(subscriber);
+        publisher.publish(&msg("second")).await.unwrap();
+        let res = tokio::time::timeout(timeout, fork.next()).await;
+        assert_eq!(res.unwrap().unwrap().unwrap().data, "second");
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_high_priority_publish_jumps_queue() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_priority_publish")
+            .await
+            .unwrap();
+
+        let publisher = nh
+            .advertise::<std_msgs::String>("/test_priority_publish", 10, false)
+            .await
+            .unwrap();
+        let mut subscriber = nh
+            .subscribe::<std_msgs::String>("/test_priority_publish", 10)
+            .await
+            .unwrap();
+
+        let timeout = tokio::time::Duration::from_millis(250);
+        let msg = |data: &str| std_msgs::String {
+            data: data.to_owned(),
+        };
+        // Wait for the connection to be established
+        loop {
+            publisher.publish(&msg("connected")).await.unwrap();
+            if tokio::time::timeout(timeout, subscriber.next())
+                .await
+                .is_ok()
+            {
+                break;
+            }
+        }
+
+        // Nothing is written to the connection until this task yields, so all of these are queued together
+        for i in 0..3 {
+            publisher
+                .publish_with_priority(
+                    &msg(&format!("bulk {i}")),
+                    roslibrust::ros1::Priority::Normal,
+                )
+                .unwrap();
+        }
+        publisher
+            .publish_with_priority(&msg("estop"), roslibrust::ros1::Priority::High)
+            .unwrap();
+
+        let mut received = vec![];
+        while received.len() < 4 {
+            let res = tokio::time::timeout(timeout, subscriber.next()).await;
+            let data = res.unwrap().unwrap().unwrap().data;
+            // Extra messages may remain from waiting for the connection
+            if data != "connected" {
+                received.push(data);
+            }
+        }
+        assert_eq!(received, ["estop", "bulk 0", "bulk 1", "bulk 2"]);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_transport_preference_falls_back_to_tcpros() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_transport_preference")
+            .await
+            .unwrap();
+        // Our own publishers only support TCPROS, so they must pick it from the offered transports
+        nh.set_transport_preference(vec![
+            roslibrust::ros1::Transport::Udpros,
+            roslibrust::ros1::Transport::Tcpros,
+        ])
+        .unwrap();
+
+        let publisher = nh
+            .advertise::<std_msgs::String>("/test_transport_preference", 1, true)
+            .await
+            .unwrap();
+
+        let mut subscriber = nh
+            .subscribe::<std_msgs::String>("/test_transport_preference", 1)
             .await
             .unwrap();
 
-        // Publish message to no one
         publisher
             .publish(&std_msgs::String {
                 data: "test".to_owned(),
@@ -92,46 +224,1180 @@ mod tests {
             .await
             .unwrap();
 
-        // Create a subscriber that will connect to the publisher
+        let res =
+            tokio::time::timeout(tokio::time::Duration::from_millis(250), subscriber.next()).await;
+        assert_eq!(res.unwrap().unwrap().unwrap().data, "test");
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_subscribe_stamped() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_subscribe_stamped")
+            .await
+            .unwrap();
+
+        let publisher = nh
+            .advertise::<std_msgs::String>("/test_subscribe_stamped", 1, true)
+            .await
+            .unwrap();
+
         let mut subscriber = nh
-            .subscribe::<std_msgs::String>("/test_latching", 1)
+            .subscribe::<std_msgs::String>("/test_subscribe_stamped", 1)
+            .await
+            .unwrap();
+
+        let before = std::time::SystemTime::now();
+        publisher
+            .publish(&std_msgs::String {
+                data: "test".to_owned(),
+            })
+            .await
+            .unwrap();
+
+        let res = tokio::time::timeout(
+            tokio::time::Duration::from_millis(250),
+            subscriber.next_stamped(),
+        )
+        .await;
+        let stamped = res.unwrap().unwrap().unwrap();
+        assert_eq!(stamped.msg.data, "test");
+        assert_eq!(stamped.caller_id, "/test_subscribe_stamped");
+        assert_eq!(stamped.size, 12);
+        assert!(stamped.receive_time >= before);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_subscribe_multi() {
+        use roslibrust::ros1::OneOf;
+
+        // Publishers of two different types on the same topic, as happens mid migration
+        let nh_old = NodeHandle::new("http://localhost:11311", "test_subscribe_multi_old")
+            .await
+            .unwrap();
+        let nh_new = NodeHandle::new("http://localhost:11311", "test_subscribe_multi_new")
+            .await
+            .unwrap();
+        let old_publisher = nh_old
+            .advertise::<std_msgs::String>("/test_subscribe_multi", 1, true)
+            .await
+            .unwrap();
+        let new_publisher = nh_new
+            .advertise::<std_msgs::Int32>("/test_subscribe_multi", 1, true)
+            .await
+            .unwrap();
+        old_publisher
+            .publish(&std_msgs::String {
+                data: "old".to_owned(),
+            })
+            .await
+            .unwrap();
+        new_publisher
+            .publish(&std_msgs::Int32 { data: 2 })
+            .await
+            .unwrap();
+
+        let nh = NodeHandle::new("http://localhost:11311", "test_subscribe_multi")
+            .await
+            .unwrap();
+        let mut subscriber = nh
+            .subscribe_multi::<(std_msgs::String, std_msgs::Int32)>("/test_subscribe_multi", 2)
+            .await
+            .unwrap();
+
+        let mut received = vec![];
+        for _ in 0..2 {
+            let msg = timeout(tokio::time::Duration::from_millis(500), subscriber.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            received.push(msg);
+        }
+        assert!(received.contains(&OneOf::First(std_msgs::String {
+            data: "old".to_owned()
+        })));
+        assert!(received.contains(&OneOf::Second(std_msgs::Int32 { data: 2 })));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_compressed_publisher() {
+        use roslibrust::ros1::{AdvertiseOptions, Compression};
+
+        // Messages arrive intact whether or not the compression feature is enabled
+        let nh_pub = NodeHandle::new("http://localhost:11311", "test_compressed_publisher_pub")
+            .await
+            .unwrap();
+        let nh_sub = NodeHandle::new("http://localhost:11311", "test_compressed_publisher_sub")
+            .await
+            .unwrap();
+        let publisher = nh_pub
+            .advertise_with_options::<std_msgs::String>(
+                "/test_compressed_publisher",
+                AdvertiseOptions::new(1)
+                    .latching(true)
+                    .compression(Compression::Lz4),
+            )
+            .await
+            .unwrap();
+        let mut subscriber = nh_sub
+            .subscribe::<std_msgs::String>("/test_compressed_publisher", 1)
+            .await
+            .unwrap();
+
+        let data = "compressible ".repeat(100);
+        publisher
+            .publish(&std_msgs::String { data: data.clone() })
+            .await
+            .unwrap();
+        let msg = timeout(tokio::time::Duration::from_millis(500), subscriber.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.data, data);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_shared_memory_publisher() {
+        use roslibrust::ros1::AdvertiseOptions;
+
+        // Messages arrive intact whether or not the shm feature is enabled
+        let nh_pub = NodeHandle::new("http://localhost:11311", "test_shared_memory_pub")
+            .await
+            .unwrap();
+        let nh_sub = NodeHandle::new("http://localhost:11311", "test_shared_memory_sub")
+            .await
+            .unwrap();
+        let publisher = nh_pub
+            .advertise_with_options::<std_msgs::String>(
+                "/test_shared_memory",
+                AdvertiseOptions::new(2).shared_memory(true),
+            )
+            .await
+            .unwrap();
+        let mut subscriber = nh_sub
+            .subscribe::<std_msgs::String>("/test_shared_memory", 2)
+            .await
+            .unwrap();
+        // Give the subscriber time to connect since the publisher isn't latching
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+        for i in 0..2 {
+            publisher
+                .publish(&std_msgs::String {
+                    data: format!("shared {i}"),
+                })
+                .await
+                .unwrap();
+            let msg = timeout(tokio::time::Duration::from_millis(500), subscriber.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            assert_eq!(msg.data, format!("shared {i}"));
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_node_stats_and_health_server() {
+        use tokio::io::{AsyncReadExt, AsyncWriteExt};
+
+        let nh = NodeHandle::new("http://localhost:11311", "test_node_health")
+            .await
+            .unwrap();
+        let publisher = nh
+            .advertise::<std_msgs::String>("/test_node_health", 1, false)
+            .await
+            .unwrap();
+        let mut subscriber = nh
+            .subscribe::<std_msgs::String>("/test_node_health", 1)
+            .await
+            .unwrap();
+        // Give the subscriber time to connect since the publisher isn't latching
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        publisher
+            .publish(&std_msgs::String {
+                data: "health".to_owned(),
+            })
+            .await
+            .unwrap();
+        timeout(tokio::time::Duration::from_millis(500), subscriber.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+
+        let stats = nh.stats().await.unwrap();
+        assert_eq!(stats.node_name, "/test_node_health");
+        let publication = stats
+            .publications
+            .iter()
+            .find(|topic| topic.topic == "/test_node_health")
+            .unwrap();
+        assert_eq!(publication.topic_type, "std_msgs/String");
+        assert!(publication.last_message_age_secs.is_some());
+        assert_eq!(publication.message_count, 1);
+        // Length prefixed "health"
+        assert_eq!(publication.byte_count, 14);
+        assert_eq!(publication.queue_depth, Some(0));
+        assert_eq!(publication.dropped_count, 0);
+        let subscription = &stats.subscriptions[0];
+        assert_eq!(subscription.connections, 1);
+        assert!(subscription.last_message_age_secs.is_some());
+        assert_eq!(subscription.message_count, 1);
+        assert!(stats.services.is_empty());
+
+        let server = nh.serve_health(([127, 0, 0, 1], 0).into()).unwrap();
+        let addr = server.local_addr();
+        let get = |path: &'static str| async move {
+            let mut stream = tokio::net::TcpStream::connect(addr).await.unwrap();
+            stream
+                .write_all(
+                    format!("GET {path} HTTP/1.1\r\nHost: localhost\r\nConnection: close\r\n\r\n")
+                        .as_bytes(),
+                )
+                .await
+                .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).await.unwrap();
+            response
+        };
+        assert!(get("/livez").await.starts_with("HTTP/1.1 200"));
+        assert!(get("/readyz").await.starts_with("HTTP/1.1 200"));
+        let response = get("/stats").await;
+        assert!(response.starts_with("HTTP/1.1 200"));
+        assert!(response.contains("\"topic\":\"/test_node_health\""));
+        assert!(get("/other").await.starts_with("HTTP/1.1 404"));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_publish_stats() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_publish_stats")
+            .await
+            .unwrap();
+        let _server = nh
+            .advertise_service::<std_srvs::Trigger, _>("~trigger", |_| {
+                Ok(std_srvs::TriggerResponse {
+                    success: true,
+                    message: String::new(),
+                })
+            })
+            .await
+            .unwrap();
+        let mut statistics = nh
+            .subscribe::<std_msgs::String>("/test_publish_stats/statistics", 1)
+            .await
+            .unwrap();
+        let _reporter = nh
+            .publish_stats(tokio::time::Duration::from_millis(100))
+            .await
+            .unwrap();
+
+        let msg = timeout(tokio::time::Duration::from_secs(2), statistics.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        let stats: serde_json::Value = serde_json::from_str(&msg.data).unwrap();
+        assert_eq!(stats["node_name"], "/test_publish_stats");
+        assert_eq!(stats["services"][0]["service_type"], "std_srvs/Trigger");
+        assert_eq!(stats["services"][0]["request_count"], 0);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_task_supervisor() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_task_supervisor")
+            .await
+            .unwrap();
+        let publisher = nh
+            .advertise::<std_msgs::String>("/test_task_supervisor", 1, false)
+            .await
+            .unwrap();
+
+        let supervisor = nh.supervisor().await.unwrap();
+        assert!(supervisor.tasks().iter().any(|task| {
+            task.kind == roslibrust_ros1::TaskKind::Publisher
+                && task.name == "/test_task_supervisor"
+        }));
+
+        let mut failures = supervisor.failures();
+        supervisor.spawn("crashing", async { panic!("crashed on purpose") });
+        let failure = timeout(tokio::time::Duration::from_millis(500), failures.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(failure.name, "crashing");
+        assert_eq!(failure.message, "crashed on purpose");
+
+        // User tasks are stopped when the node shuts down
+        let task = supervisor.spawn("forever", std::future::pending());
+        drop(publisher);
+        drop(nh);
+        let result = timeout(tokio::time::Duration::from_millis(500), task)
+            .await
+            .unwrap();
+        assert!(result.unwrap_err().is_cancelled());
+        assert!(supervisor.is_shut_down());
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_rejected_subscriber_does_not_stop_publication() {
+        use roslibrust_ros1::{ConnectionEvent, TaskKind};
+
+        let nh = NodeHandle::new("http://localhost:11311", "test_connection_events_pub")
+            .await
+            .unwrap();
+        let mut events = nh.supervisor().await.unwrap().connection_events();
+        let publisher = nh
+            .advertise::<std_msgs::String>("/test_connection_events", 1, true)
+            .await
+            .unwrap();
+
+        // Subscribing with the wrong type is rejected by the publisher due to the md5sum mismatch
+        let wrong_nh = NodeHandle::new("http://localhost:11311", "test_connection_events_wrong")
+            .await
+            .unwrap();
+        let _wrong = wrong_nh
+            .subscribe::<std_msgs::Header>("/test_connection_events", 1)
+            .await
+            .unwrap();
+        let event = timeout(tokio::time::Duration::from_secs(1), events.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        match event {
+            ConnectionEvent::Failed {
+                name, kind, error, ..
+            } => {
+                assert_eq!(name, "/test_connection_events");
+                assert_eq!(kind, TaskKind::Publisher);
+                assert!(error.contains("md5sums do not match"), "{error}");
+                // The fields which differ are listed
+                assert!(
+                    error
+                        .contains("std_msgs/String: `string data` is only in the local definition"),
+                    "{error}"
+                );
+                assert!(
+                    error.contains(
+                        "std_msgs/String: `string frame_id` is only in the remote definition"
+                    ),
+                    "{error}"
+                );
+            }
+            event => panic!("Expected a failed connection, got {event:?}"),
+        }
+
+        // The publication carries on serving other subscribers
+        let sub_nh = NodeHandle::new("http://localhost:11311", "test_connection_events_sub")
+            .await
+            .unwrap();
+        let mut subscriber = sub_nh
+            .subscribe::<std_msgs::String>("/test_connection_events", 1)
+            .await
+            .unwrap();
+        publisher
+            .publish(&std_msgs::String {
+                data: "still here".to_owned(),
+            })
+            .await
+            .unwrap();
+        let msg = timeout(tokio::time::Duration::from_secs(1), subscriber.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.data, "still here");
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_max_message_size() {
+        use roslibrust_ros1::{AdvertiseOptions, ConnectionEvent, PublisherError, TaskKind};
+
+        let nh = NodeHandle::new("http://localhost:11311", "test_max_message_size_pub")
+            .await
+            .unwrap();
+        let publisher = nh
+            .advertise_with_options::<std_msgs::String>(
+                "/test_max_message_size",
+                AdvertiseOptions::new(1).max_message_size(64),
+            )
+            .await
+            .unwrap();
+        let err = publisher
+            .publish(&std_msgs::String {
+                data: "x".repeat(100),
+            })
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            err,
+            PublisherError::MessageTooLarge { size: 104, max: 64 }
+        ));
+
+        // A subscriber refuses messages over its own limit and drops the connection
+        let sub_nh = NodeHandle::new("http://localhost:11311", "test_max_message_size_sub")
+            .await
+            .unwrap();
+        sub_nh.set_max_message_size(16).unwrap();
+        let mut events = sub_nh.supervisor().await.unwrap().connection_events();
+        let mut subscriber = sub_nh
+            .subscribe::<std_msgs::String>("/test_max_message_size", 1)
+            .await
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+        publisher
+            .publish(&std_msgs::String {
+                data: "x".repeat(32),
+            })
+            .await
+            .unwrap();
+        let failed = timeout(tokio::time::Duration::from_secs(1), async {
+            loop {
+                if let ConnectionEvent::Failed { kind, error, .. } = events.recv().await.unwrap() {
+                    break (kind, error);
+                }
+            }
+        })
+        .await
+        .unwrap();
+        assert_eq!(failed.0, TaskKind::Subscriber);
+        assert!(failed.1.contains("maximum message size"), "{}", failed.1);
+        assert!(
+            timeout(tokio::time::Duration::from_millis(200), subscriber.next())
+                .await
+                .is_err()
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_publisher_builder() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_publisher_builder")
+            .await
+            .unwrap();
+
+        let publisher = nh
+            .advertise_builder::<geometry_msgs::PointStamped>("/test_publisher_builder")
+            .latched()
+            .queue_size(5)
+            .nodelay()
+            .frame_id("base_link")
+            .map_before_send(|msg| msg.point.x += 1.0)
+            .build()
+            .await
+            .unwrap();
+
+        let mut subscriber = nh
+            .subscribe::<geometry_msgs::PointStamped>("/test_publisher_builder", 5)
+            .await
+            .unwrap();
+
+        let mut msg = geometry_msgs::PointStamped::default();
+        publisher.publish(&msg).await.unwrap();
+        // An explicit frame_id is left alone
+        msg.header.frame_id = "map".to_owned();
+        publisher.publish(&msg).await.unwrap();
+
+        let first = timeout(tokio::time::Duration::from_millis(250), subscriber.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(first.header.frame_id, "base_link");
+        assert_eq!(first.point.x, 1.0);
+        let second = timeout(tokio::time::Duration::from_millis(250), subscriber.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(second.header.frame_id, "map");
+        // The caller's message is not modified by the hook
+        assert_eq!(msg.point.x, 0.0);
+
+        // frame_id can only be filled in for messages starting with a Header
+        let res = nh
+            .advertise_builder::<std_msgs::String>("/test_publisher_builder_string")
+            .frame_id("base_link")
+            .build()
+            .await;
+        assert!(res.is_err());
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_reject_older_than() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_reject_older_than")
+            .await
+            .unwrap();
+        let publisher = nh
+            .advertise::<geometry_msgs::PointStamped>("/test_reject_older_than", 5, false)
+            .await
+            .unwrap();
+        let mut subscriber = nh
+            .subscribe_builder::<geometry_msgs::PointStamped>("/test_reject_older_than")
+            .reject_older_than(std::time::Duration::from_secs(1))
+            .build()
+            .await
+            .unwrap();
+        // Give the subscriber time to connect
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+        let now = std::time::SystemTime::now();
+        let mut msg = geometry_msgs::PointStamped::default();
+        msg.header.stamp = (now - std::time::Duration::from_secs(10))
+            .try_into()
+            .unwrap();
+        msg.point.x = 1.0;
+        publisher.publish(&msg).await.unwrap();
+        msg.header.stamp = now.try_into().unwrap();
+        msg.point.x = 2.0;
+        publisher.publish(&msg).await.unwrap();
+
+        let received = timeout(tokio::time::Duration::from_millis(250), subscriber.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(received.point.x, 2.0);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_subscriber_queue_watermarks() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_subscriber_queue_watermarks")
+            .await
+            .unwrap();
+        let publisher = nh
+            .advertise::<std_msgs::String>("/test_subscriber_queue_watermarks", 10, false)
+            .await
+            .unwrap();
+        let events = std::sync::Arc::new(std::sync::Mutex::new(vec![]));
+        let recorded = events.clone();
+        let mut subscriber = nh
+            .subscribe_builder::<std_msgs::String>("/test_subscriber_queue_watermarks")
+            .queue(10)
+            .queue_watermarks(roslibrust::ros1::Watermarks::new(
+                5,
+                0,
+                move |pressure, _| {
+                    recorded.lock().unwrap().push(pressure);
+                },
+            ))
+            .build()
+            .await
+            .unwrap();
+        // Give the subscriber time to connect
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+        for i in 0..8 {
+            publisher
+                .publish(&std_msgs::String {
+                    data: i.to_string(),
+                })
+                .await
+                .unwrap();
+        }
+        tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+        assert_eq!(subscriber.queue_len(), 8);
+        for _ in 0..8 {
+            subscriber.next().await.unwrap().unwrap();
+        }
+        assert!(
+            timeout(tokio::time::Duration::from_millis(50), subscriber.next())
+                .await
+                .is_err()
+        );
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                roslibrust::ros1::QueuePressure::High,
+                roslibrust::ros1::QueuePressure::Low
+            ]
+        );
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_tolerate_trailing_changes() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_tolerate_trailing_changes")
+            .await
+            .unwrap();
+        // An older version of geometry_msgs/Point, without z
+        let publisher = nh
+            .advertise_any(
+                "/test_tolerate_trailing_changes",
+                "geometry_msgs/Point",
+                "float64 x\nfloat64 y\n",
+                1,
+                false,
+            )
+            .await
+            .unwrap();
+        let mut subscriber = nh
+            .subscribe_builder::<geometry_msgs::Point>("/test_tolerate_trailing_changes")
+            .tolerate_trailing_changes()
+            .build()
+            .await
+            .unwrap();
+        // Give the subscriber time to connect
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+        let mut msg_raw = 16u32.to_le_bytes().to_vec();
+        msg_raw.extend_from_slice(&1.0f64.to_le_bytes());
+        msg_raw.extend_from_slice(&2.0f64.to_le_bytes());
+        publisher.publish(msg_raw).await.unwrap();
+
+        let received = timeout(tokio::time::Duration::from_millis(250), subscriber.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!((received.x, received.y, received.z), (1.0, 2.0, 0.0));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_deadline_and_liveliness() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_deadline_and_liveliness")
+            .await
+            .unwrap();
+        let (deadline_sender, mut deadline_missed) = tokio::sync::mpsc::unbounded_channel();
+        let (liveliness_sender, mut liveliness) = tokio::sync::mpsc::unbounded_channel();
+        let _subscriber = nh
+            .subscribe_builder::<std_msgs::String>("/test_deadline_and_liveliness")
+            .on_deadline_missed(tokio::time::Duration::from_millis(200), move |gap| {
+                let _ = deadline_sender.send(gap);
+            })
+            .on_liveliness_changed(
+                tokio::time::Duration::from_millis(500),
+                move |caller_id, state| {
+                    let _ = liveliness_sender.send((caller_id.to_owned(), state));
+                },
+            )
+            .build()
+            .await
+            .unwrap();
+
+        // Nothing has been published yet
+        let gap = timeout(
+            tokio::time::Duration::from_millis(500),
+            deadline_missed.recv(),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(gap >= tokio::time::Duration::from_millis(200));
+
+        let publisher = nh
+            .advertise_builder::<std_msgs::String>("/test_deadline_and_liveliness")
+            .heartbeat(tokio::time::Duration::from_millis(100))
+            .build()
+            .await
+            .unwrap();
+        let (caller_id, state) = timeout(tokio::time::Duration::from_secs(2), liveliness.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(caller_id, "/test_deadline_and_liveliness");
+        assert_eq!(state, roslibrust::ros1::Liveliness::Alive);
+
+        drop(publisher);
+        let (_, state) = timeout(tokio::time::Duration::from_secs(2), liveliness.recv())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(state, roslibrust::ros1::Liveliness::NotAlive);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_remappings_param() {
+        let setup = NodeHandle::new("http://localhost:11311", "test_remappings_param_setup")
+            .await
+            .unwrap();
+        let remappings =
+            std::collections::HashMap::from([("in", "/test_remappings_param/remapped")]);
+        setup
+            .set_param("/test_remappings_param/remappings", &remappings)
+            .await
+            .unwrap();
+
+        let nh = NodeHandle::new("http://localhost:11311", "test_remappings_param")
+            .await
+            .unwrap();
+        let mut subscriber = nh.subscribe::<std_msgs::String>("in", 1).await.unwrap();
+        let publisher = setup
+            .advertise::<std_msgs::String>("/test_remappings_param/remapped", 1, false)
+            .await
+            .unwrap();
+        // Give the subscriber time to connect
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+        publisher
+            .publish(&std_msgs::String {
+                data: "remapped".to_owned(),
+            })
+            .await
+            .unwrap();
+        let received = timeout(tokio::time::Duration::from_millis(250), subscriber.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(received.data, "remapped");
+        setup
+            .delete_param("/test_remappings_param/remappings")
+            .await
+            .unwrap();
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_publish_iter_and_owned() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_publish_iter_and_owned")
+            .await
+            .unwrap();
+
+        let publisher = nh
+            .advertise_builder::<std_msgs::Int32>("/test_publish_iter_and_owned")
+            .latched()
+            .queue_size(100)
+            .map_before_send(|msg| msg.data *= 2)
+            .build()
+            .await
+            .unwrap();
+        let mut subscriber = nh
+            .subscribe::<std_msgs::Int32>("/test_publish_iter_and_owned", 100)
+            .await
+            .unwrap();
+        // Give the subscriber time to connect so the batch isn't only seen as the latched message
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+        let batch: Vec<_> = (0..50).map(|data| std_msgs::Int32 { data }).collect();
+        publisher.publish_iter(&batch).await.unwrap();
+        publisher
+            .publish_owned(std_msgs::Int32 { data: 50 })
+            .await
+            .unwrap();
+        // An empty batch sends nothing
+        publisher
+            .publish_iter(Vec::<std_msgs::Int32>::new())
+            .await
+            .unwrap();
+        // Pre-serialized messages skip map_before_send
+        publisher
+            .publish_serialized(vec![4, 0, 0, 0, 102, 0, 0, 0])
+            .unwrap();
+        // Bytes which don't match their length prefix are rejected
+        assert!(publisher.publish_serialized(vec![8, 0, 0, 0, 1]).is_err());
+
+        for expected in 0..=51 {
+            let msg = timeout(tokio::time::Duration::from_millis(250), subscriber.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            assert_eq!(msg.data, expected * 2);
+        }
+
+        // The last message published is the latched one
+        let mut late = nh
+            .subscribe::<std_msgs::Int32>("/test_publish_iter_and_owned", 1)
+            .await
+            .unwrap();
+        let msg = timeout(tokio::time::Duration::from_millis(500), late.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.data, 102);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_cached_params() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_cached_params")
+            .await
+            .unwrap();
+        let other = NodeHandle::new("http://localhost:11311", "test_cached_params_other")
+            .await
+            .unwrap();
+
+        nh.set_param("~gain", &1.5).await.unwrap();
+        let params = nh.cached_params().await.unwrap();
+        assert_eq!(params.get::<f64>("~gain").await.unwrap(), Some(1.5));
+        assert!(!params.has("~missing").await.unwrap());
+
+        // Changed by another node, the master notifies us
+        other
+            .set_param("/test_cached_params/gain", &2.5)
+            .await
+            .unwrap();
+        timeout(tokio::time::Duration::from_secs(2), async {
+            while params.get::<f64>("~gain").await.unwrap() != Some(2.5) {
+                tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        other
+            .delete_param("/test_cached_params/gain")
+            .await
+            .unwrap();
+        timeout(tokio::time::Duration::from_secs(2), async {
+            while params.has("~gain").await.unwrap() {
+                tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_declare_param() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_declare_param")
+            .await
+            .unwrap();
+        let _ = nh.delete_param("~gain").await;
+        let positive = |gain: &f64| {
+            (*gain > 0.0)
+                .then_some(())
+                .ok_or("must be positive".to_string())
+        };
+
+        // The default is set on the master when the parameter is missing
+        let mut gain = nh.declare_param("~gain", 1.0, positive).await.unwrap();
+        assert_eq!(gain.key(), "/test_declare_param/gain");
+        assert_eq!(gain.get(), 1.0);
+        assert_eq!(nh.get_param::<f64>("~gain").await.unwrap(), Some(1.0));
+
+        // Valid updates are applied live
+        nh.set_param("~gain", &2.0).await.unwrap();
+        let updated = timeout(tokio::time::Duration::from_secs(2), gain.changed())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(updated, 2.0);
+
+        // Invalid updates are ignored
+        nh.set_param("~gain", &-1.0).await.unwrap();
+        nh.set_param("~gain", &"not a number").await.unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+        assert_eq!(gain.get(), 2.0);
+        assert!(gain.set(-3.0).await.is_err());
+
+        // An existing invalid value fails the declaration
+        let res = nh.declare_param("~gain", 1.0, positive).await;
+        assert!(matches!(res, Err(NodeError::InvalidParam { .. })));
+        nh.delete_param("~gain").await.unwrap();
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_strict_type_check() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_strict_type_check")
+            .await
+            .unwrap();
+        let other = NodeHandle::new("http://localhost:11311", "test_strict_type_check_other")
+            .await
+            .unwrap();
+        let _existing = other
+            .advertise::<std_msgs::String>("/test_strict_type_check", 1, false)
+            .await
+            .unwrap();
+
+        let res = nh
+            .advertise_builder::<std_msgs::Int32>("/test_strict_type_check")
+            .strict_type_check()
+            .build()
+            .await;
+        match res {
+            Err(NodeError::TopicTypeMismatch {
+                existing,
+                requested,
+                ..
+            }) => {
+                assert_eq!(existing, "std_msgs/String");
+                assert_eq!(requested, "std_msgs/Int32");
+            }
+            res => panic!("Expected a type mismatch, got {:?}", res.err()),
+        }
+
+        // Relative names are resolved before being compared with the master's global names
+        let res = nh
+            .advertise_builder::<std_msgs::Int32>("test_strict_type_check")
+            .strict_type_check()
+            .build()
+            .await;
+        match res {
+            Err(NodeError::TopicTypeMismatch { topic, .. }) => {
+                assert_eq!(topic, "/test_strict_type_check");
+            }
+            res => panic!("Expected a type mismatch, got {:?}", res.err()),
+        }
+
+        // The same type, or a topic the master doesn't know about, is advertised as usual
+        nh.advertise_builder::<std_msgs::String>("/test_strict_type_check")
+            .strict_type_check()
+            .build()
+            .await
+            .unwrap();
+        nh.advertise_builder::<std_msgs::Int32>("/test_strict_type_check_new")
+            .strict_type_check()
+            .build()
+            .await
+            .unwrap();
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_load_params() {
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct Limits {
+            max_speed: f64,
+        }
+        #[derive(serde::Deserialize, Debug, PartialEq)]
+        struct Config {
+            name: String,
+            limits: Limits,
+            #[serde(default)]
+            verbose: bool,
+        }
+
+        let nh = NodeHandle::new("http://localhost:11311", "test_load_params")
+            .await
+            .unwrap();
+        let _ = nh.delete_param("~").await;
+        nh.set_param("~name", &"robot").await.unwrap();
+
+        // A missing parameter is named in the error
+        let err = nh.load_params::<Config>("~").await.unwrap_err();
+        assert!(err.to_string().contains("limits"), "{err}");
+
+        nh.set_param("~limits/max_speed", &1.5).await.unwrap();
+        let config: Config = nh.load_params("~").await.unwrap();
+        assert_eq!(
+            config,
+            Config {
+                name: "robot".to_owned(),
+                limits: Limits { max_speed: 1.5 },
+                verbose: false,
+            }
+        );
+
+        let live = nh.load_params_live::<Config>("~").await.unwrap();
+        nh.set_param("~limits/max_speed", &2.5).await.unwrap();
+        timeout(tokio::time::Duration::from_secs(2), async {
+            while live.get().limits.max_speed != 2.5 {
+                tokio::time::sleep(tokio::time::Duration::from_millis(10)).await;
+            }
+        })
+        .await
+        .unwrap();
+
+        // Changes that no longer fit the struct are ignored
+        nh.set_param("~limits/max_speed", &"fast").await.unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(250)).await;
+        assert_eq!(live.shared().load().limits.max_speed, 2.5);
+        nh.delete_param("~").await.unwrap();
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_subscriber_builder() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_subscriber_builder")
+            .await
+            .unwrap();
+
+        // Nothing is published on the topic
+        let res = nh
+            .subscribe_builder::<std_msgs::Int32>("/test_subscriber_builder_silent")
+            .first_message_timeout(tokio::time::Duration::from_millis(250))
+            .build()
+            .await;
+        assert!(matches!(
+            res,
+            Err(NodeError::FirstMessageTimeout { topic, .. }) if topic == "/test_subscriber_builder_silent"
+        ));
+
+        // UInt32 and Int32 have different md5sums but the same layout on the wire
+        let publisher = nh
+            .advertise_builder::<std_msgs::UInt32>("/test_subscriber_builder")
+            .latched()
+            .build()
+            .await
+            .unwrap();
+        publisher
+            .publish(&std_msgs::UInt32 { data: 7 })
+            .await
+            .unwrap();
+
+        let mut subscriber = nh
+            .subscribe_builder::<std_msgs::Int32>("/test_subscriber_builder")
+            .queue(1)
+            .no_md5_check()
+            .reliable_only()
+            .first_message_timeout(tokio::time::Duration::from_secs(2))
+            .build()
+            .await
+            .unwrap();
+        // The message waited for is still queued
+        let msg = timeout(tokio::time::Duration::from_millis(10), subscriber.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.data, 7);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_subscribe_mapped_survives_lag() {
+        use roslibrust::{adapters::MappedError, Error, TopicProvider};
+
+        #[derive(Debug)]
+        struct Count(i32);
+
+        impl From<std_msgs::Int32> for Count {
+            fn from(msg: std_msgs::Int32) -> Self {
+                Count(msg.data)
+            }
+        }
+
+        let nh = NodeHandle::new("http://localhost:11311", "test_subscribe_mapped_lag")
+            .await
+            .unwrap();
+        let publisher = nh
+            .advertise::<std_msgs::Int32>("/test_subscribe_mapped_lag", 100, false)
+            .await
+            .unwrap();
+        let mut counts = nh
+            .subscribe_mapped::<std_msgs::Int32, Count>("/test_subscribe_mapped_lag")
+            .await
+            .unwrap();
+        tokio::time::sleep(tokio::time::Duration::from_millis(500)).await;
+
+        // Far more than the subscriber queues while nothing is reading, so it falls behind
+        for data in 0..100 {
+            publisher.publish(&std_msgs::Int32 { data }).await.unwrap();
+        }
+
+        // Falling behind is reported, but later messages still arrive
+        loop {
+            match timeout(tokio::time::Duration::from_secs(2), counts.next())
+                .await
+                .expect("Mapped subscriber stopped receiving")
+            {
+                Ok(Count(99)) => break,
+                Ok(_) => {}
+                Err(MappedError::Ros(Error::Disconnected)) => {
+                    panic!("Lagging ended the subscription")
+                }
+                Err(e) => info!("Mapped subscriber reported: {e}"),
+            }
+        }
+
+        // Only shutting the node down ends it
+        std::mem::drop(publisher);
+        std::mem::drop(nh);
+        let res = timeout(tokio::time::Duration::from_secs(2), counts.next())
+            .await
+            .unwrap();
+        assert!(matches!(res, Err(MappedError::Ros(Error::Disconnected))));
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_latching() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_latching")
+            .await
+            .unwrap();
+
+        // Create a publisher that is latching
+        let publisher = nh
+            .advertise::<std_msgs::String>("/test_latching", 1, true)
+            .await
+            .unwrap();
+
+        // Publish message to no one
+        publisher
+            .publish(&std_msgs::String {
+                data: "test".to_owned(),
+            })
+            .await
+            .unwrap();
+
+        // Create a subscriber that will connect to the publisher
+        let mut subscriber = nh
+            .subscribe::<std_msgs::String>("/test_latching", 1)
+            .await
+            .unwrap();
+
+        // Try to get message from subscriber
+        let msg = subscriber.next().await.unwrap().unwrap();
+
+        // Confirm we got the message we published
+        assert_eq!(msg.data, "test");
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_not_latching() {
+        // Opposite of test_latching, confirms no message appears when latching is false
+        let nh = NodeHandle::new("http://localhost:11311", "test_not_latching")
+            .await
+            .unwrap();
+
+        let publisher = nh
+            .advertise::<std_msgs::String>("/test_not_latching", 1, false)
+            .await
+            .unwrap();
+
+        publisher
+            .publish(&std_msgs::String {
+                data: "test".to_owned(),
+            })
+            .await
+            .unwrap();
+
+        let mut subscriber = nh
+            .subscribe::<std_msgs::String>("/test_not_latching", 1)
             .await
             .unwrap();
 
-        // Try to get message from subscriber
-        let msg = subscriber.next().await.unwrap().unwrap();
-
-        // Confirm we got the message we published
-        assert_eq!(msg.data, "test");
+        let res =
+            tokio::time::timeout(tokio::time::Duration::from_millis(250), subscriber.next()).await;
+        // Should timeout
+        assert!(res.is_err());
     }
 
     #[test_log::test(tokio::test)]
-    async fn test_not_latching() {
-        // Opposite of test_latching, confirms no message appears when latching is false
-        let nh = NodeHandle::new("http://localhost:11311", "test_not_latching")
+    async fn test_set_and_clear_latched_message() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_set_latched_message")
             .await
             .unwrap();
 
         let publisher = nh
-            .advertise::<std_msgs::String>("/test_not_latching", 1, false)
+            .advertise::<std_msgs::String>("/test_set_latched_message", 1, true)
             .await
             .unwrap();
 
         publisher
             .publish(&std_msgs::String {
-                data: "test".to_owned(),
+                data: "published".to_owned(),
             })
             .await
             .unwrap();
 
+        // Replace the latched message without publishing it
+        publisher
+            .set_latched_message(&std_msgs::String {
+                data: "replaced".to_owned(),
+            })
+            .unwrap();
+
         let mut subscriber = nh
-            .subscribe::<std_msgs::String>("/test_not_latching", 1)
+            .subscribe::<std_msgs::String>("/test_set_latched_message", 1)
             .await
             .unwrap();
+        let msg = subscriber.next().await.unwrap().unwrap();
+        assert_eq!(msg.data, "replaced");
 
+        // Once cleared new subscribers should receive nothing
+        publisher.clear_latched();
+        let mut subscriber = nh
+            .subscribe::<std_msgs::String>("/test_set_latched_message", 1)
+            .await
+            .unwrap();
         let res =
             tokio::time::timeout(tokio::time::Duration::from_millis(250), subscriber.next()).await;
-        // Should timeout
         assert!(res.is_err());
     }
 
@@ -177,6 +1443,90 @@ mod tests {
         }
     }
 
+    #[test_log::test(tokio::test)]
+    async fn test_offloaded_deserialization_keeps_order() {
+        let nh = NodeHandle::new("http://localhost:11311", "/test_offloaded_deserialization")
+            .await
+            .unwrap();
+
+        let publisher = nh
+            .advertise::<test_msgs::RoundTripArrayRequest>("/offloaded_payload_topic", 10, false)
+            .await
+            .unwrap();
+
+        // Only the large messages are deserialized on the blocking pool
+        let mut subscriber = nh
+            .subscribe::<test_msgs::RoundTripArrayRequest>("/offloaded_payload_topic", 10)
+            .await
+            .unwrap()
+            .offload_deserialization(1_000);
+
+        // Give some time for subscriber to connect to publisher
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+        let sizes = [10_000, 10, 10_000, 10, 10];
+        for (i, size) in sizes.iter().enumerate() {
+            publisher
+                .publish(&test_msgs::RoundTripArrayRequest {
+                    bytes: vec![i as u8; *size],
+                })
+                .await
+                .unwrap();
+        }
+
+        for (i, size) in sizes.iter().enumerate() {
+            let msg = timeout(tokio::time::Duration::from_secs(1), subscriber.next())
+                .await
+                .unwrap()
+                .unwrap()
+                .unwrap();
+            assert_eq!(msg.bytes, vec![i as u8; *size]);
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_offloaded_deserialization_is_cancel_safe() {
+        let nh = NodeHandle::new(
+            "http://localhost:11311",
+            "/test_offloaded_deserialization_is_cancel_safe",
+        )
+        .await
+        .unwrap();
+
+        let publisher = nh
+            .advertise::<test_msgs::RoundTripArrayRequest>("/offloaded_cancel_topic", 10, false)
+            .await
+            .unwrap();
+        let mut subscriber = nh
+            .subscribe::<test_msgs::RoundTripArrayRequest>("/offloaded_cancel_topic", 10)
+            .await
+            .unwrap()
+            .offload_deserialization(0);
+
+        // Give some time for subscriber to connect to publisher
+        tokio::time::sleep(tokio::time::Duration::from_millis(100)).await;
+
+        for i in 0..5 {
+            publisher
+                .publish(&test_msgs::RoundTripArrayRequest {
+                    bytes: vec![i; 100_000],
+                })
+                .await
+                .unwrap();
+        }
+
+        // Each call is cancelled after being polled once, usually while its message is still being deserialized
+        let mut received = vec![];
+        let start = tokio::time::Instant::now();
+        while received.len() < 5 && start.elapsed() < tokio::time::Duration::from_secs(5) {
+            if let Ok(msg) = timeout(tokio::time::Duration::ZERO, subscriber.next()).await {
+                received.push(msg.unwrap().unwrap().bytes[0]);
+            }
+            tokio::task::yield_now().await;
+        }
+        assert_eq!(received, vec![0, 1, 2, 3, 4]);
+    }
+
     #[test_log::test(tokio::test)]
     async fn test_large_service_payload_client() {
         let nh = NodeHandle::new(
@@ -273,6 +1623,104 @@ mod tests {
         }
     }
 
+    #[test_log::test(tokio::test)]
+    async fn cancelled_service_call_leaves_client_usable() {
+        let nh = NodeHandle::new(
+            "http://localhost:11311",
+            "/cancelled_service_call_leaves_client_usable",
+        )
+        .await
+        .unwrap();
+
+        let server_fn = |request: test_msgs::AddTwoIntsRequest| {
+            if request.a == 0 {
+                std::thread::sleep(std::time::Duration::from_millis(500));
+            }
+            Ok(test_msgs::AddTwoIntsResponse {
+                sum: request.a + request.b,
+            })
+        };
+        let _handle = nh
+            .advertise_service::<test_msgs::AddTwoInts, _>(
+                "/cancelled_service_call_leaves_client_usable/add_two",
+                server_fn,
+            )
+            .await
+            .unwrap();
+        let client = nh
+            .service_client::<test_msgs::AddTwoInts>(
+                "/cancelled_service_call_leaves_client_usable/add_two",
+            )
+            .await
+            .unwrap();
+
+        let cancel = roslibrust::CancellationToken::new();
+        let canceller = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(tokio::time::Duration::from_millis(50)).await;
+            canceller.cancel();
+        });
+        let res = client
+            .call_with_cancel(&test_msgs::AddTwoIntsRequest { a: 0, b: 1 }, &cancel)
+            .await;
+        assert!(matches!(res, Err(roslibrust::Error::Cancelled)));
+
+        // The next call gets its own response rather than the late one for the cancelled call
+        let call = client
+            .call(&test_msgs::AddTwoIntsRequest { a: 2, b: 2 })
+            .await
+            .unwrap();
+        assert_eq!(call.sum, 4);
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_service_introspection() {
+        use roslibrust::RosServiceType;
+
+        let nh = NodeHandle::new("http://localhost:11311", "/test_service_introspection")
+            .await
+            .unwrap();
+
+        let server_fn = |request: test_msgs::AddTwoIntsRequest| {
+            Ok(test_msgs::AddTwoIntsResponse {
+                sum: request.a + request.b,
+            })
+        };
+        let _handle = nh
+            .advertise_service::<test_msgs::AddTwoInts, _>(
+                "/test_service_introspection/add_two",
+                server_fn,
+            )
+            .await
+            .unwrap();
+
+        let info = nh
+            .service_info("/test_service_introspection/add_two")
+            .await
+            .unwrap()
+            .expect("Advertised service should be listed");
+        assert_eq!(info.service_type, "test_msgs/AddTwoInts");
+        assert_eq!(info.md5sum, test_msgs::AddTwoInts::MD5SUM);
+        assert!(info.uri.starts_with("rosrpc://"));
+        assert_eq!(info.request_count, 0);
+
+        let client = nh
+            .service_client::<test_msgs::AddTwoInts>("/test_service_introspection/add_two")
+            .await
+            .unwrap();
+        for i in 0..3 {
+            client
+                .call(&test_msgs::AddTwoIntsRequest { a: 1, b: i })
+                .await
+                .unwrap();
+        }
+
+        let services = nh.services().await.unwrap();
+        assert_eq!(services.len(), 1);
+        assert_eq!(services[0].request_count, 3);
+        assert!(nh.service_info("/not_a_service").await.unwrap().is_none());
+    }
+
     #[test_log::test(tokio::test)]
     async fn basic_service_server() {
         const TIMEOUT: std::time::Duration = std::time::Duration::from_secs(1);
@@ -319,6 +1767,53 @@ mod tests {
         debug!("Got 3");
     }
 
+    #[test_log::test(tokio::test)]
+    async fn service_server_handle_reports_stats_and_stops() {
+        let nh = NodeHandle::new("http://localhost:11311", "/service_server_stats")
+            .await
+            .unwrap();
+
+        let server_fn = |request: test_msgs::AddTwoIntsRequest| {
+            if request.a < 0 {
+                return Err(
+                    std::io::Error::new(std::io::ErrorKind::InvalidInput, "negative").into(),
+                );
+            }
+            Ok(test_msgs::AddTwoIntsResponse {
+                sum: request.a + request.b,
+            })
+        };
+        let handle = nh
+            .advertise_service::<test_msgs::AddTwoInts, _>(
+                "/service_server_stats/add_two",
+                server_fn,
+            )
+            .await
+            .unwrap();
+        assert_eq!(handle.request_count(), 0);
+        assert_eq!(handle.average_latency(), None);
+
+        let client = nh
+            .service_client::<test_msgs::AddTwoInts>("/service_server_stats/add_two")
+            .await
+            .unwrap();
+        client
+            .call(&test_msgs::AddTwoIntsRequest { a: 1, b: 2 })
+            .await
+            .unwrap();
+        client
+            .call(&test_msgs::AddTwoIntsRequest { a: -1, b: 2 })
+            .await
+            .unwrap_err();
+
+        assert_eq!(handle.request_count(), 2);
+        assert_eq!(handle.error_count(), 1);
+        assert!(handle.average_latency().is_some());
+
+        handle.stop().await.unwrap();
+        assert!(nh.services().await.unwrap().is_empty());
+    }
+
     #[test_log::test(tokio::test)]
     async fn dropping_service_server_kill_correctly() {
         debug!("Getting node handle");
@@ -467,6 +1962,51 @@ mod tests {
         )));
     }
 
+    #[test_log::test(tokio::test)]
+    async fn test_explicit_unadvertise_and_unsubscribe() {
+        let nh = NodeHandle::new("http://localhost:11311", "/test_explicit_teardown")
+            .await
+            .unwrap();
+        let publisher = nh
+            .advertise::<std_msgs::Header>("/test_explicit_teardown_pub", 1, false)
+            .await
+            .unwrap();
+        let subscriber = nh
+            .subscribe::<std_msgs::Header>("/test_explicit_teardown_sub", 1)
+            .await
+            .unwrap();
+        let second_subscriber = nh
+            .subscribe::<std_msgs::Header>("/test_explicit_teardown_sub", 1)
+            .await
+            .unwrap();
+
+        let master_client = roslibrust_ros1::MasterClient::new(
+            "http://localhost:11311",
+            "NAN",
+            "/test_explicit_teardown_checker",
+        )
+        .await
+        .unwrap();
+
+        let data = master_client.get_system_state().await.unwrap();
+        assert!(data.is_publishing("/test_explicit_teardown_pub", "/test_explicit_teardown"));
+        assert!(data.is_subscribed("/test_explicit_teardown_sub", "/test_explicit_teardown"));
+
+        // No sleeps needed, these complete once the master has been updated
+        publisher.unadvertise().await.unwrap();
+        let data = master_client.get_system_state().await.unwrap();
+        assert!(!data.is_publishing("/test_explicit_teardown_pub", "/test_explicit_teardown"));
+
+        // The topic stays subscribed while another subscriber remains
+        subscriber.unsubscribe().await.unwrap();
+        let data = master_client.get_system_state().await.unwrap();
+        assert!(data.is_subscribed("/test_explicit_teardown_sub", "/test_explicit_teardown"));
+
+        second_subscriber.unsubscribe().await.unwrap();
+        let data = master_client.get_system_state().await.unwrap();
+        assert!(!data.is_subscribed("/test_explicit_teardown_sub", "/test_explicit_teardown"));
+    }
+
     #[test_log::test(tokio::test)]
     #[ntest::timeout(6000)]
     #[cfg(feature = "ros1_test")]
@@ -593,4 +2133,139 @@ mod tests {
         assert!(!data.is_subscribed("/test_cleanup_sub", "/test_node_cleanup"));
         assert!(!data.is_service_provider("/test_cleanup_srv", "/test_node_cleanup"));
     }
+
+    static APP_SHUTDOWN_HOOKS: std::sync::atomic::AtomicUsize =
+        std::sync::atomic::AtomicUsize::new(0);
+
+    struct CountingApp {
+        publisher: roslibrust::ros1::Publisher<std_msgs::Int32>,
+        value: roslibrust::ros1::DeclaredParam<i32>,
+        steps: usize,
+    }
+
+    impl roslibrust::ros1::NodeApp for CountingApp {
+        const NAME: &'static str = "test_node_app";
+
+        async fn init(ctx: &mut roslibrust::ros1::AppContext) -> Result<Self, NodeError> {
+            ctx.on_shutdown(async {
+                APP_SHUTDOWN_HOOKS.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            });
+            Ok(CountingApp {
+                publisher: ctx.advertise("~out", 1).await?,
+                value: ctx.param("~value", 0).await?,
+                steps: 0,
+            })
+        }
+
+        fn rate(&self) -> f64 {
+            20.0
+        }
+
+        async fn step(&mut self) -> Result<std::ops::ControlFlow<()>, NodeError> {
+            self.steps += 1;
+            self.publisher
+                .publish(&std_msgs::Int32 {
+                    data: self.value.get(),
+                })
+                .await
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
+            Ok(match self.steps {
+                40 => std::ops::ControlFlow::Break(()),
+                _ => std::ops::ControlFlow::Continue(()),
+            })
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_node_app() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_node_app_listener")
+            .await
+            .unwrap();
+        let mut subscriber = nh
+            .subscribe::<std_msgs::Int32>("/test_node_app/remapped", 1)
+            .await
+            .unwrap();
+
+        let args = roslibrust::ros1::NodeArgs::parse([
+            "__ns:=/test_node_app",
+            "_value:=7",
+            "--ros-args",
+            "-r",
+            "~out:=remapped",
+        ])
+        .unwrap();
+        let app = tokio::spawn(roslibrust::ros1::app::run_with_args::<CountingApp>(args));
+
+        // The private parameter and remapping given as arguments are applied before init
+        let msg = timeout(tokio::time::Duration::from_secs(2), subscriber.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.data, 7);
+
+        // The app stops itself after 40 steps, running its shutdown hooks
+        timeout(tokio::time::Duration::from_secs(5), app)
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(
+            APP_SHUTDOWN_HOOKS.load(std::sync::atomic::Ordering::SeqCst),
+            1
+        );
+    }
+
+    struct RelativeApp {
+        publisher: roslibrust::ros1::Publisher<std_msgs::Int32>,
+    }
+
+    impl roslibrust::ros1::NodeApp for RelativeApp {
+        const NAME: &'static str = "test_node_app_relative";
+
+        async fn init(ctx: &mut roslibrust::ros1::AppContext) -> Result<Self, NodeError> {
+            let publisher = ctx.advertise("out", 1).await?;
+            // The manifest names the topic the node actually advertised
+            assert_eq!(
+                ctx.manifest().entries,
+                [roslibrust::ros1::app::ManifestEntry::Publisher {
+                    topic: "/test_node_app_relative_ns/out".to_string(),
+                    msg_type: "std_msgs/Int32".to_string(),
+                }]
+            );
+            Ok(RelativeApp { publisher })
+        }
+
+        async fn step(&mut self) -> Result<std::ops::ControlFlow<()>, NodeError> {
+            self.publisher
+                .publish(&std_msgs::Int32 { data: 1 })
+                .await
+                .map_err(|e| std::io::Error::other(e.to_string()))?;
+            Ok(std::ops::ControlFlow::Continue(()))
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn test_node_app_relative_namespace() {
+        let nh = NodeHandle::new("http://localhost:11311", "test_node_app_relative_listener")
+            .await
+            .unwrap();
+        let mut subscriber = nh
+            .subscribe::<std_msgs::Int32>("/test_node_app_relative_ns/out", 1)
+            .await
+            .unwrap();
+
+        // A relative namespace is placed at the root, not applied a second time to relative names
+        let args = roslibrust::ros1::NodeArgs::parse(["__ns:=test_node_app_relative_ns"]).unwrap();
+        let app = tokio::spawn(roslibrust::ros1::app::run_with_args::<RelativeApp>(args));
+
+        let msg = timeout(tokio::time::Duration::from_secs(2), subscriber.next())
+            .await
+            .unwrap()
+            .unwrap()
+            .unwrap();
+        assert_eq!(msg.data, 1);
+        assert!(!app.is_finished());
+        app.abort();
+    }
 }
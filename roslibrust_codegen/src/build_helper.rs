@@ -0,0 +1,119 @@
+//! Helpers for invoking codegen from a `build.rs` file.
+//!
+//! ```ignore
+//! // build.rs
+//! fn main() -> Result<(), Box<dyn std::error::Error>> {
+//!     roslibrust::codegen::build_helper::generate_to_out_dir(vec!["../my_msgs".into()])?;
+//!     Ok(())
+//! }
+//!
+//! // lib.rs or main.rs
+//! include!(concat!(env!("OUT_DIR"), "/messages.rs"));
+//! ```
+
+use crate::{CodegenOptions, Error};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+/// Name of the file written to OUT_DIR by the functions in this module
+pub const OUT_FILE_NAME: &str = "messages.rs";
+
+/// Generates messages from the given search paths and `ROS_PACKAGE_PATH` into `$OUT_DIR/messages.rs`.
+///
+/// Prints the `cargo:rerun-if-changed` directives needed for cargo to re-run the build script when a message
+/// file changes, or one is added to a search path, and returns the path of the written file.
+///
+/// Must be called from a build script, as it relies on the `OUT_DIR` environment variable set by cargo.
+pub fn generate_to_out_dir(search_paths: Vec<PathBuf>) -> Result<PathBuf, Error> {
+    println!("cargo:rerun-if-env-changed=ROS_PACKAGE_PATH");
+    let mut all_paths = crate::utils::get_search_paths();
+    all_paths.extend(search_paths);
+    generate_to_out_dir_without_ros_package_path(all_paths)
+}
+
+/// Similar to [generate_to_out_dir], but does not search the `ROS_PACKAGE_PATH` environment variable paths.
+pub fn generate_to_out_dir_without_ros_package_path(
+    search_paths: Vec<PathBuf>,
+) -> Result<PathBuf, Error> {
+    generate_to_out_dir_with_options(search_paths, &CodegenOptions::default())
+}
+
+/// Similar to [generate_to_out_dir_without_ros_package_path], but generates code according to `options`,
+/// e.g. to only generate the types listed in [CodegenOptions::root_types].
+pub fn generate_to_out_dir_with_options(
+    search_paths: Vec<PathBuf>,
+    options: &CodegenOptions,
+) -> Result<PathBuf, Error> {
+    let out_dir = std::env::var_os("OUT_DIR").ok_or(Error::new(
+        "OUT_DIR is not set, generate_to_out_dir must be called from a build script",
+    ))?;
+    // Dropped duplicate packages are otherwise only logged, which isn't visible from a build script
+    for dropped in &crate::find_ros_packages(&search_paths, options)?.dropped {
+        println!("cargo:warning={dropped}");
+    }
+
+    let (source, dependent_paths) =
+        crate::find_and_generate_ros_messages_with_options(search_paths.clone(), options)?;
+
+    let dest_path = Path::new(&out_dir).join(OUT_FILE_NAME);
+    let source = source.to_string();
+    let source = format_rust_source(&source).unwrap_or(source);
+    std::fs::write(&dest_path, source).map_err(|e| {
+        Error::with(
+            format!("Failed to write generated code to {dest_path:?}").as_str(),
+            e,
+        )
+    })?;
+
+    // Search paths are included so that adding a new message file also triggers regeneration
+    // Paths that don't exist are skipped, as cargo would otherwise re-run the build script on every build
+    for path in search_paths.iter().chain(&dependent_paths) {
+        if !path.exists() {
+            continue;
+        }
+        println!("cargo:rerun-if-changed={}", path.display());
+    }
+    Ok(dest_path)
+}
+
+// Formatting is best effort, making the generated code readable when following "go to definition",
+// if rustfmt isn't available the unformatted code is used.
+fn format_rust_source(source: &str) -> Option<String> {
+    let mut process = Command::new("rustfmt")
+        .arg("--emit=stdout")
+        .arg("--edition=2021")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()
+        .ok()?;
+    process.stdin.take()?.write_all(source.as_bytes()).ok()?;
+    let output = process.wait_with_output().ok()?;
+    if !output.status.success() {
+        return None;
+    }
+    String::from_utf8(output.stdout).ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test_log::test]
+    fn writes_formatted_code_to_out_dir() {
+        let out_dir = std::env::temp_dir().join("roslibrust_build_helper_test");
+        std::fs::create_dir_all(&out_dir).unwrap();
+        // No other tests read OUT_DIR
+        std::env::set_var("OUT_DIR", &out_dir);
+
+        let std_msgs = concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../assets/ros1_common_interfaces/std_msgs"
+        );
+        let path = generate_to_out_dir_without_ros_package_path(vec![std_msgs.into()]).unwrap();
+        assert_eq!(path, out_dir.join(OUT_FILE_NAME));
+        let source = std::fs::read_to_string(path).unwrap();
+        assert!(source.contains("pub mod std_msgs"));
+    }
+}
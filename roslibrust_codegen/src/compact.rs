@@ -0,0 +1,85 @@
+//! Support for the code generated with [crate::CodegenOptions::compact].
+//!
+//! Compact generated code implements the message and service traits through the macros in this module
+//! rather than spelling out each impl, which keeps the generated code small for large message sets.
+
+/// Implements `RosMessageType` for a generated message struct.
+///
+/// Variable length messages pass the body of `encoded_len` as a closure-like `|this| expr`, with `this` standing
+/// in for `self`.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_ros_message {
+    ($name:ident, $ros_type_name:literal, $md5sum:literal, $definition:literal, $ros2_hash:literal, $ros2_type_name:literal, $serialized_size:expr $(, |$this:ident| $encoded_len:expr)?) => {
+        impl ::roslibrust::RosMessageType for $name {
+            const ROS_TYPE_NAME: &'static str = $ros_type_name;
+            const MD5SUM: &'static str = $md5sum;
+            const DEFINITION: &'static str = $definition;
+            const ROS2_HASH: &'static [u8; 32] = &$crate::compact::ros2_hash_from_hex($ros2_hash);
+            const ROS2_TYPE_NAME: &'static str = $ros2_type_name;
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = $serialized_size;
+            $(
+                fn encoded_len(&self) -> ::std::option::Option<usize> {
+                    let $this = self;
+                    $encoded_len
+                }
+            )?
+        }
+    };
+}
+
+/// Implements `RosServiceType` for a generated service struct.
+#[doc(hidden)]
+#[macro_export]
+macro_rules! impl_ros_service {
+    ($name:ident, $ros_service_name:literal, $md5sum:literal, $ros2_hash:literal, $ros2_type_name:literal, $request:ident, $response:ident) => {
+        impl ::roslibrust::RosServiceType for $name {
+            const ROS_SERVICE_NAME: &'static str = $ros_service_name;
+            const MD5SUM: &'static str = $md5sum;
+            const ROS2_HASH: &'static [u8; 32] = &$crate::compact::ros2_hash_from_hex($ros2_hash);
+            const ROS2_TYPE_NAME: &'static str = $ros2_type_name;
+            type Request = $request;
+            type Response = $response;
+        }
+    };
+}
+
+/// Decodes a ROS2 type hash from the 64 hex characters it is written as in compact generated code.
+///
+/// Evaluated at compile time, an invalid hash fails compilation.
+pub const fn ros2_hash_from_hex(hex: &str) -> [u8; 32] {
+    let hex = hex.as_bytes();
+    assert!(hex.len() == 64, "ROS2 hash must be 64 hex characters");
+    let mut hash = [0u8; 32];
+    let mut i = 0;
+    while i < hash.len() {
+        hash[i] = (hex_value(hex[2 * i]) << 4) | hex_value(hex[2 * i + 1]);
+        i += 1;
+    }
+    hash
+}
+
+const fn hex_value(c: u8) -> u8 {
+    match c {
+        b'0'..=b'9' => c - b'0',
+        b'a'..=b'f' => c - b'a' + 10,
+        b'A'..=b'F' => c - b'A' + 10,
+        _ => panic!("ROS2 hash contains a character which isn't hex"),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn decodes_ros2_hash() {
+        let hash = crate::Ros2Hash::from_string(
+            "RIHS01_a930ae8d8d848404ac069e26ecf6e6528275a6a9c0e874431ef4c1c30a048597",
+        );
+        assert_eq!(ros2_hash_from_hex(&hash.to_hex_string()), hash.0);
+        const HASH: [u8; 32] =
+            ros2_hash_from_hex("A930AE8D8D848404AC069E26ECF6E6528275A6A9C0E874431EF4C1C30A048597");
+        assert_eq!(HASH, hash.0);
+    }
+}
@@ -0,0 +1,350 @@
+//! Conversions between the ROS1 and ROS2 variants of the same messages, for bridges between the two.
+//!
+//! The two variants of a package can't be generated into the same module, so each dialect is generated into its
+//! own module and the conversions are generated separately, referring to the types through those modules.
+//!
+//! ```no_run
+//! use roslibrust_codegen::{dialect, generate_rust_ros_message_definitions, CodegenOptions};
+//!
+//! let generate = |paths: &[std::path::PathBuf]| {
+//!     let (messages, services, _actions) = roslibrust_codegen::find_and_parse_ros_messages(paths)?;
+//!     roslibrust_codegen::resolve_dependency_graph(messages, services)
+//! };
+//! let (ros1, ros1_services) = generate(&["assets/ros1_common_interfaces/std_msgs".into()]).unwrap();
+//! let (ros2, ros2_services) = generate(&["assets/ros2_common_interfaces/std_msgs".into()]).unwrap();
+//! let conversions = dialect::generate_conversions(&ros1, &ros2, "crate::ros1", "crate::ros2").unwrap();
+//!
+//! let options = CodegenOptions::default();
+//! let ros1 = generate_rust_ros_message_definitions(ros1, ros1_services, &options).unwrap();
+//! let ros2 = generate_rust_ros_message_definitions(ros2, ros2_services, &options).unwrap();
+//! let source = quote::quote! {
+//!     pub mod ros1 { #ros1 }
+//!     pub mod ros2 { #ros2 }
+//!     #conversions
+//! };
+//! ```
+
+use std::collections::BTreeMap;
+
+use proc_macro2::TokenStream;
+use quote::{format_ident, quote};
+
+use crate::parse::convert_ros_type_to_rust_type;
+use crate::utils::RosVersion;
+use crate::{ArrayType, Error, FieldInfo, MessageFile};
+
+/// Generates `From` impls in both directions between each message in `ros1_messages` and the message with the same
+/// name in `ros2_messages`, e.g. between `ros1::geometry_msgs::Point` and `ros2::geometry_msgs::Point`.
+///
+/// `ros1_module` and `ros2_module` are the paths of the modules the two dialects are generated into, as seen from
+/// where the conversions are included, e.g. "crate::ros1".
+///
+/// Fields are matched by name. A field which only exists in the target dialect, or whose type differs between the
+/// dialects beyond the representation of time and duration, is left at its default value. Fields which only exist
+/// in the source dialect (e.g. the `seq` of a ROS1 `std_msgs/Header`) are dropped. Nested messages are converted
+/// through their own generated impls, so every message a paired message depends on should also be in both sets.
+pub fn generate_conversions(
+    ros1_messages: &[MessageFile],
+    ros2_messages: &[MessageFile],
+    ros1_module: &str,
+    ros2_module: &str,
+) -> Result<TokenStream, Error> {
+    let ros1_module = parse_module(ros1_module)?;
+    let ros2_module = parse_module(ros2_module)?;
+    let ros2_messages = ros2_messages
+        .iter()
+        .map(|msg| (msg.get_full_name(), msg))
+        .collect::<BTreeMap<_, _>>();
+    let pairs = ros1_messages
+        .iter()
+        .filter_map(|ros1| {
+            ros2_messages
+                .get(&ros1.get_full_name())
+                .map(|ros2| (ros1, *ros2))
+        })
+        .collect::<Vec<_>>();
+    let paired = pairs
+        .iter()
+        .map(|(ros1, _)| ros1.get_full_name())
+        .collect::<Vec<_>>();
+
+    let conversion = Conversion {
+        paired: &paired,
+        ros2_module: &ros2_module,
+    };
+    let mut impls = TokenStream::new();
+    for (ros1, ros2) in pairs {
+        let pkg = format_ident!("{}", ros1.parsed.package);
+        let name = format_ident!("{}", ros1.parsed.name);
+        let ros1_type = quote! { #ros1_module::#pkg::#name };
+        let ros2_type = quote! { #ros2_module::#pkg::#name };
+        let to_ros2 =
+            conversion.generate_from(ros1, ros2, RosVersion::ROS2, &ros1_type, &ros2_type);
+        let to_ros1 =
+            conversion.generate_from(ros2, ros1, RosVersion::ROS1, &ros2_type, &ros1_type);
+        impls.extend(to_ros2);
+        impls.extend(to_ros1);
+    }
+    Ok(impls)
+}
+
+fn parse_module(module: &str) -> Result<TokenStream, Error> {
+    let path = syn::parse_str::<syn::Path>(module)
+        .map_err(|e| Error::new(format!("Invalid module path {module:?}: {e}")))?;
+    Ok(quote! { #path })
+}
+
+/// What a single element of a field holds, independent of how each dialect represents it
+#[derive(Debug, PartialEq)]
+enum ElementKind {
+    /// A primitive with the given Rust type
+    Primitive(&'static str),
+    Time,
+    Duration,
+    /// A message with the given full name
+    Message(String),
+}
+
+fn element_kind(field: &FieldInfo, version: RosVersion) -> Option<ElementKind> {
+    let field_type = field.field_type.field_type.as_str();
+    match (version, field.field_type.package_name.as_deref()) {
+        (RosVersion::ROS1, None) if field_type == "time" => Some(ElementKind::Time),
+        (RosVersion::ROS1, None) if field_type == "duration" => Some(ElementKind::Duration),
+        (RosVersion::ROS2, Some("builtin_interfaces")) if field_type == "Time" => {
+            Some(ElementKind::Time)
+        }
+        (RosVersion::ROS2, Some("builtin_interfaces")) if field_type == "Duration" => {
+            Some(ElementKind::Duration)
+        }
+        (_, None) => convert_ros_type_to_rust_type(version, field_type).map(ElementKind::Primitive),
+        (_, Some(_)) => Some(ElementKind::Message(field.get_full_type_name())),
+    }
+}
+
+struct Conversion<'a> {
+    /// Full names of the messages conversions are generated for
+    paired: &'a [String],
+    ros2_module: &'a TokenStream,
+}
+
+impl Conversion<'_> {
+    /// Generates `impl From<from_type> for to_type`, where `to_version` is the dialect of `to`
+    fn generate_from(
+        &self,
+        from: &MessageFile,
+        to: &MessageFile,
+        to_version: RosVersion,
+        from_type: &TokenStream,
+        to_type: &TokenStream,
+    ) -> TokenStream {
+        let from_version = match to_version {
+            RosVersion::ROS1 => RosVersion::ROS2,
+            RosVersion::ROS2 => RosVersion::ROS1,
+        };
+        let mut defaulted = false;
+        let fields = to
+            .get_fields()
+            .iter()
+            .filter_map(|to_field| {
+                let value = from
+                    .get_fields()
+                    .iter()
+                    .find(|from_field| from_field.field_name == to_field.field_name)
+                    .and_then(|from_field| {
+                        self.convert_field(from_field, from_version, to_field, to_version)
+                    });
+                if value.is_none() {
+                    defaulted = true;
+                }
+                let name = format_ident!("r#{}", to_field.field_name);
+                value.map(|value| quote! { #name: #value, })
+            })
+            .collect::<Vec<_>>();
+        let rest = defaulted.then(|| quote! { ..::std::default::Default::default() });
+        let msg = if fields.is_empty() {
+            quote! { _msg }
+        } else {
+            quote! { msg }
+        };
+        quote! {
+            impl ::std::convert::From<#from_type> for #to_type {
+                fn from(#msg: #from_type) -> Self {
+                    Self {
+                        #(#fields)*
+                        #rest
+                    }
+                }
+            }
+        }
+    }
+
+    /// Returns an expression converting `from_field` of `msg` to `to_field`, or None if they aren't compatible
+    fn convert_field(
+        &self,
+        from_field: &FieldInfo,
+        from_version: RosVersion,
+        to_field: &FieldInfo,
+        to_version: RosVersion,
+    ) -> Option<TokenStream> {
+        let from_kind = element_kind(from_field, from_version)?;
+        let to_kind = element_kind(to_field, to_version)?;
+        let name = format_ident!("r#{}", from_field.field_name);
+        let value = quote! { msg.#name };
+        // Primitives are the same in both dialects, so can be moved across whether or not they're in an array
+        if let (ElementKind::Primitive(from), ElementKind::Primitive(to)) = (&from_kind, &to_kind) {
+            return match (
+                &from_field.field_type.array_info,
+                &to_field.field_type.array_info,
+            ) {
+                _ if from != to => None,
+                (ArrayType::NotArray, ArrayType::NotArray)
+                | (
+                    ArrayType::Unbounded | ArrayType::Bounded(_),
+                    ArrayType::Unbounded | ArrayType::Bounded(_),
+                ) => Some(value),
+                (ArrayType::FixedLength(from), ArrayType::FixedLength(to)) if from == to => {
+                    Some(value)
+                }
+                _ => None,
+            };
+        }
+        let element = quote! { e };
+        let converted = self.convert_element(&from_kind, &to_kind, to_version, &element)?;
+        // Applied to each element of an array
+        let mapper = match to_kind {
+            ElementKind::Message(_) => quote! { ::std::convert::From::from },
+            _ => quote! { |#element| #converted },
+        };
+        match (
+            &from_field.field_type.array_info,
+            &to_field.field_type.array_info,
+        ) {
+            (ArrayType::NotArray, ArrayType::NotArray) => {
+                self.convert_element(&from_kind, &to_kind, to_version, &value)
+            }
+            (
+                ArrayType::Unbounded | ArrayType::Bounded(_),
+                ArrayType::Unbounded | ArrayType::Bounded(_),
+            ) => Some(quote! { #value.into_iter().map(#mapper).collect() }),
+            (ArrayType::FixedLength(from), ArrayType::FixedLength(to)) if from == to => {
+                Some(quote! { #value.map(#mapper) })
+            }
+            _ => None,
+        }
+    }
+
+    /// Returns an expression converting a single non-primitive `value` to `to_version`
+    fn convert_element(
+        &self,
+        from_kind: &ElementKind,
+        to_kind: &ElementKind,
+        to_version: RosVersion,
+        value: &TokenStream,
+    ) -> Option<TokenStream> {
+        let ros2_module = self.ros2_module;
+        match (from_kind, to_kind, to_version) {
+            // ROS1 nanoseconds may be negative or a second or more, ROS2's are normalized
+            (ElementKind::Time, ElementKind::Time, RosVersion::ROS2) => Some(quote! {
+                {
+                    let (sec, nanosec) = #value.to_sec_nanosec();
+                    #ros2_module::builtin_interfaces::Time { sec, nanosec }
+                }
+            }),
+            (ElementKind::Time, ElementKind::Time, RosVersion::ROS1) => Some(quote! {
+                ::roslibrust::codegen::integral_types::Time::from_sec_nanosec(#value.r#sec, #value.r#nanosec)
+            }),
+            (ElementKind::Duration, ElementKind::Duration, RosVersion::ROS2) => Some(quote! {
+                {
+                    let (sec, nanosec) = #value.to_sec_nanosec();
+                    #ros2_module::builtin_interfaces::Duration { sec, nanosec }
+                }
+            }),
+            (ElementKind::Duration, ElementKind::Duration, RosVersion::ROS1) => Some(quote! {
+                ::roslibrust::codegen::integral_types::Duration::from_sec_nanosec(#value.r#sec, #value.r#nanosec)
+            }),
+            (ElementKind::Message(from), ElementKind::Message(to), _)
+                if from == to && self.paired.contains(from) =>
+            {
+                Some(quote! { ::std::convert::From::from(#value) })
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn parse(version: RosVersion, messages: &[(&str, &str, &str)]) -> Vec<MessageFile> {
+        let parsed = messages
+            .iter()
+            .map(|(pkg, name, definition)| {
+                let pkg = crate::Package {
+                    name: pkg.to_string(),
+                    path: "./not_a_path".into(),
+                    version: Some(version),
+                };
+                let path = std::path::PathBuf::from(format!("./not_a_path/msg/{name}.msg"));
+                crate::parse_ros_message_file(definition, name, &pkg, &path).unwrap()
+            })
+            .collect();
+        crate::resolve_dependency_graph(parsed, vec![]).unwrap().0
+    }
+
+    #[test_log::test]
+    fn generates_conversions_between_dialects() {
+        let ros1 = parse(
+            RosVersion::ROS1,
+            &[
+                (
+                    "std_msgs",
+                    "Header",
+                    "uint32 seq\ntime stamp\nstring frame_id\n",
+                ),
+                (
+                    "geometry_msgs",
+                    "Point",
+                    "float64 x\nfloat64 y\nfloat64 z\n",
+                ),
+                (
+                    "geometry_msgs",
+                    "Path",
+                    "std_msgs/Header header\nPoint[] points\nint32 legacy\nduration timeout\n",
+                ),
+            ],
+        );
+        let ros2 = parse(
+            RosVersion::ROS2,
+            &[
+                ("std_msgs", "Header", "builtin_interfaces/Time stamp\nstring frame_id\n"),
+                ("geometry_msgs", "Point", "float64 x\nfloat64 y\nfloat64 z\n"),
+                (
+                    "geometry_msgs",
+                    "Path",
+                    "std_msgs/Header header\nPoint[] points\nbuiltin_interfaces/Duration timeout\nfloat64 tolerance 0.5\n",
+                ),
+            ],
+        );
+        let source = generate_conversions(&ros1, &ros2, "crate::ros1", "crate::ros2")
+            .unwrap()
+            .to_string();
+
+        // Conversions in both directions for every paired message
+        for name in [
+            "std_msgs :: Header",
+            "geometry_msgs :: Point",
+            "geometry_msgs :: Path",
+        ] {
+            assert!(source.contains(&format!("impl :: std :: convert :: From < crate :: ros1 :: {name} > for crate :: ros2 :: {name}")), "{source}");
+            assert!(source.contains(&format!("impl :: std :: convert :: From < crate :: ros2 :: {name} > for crate :: ros1 :: {name}")), "{source}");
+        }
+        // seq is dropped going to ROS2 and defaulted coming back, stamps change representation
+        assert!(source.contains("r#stamp : { let (sec , nanosec) = msg . r#stamp . to_sec_nanosec () ; crate :: ros2 :: builtin_interfaces :: Time { sec , nanosec } } , r#frame_id : msg . r#frame_id , }"), "{source}");
+        assert!(source.contains("r#stamp : :: roslibrust :: codegen :: integral_types :: Time :: from_sec_nanosec (msg . r#stamp . r#sec , msg . r#stamp . r#nanosec) , r#frame_id : msg . r#frame_id , .. :: std :: default :: Default :: default () }"), "{source}");
+        // Nested messages convert through their own impls, dialect specific fields are defaulted
+        assert!(source.contains("r#header : :: std :: convert :: From :: from (msg . r#header) , r#points : msg . r#points . into_iter () . map (:: std :: convert :: From :: from) . collect () , r#timeout : { let (sec , nanosec) = msg . r#timeout . to_sec_nanosec () ; crate :: ros2 :: builtin_interfaces :: Duration"), "{source}");
+        assert!(!source.contains("r#tolerance :"), "{source}");
+        assert!(!source.contains("r#legacy :"), "{source}");
+    }
+}
@@ -2,10 +2,11 @@ use proc_macro2::TokenStream;
 use quote::{format_ident, quote, ToTokens};
 use serde::de::DeserializeOwned;
 use std::str::FromStr;
+use std::sync::Arc;
 use syn::parse_quote;
 
 use crate::parse::convert_ros_type_to_rust_type;
-use crate::utils::RosVersion;
+use crate::utils::{DuplicatePackagePolicy, RosVersion};
 use crate::{bail, ArrayType, Error};
 use crate::{ConstantInfo, FieldInfo, MessageFile, RosLiteral, ServiceFile};
 
@@ -17,6 +18,95 @@ pub struct CodegenOptions {
     pub generate_definition: bool,
     /// Whether to use roslibrust's re-exported serde (default: true)
     pub roslibrust_serde: bool,
+    /// If set, only these message and service types (e.g. "std_msgs/Header" or "std_srvs/Trigger"),
+    /// and the messages they depend on, are generated instead of every type found (default: None)
+    /// Generating only the types actually used can significantly reduce compile times.
+    pub root_types: Option<Vec<String>>,
+    /// Whether to turn comments on fields and constants in message files into doc comments (default: true)
+    pub generate_docs: bool,
+    /// How to choose between packages with the same name found in multiple search paths
+    /// (default: [DuplicatePackagePolicy::PreferFirst])
+    pub duplicate_package_policy: DuplicatePackagePolicy,
+    /// Whether to emit compact code (default: false)
+    /// The trait impls of each type are generated through macros from this crate, ROS2 hashes are emitted as
+    /// strings and attributes are merged, which significantly reduces the size of the generated code for large
+    /// message sets. The resulting types are identical.
+    pub compact: bool,
+    /// Whether to emit a `visit_message_types` function alongside the package modules (default: false)
+    /// It calls a [roslibrust_common::MessageTypeVisitor] with each generated message type, allowing a registry
+    /// of the types to be built so they can be looked up by name at runtime.
+    pub generate_type_visitor: bool,
+    /// Plugins which add their own items to the generated code (default: none), see [CodegenPlugin]
+    pub plugins: Vec<Arc<dyn CodegenPlugin>>,
+}
+
+/// Extends code generation with extra items for each message or service, e.g. ORM mappings, UI form builders
+/// or shims to other serialization formats, without forking the generator.
+///
+/// Registered through [CodegenOptions::plugins], typically from a build script. Items returned for a message or
+/// service are emitted right after its generated type, inside the module for its package, so `Self`-style impls
+/// can refer to the type by its short name.
+/// ```
+/// use roslibrust_codegen::{CodegenPlugin, CodegenOptions, MessageFile};
+/// use quote::{format_ident, quote};
+///
+/// // Adds a const with the number of fields to every message
+/// #[derive(Debug)]
+/// struct FieldCount;
+///
+/// impl CodegenPlugin for FieldCount {
+///     fn generate_for_message(
+///         &self,
+///         msg: &MessageFile,
+///         _options: &CodegenOptions,
+///     ) -> Result<proc_macro2::TokenStream, roslibrust_codegen::SimpleError> {
+///         let name = format_ident!("{}", msg.get_short_name());
+///         let count = msg.get_fields().len();
+///         Ok(quote! {
+///             impl #name {
+///                 pub const FIELD_COUNT: usize = #count;
+///             }
+///         })
+///     }
+/// }
+///
+/// let options = CodegenOptions {
+///     plugins: vec![std::sync::Arc::new(FieldCount)],
+///     ..Default::default()
+/// };
+/// ```
+pub trait CodegenPlugin: std::fmt::Debug + Send + Sync {
+    /// Returns items to emit after the struct generated for `msg`
+    fn generate_for_message(
+        &self,
+        msg: &MessageFile,
+        options: &CodegenOptions,
+    ) -> Result<TokenStream, Error> {
+        let _ = (msg, options);
+        Ok(TokenStream::new())
+    }
+
+    /// Returns items to emit after the types generated for `srv`
+    fn generate_for_service(
+        &self,
+        srv: &ServiceFile,
+        options: &CodegenOptions,
+    ) -> Result<TokenStream, Error> {
+        let _ = (srv, options);
+        Ok(TokenStream::new())
+    }
+
+    /// Returns items to emit once, at the root of the generated code alongside the package modules, e.g. a
+    /// registry of every generated type
+    fn generate_root(
+        &self,
+        messages: &[MessageFile],
+        services: &[ServiceFile],
+        options: &CodegenOptions,
+    ) -> Result<TokenStream, Error> {
+        let _ = (messages, services, options);
+        Ok(TokenStream::new())
+    }
 }
 
 impl Default for CodegenOptions {
@@ -24,40 +114,46 @@ impl Default for CodegenOptions {
         Self {
             generate_definition: true,
             roslibrust_serde: true,
+            root_types: None,
+            generate_docs: true,
+            duplicate_package_policy: DuplicatePackagePolicy::default(),
+            compact: false,
+            generate_type_visitor: false,
+            plugins: vec![],
         }
     }
 }
 
 fn derive_attrs(options: &CodegenOptions, _has_large_array: bool) -> Vec<syn::Attribute> {
-    let mut attrs = vec![
-        parse_quote! { #[derive(Debug)] },
-        parse_quote! { #[derive(Clone)] },
-        parse_quote! { #[derive(PartialEq)] },
+    let mut derives: Vec<syn::Path> = vec![
+        parse_quote! { Debug },
+        parse_quote! { Clone },
+        parse_quote! { PartialEq },
     ];
+    let mut attrs = vec![];
 
     if options.roslibrust_serde {
         // Use roslibrust's re-exported serde with SmartDefault
-        attrs.insert(
-            0,
-            parse_quote! { #[derive(::roslibrust::codegen::Deserialize)] },
-        );
-        attrs.insert(
-            1,
-            parse_quote! { #[derive(::roslibrust::codegen::Serialize)] },
-        );
-        attrs.insert(
-            2,
-            parse_quote! { #[derive(::roslibrust::codegen::SmartDefault)] },
-        );
+        derives.insert(0, parse_quote! { ::roslibrust::codegen::Deserialize });
+        derives.insert(1, parse_quote! { ::roslibrust::codegen::Serialize });
+        derives.insert(2, parse_quote! { ::roslibrust::codegen::SmartDefault });
         attrs.push(parse_quote! { #[serde(crate = "::roslibrust::codegen::serde")] });
     } else {
         // Use standard Rust serde with SmartDefault (SmartDefault works with any serde)
-        attrs.insert(0, parse_quote! { #[derive(serde::Deserialize)] });
-        attrs.insert(1, parse_quote! { #[derive(serde::Serialize)] });
-        attrs.insert(2, parse_quote! { #[derive(smart_default::SmartDefault)] });
+        derives.insert(0, parse_quote! { serde::Deserialize });
+        derives.insert(1, parse_quote! { serde::Serialize });
+        derives.insert(2, parse_quote! { smart_default::SmartDefault });
     }
 
-    attrs
+    let derives: Vec<syn::Attribute> = if options.compact {
+        vec![parse_quote! { #[derive(#(#derives),*)] }]
+    } else {
+        derives
+            .into_iter()
+            .map(|derive| parse_quote! { #[derive(#derive)] })
+            .collect()
+    };
+    derives.into_iter().chain(attrs).collect()
 }
 
 /// Generates the service for a given service file
@@ -80,6 +176,20 @@ pub fn generate_service(
 
     let request_msg = generate_struct(service.request, Some(options))?;
     let response_msg = generate_struct(service.response, Some(options))?;
+    if options.compact {
+        let service_ros2_hash = service_ros2_hash.to_hex_string();
+        return Ok(quote! {
+            #request_msg
+            #response_msg
+
+            #[allow(dead_code)]
+            pub struct #struct_name {}
+            ::roslibrust::codegen::impl_ros_service!(
+                #struct_name, #service_type_name, #service_md5sum, #service_ros2_hash, #ros2_type_name,
+                #request_name, #response_name
+            );
+        });
+    }
     Ok(quote! {
 
         #request_msg
@@ -121,6 +231,23 @@ pub fn generate_struct(
     );
 
     let attrs = derive_attrs(options, has_large_array);
+    // Compact code passes the expression into a macro, which has to name the receiver itself
+    let receiver = if options.compact {
+        quote! { this }
+    } else {
+        quote! { self }
+    };
+    let serialized_size = match msg.serialized_size {
+        Some(size) => quote! { ::std::option::Option::Some(#size) },
+        None => quote! { ::std::option::Option::None },
+    };
+    // Fixed length messages rely on the default implementation which returns SERIALIZED_SIZE
+    let encoded_len = match msg.serialized_size {
+        Some(_) => None,
+        None => Some(generate_encoded_len(&msg.parsed.fields, &receiver)),
+    };
+    let header_compat =
+        generate_header_compat(&ros_type_name, &msg.parsed.fields, &msg.parsed.name);
     let fields = msg
         .parsed
         .fields
@@ -143,6 +270,7 @@ pub fn generate_struct(
             generate_constant_field_definition(
                 constant,
                 msg.parsed.version.unwrap_or(RosVersion::ROS1),
+                options,
             )
         })
         .collect::<Result<Vec<TokenStream>, _>>()?;
@@ -153,34 +281,57 @@ pub fn generate_struct(
     let ros2_hash = msg.ros2_hash;
 
     // Generate the trait impl conditionally based on options
-    let trait_impl = if options.generate_definition {
-        // Include DEFINITION field
-        let raw_message_definition = generate_raw_string_literal(&definition);
+    let trait_impl = if options.compact {
+        let definition = if options.generate_definition {
+            generate_raw_string_literal(&definition)
+        } else {
+            quote! { "" }
+        };
+        let ros2_hash = ros2_hash.to_hex_string();
+        let encoded_len = encoded_len.map(|len| quote! { , |#receiver| { #len } });
         quote! {
-            impl ::roslibrust::RosMessageType for #struct_name {
-                const ROS_TYPE_NAME: &'static str = #ros_type_name;
-                const MD5SUM: &'static str = #md5sum;
-                const DEFINITION: &'static str = #raw_message_definition;
-                const ROS2_HASH: &'static [u8; 32] = &#ros2_hash;
-                const ROS2_TYPE_NAME: &'static str = #ros2_type_name;
-            }
+            ::roslibrust::codegen::impl_ros_message!(
+                #struct_name, #ros_type_name, #md5sum, #definition, #ros2_hash, #ros2_type_name,
+                #serialized_size #encoded_len
+            );
         }
     } else {
-        // Omit DEFINITION field
+        let definition = if options.generate_definition {
+            generate_raw_string_literal(&definition)
+        } else {
+            // Omit DEFINITION field
+            quote! { "" }
+        };
+        let encoded_len = encoded_len.map(|len| {
+            quote! {
+                fn encoded_len(&self) -> ::std::option::Option<usize> {
+                    #len
+                }
+            }
+        });
         quote! {
             impl ::roslibrust::RosMessageType for #struct_name {
                 const ROS_TYPE_NAME: &'static str = #ros_type_name;
                 const MD5SUM: &'static str = #md5sum;
-                const DEFINITION: &'static str = "";
+                const DEFINITION: &'static str = #definition;
                 const ROS2_HASH: &'static [u8; 32] = &#ros2_hash;
                 const ROS2_TYPE_NAME: &'static str = #ros2_type_name;
+                const SERIALIZED_SIZE: ::std::option::Option<usize> = #serialized_size;
+                #encoded_len
             }
         }
     };
 
+    let lints = if options.compact {
+        quote! { #[allow(non_snake_case, dead_code)] }
+    } else {
+        quote! {
+            #[allow(non_snake_case)]
+            #[allow(dead_code)]
+        }
+    };
     let mut base = quote! {
-        #[allow(non_snake_case)]
-        #[allow(dead_code)]
+        #lints
         #(#attrs )*
         pub struct #struct_name {
             #(#fields )*
@@ -198,9 +349,137 @@ pub fn generate_struct(
             }
         });
     }
+    base.extend(header_compat);
     Ok(base)
 }
 
+/// Generates conversions between a std_msgs/Header and [crate::HeaderCompat]
+/// Returns None for any other message, or a std_msgs/Header matching neither the ROS1 nor the ROS2 definition
+fn generate_header_compat(
+    ros_type_name: &str,
+    fields: &[FieldInfo],
+    struct_name: &str,
+) -> Option<TokenStream> {
+    if ros_type_name != "std_msgs/Header" {
+        return None;
+    }
+    let field_names = fields
+        .iter()
+        .map(|field| field.field_name.as_str())
+        .collect::<Vec<_>>();
+    let (into_seq, from_seq, stamp) = match field_names[..] {
+        ["seq", "stamp", "frame_id"] => (
+            quote! { r#seq: header.seq, },
+            quote! { header.r#seq },
+            &fields[1],
+        ),
+        ["stamp", "frame_id"] => (quote! {}, quote! { 0 }, &fields[0]),
+        _ => return None,
+    };
+    // ROS1 stamps are the integral time type, ROS2 stamps the generated builtin_interfaces/Time
+    let (into_stamp, from_stamp) = match stamp.field_type.package_name.as_deref() {
+        None => (quote! { header.stamp }, quote! { header.r#stamp }),
+        Some("builtin_interfaces") => (
+            quote! {
+                {
+                    let (sec, nanosec) = header.stamp.to_sec_nanosec();
+                    builtin_interfaces::Time { sec, nanosec }
+                }
+            },
+            quote! {
+                ::roslibrust::codegen::integral_types::Time::from_sec_nanosec(header.r#stamp.r#sec, header.r#stamp.r#nanosec)
+            },
+        ),
+        Some(_) => return None,
+    };
+    let struct_name = format_ident!("{}", struct_name);
+    Some(quote! {
+        impl ::std::convert::From<::roslibrust::codegen::HeaderCompat> for #struct_name {
+            fn from(header: ::roslibrust::codegen::HeaderCompat) -> Self {
+                Self {
+                    #into_seq
+                    r#stamp: #into_stamp,
+                    r#frame_id: header.frame_id,
+                }
+            }
+        }
+
+        impl ::std::convert::From<#struct_name> for ::roslibrust::codegen::HeaderCompat {
+            fn from(header: #struct_name) -> Self {
+                Self {
+                    seq: #from_seq,
+                    stamp: #from_stamp,
+                    frame_id: header.r#frame_id,
+                }
+            }
+        }
+    })
+}
+
+/// Generates the statements computing the ROS1 encoded length of a message with `fields`, read through `receiver`
+/// The statements evaluate to an `Option<usize>`, as nested types are measured through [roslibrust_common::RosMessageType::encoded_len]
+fn generate_encoded_len(fields: &[FieldInfo], receiver: &TokenStream) -> TokenStream {
+    let lengths = fields.iter().map(|field| {
+        let field_name = format_ident!("r#{}", field.field_name);
+        let value = quote! { #receiver.#field_name };
+        // Length of the elements, and whether a length prefix is written before them
+        let (elements, prefix) = match (
+            crate::parse::ros1_primitive_size(&field.field_type.field_type),
+            field.field_type.package_name.is_some(),
+        ) {
+            (Some(size), _) => match field.field_type.array_info {
+                ArrayType::NotArray => return quote! { #size },
+                ArrayType::FixedLength(n) => {
+                    let size = size * n;
+                    return quote! { #size };
+                }
+                ArrayType::Bounded(_) | ArrayType::Unbounded if size == 1 => {
+                    (quote! { #value.len() }, true)
+                }
+                ArrayType::Bounded(_) | ArrayType::Unbounded => {
+                    (quote! { #value.len() * #size }, true)
+                }
+            },
+            // Strings are written as their length followed by their contents
+            (None, false) => match field.field_type.array_info {
+                ArrayType::NotArray => return quote! { 4 + #value.len() },
+                ArrayType::FixedLength(_) => (
+                    quote! { #value.iter().map(|s| 4 + s.len()).sum::<usize>() },
+                    false,
+                ),
+                ArrayType::Bounded(_) | ArrayType::Unbounded => (
+                    quote! { #value.iter().map(|s| 4 + s.len()).sum::<usize>() },
+                    true,
+                ),
+            },
+            (None, true) => match field.field_type.array_info {
+                ArrayType::NotArray => {
+                    return quote! { ::roslibrust::RosMessageType::encoded_len(&#value)? }
+                }
+                ArrayType::FixedLength(_) => (
+                    quote! { #value.iter().map(::roslibrust::RosMessageType::encoded_len).sum::<::std::option::Option<usize>>()? },
+                    false,
+                ),
+                ArrayType::Bounded(_) | ArrayType::Unbounded => (
+                    quote! { #value.iter().map(::roslibrust::RosMessageType::encoded_len).sum::<::std::option::Option<usize>>()? },
+                    true,
+                ),
+            },
+        };
+        if prefix {
+            quote! { 4 + #elements }
+        } else {
+            elements
+        }
+    });
+    // Only variable length messages use this, so there is at least one field
+    // The sum is bound first, wrapping a lone `?` term in `Some` directly would trip clippy::needless_question_mark
+    quote! {
+        let len = #(#lengths)+*;
+        ::std::option::Option::Some(len)
+    }
+}
+
 fn generate_field_definition(
     field: FieldInfo,
     msg_pkg: &str,
@@ -234,6 +513,7 @@ fn generate_field_definition(
         "Somehow we generate a rust type that isn't valid rust syntax. This should not happen!",
     );
 
+    let doc_lines = generate_doc_lines(field.comment.as_deref(), options);
     let field_name = format_ident!("r#{}", field.field_name);
     let property_line = quote! { pub #field_name: #rust_field_type, };
 
@@ -314,6 +594,7 @@ fn generate_field_definition(
         ArrayType::NotArray => quote! {},
     };
     Ok(quote! {
+        #doc_lines
         #default_line
         #serde_line
         #property_line
@@ -323,7 +604,9 @@ fn generate_field_definition(
 fn generate_constant_field_definition(
     constant: ConstantInfo,
     version: RosVersion,
+    options: &CodegenOptions,
 ) -> Result<TokenStream, Error> {
+    let doc_lines = generate_doc_lines(constant.comment.as_deref(), options);
     let constant_name = format_ident!("r#{}", constant.constant_name);
     let constant_rust_type = convert_ros_type_to_rust_type(version, &constant.constant_type)
         .ok_or(Error::new(format!(
@@ -347,15 +630,41 @@ fn generate_constant_field_definition(
         &ArrayType::NotArray,
         version,
     )?;
-    Ok(quote! { pub const #constant_name: #constant_rust_type = #constant_value; })
+    Ok(quote! {
+        #doc_lines
+        pub const #constant_name: #constant_rust_type = #constant_value;
+    })
+}
+
+// Converts a comment from a message file into doc attributes, one per line like `///` comments produce
+fn generate_doc_lines(comment: Option<&str>, options: &CodegenOptions) -> TokenStream {
+    match comment {
+        Some(comment) if options.generate_docs => {
+            let lines = comment.lines().map(|line| format!(" {line}"));
+            quote! { #(#[doc = #lines])* }
+        }
+        _ => quote! {},
+    }
 }
 
 pub fn generate_mod(
     pkg_name: String,
     struct_definitions: Vec<TokenStream>,
     all_pkgs: &[String],
+    options: &CodegenOptions,
 ) -> TokenStream {
     let mod_name = format_ident!("{}", &pkg_name);
+    if options.compact {
+        // A single glob import of the sibling package modules instead of one import per package
+        return quote! {
+            pub mod #mod_name {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #(#struct_definitions )*
+            }
+        };
+    }
     let all_pkgs = all_pkgs
         .iter()
         .filter(|item| item.as_str() != pkg_name.as_str())
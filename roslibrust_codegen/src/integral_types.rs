@@ -131,6 +131,67 @@ impl TryFrom<Duration> for std::time::Duration {
     }
 }
 
+const NANOS_PER_SEC: i64 = 1_000_000_000;
+
+// Splits a time or duration into whole seconds and nanoseconds in [0, 1e9), the representation ROS2 uses
+// Nanoseconds outside of that range are carried into the seconds, saturating if the seconds overflow an i32
+fn normalize(secs: i32, nsecs: i64) -> (i32, u32) {
+    let total = secs as i64 * NANOS_PER_SEC + nsecs;
+    match i32::try_from(total.div_euclid(NANOS_PER_SEC)) {
+        Ok(secs) => (secs, total.rem_euclid(NANOS_PER_SEC) as u32),
+        Err(_) if total < 0 => (i32::MIN, 0),
+        Err(_) => (i32::MAX, (NANOS_PER_SEC - 1) as u32),
+    }
+}
+
+impl Time {
+    /// Returns the seconds and nanoseconds of ROS2's builtin_interfaces/Time for this time.
+    /// Negative nanoseconds, or nanoseconds of a second or more, are carried into the seconds.
+    pub fn to_sec_nanosec(&self) -> (i32, u32) {
+        normalize(self.secs, self.nsecs as i64)
+    }
+
+    /// Creates a time from the seconds and nanoseconds of ROS2's builtin_interfaces/Time.
+    /// Nanoseconds of a second or more are carried into the seconds.
+    pub fn from_sec_nanosec(sec: i32, nanosec: u32) -> Time {
+        let (secs, nsecs) = normalize(sec, nanosec as i64);
+        Time {
+            secs,
+            nsecs: nsecs as i32,
+        }
+    }
+}
+
+impl Duration {
+    /// Returns the seconds and nanoseconds of ROS2's builtin_interfaces/Duration for this duration.
+    /// Negative nanoseconds, or nanoseconds of a second or more, are carried into the seconds.
+    pub fn to_sec_nanosec(&self) -> (i32, u32) {
+        normalize(self.sec, self.nsec as i64)
+    }
+
+    /// Creates a duration from the seconds and nanoseconds of ROS2's builtin_interfaces/Duration.
+    /// Nanoseconds of a second or more are carried into the seconds.
+    pub fn from_sec_nanosec(sec: i32, nanosec: u32) -> Duration {
+        let (sec, nsec) = normalize(sec, nanosec as i64);
+        Duration {
+            sec,
+            nsec: nsec as i32,
+        }
+    }
+}
+
+/// A version independent std_msgs/Header, for code shared between ROS1 and ROS2 backends
+/// NOTE: Is not a message in and of itself, convert it into a generated std_msgs/Header with `.into()`
+///
+/// Generated std_msgs/Header types for both versions convert to and from this type.
+/// ROS2 headers have no sequence number, so `seq` is dropped when converting into one, and is 0 when converting from one.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct HeaderCompat {
+    pub seq: u32,
+    pub stamp: Time,
+    pub frame_id: String,
+}
+
 /// Conversion from chrono::DateTime<chrono::Utc> to our internal Time type
 #[cfg(feature = "chrono")]
 impl TryFrom<chrono::DateTime<chrono::Utc>> for Time {
@@ -287,6 +348,47 @@ mod test {
         assert!(tokio_duration.is_err());
     }
 
+    #[test]
+    fn test_ros2_representation() {
+        let time = crate::Time {
+            secs: 10,
+            nsecs: 500,
+        };
+        assert_eq!(time.to_sec_nanosec(), (10, 500));
+        assert_eq!(crate::Time::from_sec_nanosec(10, 500), time);
+
+        // Negative nsecs borrow from the seconds rather than wrapping around
+        let time = crate::Time { secs: 1, nsecs: -1 };
+        assert_eq!(time.to_sec_nanosec(), (0, 999_999_999));
+        let duration = crate::Duration { sec: 0, nsec: -1 };
+        assert_eq!(duration.to_sec_nanosec(), (-1, 999_999_999));
+
+        // Whole seconds of nanoseconds are carried
+        assert_eq!(
+            crate::Time::from_sec_nanosec(1, 2_500_000_000),
+            crate::Time {
+                secs: 3,
+                nsecs: 500_000_000
+            }
+        );
+        assert_eq!(
+            crate::Duration::from_sec_nanosec(-1, u32::MAX).to_sec_nanosec(),
+            (3, 294_967_295)
+        );
+
+        // Saturates rather than overflowing the seconds
+        let time = crate::Time {
+            secs: i32::MAX,
+            nsecs: i32::MAX,
+        };
+        assert_eq!(time.to_sec_nanosec(), (i32::MAX, 999_999_999));
+        let time = crate::Time {
+            secs: i32::MIN,
+            nsecs: -1,
+        };
+        assert_eq!(time.to_sec_nanosec(), (i32::MIN, 0));
+    }
+
     #[test]
     #[cfg(feature = "chrono")]
     fn test_chrono_duration_conversions() {
@@ -16,15 +16,27 @@ use std::{
 
 use log::*;
 use proc_macro2::TokenStream;
-use quote::{quote, ToTokens};
+use quote::{format_ident, quote, ToTokens};
 use simple_error::{bail, SimpleError as Error};
 use utils::Package;
 
+pub mod build_helper;
+pub mod compact;
+pub mod dialect;
+pub mod lint;
+
 mod gen;
-pub use gen::CodegenOptions;
 use gen::*;
+pub use gen::{CodegenOptions, CodegenPlugin};
+// Error type returned by codegen, re-exported so plugins can report errors without depending on simple-error
+pub use simple_error::SimpleError;
 mod parse;
 use parse::*;
+// The parsed intermediate representation is public so external tools can build on roslibrust's parser
+pub use parse::{
+    parse_ros_action_file, parse_ros_message_file, parse_ros_service_file, ParsedActionFile,
+    ParsedMessageFile, ParsedServiceFile,
+};
 pub mod utils;
 use utils::RosVersion;
 mod ros2_hashing;
@@ -56,6 +68,11 @@ impl Ros2Hash {
         format!("RIHS01_{}", hex::encode(self.0))
     }
 
+    /// Returns the hash as hex without the "RIHS01_" prefix, as used in compact generated code
+    pub fn to_hex_string(&self) -> String {
+        hex::encode(self.0)
+    }
+
     pub fn from_string(hash_str: &str) -> Self {
         // Remove "RIHS01_" prefix if present
         let hex_str = hash_str.trim_start_matches("RIHS01_");
@@ -65,6 +82,13 @@ impl Ros2Hash {
     }
 }
 
+// Serialized as the "RIHS01_" prefixed string so dumped IR matches what ROS2 tooling displays
+impl Serialize for Ros2Hash {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_hash_string())
+    }
+}
+
 // Conversion from Ros2Hash to TokenStream for use in generated code
 impl ToTokens for Ros2Hash {
     fn to_tokens(&self, tokens: &mut TokenStream) {
@@ -77,19 +101,24 @@ impl ToTokens for Ros2Hash {
     }
 }
 
-#[derive(Clone, Debug)]
+/// A [ParsedMessageFile] with the information resolved from its dependencies
+///
+/// Implements [Serialize] so the fully resolved message can be dumped, e.g. to JSON, by external tools.
+#[derive(Clone, Debug, Serialize)]
 pub struct MessageFile {
     pub parsed: ParsedMessageFile,
     pub md5sum: String,
-    // Type Hash following the ros2 RIHS01 standard stored as bytes
+    /// Type Hash following the ros2 RIHS01 standard stored as bytes
     pub ros2_hash: Ros2Hash,
-    // This is the expanded definition of the message for use in message_definition field of
-    // a connection header.
-    // See how https://wiki.ros.org/ROS/TCPROS references gendeps --cat
-    // See https://wiki.ros.org/roslib/gentools for an example of the output
+    /// This is the expanded definition of the message for use in message_definition field of
+    /// a connection header.
+    /// See how <https://wiki.ros.org/ROS/TCPROS> references gendeps --cat
+    /// See <https://wiki.ros.org/roslib/gentools> for an example of the output
     pub definition: String,
-    // If true this message has no dynamic sized members and fits in a fixed size in memory
+    /// If true this message has no dynamic sized members and fits in a fixed size in memory
     pub is_fixed_encoding_length: bool,
+    /// Exact size in bytes of the message in the ROS1 wire format, present iff every instance has the same size
+    pub serialized_size: Option<usize>,
 }
 
 impl MessageFile {
@@ -107,12 +136,14 @@ impl MessageFile {
             log::error!("Failed to determine if message is fixed length: {parsed:#?}");
             None
         })?;
+        let serialized_size = Self::compute_serialized_size(&parsed, graph);
         Some(MessageFile {
             parsed,
             md5sum,
             ros2_hash,
             definition,
             is_fixed_encoding_length: is_fixed_length,
+            serialized_size,
         })
     }
 
@@ -144,10 +175,20 @@ impl MessageFile {
         self.is_fixed_encoding_length
     }
 
+    /// Returns the exact size in bytes of the message in the ROS1 wire format, if every instance has the same size
+    pub fn get_serialized_size(&self) -> Option<usize> {
+        self.serialized_size
+    }
+
     pub fn get_definition(&self) -> &str {
         &self.definition
     }
 
+    /// Returns the contents of the message file, comments and whitespace included
+    pub fn get_source(&self) -> &str {
+        &self.parsed.source
+    }
+
     fn compute_md5sum(
         parsed: &ParsedMessageFile,
         graph: &BTreeMap<String, MessageFile>,
@@ -193,27 +234,40 @@ impl MessageFile {
         Some(md5sum_content)
     }
 
-    /// Returns the set of all referenced non-intrinsic field types in this type or any of its dependencies
-    fn get_unique_field_types(
+    /// Returns the full names of all non-intrinsic types referenced by this type or any of its dependencies
+    /// Types are ordered as they are first encountered in a depth first walk of the fields, matching the
+    /// order in which `gendeps --cat` lists them
+    fn get_all_dependencies(
         parsed: &ParsedMessageFile,
         graph: &BTreeMap<String, MessageFile>,
-    ) -> Option<BTreeSet<String>> {
-        let mut unique_field_types = BTreeSet::new();
+    ) -> Option<Vec<String>> {
+        let mut dependencies = vec![];
         for field in &parsed.fields {
             let field_type = field.field_type.field_type.as_str();
             if is_intrinsic_type(parsed.version.unwrap_or(RosVersion::ROS1), field_type) {
                 continue;
             }
-            let sub_message = graph.get(field.get_full_type_name().as_str())?;
+            let full_type_name = field.get_full_type_name();
+            let Some(sub_message) = graph.get(full_type_name.as_str()) else {
+                log::error!(
+                    "Unable to find message type: {full_type_name:?}, while computing full definition of {}",
+                    parsed.get_full_name()
+                );
+                return None;
+            };
             // Note: need to add both the field that is referenced AND its sub-dependencies
-            unique_field_types.insert(field.get_full_type_name());
-            let mut sub_deps = Self::get_unique_field_types(&sub_message.parsed, graph)?;
-            unique_field_types.append(&mut sub_deps);
+            dependencies.push(full_type_name);
+            dependencies.extend(Self::get_all_dependencies(&sub_message.parsed, graph)?);
         }
-        Some(unique_field_types)
+        // Only the first occurrence of each type is kept
+        let mut seen = BTreeSet::new();
+        dependencies.retain(|dependency| seen.insert(dependency.clone()));
+        Some(dependencies)
     }
 
     /// Computes the full definition of the message, including all referenced custom types
+    /// The output is byte for byte what `gendeps --cat` produces: the text of each message file is included as is,
+    /// comments and whitespace included, so that connection headers match those of other ROS1 clients.
     /// For reference see: https://wiki.ros.org/roslib/gentools
     /// Implementation in gentools: https://github.com/strawlab/ros/blob/c3a8785f9d9551cc05cd74000c6536e2244bb1b1/core/roslib/src/roslib/gentools.py#L245
     fn compute_full_definition(
@@ -221,20 +275,15 @@ impl MessageFile {
         graph: &BTreeMap<String, MessageFile>,
     ) -> Option<String> {
         let mut definition_content = String::new();
-        definition_content.push_str(&format!("{}\n", parsed.source.trim()));
+        definition_content.push_str(&format!("{}\n", parsed.source));
         let sep: &str =
             "================================================================================\n";
-        for field in Self::get_unique_field_types(parsed, graph)? {
-            let Some(sub_message) = graph.get(&field) else {
-                log::error!(
-                    "Unable to find message type: {field:?}, while computing full definition of {}",
-                    parsed.get_full_name()
-                );
-                return None;
-            };
+        for dependency in Self::get_all_dependencies(parsed, graph)? {
+            // Presence in the graph was checked by get_all_dependencies
+            let sub_message = graph.get(&dependency)?;
             definition_content.push_str(sep);
-            definition_content.push_str(&format!("MSG: {}\n", sub_message.get_full_name()));
-            definition_content.push_str(&format!("{}\n", sub_message.get_definition().trim()));
+            definition_content.push_str(&format!("MSG: {dependency}\n"));
+            definition_content.push_str(&format!("{}\n", sub_message.get_source()));
         }
         // Remove trailing \n added by concatenation logic
         definition_content.pop();
@@ -268,6 +317,30 @@ impl MessageFile {
         }
         Some(true)
     }
+
+    /// Computes the exact size of the message in the ROS1 wire format
+    /// Returns None if the message contains strings or sequences, or references an unresolved type
+    fn compute_serialized_size(
+        parsed: &ParsedMessageFile,
+        graph: &BTreeMap<String, MessageFile>,
+    ) -> Option<usize> {
+        let mut size = 0usize;
+        for field in &parsed.fields {
+            let element_size = match ros1_primitive_size(&field.field_type.field_type) {
+                Some(element_size) => element_size,
+                // Strings are the only intrinsic type without a fixed size
+                None if field.field_type.package_name.is_none() => return None,
+                None => graph.get(&field.get_full_type_name())?.serialized_size?,
+            };
+            size += match field.field_type.array_info {
+                ArrayType::NotArray => element_size,
+                // Fixed size arrays are written without a length
+                ArrayType::FixedLength(n) => element_size * n,
+                ArrayType::Bounded(_) | ArrayType::Unbounded => return None,
+            };
+        }
+        Some(size)
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -397,7 +470,10 @@ impl ActionWithHashes {
 }
 
 /// Stores the ROS string representation of a literal
-#[derive(Clone, Debug)]
+///
+/// Serializes as a plain string.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+#[serde(transparent)]
 pub struct RosLiteral {
     pub inner: String,
 }
@@ -415,33 +491,33 @@ impl From<String> for RosLiteral {
 }
 
 /// Represents the different options for a field being an array
-#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Serialize, Deserialize)]
 pub enum ArrayType {
     NotArray,
     FixedLength(usize),
-    // Bounded is ROS2 only
+    /// Bounded is ROS2 only
     Bounded(usize),
     Unbounded,
 }
 
 /// Describes the type for an individual field in a message
-#[derive(PartialEq, Eq, Hash, Debug, Clone)]
+#[derive(PartialEq, Eq, Hash, Debug, Clone, Serialize, Deserialize)]
 pub struct FieldType {
-    // Present when an externally referenced package is used
+    /// Present when an externally referenced package is used
     pub package_name: Option<String>,
-    // Redundantly store the name of the package the field is in
-    // This is so that when an external package_name is not present
-    // we can still construct the full name of the field "package/field_type"
+    /// Redundantly store the name of the package the field is in
+    /// This is so that when an external package_name is not present
+    /// we can still construct the full name of the field "package/field_type"
     pub source_package: String,
-    // Explicit text of type without array specifier, referenced package, or string capacity
-    // e.g. "string", "uint8", "Header", "MyCustomType"
-    // Not: "std_msgs/Header", "uint8[10]", "string<=10"
+    /// Explicit text of type without array specifier, referenced package, or string capacity
+    /// e.g. "string", "uint8", "Header", "MyCustomType"
+    /// Not: "std_msgs/Header", "uint8\[10\]", "string<=10"
     pub field_type: String,
-    // Indicates if the field is some type of list or "NotArray"
+    /// Indicates if the field is some type of list or "NotArray"
     pub array_info: ArrayType,
 
-    // ROS2 specific feature, you can write "string<=10" to indicate a string with a maximum length
-    // When this happen we'll parse the capacity here, and convert the field_type to "string"
+    /// ROS2 specific feature, you can write "string<=10" to indicate a string with a maximum length
+    /// When this happen we'll parse the capacity here, and convert the field_type to "string"
     pub string_capacity: Option<usize>,
 }
 
@@ -466,12 +542,15 @@ impl FieldType {
 }
 
 /// Describes all information for an individual field
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct FieldInfo {
     pub field_type: FieldType,
     pub field_name: String,
-    // Exists if this is a ros2 message field with a default value
+    /// Exists if this is a ros2 message field with a default value
     pub default: Option<RosLiteral>,
+    /// Comment documenting the field in the message file, taken from the comment lines directly above it
+    /// and any comment at the end of its line
+    pub comment: Option<String>,
 }
 
 // Because TokenStream doesn't impl PartialEq we have to do it manually for FieldInfo
@@ -507,11 +586,13 @@ impl FieldInfo {
 
 /// Describes all information for a constant within a message
 /// Note: Constants are not fully supported yet (waiting on codegen support)
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ConstantInfo {
     pub constant_type: String,
     pub constant_name: String,
     pub constant_value: RosLiteral,
+    /// Comment documenting the constant in the message file, captured the same way as [FieldInfo::comment]
+    pub comment: Option<String>,
 }
 
 // Because TokenStream doesn't impl PartialEq we have to do it manually for ConstantInfo
@@ -547,13 +628,25 @@ pub fn find_and_generate_ros_messages(
 pub fn find_and_generate_ros_messages_without_ros_package_path(
     search_paths: Vec<PathBuf>,
 ) -> Result<(TokenStream, Vec<PathBuf>), Error> {
-    let (messages, services, actions) = find_and_parse_ros_messages(&search_paths)?;
+    find_and_generate_ros_messages_with_options(search_paths, &CodegenOptions::default())
+}
+
+/// Similar to [find_and_generate_ros_messages_without_ros_package_path], but generates code according to `options`.
+///
+/// Paths from `ROS_PACKAGE_PATH` can be included by adding [utils::get_search_paths] to `search_paths`.
+/// When [CodegenOptions::root_types] is set the returned dependent paths only include the files of the generated types.
+pub fn find_and_generate_ros_messages_with_options(
+    search_paths: Vec<PathBuf>,
+    options: &CodegenOptions,
+) -> Result<(TokenStream, Vec<PathBuf>), Error> {
+    let (messages, services, actions) =
+        find_and_parse_ros_messages_with_options(&search_paths, options)?;
     if messages.is_empty() && services.is_empty() {
         // I'm considering this an error for now, but I could see this one being debateable
         // As it stands there is not good way for us to manually produce a warning, so I'd rather fail loud
         bail!("Failed to find any services or messages while generating ROS message definitions, paths searched: {search_paths:?}");
     }
-    tokenize_messages_and_services(messages, services, actions)
+    tokenize_messages_and_services(messages, services, actions, options)
 }
 
 /// Generates source code and list of depnendent file system paths
@@ -561,21 +654,97 @@ fn tokenize_messages_and_services(
     messages: Vec<ParsedMessageFile>,
     services: Vec<ParsedServiceFile>,
     actions: Vec<ParsedActionFile>,
+    options: &CodegenOptions,
 ) -> Result<(TokenStream, Vec<PathBuf>), Error> {
     let (messages, services) = resolve_dependency_graph(messages, services)?;
+    let (messages, services) = match &options.root_types {
+        Some(root_types) => select_dependency_closure(messages, services, root_types)?,
+        None => (messages, services),
+    };
+    // Actions are expanded into messages, so only the paths of the actions which are still used are kept
+    let actions: Vec<_> = actions
+        .into_iter()
+        .filter(|action| messages.iter().any(|msg| msg.parsed.path == action.path))
+        .collect();
     let msg_iter = messages.iter().map(|m| m.parsed.path.clone());
     let srv_iter = services.iter().map(|s| s.parsed.path.clone());
     let action_iter = actions.iter().map(|a| a.path.clone());
-    let dependent_paths = msg_iter
+    let mut dependent_paths: Vec<PathBuf> = msg_iter
         .chain(srv_iter)
         .chain(action_iter)
         .filter(|p| !p.starts_with("/tmp/roslibrust_builtin/"))
         .collect();
-    let source =
-        generate_rust_ros_message_definitions(messages, services, &CodegenOptions::default())?;
+    dependent_paths.sort();
+    dependent_paths.dedup();
+    let source = generate_rust_ros_message_definitions(messages, services, options)?;
     Ok((source, dependent_paths))
 }
 
+/// Reduces the resolved messages and services to the `root_types` and every message they depend on.
+///
+/// Returns an error if one of the root types isn't among the messages or services.
+fn select_dependency_closure(
+    messages: Vec<MessageFile>,
+    services: Vec<ServiceFile>,
+    root_types: &[String],
+) -> Result<(Vec<MessageFile>, Vec<ServiceFile>), Error> {
+    let message_names: BTreeSet<String> = messages.iter().map(|m| m.get_full_name()).collect();
+    let mut selected_services = BTreeSet::new();
+    let mut to_visit = vec![];
+    for root in root_types {
+        if message_names.contains(root) {
+            to_visit.push(root.clone());
+        } else if let Some(srv) = services.iter().find(|srv| srv.get_full_name() == *root) {
+            selected_services.insert(root.clone());
+            to_visit.push(srv.request.get_full_name());
+            to_visit.push(srv.response.get_full_name());
+        } else {
+            bail!(
+                "Codegen was asked to generate {:?}, but no message or service with that name was found",
+                root
+            );
+        }
+    }
+
+    // The request and response of a service aren't in messages, but their dependencies are
+    let fields_of = |name: &str| -> Vec<String> {
+        let msg = messages
+            .iter()
+            .map(|m| &m.parsed)
+            .chain(
+                services
+                    .iter()
+                    .flat_map(|s| [&s.request.parsed, &s.response.parsed]),
+            )
+            .find(|m| m.get_full_name() == name);
+        msg.map(|msg| {
+            msg.fields
+                .iter()
+                .filter(|f| !f.field_type.is_primitive())
+                .map(|f| f.get_full_type_name())
+                .collect()
+        })
+        .unwrap_or_default()
+    };
+    let mut selected_messages = BTreeSet::new();
+    while let Some(name) = to_visit.pop() {
+        if selected_messages.insert(name.clone()) {
+            to_visit.extend(fields_of(&name));
+        }
+    }
+
+    Ok((
+        messages
+            .into_iter()
+            .filter(|m| selected_messages.contains(&m.get_full_name()))
+            .collect(),
+        services
+            .into_iter()
+            .filter(|s| selected_services.contains(&s.get_full_name()))
+            .collect(),
+    ))
+}
+
 /// Generates struct definitions and implementations for message and service files
 /// in the given packages.
 pub fn generate_ros_messages_for_packages(
@@ -596,27 +765,18 @@ pub fn generate_ros_messages_for_packages(
     if messages.is_empty() && services.is_empty() {
         bail!("Failed to find any services or messages while generating ROS message definitions, packages searched: {packages:?}")
     }
-    tokenize_messages_and_services(messages, services, actions)
+    tokenize_messages_and_services(messages, services, actions, &CodegenOptions::default())
 }
 
-/// Searches a list of paths for ROS packages to find their associated message
-/// and service files, parsing and performing dependency resolution on those
-/// it finds. Returns a map of PACKAGE_NAME/MESSAGE_NAME strings to message file
-/// data and vector of service file data.
+/// Searches a list of paths for ROS packages, resolving packages found in more than one path according to
+/// [CodegenOptions::duplicate_package_policy].
 ///
-/// * `search_paths` - A list of paths to search.
-///
-#[allow(clippy::type_complexity)]
-pub fn find_and_parse_ros_messages(
+/// The result reports which directory each package is used from and which duplicates were left out, and is
+/// the same set of packages codegen generates from given the same `search_paths` and `options`.
+pub fn find_ros_packages(
     search_paths: &[PathBuf],
-) -> Result<
-    (
-        Vec<ParsedMessageFile>,
-        Vec<ParsedServiceFile>,
-        Vec<ParsedActionFile>,
-    ),
-    Error,
-> {
+    options: &CodegenOptions,
+) -> Result<utils::DeduplicatedPackages, Error> {
     let search_paths  = search_paths
         .iter()
         .map(|path| {
@@ -632,14 +792,57 @@ pub fn find_and_parse_ros_messages(
     );
     let packages = utils::crawl(&search_paths);
     // Check for duplicate package names
-    let packages = utils::deduplicate_packages(packages);
-    if packages.is_empty() {
+    let packages =
+        utils::deduplicate_packages_with_policy(packages, options.duplicate_package_policy)?;
+    if packages.packages.is_empty() {
         bail!(
             "No ROS packages found while searching in: {search_paths:?}, relative to {:?}",
             std::env::current_dir().unwrap()
         );
     }
-    debug!("After deduplication {:?} packages remain.", packages.len());
+    debug!(
+        "After deduplication {:?} packages remain.",
+        packages.packages.len()
+    );
+    Ok(packages)
+}
+
+/// Searches a list of paths for ROS packages to find their associated message
+/// and service files, parsing and performing dependency resolution on those
+/// it finds. Returns a map of PACKAGE_NAME/MESSAGE_NAME strings to message file
+/// data and vector of service file data.
+///
+/// * `search_paths` - A list of paths to search.
+///
+#[allow(clippy::type_complexity)]
+pub fn find_and_parse_ros_messages(
+    search_paths: &[PathBuf],
+) -> Result<
+    (
+        Vec<ParsedMessageFile>,
+        Vec<ParsedServiceFile>,
+        Vec<ParsedActionFile>,
+    ),
+    Error,
+> {
+    find_and_parse_ros_messages_with_options(search_paths, &CodegenOptions::default())
+}
+
+/// Similar to [find_and_parse_ros_messages], but resolves packages found in multiple search paths
+/// according to [CodegenOptions::duplicate_package_policy].
+#[allow(clippy::type_complexity)]
+pub fn find_and_parse_ros_messages_with_options(
+    search_paths: &[PathBuf],
+    options: &CodegenOptions,
+) -> Result<
+    (
+        Vec<ParsedMessageFile>,
+        Vec<ParsedServiceFile>,
+        Vec<ParsedActionFile>,
+    ),
+    Error,
+> {
+    let packages = find_ros_packages(search_paths, options)?.packages;
 
     let message_files = packages
         .iter()
@@ -687,10 +890,48 @@ pub fn generate_rust_ros_message_definitions(
 ) -> Result<TokenStream, Error> {
     let mut modules_to_struct_definitions: BTreeMap<String, Vec<TokenStream>> = BTreeMap::new();
 
+    let (mut messages, mut services) = match &options.root_types {
+        Some(root_types) => select_dependency_closure(messages, services, root_types)?,
+        None => (messages, services),
+    };
+
+    // Sort the inputs so the generated code is identical regardless of the order files were found in,
+    // modules are ordered by the BTreeMap
+    messages.sort_by_key(|msg| msg.get_full_name());
+    services.sort_by_key(|srv| srv.get_full_name());
+
+    let type_visitor = if options.generate_type_visitor {
+        let message_types = messages.iter().map(|message| {
+            let pkg = format_ident!("{}", message.parsed.package);
+            let name = format_ident!("{}", message.parsed.name);
+            quote! { #pkg::#name }
+        });
+        quote! {
+            /// Calls the visitor once for each message type generated here
+            pub fn visit_message_types<V: ::roslibrust::MessageTypeVisitor>(visitor: &mut V) {
+                #(visitor.visit::<#message_types>();)*
+            }
+        }
+    } else {
+        quote! {}
+    };
+
+    let plugin_items = options
+        .plugins
+        .iter()
+        .map(|plugin| plugin.generate_root(&messages, &services, options))
+        .collect::<Result<Vec<_>, _>>()?;
+
     // Convert messages files into rust token streams and insert them into BTree organized by package
     messages.into_iter().try_for_each(|message| {
         let pkg_name = message.parsed.package.clone();
-        let definition = generate_struct(message, Some(options))?;
+        let extra_items = options
+            .plugins
+            .iter()
+            .map(|plugin| plugin.generate_for_message(&message, options))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut definition = generate_struct(message, Some(options))?;
+        definition.extend(extra_items);
         if let Some(entry) = modules_to_struct_definitions.get_mut(&pkg_name) {
             entry.push(definition);
         } else {
@@ -701,7 +942,13 @@ pub fn generate_rust_ros_message_definitions(
     // Do the same for services
     services.into_iter().try_for_each(|service| {
         let pkg_name = service.parsed.package.clone();
-        let definition = generate_service(service, Some(options))?;
+        let extra_items = options
+            .plugins
+            .iter()
+            .map(|plugin| plugin.generate_for_service(&service, options))
+            .collect::<Result<Vec<_>, _>>()?;
+        let mut definition = generate_service(service, Some(options))?;
+        definition.extend(extra_items);
         if let Some(entry) = modules_to_struct_definitions.get_mut(&pkg_name) {
             entry.push(definition);
         } else {
@@ -716,12 +963,15 @@ pub fn generate_rust_ros_message_definitions(
         .collect::<Vec<String>>();
     let module_definitions = modules_to_struct_definitions
         .into_iter()
-        .map(|(pkg, struct_defs)| generate_mod(pkg, struct_defs, &all_pkgs[..]))
+        .map(|(pkg, struct_defs)| generate_mod(pkg, struct_defs, &all_pkgs[..], options))
         .collect::<Vec<TokenStream>>();
 
     Ok(quote! {
         #(#module_definitions)*
 
+        #type_visitor
+
+        #(#plugin_items)*
     })
 }
 
@@ -807,10 +1057,41 @@ pub fn resolve_dependency_graph(
                 .filter(|f| !msg_names.contains(f))
                 .collect::<Vec<_>>();
 
+            // Packages that none of the found messages belong to are most likely missing from the search paths
+            let mut missing_packages = unresolved_fields
+                .iter()
+                .filter_map(|f| f.split_once('/').map(|(pkg, _)| pkg))
+                .filter(|pkg| {
+                    !resolved_messages
+                        .values()
+                        .any(|msg| msg.parsed.package == *pkg)
+                        && !unresolved_messages
+                            .iter()
+                            .any(|item| item.msg.package == *pkg)
+                })
+                .collect::<Vec<_>>();
+            missing_packages.sort();
+            missing_packages.dedup();
+            // Note: bail! only interpolates when given explicit arguments
+            if !missing_packages.is_empty() {
+                bail!(
+                    "Unable to resolve ROS message dependencies, no messages were found for the following packages:\n\
+                     {:#?}\n\
+                     Make sure they are installed and their paths are included in the search paths or ROS_PACKAGE_PATH.\n\
+                     The following types are unresolved:\n{:#?}\n\
+                     This is preventing full resolution for the following messages:\n{:#?}",
+                    missing_packages,
+                    unresolved_fields,
+                    msg_names
+                );
+            }
+
             bail!(
                 "Unable to resolve ROS message dependencies after reaching search limit.\n\
-                 The following types are still unresolved:\n{unresolved_fields:#?}\n
-                 This is preventing full resolution for the following messages:\n{msg_names:#?}"
+                 The following types are still unresolved:\n{:#?}\n
+                 This is preventing full resolution for the following messages:\n{:#?}",
+                unresolved_fields,
+                msg_names
             );
         }
     }
@@ -972,6 +1253,441 @@ mod test {
         assert!(!paths.is_empty());
     }
 
+    /// Confirms generated code doesn't depend on the order of search paths or of the parsed files
+    #[test_log::test]
+    fn generation_is_deterministic() {
+        let paths: Vec<std::path::PathBuf> = vec![
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/ros1_test_msgs").into(),
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../assets/ros1_common_interfaces/std_msgs"
+            )
+            .into(),
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../assets/ros1_common_interfaces/rosapi"
+            )
+            .into(),
+        ];
+        let (first, first_paths) =
+            crate::find_and_generate_ros_messages_without_ros_package_path(paths.clone()).unwrap();
+        let reversed = paths.into_iter().rev().collect();
+        let (second, second_paths) =
+            crate::find_and_generate_ros_messages_without_ros_package_path(reversed).unwrap();
+        assert_eq!(first.to_string(), second.to_string());
+        assert_eq!(first_paths, second_paths);
+
+        // Also directly against the order of the resolved files
+        let (messages, services, _actions) = crate::find_and_parse_ros_messages(&[concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../assets/ros1_common_interfaces/std_msgs"
+        )
+        .into()])
+        .unwrap();
+        let (messages, services) = crate::resolve_dependency_graph(messages, services).unwrap();
+        let options = crate::CodegenOptions::default();
+        let forward = crate::generate_rust_ros_message_definitions(
+            messages.clone(),
+            services.clone(),
+            &options,
+        )
+        .unwrap();
+        let backward = crate::generate_rust_ros_message_definitions(
+            messages.into_iter().rev().collect(),
+            services.into_iter().rev().collect(),
+            &options,
+        )
+        .unwrap();
+        assert_eq!(forward.to_string(), backward.to_string());
+    }
+
+    /// Confirms full definitions and md5sums match those of `gendeps --cat` and genpy byte for byte
+    #[test_log::test]
+    fn full_definition_matches_gendeps() {
+        let corpus = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/gendeps");
+        let (messages, services, _actions) =
+            crate::find_and_parse_ros_messages(&[corpus.into()]).unwrap();
+        let (messages, _services) = crate::resolve_dependency_graph(messages, services).unwrap();
+        let find = |name: &str| {
+            messages
+                .iter()
+                .find(|msg| msg.get_full_name() == name)
+                .unwrap_or_else(|| panic!("{name} not found in {corpus}"))
+        };
+
+        for (name, md5sum) in [
+            ("std_msgs/Header", "2176decaecbce78abc3b96ef049fabed"),
+            ("geometry_msgs/Pose", "e45d45a5a1ce597b249e23fb30fc871f"),
+            (
+                "geometry_msgs/PoseStamped",
+                "d3812c3cbc69362b77dc0b19b345f8f5",
+            ),
+        ] {
+            let expected = std::fs::read_to_string(format!(
+                "{corpus}/expected/{}.txt",
+                name.replace('/', "__")
+            ))
+            .unwrap();
+            let msg = find(name);
+            assert_eq!(msg.get_definition(), expected, "{name}");
+            assert_eq!(msg.get_md5sum(), md5sum, "{name}");
+            // The definition must also round trip through the runtime md5sum calculation
+            assert_eq!(
+                roslibrust_common::md5sum::from_message_definition(name, msg.get_definition())
+                    .unwrap(),
+                md5sum,
+                "{name}"
+            );
+        }
+    }
+
+    /// Confirms packages in earlier search paths shadow the same package in later ones, like workspace overlays
+    #[test_log::test]
+    fn earlier_search_paths_overlay_later_ones() {
+        let root = std::env::temp_dir().join("roslibrust_overlay_test");
+        let add_package = |workspace: &str, name: &str| {
+            let path = root.join(workspace).join(name);
+            std::fs::create_dir_all(&path).unwrap();
+            std::fs::write(
+                path.join("package.xml"),
+                format!("<package><name>{name}</name><buildtool_depend>catkin</buildtool_depend></package>"),
+            )
+            .unwrap();
+        };
+        add_package("overlay", "my_msgs");
+        add_package("underlay", "my_msgs");
+        add_package("underlay", "other_msgs");
+        let overlay = root.join("overlay").canonicalize().unwrap();
+        let underlay = root.join("underlay").canonicalize().unwrap();
+
+        let packages = crate::find_ros_packages(
+            &[overlay.clone(), underlay.clone(), overlay.clone()],
+            &crate::CodegenOptions::default(),
+        )
+        .unwrap();
+        let paths = packages.package_paths();
+        assert_eq!(paths.len(), 2);
+        assert_eq!(paths["my_msgs"], overlay.join("my_msgs"));
+        // Shadowing is per package, not per workspace
+        assert_eq!(paths["other_msgs"], underlay.join("other_msgs"));
+        // Searching the overlay twice doesn't make its packages duplicates of themselves
+        assert_eq!(packages.dropped.len(), 1);
+        assert_eq!(packages.dropped[0].dropped.path, underlay.join("my_msgs"));
+    }
+
+    /// Confirms the serialized size is computed for fixed length messages and encoded_len generated for others
+    #[test_log::test]
+    fn serialized_sizes_are_generated() {
+        let pkg = crate::Package {
+            name: "test_pkg".to_string(),
+            path: "./not_a_path".into(),
+            version: Some(crate::RosVersion::ROS1),
+        };
+        let parse = |name: &str, data: &str| {
+            let path = std::path::PathBuf::from(format!("./not_a_path/msg/{name}.msg"));
+            crate::parse_ros_message_file(data, name, &pkg, &path).unwrap()
+        };
+        let point = parse(
+            "Point",
+            "float64 x\nfloat64 y\nuint8[4] flags\ntime stamp\n",
+        );
+        let path = parse(
+            "Path",
+            "Point[2] ends\nPoint[] points\nstring name\nint16[] ids\n",
+        );
+        let (messages, _) = crate::resolve_dependency_graph(vec![point, path], vec![]).unwrap();
+        let find = |name: &str| {
+            messages
+                .iter()
+                .find(|msg| msg.get_full_name() == name)
+                .unwrap()
+                .clone()
+        };
+        assert_eq!(find("test_pkg/Point").get_serialized_size(), Some(28));
+        assert_eq!(find("test_pkg/Path").get_serialized_size(), None);
+
+        let point = crate::gen::generate_struct(find("test_pkg/Point"), None)
+            .unwrap()
+            .to_string();
+        assert!(point.contains(
+            "const SERIALIZED_SIZE : :: std :: option :: Option < usize > = :: std :: option :: Option :: Some (28usize) ;"
+        ), "{point}");
+        assert!(!point.contains("fn encoded_len"), "{point}");
+
+        let path = crate::gen::generate_struct(find("test_pkg/Path"), None)
+            .unwrap()
+            .to_string();
+        assert!(path.contains("const SERIALIZED_SIZE : :: std :: option :: Option < usize > = :: std :: option :: Option :: None ;"), "{path}");
+        assert!(path.contains("fn encoded_len (& self) -> :: std :: option :: Option < usize > { let len = self . r#ends . iter () . map (:: roslibrust :: RosMessageType :: encoded_len) . sum :: < :: std :: option :: Option < usize >> () ? + 4 + self . r#points . iter () . map (:: roslibrust :: RosMessageType :: encoded_len) . sum :: < :: std :: option :: Option < usize >> () ? + 4 + self . r#name . len () + 4 + self . r#ids . len () * 2usize ; :: std :: option :: Option :: Some (len) }"), "{path}");
+
+        let options = crate::CodegenOptions {
+            compact: true,
+            ..Default::default()
+        };
+        let path = crate::gen::generate_struct(find("test_pkg/Path"), Some(&options))
+            .unwrap()
+            .to_string();
+        assert!(
+            path.contains(
+                ":: std :: option :: Option :: None , | this | { let len = this . r#ends"
+            ),
+            "{path}"
+        );
+    }
+
+    /// Confirms std_msgs/Header converts to and from HeaderCompat for both ROS versions
+    #[test_log::test]
+    fn header_compat_conversions_are_generated() {
+        for (version, definition) in [
+            (
+                crate::RosVersion::ROS1,
+                "uint32 seq\ntime stamp\nstring frame_id\n",
+            ),
+            (
+                crate::RosVersion::ROS2,
+                "builtin_interfaces/Time stamp\nstring frame_id\n",
+            ),
+        ] {
+            let pkg = crate::Package {
+                name: "std_msgs".to_string(),
+                path: "./not_a_path".into(),
+                version: Some(version),
+            };
+            let path = std::path::PathBuf::from("./not_a_path/msg/Header.msg");
+            let header = crate::parse_ros_message_file(definition, "Header", &pkg, &path).unwrap();
+            let (messages, _) = crate::resolve_dependency_graph(vec![header], vec![]).unwrap();
+            let header = messages
+                .into_iter()
+                .find(|msg| msg.get_full_name() == "std_msgs/Header")
+                .unwrap();
+            let source = crate::gen::generate_struct(header, None)
+                .unwrap()
+                .to_string();
+            assert!(
+                source.contains("impl :: std :: convert :: From < :: roslibrust :: codegen :: HeaderCompat > for Header"),
+                "{source}"
+            );
+            assert!(
+                source.contains("impl :: std :: convert :: From < Header > for :: roslibrust :: codegen :: HeaderCompat"),
+                "{source}"
+            );
+            let (seq, stamp) = match version {
+                crate::RosVersion::ROS1 => ("seq : header . r#seq ,", "r#stamp : header . stamp ,"),
+                crate::RosVersion::ROS2 => (
+                    "seq : 0 ,",
+                    "r#stamp : { let (sec , nanosec) = header . stamp . to_sec_nanosec () ; builtin_interfaces :: Time { sec , nanosec } } ,",
+                ),
+            };
+            assert!(source.contains(seq), "{source}");
+            assert!(source.contains(stamp), "{source}");
+        }
+    }
+
+    /// Confirms compact codegen produces substantially less code
+    #[test_log::test]
+    fn compact_output_is_smaller() {
+        let paths = vec![
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/ros1_test_msgs").into(),
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../assets/ros1_common_interfaces/std_msgs"
+            )
+            .into(),
+        ];
+        let (full, _) = crate::find_and_generate_ros_messages_with_options(
+            paths.clone(),
+            &crate::CodegenOptions::default(),
+        )
+        .unwrap();
+        let options = crate::CodegenOptions {
+            compact: true,
+            ..Default::default()
+        };
+        let (compact, _) =
+            crate::find_and_generate_ros_messages_with_options(paths, &options).unwrap();
+        let (full, compact) = (full.to_string(), compact.to_string());
+        assert!(compact.contains("impl_ros_message !"));
+        assert!(compact.contains("impl_ros_service !"));
+        assert!(!compact.contains("impl :: roslibrust :: RosMessageType"));
+        // Mostly the ROS2 hashes, the DEFINITION strings are the same size in both
+        assert!(
+            compact.len() < full.len() * 9 / 10,
+            "{} vs {}",
+            compact.len(),
+            full.len()
+        );
+    }
+
+    /// Confirms the type visitor lists every generated message type when enabled
+    #[test_log::test]
+    fn generate_type_visitor() {
+        let paths = vec![
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/ros1_test_msgs").into(),
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../assets/ros1_common_interfaces/std_msgs"
+            )
+            .into(),
+        ];
+        let (without, _) = crate::find_and_generate_ros_messages_with_options(
+            paths.clone(),
+            &crate::CodegenOptions::default(),
+        )
+        .unwrap();
+        assert!(!without.to_string().contains("visit_message_types"));
+
+        let options = crate::CodegenOptions {
+            generate_type_visitor: true,
+            ..Default::default()
+        };
+        let (with, _) =
+            crate::find_and_generate_ros_messages_with_options(paths, &options).unwrap();
+        let with = with.to_string();
+        assert!(with.contains("pub fn visit_message_types"));
+        assert!(with.contains("visitor . visit :: < std_msgs :: Header > ()"));
+        assert!(with.contains("visitor . visit :: < test_msgs :: Float64Stamped > ()"));
+    }
+
+    /// Confirms plugins' items are emitted alongside the generated types
+    #[test_log::test]
+    fn plugins_add_items_per_message_and_service() {
+        use proc_macro2::TokenStream;
+        use quote::{format_ident, quote};
+
+        #[derive(Debug)]
+        struct Plugin;
+
+        impl crate::CodegenPlugin for Plugin {
+            fn generate_for_message(
+                &self,
+                msg: &crate::MessageFile,
+                _options: &crate::CodegenOptions,
+            ) -> Result<TokenStream, crate::Error> {
+                let name = format_ident!("{}", msg.get_short_name());
+                let count = msg.get_fields().len();
+                Ok(quote! { impl #name { pub const FIELD_COUNT: usize = #count; } })
+            }
+
+            fn generate_for_service(
+                &self,
+                srv: &crate::ServiceFile,
+                _options: &crate::CodegenOptions,
+            ) -> Result<TokenStream, crate::Error> {
+                if srv.get_short_name() == "AddTwoInts" {
+                    return Err(crate::Error::new("AddTwoInts is not supported"));
+                }
+                Ok(TokenStream::new())
+            }
+
+            fn generate_root(
+                &self,
+                messages: &[crate::MessageFile],
+                _services: &[crate::ServiceFile],
+                _options: &crate::CodegenOptions,
+            ) -> Result<TokenStream, crate::Error> {
+                let count = messages.len();
+                Ok(quote! { pub const MESSAGE_COUNT: usize = #count; })
+            }
+        }
+
+        let paths = vec![concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../assets/ros1_common_interfaces/std_msgs"
+        )
+        .into()];
+        let options = crate::CodegenOptions {
+            plugins: vec![std::sync::Arc::new(Plugin)],
+            ..Default::default()
+        };
+        let (source, _) =
+            crate::find_and_generate_ros_messages_with_options(paths, &options).unwrap();
+        let source = source.to_string();
+        assert!(source.contains("impl Header { pub const FIELD_COUNT : usize = 3usize ; }"));
+        assert!(source.contains("pub const MESSAGE_COUNT : usize"));
+
+        // Errors from plugins fail generation
+        let paths = vec![
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/ros1_test_msgs").into(),
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../assets/ros1_common_interfaces/std_msgs"
+            )
+            .into(),
+        ];
+        let err = crate::find_and_generate_ros_messages_with_options(paths, &options)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("AddTwoInts is not supported"), "{err}");
+    }
+
+    /// Confirms only the root types and their dependencies are generated when root_types is set
+    #[test_log::test]
+    fn generate_only_root_types() {
+        let paths = vec![
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/ros1_test_msgs").into(),
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../assets/ros1_common_interfaces/std_msgs"
+            )
+            .into(),
+        ];
+        let options = crate::CodegenOptions {
+            root_types: Some(vec![
+                "test_msgs/Float64Stamped".to_string(),
+                "test_msgs/AddTwoInts".to_string(),
+            ]),
+            ..Default::default()
+        };
+        let (source, paths) =
+            crate::find_and_generate_ros_messages_with_options(paths, &options).unwrap();
+        let source = source.to_string();
+        assert!(source.contains("pub struct Float64Stamped"));
+        assert!(source.contains("pub struct AddTwoInts"));
+        // Dependency of Float64Stamped
+        assert!(source.contains("pub struct Header"));
+        // Not referenced by either root
+        assert!(!source.contains("pub struct RoundTripArray"));
+        assert!(!source.contains("pub struct Int32 "));
+        assert!(paths.iter().all(|p| p.ends_with("Float64Stamped.msg")
+            || p.ends_with("AddTwoInts.srv")
+            || p.ends_with("Header.msg")));
+
+        // Unknown roots are an error rather than silently generating nothing
+        let options = crate::CodegenOptions {
+            root_types: Some(vec!["test_msgs/DoesNotExist".to_string()]),
+            ..Default::default()
+        };
+        let paths = vec![
+            concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/ros1_test_msgs").into(),
+            concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../assets/ros1_common_interfaces/std_msgs"
+            )
+            .into(),
+        ];
+        let err = crate::find_and_generate_ros_messages_with_options(paths, &options)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("test_msgs/DoesNotExist"), "{err}");
+    }
+
+    /// Confirms packages missing from the search paths are named in the error
+    #[test_log::test]
+    fn missing_dependency_packages_are_reported() {
+        let assets_path = concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/ros1_test_msgs");
+        let err =
+            crate::find_and_generate_ros_messages_without_ros_package_path(
+                vec![assets_path.into()],
+            )
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("no messages were found for the following packages"),
+            "{err}"
+        );
+        assert!(err.contains("\"std_msgs\""), "{err}");
+    }
+
     /// Confirms we don't panic on ros2_test_msgs parsing
     #[test_log::test]
     fn generate_ok_on_ros2_test_msgs() {
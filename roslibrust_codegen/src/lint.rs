@@ -0,0 +1,424 @@
+//! Checks ROS message definitions for common problems, intended for use in the CI of interface packages.
+//!
+//! ```no_run
+//! let diagnostics = roslibrust_codegen::lint::lint_messages(&["./my_interfaces".into()]).unwrap();
+//! for diagnostic in &diagnostics {
+//!     eprintln!("{diagnostic}");
+//! }
+//! assert!(diagnostics.is_empty());
+//! ```
+
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+
+use crate::parse::{is_intrinsic_type, ros1_primitive_size, ParsedMessageFile};
+use crate::utils::RosVersion;
+use crate::{ArrayType, Error, FieldInfo, MessageFile};
+
+/// The kind of problem a [LintDiagnostic] reports
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+pub enum LintKind {
+    /// The field uses a type that is deprecated or does not exist in the message's ROS version
+    DeprecatedType,
+    /// The field is a fixed size array longer than [LintOptions::max_fixed_array_length]
+    LargeFixedArray,
+    /// The field name does not follow the naming rules of the message's ROS version
+    FieldNaming,
+    /// The smallest possible serialization of the message exceeds [LintOptions::max_serialized_size]
+    SerializedSize,
+}
+
+/// How seriously a [LintDiagnostic] should be taken
+#[derive(
+    Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, serde::Serialize, serde::Deserialize,
+)]
+pub enum LintSeverity {
+    /// The definition works, but is likely to cause problems
+    Warning,
+    /// The definition will be rejected by other ROS tooling
+    Error,
+}
+
+/// A single problem found in a message definition
+#[derive(Clone, Debug, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct LintDiagnostic {
+    pub kind: LintKind,
+    pub severity: LintSeverity,
+    /// Full name of the message the problem was found in, e.g. "std_msgs/Header"
+    pub message_type: String,
+    /// Name of the field the problem was found on, if it is specific to one field
+    pub field: Option<String>,
+    /// Path of the file the message was parsed from
+    pub path: PathBuf,
+    /// Human readable description of the problem
+    pub message: String,
+}
+
+impl Display for LintDiagnostic {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}: {:?} {:?} in {}",
+            self.path.display(),
+            self.severity,
+            self.kind,
+            self.message_type
+        )?;
+        if let Some(field) = &self.field {
+            write!(f, ".{field}")?;
+        }
+        write!(f, ": {}", self.message)
+    }
+}
+
+/// Thresholds used by [lint_messages_with_options]
+#[derive(Clone, Debug)]
+pub struct LintOptions {
+    /// Fixed size arrays with more elements than this are reported as [LintKind::LargeFixedArray]
+    pub max_fixed_array_length: usize,
+    /// Messages whose smallest possible serialization is larger than this many bytes are reported as
+    /// [LintKind::SerializedSize]
+    pub max_serialized_size: usize,
+}
+
+impl Default for LintOptions {
+    fn default() -> Self {
+        Self {
+            max_fixed_array_length: 4096,
+            max_serialized_size: 1024 * 1024,
+        }
+    }
+}
+
+/// Estimated size of a message once serialized
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct SerializedSize {
+    /// Smallest number of bytes the message can serialize to, i.e. with all strings and sequences empty
+    pub min_bytes: usize,
+    /// True iff the message always serializes to exactly `min_bytes`
+    pub is_fixed: bool,
+}
+
+/// Estimates the serialized size of a message in the ROS1 wire format.
+///
+/// `messages` must contain every message type referenced by `msg`, as returned by
+/// [crate::resolve_dependency_graph]. Returns None if a referenced type is missing.
+/// The CDR encoding used by ROS2 adds alignment padding, so ROS2 messages may be somewhat larger.
+pub fn estimate_serialized_size(
+    msg: &MessageFile,
+    messages: &[MessageFile],
+) -> Option<SerializedSize> {
+    let graph = messages
+        .iter()
+        .map(|msg| (msg.get_full_name(), msg))
+        .collect::<BTreeMap<_, _>>();
+    size_of_message(&msg.parsed, &graph)
+}
+
+/// Searches `search_paths` for ROS packages and lints every message and service found, using the default
+/// [LintOptions].
+pub fn lint_messages(search_paths: &[PathBuf]) -> Result<Vec<LintDiagnostic>, Error> {
+    lint_messages_with_options(search_paths, &LintOptions::default())
+}
+
+/// Similar to [lint_messages], but with custom thresholds.
+pub fn lint_messages_with_options(
+    search_paths: &[PathBuf],
+    options: &LintOptions,
+) -> Result<Vec<LintDiagnostic>, Error> {
+    let (messages, services, _actions) = crate::find_and_parse_ros_messages(search_paths)?;
+    let (messages, services) = crate::resolve_dependency_graph(messages, services)?;
+    let mut all = messages;
+    for srv in services {
+        all.push(srv.request);
+        all.push(srv.response);
+    }
+    Ok(lint_message_files(&all, options))
+}
+
+/// Lints already resolved messages, e.g. those returned by [crate::resolve_dependency_graph].
+///
+/// Messages built into roslibrust (e.g. builtin_interfaces/Time) are used to estimate sizes but are not linted.
+pub fn lint_message_files(messages: &[MessageFile], options: &LintOptions) -> Vec<LintDiagnostic> {
+    let graph = messages
+        .iter()
+        .map(|msg| (msg.get_full_name(), msg))
+        .collect::<BTreeMap<_, _>>();
+
+    // Built in definitions are shipped with roslibrust, so there is nothing for the user to fix
+    let builtin = crate::ros2_builtin_interfaces::get_builtin_interfaces();
+
+    let mut diagnostics = vec![];
+    for msg in messages {
+        if builtin.contains_key(&msg.get_full_name()) {
+            continue;
+        }
+        let version = msg.parsed.version.unwrap_or(RosVersion::ROS1);
+        let diagnostic = |kind, severity, field: &FieldInfo, message: String| LintDiagnostic {
+            kind,
+            severity,
+            message_type: msg.get_full_name(),
+            field: Some(field.field_name.clone()),
+            path: msg.parsed.path.clone(),
+            message,
+        };
+
+        for field in msg.get_fields() {
+            if let Some(message) = deprecated_type(version, field) {
+                diagnostics.push(diagnostic(
+                    LintKind::DeprecatedType,
+                    LintSeverity::Warning,
+                    field,
+                    message,
+                ));
+            }
+            if let ArrayType::FixedLength(n) = field.field_type.array_info {
+                if n > options.max_fixed_array_length {
+                    diagnostics.push(diagnostic(
+                        LintKind::LargeFixedArray,
+                        LintSeverity::Warning,
+                        field,
+                        format!(
+                            "fixed size array of {n} elements is larger than {}, consider an unbounded array",
+                            options.max_fixed_array_length
+                        ),
+                    ));
+                }
+            }
+            if version == RosVersion::ROS2 && !is_ros2_field_name(&field.field_name) {
+                diagnostics.push(diagnostic(
+                    LintKind::FieldNaming,
+                    LintSeverity::Error,
+                    field,
+                    "ROS2 field names must be snake_case: lowercase letters, digits and single underscores, starting with a letter".to_string(),
+                ));
+            }
+        }
+
+        if let Some(size) = size_of_message(&msg.parsed, &graph) {
+            if size.min_bytes > options.max_serialized_size {
+                diagnostics.push(LintDiagnostic {
+                    kind: LintKind::SerializedSize,
+                    severity: LintSeverity::Warning,
+                    message_type: msg.get_full_name(),
+                    field: None,
+                    path: msg.parsed.path.clone(),
+                    message: format!(
+                        "serializes to at least {} bytes, more than {}",
+                        size.min_bytes, options.max_serialized_size
+                    ),
+                });
+            }
+        }
+    }
+    diagnostics
+}
+
+/// Returns a description of the problem if the field's type is deprecated in `version`
+fn deprecated_type(version: RosVersion, field: &FieldInfo) -> Option<String> {
+    // Types referencing another package can't be one of the primitives
+    if field.field_type.package_name.is_some() {
+        return None;
+    }
+    let message = match (version, field.field_type.field_type.as_str()) {
+        (RosVersion::ROS1, "byte") => "`byte` is a deprecated alias in ROS1, use `int8`",
+        (RosVersion::ROS1, "char") => "`char` is a deprecated alias in ROS1, use `uint8`",
+        (RosVersion::ROS2, "time") => {
+            "`time` is a ROS1 only type, use `builtin_interfaces/Time` in ROS2"
+        }
+        (RosVersion::ROS2, "duration") => {
+            "`duration` is a ROS1 only type, use `builtin_interfaces/Duration` in ROS2"
+        }
+        _ => return None,
+    };
+    Some(message.to_string())
+}
+
+/// Checks the field name against the pattern ROS2's rosidl enforces: `^[a-z][a-z0-9]*(_[a-z0-9]+)*$`
+fn is_ros2_field_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.split('_').all(|part| {
+            !part.is_empty()
+                && part
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit())
+        })
+}
+
+fn size_of_message(
+    parsed: &ParsedMessageFile,
+    graph: &BTreeMap<String, &MessageFile>,
+) -> Option<SerializedSize> {
+    let version = parsed.version.unwrap_or(RosVersion::ROS1);
+    let mut total = SerializedSize {
+        min_bytes: 0,
+        is_fixed: true,
+    };
+    for field in &parsed.fields {
+        let field_type = field.field_type.field_type.as_str();
+        let element = if let Some(bytes) = ros1_primitive_size(field_type) {
+            SerializedSize {
+                min_bytes: bytes,
+                is_fixed: true,
+            }
+        } else if is_intrinsic_type(version, field_type) {
+            // Strings are a length followed by their contents
+            SerializedSize {
+                min_bytes: 4,
+                is_fixed: false,
+            }
+        } else {
+            size_of_message(&graph.get(&field.get_full_type_name())?.parsed, graph)?
+        };
+        let field_size = match field.field_type.array_info {
+            ArrayType::NotArray => element,
+            // Fixed size arrays are written without a length
+            ArrayType::FixedLength(n) => SerializedSize {
+                min_bytes: element.min_bytes.saturating_mul(n),
+                is_fixed: element.is_fixed,
+            },
+            ArrayType::Bounded(_) | ArrayType::Unbounded => SerializedSize {
+                min_bytes: 4,
+                is_fixed: false,
+            },
+        };
+        total.min_bytes = total.min_bytes.saturating_add(field_size.min_bytes);
+        total.is_fixed &= field_size.is_fixed;
+    }
+    Some(total)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parse::parse_ros_message_file;
+    use crate::utils::Package;
+
+    fn parse(version: RosVersion, name: &str, data: &str) -> ParsedMessageFile {
+        let pkg = Package {
+            name: "test_pkg".to_string(),
+            path: "./not_a_path".into(),
+            version: Some(version),
+        };
+        let path = PathBuf::from(format!("./not_a_path/msg/{name}.msg"));
+        parse_ros_message_file(data, name, &pkg, &path).unwrap()
+    }
+
+    fn lint(messages: Vec<ParsedMessageFile>, options: &LintOptions) -> Vec<LintDiagnostic> {
+        let (messages, _) = crate::resolve_dependency_graph(messages, vec![]).unwrap();
+        lint_message_files(&messages, options)
+    }
+
+    #[test_log::test]
+    fn clean_messages_have_no_diagnostics() {
+        let msg = parse(
+            RosVersion::ROS2,
+            "Clean",
+            "builtin_interfaces/Time stamp\nfloat64[3] position_2d\nstring name\n",
+        );
+        assert_eq!(lint(vec![msg], &LintOptions::default()), vec![]);
+    }
+
+    #[test_log::test]
+    fn deprecated_types_are_reported_per_version() {
+        let ros1 = parse(RosVersion::ROS1, "Old", "byte b\nchar c\ntime t\n");
+        let ros2 = parse(
+            RosVersion::ROS2,
+            "New",
+            "byte b\nchar c\ntime t\nduration d\n",
+        );
+        let diagnostics = lint(vec![ros1, ros2], &LintOptions::default());
+        let flagged = diagnostics
+            .iter()
+            .filter(|d| d.kind == LintKind::DeprecatedType)
+            .map(|d| format!("{}.{}", d.message_type, d.field.as_deref().unwrap()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            flagged,
+            vec![
+                "test_pkg/New.t",
+                "test_pkg/New.d",
+                "test_pkg/Old.b",
+                "test_pkg/Old.c"
+            ]
+        );
+    }
+
+    #[test_log::test]
+    fn ros2_field_names_must_be_snake_case() {
+        assert!(is_ros2_field_name("position_2d"));
+        assert!(!is_ros2_field_name("Position"));
+        assert!(!is_ros2_field_name("camelCase"));
+        assert!(!is_ros2_field_name("double__underscore"));
+        assert!(!is_ros2_field_name("trailing_"));
+        assert!(!is_ros2_field_name("_leading"));
+        assert!(!is_ros2_field_name("2d"));
+
+        // ROS1 has no such rule
+        let ros1 = parse(RosVersion::ROS1, "Camel", "int32 camelCase\n");
+        let ros2 = parse(RosVersion::ROS2, "Camel2", "int32 camelCase\n");
+        let diagnostics = lint(vec![ros1, ros2], &LintOptions::default());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].kind, LintKind::FieldNaming);
+        assert_eq!(diagnostics[0].severity, LintSeverity::Error);
+        assert_eq!(diagnostics[0].message_type, "test_pkg/Camel2");
+        assert_eq!(
+            diagnostics[0].to_string(),
+            "./not_a_path/msg/Camel2.msg: Error FieldNaming in test_pkg/Camel2.camelCase: ROS2 field names must be snake_case: lowercase letters, digits and single underscores, starting with a letter"
+        );
+    }
+
+    #[test_log::test]
+    fn large_arrays_and_messages_are_reported() {
+        let inner = parse(RosVersion::ROS1, "Inner", "float64[100] values\n");
+        let outer = parse(RosVersion::ROS1, "Outer", "Inner[20] inners\nstring name\n");
+        let options = LintOptions {
+            max_fixed_array_length: 50,
+            max_serialized_size: 10_000,
+        };
+        let diagnostics = lint(vec![inner, outer], &options);
+        let kinds = diagnostics
+            .iter()
+            .map(|d| (d.message_type.as_str(), d.kind))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            kinds,
+            vec![
+                ("test_pkg/Inner", LintKind::LargeFixedArray),
+                ("test_pkg/Outer", LintKind::SerializedSize),
+            ]
+        );
+    }
+
+    #[test_log::test]
+    fn serialized_size_is_estimated() {
+        let inner = parse(RosVersion::ROS1, "Inner", "float64[3] values\ntime t\n");
+        let outer = parse(
+            RosVersion::ROS1,
+            "Outer",
+            "Inner[2] inners\nInner single\nstring name\nuint8[] data\n",
+        );
+        let (messages, _) = crate::resolve_dependency_graph(vec![inner, outer], vec![]).unwrap();
+        let find = |name: &str| {
+            messages
+                .iter()
+                .find(|msg| msg.get_full_name() == name)
+                .unwrap()
+        };
+        assert_eq!(
+            estimate_serialized_size(find("test_pkg/Inner"), &messages),
+            Some(SerializedSize {
+                min_bytes: 32,
+                is_fixed: true
+            })
+        );
+        assert_eq!(
+            estimate_serialized_size(find("test_pkg/Outer"), &messages),
+            Some(SerializedSize {
+                min_bytes: 3 * 32 + 4 + 4,
+                is_fixed: false
+            })
+        );
+    }
+}
@@ -4,7 +4,11 @@ use std::path::{Path, PathBuf};
 
 use super::parse_ros_message_file;
 
-#[derive(Clone, Debug)]
+/// Comment genaction places at the top of each message it generates from an action file
+const AUTOGEN: &str = "# ====== DO NOT MODIFY! AUTOGENERATED FROM AN ACTION DEFINITION ======\n";
+
+/// Describes all information for a single action file, including the messages generated from it
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ParsedActionFile {
     pub name: String,
     pub package: String,
@@ -21,6 +25,11 @@ pub struct ParsedActionFile {
     pub path: PathBuf,
 }
 
+/// Parses the contents of an action file and returns a struct containing the messages generated from it.
+/// * `data` -- Actual contents of the file
+/// * `name` -- Name of the file excluding the extension, e.g. 'Fibonacci'
+/// * `package` -- Name of the package the file was found within, required for understanding relative type paths
+/// * `path` -- Path to the action file
 pub fn parse_ros_action_file(
     data: &str,
     name: &str,
@@ -80,21 +89,11 @@ pub fn parse_ros_action_file(
             name: name.to_owned(),
             package: package.name.clone(),
             action_type: generate_action_msg(name, package, path)?,
-            goal_type: parse_ros_message_file(
-                &goal_str,
-                format!("{name}Goal").as_str(),
-                package,
-                path,
-            )?,
-            result_type: parse_ros_message_file(
-                &result_str,
-                format!("{name}Result").as_str(),
-                package,
-                path,
-            )?,
-            feedback_type: parse_ros_message_file(
+            goal_type: parse_action_part(&goal_str, &format!("{name}Goal"), package, path)?,
+            result_type: parse_action_part(&result_str, &format!("{name}Result"), package, path)?,
+            feedback_type: parse_action_part(
                 &feedback_str,
-                format!("{name}Feedback").as_str(),
+                &format!("{name}Feedback"),
                 package,
                 path,
             )?,
@@ -112,6 +111,32 @@ pub fn parse_ros_action_file(
     }
 }
 
+// genaction writes each part of the action file stripped of surrounding whitespace behind its header,
+// the source is set to match so full definitions are the same as those of other ROS1 clients
+fn parse_action_part(
+    data: &str,
+    name: &str,
+    package: &Package,
+    path: &Path,
+) -> Result<ParsedMessageFile, Error> {
+    // Parsed without the header, so it isn't picked up as a comment on the first field
+    let mut parsed = parse_ros_message_file(data, name, package, path)?;
+    parsed.source = format!("{AUTOGEN}{}", data.trim());
+    Ok(parsed)
+}
+
+// The messages wrapping the parts of an action are generated in full by genaction
+fn parse_generated_msg(
+    body: &str,
+    name: &str,
+    package: &Package,
+    path: &Path,
+) -> Result<ParsedMessageFile, Error> {
+    let mut parsed = parse_ros_message_file(body, name, package, path)?;
+    parsed.source = format!("{AUTOGEN}{body}");
+    Ok(parsed)
+}
+
 fn generate_action_msg(
     name: &str,
     package: &Package,
@@ -122,10 +147,10 @@ fn generate_action_msg(
 {name}ActionGoal action_goal
 {name}ActionResult action_result
 {name}ActionFeedback action_feedback
-    "#
+"#
     );
 
-    parse_ros_message_file(&source, format!("{name}Action").as_str(), package, path)
+    parse_generated_msg(&source, &format!("{name}Action"), package, path)
 }
 
 fn generate_action_goal_msg(
@@ -138,10 +163,10 @@ fn generate_action_goal_msg(
 Header header
 actionlib_msgs/GoalID goal_id
 {name}Goal goal
-    "#
+"#
     );
 
-    parse_ros_message_file(&source, format!("{name}ActionGoal").as_str(), package, path)
+    parse_generated_msg(&source, &format!("{name}ActionGoal"), package, path)
 }
 
 fn generate_action_result_msg(
@@ -154,15 +179,10 @@ fn generate_action_result_msg(
 Header header
 actionlib_msgs/GoalStatus status
 {name}Result result
-        "#
+"#
     );
 
-    parse_ros_message_file(
-        &source,
-        format!("{name}ActionResult").as_str(),
-        package,
-        path,
-    )
+    parse_generated_msg(&source, &format!("{name}ActionResult"), package, path)
 }
 
 fn generate_action_feedback_msg(
@@ -175,13 +195,8 @@ fn generate_action_feedback_msg(
 Header header
 actionlib_msgs/GoalStatus status
 {name}Feedback feedback
-        "#
+"#
     );
 
-    parse_ros_message_file(
-        &source,
-        format!("{name}ActionFeedback").as_str(),
-        package,
-        path,
-    )
+    parse_generated_msg(&source, &format!("{name}ActionFeedback"), package, path)
 }
@@ -67,6 +67,17 @@ pub fn is_intrinsic_type(version: RosVersion, ros_type: &str) -> bool {
     }
 }
 
+/// Size in bytes of a primitive type in the ROS1 wire format, None for strings and non-primitive types
+pub fn ros1_primitive_size(ros_type: &str) -> Option<usize> {
+    Some(match ros_type {
+        "bool" | "int8" | "uint8" | "byte" | "char" => 1,
+        "int16" | "uint16" => 2,
+        "int32" | "uint32" | "float32" => 4,
+        "int64" | "uint64" | "float64" | "time" | "duration" => 8,
+        _ => return None,
+    })
+}
+
 pub fn convert_ros_type_to_rust_type(version: RosVersion, ros_type: &str) -> Option<&'static str> {
     match version {
         RosVersion::ROS1 => ROS_TYPE_TO_RUST_TYPE_MAP.get(ros_type).copied(),
@@ -85,7 +96,8 @@ fn parse_field(line: &str, pkg: &Package, msg_name: &str) -> Result<FieldInfo, E
         "Did not find field_name on line: {line} while parsing {pkg_name}/{msg_name}"
     )))?;
 
-    let sep = line.find(' ').unwrap();
+    // Can't fail, split_whitespace found at least two tokens above
+    let sep = line.find([' ', '\t']).unwrap();
     // Determine if there is a default value for this field
     let default = if matches!(pkg.version, Some(RosVersion::ROS2)) {
         // For ros2 packages only, check if there is a default value
@@ -112,11 +124,12 @@ fn parse_field(line: &str, pkg: &Package, msg_name: &str) -> Result<FieldInfo, E
         field_type,
         field_name: field_name.to_string(),
         default,
+        comment: None,
     })
 }
 
 fn parse_constant_field(line: &str, pkg: &Package) -> Result<ConstantInfo, Error> {
-    let sep = line.find(' ').ok_or(
+    let sep = line.find([' ', '\t']).ok_or(
         Error::new(format!("Failed to find white space seperator ' ' while parsing constant information one line {line} for package {pkg:?}"))
     )?;
     let equal_after_sep = line[sep..].find('=').ok_or(
@@ -135,6 +148,7 @@ fn parse_constant_field(line: &str, pkg: &Package) -> Result<ConstantInfo, Error
         constant_type,
         constant_name,
         constant_value: constant_value.into(),
+        comment: None,
     })
 }
 
@@ -146,6 +160,12 @@ fn strip_comments(line: &str) -> &str {
     line
 }
 
+/// Returns the text of the comment on a line, if any, without the leading `#`s
+fn comment_text(line: &str) -> Option<&str> {
+    let token = line.find('#')?;
+    Some(line[token..].trim_start_matches('#').trim())
+}
+
 fn parse_field_type(
     type_str: &str,
     array_info: ArrayType,
@@ -299,4 +319,82 @@ mod test {
         let parsed = parse_type(line, &pkg).unwrap();
         assert_eq!(parsed.array_info, ArrayType::Unbounded);
     }
+
+    #[test_log::test]
+    fn parse_errors_report_file_and_line() {
+        let pkg = Package {
+            name: "test_pkg".to_string(),
+            path: "./not_a_path".into(),
+            version: Some(RosVersion::ROS1),
+        };
+        let data = "# A comment\nint32 x\nfloat64\n";
+        let err = super::parse_ros_message_file(data, "Bad", &pkg, "./not_a_path/Bad.msg".as_ref())
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.starts_with("./not_a_path/Bad.msg:3 (in test_pkg/Bad)"),
+            "{err}"
+        );
+
+        // Tabs are valid separators and should not panic
+        let parsed =
+            super::parse_ros_message_file("int32\tx", "Tab", &pkg, "./not_a_path/Tab.msg".as_ref())
+                .unwrap();
+        assert_eq!(parsed.fields[0].field_name, "x");
+    }
+
+    #[test_log::test]
+    fn comments_are_captured() {
+        let pkg = Package {
+            name: "test_pkg".to_string(),
+            path: "./not_a_path".into(),
+            version: Some(RosVersion::ROS1),
+        };
+        let data = "# Describes the message, not a field\n\n## The x coordinate\n# in meters\nfloat64 x\nfloat64 y # The y coordinate\nfloat64 z\nint32 MAX=5 # Largest value\n";
+        let parsed =
+            super::parse_ros_message_file(data, "Point", &pkg, "./not_a_path/Point.msg".as_ref())
+                .unwrap();
+        assert_eq!(
+            parsed.fields[0].comment.as_deref(),
+            Some("The x coordinate\nin meters")
+        );
+        assert_eq!(
+            parsed.fields[1].comment.as_deref(),
+            Some("The y coordinate")
+        );
+        assert_eq!(parsed.fields[2].comment, None);
+        assert_eq!(
+            parsed.constants[0].comment.as_deref(),
+            Some("Largest value")
+        );
+    }
+
+    #[test_log::test]
+    fn parsed_message_round_trips_through_json() {
+        let pkg = Package {
+            name: "test_pkg".to_string(),
+            path: "./not_a_path".into(),
+            version: Some(RosVersion::ROS2),
+        };
+        let data =
+            "std_msgs/Header header\nstring<=10 name \"robot\"\nfloat64[3] position\nint32 MAX=5\n";
+        let parsed =
+            super::parse_ros_message_file(data, "Pose", &pkg, "./not_a_path/Pose.msg".as_ref())
+                .unwrap();
+
+        let json = serde_json::to_value(&parsed).unwrap();
+        assert_eq!(json["package"], "test_pkg");
+        assert_eq!(json["version"], "ROS2");
+        assert_eq!(json["fields"][0]["field_type"]["package_name"], "std_msgs");
+        assert_eq!(json["fields"][1]["field_type"]["string_capacity"], 10);
+        assert_eq!(json["fields"][1]["default"], "\"robot\"");
+        assert_eq!(
+            json["fields"][2]["field_type"]["array_info"],
+            serde_json::json!({ "FixedLength": 3 })
+        );
+        assert_eq!(json["constants"][0]["constant_value"], "5");
+
+        let round_tripped: super::ParsedMessageFile = serde_json::from_value(json).unwrap();
+        assert_eq!(round_tripped, parsed);
+    }
 }
@@ -1,11 +1,15 @@
-use crate::parse::{parse_constant_field, parse_field, strip_comments};
+use crate::parse::{comment_text, parse_constant_field, parse_field, strip_comments};
 use crate::Error;
 use crate::{ConstantInfo, FieldInfo, Package, RosVersion};
 use std::path::{Path, PathBuf};
 
 /// Describes all information for a single message file available in the file without other context
 /// This is different from MessageFile which contains resolved information from dependencies
-#[derive(Clone, PartialEq, Debug)]
+///
+/// This is the intermediate representation produced by the parser. It implements [serde::Serialize] and
+/// [serde::Deserialize] so that external tools can dump it (e.g. to JSON) and build their own generators or
+/// linters on top of it.
+#[derive(Clone, PartialEq, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ParsedMessageFile {
     pub name: String,
     pub package: String,
@@ -67,27 +71,48 @@ pub fn parse_ros_message_file(
 ) -> Result<ParsedMessageFile, Error> {
     let mut fields = vec![];
     let mut constants = vec![];
+    // Comment lines seen since the last field, constant, or blank line
+    let mut leading_comment: Vec<&str> = vec![];
 
-    for line in data.lines() {
-        let line = strip_comments(line).trim();
+    for (line_number, raw_line) in data.lines().enumerate() {
+        let line = strip_comments(raw_line).trim();
+        let comment = comment_text(raw_line);
         if line.is_empty() {
-            // Comment only line skip
+            // Comment only lines document the field below them, a blank line ends the comment
+            match comment {
+                Some(comment) => leading_comment.push(comment),
+                None => leading_comment.clear(),
+            }
             continue;
         }
+        let comment = join_comment(leading_comment.drain(..).chain(comment));
+        // Prefix errors with file and line so they can be found from the compiler output of the codegen macro
+        let with_location = |e: Error| {
+            Error::new(format!(
+                "{}:{} (in {}/{name}): {e}",
+                path.display(),
+                line_number + 1,
+                package.name
+            ))
+        };
         // Determine if we're looking at a constant or a field
-        let sep = line.find(' ').ok_or(
-            Error::new(
-                format!("Found an invalid ros field line, no space delinting type from name: {line} in {}\n{data}",
-                path.display())
-            )
-        )?;
+        let sep = line
+            .find([' ', '\t'])
+            .ok_or(Error::new(format!(
+                "Found an invalid ros field line, no space delimiting type from name: {line}"
+            )))
+            .map_err(with_location)?;
         let equal_after_sep = line[sep..].find('=');
         if equal_after_sep.is_some() {
             // Since we found an equal sign after a space, this must be a constant
-            constants.push(parse_constant_field(line, package)?)
+            let mut constant = parse_constant_field(line, package).map_err(with_location)?;
+            constant.comment = comment;
+            constants.push(constant);
         } else {
             // Is regular field
-            fields.push(parse_field(line, package, name)?);
+            let mut field = parse_field(line, package, name).map_err(with_location)?;
+            field.comment = comment;
+            fields.push(field);
         }
     }
     Ok(ParsedMessageFile {
@@ -100,3 +125,11 @@ pub fn parse_ros_message_file(
         path: path.to_owned(),
     })
 }
+
+// Joins comment lines into a single comment, dropping empty lines at the start and end
+fn join_comment<'a>(lines: impl Iterator<Item = &'a str>) -> Option<String> {
+    let lines: Vec<&str> = lines.collect();
+    let start = lines.iter().position(|l| !l.is_empty())?;
+    let end = lines.iter().rposition(|l| !l.is_empty())?;
+    Some(lines[start..=end].join("\n"))
+}
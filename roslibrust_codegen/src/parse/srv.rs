@@ -5,11 +5,11 @@ use crate::{
 use std::path::{Path, PathBuf};
 
 /// Describes all information for a single service file
-#[derive(Clone, Debug)]
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
 pub struct ParsedServiceFile {
     pub name: String,
     pub package: String,
-    // The names of these types will be auto generated as {name}Request and {name}Response
+    /// The names of these types will be auto generated as {name}Request and {name}Response
     pub request_type: ParsedMessageFile,
     pub response_type: ParsedMessageFile,
     /// The contents of the service file this instance was parsed from
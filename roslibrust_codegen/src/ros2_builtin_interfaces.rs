@@ -71,10 +71,11 @@ uint32 nanosec
 
 pub fn get_builtin_interfaces() -> BTreeMap<String, MessageFile> {
     let mut map = BTreeMap::new();
+    // The constants above start with a newline only to keep them readable, it isn't part of the message files
 
     // Time
     let parsed = parse_ros_message_file(
-        TIME_MSG,
+        TIME_MSG.trim_start(),
         "Time",
         &crate::utils::Package {
             name: "builtin_interfaces".to_string(),
@@ -90,7 +91,7 @@ pub fn get_builtin_interfaces() -> BTreeMap<String, MessageFile> {
 
     // Duration
     let parsed = parse_ros_message_file(
-        DURATION_MSG,
+        DURATION_MSG.trim_start(),
         "Duration",
         &crate::utils::Package {
             name: "builtin_interfaces".to_string(),
@@ -106,7 +107,7 @@ pub fn get_builtin_interfaces() -> BTreeMap<String, MessageFile> {
 
     // ServiceEventInfo
     let parsed = parse_ros_message_file(
-        SERVICE_EVENT_INFO,
+        SERVICE_EVENT_INFO.trim_start(),
         "ServiceEventInfo",
         &crate::utils::Package {
             name: "service_msgs".to_string(),
@@ -126,6 +126,7 @@ pub fn calculate_ros2_srv_hash(
                     string_capacity: None,
                 },
                 default: None,
+                comment: None,
             },
             // Every event has a request field with the request type
             FieldInfo {
@@ -139,6 +140,7 @@ pub fn calculate_ros2_srv_hash(
                     string_capacity: None,
                 },
                 default: None,
+                comment: None,
             },
             // Every event has a response field with the response type
             FieldInfo {
@@ -152,6 +154,7 @@ pub fn calculate_ros2_srv_hash(
                     string_capacity: None,
                 },
                 default: None,
+                comment: None,
             },
         ],
     };
@@ -174,6 +177,7 @@ pub fn calculate_ros2_srv_hash(
                     string_capacity: None,
                 },
                 default: None,
+                comment: None,
             },
             FieldInfo {
                 field_name: "response_message".to_string(),
@@ -185,6 +189,7 @@ pub fn calculate_ros2_srv_hash(
                     string_capacity: None,
                 },
                 default: None,
+                comment: None,
             },
             FieldInfo {
                 field_name: "event_message".to_string(),
@@ -196,6 +201,7 @@ pub fn calculate_ros2_srv_hash(
                     string_capacity: None,
                 },
                 default: None,
+                comment: None,
             },
         ],
     };
@@ -218,6 +224,7 @@ pub fn calculate_ros2_srv_hash(
             md5sum: "".to_string(),
             definition: "".to_string(),
             is_fixed_encoding_length: true,
+            serialized_size: None,
         },
     );
     graph_copy.insert(
@@ -229,6 +236,7 @@ pub fn calculate_ros2_srv_hash(
             md5sum: "".to_string(),
             definition: "".to_string(),
             is_fixed_encoding_length: true,
+            serialized_size: None,
         },
     );
     graph_copy.insert(
@@ -240,6 +248,7 @@ pub fn calculate_ros2_srv_hash(
             md5sum: "".to_string(),
             definition: "".to_string(),
             is_fixed_encoding_length: true,
+            serialized_size: None,
         },
     );
 
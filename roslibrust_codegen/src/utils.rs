@@ -1,9 +1,11 @@
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
 use std::io;
 use std::path::{Path, PathBuf};
 
 use log::debug;
 
+use crate::{bail, Error};
+
 #[derive(Clone, Debug)]
 pub struct Package {
     pub name: String,
@@ -18,7 +20,7 @@ impl PartialEq for Package {
     }
 }
 
-#[derive(Clone, Debug, PartialEq, Copy)]
+#[derive(Clone, Debug, PartialEq, Copy, serde::Serialize, serde::Deserialize)]
 pub enum RosVersion {
     ROS1,
     ROS2,
@@ -105,19 +107,17 @@ pub fn packages_from_path(mut path: PathBuf, depth: u16) -> io::Result<Vec<Packa
             } else {
                 // No file here, we'll have to go deeper
                 assert!(path.pop());
-                for subdir in std::fs::read_dir(path)
+                // Sorted as read_dir order is platform and filesystem dependent
+                let mut subdirs = std::fs::read_dir(path)
                     .unwrap()
-                    .filter(|entry| match entry {
-                        Ok(entry) => entry.path().as_path().is_dir(),
-                        Err(_err) => false,
-                    })
-                    .map(|entry| entry.unwrap())
-                {
-                    found_packages = [
-                        found_packages,
-                        packages_from_path(subdir.path(), depth - 1)?,
-                    ]
-                    .concat()
+                    .filter_map(|entry| entry.ok())
+                    .map(|entry| entry.path())
+                    .filter(|path| path.is_dir())
+                    .collect::<Vec<_>>();
+                subdirs.sort();
+                for subdir in subdirs {
+                    found_packages =
+                        [found_packages, packages_from_path(subdir, depth - 1)?].concat()
                 }
             }
         }
@@ -138,7 +138,10 @@ pub fn get_message_files(pkg: &Package) -> io::Result<Vec<PathBuf>> {
 
 fn message_files_from_path(path: &Path, ext: &str) -> io::Result<Vec<PathBuf>> {
     let mut msg_files = vec![];
-    for entry in (std::fs::read_dir(path)?).flatten() {
+    // Sorted as read_dir order is platform and filesystem dependent
+    let mut entries = (std::fs::read_dir(path)?).flatten().collect::<Vec<_>>();
+    entries.sort_by_key(|entry| entry.path());
+    for entry in entries {
         if entry.path().as_path().is_dir() {
             msg_files = [
                 msg_files,
@@ -157,7 +160,86 @@ fn message_files_from_path(path: &Path, ext: &str) -> io::Result<Vec<PathBuf>> {
     Ok(msg_files)
 }
 
+/// How codegen chooses between packages with the same name (and ROS version) found in multiple search paths
+///
+/// The default follows the overlay rules of catkin and colcon workspaces, where a package in an earlier search path
+/// (e.g. `ROS_PACKAGE_PATH=/overlay/src:/opt/ros/noetic/share`) shadows the same package in any later one.
+/// Shadowing is per package, packages only present in a later search path are still used.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum DuplicatePackagePolicy {
+    /// Fail with an error naming every path the package was found at
+    Error,
+    /// Use the package found in the earliest search path (default)
+    #[default]
+    PreferFirst,
+    /// Use the package with the highest `<version>` in its package.xml
+    /// Packages whose version can't be read lose to any that can, ties go to the package found first.
+    PreferHighestVersion,
+}
+
+/// A package which was left out of codegen in favor of another package with the same name
+#[derive(Clone, Debug)]
+pub struct DroppedPackage {
+    /// The package that is used
+    pub kept: Package,
+    /// The package that was left out
+    pub dropped: Package,
+}
+
+impl std::fmt::Display for DroppedPackage {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Duplicate package {} found, using {} and ignoring {}",
+            self.kept.name,
+            self.kept.path.display(),
+            self.dropped.path.display()
+        )
+    }
+}
+
+/// The result of [deduplicate_packages_with_policy]
+#[derive(Clone, Debug)]
+pub struct DeduplicatedPackages {
+    /// The packages to generate from, in the order they were found
+    pub packages: Vec<Package>,
+    /// Every duplicate which was left out, and the package used in its place
+    pub dropped: Vec<DroppedPackage>,
+}
+
+impl DeduplicatedPackages {
+    /// Returns the package with the given name, if the same name is used by a ROS1 and a ROS2 package
+    /// the first one found is returned
+    pub fn get(&self, name: &str) -> Option<&Package> {
+        self.packages.iter().find(|package| package.name == name)
+    }
+
+    /// Returns the directory each package is used from, keyed by package name
+    pub fn package_paths(&self) -> BTreeMap<&str, &Path> {
+        let mut paths = BTreeMap::new();
+        for package in &self.packages {
+            paths
+                .entry(package.name.as_str())
+                .or_insert(package.path.as_path());
+        }
+        paths
+    }
+}
+
+/// Removes duplicate packages, keeping the first one found
 pub fn deduplicate_packages(packages: Vec<Package>) -> Vec<Package> {
+    match deduplicate_packages_with_policy(packages, DuplicatePackagePolicy::PreferFirst) {
+        Ok(deduplicated) => deduplicated.packages,
+        Err(_) => unreachable!("PreferFirst never fails"),
+    }
+}
+
+/// Removes packages with the same name and ROS version, choosing which one to keep according to `policy`
+/// Every duplicate left out is logged as a warning and returned so it can be reported.
+pub fn deduplicate_packages_with_policy(
+    packages: Vec<Package>,
+    policy: DuplicatePackagePolicy,
+) -> Result<DeduplicatedPackages, Error> {
     fn package_name_fmt(pkg: &Package) -> String {
         format!(
             "{}_{}",
@@ -170,29 +252,90 @@ pub fn deduplicate_packages(packages: Vec<Package>) -> Vec<Package> {
         )
     }
 
-    let mut package_map: HashMap<String, Package> = HashMap::new();
+    // Packages are kept in the order they were found so codegen output doesn't depend on HashMap iteration order
+    let mut package_map: HashMap<String, usize> = HashMap::new();
+    let mut deduplicated: Vec<Package> = vec![];
+    let mut dropped = vec![];
     for package in packages {
-        if let Some(duplicate) = package_map.get(package.name.as_str()) {
-            if &package == duplicate {
-                log::warn!(
-                    "Duplicate package found: {}. Discovered at paths: ({}, {})",
+        let key = package_name_fmt(&package);
+        let Some(&index) = package_map.get(&key) else {
+            package_map.insert(key, deduplicated.len());
+            deduplicated.push(package);
+            continue;
+        };
+        let duplicate = &mut deduplicated[index];
+        if duplicate.path == package.path {
+            // The same package reached through overlapping search paths isn't a duplicate
+            debug!(
+                "Package {} found more than once at {}",
+                package.name,
+                package.path.display()
+            );
+            continue;
+        }
+        match policy {
+            DuplicatePackagePolicy::Error => {
+                bail!(
+                    "Duplicate package {} found at paths: ({}, {}). Remove one of them from the search paths \
+                     or set a different CodegenOptions::duplicate_package_policy",
                     package.name,
                     duplicate.path.display(),
                     package.path.display()
                 );
-                log::warn!(
-                    "Proceeding with the package found at the first path: {}",
-                    duplicate.path.display()
-                );
-            } else {
-                package_map.insert(package_name_fmt(&package), package);
             }
-        } else {
-            package_map.insert(package_name_fmt(&package), package);
+            DuplicatePackagePolicy::PreferHighestVersion
+                if read_package_version(&package.path) > read_package_version(&duplicate.path) =>
+            {
+                let replaced = std::mem::replace(duplicate, package);
+                // Duplicates dropped in favor of the replaced package are now dropped in favor of this one
+                for earlier in dropped
+                    .iter_mut()
+                    .filter(|earlier: &&mut DroppedPackage| earlier.kept.path == replaced.path)
+                {
+                    earlier.kept = duplicate.clone();
+                }
+                dropped.push(DroppedPackage {
+                    kept: duplicate.clone(),
+                    dropped: replaced,
+                });
+            }
+            DuplicatePackagePolicy::PreferFirst | DuplicatePackagePolicy::PreferHighestVersion => {
+                dropped.push(DroppedPackage {
+                    kept: duplicate.clone(),
+                    dropped: package,
+                })
+            }
         }
     }
+    for package in &dropped {
+        log::warn!("{package}");
+    }
+
+    Ok(DeduplicatedPackages {
+        packages: deduplicated,
+        dropped,
+    })
+}
+
+/// Reads the `<version>` of the package.xml in `package_path` as (major, minor, patch)
+fn read_package_version(package_path: &Path) -> Option<(u64, u64, u64)> {
+    use xml::reader::{EventReader, XmlEvent};
+    const VERSION_TAG: &str = "version";
 
-    package_map.into_values().collect()
+    let file = std::fs::File::open(package_path.join(PACKAGE_FILE_NAME)).ok()?;
+    let mut in_version = false;
+    for e in EventReader::new(io::BufReader::new(file)) {
+        match e.ok()? {
+            XmlEvent::StartElement { name, .. } => in_version = name.local_name == VERSION_TAG,
+            XmlEvent::Characters(data) if in_version => {
+                let mut parts = data.trim().split('.').map(|part| part.parse::<u64>().ok());
+                return Some((parts.next()??, parts.next()??, parts.next()??));
+            }
+            XmlEvent::EndElement { .. } => in_version = false,
+            _ => {}
+        }
+    }
+    None
 }
 
 /// Parses a ROS package.xml file, which may be in any of the 3 supported formats,
@@ -308,5 +451,82 @@ mod test {
 
         let deduplicated = utils::deduplicate_packages(packages);
         assert_eq!(deduplicated.len(), 3);
+        // The first package found is kept and the order is preserved
+        assert_eq!(
+            deduplicated[0].path,
+            std::path::PathBuf::from("/opt/ros/noetic/share/diagnostic_msgs")
+        );
+        assert_eq!(deduplicated[1].name, "std_msgs");
+        assert_eq!(
+            deduplicated[2].path,
+            std::path::PathBuf::from("/ros2/std_msgs")
+        );
+    }
+
+    #[test]
+    fn duplicate_package_policies() {
+        let root = std::env::temp_dir().join("roslibrust_duplicate_package_policies");
+        let make_package = |dir: &str, version: &str| {
+            let path = root.join(dir);
+            std::fs::create_dir_all(&path).unwrap();
+            std::fs::write(
+                path.join("package.xml"),
+                format!(
+                    "<package><name>my_msgs</name><version>{version}</version>\
+                     <buildtool_depend>catkin</buildtool_depend></package>"
+                ),
+            )
+            .unwrap();
+            utils::Package {
+                name: "my_msgs".into(),
+                path,
+                version: Some(utils::RosVersion::ROS1),
+            }
+        };
+        let packages = vec![
+            make_package("old", "1.9.0"),
+            make_package("new", "1.10.0"),
+            make_package("unreadable", "not a version"),
+        ];
+
+        let first = utils::deduplicate_packages_with_policy(
+            packages.clone(),
+            utils::DuplicatePackagePolicy::PreferFirst,
+        )
+        .unwrap();
+        assert_eq!(first.packages.len(), 1);
+        assert_eq!(first.packages[0].path, root.join("old"));
+        assert_eq!(first.dropped.len(), 2);
+        assert!(first
+            .dropped
+            .iter()
+            .all(|d| d.kept.path == root.join("old")));
+
+        let highest = utils::deduplicate_packages_with_policy(
+            packages.clone(),
+            utils::DuplicatePackagePolicy::PreferHighestVersion,
+        )
+        .unwrap();
+        assert_eq!(highest.packages.len(), 1);
+        assert_eq!(highest.packages[0].path, root.join("new"));
+        let dropped = highest
+            .dropped
+            .iter()
+            .map(|d| (d.kept.path.clone(), d.dropped.path.clone()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            dropped,
+            vec![
+                (root.join("new"), root.join("old")),
+                (root.join("new"), root.join("unreadable"))
+            ]
+        );
+
+        let err =
+            utils::deduplicate_packages_with_policy(packages, utils::DuplicatePackagePolicy::Error)
+                .unwrap_err()
+                .to_string();
+        assert!(err.contains("my_msgs"), "{err}");
+        assert!(err.contains("old") && err.contains("new"), "{err}");
     }
 }
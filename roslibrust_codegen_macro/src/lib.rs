@@ -2,8 +2,11 @@ use proc_macro::TokenStream;
 use syn::parse::{Parse, ParseStream};
 use syn::{parse_macro_input, Token};
 
+mod path_expansion;
+
 struct RosLibRustMessagePaths {
-    paths: Vec<std::path::PathBuf>,
+    // Literals are kept so errors can be reported against the path that caused them
+    paths: Vec<syn::LitStr>,
 }
 
 /// Parses a comma-separated list of str literals specifying paths.
@@ -11,36 +14,91 @@ impl Parse for RosLibRustMessagePaths {
     fn parse(input: ParseStream) -> syn::Result<Self> {
         let mut paths = vec![];
         while let Ok(path) = input.parse::<syn::LitStr>() {
-            paths.push(path.value().into());
+            paths.push(path);
             if input.parse::<Token![,]>().is_ok() {
                 continue;
             } else {
                 break;
             }
         }
+        if !input.is_empty() {
+            return Err(input.error("expected a comma-separated list of string literal paths"));
+        }
         Ok(Self { paths })
     }
 }
 
+/// Signature shared by the codegen entry points wrapped by [generate]
+type CodegenFn = fn(
+    Vec<std::path::PathBuf>,
+) -> Result<(proc_macro2::TokenStream, Vec<std::path::PathBuf>), String>;
+
+/// Runs codegen over the given paths, converting any error or panic into a `compile_error!`.
+///
+/// The error is spanned to the path literal it concerns when it can be identified, e.g. the search path
+/// containing a `.msg` file which failed to parse, otherwise it points at the macro invocation.
+fn generate(paths: Vec<syn::LitStr>, codegen: CodegenFn) -> TokenStream {
+    let mut path_bufs = Vec::with_capacity(paths.len());
+    for path in &paths {
+        match path_expansion::expand_path(&path.value()) {
+            Ok(path_buf) => path_bufs.push(path_buf),
+            Err(e) => return syn::Error::new(path.span(), e).to_compile_error().into(),
+        }
+    }
+    let expanded_paths = path_bufs.clone();
+    let result = std::panic::catch_unwind(|| codegen(path_bufs)).unwrap_or_else(|panic| {
+        let reason = panic
+            .downcast_ref::<String>()
+            .map(String::as_str)
+            .or_else(|| panic.downcast_ref::<&str>().copied())
+            .unwrap_or("unknown cause");
+        Err(format!("roslibrust codegen panicked: {reason}"))
+    });
+    match result {
+        // Note: there is not currently a way for proc_macros to indicate that they need to be re-generated
+        // We discard the "dependent_paths" part of the response here...
+        Ok((source, _dependent_paths)) => source.into(),
+        Err(error_msg) => {
+            let span = paths
+                .iter()
+                .zip(&expanded_paths)
+                .find(|(_, expanded)| {
+                    error_mentions_path(&error_msg, &expanded.display().to_string())
+                })
+                .map(|(path, _)| path.span())
+                .unwrap_or_else(proc_macro2::Span::call_site);
+            syn::Error::new(span, error_msg).to_compile_error().into()
+        }
+    }
+}
+
+// Errors refer to files by their canonicalized paths, so also check for that form of the path
+fn error_mentions_path(error_msg: &str, path: &str) -> bool {
+    let canonical = std::fs::canonicalize(path)
+        .ok()
+        .map(|p| p.display().to_string());
+    error_msg.contains(path) || canonical.is_some_and(|p| error_msg.contains(&p))
+}
+
 /// Given a list of paths, generates struct definitions and trait impls for any
 /// ros messages found within those paths.
-/// Paths are relative to where rustc is being invoked from your mileage may vary.
+/// Paths may reference environment variables as `$VAR` or `${VAR}`, as well as `$CARGO_WORKSPACE_ROOT`
+/// for the root of the invoking crate's workspace e.g. `"${ROS_WS}/src"` or `"$CARGO_WORKSPACE_ROOT/msgs"`.
+/// Relative paths are resolved against the directory rustc is invoked from, falling back to the
+/// invoking crate's directory, so `"$CARGO_WORKSPACE_ROOT/..."` or `"$CARGO_MANIFEST_DIR/..."` are more robust.
 ///
 /// In addition to provided paths, this will search paths found in the environment
 /// variable ROS_PACKAGE_PATH.
+///
+/// Problems found while generating, such as a malformed `.msg` file or a dependency on a package that
+/// could not be found, are reported as compile errors naming the offending file and line or package.
 #[proc_macro]
 pub fn find_and_generate_ros_messages(input_stream: TokenStream) -> TokenStream {
-    // Note: there is not currently a way for proc_macros to indicate that they need to be re-generated
-    // We discard the "dependent_paths" part of the response here...
     let RosLibRustMessagePaths { paths } =
         parse_macro_input!(input_stream as RosLibRustMessagePaths);
-    match roslibrust_codegen::find_and_generate_ros_messages(paths) {
-        Ok((source, _dependent_paths)) => source.into(),
-        Err(e) => {
-            let error_msg = e.to_string();
-            quote::quote!(compile_error!(#error_msg);).into()
-        }
-    }
+    generate(paths, |paths| {
+        roslibrust_codegen::find_and_generate_ros_messages(paths).map_err(|e| e.to_string())
+    })
 }
 
 /// Similar to `find_and_generate_ros_messages`, but does not search the
@@ -51,13 +109,8 @@ pub fn find_and_generate_ros_messages_without_ros_package_path(
 ) -> TokenStream {
     let RosLibRustMessagePaths { paths } =
         parse_macro_input!(input_stream as RosLibRustMessagePaths);
-    match roslibrust_codegen::find_and_generate_ros_messages_without_ros_package_path(paths) {
-        // Note: there is not currently a way for proc_macros to indicate that they need to be re-generated
-        // We discard the "dependent_paths" part of the response here...
-        Ok((source, _dependent_paths)) => source.into(),
-        Err(e) => {
-            let error_msg = e.to_string();
-            quote::quote!( compile_error!(#error_msg); ).into()
-        }
-    }
+    generate(paths, |paths| {
+        roslibrust_codegen::find_and_generate_ros_messages_without_ros_package_path(paths)
+            .map_err(|e| e.to_string())
+    })
 }
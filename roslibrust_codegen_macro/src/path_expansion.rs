@@ -0,0 +1,136 @@
+use std::path::{Path, PathBuf};
+
+/// Variable which expands to the root of the cargo workspace containing the crate invoking the macro
+const WORKSPACE_ROOT_VAR: &str = "CARGO_WORKSPACE_ROOT";
+
+/// Expands a path given to the codegen macros into the path to search.
+///
+/// `$VAR` and `${VAR}` are replaced with the value of the environment variable `VAR`, with the
+/// addition of `$CARGO_WORKSPACE_ROOT`, which is the directory of the nearest `Cargo.toml` above the invoking
+/// crate that declares a `[workspace]` (or the crate itself if it isn't part of a workspace).
+/// `$$` produces a literal `$`.
+///
+/// Relative paths are resolved against the directory rustc is invoked from if they exist there, as they
+/// historically have been, otherwise against the invoking crate's directory. Cargo invokes rustc from the
+/// workspace root for workspace members, but from the crate's own directory for crates from a registry.
+pub(crate) fn expand_path(path: &str) -> Result<PathBuf, String> {
+    expand_path_with(path, &|var| std::env::var(var).ok())
+}
+
+fn expand_path_with(
+    path: &str,
+    lookup: &dyn Fn(&str) -> Option<String>,
+) -> Result<PathBuf, String> {
+    let expanded = PathBuf::from(expand_vars(path, lookup)?);
+    if expanded.is_absolute() || expanded.exists() {
+        return Ok(expanded);
+    }
+    match lookup("CARGO_MANIFEST_DIR") {
+        Some(manifest_dir) if Path::new(&manifest_dir).join(&expanded).exists() => {
+            Ok(Path::new(&manifest_dir).join(expanded))
+        }
+        // Left as is, so codegen reports the path it couldn't find as written
+        _ => Ok(expanded),
+    }
+}
+
+fn expand_vars(path: &str, lookup: &dyn Fn(&str) -> Option<String>) -> Result<String, String> {
+    let mut expanded = String::with_capacity(path.len());
+    let mut rest = path;
+    while let Some(start) = rest.find('$') {
+        expanded.push_str(&rest[..start]);
+        rest = &rest[start + 1..];
+        let (name, remainder) = if let Some(braced) = rest.strip_prefix('{') {
+            let end = braced
+                .find('}')
+                .ok_or_else(|| format!("Unterminated '${{' in path {path:?}"))?;
+            (&braced[..end], &braced[end + 1..])
+        } else if let Some(remainder) = rest.strip_prefix('$') {
+            expanded.push('$');
+            rest = remainder;
+            continue;
+        } else {
+            let end = rest
+                .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_'))
+                .unwrap_or(rest.len());
+            (&rest[..end], &rest[end..])
+        };
+        if name.is_empty() {
+            return Err(format!(
+                "Expected a variable name after '$' in path {path:?}"
+            ));
+        }
+        let value = if name == WORKSPACE_ROOT_VAR {
+            lookup(WORKSPACE_ROOT_VAR).or_else(|| workspace_root(lookup))
+        } else {
+            lookup(name)
+        };
+        let value = value.ok_or_else(|| {
+            format!("Environment variable {name} used in path {path:?} is not set")
+        })?;
+        expanded.push_str(&value);
+        rest = remainder;
+    }
+    expanded.push_str(rest);
+    Ok(expanded)
+}
+
+fn workspace_root(lookup: &dyn Fn(&str) -> Option<String>) -> Option<String> {
+    let manifest_dir = PathBuf::from(lookup("CARGO_MANIFEST_DIR")?);
+    let root = manifest_dir
+        .ancestors()
+        .find(|dir| {
+            std::fs::read_to_string(dir.join("Cargo.toml"))
+                .is_ok_and(|manifest| manifest.lines().any(|l| l.trim() == "[workspace]"))
+        })
+        .unwrap_or(&manifest_dir);
+    Some(root.display().to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn lookup(var: &str) -> Option<String> {
+        match var {
+            "ROS_WS" => Some("/home/user/ws".to_string()),
+            "CARGO_MANIFEST_DIR" => Some(env!("CARGO_MANIFEST_DIR").to_string()),
+            _ => None,
+        }
+    }
+
+    #[test]
+    fn expands_variables() {
+        assert_eq!(
+            expand_vars("${ROS_WS}/src", &lookup).unwrap(),
+            "/home/user/ws/src"
+        );
+        assert_eq!(
+            expand_vars("$ROS_WS/src", &lookup).unwrap(),
+            "/home/user/ws/src"
+        );
+        assert_eq!(expand_vars("a$$b", &lookup).unwrap(), "a$b");
+        assert_eq!(expand_vars("plain/path", &lookup).unwrap(), "plain/path");
+        assert!(expand_vars("$NOT_SET/msgs", &lookup)
+            .unwrap_err()
+            .contains("NOT_SET"));
+        assert!(expand_vars("${ROS_WS/src", &lookup).is_err());
+        assert!(expand_vars("$/src", &lookup).is_err());
+    }
+
+    #[test]
+    fn expands_workspace_root() {
+        let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+        assert_eq!(
+            expand_vars("$CARGO_WORKSPACE_ROOT/assets", &lookup).unwrap(),
+            format!("{}/assets", workspace.display())
+        );
+    }
+
+    #[test]
+    fn relative_paths_fall_back_to_manifest_dir() {
+        // Either found from the current directory or from the crate's directory
+        let expanded = expand_path_with("src/path_expansion.rs", &lookup).unwrap();
+        assert!(expanded.exists());
+    }
+}
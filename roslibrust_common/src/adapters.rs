@@ -0,0 +1,280 @@
+use crate::{Error, Result, RosMessageType, Subscribe};
+use std::marker::PhantomData;
+use std::sync::Arc;
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    mpsc,
+};
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+/// Subscriber adapter created by [Subscribe::filter], only yields messages matching a predicate.
+pub struct Filter<S, T, F> {
+    inner: S,
+    predicate: F,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<S, T, F> Filter<S, T, F> {
+    pub(crate) fn new(inner: S, predicate: F) -> Self {
+        Self {
+            inner,
+            predicate,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the underlying subscriber
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, T, F> Subscribe<T> for Filter<S, T, F>
+where
+    T: RosMessageType,
+    S: Subscribe<T> + Send,
+    F: FnMut(&T) -> bool + Send,
+{
+    async fn next(&mut self) -> Result<T> {
+        loop {
+            let msg = self.inner.next().await?;
+            if (self.predicate)(&msg) {
+                return Ok(msg);
+            }
+        }
+    }
+}
+
+/// Subscriber adapter created by [Subscribe::sample_every], yields one of every `n` messages.
+pub struct SampleEvery<S, T> {
+    inner: S,
+    n: usize,
+    // Messages still to be discarded after the last one yielded
+    skip: usize,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<S, T> SampleEvery<S, T> {
+    pub(crate) fn new(inner: S, n: usize) -> Self {
+        Self {
+            inner,
+            // Sampling every 0 messages is treated as keeping every message
+            n: n.max(1),
+            skip: 0,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the underlying subscriber
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, T> Subscribe<T> for SampleEvery<S, T>
+where
+    T: RosMessageType,
+    S: Subscribe<T> + Send,
+{
+    async fn next(&mut self) -> Result<T> {
+        // Discard the n - 1 messages following the last one yielded, then yield the next
+        // Progress is kept in self so that a cancelled call neither loses a message nor discards too many
+        while self.skip > 0 {
+            self.inner.next().await?;
+            self.skip -= 1;
+        }
+        let msg = self.inner.next().await?;
+        self.skip = self.n - 1;
+        Ok(msg)
+    }
+}
+
+/// Subscriber adapter created by [Subscribe::map_msg], transforms each message.
+///
+/// If the output type is a [RosMessageType] this itself implements [Subscribe],
+/// otherwise messages can be received with [MapMsg::next].
+pub struct MapMsg<S, T, F> {
+    inner: S,
+    f: F,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<S, T, F> MapMsg<S, T, F> {
+    pub(crate) fn new(inner: S, f: F) -> Self {
+        Self {
+            inner,
+            f,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the underlying subscriber
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+}
+
+impl<S, T, U, F> MapMsg<S, T, F>
+where
+    T: RosMessageType,
+    S: Subscribe<T>,
+    F: FnMut(T) -> U,
+{
+    /// Returns the next message on the topic transformed by the mapping function
+    pub async fn next(&mut self) -> Result<U> {
+        let msg = self.inner.next().await?;
+        Ok((self.f)(msg))
+    }
+}
+
+impl<S, T, U, F> Subscribe<U> for MapMsg<S, T, F>
+where
+    T: RosMessageType,
+    U: RosMessageType,
+    S: Subscribe<T> + Send,
+    F: FnMut(T) -> U + Send,
+{
+    async fn next(&mut self) -> Result<U> {
+        MapMsg::next(self).await
+    }
+}
+
+/// Shares one subscription between several consumers in the same process, created by [Subscribe::fan_out].
+///
+/// Each consumer gets a [SharedSubscriber] from [FanOut::subscribe] and receives every message from the point it
+/// subscribed, without the message being deserialized or cloned again for each. The subscription ends when the
+/// FanOut is dropped, after which its SharedSubscribers return [Error::Disconnected] once no messages remain.
+pub struct FanOut<T> {
+    // Never received from, only used to create receivers for new consumers
+    template: broadcast::Receiver<Arc<T>>,
+    _stop: DropGuard,
+}
+
+impl<T: RosMessageType> FanOut<T> {
+    pub(crate) fn new<S: Subscribe<T> + Send + 'static>(mut inner: S, capacity: usize) -> Self {
+        let (sender, template) = broadcast::channel(capacity.max(1));
+        let stop = CancellationToken::new();
+        let task_stop = stop.clone();
+        tokio::spawn(async move {
+            loop {
+                let msg = tokio::select! {
+                    _ = task_stop.cancelled() => break,
+                    msg = inner.next_shared() => msg,
+                };
+                match msg {
+                    // Sending only fails when there are no consumers right now
+                    Ok(msg) => {
+                        let _ = sender.send(msg);
+                    }
+                    // A single malformed message shouldn't end the subscription
+                    Err(Error::SerializationError(_)) => continue,
+                    Err(_) => break,
+                }
+            }
+        });
+        Self {
+            template,
+            _stop: stop.drop_guard(),
+        }
+    }
+
+    /// Creates a consumer which receives every message from now on
+    pub fn subscribe(&self) -> SharedSubscriber<T> {
+        SharedSubscriber {
+            receiver: self.template.resubscribe(),
+        }
+    }
+}
+
+/// A consumer of a [FanOut].
+///
+/// [Subscribe::next_shared] returns the message shared with the other consumers, [Subscribe::next] returns a clone
+/// of it.
+pub struct SharedSubscriber<T> {
+    receiver: broadcast::Receiver<Arc<T>>,
+}
+
+impl<T: RosMessageType> Subscribe<T> for SharedSubscriber<T> {
+    async fn next(&mut self) -> Result<T> {
+        Ok(Arc::unwrap_or_clone(self.next_shared().await?))
+    }
+
+    async fn next_shared(&mut self) -> Result<Arc<T>> {
+        loop {
+            match self.receiver.recv().await {
+                Ok(msg) => return Ok(msg),
+                // As with a full subscriber queue the oldest messages are dropped for slow consumers
+                Err(RecvError::Lagged(_)) => continue,
+                Err(RecvError::Closed) => return Err(Error::Disconnected),
+            }
+        }
+    }
+}
+
+/// Error returned by [MappedSubscriber::next], keeping failures to convert a message apart from those of the
+/// subscription itself.
+#[derive(thiserror::Error, Debug)]
+pub enum MappedError<E> {
+    /// The underlying subscriber returned an error
+    #[error(transparent)]
+    Ros(#[from] Error),
+    /// A message was received, but could not be converted to the domain type
+    #[error("Failed to convert message: {0}")]
+    Conversion(E),
+}
+
+/// Subscriber created by [TopicProvider::subscribe_mapped](crate::TopicProvider::subscribe_mapped), yields messages
+/// converted to a domain type `D` through its [TryFrom] impl.
+///
+/// Messages are received and converted by a background task, so the conversion doesn't run on the task reading
+/// from the subscriber. The task ends when the MappedSubscriber is dropped.
+pub struct MappedSubscriber<D, E> {
+    receiver: mpsc::Receiver<std::result::Result<D, MappedError<E>>>,
+}
+
+impl<D, E> MappedSubscriber<D, E>
+where
+    D: Send + 'static,
+    E: Send + 'static,
+{
+    pub(crate) fn new<S, T>(mut inner: S) -> Self
+    where
+        S: Subscribe<T> + Send + 'static,
+        T: RosMessageType,
+        D: TryFrom<T, Error = E>,
+    {
+        // Only the message currently being handed over is converted ahead of the reader, the rest stay queued in
+        // the underlying subscriber
+        let (sender, receiver) = mpsc::channel(1);
+        tokio::spawn(async move {
+            loop {
+                let msg = tokio::select! {
+                    _ = sender.closed() => break,
+                    msg = inner.next() => msg,
+                };
+                // Malformed or unconvertible messages, or falling behind, shouldn't end the subscription
+                let (item, done) = match msg {
+                    Ok(msg) => (D::try_from(msg).map_err(MappedError::Conversion), false),
+                    Err(Error::Disconnected) => (Err(MappedError::Ros(Error::Disconnected)), true),
+                    Err(e) => (Err(MappedError::Ros(e)), false),
+                };
+                if sender.send(item).await.is_err() || done {
+                    break;
+                }
+            }
+        });
+        Self { receiver }
+    }
+
+    /// Returns the next converted message on the topic.
+    ///
+    /// Returns [MappedError::Conversion] for a message that failed to convert, and [MappedError::Ros] for other
+    /// errors of the underlying subscriber, after either of which later messages are still returned.
+    /// Once the subscription has ended [Error::Disconnected] is returned.
+    pub async fn next(&mut self) -> std::result::Result<D, MappedError<E>> {
+        self.receiver
+            .recv()
+            .await
+            .unwrap_or(Err(MappedError::Ros(Error::Disconnected)))
+    }
+}
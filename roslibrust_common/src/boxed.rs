@@ -0,0 +1,80 @@
+use crate::{Publish, Result, RosMessageType, Subscribe};
+use std::future::Future;
+use std::pin::Pin;
+
+pub(crate) type BoxFuture<'a, T> = Pin<Box<dyn Future<Output = T> + Send + 'a>>;
+
+// Object safe versions of Publish and Subscribe, auto implemented for all implementors of those traits
+trait DynPublish<T>: Send + Sync {
+    fn publish_boxed<'a>(&'a self, data: &'a T) -> BoxFuture<'a, Result<()>>;
+}
+
+impl<T, P> DynPublish<T> for P
+where
+    T: RosMessageType,
+    P: Publish<T> + Send + Sync + 'static,
+{
+    fn publish_boxed<'a>(&'a self, data: &'a T) -> BoxFuture<'a, Result<()>> {
+        Box::pin(self.publish(data))
+    }
+}
+
+trait DynSubscribe<T>: Send + Sync {
+    fn next_boxed(&mut self) -> BoxFuture<'_, Result<T>>;
+}
+
+impl<T, S> DynSubscribe<T> for S
+where
+    T: RosMessageType,
+    S: Subscribe<T> + Send + Sync + 'static,
+{
+    fn next_boxed(&mut self) -> BoxFuture<'_, Result<T>> {
+        Box::pin(self.next())
+    }
+}
+
+/// A type erased publisher which can wrap the publisher of any backend.
+///
+/// Allows storing publishers from different backends in the same collection, or holding a publisher
+/// in a struct without making the struct generic over the backend.
+pub struct BoxPublisher<T> {
+    inner: Box<dyn DynPublish<T>>,
+}
+
+impl<T: RosMessageType> BoxPublisher<T> {
+    /// Wraps the given publisher
+    pub fn new(publisher: impl Publish<T> + Send + Sync + 'static) -> Self {
+        Self {
+            inner: Box::new(publisher),
+        }
+    }
+}
+
+impl<T: RosMessageType> Publish<T> for BoxPublisher<T> {
+    async fn publish(&self, data: &T) -> Result<()> {
+        self.inner.publish_boxed(data).await
+    }
+}
+
+/// A type erased subscriber which can wrap the subscriber of any backend.
+///
+/// Allows storing subscribers from different backends in the same collection, or holding a subscriber
+/// in a struct without making the struct generic over the backend.
+pub struct BoxSubscriber<T> {
+    inner: Box<dyn DynSubscribe<T>>,
+}
+
+impl<T: RosMessageType> BoxSubscriber<T> {
+    /// Wraps the given subscriber
+    pub fn new(subscriber: impl Subscribe<T> + Send + Sync + 'static) -> Self {
+        Self {
+            inner: Box::new(subscriber),
+        }
+    }
+}
+
+impl<T: RosMessageType> Subscribe<T> for BoxSubscriber<T> {
+    async fn next(&mut self) -> Result<T> {
+        self.inner.next_boxed().await
+    }
+}
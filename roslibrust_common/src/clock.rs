@@ -0,0 +1,504 @@
+use crate::boxed::BoxFuture;
+use crate::rosout::Time;
+use crate::{Error, Result, RosMessageType, Subscribe, TopicProvider};
+use std::sync::Arc;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::watch;
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+/// Topic on which ROS publishes simulated time, see [SimClock].
+pub const CLOCK_TOPIC: &str = "/clock";
+
+/// A source of "ROS time".
+///
+/// Times are expressed as a [SystemTime], for simulated time [UNIX_EPOCH] is the start of the simulation.
+/// Use through a [Clock], which is how clocks are passed to publishers, [crate::clock::Rate] and other consumers.
+pub trait ClockProvider: Send + Sync {
+    /// The current time
+    fn now(&self) -> SystemTime;
+
+    /// Completes once [ClockProvider::now] has reached `deadline`
+    fn sleep_until(&self, deadline: SystemTime) -> BoxFuture<'_, ()>;
+
+    /// Completes once the time has jumped backwards to before `from`, e.g. when a bag being played back loops.
+    ///
+    /// The default never completes, which suits clocks that only move forwards.
+    fn jumped_back(&self, from: SystemTime) -> BoxFuture<'_, ()> {
+        let _ = from;
+        Box::pin(std::future::pending())
+    }
+}
+
+/// Reads the system's wall clock, sleeping with tokio's timers.
+///
+/// Note: pausing tokio's time does not pause this clock, use a [ManualClock] for deterministic tests.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct WallClock;
+
+impl ClockProvider for WallClock {
+    fn now(&self) -> SystemTime {
+        SystemTime::now()
+    }
+
+    fn sleep_until(&self, deadline: SystemTime) -> BoxFuture<'_, ()> {
+        Box::pin(async move {
+            // The wall clock may be adjusted while sleeping, so check again on waking
+            while let Ok(remaining) = deadline.duration_since(SystemTime::now()) {
+                if remaining.is_zero() {
+                    break;
+                }
+                tokio::time::sleep(remaining).await;
+            }
+        })
+    }
+}
+
+/// A clock which only moves when it is told to, for driving time deterministically in tests.
+///
+/// Clones share the same time.
+/// ```
+/// # use roslibrust_common::clock::{Clock, ManualClock};
+/// # use std::time::{Duration, UNIX_EPOCH};
+/// let time = ManualClock::new(UNIX_EPOCH);
+/// let clock = Clock::new(time.clone());
+/// time.advance(Duration::from_secs(2));
+/// assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(2));
+/// ```
+#[derive(Clone, Debug)]
+pub struct ManualClock {
+    time: Arc<watch::Sender<SystemTime>>,
+}
+
+impl ManualClock {
+    /// Creates a clock reading `start`
+    pub fn new(start: SystemTime) -> Self {
+        Self {
+            time: Arc::new(watch::Sender::new(start)),
+        }
+    }
+
+    /// Sets the time, waking anything sleeping until a time that has been reached.
+    ///
+    /// Time may be set backwards, e.g. when a simulation is restarted.
+    pub fn set(&self, time: SystemTime) {
+        self.time.send_replace(time);
+    }
+
+    /// Moves the time forward by `duration`
+    pub fn advance(&self, duration: Duration) {
+        self.time.send_modify(|time| *time += duration);
+    }
+}
+
+impl Default for ManualClock {
+    fn default() -> Self {
+        Self::new(UNIX_EPOCH)
+    }
+}
+
+impl ClockProvider for ManualClock {
+    fn now(&self) -> SystemTime {
+        *self.time.borrow()
+    }
+
+    fn sleep_until(&self, deadline: SystemTime) -> BoxFuture<'_, ()> {
+        let mut time = self.time.subscribe();
+        Box::pin(async move {
+            // The sender lives as long as self, which is borrowed for the duration of the sleep
+            let _ = time.wait_for(|time| *time >= deadline).await;
+        })
+    }
+
+    fn jumped_back(&self, from: SystemTime) -> BoxFuture<'_, ()> {
+        let mut time = self.time.subscribe();
+        Box::pin(async move {
+            let _ = time.wait_for(|time| *time < from).await;
+        })
+    }
+}
+
+/// Equivalent of ROS's `rosgraph_msgs/Clock`, the message published on [CLOCK_TOPIC] by simulators.
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct ClockMessage {
+    pub clock: Time,
+}
+
+impl RosMessageType for ClockMessage {
+    const ROS_TYPE_NAME: &'static str = "rosgraph_msgs/Clock";
+    const ROS2_TYPE_NAME: &'static str = "rosgraph_msgs::msg::dds_::Clock_";
+    const MD5SUM: &'static str = "a9c97c1d230cfc112e270351a944ee47";
+    const DEFINITION: &'static str = "time clock";
+}
+
+impl From<SystemTime> for ClockMessage {
+    fn from(time: SystemTime) -> Self {
+        let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+        ClockMessage {
+            clock: Time {
+                secs: since_epoch.as_secs() as u32,
+                nsecs: since_epoch.subsec_nanos(),
+            },
+        }
+    }
+}
+
+impl From<&ClockMessage> for SystemTime {
+    fn from(msg: &ClockMessage) -> Self {
+        UNIX_EPOCH + Duration::new(msg.clock.secs as u64, msg.clock.nsecs)
+    }
+}
+
+/// Simulated time, following the [ClockMessage]s published on [CLOCK_TOPIC].
+///
+/// This is what ROS nodes use when the `use_sim_time` parameter is set. Reads [UNIX_EPOCH] until the first message
+/// arrives. Works with any backend, including the mock backend.
+#[derive(Clone)]
+pub struct SimClock {
+    time: ManualClock,
+    // Stops following /clock once the last clone is dropped
+    _stop: Arc<DropGuard>,
+}
+
+impl SimClock {
+    /// Subscribes to [CLOCK_TOPIC] and starts following it
+    pub async fn new<T: TopicProvider>(ros: &T) -> Result<Self>
+    where
+        T::Subscriber<ClockMessage>: Send + 'static,
+    {
+        let mut subscriber = ros.subscribe::<ClockMessage>(CLOCK_TOPIC).await?;
+        let time = ManualClock::default();
+        let stop = CancellationToken::new();
+        let task_time = time.clone();
+        let task_stop = stop.clone();
+        tokio::spawn(async move {
+            loop {
+                let msg = tokio::select! {
+                    _ = task_stop.cancelled() => break,
+                    msg = subscriber.next() => msg,
+                };
+                match msg {
+                    Ok(msg) => task_time.set((&msg).into()),
+                    // A single malformed message shouldn't stop time
+                    Err(Error::SerializationError(_)) => continue,
+                    Err(_) => break,
+                }
+            }
+        });
+        Ok(Self {
+            time,
+            _stop: Arc::new(stop.drop_guard()),
+        })
+    }
+}
+
+impl ClockProvider for SimClock {
+    fn now(&self) -> SystemTime {
+        self.time.now()
+    }
+
+    fn sleep_until(&self, deadline: SystemTime) -> BoxFuture<'_, ()> {
+        self.time.sleep_until(deadline)
+    }
+
+    fn jumped_back(&self, from: SystemTime) -> BoxFuture<'_, ()> {
+        self.time.jumped_back(from)
+    }
+}
+
+/// A shareable handle to a [ClockProvider], the form in which clocks are handed to publishers, rate limiters,
+/// transform buffers and the mock backend.
+///
+/// Defaults to the [WallClock].
+#[derive(Clone)]
+pub struct Clock {
+    provider: Arc<dyn ClockProvider>,
+}
+
+impl Clock {
+    pub fn new(provider: impl ClockProvider + 'static) -> Self {
+        Self {
+            provider: Arc::new(provider),
+        }
+    }
+
+    /// A handle to the system's wall clock
+    pub fn wall() -> Self {
+        Self::new(WallClock)
+    }
+
+    /// The current time
+    pub fn now(&self) -> SystemTime {
+        self.provider.now()
+    }
+
+    /// Completes once the clock has reached `deadline`
+    pub async fn sleep_until(&self, deadline: SystemTime) {
+        self.provider.sleep_until(deadline).await
+    }
+
+    /// Completes once the time has jumped backwards to before `from`, see [ClockProvider::jumped_back]
+    pub async fn jumped_back(&self, from: SystemTime) {
+        self.provider.jumped_back(from).await
+    }
+
+    /// Completes once `duration` has passed on the clock
+    pub async fn sleep(&self, duration: Duration) {
+        self.sleep_until(self.now() + duration).await
+    }
+
+    /// Creates a [Rate] which runs a cycle every `period` of this clock's time
+    pub fn rate(&self, period: Duration) -> Rate {
+        let now = self.now();
+        Rate {
+            clock: self.clone(),
+            period,
+            start: now,
+            next: now + period,
+            cycle_time: Duration::ZERO,
+            overruns: 0,
+        }
+    }
+}
+
+impl Default for Clock {
+    fn default() -> Self {
+        Self::wall()
+    }
+}
+
+impl std::fmt::Debug for Clock {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Clock")
+            .field("now", &self.now())
+            .finish_non_exhaustive()
+    }
+}
+
+/// What happened during one cycle of a [Rate], returned by [Rate::sleep].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Cycle {
+    /// The loop body finished within its period, and the rate slept until the next cycle was due
+    OnTime,
+    /// The loop body overran its period, so the next cycle starts immediately.
+    ///
+    /// `behind` is how late this cycle finished. `skipped` counts the cycles that were missed entirely and dropped
+    /// from the schedule rather than being run back to back.
+    Overrun { behind: Duration, skipped: u32 },
+    /// The clock jumped backwards, e.g. a bag being played back looped, so the schedule restarted from the new time
+    TimeJumpedBack,
+}
+
+/// Runs a loop at a steady rate, or limits how often something runs, measured on a [Clock].
+///
+/// Equivalent of roscpp's `ros::Rate`: [Rate::sleep] paces a control loop, [Rate::try_tick] is a rate limiter.
+/// Cycles are scheduled at fixed times, so the time spent in the loop body is compensated for and the rate doesn't
+/// drift. Defaults to the wall clock, see [Rate::with_clock] to follow simulated time.
+/// ```no_run
+/// # async fn example() {
+/// use roslibrust_common::clock::{Cycle, Rate};
+/// let mut rate = Rate::new(100.0);
+/// loop {
+///     // Control loop body
+///     if let Cycle::Overrun { behind, .. } = rate.sleep().await {
+///         eprintln!("Control loop overran by {behind:?}");
+///     }
+/// }
+/// # }
+/// ```
+#[derive(Clone, Debug)]
+pub struct Rate {
+    clock: Clock,
+    period: Duration,
+    // When the current cycle started
+    start: SystemTime,
+    // When the next cycle is due
+    next: SystemTime,
+    // How long the last loop body took
+    cycle_time: Duration,
+    overruns: u64,
+}
+
+impl Rate {
+    /// Creates a rate running `hz` cycles per second of wall clock time.
+    ///
+    /// Panics if `hz` is not positive and finite.
+    pub fn new(hz: f64) -> Rate {
+        assert!(
+            hz.is_finite() && hz > 0.0,
+            "Rate must be positive and finite, got {hz}"
+        );
+        Clock::wall().rate(Duration::from_secs_f64(1.0 / hz))
+    }
+
+    /// Measures the rate on `clock` instead, e.g. a [SimClock]. Restarts the schedule from the clock's current time.
+    pub fn with_clock(mut self, clock: Clock) -> Rate {
+        self.clock = clock;
+        self.reset();
+        self
+    }
+
+    /// Waits until the next cycle is due.
+    ///
+    /// Returns immediately if the loop body overran its period, and wakes early if the clock jumps backwards.
+    pub async fn sleep(&mut self) -> Cycle {
+        let now = self.clock.now();
+        if now < self.start {
+            self.restart(now);
+            return Cycle::TimeJumpedBack;
+        }
+        self.cycle_time = now.duration_since(self.start).unwrap_or_default();
+        if now > self.next {
+            let behind = now.duration_since(self.next).unwrap_or_default();
+            // Keep to the original schedule, dropping the cycles that were missed entirely
+            let skipped = (behind.as_nanos() / self.period.as_nanos().max(1)) as u32;
+            self.next += self.period * (skipped + 1);
+            self.start = now;
+            self.overruns += 1;
+            return Cycle::Overrun { behind, skipped };
+        }
+        tokio::select! {
+            _ = self.clock.sleep_until(self.next) => {
+                self.start = self.next;
+                self.next += self.period;
+                Cycle::OnTime
+            }
+            _ = self.clock.jumped_back(now) => {
+                self.restart(self.clock.now());
+                Cycle::TimeJumpedBack
+            }
+        }
+    }
+
+    /// Returns true, and starts the next period, if the current period has elapsed
+    pub fn try_tick(&mut self) -> bool {
+        let now = self.clock.now();
+        if now < self.start {
+            // The clock jumped backwards, start a new period from now
+            self.restart(now);
+            return true;
+        }
+        if now < self.next {
+            return false;
+        }
+        self.start = now;
+        self.next += self.period;
+        if self.next <= now {
+            self.next = now + self.period;
+        }
+        true
+    }
+
+    /// Restarts the current period from now
+    pub fn reset(&mut self) {
+        self.restart(self.clock.now());
+    }
+
+    /// The time between cycles
+    pub fn period(&self) -> Duration {
+        self.period
+    }
+
+    /// How long the loop body took in the last cycle, i.e. from the end of one [Rate::sleep] to the start of the next
+    pub fn cycle_time(&self) -> Duration {
+        self.cycle_time
+    }
+
+    /// How many cycles have overrun their period so far
+    pub fn overruns(&self) -> u64 {
+        self.overruns
+    }
+
+    fn restart(&mut self, now: SystemTime) {
+        self.start = now;
+        self.next = now + self.period;
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[tokio::test]
+    async fn manual_clock_wakes_sleepers_once_advanced_far_enough() {
+        let time = ManualClock::default();
+        let clock = Clock::new(time.clone());
+        let sleeper = tokio::spawn({
+            let clock = clock.clone();
+            async move { clock.sleep(Duration::from_secs(5)).await }
+        });
+        tokio::task::yield_now().await;
+        time.advance(Duration::from_secs(3));
+        tokio::task::yield_now().await;
+        assert!(!sleeper.is_finished());
+        time.advance(Duration::from_secs(2));
+        sleeper.await.unwrap();
+        assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(5));
+    }
+
+    #[tokio::test]
+    async fn rate_compensates_for_loop_time_and_reports_overruns() {
+        let time = ManualClock::default();
+        let mut rate = Rate::new(1.0).with_clock(Clock::new(time.clone()));
+        let advance = |millis| {
+            let time = time.clone();
+            async move {
+                tokio::task::yield_now().await;
+                time.advance(Duration::from_millis(millis));
+            }
+        };
+
+        // The loop body took 300ms, so only 700ms more is slept
+        time.advance(Duration::from_millis(300));
+        let (cycle, _) = tokio::join!(rate.sleep(), advance(700));
+        assert_eq!(cycle, Cycle::OnTime);
+        assert_eq!(rate.cycle_time(), Duration::from_millis(300));
+
+        // Finishing at 3.5s misses the cycle due at 3s, the next one is still due at 4s
+        time.advance(Duration::from_millis(2500));
+        assert_eq!(
+            rate.sleep().await,
+            Cycle::Overrun {
+                behind: Duration::from_millis(1500),
+                skipped: 1
+            }
+        );
+        assert_eq!(rate.overruns(), 1);
+        let (cycle, _) = tokio::join!(rate.sleep(), advance(500));
+        assert_eq!(cycle, Cycle::OnTime);
+        assert_eq!(time.now(), UNIX_EPOCH + Duration::from_secs(4));
+
+        assert!(!rate.try_tick());
+        time.advance(Duration::from_secs(1));
+        assert!(rate.try_tick());
+        assert!(!rate.try_tick());
+    }
+
+    #[tokio::test]
+    async fn rate_restarts_when_time_jumps_back() {
+        let time = ManualClock::new(UNIX_EPOCH + Duration::from_secs(100));
+        let mut rate = Clock::new(time.clone()).rate(Duration::from_secs(1));
+        let jump_back = async {
+            tokio::task::yield_now().await;
+            time.set(UNIX_EPOCH + Duration::from_secs(10));
+        };
+        let (cycle, _) = tokio::join!(rate.sleep(), jump_back);
+        assert_eq!(cycle, Cycle::TimeJumpedBack);
+
+        let advance = async {
+            tokio::task::yield_now().await;
+            time.advance(Duration::from_secs(1));
+        };
+        let (cycle, _) = tokio::join!(rate.sleep(), advance);
+        assert_eq!(cycle, Cycle::OnTime);
+        assert_eq!(time.now(), UNIX_EPOCH + Duration::from_secs(11));
+    }
+
+    #[test]
+    fn clock_message_converts_to_and_from_system_time() {
+        let time = UNIX_EPOCH + Duration::new(12, 345);
+        let msg = ClockMessage::from(time);
+        assert_eq!(msg.clock.secs, 12);
+        assert_eq!(msg.clock.nsecs, 345);
+        assert_eq!(SystemTime::from(&msg), time);
+    }
+}
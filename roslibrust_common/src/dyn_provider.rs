@@ -0,0 +1,127 @@
+use crate::boxed::BoxFuture;
+use crate::topic_name::{GlobalTopicName, ToGlobalTopicName};
+use crate::TopicProvider;
+use crate::{BoxPublisher, BoxSubscriber, Error, Publish, Result, RosMessageType, Subscribe};
+use std::any::{Any, TypeId};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+type AdvertiseFn<T> =
+    Box<dyn Fn(GlobalTopicName) -> BoxFuture<'static, Result<BoxPublisher<T>>> + Send + Sync>;
+type SubscribeFn<T> =
+    Box<dyn Fn(GlobalTopicName) -> BoxFuture<'static, Result<BoxSubscriber<T>>> + Send + Sync>;
+
+// The functions for advertising and subscribing to a single message type on the wrapped backend
+struct MessageEntry<T> {
+    advertise: AdvertiseFn<T>,
+    subscribe: SubscribeFn<T>,
+}
+
+/// A type erased [TopicProvider] which can wrap any backend.
+///
+/// Intended for plugin style architectures, where code loaded at runtime needs to be handed a way to publish
+/// and subscribe without being compiled against a specific backend. The provider is not generic, so it can be
+/// stored in structs and passed across plugin boundaries freely, and cloning it is cheap.
+///
+/// Because the wrapped backend's `advertise` and `subscribe` are generic over the message type, each message type
+/// that will be used through the provider must be registered when it is built with [DynTopicProviderBuilder::with_message].
+/// Advertising or subscribing with a type that wasn't registered returns an error.
+///
+/// ```ignore
+/// let provider = DynTopicProvider::builder(ros)
+///     .with_message::<std_msgs::String>()
+///     .build();
+/// plugin.start(provider.clone());
+/// ```
+#[derive(Clone)]
+pub struct DynTopicProvider {
+    // Each value is a MessageEntry<T> for the T matching the TypeId key
+    messages: Arc<HashMap<TypeId, Box<dyn Any + Send + Sync>>>,
+}
+
+impl DynTopicProvider {
+    /// Starts building a provider which wraps the given backend
+    pub fn builder<P>(provider: P) -> DynTopicProviderBuilder<P>
+    where
+        P: TopicProvider + Clone + Send + Sync + 'static,
+    {
+        DynTopicProviderBuilder {
+            provider,
+            messages: HashMap::new(),
+        }
+    }
+
+    /// Returns true if the given message type was registered with this provider
+    pub fn supports<T: RosMessageType>(&self) -> bool {
+        self.messages.contains_key(&TypeId::of::<T>())
+    }
+
+    fn entry<T: RosMessageType>(&self) -> Result<&MessageEntry<T>> {
+        self.messages
+            .get(&TypeId::of::<T>())
+            .and_then(|entry| entry.downcast_ref::<MessageEntry<T>>())
+            .ok_or_else(|| {
+                Error::Unexpected(anyhow::anyhow!(
+                    "Message type {} was not registered with this DynTopicProvider",
+                    T::ROS_TYPE_NAME
+                ))
+            })
+    }
+}
+
+impl TopicProvider for DynTopicProvider {
+    type Publisher<T: RosMessageType> = BoxPublisher<T>;
+    type Subscriber<T: RosMessageType> = BoxSubscriber<T>;
+
+    async fn advertise<T: RosMessageType>(
+        &self,
+        topic: impl ToGlobalTopicName,
+    ) -> Result<BoxPublisher<T>> {
+        let topic = topic.to_global_name()?;
+        (self.entry::<T>()?.advertise)(topic).await
+    }
+
+    async fn subscribe<T: RosMessageType>(
+        &self,
+        topic: impl ToGlobalTopicName,
+    ) -> Result<BoxSubscriber<T>> {
+        let topic = topic.to_global_name()?;
+        (self.entry::<T>()?.subscribe)(topic).await
+    }
+}
+
+/// Builder for [DynTopicProvider], created with [DynTopicProvider::builder].
+pub struct DynTopicProviderBuilder<P> {
+    provider: P,
+    messages: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl<P> DynTopicProviderBuilder<P>
+where
+    P: TopicProvider + Clone + Send + Sync + 'static,
+{
+    /// Registers a message type which can be advertised and subscribed to through the provider
+    pub fn with_message<T: RosMessageType>(mut self) -> Self {
+        let advertise_provider = self.provider.clone();
+        let subscribe_provider = self.provider.clone();
+        let entry = MessageEntry::<T> {
+            advertise: Box::new(move |topic| {
+                let provider = advertise_provider.clone();
+                Box::pin(async move { Ok(provider.advertise::<T>(topic).await?.boxed()) })
+            }),
+            subscribe: Box::new(move |topic| {
+                let provider = subscribe_provider.clone();
+                Box::pin(async move { Ok(provider.subscribe::<T>(topic).await?.boxed()) })
+            }),
+        };
+        self.messages.insert(TypeId::of::<T>(), Box::new(entry));
+        self
+    }
+
+    /// Finishes building the provider
+    pub fn build(self) -> DynTopicProvider {
+        DynTopicProvider {
+            messages: Arc::new(self.messages),
+        }
+    }
+}
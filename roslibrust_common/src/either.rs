@@ -0,0 +1,193 @@
+use crate::{
+    Publish, Result, RosMessageType, RosServiceType, Service, ServiceFn, ServiceProvider,
+    Subscribe, ToGlobalTopicName, TopicProvider,
+};
+
+/// One of two backends, chosen at runtime, which can be used anywhere a single backend is expected.
+///
+/// Implements [TopicProvider] and [ServiceProvider] by forwarding to whichever backend it holds, and so [crate::Ros]
+/// when both backends do. The publishers, subscribers, service clients and servers it returns are themselves an
+/// `Either` of the two backends' types. Unlike a [DynTopicProvider](crate::DynTopicProvider) no message types need
+/// registering and nothing is boxed.
+///
+/// ```ignore
+/// let ros: Either<ros1::NodeHandle, rosbridge::ClientHandle> = match config.backend {
+///     Backend::Ros1 => Either::Left(ros1::NodeHandle::new(&config.master_uri, "my_node").await?),
+///     Backend::Rosbridge => Either::Right(rosbridge::ClientHandle::new(&config.rosbridge_url).await?),
+/// };
+/// let publisher = ros.advertise::<std_msgs::String>("/chatter").await?;
+/// ```
+///
+/// Code needing more than the generic traits, e.g. a backend specific feature, can reach the backend with
+/// [crate::for_both!] or by matching.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Either<A, B> {
+    Left(A),
+    Right(B),
+}
+
+/// Evaluates the same expression for either variant of an [Either], binding the value it holds to `$pattern`.
+///
+/// Both arms must produce the same type, so this works best with generic functions or trait methods:
+/// ```
+/// use roslibrust_common::{either::Either, for_both};
+///
+/// let value: Either<u32, String> = Either::Right("hello".to_string());
+/// assert_eq!(for_both!(&value, inner => inner.to_string()), "hello");
+/// ```
+#[macro_export]
+macro_rules! for_both {
+    ($value:expr, $pattern:pat => $body:expr) => {
+        match $value {
+            $crate::either::Either::Left($pattern) => $body,
+            $crate::either::Either::Right($pattern) => $body,
+        }
+    };
+}
+
+impl<T, A, B> Publish<T> for Either<A, B>
+where
+    T: RosMessageType,
+    A: Publish<T> + Sync,
+    B: Publish<T> + Sync,
+{
+    async fn publish(&self, data: &T) -> Result<()> {
+        for_both!(self, publisher => publisher.publish(data).await)
+    }
+}
+
+impl<T, A, B> Subscribe<T> for Either<A, B>
+where
+    T: RosMessageType,
+    A: Subscribe<T> + Send,
+    B: Subscribe<T> + Send,
+{
+    async fn next(&mut self) -> Result<T> {
+        for_both!(self, subscriber => subscriber.next().await)
+    }
+}
+
+impl<T, A, B> Service<T> for Either<A, B>
+where
+    T: RosServiceType,
+    A: Service<T> + Sync,
+    B: Service<T> + Sync,
+{
+    async fn call(&self, request: &T::Request) -> Result<T::Response> {
+        for_both!(self, client => client.call(request).await)
+    }
+}
+
+impl<A, B> TopicProvider for Either<A, B>
+where
+    A: TopicProvider + Sync,
+    B: TopicProvider + Sync,
+{
+    type Publisher<T: RosMessageType> = Either<A::Publisher<T>, B::Publisher<T>>;
+    type Subscriber<T: RosMessageType> = Either<A::Subscriber<T>, B::Subscriber<T>>;
+
+    async fn advertise<T: RosMessageType>(
+        &self,
+        topic: impl ToGlobalTopicName,
+    ) -> Result<Self::Publisher<T>> {
+        Ok(match self {
+            Either::Left(ros) => Either::Left(ros.advertise(topic).await?),
+            Either::Right(ros) => Either::Right(ros.advertise(topic).await?),
+        })
+    }
+
+    async fn subscribe<T: RosMessageType>(
+        &self,
+        topic: impl ToGlobalTopicName,
+    ) -> Result<Self::Subscriber<T>> {
+        Ok(match self {
+            Either::Left(ros) => Either::Left(ros.subscribe(topic).await?),
+            Either::Right(ros) => Either::Right(ros.subscribe(topic).await?),
+        })
+    }
+}
+
+impl<A, B> ServiceProvider for Either<A, B>
+where
+    A: ServiceProvider + Sync,
+    B: ServiceProvider + Sync,
+{
+    type ServiceClient<T: RosServiceType> = Either<A::ServiceClient<T>, B::ServiceClient<T>>;
+    type ServiceServer = Either<A::ServiceServer, B::ServiceServer>;
+
+    async fn call_service<T: RosServiceType>(
+        &self,
+        service: impl ToGlobalTopicName,
+        request: T::Request,
+    ) -> Result<T::Response> {
+        for_both!(self, ros => ros.call_service::<T>(service, request).await)
+    }
+
+    async fn service_client<T: RosServiceType + 'static>(
+        &self,
+        service: impl ToGlobalTopicName,
+    ) -> Result<Self::ServiceClient<T>> {
+        Ok(match self {
+            Either::Left(ros) => Either::Left(ros.service_client::<T>(service).await?),
+            Either::Right(ros) => Either::Right(ros.service_client::<T>(service).await?),
+        })
+    }
+
+    async fn advertise_service<T: RosServiceType + 'static, F: ServiceFn<T>>(
+        &self,
+        service: impl ToGlobalTopicName,
+        server: F,
+    ) -> Result<Self::ServiceServer> {
+        Ok(match self {
+            Either::Left(ros) => {
+                Either::Left(ros.advertise_service::<T, F>(service, server).await?)
+            }
+            Either::Right(ros) => {
+                Either::Right(ros.advertise_service::<T, F>(service, server).await?)
+            }
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ShapeShifter;
+    use std::sync::{Arc, Mutex};
+
+    // Publishes into, and subscribes from, a shared list of messages
+    #[derive(Clone, Default)]
+    struct Queue(Arc<Mutex<Vec<ShapeShifter>>>);
+
+    impl Publish<ShapeShifter> for Queue {
+        async fn publish(&self, data: &ShapeShifter) -> Result<()> {
+            self.0.lock().unwrap().push(data.clone());
+            Ok(())
+        }
+    }
+
+    impl Subscribe<ShapeShifter> for Queue {
+        async fn next(&mut self) -> Result<ShapeShifter> {
+            self.0
+                .lock()
+                .unwrap()
+                .pop()
+                .ok_or(crate::Error::Disconnected)
+        }
+    }
+
+    #[tokio::test]
+    async fn forwards_to_held_variant() {
+        let left = Queue::default();
+        let right = Queue::default();
+        let publisher: Either<Queue, Queue> = Either::Left(left.clone());
+        publisher.publish(&ShapeShifter::default()).await.unwrap();
+        assert_eq!(left.0.lock().unwrap().len(), 1);
+        assert!(right.0.lock().unwrap().is_empty());
+
+        let mut subscriber: Either<Queue, Queue> = Either::Right(right);
+        assert!(subscriber.next().await.is_err());
+        let mut subscriber: Either<Queue, Queue> = Either::Left(left);
+        subscriber.next().await.unwrap();
+    }
+}
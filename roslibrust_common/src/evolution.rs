@@ -0,0 +1,259 @@
+use crate::{Error, Result};
+use std::collections::HashMap;
+
+// Messages evolve by appending fields, so a message serialized with an older or newer version of a definition starts
+// with exactly the fields both versions share. Walking the serialized data with the local definition finds where
+// those fields end, which is all that's needed to drop the fields only the sender knows about, or to fill in the
+// ones it doesn't know about with zeros, which every ROS type decodes as its default value.
+
+/// Fits a serialized ROS1 message, including its 4 byte length prefix, to the layout described by the expanded
+/// definition `definition` of `msg_name` (e.g. `T::DEFINITION` of `T::ROS_TYPE_NAME`).
+///
+/// Bytes beyond the last field of the definition are dropped, and fields missing from the end of the message are
+/// filled in as zeros, so the result decodes as the local type with those fields left at their defaults. This only
+/// gives meaningful results when the sender's definition differs from the local one by fields added or removed at
+/// the end of a message, not by changed or re-ordered fields.
+///
+/// ```
+/// use roslibrust_common::evolution::fit_to_definition;
+///
+/// // Sent by a node with `uint32 a` only, received by one which has since added `string b`
+/// let old = [4, 0, 0, 0, 7, 0, 0, 0];
+/// let fitted = fit_to_definition("pkg/Msg", "uint32 a\nstring b", &old).unwrap();
+/// assert_eq!(fitted, [8, 0, 0, 0, 7, 0, 0, 0, 0, 0, 0, 0]);
+/// ```
+pub fn fit_to_definition(msg_name: &str, definition: &str, data: &[u8]) -> Result<Vec<u8>> {
+    let Some(body) = data.get(4..) else {
+        return Err(Error::SerializationError(
+            "message is too short to contain its length".to_string(),
+        ));
+    };
+    let layouts = parse_layouts(msg_name, definition)?;
+    let mut reader = Reader { data: body, pos: 0 };
+    reader.walk(&layouts, msg_name)?;
+
+    let len = reader.pos;
+    let mut fitted = Vec::with_capacity(4 + len);
+    fitted.extend_from_slice(&(len as u32).to_le_bytes());
+    fitted.extend_from_slice(&body[..len.min(body.len())]);
+    fitted.resize(4 + len, 0);
+    Ok(fitted)
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum Count {
+    One,
+    Variable,
+    Fixed(usize),
+}
+
+#[derive(Debug)]
+struct Field {
+    // Fully qualified for message types, e.g. "std_msgs/Header"
+    field_type: String,
+    count: Count,
+}
+
+// Fields of each message type in an expanded definition
+type Layouts = HashMap<String, Vec<Field>>;
+
+fn parse_layouts(msg_name: &str, definition: &str) -> Result<Layouts> {
+    let sep = "================================================================================\n";
+    let mut sections = definition.split(sep);
+    let mut layouts = HashMap::new();
+    let root = sections.next().unwrap_or_default();
+    layouts.insert(msg_name.to_string(), parse_fields(msg_name, root)?);
+    for section in sections {
+        let (line0, body) = section.split_once('\n').unwrap_or((section, ""));
+        let Some(section_type) = line0.strip_prefix("MSG: ") else {
+            return Err(Error::SerializationError(format!(
+                "bad definition section, '{line0}' doesn't start with 'MSG: '"
+            )));
+        };
+        let section_type = section_type.trim();
+        layouts.insert(section_type.to_string(), parse_fields(section_type, body)?);
+    }
+    Ok(layouts)
+}
+
+fn parse_fields(msg_type: &str, body: &str) -> Result<Vec<Field>> {
+    let package = msg_type.split('/').next().unwrap_or_default();
+    let mut fields = vec![];
+    for line in body.lines() {
+        let line = line.split('#').next().unwrap_or_default().trim();
+        // Constants aren't serialized
+        if line.is_empty() || line.contains('=') {
+            continue;
+        }
+        let raw_type = line.split_whitespace().next().unwrap_or_default();
+        let (base_type, count) = match raw_type.split_once('[') {
+            None => (raw_type, Count::One),
+            Some((base_type, "]")) => (base_type, Count::Variable),
+            Some((base_type, size)) => {
+                let size = size.trim_end_matches(']').parse().map_err(|_| {
+                    Error::SerializationError(format!("bad array size in '{line}'"))
+                })?;
+                (base_type, Count::Fixed(size))
+            }
+        };
+        let field_type = if primitive_size(base_type).is_some() || base_type == "string" {
+            base_type.to_string()
+        } else if base_type == "Header" {
+            "std_msgs/Header".to_string()
+        } else if !base_type.contains('/') {
+            format!("{package}/{base_type}")
+        } else {
+            base_type.to_string()
+        };
+        fields.push(Field { field_type, count });
+    }
+    Ok(fields)
+}
+
+// Size in bytes of a fixed size builtin type
+fn primitive_size(field_type: &str) -> Option<usize> {
+    Some(match field_type {
+        "bool" | "int8" | "uint8" | "byte" | "char" => 1,
+        "int16" | "uint16" => 2,
+        "int32" | "uint32" | "float32" => 4,
+        "int64" | "uint64" | "float64" | "time" | "duration" => 8,
+        _ => return None,
+    })
+}
+
+// Walks serialized fields, reading anything past the end of the data as zeros
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Reader<'_> {
+    fn read_len(&mut self) -> usize {
+        let mut bytes = [0; 4];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = self.data.get(self.pos + i).copied().unwrap_or(0);
+        }
+        self.pos += 4;
+        u32::from_le_bytes(bytes) as usize
+    }
+
+    // Skips `len` bytes of a string or array whose length was read from the data, which must contain all of them
+    fn skip(&mut self, len: usize) -> Result<()> {
+        self.pos += len;
+        if len > 0 && self.pos > self.data.len() {
+            return Err(Error::SerializationError(format!(
+                "message ends {} bytes into a field it says is {len} bytes long",
+                len - (self.pos - self.data.len())
+            )));
+        }
+        Ok(())
+    }
+
+    fn walk(&mut self, layouts: &Layouts, msg_type: &str) -> Result<()> {
+        let fields = layouts.get(msg_type).ok_or_else(|| {
+            Error::SerializationError(format!("definition of {msg_type} not found"))
+        })?;
+        for field in fields {
+            let count = match field.count {
+                Count::One => 1,
+                Count::Fixed(count) => count,
+                Count::Variable => self.read_len(),
+            };
+            match primitive_size(&field.field_type) {
+                Some(size) if field.count == Count::Variable => self.skip(count * size)?,
+                Some(size) => self.pos += count * size,
+                None => {
+                    let start = self.pos;
+                    for _ in 0..count {
+                        if field.field_type == "string" {
+                            let len = self.read_len();
+                            self.skip(len)?;
+                        } else {
+                            self.walk(layouts, &field.field_type)?;
+                        }
+                    }
+                    if field.count == Count::Variable && count > 0 && self.pos > self.data.len() {
+                        return Err(Error::SerializationError(format!(
+                            "message ends within an array of {count} {} starting at byte {start}",
+                            field.field_type
+                        )));
+                    }
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    const DEFINITION: &str = r#"Header header
+string[] names # comment
+uint8[] data
+Point[2] corners
+uint8 MODE=1
+================================================================================
+MSG: std_msgs/Header
+uint32 seq
+time stamp
+string frame_id
+================================================================================
+MSG: pkg/Point
+float64 x
+"#;
+
+    // Rewrites the length prefix to match the body
+    fn set_len(data: &mut [u8]) {
+        let len = (data.len() - 4) as u32;
+        data[..4].copy_from_slice(&len.to_le_bytes());
+    }
+
+    fn message() -> Vec<u8> {
+        let mut body = vec![];
+        // header: seq, stamp, frame_id "map"
+        body.extend_from_slice(&[1, 0, 0, 0]);
+        body.extend_from_slice(&[0; 8]);
+        body.extend_from_slice(&[3, 0, 0, 0, b'm', b'a', b'p']);
+        // names: ["a"]
+        body.extend_from_slice(&[1, 0, 0, 0, 1, 0, 0, 0, b'a']);
+        // data: [9, 9]
+        body.extend_from_slice(&[2, 0, 0, 0, 9, 9]);
+        // corners
+        body.extend_from_slice(&[0; 16]);
+        let mut data = (body.len() as u32).to_le_bytes().to_vec();
+        data.extend(body);
+        data
+    }
+
+    #[test]
+    fn fits_messages_to_definition() {
+        let msg = message();
+        assert_eq!(fit_to_definition("pkg/Msg", DEFINITION, &msg).unwrap(), msg);
+
+        // A newer sender appended a field
+        let mut newer = msg.clone();
+        newer.extend_from_slice(&[5, 0, 0, 0]);
+        set_len(&mut newer);
+        assert_eq!(
+            fit_to_definition("pkg/Msg", DEFINITION, &newer).unwrap(),
+            msg
+        );
+
+        // An older sender doesn't know about data or corners
+        let mut older = msg[..msg.len() - 22].to_vec();
+        set_len(&mut older);
+        let mut expected = older.clone();
+        expected.extend_from_slice(&[0; 20]);
+        set_len(&mut expected);
+        assert_eq!(
+            fit_to_definition("pkg/Msg", DEFINITION, &older).unwrap(),
+            expected
+        );
+
+        // Cut off part way through a string is corruption, not evolution
+        let truncated = msg[..20].to_vec();
+        assert!(fit_to_definition("pkg/Msg", DEFINITION, &truncated).is_err());
+    }
+}
@@ -0,0 +1,243 @@
+use crate::clock::Clock;
+use crate::field_path::{FieldPath, FieldPathError, FieldValue};
+use crate::{RosMessageType, Subscribe};
+use serde::Serialize;
+use std::io::Write;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Writes the numeric fields of a series of messages as CSV, one row per message, for analysis in tools like
+/// polars or pandas.
+///
+/// Works with any message implementing [Serialize], so both generated types and dynamic messages held as
+/// `serde_json::Value` can be exported. Nested messages and arrays are flattened into one column per number,
+/// named by the [FieldPath] selecting it (e.g. `pose.position.x` or `covariance[3]`), and booleans are written
+/// as 0 or 1. Strings are skipped. The first column, `timestamp`, holds the time given for each message in
+/// seconds since the unix epoch.
+///
+/// The columns are decided by the first message written. Later messages with fewer elements in an array leave
+/// the missing cells empty, and elements beyond those in the first message are dropped.
+///
+/// ```
+/// use roslibrust_common::export::CsvExporter;
+/// use roslibrust_common::field_path::FieldPath;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// #[derive(serde::Serialize)]
+/// struct Point {
+///     x: f64,
+///     y: f64,
+///     label: String,
+/// }
+///
+/// let mut exporter = CsvExporter::new(vec![]);
+/// let point = Point { x: 1.0, y: 2.5, label: "a".to_string() };
+/// exporter.write(UNIX_EPOCH + Duration::from_millis(1500), &point).unwrap();
+/// let csv = String::from_utf8(exporter.into_inner()).unwrap();
+/// assert_eq!(csv, "timestamp,x,y\n1.500000000,1,2.5\n");
+/// ```
+pub struct CsvExporter<W: Write> {
+    writer: W,
+    // The fields to export, flattened into one or more columns each
+    fields: Vec<FieldPath>,
+    // Names of the columns after the timestamp, decided by the first message
+    columns: Option<Vec<String>>,
+}
+
+impl<W: Write> CsvExporter<W> {
+    /// Creates an exporter writing every numeric field of each message to `writer`.
+    ///
+    /// For messages with large arrays (e.g. images or point clouds) prefer [CsvExporter::with_fields].
+    pub fn new(writer: W) -> Self {
+        Self::with_fields(writer, [FieldPath::root()])
+    }
+
+    /// Creates an exporter writing only the numeric values found at `fields` of each message to `writer`.
+    pub fn with_fields(writer: W, fields: impl IntoIterator<Item = FieldPath>) -> Self {
+        Self {
+            writer,
+            fields: fields.into_iter().collect(),
+            columns: None,
+        }
+    }
+
+    /// Writes a row for `msg`, with `stamp` in the timestamp column.
+    ///
+    /// The header row is written before the first message.
+    pub fn write<T: Serialize + ?Sized>(
+        &mut self,
+        stamp: SystemTime,
+        msg: &T,
+    ) -> Result<(), ExportError> {
+        let mut cells = vec![];
+        for field in &self.fields {
+            flatten(&field.to_string(), &field.extract(msg)?, &mut cells);
+        }
+        let columns = match &self.columns {
+            Some(columns) => columns,
+            None => {
+                let columns = cells.iter().map(|(name, _)| name.clone()).collect();
+                let columns = self.columns.insert(columns);
+                write!(self.writer, "timestamp")?;
+                for column in columns.iter() {
+                    write!(self.writer, ",{column}")?;
+                }
+                writeln!(self.writer)?;
+                columns
+            }
+        };
+
+        let stamp = stamp
+            .duration_since(UNIX_EPOCH)
+            .map_err(|_| ExportError::BeforeEpoch)?;
+        write!(
+            self.writer,
+            "{}.{:09}",
+            stamp.as_secs(),
+            stamp.subsec_nanos()
+        )?;
+        for column in columns {
+            match cells.iter().find(|(name, _)| name == column) {
+                Some((_, value)) => write!(self.writer, ",{value}")?,
+                None => write!(self.writer, ",")?,
+            }
+        }
+        writeln!(self.writer)?;
+        Ok(())
+    }
+
+    /// Writes a row for each of the next `count` messages received by `subscriber`, stamped with the time given by
+    /// `clock` when each was received.
+    pub async fn write_from<T: RosMessageType>(
+        &mut self,
+        subscriber: &mut impl Subscribe<T>,
+        clock: &Clock,
+        count: usize,
+    ) -> Result<(), ExportError> {
+        for _ in 0..count {
+            let msg = subscriber.next().await?;
+            self.write(clock.now(), &msg)?;
+        }
+        Ok(())
+    }
+
+    /// Flushes the underlying writer
+    pub fn flush(&mut self) -> Result<(), ExportError> {
+        Ok(self.writer.flush()?)
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// Appends a cell for each number within `value`, named by the path to it
+fn flatten(path: &str, value: &FieldValue, cells: &mut Vec<(String, String)>) {
+    match value {
+        FieldValue::Bool(v) => cells.push((path.to_owned(), (*v as u8).to_string())),
+        FieldValue::Int(v) => cells.push((path.to_owned(), v.to_string())),
+        FieldValue::UInt(v) => cells.push((path.to_owned(), v.to_string())),
+        FieldValue::Float(v) => cells.push((path.to_owned(), v.to_string())),
+        FieldValue::String(_) => {}
+        FieldValue::Sequence(values) => {
+            for (i, value) in values.iter().enumerate() {
+                flatten(&format!("{path}[{i}]"), value, cells);
+            }
+        }
+        FieldValue::Struct(fields) => {
+            for (name, value) in fields {
+                let path = if path.is_empty() {
+                    name.clone()
+                } else {
+                    format!("{path}.{name}")
+                };
+                flatten(&path, value, cells);
+            }
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug)]
+pub enum ExportError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Field(#[from] FieldPathError),
+    #[error("Failed to receive a message: {0}")]
+    Subscribe(#[from] crate::Error),
+    #[error("Message timestamps must be after the unix epoch")]
+    BeforeEpoch,
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::Duration;
+
+    #[derive(serde::Serialize)]
+    struct Odometry {
+        frame_id: String,
+        position: [f64; 2],
+        ranges: Vec<f32>,
+        valid: bool,
+        seq: u64,
+    }
+
+    fn odometry(ranges: Vec<f32>) -> Odometry {
+        Odometry {
+            frame_id: "odom".to_string(),
+            position: [1.5, -2.0],
+            ranges,
+            valid: true,
+            seq: u64::MAX,
+        }
+    }
+
+    fn at(millis: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn flattens_all_numeric_fields() {
+        let mut exporter = CsvExporter::new(vec![]);
+        exporter.write(at(1000), &odometry(vec![0.5, 1.0])).unwrap();
+        // Fewer elements leave cells empty
+        exporter.write(at(2001), &odometry(vec![0.25])).unwrap();
+        // Extra elements are dropped
+        exporter
+            .write(at(3000), &odometry(vec![1.0, 2.0, 3.0]))
+            .unwrap();
+        let csv = String::from_utf8(exporter.into_inner()).unwrap();
+        assert_eq!(
+            csv,
+            "timestamp,position[0],position[1],ranges[0],ranges[1],valid,seq\n\
+             1.000000000,1.5,-2,0.5,1,1,18446744073709551615\n\
+             2.001000000,1.5,-2,0.25,,1,18446744073709551615\n\
+             3.000000000,1.5,-2,1,2,1,18446744073709551615\n"
+        );
+    }
+
+    #[test]
+    fn exports_selected_fields_of_dynamic_messages() {
+        let fields = ["pose.position", "twist.linear.x"].map(|f| FieldPath::new(f).unwrap());
+        let mut exporter = CsvExporter::with_fields(vec![], fields);
+        let msg = serde_json::json!({
+            "pose": { "position": { "x": 1.0, "y": 2.0 }, "orientation": { "w": 1.0 } },
+            "twist": { "linear": { "x": 0.5, "y": 0.0 } },
+        });
+        exporter.write(at(0), &msg).unwrap();
+        let csv = String::from_utf8(exporter.into_inner()).unwrap();
+        assert_eq!(
+            csv,
+            "timestamp,pose.position.x,pose.position.y,twist.linear.x\n0.000000000,1,2,0.5\n"
+        );
+
+        // Missing fields are reported
+        let mut exporter =
+            CsvExporter::with_fields(vec![], [FieldPath::new("pose.velocity").unwrap()]);
+        assert!(matches!(
+            exporter.write(at(0), &msg),
+            Err(ExportError::Field(FieldPathError::NotFound(_)))
+        ));
+    }
+}
@@ -0,0 +1,926 @@
+use serde::ser::{self, Serialize};
+use std::fmt;
+
+/// A path selecting a single field out of a message, like `pose.pose.position.x` or `poses[2].position`.
+///
+/// Extraction works on any message type via its [Serialize] impl, and only the fields along the path are
+/// visited, so pulling a few scalars out of a large message (e.g. `header.stamp` of a point cloud) stays cheap.
+///
+/// ```
+/// use roslibrust_common::field_path::{FieldPath, FieldValue};
+///
+/// #[derive(serde::Serialize)]
+/// struct Point {
+///     x: f64,
+///     y: f64,
+/// }
+///
+/// #[derive(serde::Serialize)]
+/// struct Path {
+///     points: Vec<Point>,
+/// }
+///
+/// let msg = Path {
+///     points: vec![Point { x: 1.0, y: 2.0 }, Point { x: 3.0, y: 4.0 }],
+/// };
+/// let path = FieldPath::new("points[1].y").unwrap();
+/// assert_eq!(path.extract(&msg).unwrap(), FieldValue::Float(4.0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FieldPath {
+    path: String,
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+enum Segment {
+    Field(String),
+    Index(usize),
+}
+
+impl FieldPath {
+    /// Parses a path of `.` separated field names, each optionally followed by `[index]` for array elements
+    pub fn new(path: &str) -> Result<FieldPath, FieldPathError> {
+        let invalid = |reason: &str| FieldPathError::InvalidPath {
+            path: path.to_owned(),
+            reason: reason.to_owned(),
+        };
+        let mut segments = vec![];
+        for (part_num, part) in path.split('.').enumerate() {
+            let (name, mut indices) = part.split_at(part.find('[').unwrap_or(part.len()));
+            if name.is_empty() {
+                // Only the very start of a path may index straight into the message, e.g. `[0].x` for a tuple
+                if part_num != 0 || indices.is_empty() {
+                    return Err(invalid("empty field name"));
+                }
+            } else if !name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') {
+                return Err(invalid(
+                    "field names may only contain letters, digits and '_'",
+                ));
+            } else {
+                segments.push(Segment::Field(name.to_owned()));
+            }
+            while !indices.is_empty() {
+                let Some(end) = indices.find(']') else {
+                    return Err(invalid("missing ']'"));
+                };
+                let Ok(index) = indices[1..end].parse() else {
+                    return Err(invalid("array indices must be non-negative integers"));
+                };
+                segments.push(Segment::Index(index));
+                indices = &indices[end + 1..];
+                if !indices.is_empty() && !indices.starts_with('[') {
+                    return Err(invalid("unexpected characters after ']'"));
+                }
+            }
+        }
+        Ok(FieldPath {
+            path: path.to_owned(),
+            segments,
+        })
+    }
+
+    /// Returns the empty path, which selects the whole message
+    pub fn root() -> FieldPath {
+        FieldPath {
+            path: String::new(),
+            segments: vec![],
+        }
+    }
+
+    /// Gets the value of the selected field from a message.
+    ///
+    /// When the path ends at a nested message or array the whole value is returned.
+    pub fn extract<T: Serialize + ?Sized>(&self, msg: &T) -> Result<FieldValue, FieldPathError> {
+        msg.serialize(Select {
+            path: &self.segments,
+        })
+        .map_err(|e| match e {
+            FieldPathError::NotFound(_) => FieldPathError::NotFound(self.path.clone()),
+            e => e,
+        })
+    }
+}
+
+impl std::str::FromStr for FieldPath {
+    type Err = FieldPathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        FieldPath::new(s)
+    }
+}
+
+impl fmt::Display for FieldPath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.path.fmt(f)
+    }
+}
+
+/// A value extracted from a message by [FieldPath::extract].
+#[derive(Debug, Clone, PartialEq)]
+pub enum FieldValue {
+    Bool(bool),
+    Int(i64),
+    UInt(u64),
+    Float(f64),
+    String(String),
+    Sequence(Vec<FieldValue>),
+    /// A nested message, as its field names and values in order
+    Struct(Vec<(String, FieldValue)>),
+}
+
+impl FieldValue {
+    /// Converts numeric and boolean values to a float, convenient for forwarding telemetry
+    pub fn as_f64(&self) -> Option<f64> {
+        match self {
+            FieldValue::Bool(v) => Some(if *v { 1.0 } else { 0.0 }),
+            FieldValue::Int(v) => Some(*v as f64),
+            FieldValue::UInt(v) => Some(*v as f64),
+            FieldValue::Float(v) => Some(*v),
+            _ => None,
+        }
+    }
+}
+
+/// Can print the value with `{}` syntax, nested values are written inline like `{x: 1, y: [2, 3]}`
+impl fmt::Display for FieldValue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FieldValue::Bool(v) => v.fmt(f),
+            FieldValue::Int(v) => v.fmt(f),
+            FieldValue::UInt(v) => v.fmt(f),
+            FieldValue::Float(v) => v.fmt(f),
+            FieldValue::String(v) => write!(f, "{v:?}"),
+            FieldValue::Sequence(values) => {
+                write!(f, "[")?;
+                for (i, value) in values.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{value}")?;
+                }
+                write!(f, "]")
+            }
+            FieldValue::Struct(fields) => {
+                write!(f, "{{")?;
+                for (i, (name, value)) in fields.iter().enumerate() {
+                    if i != 0 {
+                        write!(f, ", ")?;
+                    }
+                    write!(f, "{name}: {value}")?;
+                }
+                write!(f, "}}")
+            }
+        }
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum FieldPathError {
+    #[error("Invalid field path {path:?}: {reason}")]
+    InvalidPath { path: String, reason: String },
+    #[error("Field path {0:?} does not exist in the message")]
+    NotFound(String),
+    #[error("Failed to serialize message: {0}")]
+    Serialize(String),
+}
+
+impl ser::Error for FieldPathError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        FieldPathError::Serialize(msg.to_string())
+    }
+}
+
+// The path is filled in by FieldPath::extract
+fn not_found() -> FieldPathError {
+    FieldPathError::NotFound(String::new())
+}
+
+// Serializer which walks down the path, skipping every field not on it
+struct Select<'a> {
+    path: &'a [Segment],
+}
+
+// Scalars can only be selected once the path has been used up
+macro_rules! select_scalar {
+    ($($method:ident: $ty:ty),*) => {
+        $(
+            fn $method(self, v: $ty) -> Result<FieldValue, FieldPathError> {
+                if self.path.is_empty() {
+                    Capture.$method(v)
+                } else {
+                    Err(not_found())
+                }
+            }
+        )*
+    };
+}
+
+impl<'a> ser::Serializer for Select<'a> {
+    type Ok = FieldValue;
+    type Error = FieldPathError;
+    type SerializeSeq = SelectSeq<'a>;
+    type SerializeTuple = SelectSeq<'a>;
+    type SerializeTupleStruct = SelectSeq<'a>;
+    type SerializeTupleVariant = SelectSeq<'a>;
+    type SerializeMap = SelectMap<'a>;
+    type SerializeStruct = SelectStruct<'a>;
+    type SerializeStructVariant = SelectStruct<'a>;
+
+    select_scalar!(
+        serialize_bool: bool,
+        serialize_i8: i8,
+        serialize_i16: i16,
+        serialize_i32: i32,
+        serialize_i64: i64,
+        serialize_u8: u8,
+        serialize_u16: u16,
+        serialize_u32: u32,
+        serialize_u64: u64,
+        serialize_f32: f32,
+        serialize_f64: f64,
+        serialize_char: char,
+        serialize_str: &str,
+        serialize_unit_struct: &'static str
+    );
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<FieldValue, FieldPathError> {
+        match self.path {
+            [] => Capture.serialize_bytes(v),
+            [Segment::Index(i)] => v
+                .get(*i)
+                .map(|b| FieldValue::UInt(*b as u64))
+                .ok_or_else(not_found),
+            _ => Err(not_found()),
+        }
+    }
+
+    fn serialize_none(self) -> Result<FieldValue, FieldPathError> {
+        Err(not_found())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(
+        self,
+        value: &T,
+    ) -> Result<FieldValue, FieldPathError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<FieldValue, FieldPathError> {
+        Err(not_found())
+    }
+
+    fn serialize_unit_variant(
+        self,
+        name: &'static str,
+        variant_index: u32,
+        variant: &'static str,
+    ) -> Result<FieldValue, FieldPathError> {
+        if self.path.is_empty() {
+            Capture.serialize_unit_variant(name, variant_index, variant)
+        } else {
+            Err(not_found())
+        }
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<FieldValue, FieldPathError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<FieldValue, FieldPathError> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<SelectSeq<'a>, FieldPathError> {
+        SelectSeq::new(self.path, len)
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<SelectSeq<'a>, FieldPathError> {
+        SelectSeq::new(self.path, Some(len))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SelectSeq<'a>, FieldPathError> {
+        SelectSeq::new(self.path, Some(len))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> Result<SelectSeq<'a>, FieldPathError> {
+        SelectSeq::new(self.path, Some(len))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, FieldPathError> {
+        SelectStruct::new(self.path, len.unwrap_or(0)).map(SelectMap::new)
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<SelectStruct<'a>, FieldPathError> {
+        SelectStruct::new(self.path, len)
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> Result<SelectStruct<'a>, FieldPathError> {
+        SelectStruct::new(self.path, len)
+    }
+}
+
+// Either captures a whole array when the path ends at it, or only the selected element
+enum SelectSeq<'a> {
+    Capture(CaptureSeq),
+    Element {
+        index: usize,
+        rest: &'a [Segment],
+        current: usize,
+        found: Option<FieldValue>,
+    },
+}
+
+impl<'a> SelectSeq<'a> {
+    fn new(path: &'a [Segment], len: Option<usize>) -> Result<Self, FieldPathError> {
+        match path {
+            [] => Ok(SelectSeq::Capture(CaptureSeq::new(len))),
+            [Segment::Index(index), rest @ ..] => Ok(SelectSeq::Element {
+                index: *index,
+                rest,
+                current: 0,
+                found: None,
+            }),
+            _ => Err(not_found()),
+        }
+    }
+
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FieldPathError> {
+        match self {
+            SelectSeq::Capture(seq) => seq.element(value),
+            SelectSeq::Element {
+                index,
+                rest,
+                current,
+                found,
+            } => {
+                if current == index {
+                    *found = Some(value.serialize(Select { path: rest })?);
+                }
+                *current += 1;
+                Ok(())
+            }
+        }
+    }
+
+    fn finish(self) -> Result<FieldValue, FieldPathError> {
+        match self {
+            SelectSeq::Capture(seq) => Ok(FieldValue::Sequence(seq.0)),
+            SelectSeq::Element { found, .. } => found.ok_or_else(not_found),
+        }
+    }
+}
+
+macro_rules! impl_select_seq {
+    ($($trait:ident: $method:ident),*) => {
+        $(
+            impl ser::$trait for SelectSeq<'_> {
+                type Ok = FieldValue;
+                type Error = FieldPathError;
+
+                fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FieldPathError> {
+                    self.element(value)
+                }
+
+                fn end(self) -> Result<FieldValue, FieldPathError> {
+                    self.finish()
+                }
+            }
+        )*
+    };
+}
+
+impl_select_seq!(
+    SerializeSeq: serialize_element,
+    SerializeTuple: serialize_element,
+    SerializeTupleStruct: serialize_field,
+    SerializeTupleVariant: serialize_field
+);
+
+// Either captures a whole struct when the path ends at it, or only the selected field
+enum SelectStruct<'a> {
+    Capture(CaptureStruct),
+    Field {
+        name: &'a str,
+        rest: &'a [Segment],
+        found: Option<FieldValue>,
+    },
+}
+
+impl<'a> SelectStruct<'a> {
+    fn new(path: &'a [Segment], len: usize) -> Result<Self, FieldPathError> {
+        match path {
+            [] => Ok(SelectStruct::Capture(CaptureStruct::new(len))),
+            [Segment::Field(name), rest @ ..] => Ok(SelectStruct::Field {
+                name,
+                rest,
+                found: None,
+            }),
+            _ => Err(not_found()),
+        }
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), FieldPathError> {
+        match self {
+            SelectStruct::Capture(fields) => fields.field(key, value),
+            SelectStruct::Field { name, rest, found } => {
+                if *name == key {
+                    *found = Some(value.serialize(Select { path: rest })?);
+                }
+                Ok(())
+            }
+        }
+    }
+
+    fn finish(self) -> Result<FieldValue, FieldPathError> {
+        match self {
+            SelectStruct::Capture(fields) => Ok(FieldValue::Struct(fields.0)),
+            SelectStruct::Field { found, .. } => found.ok_or_else(not_found),
+        }
+    }
+}
+
+impl ser::SerializeStruct for SelectStruct<'_> {
+    type Ok = FieldValue;
+    type Error = FieldPathError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), FieldPathError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<FieldValue, FieldPathError> {
+        self.finish()
+    }
+}
+
+impl ser::SerializeStructVariant for SelectStruct<'_> {
+    type Ok = FieldValue;
+    type Error = FieldPathError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), FieldPathError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<FieldValue, FieldPathError> {
+        self.finish()
+    }
+}
+
+// Maps don't appear in generated messages, but do in dynamic ones like a serde_json::Value
+// They are treated as structs with their string keys as field names
+struct SelectMap<'a> {
+    fields: SelectStruct<'a>,
+    key: Option<String>,
+}
+
+impl<'a> SelectMap<'a> {
+    fn new(fields: SelectStruct<'a>) -> Self {
+        SelectMap { fields, key: None }
+    }
+}
+
+impl ser::SerializeMap for SelectMap<'_> {
+    type Ok = FieldValue;
+    type Error = FieldPathError;
+
+    fn serialize_key<T: Serialize + ?Sized>(&mut self, key: &T) -> Result<(), FieldPathError> {
+        match key.serialize(Capture)? {
+            FieldValue::String(key) => {
+                self.key = Some(key);
+                Ok(())
+            }
+            _ => Err(ser::Error::custom(
+                "only maps with string keys are supported",
+            )),
+        }
+    }
+
+    fn serialize_value<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FieldPathError> {
+        let key = self
+            .key
+            .take()
+            .ok_or_else(|| ser::Error::custom("map value serialized before its key"))?;
+        self.fields.field(&key, value)
+    }
+
+    fn end(self) -> Result<FieldValue, FieldPathError> {
+        self.fields.finish()
+    }
+}
+
+// Serializer which converts a whole value into a FieldValue
+struct Capture;
+
+impl ser::Serializer for Capture {
+    type Ok = FieldValue;
+    type Error = FieldPathError;
+    type SerializeSeq = CaptureSeq;
+    type SerializeTuple = CaptureSeq;
+    type SerializeTupleStruct = CaptureSeq;
+    type SerializeTupleVariant = CaptureSeq;
+    type SerializeMap = SelectMap<'static>;
+    type SerializeStruct = CaptureStruct;
+    type SerializeStructVariant = CaptureStruct;
+
+    fn serialize_bool(self, v: bool) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::Bool(v))
+    }
+
+    fn serialize_i8(self, v: i8) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::Int(v.into()))
+    }
+
+    fn serialize_i16(self, v: i16) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::Int(v.into()))
+    }
+
+    fn serialize_i32(self, v: i32) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::Int(v.into()))
+    }
+
+    fn serialize_i64(self, v: i64) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::Int(v))
+    }
+
+    fn serialize_u8(self, v: u8) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::UInt(v.into()))
+    }
+
+    fn serialize_u16(self, v: u16) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::UInt(v.into()))
+    }
+
+    fn serialize_u32(self, v: u32) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::UInt(v.into()))
+    }
+
+    fn serialize_u64(self, v: u64) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::UInt(v))
+    }
+
+    fn serialize_f32(self, v: f32) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::Float(v.into()))
+    }
+
+    fn serialize_f64(self, v: f64) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::Float(v))
+    }
+
+    fn serialize_char(self, v: char) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::String(v.to_string()))
+    }
+
+    fn serialize_str(self, v: &str) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::String(v.to_owned()))
+    }
+
+    fn serialize_bytes(self, v: &[u8]) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::Sequence(
+            v.iter().map(|b| FieldValue::UInt(*b as u64)).collect(),
+        ))
+    }
+
+    fn serialize_none(self) -> Result<FieldValue, FieldPathError> {
+        Err(not_found())
+    }
+
+    fn serialize_some<T: Serialize + ?Sized>(
+        self,
+        value: &T,
+    ) -> Result<FieldValue, FieldPathError> {
+        value.serialize(self)
+    }
+
+    fn serialize_unit(self) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::Struct(vec![]))
+    }
+
+    fn serialize_unit_struct(self, _name: &'static str) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::Struct(vec![]))
+    }
+
+    fn serialize_unit_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        variant: &'static str,
+    ) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::String(variant.to_owned()))
+    }
+
+    fn serialize_newtype_struct<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        value: &T,
+    ) -> Result<FieldValue, FieldPathError> {
+        value.serialize(self)
+    }
+
+    fn serialize_newtype_variant<T: Serialize + ?Sized>(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        value: &T,
+    ) -> Result<FieldValue, FieldPathError> {
+        value.serialize(self)
+    }
+
+    fn serialize_seq(self, len: Option<usize>) -> Result<CaptureSeq, FieldPathError> {
+        Ok(CaptureSeq::new(len))
+    }
+
+    fn serialize_tuple(self, len: usize) -> Result<CaptureSeq, FieldPathError> {
+        Ok(CaptureSeq::new(Some(len)))
+    }
+
+    fn serialize_tuple_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<CaptureSeq, FieldPathError> {
+        Ok(CaptureSeq::new(Some(len)))
+    }
+
+    fn serialize_tuple_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> Result<CaptureSeq, FieldPathError> {
+        Ok(CaptureSeq::new(Some(len)))
+    }
+
+    fn serialize_map(self, len: Option<usize>) -> Result<Self::SerializeMap, FieldPathError> {
+        Ok(SelectMap::new(SelectStruct::Capture(CaptureStruct::new(
+            len.unwrap_or(0),
+        ))))
+    }
+
+    fn serialize_struct(
+        self,
+        _name: &'static str,
+        len: usize,
+    ) -> Result<CaptureStruct, FieldPathError> {
+        Ok(CaptureStruct::new(len))
+    }
+
+    fn serialize_struct_variant(
+        self,
+        _name: &'static str,
+        _variant_index: u32,
+        _variant: &'static str,
+        len: usize,
+    ) -> Result<CaptureStruct, FieldPathError> {
+        Ok(CaptureStruct::new(len))
+    }
+}
+
+struct CaptureSeq(Vec<FieldValue>);
+
+impl CaptureSeq {
+    fn new(len: Option<usize>) -> Self {
+        CaptureSeq(Vec::with_capacity(len.unwrap_or(0)))
+    }
+
+    fn element<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FieldPathError> {
+        self.0.push(value.serialize(Capture)?);
+        Ok(())
+    }
+}
+
+macro_rules! impl_capture_seq {
+    ($($trait:ident: $method:ident),*) => {
+        $(
+            impl ser::$trait for CaptureSeq {
+                type Ok = FieldValue;
+                type Error = FieldPathError;
+
+                fn $method<T: Serialize + ?Sized>(&mut self, value: &T) -> Result<(), FieldPathError> {
+                    self.element(value)
+                }
+
+                fn end(self) -> Result<FieldValue, FieldPathError> {
+                    Ok(FieldValue::Sequence(self.0))
+                }
+            }
+        )*
+    };
+}
+
+impl_capture_seq!(
+    SerializeSeq: serialize_element,
+    SerializeTuple: serialize_element,
+    SerializeTupleStruct: serialize_field,
+    SerializeTupleVariant: serialize_field
+);
+
+struct CaptureStruct(Vec<(String, FieldValue)>);
+
+impl CaptureStruct {
+    fn new(len: usize) -> Self {
+        CaptureStruct(Vec::with_capacity(len))
+    }
+
+    fn field<T: Serialize + ?Sized>(&mut self, key: &str, value: &T) -> Result<(), FieldPathError> {
+        self.0.push((key.to_owned(), value.serialize(Capture)?));
+        Ok(())
+    }
+}
+
+impl ser::SerializeStruct for CaptureStruct {
+    type Ok = FieldValue;
+    type Error = FieldPathError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), FieldPathError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::Struct(self.0))
+    }
+}
+
+impl ser::SerializeStructVariant for CaptureStruct {
+    type Ok = FieldValue;
+    type Error = FieldPathError;
+
+    fn serialize_field<T: Serialize + ?Sized>(
+        &mut self,
+        key: &'static str,
+        value: &T,
+    ) -> Result<(), FieldPathError> {
+        self.field(key, value)
+    }
+
+    fn end(self) -> Result<FieldValue, FieldPathError> {
+        Ok(FieldValue::Struct(self.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(serde::Serialize)]
+    struct Time {
+        secs: u32,
+        nsecs: u32,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Header {
+        seq: u32,
+        stamp: Time,
+        frame_id: String,
+    }
+
+    #[derive(serde::Serialize)]
+    struct Scan {
+        header: Header,
+        ranges: Vec<f32>,
+        covariance: [f64; 3],
+        #[serde(with = "serde_bytes_shim")]
+        data: Vec<u8>,
+    }
+
+    // Stands in for serde_bytes so the bytes path is exercised without another dependency
+    mod serde_bytes_shim {
+        pub fn serialize<S: serde::Serializer>(v: &[u8], s: S) -> Result<S::Ok, S::Error> {
+            s.serialize_bytes(v)
+        }
+    }
+
+    fn scan() -> Scan {
+        Scan {
+            header: Header {
+                seq: 3,
+                stamp: Time { secs: 10, nsecs: 5 },
+                frame_id: "laser".to_owned(),
+            },
+            ranges: vec![1.0, 2.5, 4.0],
+            covariance: [0.1, 0.2, 0.3],
+            data: vec![7, 8, 9],
+        }
+    }
+
+    #[test]
+    fn extracts_fields() {
+        let msg = scan();
+        let extract = |path: &str| FieldPath::new(path).unwrap().extract(&msg).unwrap();
+        assert_eq!(extract("header.seq"), FieldValue::UInt(3));
+        assert_eq!(
+            extract("header.frame_id"),
+            FieldValue::String("laser".to_owned())
+        );
+        assert_eq!(extract("ranges[1]"), FieldValue::Float(2.5));
+        assert_eq!(extract("covariance[2]"), FieldValue::Float(0.3));
+        assert_eq!(extract("data[1]"), FieldValue::UInt(8));
+        assert_eq!(
+            extract("header.stamp"),
+            FieldValue::Struct(vec![
+                ("secs".to_owned(), FieldValue::UInt(10)),
+                ("nsecs".to_owned(), FieldValue::UInt(5)),
+            ])
+        );
+        assert_eq!(extract("header.stamp").to_string(), "{secs: 10, nsecs: 5}");
+        assert_eq!(extract("ranges").to_string(), "[1, 2.5, 4]");
+        assert_eq!(extract("header.stamp.nsecs").as_f64(), Some(5.0));
+    }
+
+    #[test]
+    fn reports_missing_fields() {
+        let msg = scan();
+        for path in [
+            "header.missing",
+            "ranges[3]",
+            "header[0]",
+            "ranges.x",
+            "header.seq.x",
+        ] {
+            assert_eq!(
+                FieldPath::new(path).unwrap().extract(&msg),
+                Err(FieldPathError::NotFound(path.to_owned()))
+            );
+        }
+    }
+
+    #[test]
+    fn extracts_fields_of_json_values() {
+        let msg = serde_json::json!({
+            "header": { "seq": 3, "frame_id": "laser" },
+            "ranges": [1.0, 2.5],
+        });
+        let extract = |path: &str| FieldPath::new(path).unwrap().extract(&msg);
+        assert_eq!(extract("header.seq"), Ok(FieldValue::UInt(3)));
+        assert_eq!(extract("ranges[1]"), Ok(FieldValue::Float(2.5)));
+        assert_eq!(
+            extract("header").unwrap().to_string(),
+            "{frame_id: \"laser\", seq: 3}"
+        );
+        assert_eq!(
+            extract("header.stamp"),
+            Err(FieldPathError::NotFound("header.stamp".to_owned()))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_paths() {
+        for path in [
+            "", "a..b", "a.", "a[", "a[-1]", "a[x]", "a[0]b", "a-b", "a.[0]",
+        ] {
+            assert!(
+                matches!(
+                    FieldPath::new(path),
+                    Err(FieldPathError::InvalidPath { .. })
+                ),
+                "{path}"
+            );
+        }
+        assert!(FieldPath::new("[0].x").is_ok());
+        assert!(FieldPath::new("poses[1][2].position").is_ok());
+    }
+}
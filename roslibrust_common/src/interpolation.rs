@@ -0,0 +1,354 @@
+use std::collections::VecDeque;
+use std::time::{Duration, SystemTime};
+
+/// Values which can be interpolated between two samples.
+///
+/// Implemented for floats, and element-wise for arrays and `Vec`s of them, so e.g. the positions of a joint state
+/// can be buffered as a `Vec<f64>`. Implement it for estimator state or messages, using [UnitQuaternion] for
+/// orientations, to buffer those.
+pub trait Interpolate: Clone {
+    /// Returns the value a fraction `t`, between 0 and 1, of the way from `self` to `other`
+    fn interpolate(&self, other: &Self, t: f64) -> Self;
+}
+
+impl Interpolate for f64 {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        self + (other - self) * t
+    }
+}
+
+impl Interpolate for f32 {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        self + (other - self) * t as f32
+    }
+}
+
+impl<T: Interpolate, const N: usize> Interpolate for [T; N] {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        std::array::from_fn(|i| self[i].interpolate(&other[i], t))
+    }
+}
+
+impl<T: Interpolate> Interpolate for Vec<T> {
+    /// Interpolates element-wise. If the lengths differ the nearest of the two is returned, as there is nothing to
+    /// interpolate the extra elements with.
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        if self.len() != other.len() {
+            return if t < 0.5 { self.clone() } else { other.clone() };
+        }
+        self.iter()
+            .zip(other)
+            .map(|(a, b)| a.interpolate(b, t))
+            .collect()
+    }
+}
+
+/// An orientation as a unit quaternion, laid out like a `geometry_msgs/Quaternion`. Interpolated spherically.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UnitQuaternion {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+    pub w: f64,
+}
+
+impl UnitQuaternion {
+    pub const IDENTITY: UnitQuaternion = UnitQuaternion {
+        x: 0.0,
+        y: 0.0,
+        z: 0.0,
+        w: 1.0,
+    };
+
+    /// Creates a quaternion from its components, normalizing it
+    pub fn new(x: f64, y: f64, z: f64, w: f64) -> Self {
+        UnitQuaternion { x, y, z, w }.normalized()
+    }
+
+    /// The rotation of `angle` radians about `axis`
+    pub fn from_axis_angle(axis: [f64; 3], angle: f64) -> Self {
+        let norm = axis.iter().map(|a| a * a).sum::<f64>().sqrt();
+        if norm == 0.0 {
+            return Self::IDENTITY;
+        }
+        let s = (angle / 2.0).sin() / norm;
+        UnitQuaternion {
+            x: axis[0] * s,
+            y: axis[1] * s,
+            z: axis[2] * s,
+            w: (angle / 2.0).cos(),
+        }
+    }
+
+    /// Spherical linear interpolation, rotating at a constant rate along the shortest path from `self` to `other`
+    pub fn slerp(&self, other: &Self, t: f64) -> Self {
+        let mut other = *other;
+        let mut dot = self.dot(&other);
+        // q and -q are the same rotation, go the short way around
+        if dot < 0.0 {
+            other = other.scaled(-1.0);
+            dot = -dot;
+        }
+        // Nearly identical rotations, where slerp is numerically unstable and linear interpolation is accurate
+        if dot > 0.9995 {
+            return self
+                .add(&other.add(&self.scaled(-1.0)).scaled(t))
+                .normalized();
+        }
+        let theta_0 = dot.acos();
+        let theta = theta_0 * t;
+        let s1 = theta.sin() / theta_0.sin();
+        let s0 = theta.cos() - dot * s1;
+        self.scaled(s0).add(&other.scaled(s1)).normalized()
+    }
+
+    fn dot(&self, other: &Self) -> f64 {
+        self.x * other.x + self.y * other.y + self.z * other.z + self.w * other.w
+    }
+
+    fn scaled(&self, s: f64) -> Self {
+        UnitQuaternion {
+            x: self.x * s,
+            y: self.y * s,
+            z: self.z * s,
+            w: self.w * s,
+        }
+    }
+
+    fn add(&self, other: &Self) -> Self {
+        UnitQuaternion {
+            x: self.x + other.x,
+            y: self.y + other.y,
+            z: self.z + other.z,
+            w: self.w + other.w,
+        }
+    }
+
+    fn normalized(&self) -> Self {
+        let norm = self.dot(self).sqrt();
+        if norm == 0.0 {
+            return Self::IDENTITY;
+        }
+        self.scaled(1.0 / norm)
+    }
+}
+
+impl Interpolate for UnitQuaternion {
+    fn interpolate(&self, other: &Self, t: f64) -> Self {
+        self.slerp(other, t)
+    }
+}
+
+#[derive(thiserror::Error, Debug, Clone, PartialEq)]
+pub enum InterpolationError {
+    #[error("No samples have been buffered")]
+    Empty,
+    #[error("Requested time {requested:?} is before the earliest buffered sample at {earliest:?}")]
+    TooOld {
+        requested: SystemTime,
+        earliest: SystemTime,
+    },
+    #[error("Requested time {requested:?} is after the latest buffered sample at {latest:?}")]
+    TooNew {
+        requested: SystemTime,
+        latest: SystemTime,
+    },
+}
+
+/// A time ordered buffer of stamped samples, e.g. odometry or joint states, which can be queried at any time
+/// between the oldest and newest sample.
+///
+/// Samples older than `max_age` before the newest sample are dropped. Header stamps convert to [SystemTime] with
+/// `try_into()`, and the buffer follows the same convention as [crate::clock::Clock].
+/// ```
+/// use roslibrust_common::interpolation::StampedBuffer;
+/// use std::time::{Duration, UNIX_EPOCH};
+///
+/// let mut positions = StampedBuffer::new(Duration::from_secs(10));
+/// positions.insert(UNIX_EPOCH + Duration::from_secs(1), vec![0.0, 1.0]);
+/// positions.insert(UNIX_EPOCH + Duration::from_secs(2), vec![1.0, 3.0]);
+/// let halfway = positions.get(UNIX_EPOCH + Duration::from_millis(1500)).unwrap();
+/// assert_eq!(halfway, vec![0.5, 2.0]);
+/// ```
+#[derive(Clone, Debug)]
+pub struct StampedBuffer<T> {
+    // Ordered by stamp, oldest first
+    samples: VecDeque<(SystemTime, T)>,
+    max_age: Duration,
+}
+
+impl<T> StampedBuffer<T> {
+    pub fn new(max_age: Duration) -> Self {
+        Self {
+            samples: VecDeque::new(),
+            max_age,
+        }
+    }
+
+    /// Adds a sample, replacing any sample with exactly the same stamp.
+    ///
+    /// Samples may arrive out of order. A sample older than `max_age` before the newest one is dropped.
+    pub fn insert(&mut self, stamp: SystemTime, value: T) {
+        let index = self.samples.partition_point(|(s, _)| *s < stamp);
+        match self.samples.get_mut(index) {
+            Some((s, existing)) if *s == stamp => *existing = value,
+            _ => self.samples.insert(index, (stamp, value)),
+        }
+        if let Some(cutoff) = self
+            .latest()
+            .and_then(|(latest, _)| latest.checked_sub(self.max_age))
+        {
+            while self.samples.front().is_some_and(|(s, _)| *s < cutoff) {
+                self.samples.pop_front();
+            }
+        }
+    }
+
+    /// The oldest sample
+    pub fn earliest(&self) -> Option<(SystemTime, &T)> {
+        self.samples.front().map(|(s, v)| (*s, v))
+    }
+
+    /// The newest sample
+    pub fn latest(&self) -> Option<(SystemTime, &T)> {
+        self.samples.back().map(|(s, v)| (*s, v))
+    }
+
+    /// The sample stamped closest to `time`, for values which can't be interpolated
+    pub fn nearest(&self, time: SystemTime) -> Option<(SystemTime, &T)> {
+        let index = self.samples.partition_point(|(s, _)| *s < time);
+        let distance = |s: SystemTime| {
+            s.duration_since(time)
+                .or_else(|_| time.duration_since(s))
+                .unwrap_or_default()
+        };
+        [index.checked_sub(1), Some(index)]
+            .into_iter()
+            .flatten()
+            .filter_map(|i| self.samples.get(i))
+            .min_by_key(|(s, _)| distance(*s))
+            .map(|(s, v)| (*s, v))
+    }
+
+    /// Iterates over the samples, oldest first
+    pub fn iter(&self) -> impl Iterator<Item = (SystemTime, &T)> {
+        self.samples.iter().map(|(s, v)| (*s, v))
+    }
+
+    pub fn len(&self) -> usize {
+        self.samples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.samples.is_empty()
+    }
+
+    /// Removes every sample, e.g. after the clock has jumped backwards
+    pub fn clear(&mut self) {
+        self.samples.clear();
+    }
+}
+
+impl<T: Interpolate> StampedBuffer<T> {
+    /// The value at `time`, interpolated between the samples either side of it.
+    ///
+    /// Fails rather than extrapolating if `time` is outside the buffered samples.
+    pub fn get(&self, time: SystemTime) -> Result<T, InterpolationError> {
+        let (earliest, _) = self.earliest().ok_or(InterpolationError::Empty)?;
+        let (latest, _) = self.latest().ok_or(InterpolationError::Empty)?;
+        if time < earliest {
+            return Err(InterpolationError::TooOld {
+                requested: time,
+                earliest,
+            });
+        }
+        if time > latest {
+            return Err(InterpolationError::TooNew {
+                requested: time,
+                latest,
+            });
+        }
+        let index = self.samples.partition_point(|(s, _)| *s < time);
+        let (after_stamp, after) = &self.samples[index];
+        if *after_stamp == time || index == 0 {
+            return Ok(after.clone());
+        }
+        let (before_stamp, before) = &self.samples[index - 1];
+        let span = after_stamp
+            .duration_since(*before_stamp)
+            .unwrap_or_default()
+            .as_secs_f64();
+        let offset = time
+            .duration_since(*before_stamp)
+            .unwrap_or_default()
+            .as_secs_f64();
+        Ok(before.interpolate(after, offset / span))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::time::UNIX_EPOCH;
+
+    fn at(millis: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn buffer_interpolates_between_out_of_order_samples() {
+        let mut buffer = StampedBuffer::new(Duration::from_secs(10));
+        assert_eq!(buffer.get(at(0)), Err(InterpolationError::Empty));
+        buffer.insert(at(2000), 20.0);
+        buffer.insert(at(1000), 10.0);
+        buffer.insert(at(4000), 0.0);
+
+        assert_eq!(buffer.get(at(1000)), Ok(10.0));
+        assert_eq!(buffer.get(at(1250)), Ok(12.5));
+        assert_eq!(buffer.get(at(3000)), Ok(10.0));
+        assert_eq!(buffer.get(at(4000)), Ok(0.0));
+        assert!(matches!(
+            buffer.get(at(500)),
+            Err(InterpolationError::TooOld { .. })
+        ));
+        assert!(matches!(
+            buffer.get(at(4001)),
+            Err(InterpolationError::TooNew { .. })
+        ));
+        assert_eq!(buffer.nearest(at(2900)), Some((at(2000), &20.0)));
+        assert_eq!(buffer.nearest(at(3100)), Some((at(4000), &0.0)));
+    }
+
+    #[test]
+    fn buffer_drops_samples_older_than_max_age() {
+        let mut buffer = StampedBuffer::new(Duration::from_secs(1));
+        buffer.insert(at(0), 0.0);
+        buffer.insert(at(1000), 1.0);
+        assert_eq!(buffer.len(), 2);
+        buffer.insert(at(1500), 1.5);
+        assert_eq!(buffer.earliest(), Some((at(1000), &1.0)));
+        // Too old to be kept once it arrives
+        buffer.insert(at(100), 0.1);
+        assert_eq!(buffer.len(), 2);
+        // Replaces the existing sample
+        buffer.insert(at(1500), 2.0);
+        assert_eq!(buffer.latest(), Some((at(1500), &2.0)));
+    }
+
+    #[test]
+    fn quaternions_are_interpolated_spherically() {
+        let start = UnitQuaternion::IDENTITY;
+        let end = UnitQuaternion::from_axis_angle([0.0, 0.0, 1.0], std::f64::consts::FRAC_PI_2);
+        let halfway = start.interpolate(&end, 0.5);
+        let expected =
+            UnitQuaternion::from_axis_angle([0.0, 0.0, 1.0], std::f64::consts::FRAC_PI_4);
+        assert!((halfway.z - expected.z).abs() < 1e-9);
+        assert!((halfway.w - expected.w).abs() < 1e-9);
+
+        // The negated quaternion is the same rotation, so interpolation takes the short way around
+        let negated = UnitQuaternion::new(-end.x, -end.y, -end.z, -end.w);
+        let halfway = start.interpolate(&negated, 0.5);
+        assert!((halfway.z - expected.z).abs() < 1e-9);
+        assert!((halfway.w - expected.w).abs() < 1e-9);
+    }
+}
@@ -38,6 +38,17 @@ pub enum Error {
     /// When a topic name is used that isn't a valid topic name.
     #[error("Name does not meet ROS requirements: {0}")]
     InvalidName(String),
+    /// Returned when a message (or a length prefix claiming a message) exceeds the configured maximum message size.
+    ///
+    /// Backends check sizes before allocating, so a corrupt length prefix or a hostile peer can't trigger
+    /// multi-gigabyte allocations. Sizes are in bytes of serialized message.
+    #[error("Message of {size} bytes exceeds the maximum message size of {max} bytes")]
+    MessageTooLarge { size: usize, max: usize },
+    /// Returned when an operation is aborted through the [CancellationToken] passed to it.
+    ///
+    /// Backends clean up any state associated with the operation before returning this.
+    #[error("Operation was cancelled")]
+    Cancelled,
     /// Backends are free to return this error if they encounter any error that doesn't cleanly fit in the other categories.
     #[error(transparent)]
     Unexpected(#[from] anyhow::Error),
@@ -46,6 +57,13 @@ pub enum Error {
 /// Generic result type used throughout roslibrust.
 pub type Result<T> = std::result::Result<T, Error>;
 
+/// Token used to abort long running operations, such as [Service::call_with_cancel].
+///
+/// Cancelling the token makes the operation return [Error::Cancelled] after cleaning up after itself, unlike
+/// dropping its future in a `select!` which can leave the backend waiting on a response nobody will read.
+/// Re-exported from [tokio_util](https://docs.rs/tokio-util/latest/tokio_util/sync/struct.CancellationToken.html).
+pub use tokio_util::sync::CancellationToken;
+
 /// The error type used by [ServiceFn]
 ///
 /// When writing service callbacks this is the error type that should be returned.
@@ -73,6 +91,59 @@ pub mod md5sum;
 pub mod traits;
 pub use traits::*; // Bring topic provider traits into root namespace
 
+/// Contains the subscriber adapters returned by [Subscribe::filter], [Subscribe::sample_every], and [Subscribe::map_msg].
+pub mod adapters;
+
+/// Contains [BoxPublisher] and [BoxSubscriber], type erased wrappers usable with any backend.
+pub mod boxed;
+pub use boxed::{BoxPublisher, BoxSubscriber};
+
+/// Contains [DynTopicProvider], a type erased [TopicProvider] for plugin style architectures.
+pub mod dyn_provider;
+pub use dyn_provider::{DynTopicProvider, DynTopicProviderBuilder};
+
+/// Contains [either::Either], for choosing between two backends at runtime.
+pub mod either;
+
+/// Contains [progress::call_service_with_progress] for long running services which report their progress on a topic.
+pub mod progress;
+
+/// Contains [rosout::RosoutReader] for reading and filtering the log output of other nodes.
+pub mod rosout;
+
+/// Contains [logging::RosoutLogger] and the [ros_info!] family of roscpp style logging macros.
+pub mod logging;
+
+/// Contains [lifecycle::LifecycleNode] for ROS2 style managed nodes on any backend.
+pub mod lifecycle;
+
+/// Contains [mux::Mux] for switching which of several topics is forwarded to an output, like topic_tools' mux.
+pub mod mux;
+
+/// Contains [teleop::Teleop] for smoothing velocity commands from input devices, with a deadman timeout.
+pub mod teleop;
+
 /// Contains the validation logic for topic, service, and action names.
 pub mod topic_name;
 pub use topic_name::*; // Bring topic name validation into root namespace
+
+/// Contains [field_path::FieldPath] for extracting individual fields out of messages by name.
+pub mod field_path;
+
+/// Contains general ROS graph resource names, with normalization, resolution and glob matching.
+pub mod names;
+
+/// Contains [params::ParameterProvider] for reading and writing parameters, and its ROS2 implementation.
+pub mod params;
+
+/// Contains [clock::Clock], a source of ROS time which may be the wall clock, simulated or driven by tests.
+pub mod clock;
+
+/// Contains [interpolation::StampedBuffer] for querying buffered stamped values at any time.
+pub mod interpolation;
+
+/// Contains [evolution::fit_to_definition] for decoding messages sent with an older or newer version of their type.
+pub mod evolution;
+
+/// Contains [export::CsvExporter] for flattening the numeric fields of messages into a table.
+pub mod export;
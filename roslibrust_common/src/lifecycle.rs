@@ -0,0 +1,704 @@
+use crate::{
+    Error, Publish, Result, RosMessageType, RosServiceType, ServiceProvider, TopicProvider,
+};
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+
+// ROS2 style managed nodes, built only on the generic traits so they work with every backend.
+// The message and service types mirror ROS2's lifecycle_msgs so that tooling written against those types
+// can drive a roslibrust node. Only the primary states and the transitions a client can request are modelled,
+// the intermediate states (configuring, activating, ...) are never observable from outside because a transition
+// holds the state lock while its callback runs.
+
+/// Equivalent of ROS2's `lifecycle_msgs/State`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct State {
+    pub id: u8,
+    pub label: String,
+}
+
+impl State {
+    pub const PRIMARY_STATE_UNKNOWN: u8 = 0;
+    pub const PRIMARY_STATE_UNCONFIGURED: u8 = 1;
+    pub const PRIMARY_STATE_INACTIVE: u8 = 2;
+    pub const PRIMARY_STATE_ACTIVE: u8 = 3;
+    pub const PRIMARY_STATE_FINALIZED: u8 = 4;
+    pub const TRANSITION_STATE_CONFIGURING: u8 = 10;
+    pub const TRANSITION_STATE_CLEANINGUP: u8 = 11;
+    pub const TRANSITION_STATE_SHUTTINGDOWN: u8 = 12;
+    pub const TRANSITION_STATE_ACTIVATING: u8 = 13;
+    pub const TRANSITION_STATE_DEACTIVATING: u8 = 14;
+    pub const TRANSITION_STATE_ERRORPROCESSING: u8 = 15;
+}
+
+impl RosMessageType for State {
+    const ROS_TYPE_NAME: &'static str = "lifecycle_msgs/State";
+    const MD5SUM: &'static str = "245b75da3b047157ebbb244e4013610e";
+    const DEFINITION: &'static str = r####"uint8 PRIMARY_STATE_UNKNOWN=0
+uint8 PRIMARY_STATE_UNCONFIGURED=1
+uint8 PRIMARY_STATE_INACTIVE=2
+uint8 PRIMARY_STATE_ACTIVE=3
+uint8 PRIMARY_STATE_FINALIZED=4
+uint8 TRANSITION_STATE_CONFIGURING=10
+uint8 TRANSITION_STATE_CLEANINGUP=11
+uint8 TRANSITION_STATE_SHUTTINGDOWN=12
+uint8 TRANSITION_STATE_ACTIVATING=13
+uint8 TRANSITION_STATE_DEACTIVATING=14
+uint8 TRANSITION_STATE_ERRORPROCESSING=15
+uint8 id
+string label"####;
+}
+
+/// Equivalent of ROS2's `lifecycle_msgs/Transition`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct Transition {
+    pub id: u8,
+    pub label: String,
+}
+
+impl Transition {
+    pub const TRANSITION_CREATE: u8 = 0;
+    pub const TRANSITION_CONFIGURE: u8 = 1;
+    pub const TRANSITION_CLEANUP: u8 = 2;
+    pub const TRANSITION_ACTIVATE: u8 = 3;
+    pub const TRANSITION_DEACTIVATE: u8 = 4;
+    pub const TRANSITION_UNCONFIGURED_SHUTDOWN: u8 = 5;
+    pub const TRANSITION_INACTIVE_SHUTDOWN: u8 = 6;
+    pub const TRANSITION_ACTIVE_SHUTDOWN: u8 = 7;
+    pub const TRANSITION_DESTROY: u8 = 8;
+}
+
+impl RosMessageType for Transition {
+    const ROS_TYPE_NAME: &'static str = "lifecycle_msgs/Transition";
+    const MD5SUM: &'static str = "95c88394daa904c8d8852ea5bc00e3c7";
+    const DEFINITION: &'static str = r####"uint8 TRANSITION_CREATE=0
+uint8 TRANSITION_CONFIGURE=1
+uint8 TRANSITION_CLEANUP=2
+uint8 TRANSITION_ACTIVATE=3
+uint8 TRANSITION_DEACTIVATE=4
+uint8 TRANSITION_UNCONFIGURED_SHUTDOWN=5
+uint8 TRANSITION_INACTIVE_SHUTDOWN=6
+uint8 TRANSITION_ACTIVE_SHUTDOWN=7
+uint8 TRANSITION_DESTROY=8
+uint8 id
+string label"####;
+}
+
+/// Equivalent of ROS2's `lifecycle_msgs/TransitionEvent`, published by a [LifecycleNode] after every transition
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct TransitionEvent {
+    /// Nanoseconds since the unix epoch at which the transition completed
+    pub timestamp: u64,
+    pub transition: Transition,
+    pub start_state: State,
+    pub goal_state: State,
+}
+
+impl RosMessageType for TransitionEvent {
+    const ROS_TYPE_NAME: &'static str = "lifecycle_msgs/TransitionEvent";
+    const MD5SUM: &'static str = "fae91fe2d175760a4451f7b72e103579";
+    const DEFINITION: &'static str = r####"uint64 timestamp
+Transition transition
+State start_state
+State goal_state
+================================================================================
+MSG: lifecycle_msgs/Transition
+uint8 TRANSITION_CREATE=0
+uint8 TRANSITION_CONFIGURE=1
+uint8 TRANSITION_CLEANUP=2
+uint8 TRANSITION_ACTIVATE=3
+uint8 TRANSITION_DEACTIVATE=4
+uint8 TRANSITION_UNCONFIGURED_SHUTDOWN=5
+uint8 TRANSITION_INACTIVE_SHUTDOWN=6
+uint8 TRANSITION_ACTIVE_SHUTDOWN=7
+uint8 TRANSITION_DESTROY=8
+uint8 id
+string label
+================================================================================
+MSG: lifecycle_msgs/State
+uint8 PRIMARY_STATE_UNKNOWN=0
+uint8 PRIMARY_STATE_UNCONFIGURED=1
+uint8 PRIMARY_STATE_INACTIVE=2
+uint8 PRIMARY_STATE_ACTIVE=3
+uint8 PRIMARY_STATE_FINALIZED=4
+uint8 TRANSITION_STATE_CONFIGURING=10
+uint8 TRANSITION_STATE_CLEANINGUP=11
+uint8 TRANSITION_STATE_SHUTTINGDOWN=12
+uint8 TRANSITION_STATE_ACTIVATING=13
+uint8 TRANSITION_STATE_DEACTIVATING=14
+uint8 TRANSITION_STATE_ERRORPROCESSING=15
+uint8 id
+string label"####;
+}
+
+/// Equivalent of ROS2's `lifecycle_msgs/ChangeState` service, served on `<node>/change_state`
+pub struct ChangeState {}
+
+impl RosServiceType for ChangeState {
+    const ROS_SERVICE_NAME: &'static str = "lifecycle_msgs/ChangeState";
+    const MD5SUM: &'static str = "70df4b012322fc5ae1328b2fa03d664f";
+    type Request = ChangeStateRequest;
+    type Response = ChangeStateResponse;
+}
+
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct ChangeStateRequest {
+    /// Only the id is used, the label may be left empty
+    pub transition: Transition,
+}
+
+impl RosMessageType for ChangeStateRequest {
+    const ROS_TYPE_NAME: &'static str = "lifecycle_msgs/ChangeStateRequest";
+    const MD5SUM: &'static str = "43640e9e3ebe13003442c06e65d8b594";
+    const DEFINITION: &'static str = r####"Transition transition
+================================================================================
+MSG: lifecycle_msgs/Transition
+uint8 TRANSITION_CREATE=0
+uint8 TRANSITION_CONFIGURE=1
+uint8 TRANSITION_CLEANUP=2
+uint8 TRANSITION_ACTIVATE=3
+uint8 TRANSITION_DEACTIVATE=4
+uint8 TRANSITION_UNCONFIGURED_SHUTDOWN=5
+uint8 TRANSITION_INACTIVE_SHUTDOWN=6
+uint8 TRANSITION_ACTIVE_SHUTDOWN=7
+uint8 TRANSITION_DESTROY=8
+uint8 id
+string label"####;
+}
+
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct ChangeStateResponse {
+    /// False if the transition isn't valid from the current state or its callback did not succeed
+    pub success: bool,
+}
+
+impl RosMessageType for ChangeStateResponse {
+    const ROS_TYPE_NAME: &'static str = "lifecycle_msgs/ChangeStateResponse";
+    const MD5SUM: &'static str = "358e233cde0c8a8bcfea4ce193f8fc15";
+    const DEFINITION: &'static str = "bool success";
+}
+
+/// Equivalent of ROS2's `lifecycle_msgs/GetState` service, served on `<node>/get_state`
+pub struct GetState {}
+
+impl RosServiceType for GetState {
+    const ROS_SERVICE_NAME: &'static str = "lifecycle_msgs/GetState";
+    const MD5SUM: &'static str = "db428c05d10d50f6592ba030b8e4b051";
+    type Request = GetStateRequest;
+    type Response = GetStateResponse;
+}
+
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct GetStateRequest {}
+
+impl RosMessageType for GetStateRequest {
+    const ROS_TYPE_NAME: &'static str = "lifecycle_msgs/GetStateRequest";
+    const MD5SUM: &'static str = "d41d8cd98f00b204e9800998ecf8427e";
+    const DEFINITION: &'static str = "";
+}
+
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct GetStateResponse {
+    pub current_state: State,
+}
+
+impl RosMessageType for GetStateResponse {
+    const ROS_TYPE_NAME: &'static str = "lifecycle_msgs/GetStateResponse";
+    const MD5SUM: &'static str = "db428c05d10d50f6592ba030b8e4b051";
+    const DEFINITION: &'static str = r####"State current_state
+================================================================================
+MSG: lifecycle_msgs/State
+uint8 PRIMARY_STATE_UNKNOWN=0
+uint8 PRIMARY_STATE_UNCONFIGURED=1
+uint8 PRIMARY_STATE_INACTIVE=2
+uint8 PRIMARY_STATE_ACTIVE=3
+uint8 PRIMARY_STATE_FINALIZED=4
+uint8 TRANSITION_STATE_CONFIGURING=10
+uint8 TRANSITION_STATE_CLEANINGUP=11
+uint8 TRANSITION_STATE_SHUTTINGDOWN=12
+uint8 TRANSITION_STATE_ACTIVATING=13
+uint8 TRANSITION_STATE_DEACTIVATING=14
+uint8 TRANSITION_STATE_ERRORPROCESSING=15
+uint8 id
+string label"####;
+}
+
+/// The primary states of a [LifecycleNode]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LifecycleState {
+    /// The state a node starts in, it holds no resources
+    Unconfigured,
+    /// Configured but not processing data
+    Inactive,
+    /// Configured and processing data
+    Active,
+    /// Shut down, no further transitions are possible
+    Finalized,
+}
+
+impl LifecycleState {
+    /// The id used for this state in `lifecycle_msgs/State`
+    pub fn id(self) -> u8 {
+        match self {
+            LifecycleState::Unconfigured => State::PRIMARY_STATE_UNCONFIGURED,
+            LifecycleState::Inactive => State::PRIMARY_STATE_INACTIVE,
+            LifecycleState::Active => State::PRIMARY_STATE_ACTIVE,
+            LifecycleState::Finalized => State::PRIMARY_STATE_FINALIZED,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LifecycleState::Unconfigured => "unconfigured",
+            LifecycleState::Inactive => "inactive",
+            LifecycleState::Active => "active",
+            LifecycleState::Finalized => "finalized",
+        }
+    }
+
+    pub fn to_msg(self) -> State {
+        State {
+            id: self.id(),
+            label: self.label().to_string(),
+        }
+    }
+}
+
+/// The transitions which can be requested of a [LifecycleNode]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum LifecycleTransition {
+    /// Unconfigured to Inactive
+    Configure,
+    /// Inactive to Unconfigured
+    Cleanup,
+    /// Inactive to Active
+    Activate,
+    /// Active to Inactive
+    Deactivate,
+    /// Any state other than Finalized to Finalized
+    Shutdown,
+}
+
+impl LifecycleTransition {
+    /// Converts the id from a `lifecycle_msgs/Transition`, each of the three shutdown ids is [LifecycleTransition::Shutdown]
+    pub fn from_id(id: u8) -> Option<Self> {
+        match id {
+            Transition::TRANSITION_CONFIGURE => Some(LifecycleTransition::Configure),
+            Transition::TRANSITION_CLEANUP => Some(LifecycleTransition::Cleanup),
+            Transition::TRANSITION_ACTIVATE => Some(LifecycleTransition::Activate),
+            Transition::TRANSITION_DEACTIVATE => Some(LifecycleTransition::Deactivate),
+            Transition::TRANSITION_UNCONFIGURED_SHUTDOWN
+            | Transition::TRANSITION_INACTIVE_SHUTDOWN
+            | Transition::TRANSITION_ACTIVE_SHUTDOWN => Some(LifecycleTransition::Shutdown),
+            _ => None,
+        }
+    }
+
+    /// The id used for this transition in `lifecycle_msgs/Transition` when starting from `from`,
+    /// which only matters for shutdown as it has a separate id for each state
+    pub fn id(self, from: LifecycleState) -> u8 {
+        match (self, from) {
+            (LifecycleTransition::Configure, _) => Transition::TRANSITION_CONFIGURE,
+            (LifecycleTransition::Cleanup, _) => Transition::TRANSITION_CLEANUP,
+            (LifecycleTransition::Activate, _) => Transition::TRANSITION_ACTIVATE,
+            (LifecycleTransition::Deactivate, _) => Transition::TRANSITION_DEACTIVATE,
+            (LifecycleTransition::Shutdown, LifecycleState::Inactive) => {
+                Transition::TRANSITION_INACTIVE_SHUTDOWN
+            }
+            (LifecycleTransition::Shutdown, LifecycleState::Active) => {
+                Transition::TRANSITION_ACTIVE_SHUTDOWN
+            }
+            (LifecycleTransition::Shutdown, _) => Transition::TRANSITION_UNCONFIGURED_SHUTDOWN,
+        }
+    }
+
+    pub fn label(self) -> &'static str {
+        match self {
+            LifecycleTransition::Configure => "configure",
+            LifecycleTransition::Cleanup => "cleanup",
+            LifecycleTransition::Activate => "activate",
+            LifecycleTransition::Deactivate => "deactivate",
+            LifecycleTransition::Shutdown => "shutdown",
+        }
+    }
+
+    /// The state reached when this transition succeeds, or None if it can't be started from `from`
+    pub fn goal(self, from: LifecycleState) -> Option<LifecycleState> {
+        match (self, from) {
+            (LifecycleTransition::Configure, LifecycleState::Unconfigured) => {
+                Some(LifecycleState::Inactive)
+            }
+            (LifecycleTransition::Cleanup, LifecycleState::Inactive) => {
+                Some(LifecycleState::Unconfigured)
+            }
+            (LifecycleTransition::Activate, LifecycleState::Inactive) => {
+                Some(LifecycleState::Active)
+            }
+            (LifecycleTransition::Deactivate, LifecycleState::Active) => {
+                Some(LifecycleState::Inactive)
+            }
+            (LifecycleTransition::Shutdown, LifecycleState::Finalized) => None,
+            (LifecycleTransition::Shutdown, _) => Some(LifecycleState::Finalized),
+            _ => None,
+        }
+    }
+}
+
+/// The outcome of a [LifecycleCallbacks] method
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CallbackReturn {
+    /// The transition completes
+    Success,
+    /// The transition is rejected and the node stays in the state it started from
+    Failure,
+    /// Something went wrong which [LifecycleCallbacks::on_error] has to recover from
+    Error,
+}
+
+/// User code run on each transition of a [LifecycleNode], every method defaults to succeeding.
+///
+/// The callbacks are called with the node's state locked, so they should complete reasonably quickly.
+/// When a transition is requested through the `change_state` service they run on a blocking thread and may block,
+/// when requested with [LifecycleNode::trigger] they run within the calling task.
+pub trait LifecycleCallbacks: Send + 'static {
+    fn on_configure(&mut self) -> CallbackReturn {
+        CallbackReturn::Success
+    }
+
+    fn on_cleanup(&mut self) -> CallbackReturn {
+        CallbackReturn::Success
+    }
+
+    fn on_activate(&mut self) -> CallbackReturn {
+        CallbackReturn::Success
+    }
+
+    fn on_deactivate(&mut self) -> CallbackReturn {
+        CallbackReturn::Success
+    }
+
+    /// Called when shutting down from any state other than Finalized
+    fn on_shutdown(&mut self, _from: LifecycleState) -> CallbackReturn {
+        CallbackReturn::Success
+    }
+
+    /// Called when another callback returns [CallbackReturn::Error].
+    /// Returning Success moves the node to Unconfigured, anything else to Finalized.
+    fn on_error(&mut self, _from: LifecycleState) -> CallbackReturn {
+        CallbackReturn::Success
+    }
+}
+
+// The state of a LifecycleNode separate from any ROS communication
+struct Machine<C> {
+    state: LifecycleState,
+    callbacks: C,
+}
+
+impl<C: LifecycleCallbacks> Machine<C> {
+    fn new(callbacks: C) -> Self {
+        Self {
+            state: LifecycleState::Unconfigured,
+            callbacks,
+        }
+    }
+
+    /// Runs a transition's callback and returns the event describing it, or an error if the transition
+    /// isn't valid from the current state
+    fn run(&mut self, transition: LifecycleTransition) -> Result<TransitionEvent> {
+        let start = self.state;
+        let goal = transition.goal(start).ok_or_else(|| {
+            Error::Unexpected(anyhow::anyhow!(
+                "Lifecycle transition {} is not valid from state {}",
+                transition.label(),
+                start.label()
+            ))
+        })?;
+        let result = match transition {
+            LifecycleTransition::Configure => self.callbacks.on_configure(),
+            LifecycleTransition::Cleanup => self.callbacks.on_cleanup(),
+            LifecycleTransition::Activate => self.callbacks.on_activate(),
+            LifecycleTransition::Deactivate => self.callbacks.on_deactivate(),
+            LifecycleTransition::Shutdown => self.callbacks.on_shutdown(start),
+        };
+        self.state = match result {
+            CallbackReturn::Success => goal,
+            CallbackReturn::Failure => start,
+            CallbackReturn::Error => match self.callbacks.on_error(start) {
+                CallbackReturn::Success => LifecycleState::Unconfigured,
+                _ => LifecycleState::Finalized,
+            },
+        };
+        let timestamp = std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_nanos() as u64)
+            .unwrap_or_default();
+        Ok(TransitionEvent {
+            timestamp,
+            transition: Transition {
+                id: transition.id(start),
+                label: transition.label().to_string(),
+            },
+            start_state: start.to_msg(),
+            goal_state: self.state.to_msg(),
+        })
+    }
+}
+
+/// A ROS2 style managed node, layered on top of any backend.
+///
+/// The node starts Unconfigured and moves between [LifecycleState]s when a [LifecycleTransition] is triggered,
+/// either locally with [LifecycleNode::trigger] or remotely through its services, running the matching
+/// [LifecycleCallbacks] method to decide whether the transition succeeds.
+///
+/// For a node named `/my_node` it provides:
+/// - `/my_node/change_state`: a [ChangeState] service requesting a transition
+/// - `/my_node/get_state`: a [GetState] service returning the current state
+/// - `/my_node/transition_event`: a topic of [TransitionEvent]s, published after every transition
+///
+/// The services and topic are removed when the LifecycleNode is dropped.
+///
+/// ```ignore
+/// struct Camera;
+/// impl LifecycleCallbacks for Camera {
+///     fn on_activate(&mut self) -> CallbackReturn {
+///         // Start streaming...
+///         CallbackReturn::Success
+///     }
+/// }
+/// let lifecycle = LifecycleNode::new(&ros, "/camera", Camera).await?;
+/// lifecycle.trigger(LifecycleTransition::Configure).await?;
+/// ```
+pub struct LifecycleNode<T: TopicProvider + ServiceProvider, C> {
+    machine: Arc<Mutex<Machine<C>>>,
+    events: Arc<T::Publisher<TransitionEvent>>,
+    _change_state: T::ServiceServer,
+    _get_state: T::ServiceServer,
+}
+
+impl<T: TopicProvider + ServiceProvider, C: LifecycleCallbacks> LifecycleNode<T, C> {
+    /// Advertises the lifecycle services and topic under `node_name` using `ros`
+    pub async fn new(ros: &T, node_name: &str, callbacks: C) -> Result<Self> {
+        let machine = Arc::new(Mutex::new(Machine::new(callbacks)));
+        let events = Arc::new(
+            ros.advertise::<TransitionEvent>(format!("{node_name}/transition_event"))
+                .await?,
+        );
+
+        let change_state = {
+            let machine = machine.clone();
+            let events = events.clone();
+            move |request: ChangeStateRequest| {
+                let requested = request.transition.id;
+                let event = {
+                    let mut machine = machine.lock().unwrap();
+                    let current = machine.state;
+                    match LifecycleTransition::from_id(requested) {
+                        // Shutdown ids name the state being shut down from, reject ones that don't match
+                        Some(transition) if transition.id(current) == requested => {
+                            machine.run(transition).ok()
+                        }
+                        _ => None,
+                    }
+                };
+                let Some(event) = event else {
+                    return Ok(ChangeStateResponse { success: false });
+                };
+                let success = event.goal_state.id != event.start_state.id;
+                // Service functions always run in spawn_blocking so blocking on the publish is fine here
+                block_on(events.publish(&event))?;
+                Ok(ChangeStateResponse { success })
+            }
+        };
+        let _change_state = ros
+            .advertise_service::<ChangeState, _>(format!("{node_name}/change_state"), change_state)
+            .await?;
+
+        let get_state = {
+            let machine = machine.clone();
+            move |_request: GetStateRequest| {
+                Ok(GetStateResponse {
+                    current_state: machine.lock().unwrap().state.to_msg(),
+                })
+            }
+        };
+        let _get_state = ros
+            .advertise_service::<GetState, _>(format!("{node_name}/get_state"), get_state)
+            .await?;
+
+        Ok(Self {
+            machine,
+            events,
+            _change_state,
+            _get_state,
+        })
+    }
+
+    /// The current state of the node
+    pub fn state(&self) -> LifecycleState {
+        self.machine.lock().unwrap().state
+    }
+
+    /// Shorthand for checking if the node is [LifecycleState::Active], e.g. before publishing data
+    pub fn is_active(&self) -> bool {
+        self.state() == LifecycleState::Active
+    }
+
+    /// Runs a transition and publishes the resulting [TransitionEvent], returning the state the node ended up in.
+    ///
+    /// Returns an error if the transition isn't valid from the current state, a callback rejecting the
+    /// transition is not an error and is reported by the returned state.
+    pub async fn trigger(&self, transition: LifecycleTransition) -> Result<LifecycleState> {
+        let (event, state) = {
+            let mut machine = self.machine.lock().unwrap();
+            (machine.run(transition)?, machine.state)
+        };
+        self.events.publish(&event).await?;
+        Ok(state)
+    }
+}
+
+// Minimal executor for publishing from the synchronous change_state service function
+fn block_on<F: Future>(future: F) -> F::Output {
+    struct ThreadWaker(std::thread::Thread);
+
+    impl std::task::Wake for ThreadWaker {
+        fn wake(self: Arc<Self>) {
+            self.0.unpark();
+        }
+    }
+
+    let waker = Arc::new(ThreadWaker(std::thread::current())).into();
+    let mut cx = std::task::Context::from_waker(&waker);
+    let mut future = std::pin::pin!(future);
+    loop {
+        if let std::task::Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+        std::thread::park();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[derive(Default)]
+    struct Recorder {
+        calls: Vec<&'static str>,
+        activate: Option<CallbackReturn>,
+        recover: bool,
+    }
+
+    impl LifecycleCallbacks for Recorder {
+        fn on_configure(&mut self) -> CallbackReturn {
+            self.calls.push("configure");
+            CallbackReturn::Success
+        }
+
+        fn on_activate(&mut self) -> CallbackReturn {
+            self.calls.push("activate");
+            self.activate.unwrap_or(CallbackReturn::Success)
+        }
+
+        fn on_error(&mut self, _from: LifecycleState) -> CallbackReturn {
+            self.calls.push("error");
+            if self.recover {
+                CallbackReturn::Success
+            } else {
+                CallbackReturn::Failure
+            }
+        }
+    }
+
+    #[test]
+    fn md5sums_match_definitions() {
+        use crate::md5sum::from_message_definition;
+        assert_eq!(
+            from_message_definition(State::ROS_TYPE_NAME, State::DEFINITION).unwrap(),
+            State::MD5SUM
+        );
+        assert_eq!(
+            from_message_definition(Transition::ROS_TYPE_NAME, Transition::DEFINITION).unwrap(),
+            Transition::MD5SUM
+        );
+        assert_eq!(
+            from_message_definition(TransitionEvent::ROS_TYPE_NAME, TransitionEvent::DEFINITION)
+                .unwrap(),
+            TransitionEvent::MD5SUM
+        );
+        assert_eq!(
+            from_message_definition(
+                GetStateResponse::ROS_TYPE_NAME,
+                GetStateResponse::DEFINITION
+            )
+            .unwrap(),
+            GetStateResponse::MD5SUM
+        );
+        // A service's md5sum covers its request then its response, with sub messages replaced by their md5sums
+        let change_state = format!("{} transition{}", Transition::MD5SUM, "bool success");
+        assert_eq!(
+            format!("{:x}", md5::compute(change_state)),
+            ChangeState::MD5SUM
+        );
+    }
+
+    #[test]
+    fn follows_valid_transitions() {
+        let mut machine = Machine::new(Recorder::default());
+        assert!(machine.run(LifecycleTransition::Activate).is_err());
+        assert_eq!(machine.state, LifecycleState::Unconfigured);
+
+        let event = machine.run(LifecycleTransition::Configure).unwrap();
+        assert_eq!(event.start_state, LifecycleState::Unconfigured.to_msg());
+        assert_eq!(event.goal_state, LifecycleState::Inactive.to_msg());
+        machine.run(LifecycleTransition::Activate).unwrap();
+        assert_eq!(machine.state, LifecycleState::Active);
+
+        let event = machine.run(LifecycleTransition::Shutdown).unwrap();
+        assert_eq!(event.transition.id, Transition::TRANSITION_ACTIVE_SHUTDOWN);
+        assert_eq!(machine.state, LifecycleState::Finalized);
+        assert!(machine.run(LifecycleTransition::Shutdown).is_err());
+        assert_eq!(machine.callbacks.calls, ["configure", "activate"]);
+    }
+
+    #[test]
+    fn callback_outcomes() {
+        let mut machine = Machine::new(Recorder {
+            activate: Some(CallbackReturn::Failure),
+            ..Default::default()
+        });
+        machine.run(LifecycleTransition::Configure).unwrap();
+        let event = machine.run(LifecycleTransition::Activate).unwrap();
+        assert_eq!(event.goal_state, LifecycleState::Inactive.to_msg());
+
+        machine.callbacks.activate = Some(CallbackReturn::Error);
+        machine.callbacks.recover = true;
+        machine.run(LifecycleTransition::Activate).unwrap();
+        assert_eq!(machine.state, LifecycleState::Unconfigured);
+
+        machine.callbacks.recover = false;
+        machine.run(LifecycleTransition::Configure).unwrap();
+        machine.run(LifecycleTransition::Activate).unwrap();
+        assert_eq!(machine.state, LifecycleState::Finalized);
+    }
+
+    #[test]
+    fn transition_ids_round_trip() {
+        for (state, id) in [
+            (LifecycleState::Unconfigured, 5),
+            (LifecycleState::Inactive, 6),
+            (LifecycleState::Active, 7),
+        ] {
+            assert_eq!(LifecycleTransition::Shutdown.id(state), id);
+            assert_eq!(
+                LifecycleTransition::from_id(id),
+                Some(LifecycleTransition::Shutdown)
+            );
+        }
+        assert_eq!(
+            LifecycleTransition::from_id(Transition::TRANSITION_CREATE),
+            None
+        );
+    }
+}
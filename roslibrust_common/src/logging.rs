@@ -0,0 +1,360 @@
+use crate::clock::Clock;
+use crate::rosout::{Header, Log, LogLevel, Time, ROSOUT_TOPIC};
+use crate::{Publish, Result, TopicProvider};
+use std::fmt;
+use std::sync::{Mutex, RwLock};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use tokio::sync::mpsc;
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+// Backing for the ros_info! family of macros, which follow roscpp's ROS_INFO, ROS_WARN_THROTTLE, ROS_ERROR_ONCE etc.
+// Messages always go to the `log` crate, and are also published on /rosout while a RosoutLogger is alive.
+// Throttling uses ROS time, so follows simulated time once set_clock has been given a SimClock.
+
+// Read for throttling and stamping, None until set_clock is called meaning the wall clock
+static CLOCK: RwLock<Option<Clock>> = RwLock::new(None);
+// Where messages to publish on /rosout are sent, None unless a RosoutLogger is alive
+static ROSOUT: Mutex<Option<RosoutSink>> = Mutex::new(None);
+
+struct RosoutSink {
+    node_name: String,
+    min_level: LogLevel,
+    sender: mpsc::UnboundedSender<Log>,
+}
+
+/// Sets the clock the logging macros read ROS time from, both to throttle and to stamp the messages published on
+/// /rosout. Defaults to the wall clock, pass a [SimClock](crate::clock::SimClock) when running in simulation.
+pub fn set_clock(clock: Clock) {
+    *CLOCK.write().unwrap() = Some(clock);
+}
+
+fn now() -> SystemTime {
+    match &*CLOCK.read().unwrap() {
+        Some(clock) => clock.now(),
+        None => SystemTime::now(),
+    }
+}
+
+/// Publishes the messages logged with [ros_info!](crate::ros_info) and the other logging macros on /rosout,
+/// where rqt_console and readers of /rosout_agg see them.
+///
+/// Only messages at or above the minimum level are published, [LogLevel::Info] by default as with roscpp.
+/// Messages still go to the local logger whatever the level. The `topics` field of the published messages is
+/// left empty. Publishing stops when the RosoutLogger is dropped.
+///
+/// Must be created within a tokio runtime.
+/// ```ignore
+/// let _rosout = RosoutLogger::new(&ros, "/my_node").await?;
+/// ros_info!("Started with {} cameras", cameras.len());
+/// ```
+pub struct RosoutLogger {
+    sender: mpsc::UnboundedSender<Log>,
+    _stop: DropGuard,
+}
+
+impl RosoutLogger {
+    /// Advertises /rosout using `ros` and starts publishing logged messages as coming from `node_name`.
+    ///
+    /// Replaces any RosoutLogger created before it.
+    pub async fn new<T: TopicProvider>(ros: &T, node_name: &str) -> Result<Self> {
+        let publisher = ros.advertise::<Log>(ROSOUT_TOPIC).await?;
+        let (sender, mut receiver) = mpsc::unbounded_channel::<Log>();
+        let stop = CancellationToken::new();
+        let task_stop = stop.clone();
+        tokio::spawn(async move {
+            let mut seq = 0u32;
+            loop {
+                let log = tokio::select! {
+                    _ = task_stop.cancelled() => break,
+                    log = receiver.recv() => log,
+                };
+                let Some(mut log) = log else {
+                    break;
+                };
+                log.header.seq = seq;
+                seq = seq.wrapping_add(1);
+                // Reporting a failure to log would have to go through the log
+                let _ = publisher.publish(&log).await;
+            }
+        });
+        *ROSOUT.lock().unwrap() = Some(RosoutSink {
+            node_name: node_name.to_string(),
+            min_level: LogLevel::Info,
+            sender: sender.clone(),
+        });
+        Ok(Self {
+            sender,
+            _stop: stop.drop_guard(),
+        })
+    }
+
+    /// Changes the least severe level published on /rosout
+    pub fn set_min_level(&self, level: LogLevel) {
+        if let Some(sink) = ROSOUT.lock().unwrap().as_mut() {
+            if sink.sender.same_channel(&self.sender) {
+                sink.min_level = level;
+            }
+        }
+    }
+}
+
+impl Drop for RosoutLogger {
+    fn drop(&mut self) {
+        let mut sink = ROSOUT.lock().unwrap();
+        // A logger created after this one has already replaced it
+        if sink
+            .as_ref()
+            .is_some_and(|sink| sink.sender.same_channel(&self.sender))
+        {
+            *sink = None;
+        }
+    }
+}
+
+/// Called by the logging macros, see [ros_log!](crate::ros_log)
+#[doc(hidden)]
+pub fn log(
+    level: LogLevel,
+    args: fmt::Arguments,
+    file: &'static str,
+    module: &'static str,
+    line: u32,
+) {
+    let local_level = match level {
+        LogLevel::Debug => log::Level::Debug,
+        LogLevel::Info => log::Level::Info,
+        LogLevel::Warn => log::Level::Warn,
+        LogLevel::Error | LogLevel::Fatal => log::Level::Error,
+    };
+    if local_level <= log::max_level() {
+        log::logger().log(
+            &log::Record::builder()
+                .args(args)
+                .level(local_level)
+                .target(module)
+                .module_path_static(Some(module))
+                .file_static(Some(file))
+                .line(Some(line))
+                .build(),
+        );
+    }
+
+    if let Some(sink) = ROSOUT.lock().unwrap().as_ref() {
+        if level < sink.min_level {
+            return;
+        }
+        let stamp = now().duration_since(UNIX_EPOCH).unwrap_or_default();
+        let _ = sink.sender.send(Log {
+            header: Header {
+                seq: 0,
+                stamp: Time {
+                    secs: stamp.as_secs() as u32,
+                    nsecs: stamp.subsec_nanos(),
+                },
+                frame_id: String::new(),
+            },
+            level: level.as_byte(),
+            name: sink.node_name.clone(),
+            msg: args.to_string(),
+            file: file.to_string(),
+            function: module.to_string(),
+            line,
+            topics: vec![],
+        });
+    }
+}
+
+/// Remembers when a call site of the throttled logging macros last logged, see [ros_log_throttle!](crate::ros_log_throttle)
+#[doc(hidden)]
+#[derive(Default)]
+pub struct Throttle {
+    last: Mutex<Option<SystemTime>>,
+}
+
+impl Throttle {
+    pub const fn new() -> Self {
+        Self {
+            last: Mutex::new(None),
+        }
+    }
+
+    /// True if at least `period` of ROS time has passed since this last returned true
+    pub fn ready(&self, period: Duration) -> bool {
+        self.ready_at(period, now())
+    }
+
+    fn ready_at(&self, period: Duration, now: SystemTime) -> bool {
+        let mut last = self.last.lock().unwrap();
+        let ready = match *last {
+            // Time jumping backwards, e.g. a simulation being reset, also allows a message
+            Some(last) => now
+                .duration_since(last)
+                .map_or(true, |elapsed| elapsed >= period),
+            None => true,
+        };
+        if ready {
+            *last = Some(now);
+        }
+        ready
+    }
+}
+
+/// Logs a message at a [LogLevel](crate::rosout::LogLevel) to the `log` crate, and on /rosout while a
+/// [RosoutLogger](crate::logging::RosoutLogger) is alive.
+///
+/// Takes the level followed by the same arguments as [format!]. Usually used through the level specific macros,
+/// e.g. [ros_info!](crate::ros_info).
+#[macro_export]
+macro_rules! ros_log {
+    ($level:expr, $($arg:tt)+) => {
+        $crate::logging::log(
+            $level,
+            ::std::format_args!($($arg)+),
+            ::std::file!(),
+            ::std::module_path!(),
+            ::std::line!(),
+        )
+    };
+}
+
+/// Like [ros_log!](crate::ros_log), but logs at most once per `period` of ROS time from each place it is used.
+#[macro_export]
+macro_rules! ros_log_throttle {
+    ($level:expr, $period:expr, $($arg:tt)+) => {{
+        static THROTTLE: $crate::logging::Throttle = $crate::logging::Throttle::new();
+        if THROTTLE.ready($period) {
+            $crate::ros_log!($level, $($arg)+);
+        }
+    }};
+}
+
+/// Like [ros_log!](crate::ros_log), but logs only the first time it is reached from each place it is used.
+#[macro_export]
+macro_rules! ros_log_once {
+    ($level:expr, $($arg:tt)+) => {{
+        static LOGGED: ::std::sync::atomic::AtomicBool = ::std::sync::atomic::AtomicBool::new(false);
+        if !LOGGED.swap(true, ::std::sync::atomic::Ordering::Relaxed) {
+            $crate::ros_log!($level, $($arg)+);
+        }
+    }};
+}
+
+/// Equivalent of roscpp's `ROS_DEBUG`, see [ros_log!](crate::ros_log)
+#[macro_export]
+macro_rules! ros_debug {
+    ($($arg:tt)+) => { $crate::ros_log!($crate::rosout::LogLevel::Debug, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_INFO`, see [ros_log!](crate::ros_log)
+#[macro_export]
+macro_rules! ros_info {
+    ($($arg:tt)+) => { $crate::ros_log!($crate::rosout::LogLevel::Info, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_WARN`, see [ros_log!](crate::ros_log)
+#[macro_export]
+macro_rules! ros_warn {
+    ($($arg:tt)+) => { $crate::ros_log!($crate::rosout::LogLevel::Warn, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_ERROR`, see [ros_log!](crate::ros_log)
+#[macro_export]
+macro_rules! ros_error {
+    ($($arg:tt)+) => { $crate::ros_log!($crate::rosout::LogLevel::Error, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_FATAL`, see [ros_log!](crate::ros_log)
+#[macro_export]
+macro_rules! ros_fatal {
+    ($($arg:tt)+) => { $crate::ros_log!($crate::rosout::LogLevel::Fatal, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_DEBUG_THROTTLE`, taking the period as a [Duration], see [ros_log_throttle!](crate::ros_log_throttle)
+#[macro_export]
+macro_rules! ros_debug_throttle {
+    ($period:expr, $($arg:tt)+) => { $crate::ros_log_throttle!($crate::rosout::LogLevel::Debug, $period, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_INFO_THROTTLE`, taking the period as a [Duration], see [ros_log_throttle!](crate::ros_log_throttle)
+#[macro_export]
+macro_rules! ros_info_throttle {
+    ($period:expr, $($arg:tt)+) => { $crate::ros_log_throttle!($crate::rosout::LogLevel::Info, $period, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_WARN_THROTTLE`, taking the period as a [Duration], see [ros_log_throttle!](crate::ros_log_throttle)
+#[macro_export]
+macro_rules! ros_warn_throttle {
+    ($period:expr, $($arg:tt)+) => { $crate::ros_log_throttle!($crate::rosout::LogLevel::Warn, $period, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_ERROR_THROTTLE`, taking the period as a [Duration], see [ros_log_throttle!](crate::ros_log_throttle)
+#[macro_export]
+macro_rules! ros_error_throttle {
+    ($period:expr, $($arg:tt)+) => { $crate::ros_log_throttle!($crate::rosout::LogLevel::Error, $period, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_FATAL_THROTTLE`, taking the period as a [Duration], see [ros_log_throttle!](crate::ros_log_throttle)
+#[macro_export]
+macro_rules! ros_fatal_throttle {
+    ($period:expr, $($arg:tt)+) => { $crate::ros_log_throttle!($crate::rosout::LogLevel::Fatal, $period, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_DEBUG_ONCE`, see [ros_log_once!](crate::ros_log_once)
+#[macro_export]
+macro_rules! ros_debug_once {
+    ($($arg:tt)+) => { $crate::ros_log_once!($crate::rosout::LogLevel::Debug, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_INFO_ONCE`, see [ros_log_once!](crate::ros_log_once)
+#[macro_export]
+macro_rules! ros_info_once {
+    ($($arg:tt)+) => { $crate::ros_log_once!($crate::rosout::LogLevel::Info, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_WARN_ONCE`, see [ros_log_once!](crate::ros_log_once)
+#[macro_export]
+macro_rules! ros_warn_once {
+    ($($arg:tt)+) => { $crate::ros_log_once!($crate::rosout::LogLevel::Warn, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_ERROR_ONCE`, see [ros_log_once!](crate::ros_log_once)
+#[macro_export]
+macro_rules! ros_error_once {
+    ($($arg:tt)+) => { $crate::ros_log_once!($crate::rosout::LogLevel::Error, $($arg)+) };
+}
+
+/// Equivalent of roscpp's `ROS_FATAL_ONCE`, see [ros_log_once!](crate::ros_log_once)
+#[macro_export]
+macro_rules! ros_fatal_once {
+    ($($arg:tt)+) => { $crate::ros_log_once!($crate::rosout::LogLevel::Fatal, $($arg)+) };
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn throttles_by_ros_time() {
+        let throttle = Throttle::new();
+        let period = Duration::from_secs(1);
+        let at = |millis| UNIX_EPOCH + Duration::from_millis(millis);
+        assert!(throttle.ready_at(period, at(5000)));
+        assert!(!throttle.ready_at(period, at(5999)));
+        assert!(throttle.ready_at(period, at(6000)));
+        // A simulation restarting from an earlier time isn't silenced until it catches up
+        assert!(throttle.ready_at(period, at(100)));
+        assert!(!throttle.ready_at(period, at(200)));
+    }
+
+    #[test]
+    fn once_logs_only_first_time() {
+        let mut logged = 0;
+        for _ in 0..3 {
+            crate::ros_log_once!(LogLevel::Info, "{}", {
+                logged += 1;
+                logged
+            });
+        }
+        assert_eq!(logged, 1);
+    }
+}
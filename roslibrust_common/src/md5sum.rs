@@ -166,6 +166,129 @@ fn clean_msg(msg: &str) -> String {
     .to_string() // Last trim here is lazy, but gets job done
 }
 
+/// Compares two expanded message definitions (see [from_message_definition]) field by field.
+///
+/// Meant to explain an md5sum mismatch, e.g. between the definition a subscriber was built with and the one a
+/// publisher sends in its connection header. Returns one human readable line per difference found, e.g.
+/// "geometry_msgs/Point: `z` is `float64 z` locally but `float32 z` remotely".
+/// Comments and whitespace are ignored. An empty result means the definitions describe the same fields.
+pub fn diff_message_definitions(msg_name: &str, local: &str, remote: &str) -> Vec<String> {
+    if remote.trim().is_empty() {
+        return vec!["the remote definition is not available".to_string()];
+    }
+    if local.trim().is_empty() {
+        return vec!["the local definition is not available".to_string()];
+    }
+    let local = definition_sections(msg_name, local);
+    let remote = definition_sections(msg_name, remote);
+
+    let mut differences = vec![];
+    // The message itself first, then its dependencies in the order the local definition lists them
+    let mut msg_types = local
+        .iter()
+        .map(|(msg_type, _)| *msg_type)
+        .collect::<Vec<_>>();
+    msg_types.extend(
+        remote
+            .iter()
+            .map(|(msg_type, _)| *msg_type)
+            .filter(|msg_type| !local.iter().any(|(local_type, _)| local_type == msg_type)),
+    );
+    for msg_type in msg_types {
+        let find = |sections: &[(&str, String)]| {
+            sections
+                .iter()
+                .find(|(section_type, _)| *section_type == msg_type)
+                .map(|(_, body)| body.clone())
+        };
+        match (find(&local), find(&remote)) {
+            (Some(local), Some(remote)) => {
+                diff_section(msg_type, &local, &remote, &mut differences)
+            }
+            (Some(_), None) => {
+                differences.push(format!("{msg_type}: only in the local definition"))
+            }
+            (None, Some(_)) => {
+                differences.push(format!("{msg_type}: only in the remote definition"))
+            }
+            (None, None) => {}
+        }
+    }
+    differences
+}
+
+/// Splits an expanded definition into (message type, cleaned body) pairs, starting with `msg_name` itself
+/// Sections which can't be parsed are skipped, this is only used to explain mismatches
+fn definition_sections<'a>(msg_name: &'a str, full_def: &'a str) -> Vec<(&'a str, String)> {
+    let sep: &str =
+        "================================================================================\n";
+    let mut sections = full_def.split(sep);
+    let mut result = vec![(msg_name, clean_msg(sections.next().unwrap_or_default()))];
+    for section in sections {
+        let Some((line0, body)) = section.split_once('\n') else {
+            continue;
+        };
+        if let Some(section_type) = line0.strip_prefix("MSG: ") {
+            result.push((section_type.trim(), clean_msg(body)));
+        }
+    }
+    result
+}
+
+/// Compares the cleaned bodies of one message type, see [clean_msg]
+fn diff_section(msg_type: &str, local: &str, remote: &str, differences: &mut Vec<String>) {
+    if local == remote {
+        return;
+    }
+    // Maps each field or constant name to its declaration
+    let entries = |body: &str| {
+        body.lines()
+            .map(|line| {
+                let name = match line.split_once('=') {
+                    // Constants: "type NAME=value"
+                    Some((declaration, _)) => declaration.split_whitespace().last(),
+                    // Fields: "type name"
+                    None => line.split_whitespace().nth(1),
+                };
+                (name.unwrap_or(line).to_string(), line.to_string())
+            })
+            .collect::<Vec<_>>()
+    };
+    let (local, remote) = (entries(local), entries(remote));
+    let find = |entries: &[(String, String)], name: &str| {
+        entries
+            .iter()
+            .find(|(entry_name, _)| entry_name == name)
+            .map(|(_, line)| line.clone())
+    };
+
+    let before = differences.len();
+    for (name, local_line) in &local {
+        match find(&remote, name) {
+            Some(remote_line) if remote_line != *local_line => differences.push(format!(
+                "{msg_type}: `{name}` is `{local_line}` locally but `{remote_line}` remotely"
+            )),
+            Some(_) => {}
+            None => differences.push(format!(
+                "{msg_type}: `{local_line}` is only in the local definition"
+            )),
+        }
+    }
+    for (name, remote_line) in &remote {
+        if find(&local, name).is_none() {
+            differences.push(format!(
+                "{msg_type}: `{remote_line}` is only in the remote definition"
+            ));
+        }
+    }
+    // Same entries, so they must be declared in a different order
+    if differences.len() == before {
+        differences.push(format!(
+            "{msg_type}: fields are declared in a different order"
+        ));
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -729,6 +852,57 @@ uint32 count     # How many elements in the field
     }
 
     // Basic test of clean_msg function
+    #[test]
+    fn diff_message_definitions_reports_fields() {
+        let sep =
+            "================================================================================\n";
+        let local = format!(
+            "# A pose\nHeader header\nPoint position\nint8 MODE=1\n{sep}MSG: std_msgs/Header\nuint32 seq\ntime stamp\nstring frame_id\n{sep}MSG: test_msgs/Point\nfloat64 x # in meters\nfloat64 y\nfloat64 z\n"
+        );
+        assert!(diff_message_definitions("test_msgs/Pose", &local, &local).is_empty());
+        // Comments and whitespace don't matter
+        let reformatted = local
+            .replace("# A pose\n", "")
+            .replace(" # in meters", "   ");
+        assert!(diff_message_definitions("test_msgs/Pose", &local, &reformatted).is_empty());
+
+        let remote = format!(
+            "Header header\nPoint position\nint8 MODE=2\nuint8 flags\n{sep}MSG: std_msgs/Header\nuint32 seq\ntime stamp\nstring frame_id\n{sep}MSG: test_msgs/Point\nfloat64 x\nfloat32 y\n"
+        );
+        assert_eq!(
+            diff_message_definitions("test_msgs/Pose", &local, &remote),
+            vec![
+                "test_msgs/Pose: `MODE` is `int8 MODE=1` locally but `int8 MODE=2` remotely",
+                "test_msgs/Pose: `uint8 flags` is only in the remote definition",
+                "test_msgs/Point: `y` is `float64 y` locally but `float32 y` remotely",
+                "test_msgs/Point: `float64 z` is only in the local definition",
+            ]
+        );
+
+        let reordered = local.replace(
+            "Header header\nPoint position",
+            "Point position\nHeader header",
+        );
+        assert_eq!(
+            diff_message_definitions("test_msgs/Pose", &local, &reordered),
+            vec!["test_msgs/Pose: fields are declared in a different order"]
+        );
+
+        let without_header = format!("Point position\nint8 MODE=1\n{sep}MSG: test_msgs/Point\nfloat64 x\nfloat64 y\nfloat64 z\n");
+        assert_eq!(
+            diff_message_definitions("test_msgs/Pose", &local, &without_header),
+            vec![
+                "test_msgs/Pose: `Header header` is only in the local definition",
+                "std_msgs/Header: only in the local definition",
+            ]
+        );
+
+        assert_eq!(
+            diff_message_definitions("test_msgs/Pose", &local, ""),
+            vec!["the remote definition is not available"]
+        );
+    }
+
     #[test]
     fn clean_msg_test() {
         let test_msg = r#"
@@ -0,0 +1,280 @@
+use crate::{
+    Error, Publish, Result, RosMessageType, RosServiceType, ServiceProvider, Subscribe,
+    TopicProvider,
+};
+use std::sync::{Arc, Mutex};
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+// Equivalent of topic_tools' mux node, built only on the generic traits so it works with every backend.
+// The service types mirror topic_tools' MuxSelect and MuxList so that existing tooling (e.g. `rosrun topic_tools
+// mux_select`) can switch a roslibrust mux. Adding and deleting inputs at runtime is not supported.
+
+/// Selecting this topic stops the mux from forwarding any input, as with topic_tools
+pub const NONE_TOPIC: &str = "__none";
+
+/// Equivalent of `topic_tools/MuxSelect`, served on `<mux>/select`
+pub struct MuxSelect {}
+
+impl RosServiceType for MuxSelect {
+    const ROS_SERVICE_NAME: &'static str = "topic_tools/MuxSelect";
+    const MD5SUM: &'static str = "053052240ca985e1f2eedbb0dae9b1f7";
+    type Request = MuxSelectRequest;
+    type Response = MuxSelectResponse;
+}
+
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct MuxSelectRequest {
+    /// One of the mux's inputs, or [NONE_TOPIC]
+    pub topic: String,
+}
+
+impl RosMessageType for MuxSelectRequest {
+    const ROS_TYPE_NAME: &'static str = "topic_tools/MuxSelectRequest";
+    const MD5SUM: &'static str = "d8f94bae31b356b24d0427f80426d0c3";
+    const DEFINITION: &'static str = "string topic";
+}
+
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct MuxSelectResponse {
+    /// The input selected before the request, or [NONE_TOPIC]
+    pub prev_topic: String,
+}
+
+impl RosMessageType for MuxSelectResponse {
+    const ROS_TYPE_NAME: &'static str = "topic_tools/MuxSelectResponse";
+    const MD5SUM: &'static str = "3db0a473debdbafea387c9e49358c320";
+    const DEFINITION: &'static str = "string prev_topic";
+}
+
+/// Equivalent of `topic_tools/MuxList`, served on `<mux>/list`
+pub struct MuxList {}
+
+impl RosServiceType for MuxList {
+    const ROS_SERVICE_NAME: &'static str = "topic_tools/MuxList";
+    const MD5SUM: &'static str = "b0eef9a05d4e829092fc2f2c3c2aad3d";
+    type Request = MuxListRequest;
+    type Response = MuxListResponse;
+}
+
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct MuxListRequest {}
+
+impl RosMessageType for MuxListRequest {
+    const ROS_TYPE_NAME: &'static str = "topic_tools/MuxListRequest";
+    const MD5SUM: &'static str = "d41d8cd98f00b204e9800998ecf8427e";
+    const DEFINITION: &'static str = "";
+}
+
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct MuxListResponse {
+    pub topics: Vec<String>,
+}
+
+impl RosMessageType for MuxListResponse {
+    const ROS_TYPE_NAME: &'static str = "topic_tools/MuxListResponse";
+    const MD5SUM: &'static str = "b0eef9a05d4e829092fc2f2c3c2aad3d";
+    const DEFINITION: &'static str = "string[] topics";
+}
+
+// The inputs of a Mux and which one is forwarded, separate from any ROS communication
+struct Selection {
+    inputs: Vec<String>,
+    selected: Option<String>,
+}
+
+impl Selection {
+    /// Selects `topic`, returning the previously selected input, or an error if `topic` isn't an input
+    fn select(&mut self, topic: &str) -> Result<Option<String>> {
+        let selected = if topic == NONE_TOPIC {
+            None
+        } else if self.inputs.iter().any(|input| input == topic) {
+            Some(topic.to_string())
+        } else {
+            return Err(Error::InvalidName(format!(
+                "{topic} is not an input of this mux"
+            )));
+        };
+        Ok(std::mem::replace(&mut self.selected, selected))
+    }
+
+    fn is_selected(&self, topic: &str) -> bool {
+        self.selected.as_deref() == Some(topic)
+    }
+}
+
+/// Forwards the messages of one of several input topics to a single output topic, like topic_tools' mux.
+///
+/// The first input is selected initially. Which input is forwarded can be switched locally with [Mux::select]
+/// or remotely through its services. For a mux named `/cmd_vel_mux` it provides:
+/// - `/cmd_vel_mux/select`: a [MuxSelect] service switching the selected input, [NONE_TOPIC] selects none
+/// - `/cmd_vel_mux/list`: a [MuxList] service returning the inputs
+///
+/// Forwarding stops and the services are removed when the Mux is dropped.
+///
+/// ```ignore
+/// let mux = Mux::new::<geometry_msgs::Twist>(
+///     &ros,
+///     "/cmd_vel_mux",
+///     "/cmd_vel",
+///     &["/joy/cmd_vel", "/nav/cmd_vel"],
+/// )
+/// .await?;
+/// // Hand control to the navigation stack
+/// mux.select("/nav/cmd_vel")?;
+/// ```
+pub struct Mux<T: ServiceProvider> {
+    selection: Arc<Mutex<Selection>>,
+    _stop: DropGuard,
+    _select: T::ServiceServer,
+    _list: T::ServiceServer,
+}
+
+impl<T: TopicProvider + ServiceProvider> Mux<T> {
+    /// Subscribes to each of `inputs`, advertises `output` and the mux services under `node_name` using `ros`
+    pub async fn new<M: RosMessageType>(
+        ros: &T,
+        node_name: &str,
+        output: &str,
+        inputs: &[&str],
+    ) -> Result<Self> {
+        let selection = Arc::new(Mutex::new(Selection {
+            inputs: inputs.iter().map(|input| input.to_string()).collect(),
+            selected: inputs.first().map(|input| input.to_string()),
+        }));
+        let publisher = Arc::new(ros.advertise::<M>(output).await?);
+        let stop = CancellationToken::new();
+
+        for input in inputs {
+            let mut subscriber = ros.subscribe::<M>(*input).await?;
+            let input = input.to_string();
+            let selection = selection.clone();
+            let publisher = publisher.clone();
+            let stop = stop.clone();
+            tokio::spawn(async move {
+                loop {
+                    let msg = tokio::select! {
+                        _ = stop.cancelled() => break,
+                        msg = subscriber.next() => msg,
+                    };
+                    match msg {
+                        Ok(msg) => {
+                            // Checked per message so a switch takes effect with the next message received
+                            let selected = selection.lock().unwrap().is_selected(&input);
+                            if selected {
+                                // A failed publish only loses this message, keep forwarding
+                                let _ = publisher.publish(&msg).await;
+                            }
+                        }
+                        // A single malformed message shouldn't stop the input
+                        Err(Error::SerializationError(_)) => continue,
+                        Err(_) => break,
+                    }
+                }
+            });
+        }
+
+        let select = {
+            let selection = selection.clone();
+            move |request: MuxSelectRequest| {
+                let prev_topic = selection.lock().unwrap().select(&request.topic)?;
+                Ok(MuxSelectResponse {
+                    prev_topic: prev_topic.unwrap_or_else(|| NONE_TOPIC.to_string()),
+                })
+            }
+        };
+        let _select = ros
+            .advertise_service::<MuxSelect, _>(format!("{node_name}/select"), select)
+            .await?;
+
+        let list = {
+            let selection = selection.clone();
+            move |_request: MuxListRequest| {
+                Ok(MuxListResponse {
+                    topics: selection.lock().unwrap().inputs.clone(),
+                })
+            }
+        };
+        let _list = ros
+            .advertise_service::<MuxList, _>(format!("{node_name}/list"), list)
+            .await?;
+
+        Ok(Self {
+            selection,
+            _stop: stop.drop_guard(),
+            _select,
+            _list,
+        })
+    }
+
+    /// Switches the forwarded input to `topic`, or to none with [NONE_TOPIC], returning the previously selected input.
+    ///
+    /// Returns an error if `topic` isn't one of the inputs.
+    pub fn select(&self, topic: &str) -> Result<Option<String>> {
+        self.selection.lock().unwrap().select(topic)
+    }
+
+    /// The currently forwarded input, if any
+    pub fn selected(&self) -> Option<String> {
+        self.selection.lock().unwrap().selected.clone()
+    }
+
+    /// The input topics, in the order given to [Mux::new]
+    pub fn inputs(&self) -> Vec<String> {
+        self.selection.lock().unwrap().inputs.clone()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn md5sums_match_definitions() {
+        use crate::md5sum::from_message_definition;
+        for (name, definition, md5sum) in [
+            (
+                MuxSelectRequest::ROS_TYPE_NAME,
+                MuxSelectRequest::DEFINITION,
+                MuxSelectRequest::MD5SUM,
+            ),
+            (
+                MuxSelectResponse::ROS_TYPE_NAME,
+                MuxSelectResponse::DEFINITION,
+                MuxSelectResponse::MD5SUM,
+            ),
+            (
+                MuxListResponse::ROS_TYPE_NAME,
+                MuxListResponse::DEFINITION,
+                MuxListResponse::MD5SUM,
+            ),
+        ] {
+            assert_eq!(from_message_definition(name, definition).unwrap(), md5sum);
+        }
+        // A service's md5sum covers its request then its response
+        assert_eq!(
+            format!("{:x}", md5::compute("string topicstring prev_topic")),
+            MuxSelect::MD5SUM
+        );
+        assert_eq!(
+            format!("{:x}", md5::compute("string[] topics")),
+            MuxList::MD5SUM
+        );
+    }
+
+    #[test]
+    fn selects_only_inputs() {
+        let mut selection = Selection {
+            inputs: vec!["/a".to_string(), "/b".to_string()],
+            selected: Some("/a".to_string()),
+        };
+        assert_eq!(selection.select("/b").unwrap().as_deref(), Some("/a"));
+        assert!(selection.is_selected("/b"));
+        assert!(!selection.is_selected("/a"));
+
+        assert!(selection.select("/c").is_err());
+        assert!(selection.is_selected("/b"));
+
+        assert_eq!(selection.select(NONE_TOPIC).unwrap().as_deref(), Some("/b"));
+        assert_eq!(selection.select("/a").unwrap(), None);
+    }
+}
@@ -0,0 +1,358 @@
+use crate::topic_name::{GlobalTopicName, ToGlobalTopicName};
+use crate::Error as RError;
+
+/// A ROS graph resource name, as used for nodes, topics, services and parameters.
+///
+/// Unlike [GlobalTopicName], a Name may be global (`/foo/bar`), relative (`foo/bar`) or private (`~foo/bar`),
+/// and can be resolved into a global name relative to a node with [Name::resolve_to_global].
+///
+/// Names are normalized when they are created:
+/// * Repeated slashes are collapsed, `/foo//bar` becomes `/foo/bar`
+/// * Trailing slashes are removed, `/foo/bar/` becomes `/foo/bar`
+/// * ROS2 style private names are converted to ROS1 style, `~/foo` becomes `~foo`
+///
+/// After normalization the name is validated against ROS1's rules:
+/// the first character must be a letter, `/` or `~`, and the remaining characters must be
+/// letters, digits, underscores or `/`.
+// See https://wiki.ros.org/Names
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Name {
+    inner: String,
+}
+
+impl Name {
+    /// Normalizes and validates the given name, returning [RError::InvalidName] if it is not a valid ROS name
+    pub fn new(name: impl Into<String>) -> Result<Name, RError> {
+        let name: String = name.into();
+        let normalized = normalize_name(&name);
+        match validate_name(&normalized) {
+            Ok(()) => Ok(Self { inner: normalized }),
+            Err(failures) => Err(RError::InvalidName(format!(
+                "Invalid name: {name}, reasons: {failures:?}"
+            ))),
+        }
+    }
+
+    /// True if the name starts with `/`
+    pub fn is_global(&self) -> bool {
+        self.inner.starts_with('/')
+    }
+
+    /// True if the name starts with `~`, meaning it is resolved within the node's own namespace
+    pub fn is_private(&self) -> bool {
+        self.inner.starts_with('~')
+    }
+
+    /// True if the name is resolved within the node's parent namespace
+    pub fn is_relative(&self) -> bool {
+        !self.is_global() && !self.is_private()
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.inner
+    }
+
+    /// Returns the namespace containing this name, e.g. `/foo` for `/foo/bar` and `/` for `/foo`
+    /// Returns None for relative and private names which have only a single component
+    pub fn namespace(&self) -> Option<&str> {
+        match self.inner.rfind('/') {
+            Some(0) => Some("/"),
+            Some(index) => Some(&self.inner[..index]),
+            None => None,
+        }
+    }
+
+    /// Resolves this name into a global name following ROS1's rules:
+    /// * Global names are returned unchanged
+    /// * Private names are placed under the node's name, `~bar` for node `/wg/node` is `/wg/node/bar`
+    /// * Relative names are placed in the node's namespace, `bar` for node `/wg/node` is `/wg/bar`
+    ///
+    /// `node_name` is expected to be a global name.
+    pub fn resolve_to_global(&self, node_name: &Name) -> Self {
+        if self.is_global() {
+            return self.clone();
+        }
+        let inner = if self.is_private() {
+            format!("{}/{}", node_name.inner, &self.inner[1..])
+        } else {
+            match node_name.namespace() {
+                Some("/") | None => format!("/{}", self.inner),
+                Some(namespace) => format!("{namespace}/{}", self.inner),
+            }
+        };
+        Name { inner }
+    }
+}
+
+impl std::fmt::Display for Name {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.inner.fmt(f)
+    }
+}
+
+impl From<Name> for String {
+    fn from(name: Name) -> Self {
+        name.inner
+    }
+}
+
+impl AsRef<str> for Name {
+    fn as_ref(&self) -> &str {
+        &self.inner
+    }
+}
+
+// Only global names can be used directly as topic names
+impl ToGlobalTopicName for Name {
+    fn to_global_name(self) -> Result<GlobalTopicName, RError> {
+        GlobalTopicName::new(self.inner)
+    }
+}
+
+impl ToGlobalTopicName for &Name {
+    fn to_global_name(self) -> Result<GlobalTopicName, RError> {
+        GlobalTopicName::new(self.inner.clone())
+    }
+}
+
+/// Applies the normalization described on [Name] without performing any validation
+pub fn normalize_name(name: &str) -> String {
+    let mut normalized = String::with_capacity(name.len());
+    for c in name.chars() {
+        if c == '/' && normalized.ends_with('/') {
+            continue;
+        }
+        normalized.push(c);
+    }
+    if let Some(private) = normalized.strip_prefix("~/") {
+        normalized = format!("~{private}");
+    }
+    while normalized.len() > 1 && normalized.ends_with('/') {
+        normalized.pop();
+    }
+    normalized
+}
+
+/// Check the name against ROS1's rules for validity
+/// Returns a list of reasons the name is invalid
+fn validate_name(name: &str) -> Result<(), Vec<String>> {
+    let mut failures = vec![];
+    let mut chars = name.chars();
+    match chars.next() {
+        None => failures.push("Name must not be empty".to_string()),
+        Some(c) if c.is_ascii_alphabetic() || c == '/' || c == '~' => {}
+        Some(_) => failures.push("Name must start with a letter, '/' or '~'".to_string()),
+    }
+    if !chars.all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '/') {
+        failures.push(
+            "Name must only contain letters, digits, underscores, and forward slashes after its first character"
+                .to_string(),
+        );
+    }
+    if name == "/" || name == "~" {
+        failures.push("Name must contain at least one component".to_string());
+    }
+
+    if failures.is_empty() {
+        Ok(())
+    } else {
+        Err(failures)
+    }
+}
+
+/// A glob pattern matched against graph resource names, e.g. for selecting topics to record or bridge.
+///
+/// Patterns are split into components on `/`, and within a component:
+/// * `*` matches any run of characters, e.g. `/camera/*_raw` matches `/camera/image_raw`
+/// * `?` matches any single character
+///
+/// A component consisting only of `**` matches any number of components (including none),
+/// e.g. `/robot/**/cmd_vel` matches both `/robot/cmd_vel` and `/robot/arm/left/cmd_vel`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamePattern {
+    pattern: String,
+    components: Vec<String>,
+}
+
+impl NamePattern {
+    /// Normalizes the pattern as with [Name], and returns [RError::InvalidName] if it could never match a valid name
+    pub fn new(pattern: impl Into<String>) -> Result<NamePattern, RError> {
+        let pattern: String = pattern.into();
+        let normalized = normalize_name(&pattern);
+        // Replace wildcards with a valid character to check the rest of the pattern
+        let skeleton = normalized.replace(['*', '?'], "a");
+        if let Err(failures) = validate_name(&skeleton) {
+            return Err(RError::InvalidName(format!(
+                "Invalid name pattern: {pattern}, reasons: {failures:?}"
+            )));
+        }
+        let components = normalized.split('/').map(str::to_owned).collect();
+        Ok(NamePattern {
+            pattern: normalized,
+            components,
+        })
+    }
+
+    /// True if the given name matches this pattern, the name is normalized before matching
+    pub fn matches(&self, name: impl AsRef<str>) -> bool {
+        let name = normalize_name(name.as_ref());
+        let name_components = name.split('/').collect::<Vec<_>>();
+        let pattern_components = self
+            .components
+            .iter()
+            .map(String::as_str)
+            .collect::<Vec<_>>();
+        match_components(&pattern_components, &name_components)
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.pattern
+    }
+}
+
+impl std::fmt::Display for NamePattern {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.pattern.fmt(f)
+    }
+}
+
+fn match_components(pattern: &[&str], name: &[&str]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((&"**", rest)) => (0..=name.len()).any(|skip| match_components(rest, &name[skip..])),
+        Some((component, rest)) => match name.split_first() {
+            Some((name_component, name_rest)) => {
+                match_component(component.as_bytes(), name_component.as_bytes())
+                    && match_components(rest, name_rest)
+            }
+            None => false,
+        },
+    }
+}
+
+// Wildcard matching within a single component, names are ascii so matching bytes is sufficient
+fn match_component(pattern: &[u8], name: &[u8]) -> bool {
+    match pattern.split_first() {
+        None => name.is_empty(),
+        Some((b'*', rest)) => (0..=name.len()).any(|skip| match_component(rest, &name[skip..])),
+        Some((b'?', rest)) => !name.is_empty() && match_component(rest, &name[1..]),
+        Some((c, rest)) => name.first() == Some(c) && match_component(rest, &name[1..]),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_name_valid() {
+        assert!(Name::new("base").is_ok());
+        assert!(Name::new("relative/name").is_ok());
+        assert!(Name::new("/global/name").is_ok());
+        assert!(Name::new("~private/name").is_ok());
+        assert!(Name::new("/123/_456").is_ok());
+
+        // These are invalid names
+        assert!(Name::new("").is_err());
+        assert!(Name::new("/").is_err());
+        assert!(Name::new("~").is_err());
+        assert!(Name::new("~~").is_err());
+        assert!(Name::new("_leading").is_err());
+        assert!(Name::new("/with space").is_err());
+        assert!(Name::new("/dash-ed").is_err());
+        assert!(Name::new("/middle~tilde").is_err());
+        assert!(matches!(Name::new("#"), Err(RError::InvalidName(_))));
+    }
+
+    #[test]
+    fn test_name_normalization() {
+        assert_eq!(Name::new("/foo//bar").unwrap().as_str(), "/foo/bar");
+        assert_eq!(Name::new("/foo/bar/").unwrap().as_str(), "/foo/bar");
+        assert_eq!(Name::new("//foo///bar//").unwrap().as_str(), "/foo/bar");
+        assert_eq!(Name::new("~/foo").unwrap().as_str(), "~foo");
+        assert_eq!(Name::new("foo/").unwrap().as_str(), "foo");
+        assert!(Name::new("~/foo").unwrap().is_private());
+        assert!(Name::new("foo").unwrap().is_relative());
+        assert!(Name::new("/foo").unwrap().is_global());
+    }
+
+    #[test]
+    fn test_namespace() {
+        assert_eq!(Name::new("/foo").unwrap().namespace(), Some("/"));
+        assert_eq!(Name::new("/foo/bar").unwrap().namespace(), Some("/foo"));
+        assert_eq!(Name::new("foo").unwrap().namespace(), None);
+    }
+
+    // Examples pulled from http://wiki.ros.org/Names
+    #[test]
+    fn resolve_name() {
+        let node1 = Name::new("/node1").unwrap();
+        assert_eq!(
+            Name::new("bar").unwrap().resolve_to_global(&node1),
+            Name::new("/bar").unwrap()
+        );
+        assert_eq!(
+            Name::new("/bar").unwrap().resolve_to_global(&node1),
+            Name::new("/bar").unwrap()
+        );
+        assert_eq!(
+            Name::new("~bar").unwrap().resolve_to_global(&node1),
+            Name::new("/node1/bar").unwrap()
+        );
+
+        let node2 = Name::new("/wg/node2").unwrap();
+        assert_eq!(
+            Name::new("bar").unwrap().resolve_to_global(&node2),
+            Name::new("/wg/bar").unwrap()
+        );
+        assert_eq!(
+            Name::new("/bar").unwrap().resolve_to_global(&node2),
+            Name::new("/bar").unwrap()
+        );
+        assert_eq!(
+            Name::new("~bar").unwrap().resolve_to_global(&node2),
+            Name::new("/wg/node2/bar").unwrap()
+        );
+
+        let node3 = Name::new("/wg/node3").unwrap();
+        assert_eq!(
+            Name::new("foo/bar").unwrap().resolve_to_global(&node3),
+            Name::new("/wg/foo/bar").unwrap()
+        );
+        assert_eq!(
+            Name::new("/foo/bar").unwrap().resolve_to_global(&node3),
+            Name::new("/foo/bar").unwrap()
+        );
+        assert_eq!(
+            Name::new("~foo/bar").unwrap().resolve_to_global(&node3),
+            Name::new("/wg/node3/foo/bar").unwrap()
+        );
+    }
+
+    #[test]
+    fn test_global_conversion() {
+        assert!(Name::new("/chatter").unwrap().to_global_name().is_ok());
+        assert!(Name::new("chatter").unwrap().to_global_name().is_err());
+    }
+
+    #[test]
+    fn test_name_patterns() {
+        let pattern = NamePattern::new("/camera/*_raw").unwrap();
+        assert!(pattern.matches("/camera/image_raw"));
+        assert!(pattern.matches("/camera//depth_raw/"));
+        assert!(!pattern.matches("/camera/image_rect"));
+        assert!(!pattern.matches("/camera/left/image_raw"));
+
+        let pattern = NamePattern::new("/robot/**/cmd_vel").unwrap();
+        assert!(pattern.matches("/robot/cmd_vel"));
+        assert!(pattern.matches("/robot/arm/left/cmd_vel"));
+        assert!(!pattern.matches("/other/cmd_vel"));
+
+        let pattern = NamePattern::new("/imu?").unwrap();
+        assert!(pattern.matches("/imu1"));
+        assert!(!pattern.matches("/imu"));
+
+        assert!(NamePattern::new("/**").unwrap().matches("/anything/at/all"));
+        assert!(NamePattern::new("/bad-pattern*").is_err());
+    }
+}
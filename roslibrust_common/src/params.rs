@@ -0,0 +1,466 @@
+use crate::{Error, Result, RosMessageType, RosServiceType, ServiceProvider};
+use serde::{de::DeserializeOwned, Serialize};
+use serde_json::Value;
+use std::future::Future;
+
+// Parameters differ a lot between ROS1, where the master hosts a single tree of parameters, and ROS2, where every
+// node hosts its own parameters behind a set of services. ParameterProvider covers what the two have in common:
+// reading, writing and listing parameters by their global name.
+
+/// Represents that an object can read and write ROS parameters.
+///
+/// Parameters are identified by global names. In ROS2, where parameters belong to a node, the last component of the
+/// name is the parameter and the rest is the node, e.g. `/robot/controller/gain` is the parameter `gain` of the node
+/// `/robot/controller`.
+pub trait ParameterProvider {
+    /// Returns the value of the parameter, or `None` if it isn't set.
+    fn get_param<T: DeserializeOwned + Send + 'static>(
+        &self,
+        name: &str,
+    ) -> impl Future<Output = Result<Option<T>>> + Send;
+
+    /// Sets the parameter to `value`.
+    fn set_param<T: Serialize + Sync>(
+        &self,
+        name: &str,
+        value: &T,
+    ) -> impl Future<Output = Result<()>> + Send;
+
+    /// Returns the global names of every parameter under `namespace`.
+    ///
+    /// In ROS2 `namespace` is the name of a node, and the node's parameters are returned.
+    fn list_params(&self, namespace: &str) -> impl Future<Output = Result<Vec<String>>> + Send;
+}
+
+/// [ParameterProvider] for ROS2 nodes, calling their `get_parameters`, `set_parameters` and `list_parameters`
+/// services through any [ServiceProvider], e.g. a rosbridge client connected to a ROS2 rosbridge_server.
+///
+/// Values are converted to the matching ROS2 parameter type: booleans, integers, floats and strings, or arrays of one
+/// of those. Byte array parameters are not supported.
+#[derive(Clone)]
+pub struct Ros2Parameters<S> {
+    provider: S,
+}
+
+impl<S> Ros2Parameters<S> {
+    pub fn new(provider: S) -> Self {
+        Self { provider }
+    }
+}
+
+impl<S: ServiceProvider + Send + Sync> Ros2Parameters<S> {
+    // Fetches the raw value of a node's parameter
+    async fn get_value(&self, node: &str, param: &str) -> Result<ParameterValue> {
+        let response = self
+            .provider
+            .call_service::<GetParameters>(
+                format!("{node}/get_parameters"),
+                GetParametersRequest {
+                    names: vec![param.to_owned()],
+                },
+            )
+            .await?;
+        response.values.into_iter().next().ok_or_else(|| {
+            Error::ServerError(format!("{node} returned no value for parameter {param}"))
+        })
+    }
+}
+
+impl<S: ServiceProvider + Send + Sync> ParameterProvider for Ros2Parameters<S> {
+    async fn get_param<T: DeserializeOwned + Send + 'static>(
+        &self,
+        name: &str,
+    ) -> Result<Option<T>> {
+        let (node, param) = split_name(name)?;
+        let value = self.get_value(node, param).await?;
+        value
+            .to_json()?
+            .map(serde_json::from_value)
+            .transpose()
+            .map_err(|e| Error::SerializationError(e.to_string()))
+    }
+
+    async fn set_param<T: Serialize + Sync>(&self, name: &str, value: &T) -> Result<()> {
+        let (node, param) = split_name(name)?;
+        let value =
+            serde_json::to_value(value).map_err(|e| Error::SerializationError(e.to_string()))?;
+        // The type of an empty array can't be inferred, keep the type the parameter already has
+        let value = match &value {
+            Value::Array(values) if values.is_empty() => {
+                let current = self.get_value(node, param).await?;
+                ParameterValue::empty_array(current.r#type)?
+            }
+            _ => ParameterValue::from_json(&value)?,
+        };
+        let response = self
+            .provider
+            .call_service::<SetParameters>(
+                format!("{node}/set_parameters"),
+                SetParametersRequest {
+                    parameters: vec![Parameter {
+                        name: param.to_owned(),
+                        value,
+                    }],
+                },
+            )
+            .await?;
+        match response.results.into_iter().next() {
+            Some(result) if result.successful => Ok(()),
+            Some(result) => Err(Error::ServerError(format!(
+                "{node} rejected parameter {param}: {}",
+                result.reason
+            ))),
+            None => Err(Error::ServerError(format!(
+                "{node} returned no result setting parameter {param}"
+            ))),
+        }
+    }
+
+    async fn list_params(&self, namespace: &str) -> Result<Vec<String>> {
+        let node = namespace.trim_end_matches('/');
+        let response = self
+            .provider
+            .call_service::<ListParameters>(
+                format!("{node}/list_parameters"),
+                ListParametersRequest {
+                    prefixes: vec![],
+                    depth: ListParametersRequest::DEPTH_RECURSIVE,
+                },
+            )
+            .await?;
+        Ok(response
+            .result
+            .names
+            .into_iter()
+            .map(|name| format!("{node}/{name}"))
+            .collect())
+    }
+}
+
+// Splits a global parameter name into the node and the node's parameter
+fn split_name(name: &str) -> Result<(&str, &str)> {
+    match name.rsplit_once('/') {
+        Some((node, param)) if !node.is_empty() && !param.is_empty() => Ok((node, param)),
+        _ => Err(Error::InvalidName(format!(
+            "{name} does not name a node's parameter, expected /node_name/parameter_name"
+        ))),
+    }
+}
+
+/// Equivalent of ROS2's `rcl_interfaces/ParameterValue`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct ParameterValue {
+    pub r#type: u8,
+    #[serde(default)]
+    pub bool_value: bool,
+    #[serde(default)]
+    pub integer_value: i64,
+    #[serde(default)]
+    pub double_value: f64,
+    #[serde(default)]
+    pub string_value: String,
+    // rosbridge may send byte arrays base64 encoded, they are never read so it isn't decoded
+    #[serde(skip_deserializing)]
+    pub byte_array_value: Vec<u8>,
+    #[serde(default)]
+    pub bool_array_value: Vec<bool>,
+    #[serde(default)]
+    pub integer_array_value: Vec<i64>,
+    #[serde(default)]
+    pub double_array_value: Vec<f64>,
+    #[serde(default)]
+    pub string_array_value: Vec<String>,
+}
+
+impl ParameterValue {
+    pub const PARAMETER_NOT_SET: u8 = 0;
+    pub const PARAMETER_BOOL: u8 = 1;
+    pub const PARAMETER_INTEGER: u8 = 2;
+    pub const PARAMETER_DOUBLE: u8 = 3;
+    pub const PARAMETER_STRING: u8 = 4;
+    pub const PARAMETER_BYTE_ARRAY: u8 = 5;
+    pub const PARAMETER_BOOL_ARRAY: u8 = 6;
+    pub const PARAMETER_INTEGER_ARRAY: u8 = 7;
+    pub const PARAMETER_DOUBLE_ARRAY: u8 = 8;
+    pub const PARAMETER_STRING_ARRAY: u8 = 9;
+
+    /// Converts to JSON, returning None if the parameter isn't set
+    pub fn to_json(&self) -> Result<Option<Value>> {
+        let value = match self.r#type {
+            Self::PARAMETER_NOT_SET => return Ok(None),
+            Self::PARAMETER_BOOL => Value::from(self.bool_value),
+            Self::PARAMETER_INTEGER => Value::from(self.integer_value),
+            Self::PARAMETER_DOUBLE => Value::from(self.double_value),
+            Self::PARAMETER_STRING => Value::from(self.string_value.clone()),
+            Self::PARAMETER_BOOL_ARRAY => Value::from(self.bool_array_value.clone()),
+            Self::PARAMETER_INTEGER_ARRAY => Value::from(self.integer_array_value.clone()),
+            Self::PARAMETER_DOUBLE_ARRAY => Value::from(self.double_array_value.clone()),
+            Self::PARAMETER_STRING_ARRAY => Value::from(self.string_array_value.clone()),
+            other => {
+                return Err(Error::SerializationError(format!(
+                    "Unsupported parameter type {other}"
+                )))
+            }
+        };
+        Ok(Some(value))
+    }
+
+    /// Converts from JSON, inferring the parameter type from the value
+    pub fn from_json(value: &Value) -> Result<Self> {
+        let unsupported = || {
+            Error::SerializationError(format!(
+                "{value} can't be converted to a ROS2 parameter, only booleans, numbers, strings and arrays of one of those are supported"
+            ))
+        };
+        let mut param = ParameterValue::default();
+        match value {
+            Value::Bool(b) => {
+                param.r#type = Self::PARAMETER_BOOL;
+                param.bool_value = *b;
+            }
+            Value::Number(n) => match n.as_i64() {
+                Some(i) => {
+                    param.r#type = Self::PARAMETER_INTEGER;
+                    param.integer_value = i;
+                }
+                None => {
+                    param.r#type = Self::PARAMETER_DOUBLE;
+                    param.double_value = n.as_f64().ok_or_else(unsupported)?;
+                }
+            },
+            Value::String(s) => {
+                param.r#type = Self::PARAMETER_STRING;
+                param.string_value = s.clone();
+            }
+            Value::Array(values) => {
+                if let Some(bools) = values.iter().map(Value::as_bool).collect() {
+                    param.r#type = Self::PARAMETER_BOOL_ARRAY;
+                    param.bool_array_value = bools;
+                } else if let Some(ints) = values.iter().map(Value::as_i64).collect() {
+                    param.r#type = Self::PARAMETER_INTEGER_ARRAY;
+                    param.integer_array_value = ints;
+                } else if let Some(doubles) = values.iter().map(Value::as_f64).collect() {
+                    param.r#type = Self::PARAMETER_DOUBLE_ARRAY;
+                    param.double_array_value = doubles;
+                } else if let Some(strings) = values
+                    .iter()
+                    .map(|v| v.as_str().map(str::to_owned))
+                    .collect()
+                {
+                    param.r#type = Self::PARAMETER_STRING_ARRAY;
+                    param.string_array_value = strings;
+                } else {
+                    return Err(unsupported());
+                }
+            }
+            Value::Null | Value::Object(_) => return Err(unsupported()),
+        }
+        Ok(param)
+    }
+
+    // An empty array of the given array type
+    fn empty_array(r#type: u8) -> Result<Self> {
+        match r#type {
+            Self::PARAMETER_BOOL_ARRAY
+            | Self::PARAMETER_INTEGER_ARRAY
+            | Self::PARAMETER_DOUBLE_ARRAY
+            | Self::PARAMETER_STRING_ARRAY => Ok(ParameterValue {
+                r#type,
+                ..Default::default()
+            }),
+            // Nothing to go on, and an empty list of strings is the most common
+            Self::PARAMETER_NOT_SET => Ok(ParameterValue {
+                r#type: Self::PARAMETER_STRING_ARRAY,
+                ..Default::default()
+            }),
+            other => Err(Error::SerializationError(format!(
+                "Can't set a parameter of type {other} to an empty array"
+            ))),
+        }
+    }
+}
+
+impl RosMessageType for ParameterValue {
+    const ROS_TYPE_NAME: &'static str = "rcl_interfaces/ParameterValue";
+    const ROS2_TYPE_NAME: &'static str = "rcl_interfaces::msg::dds_::ParameterValue_";
+}
+
+/// Equivalent of ROS2's `rcl_interfaces/Parameter`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct Parameter {
+    pub name: String,
+    pub value: ParameterValue,
+}
+
+impl RosMessageType for Parameter {
+    const ROS_TYPE_NAME: &'static str = "rcl_interfaces/Parameter";
+    const ROS2_TYPE_NAME: &'static str = "rcl_interfaces::msg::dds_::Parameter_";
+}
+
+/// Equivalent of ROS2's `rcl_interfaces/SetParametersResult`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct SetParametersResult {
+    pub successful: bool,
+    pub reason: String,
+}
+
+impl RosMessageType for SetParametersResult {
+    const ROS_TYPE_NAME: &'static str = "rcl_interfaces/SetParametersResult";
+    const ROS2_TYPE_NAME: &'static str = "rcl_interfaces::msg::dds_::SetParametersResult_";
+}
+
+/// Equivalent of ROS2's `rcl_interfaces/ListParametersResult`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct ListParametersResult {
+    pub names: Vec<String>,
+    pub prefixes: Vec<String>,
+}
+
+impl RosMessageType for ListParametersResult {
+    const ROS_TYPE_NAME: &'static str = "rcl_interfaces/ListParametersResult";
+    const ROS2_TYPE_NAME: &'static str = "rcl_interfaces::msg::dds_::ListParametersResult_";
+}
+
+/// Request of ROS2's `rcl_interfaces/GetParameters`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct GetParametersRequest {
+    pub names: Vec<String>,
+}
+
+impl RosMessageType for GetParametersRequest {
+    const ROS_TYPE_NAME: &'static str = "rcl_interfaces/GetParametersRequest";
+    const ROS2_TYPE_NAME: &'static str = "rcl_interfaces::srv::dds_::GetParameters_Request_";
+}
+
+/// Response of ROS2's `rcl_interfaces/GetParameters`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct GetParametersResponse {
+    pub values: Vec<ParameterValue>,
+}
+
+impl RosMessageType for GetParametersResponse {
+    const ROS_TYPE_NAME: &'static str = "rcl_interfaces/GetParametersResponse";
+    const ROS2_TYPE_NAME: &'static str = "rcl_interfaces::srv::dds_::GetParameters_Response_";
+}
+
+/// Equivalent of ROS2's `rcl_interfaces/GetParameters`
+pub struct GetParameters {}
+
+impl RosServiceType for GetParameters {
+    const ROS_SERVICE_NAME: &'static str = "rcl_interfaces/GetParameters";
+    const ROS2_TYPE_NAME: &'static str = "rcl_interfaces::srv::dds_::GetParameters_";
+    type Request = GetParametersRequest;
+    type Response = GetParametersResponse;
+}
+
+/// Request of ROS2's `rcl_interfaces/SetParameters`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct SetParametersRequest {
+    pub parameters: Vec<Parameter>,
+}
+
+impl RosMessageType for SetParametersRequest {
+    const ROS_TYPE_NAME: &'static str = "rcl_interfaces/SetParametersRequest";
+    const ROS2_TYPE_NAME: &'static str = "rcl_interfaces::srv::dds_::SetParameters_Request_";
+}
+
+/// Response of ROS2's `rcl_interfaces/SetParameters`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct SetParametersResponse {
+    pub results: Vec<SetParametersResult>,
+}
+
+impl RosMessageType for SetParametersResponse {
+    const ROS_TYPE_NAME: &'static str = "rcl_interfaces/SetParametersResponse";
+    const ROS2_TYPE_NAME: &'static str = "rcl_interfaces::srv::dds_::SetParameters_Response_";
+}
+
+/// Equivalent of ROS2's `rcl_interfaces/SetParameters`
+pub struct SetParameters {}
+
+impl RosServiceType for SetParameters {
+    const ROS_SERVICE_NAME: &'static str = "rcl_interfaces/SetParameters";
+    const ROS2_TYPE_NAME: &'static str = "rcl_interfaces::srv::dds_::SetParameters_";
+    type Request = SetParametersRequest;
+    type Response = SetParametersResponse;
+}
+
+/// Request of ROS2's `rcl_interfaces/ListParameters`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct ListParametersRequest {
+    pub prefixes: Vec<String>,
+    pub depth: u64,
+}
+
+impl ListParametersRequest {
+    pub const DEPTH_RECURSIVE: u64 = 0;
+}
+
+impl RosMessageType for ListParametersRequest {
+    const ROS_TYPE_NAME: &'static str = "rcl_interfaces/ListParametersRequest";
+    const ROS2_TYPE_NAME: &'static str = "rcl_interfaces::srv::dds_::ListParameters_Request_";
+}
+
+/// Response of ROS2's `rcl_interfaces/ListParameters`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct ListParametersResponse {
+    pub result: ListParametersResult,
+}
+
+impl RosMessageType for ListParametersResponse {
+    const ROS_TYPE_NAME: &'static str = "rcl_interfaces/ListParametersResponse";
+    const ROS2_TYPE_NAME: &'static str = "rcl_interfaces::srv::dds_::ListParameters_Response_";
+}
+
+/// Equivalent of ROS2's `rcl_interfaces/ListParameters`
+pub struct ListParameters {}
+
+impl RosServiceType for ListParameters {
+    const ROS_SERVICE_NAME: &'static str = "rcl_interfaces/ListParameters";
+    const ROS2_TYPE_NAME: &'static str = "rcl_interfaces::srv::dds_::ListParameters_";
+    type Request = ListParametersRequest;
+    type Response = ListParametersResponse;
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parameter_values_round_trip_through_json() {
+        let values = [
+            serde_json::json!(true),
+            serde_json::json!(-3),
+            serde_json::json!(1.5),
+            serde_json::json!("hello"),
+            serde_json::json!([true, false]),
+            serde_json::json!([1, 2]),
+            serde_json::json!([0.5, 2.5]),
+            serde_json::json!(["a", "b"]),
+        ];
+        let types = [1, 2, 3, 4, 6, 7, 8, 9];
+        for (value, r#type) in values.iter().zip(types) {
+            let param = ParameterValue::from_json(value).unwrap();
+            assert_eq!(param.r#type, r#type, "{value}");
+            assert_eq!(param.to_json().unwrap().as_ref(), Some(value));
+        }
+
+        assert!(ParameterValue::from_json(&serde_json::json!({"a": 1})).is_err());
+        assert!(ParameterValue::from_json(&serde_json::json!([1, "a"])).is_err());
+        assert_eq!(ParameterValue::default().to_json().unwrap(), None);
+    }
+
+    #[test]
+    fn parameter_names_are_split_into_node_and_parameter() {
+        assert_eq!(
+            split_name("/robot/controller/gain").unwrap(),
+            ("/robot/controller", "gain")
+        );
+        assert_eq!(
+            split_name("/talker/qos.depth").unwrap(),
+            ("/talker", "qos.depth")
+        );
+        assert!(split_name("/gain").is_err());
+        assert!(split_name("/talker/").is_err());
+    }
+}
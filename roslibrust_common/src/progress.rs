@@ -0,0 +1,170 @@
+use crate::{
+    Error, Publish, Result, Ros, RosMessageType, RosServiceType, Subscribe, TopicProvider,
+};
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+// A convention for services which take a long time to complete and report their progress along the way, for stacks
+// where full actions aren't available. The client picks a unique feedback topic for each call and passes it in the
+// request, the server publishes progress messages on it while it works and then responds as normal.
+
+/// Calls a long running service while receiving the progress it reports on a feedback topic.
+///
+/// A unique feedback topic is created for the call, named `<service>/progress_<uuid>`, and subscribed to before the
+/// service is called. `make_request` is given the topic's name to build the request with, so the server knows where
+/// to report progress, typically using a [ProgressReporter].
+///
+/// Progress is delivered like any topic, messages published before the server's publisher has connected to the
+/// subscription can be missed depending on the backend.
+///
+/// ```ignore
+/// // ExportMap.srv has a `string progress_topic` request field, progress is reported as std_msgs/Float32
+/// let mut call = call_service_with_progress::<_, ExportMap, std_msgs::Float32>(&ros, "/export_map", |topic| {
+///     ExportMapRequest { path: "/tmp/map.pgm".to_string(), progress_topic: topic.to_string() }
+/// })
+/// .await?;
+/// while let Some(progress) = call.next_progress().await {
+///     println!("{:.0}% done", progress.data * 100.0);
+/// }
+/// let response = call.response().await?;
+/// ```
+pub async fn call_service_with_progress<T, S, P>(
+    ros: &T,
+    service: &str,
+    make_request: impl FnOnce(&str) -> S::Request,
+) -> Result<ServiceProgress<T, S, P>>
+where
+    T: Ros,
+    S: RosServiceType,
+    P: RosMessageType,
+{
+    let feedback_topic = format!(
+        "{}/progress_{}",
+        service.trim_end_matches('/'),
+        uuid::Uuid::new_v4().simple()
+    );
+    let progress = ros.subscribe::<P>(feedback_topic.as_str()).await?;
+    let request = make_request(&feedback_topic);
+    let call = {
+        let ros = ros.clone();
+        let service = service.to_string();
+        tokio::spawn(async move { ros.call_service::<S>(service, request).await })
+    };
+    Ok(ServiceProgress {
+        feedback_topic,
+        progress,
+        call,
+        response: None,
+    })
+}
+
+/// A service call in progress, returned by [call_service_with_progress].
+///
+/// Dropping it before the response arrives stops waiting for the response, the server still completes the call.
+pub struct ServiceProgress<T: TopicProvider, S: RosServiceType, P: RosMessageType> {
+    feedback_topic: String,
+    progress: T::Subscriber<P>,
+    call: JoinHandle<Result<S::Response>>,
+    // Set once the call has completed, while progress is still being read
+    response: Option<Result<S::Response>>,
+}
+
+impl<T: TopicProvider, S: RosServiceType, P: RosMessageType> ServiceProgress<T, S, P> {
+    /// The topic progress is reported on for this call
+    pub fn feedback_topic(&self) -> &str {
+        &self.feedback_topic
+    }
+
+    /// Returns the next progress message, or None once the service has responded.
+    ///
+    /// Progress already received when the response arrives is returned first. Cancel safe.
+    pub async fn next_progress(&mut self) -> Option<P> {
+        while self.response.is_none() {
+            tokio::select! {
+                biased;
+                msg = self.progress.next() => match msg {
+                    Ok(msg) => return Some(msg),
+                    // A single malformed message shouldn't end the progress
+                    Err(Error::SerializationError(_)) => continue,
+                    // Without progress there is only the response left to wait for
+                    Err(_) => self.response = Some(join(&mut self.call).await),
+                },
+                response = &mut self.call => self.response = Some(flatten(response)),
+            }
+        }
+        None
+    }
+
+    /// Waits for the service's response, discarding any progress not yet read
+    pub async fn response(mut self) -> Result<S::Response> {
+        match self.response.take() {
+            Some(response) => response,
+            None => join(&mut self.call).await,
+        }
+    }
+}
+
+impl<T: TopicProvider, S: RosServiceType, P: RosMessageType> Drop for ServiceProgress<T, S, P> {
+    fn drop(&mut self) {
+        self.call.abort();
+    }
+}
+
+async fn join<R>(call: &mut JoinHandle<Result<R>>) -> Result<R> {
+    flatten(call.await)
+}
+
+fn flatten<R>(result: std::result::Result<Result<R>, tokio::task::JoinError>) -> Result<R> {
+    result.map_err(|e| Error::Unexpected(anyhow::anyhow!("Service call task failed: {e}")))?
+}
+
+/// Publishes progress on the feedback topic given in a request, from the server side of
+/// [call_service_with_progress].
+///
+/// Reporting doesn't block or need to be awaited, so it can be used directly from a service's server function.
+/// The topic is advertised in the background and un-advertised once the reporter is dropped and all reports
+/// have been published.
+///
+/// ```ignore
+/// let ros_for_server = ros.clone();
+/// let server = move |request: ExportMapRequest| {
+///     let progress = ProgressReporter::new(&ros_for_server, &request.progress_topic);
+///     for (i, row) in rows.iter().enumerate() {
+///         write_row(row)?;
+///         progress.report(std_msgs::Float32 { data: i as f32 / rows.len() as f32 });
+///     }
+///     Ok(ExportMapResponse {})
+/// };
+/// ```
+pub struct ProgressReporter<P> {
+    sender: mpsc::UnboundedSender<P>,
+}
+
+impl<P: RosMessageType> ProgressReporter<P> {
+    /// Starts advertising `feedback_topic` with `ros`, must be called within a tokio runtime
+    pub fn new<T: Ros>(ros: &T, feedback_topic: &str) -> Self {
+        let (sender, mut receiver) = mpsc::unbounded_channel::<P>();
+        let ros = ros.clone();
+        let feedback_topic = feedback_topic.to_string();
+        tokio::spawn(async move {
+            let publisher = match ros.advertise::<P>(feedback_topic.as_str()).await {
+                Ok(publisher) => publisher,
+                Err(e) => {
+                    log::warn!("Failed to advertise progress topic {feedback_topic}: {e}");
+                    return;
+                }
+            };
+            while let Some(progress) = receiver.recv().await {
+                // Progress is best effort, a lost message is superseded by the next
+                let _ = publisher.publish(&progress).await;
+            }
+        });
+        Self { sender }
+    }
+
+    /// Queues a progress message to be published
+    pub fn report(&self, progress: P) {
+        // Only fails if advertising failed, which has already been logged
+        let _ = self.sender.send(progress);
+    }
+}
@@ -0,0 +1,321 @@
+use crate::names::NamePattern;
+use crate::topic_name::ToGlobalTopicName;
+use crate::{Error, Result, RosMessageType, Subscribe, TopicProvider};
+
+/// Topic where rosout republishes the log messages of every node, this is what [RosoutFilter::subscribe] reads.
+pub const ROSOUT_AGG_TOPIC: &str = "/rosout_agg";
+/// Topic where each node publishes its own log messages.
+pub const ROSOUT_TOPIC: &str = "/rosout";
+
+/// The time format used in a ROS1 `std_msgs/Header`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct Time {
+    // ROS2 names these fields sec and nanosec
+    #[serde(alias = "sec")]
+    pub secs: u32,
+    #[serde(alias = "nanosec")]
+    pub nsecs: u32,
+}
+
+/// Equivalent of ROS1's `std_msgs/Header`, used in [Log]
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct Header {
+    pub seq: u32,
+    pub stamp: Time,
+    pub frame_id: String,
+}
+
+/// Equivalent of ROS1's `rosgraph_msgs/Log`, the message published on `/rosout` and `/rosout_agg`.
+///
+/// Provided here so that log output can be read without needing to generate rosgraph_msgs.
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct Log {
+    pub header: Header,
+    /// The raw severity byte, see [Log::log_level]
+    pub level: u8,
+    /// Name of the node which produced the message
+    pub name: String,
+    pub msg: String,
+    pub file: String,
+    pub function: String,
+    pub line: u32,
+    /// Topics the node publishes
+    pub topics: Vec<String>,
+}
+
+impl RosMessageType for Log {
+    const ROS_TYPE_NAME: &'static str = "rosgraph_msgs/Log";
+    const MD5SUM: &'static str = "acffd30cd6b6de30f120938c17c593fb";
+    const DEFINITION: &'static str = r####"##
+## Severity level constants
+##
+byte DEBUG=1 #debug level
+byte INFO=2  #general level
+byte WARN=4  #warning level
+byte ERROR=8 #error level
+byte FATAL=16 #fatal/critical level
+##
+## Fields
+##
+Header header
+byte level
+string name # name of the node
+string msg # message
+string file # file the message came from
+string function # function the message came from
+uint32 line # line the message came from
+string[] topics # topic names that the node publishes
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data
+# in a particular coordinate frame.
+#
+# sequence ID: consecutively increasing ID
+uint32 seq
+#Two-integer timestamp that is expressed as:
+# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
+# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
+# time-handling sugar is provided by the client library
+time stamp
+#Frame this data is associated with
+string frame_id"####;
+}
+
+impl Log {
+    /// Returns the severity of the message, or None if the level byte isn't one of the values defined by ROS
+    pub fn log_level(&self) -> Option<LogLevel> {
+        LogLevel::from_byte(self.level)
+    }
+}
+
+/// Severity of a [Log] message, ordered from least to most severe.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum LogLevel {
+    Debug,
+    Info,
+    Warn,
+    Error,
+    Fatal,
+}
+
+impl LogLevel {
+    /// Converts the level byte used in `rosgraph_msgs/Log` to a level
+    pub fn from_byte(level: u8) -> Option<LogLevel> {
+        match level {
+            1 => Some(LogLevel::Debug),
+            2 => Some(LogLevel::Info),
+            4 => Some(LogLevel::Warn),
+            8 => Some(LogLevel::Error),
+            16 => Some(LogLevel::Fatal),
+            _ => None,
+        }
+    }
+
+    /// Converts the level to the byte used in `rosgraph_msgs/Log`
+    pub fn as_byte(self) -> u8 {
+        match self {
+            LogLevel::Debug => 1,
+            LogLevel::Info => 2,
+            LogLevel::Warn => 4,
+            LogLevel::Error => 8,
+            LogLevel::Fatal => 16,
+        }
+    }
+}
+
+/// Selects which log messages a [RosoutReader] returns.
+///
+/// An empty filter accepts every message, each condition that is added must also match.
+#[derive(Debug, Clone, Default)]
+pub struct RosoutFilter {
+    nodes: Vec<NamePattern>,
+    min_level: Option<LogLevel>,
+    message: Option<regex::Regex>,
+}
+
+impl RosoutFilter {
+    /// Creates a filter which accepts every message
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only accept messages from nodes matching `pattern`, see [NamePattern] for the syntax.
+    ///
+    /// Can be called multiple times, in which case messages from a node matching any of the patterns are accepted.
+    pub fn node(mut self, pattern: &str) -> Result<Self> {
+        self.nodes.push(NamePattern::new(pattern)?);
+        Ok(self)
+    }
+
+    /// Only accept messages at least as severe as `level`
+    pub fn min_level(mut self, level: LogLevel) -> Self {
+        self.min_level = Some(level);
+        self
+    }
+
+    /// Only accept messages whose text matches the regular expression `regex`
+    pub fn message_regex(mut self, regex: &str) -> Result<Self> {
+        let regex = regex::Regex::new(regex).map_err(|e| Error::Unexpected(e.into()))?;
+        self.message = Some(regex);
+        Ok(self)
+    }
+
+    /// Returns true if the message passes the filter
+    pub fn matches(&self, log: &Log) -> bool {
+        if !self.nodes.is_empty() && !self.nodes.iter().any(|p| p.matches(&log.name)) {
+            return false;
+        }
+        if let Some(min_level) = self.min_level {
+            // Messages with an unknown level can't be compared, so are only accepted without a level filter
+            if log.log_level().is_none_or(|level| level < min_level) {
+                return false;
+            }
+        }
+        if let Some(regex) = &self.message {
+            if !regex.is_match(&log.msg) {
+                return false;
+            }
+        }
+        true
+    }
+
+    /// Subscribes to [ROSOUT_AGG_TOPIC], which carries the log messages of every node, returning a reader using this filter.
+    pub async fn subscribe<T: TopicProvider>(
+        self,
+        ros: &T,
+    ) -> Result<RosoutReader<T::Subscriber<Log>>> {
+        self.subscribe_to(ros, ROSOUT_AGG_TOPIC).await
+    }
+
+    /// Subscribes to the given topic instead of [ROSOUT_AGG_TOPIC], e.g. [ROSOUT_TOPIC] when rosout isn't running.
+    pub async fn subscribe_to<T: TopicProvider>(
+        self,
+        ros: &T,
+        topic: impl ToGlobalTopicName,
+    ) -> Result<RosoutReader<T::Subscriber<Log>>> {
+        let subscriber = ros.subscribe::<Log>(topic).await?;
+        Ok(RosoutReader {
+            subscriber,
+            filter: self,
+        })
+    }
+}
+
+/// Reads the log output of other nodes from rosout, returning only the messages accepted by a [RosoutFilter].
+///
+/// Useful for supervisors and for tests which need to assert on what other nodes are logging.
+/// Messages which don't pass the filter are discarded as they are received.
+///
+/// ```ignore
+/// let mut errors = RosoutFilter::new()
+///     .min_level(LogLevel::Error)
+///     .node("/camera/*")?
+///     .subscribe(&ros)
+///     .await?;
+/// let log = errors.next().await?;
+/// ```
+pub struct RosoutReader<S> {
+    subscriber: S,
+    filter: RosoutFilter,
+}
+
+impl<S: Subscribe<Log> + Send> RosoutReader<S> {
+    /// Wraps an existing subscriber to a topic carrying `rosgraph_msgs/Log`
+    pub fn new(subscriber: S, filter: RosoutFilter) -> Self {
+        Self { subscriber, filter }
+    }
+
+    /// Returns the next message accepted by the filter
+    pub async fn next(&mut self) -> Result<Log> {
+        loop {
+            let log = self.subscriber.next().await?;
+            if self.filter.matches(&log) {
+                return Ok(log);
+            }
+        }
+    }
+
+    /// Returns the filter in use
+    pub fn filter(&self) -> &RosoutFilter {
+        &self.filter
+    }
+}
+
+impl<S: Subscribe<Log> + Send> Subscribe<Log> for RosoutReader<S> {
+    async fn next(&mut self) -> Result<Log> {
+        RosoutReader::next(self).await
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn log(name: &str, level: LogLevel, msg: &str) -> Log {
+        Log {
+            name: name.to_string(),
+            level: level.as_byte(),
+            msg: msg.to_string(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn empty_filter_accepts_everything() {
+        let filter = RosoutFilter::new();
+        assert!(filter.matches(&log("/a", LogLevel::Debug, "hello")));
+        assert!(filter.matches(&Log {
+            level: 3,
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn filter_conditions_combine() {
+        let filter = RosoutFilter::new()
+            .node("/camera/*")
+            .unwrap()
+            .node("/lidar")
+            .unwrap()
+            .min_level(LogLevel::Warn)
+            .message_regex("time(d)? ?out")
+            .unwrap();
+
+        assert!(filter.matches(&log("/camera/driver", LogLevel::Error, "timed out")));
+        assert!(filter.matches(&log("/lidar", LogLevel::Warn, "timeout")));
+        // Wrong node
+        assert!(!filter.matches(&log("/imu", LogLevel::Error, "timeout")));
+        // Not severe enough
+        assert!(!filter.matches(&log("/lidar", LogLevel::Info, "timeout")));
+        // Message doesn't match
+        assert!(!filter.matches(&log("/lidar", LogLevel::Fatal, "crashed")));
+        // Unknown levels don't pass a level filter
+        assert!(!filter.matches(&Log {
+            name: "/lidar".to_string(),
+            level: 3,
+            msg: "timeout".to_string(),
+            ..Default::default()
+        }));
+    }
+
+    #[test]
+    fn invalid_filters_are_rejected() {
+        assert!(RosoutFilter::new().message_regex("(").is_err());
+        assert!(RosoutFilter::new().node("not a name").is_err());
+    }
+
+    #[test]
+    fn log_levels_round_trip() {
+        for level in [
+            LogLevel::Debug,
+            LogLevel::Info,
+            LogLevel::Warn,
+            LogLevel::Error,
+            LogLevel::Fatal,
+        ] {
+            assert_eq!(LogLevel::from_byte(level.as_byte()), Some(level));
+        }
+        assert_eq!(LogLevel::from_byte(0), None);
+    }
+}
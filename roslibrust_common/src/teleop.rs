@@ -0,0 +1,322 @@
+use crate::clock::Clock;
+use crate::{Publish, RosMessageType};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, SystemTime};
+use tokio_util::sync::{CancellationToken, DropGuard};
+
+// Building blocks for teleoperation bridges, e.g. forwarding a web gamepad over rosbridge.
+// They only deal in velocities, so are agnostic to the input device: mapping buttons and axes to a Twist is left to
+// the caller. The message types mirror geometry_msgs so no generated code is needed to drive a robot.
+
+/// Equivalent of `geometry_msgs/Vector3`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct Vector3 {
+    pub x: f64,
+    pub y: f64,
+    pub z: f64,
+}
+
+impl RosMessageType for Vector3 {
+    const ROS_TYPE_NAME: &'static str = "geometry_msgs/Vector3";
+    const MD5SUM: &'static str = "4a842b65f413084dc2b10fb484ea7f17";
+    const DEFINITION: &'static str = r####"float64 x
+float64 y
+float64 z"####;
+    const SERIALIZED_SIZE: Option<usize> = Some(24);
+}
+
+/// Equivalent of `geometry_msgs/Twist`, the velocity command accepted by most mobile robots on `/cmd_vel`
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct Twist {
+    /// In m/s
+    pub linear: Vector3,
+    /// In rad/s
+    pub angular: Vector3,
+}
+
+impl RosMessageType for Twist {
+    const ROS_TYPE_NAME: &'static str = "geometry_msgs/Twist";
+    const MD5SUM: &'static str = "9f195f881246fdfa2798d1d3eebca84a";
+    const DEFINITION: &'static str = r####"Vector3 linear
+Vector3 angular
+================================================================================
+MSG: geometry_msgs/Vector3
+float64 x
+float64 y
+float64 z"####;
+    const SERIALIZED_SIZE: Option<usize> = Some(48);
+}
+
+/// Limits applied by a [TwistSmoother], each applies to every axis separately
+#[derive(Debug, Clone, PartialEq)]
+pub struct SmootherConfig {
+    /// In m/s, commands are clamped to this speed
+    pub max_linear_speed: f64,
+    /// In rad/s, commands are clamped to this speed
+    pub max_angular_speed: f64,
+    /// In m/s², how quickly the output may approach the command
+    pub max_linear_accel: f64,
+    /// In rad/s², how quickly the output may approach the command
+    pub max_angular_accel: f64,
+    /// If no command is received for this long the output drops straight to zero
+    pub deadman_timeout: Duration,
+}
+
+impl Default for SmootherConfig {
+    fn default() -> Self {
+        Self {
+            max_linear_speed: 0.5,
+            max_angular_speed: 1.0,
+            max_linear_accel: 1.0,
+            max_angular_accel: 2.0,
+            deadman_timeout: Duration::from_millis(500),
+        }
+    }
+}
+
+/// Limits the acceleration of velocity commands and stops when they stop arriving.
+///
+/// Commands from an input device are given to [TwistSmoother::command], and [TwistSmoother::update] is called
+/// periodically to get the velocity to send. Times are passed in rather than read, so the smoother can follow
+/// any [Clock] and be driven deterministically in tests. See [Teleop] for running one at a fixed rate.
+#[derive(Debug, Clone)]
+pub struct TwistSmoother {
+    config: SmootherConfig,
+    target: Twist,
+    output: Twist,
+    last_command: Option<SystemTime>,
+    last_update: Option<SystemTime>,
+}
+
+impl TwistSmoother {
+    pub fn new(config: SmootherConfig) -> Self {
+        Self {
+            config,
+            target: Twist::default(),
+            output: Twist::default(),
+            last_command: None,
+            last_update: None,
+        }
+    }
+
+    /// Sets the velocity to approach, received at `now`
+    pub fn command(&mut self, twist: Twist, now: SystemTime) {
+        self.target = Twist {
+            linear: clamp_axes(&twist.linear, self.config.max_linear_speed),
+            angular: clamp_axes(&twist.angular, self.config.max_angular_speed),
+        };
+        self.last_command = Some(now);
+    }
+
+    /// True if no command has been received within the deadman timeout of `now`
+    pub fn timed_out(&self, now: SystemTime) -> bool {
+        match self.last_command {
+            Some(last) => {
+                now.duration_since(last).unwrap_or_default() > self.config.deadman_timeout
+            }
+            None => true,
+        }
+    }
+
+    /// Moves the output towards the last command as far as the acceleration limits allow since the previous update,
+    /// and returns it. Returns zero once the deadman timeout has expired.
+    pub fn update(&mut self, now: SystemTime) -> Twist {
+        // A clock jumping backwards is treated as no time having passed
+        let dt = self
+            .last_update
+            .map(|last| now.duration_since(last).unwrap_or_default())
+            .unwrap_or_default()
+            .as_secs_f64();
+        self.last_update = Some(now);
+
+        if self.timed_out(now) {
+            self.target = Twist::default();
+            self.output = Twist::default();
+            return Twist::default();
+        }
+
+        let linear = self.config.max_linear_accel * dt;
+        let angular = self.config.max_angular_accel * dt;
+        self.output = Twist {
+            linear: map_axes(&self.output.linear, &self.target.linear, |v, target| {
+                v + (target - v).clamp(-linear, linear)
+            }),
+            angular: map_axes(&self.output.angular, &self.target.angular, |v, target| {
+                v + (target - v).clamp(-angular, angular)
+            }),
+        };
+        self.output.clone()
+    }
+}
+
+fn clamp_axes(v: &Vector3, max: f64) -> Vector3 {
+    Vector3 {
+        x: v.x.clamp(-max, max),
+        y: v.y.clamp(-max, max),
+        z: v.z.clamp(-max, max),
+    }
+}
+
+fn map_axes(a: &Vector3, b: &Vector3, f: impl Fn(f64, f64) -> f64) -> Vector3 {
+    Vector3 {
+        x: f(a.x, b.x),
+        y: f(a.y, b.y),
+        z: f(a.z, b.z),
+    }
+}
+
+/// Publishes the output of a [TwistSmoother] every `period` of a [Clock].
+///
+/// Publishing continues while no commands arrive, so the deadman timeout stops the robot if the input device or
+/// the connection to it is lost. A final zero Twist is published when the Teleop is dropped.
+///
+/// Must be created within a tokio runtime.
+/// ```ignore
+/// let publisher = ros.advertise::<Twist>("/cmd_vel").await?;
+/// let teleop = Teleop::new(publisher, Clock::wall(), Duration::from_millis(50), SmootherConfig::default());
+/// // For each gamepad event
+/// teleop.command(Twist {
+///     linear: Vector3 { x: 0.4 * stick_y, ..Default::default() },
+///     angular: Vector3 { z: 0.8 * stick_x, ..Default::default() },
+/// });
+/// ```
+pub struct Teleop {
+    smoother: Arc<Mutex<TwistSmoother>>,
+    clock: Clock,
+    _stop: DropGuard,
+}
+
+impl Teleop {
+    pub fn new<P: Publish<Twist> + Send + Sync + 'static>(
+        publisher: P,
+        clock: Clock,
+        period: Duration,
+        config: SmootherConfig,
+    ) -> Self {
+        let smoother = Arc::new(Mutex::new(TwistSmoother::new(config)));
+        let stop = CancellationToken::new();
+        let mut rate = clock.rate(period);
+        let task_smoother = smoother.clone();
+        let task_clock = clock.clone();
+        let task_stop = stop.clone();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    _ = task_stop.cancelled() => break,
+                    _ = rate.sleep() => {}
+                }
+                let twist = task_smoother.lock().unwrap().update(task_clock.now());
+                // A failed publish is covered by the next cycle, keep going
+                let _ = publisher.publish(&twist).await;
+            }
+            let _ = publisher.publish(&Twist::default()).await;
+        });
+        Self {
+            smoother,
+            clock,
+            _stop: stop.drop_guard(),
+        }
+    }
+
+    /// Sets the velocity to approach, typically called for every event from the input device
+    pub fn command(&self, twist: Twist) {
+        self.smoother
+            .lock()
+            .unwrap()
+            .command(twist, self.clock.now());
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::clock::ManualClock;
+    use std::time::UNIX_EPOCH;
+
+    fn forward(x: f64) -> Twist {
+        Twist {
+            linear: Vector3 {
+                x,
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn at(millis: u64) -> SystemTime {
+        UNIX_EPOCH + Duration::from_millis(millis)
+    }
+
+    #[test]
+    fn md5sums_match_definitions() {
+        use crate::md5sum::from_message_definition;
+        assert_eq!(
+            from_message_definition(Vector3::ROS_TYPE_NAME, Vector3::DEFINITION).unwrap(),
+            Vector3::MD5SUM
+        );
+        assert_eq!(
+            from_message_definition(Twist::ROS_TYPE_NAME, Twist::DEFINITION).unwrap(),
+            Twist::MD5SUM
+        );
+    }
+
+    #[test]
+    fn limits_acceleration_and_speed() {
+        let mut smoother = TwistSmoother::new(SmootherConfig::default());
+        assert_eq!(smoother.update(at(0)), Twist::default());
+        smoother.command(forward(2.0), at(0));
+        assert_eq!(smoother.update(at(100)).linear.x, 0.1);
+        assert_eq!(smoother.update(at(300)).linear.x, 0.30000000000000004);
+        // Clamped to the maximum speed
+        assert_eq!(smoother.update(at(500)).linear.x, 0.5);
+        assert_eq!(smoother.update(at(500)).linear.x, 0.5);
+
+        smoother.command(forward(-0.5), at(500));
+        assert_eq!(smoother.update(at(1000)).linear.x, 0.0);
+    }
+
+    #[test]
+    fn stops_after_deadman_timeout() {
+        let mut smoother = TwistSmoother::new(SmootherConfig::default());
+        assert!(smoother.timed_out(at(0)));
+        smoother.update(at(0));
+        smoother.command(forward(0.5), at(0));
+        assert_eq!(smoother.update(at(500)).linear.x, 0.5);
+        assert_eq!(smoother.update(at(501)), Twist::default());
+        // Resumes smoothly from zero once commands return
+        assert_eq!(smoother.update(at(600)), Twist::default());
+        smoother.command(forward(0.5), at(600));
+        assert_eq!(smoother.update(at(700)).linear.x, 0.1);
+    }
+
+    struct ChannelPublisher(tokio::sync::mpsc::UnboundedSender<Twist>);
+
+    impl Publish<Twist> for ChannelPublisher {
+        async fn publish(&self, data: &Twist) -> crate::Result<()> {
+            self.0
+                .send(data.clone())
+                .map_err(|_| crate::Error::Disconnected)
+        }
+    }
+
+    #[tokio::test]
+    async fn teleop_publishes_smoothed_commands() {
+        let time = ManualClock::default();
+        let (sender, mut published) = tokio::sync::mpsc::unbounded_channel();
+        let teleop = Teleop::new(
+            ChannelPublisher(sender),
+            Clock::new(time.clone()),
+            Duration::from_millis(100),
+            SmootherConfig::default(),
+        );
+        teleop.command(forward(0.5));
+
+        time.advance(Duration::from_millis(100));
+        assert_eq!(published.recv().await.unwrap().linear.x, 0.0);
+        time.advance(Duration::from_millis(100));
+        assert_eq!(published.recv().await.unwrap().linear.x, 0.1);
+
+        drop(teleop);
+        assert_eq!(published.recv().await.unwrap(), Twist::default());
+    }
+}
@@ -1,6 +1,9 @@
+use crate::adapters::{FanOut, Filter, MapMsg, MappedSubscriber, SampleEvery};
+use crate::boxed::{BoxPublisher, BoxSubscriber};
 use crate::topic_name::*;
-use crate::{Result, ServiceError};
+use crate::{CancellationToken, Error, Result, ServiceError};
 use std::future::Future;
+use std::sync::Arc;
 
 /// Fundamental traits for message types this crate works with
 /// This trait will be satisfied for any types generated with this crate's message_gen functionality
@@ -23,6 +26,18 @@ pub trait RosMessageType:
     /// The computed ROS2 hash of the message file and its dependencies
     /// This field is optional, and only needed when using ros2 native communication
     const ROS2_HASH: &'static [u8; 32] = &[0; 32];
+    /// The number of bytes every instance of this message is encoded to in the ROS1 wire format,
+    /// None if the size varies, e.g. because the message contains strings or sequences
+    /// This field is optional, generated code provides it for all fixed length messages
+    const SERIALIZED_SIZE: Option<usize> = None;
+
+    /// Returns the number of bytes this message is encoded to in the ROS1 wire format, e.g. to preallocate buffers
+    /// or check a message fits within a transport's MTU
+    /// The 4 byte length prefix each message is sent with is not included.
+    /// Returns None if the size is not known, generated code always returns Some.
+    fn encoded_len(&self) -> Option<usize> {
+        Self::SERIALIZED_SIZE
+    }
 }
 
 // This special impl allows for services with no args / returns
@@ -30,6 +45,16 @@ impl RosMessageType for () {
     const ROS_TYPE_NAME: &'static str = "";
     const MD5SUM: &'static str = "";
     const DEFINITION: &'static str = "";
+    const SERIALIZED_SIZE: Option<usize> = Some(0);
+}
+
+/// Visits a set of message types, used to enumerate types at runtime.
+///
+/// Code generated with `CodegenOptions::generate_type_visitor` includes a `visit_message_types` function calling
+/// [MessageTypeVisitor::visit] once for each generated message type, which can be used to fill in a registry of
+/// the types known to a program.
+pub trait MessageTypeVisitor {
+    fn visit<T: RosMessageType>(&mut self);
 }
 
 /// Represents a ROS service type definition corresponding to a `.srv` file.
@@ -82,6 +107,14 @@ pub trait Publish<T: RosMessageType> {
     // This generates a warning is rust as of writing due to ambiguity around the "Send-ness" of the return type
     // We only plan to work with multi-threaded work stealing executors (e.g. tokio) so we're manually specifying Send
     fn publish(&self, data: &T) -> impl Future<Output = Result<()>> + Send;
+
+    /// Converts the publisher into a type erased [BoxPublisher].
+    fn boxed(self) -> BoxPublisher<T>
+    where
+        Self: Sized + Send + Sync + 'static,
+    {
+        BoxPublisher::new(self)
+    }
 }
 // ANCHOR_END: publish
 
@@ -98,8 +131,23 @@ where
     /// Returns the next message on the topic, or an Err as appropriate.
     /// [crate::Error] is currently quite generic, and the different backends can return different error variants in different circumstances.
     /// We hope to clean-up this error type substantially in the future.
+    ///
+    /// Implementations must be cancel safe: dropping the returned future before it completes, as happens to the
+    /// losing branches of a `tokio::select!` loop, must not lose a message. The message it would have returned is
+    /// returned by the next call instead. All backends and the adapters in this crate uphold this.
     fn next(&mut self) -> impl Future<Output = Result<T>> + Send;
 
+    /// Returns the next message wrapped in an [Arc], so it can be shared between consumers without being cloned.
+    ///
+    /// The default implementation wraps the message returned by [Subscribe::next]. Subscribers which already hold
+    /// shared messages, like the [SharedSubscriber](crate::adapters::SharedSubscriber)s of a [FanOut], return them without copying.
+    fn next_shared(&mut self) -> impl Future<Output = Result<Arc<T>>> + Send
+    where
+        Self: Send,
+    {
+        async move { self.next().await.map(Arc::new) }
+    }
+
     /// Converts the subscriber into an async [futures_core::Stream].
     /// This allows using the various adaptors in either [tokio_stream::StreamExt](https://docs.rs/tokio-stream/latest/tokio_stream/trait.StreamExt.html)
     /// or  [futures::stream::StreamExt](https://docs.rs/futures/latest/futures/stream/trait.StreamExt.html) to manipulate the stream.
@@ -116,6 +164,51 @@ where
             }
         }
     }
+
+    /// Wraps the subscriber so that [Subscribe::next] only returns messages for which `predicate` returns true.
+    ///
+    /// Messages that don't match are discarded as they are received rather than being returned to the caller.
+    /// Errors from the underlying subscriber are always returned.
+    fn filter<F>(self, predicate: F) -> Filter<Self, T, F>
+    where
+        F: FnMut(&T) -> bool + Send,
+    {
+        Filter::new(self, predicate)
+    }
+
+    /// Wraps the subscriber so that [Subscribe::next] returns only one of every `n` messages received,
+    /// starting with the first. Useful for processing a high rate topic at a lower rate.
+    fn sample_every(self, n: usize) -> SampleEvery<Self, T> {
+        SampleEvery::new(self, n)
+    }
+
+    /// Wraps the subscriber so that each message is transformed by `f` before it is returned.
+    fn map_msg<U, F>(self, f: F) -> MapMsg<Self, T, F>
+    where
+        F: FnMut(T) -> U + Send,
+    {
+        MapMsg::new(self, f)
+    }
+
+    /// Shares this subscription between several consumers within the process, see [FanOut].
+    ///
+    /// Each message is deserialized once and handed to every consumer as an [Arc]. Up to `capacity` messages are
+    /// buffered per consumer, consumers that fall further behind miss the oldest messages.
+    /// Must be called within a tokio runtime.
+    fn fan_out(self, capacity: usize) -> FanOut<T>
+    where
+        Self: Send + 'static,
+    {
+        FanOut::new(self, capacity)
+    }
+
+    /// Converts the subscriber into a type erased [BoxSubscriber].
+    fn boxed(self) -> BoxSubscriber<T>
+    where
+        Self: Send + Sync + 'static,
+    {
+        BoxSubscriber::new(self)
+    }
 }
 
 // ANCHOR: topic_provider
@@ -151,12 +244,54 @@ pub trait TopicProvider {
         &self,
         topic: impl ToGlobalTopicName,
     ) -> impl Future<Output = Result<Self::Subscriber<MsgType>>> + Send;
+
+    /// Subscribes to a topic of `RosT` messages, returning a subscriber which yields them converted to `DomainT`.
+    ///
+    /// Conversion happens in a background task as messages arrive, so application code can work purely with its
+    /// own types. Messages which fail to convert are returned as
+    /// [MappedError::Conversion](crate::adapters::MappedError::Conversion), distinct from errors of the subscription
+    /// itself. Must be called within a tokio runtime.
+    fn subscribe_mapped<RosT, DomainT>(
+        &self,
+        topic: impl ToGlobalTopicName,
+    ) -> impl Future<Output = Result<MappedSubscriber<DomainT, DomainT::Error>>> + Send
+    where
+        Self: Sync,
+        RosT: RosMessageType,
+        DomainT: TryFrom<RosT> + Send + 'static,
+        DomainT::Error: Send + 'static,
+    {
+        async move {
+            let subscriber = self.subscribe::<RosT>(topic).await?;
+            Ok(MappedSubscriber::new(subscriber))
+        }
+    }
 }
 // ANCHOR_END: topic_provider
 
 /// Defines what it means to be something that is callable as a service
 pub trait Service<T: RosServiceType> {
     fn call(&self, request: &T::Request) -> impl Future<Output = Result<T::Response>> + Send;
+
+    /// Calls the service, returning [Error::Cancelled] if `cancel` is cancelled before the response arrives.
+    ///
+    /// The default implementation drops the in progress call, backends override this where a call has state
+    /// that needs cleaning up.
+    fn call_with_cancel(
+        &self,
+        request: &T::Request,
+        cancel: &CancellationToken,
+    ) -> impl Future<Output = Result<T::Response>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            cancel
+                .run_until_cancelled(self.call(request))
+                .await
+                .unwrap_or(Err(Error::Cancelled))
+        }
+    }
 }
 
 /// This trait is analogous to TopicProvider, but instead provides the capability to create service servers and service clients
@@ -171,6 +306,27 @@ pub trait ServiceProvider {
         request: SrvType::Request,
     ) -> impl Future<Output = Result<SrvType::Response>> + Send;
 
+    /// [ServiceProvider::call_service], returning [Error::Cancelled] if `cancel` is cancelled before the response arrives.
+    ///
+    /// The default implementation drops the in progress call, backends override this where a call has state
+    /// that needs cleaning up.
+    fn call_service_with_cancel<SrvType: RosServiceType>(
+        &self,
+        service: impl ToGlobalTopicName,
+        request: SrvType::Request,
+        cancel: &CancellationToken,
+    ) -> impl Future<Output = Result<SrvType::Response>> + Send
+    where
+        Self: Sync,
+    {
+        async move {
+            cancel
+                .run_until_cancelled(self.call_service::<SrvType>(service, request))
+                .await
+                .unwrap_or(Err(Error::Cancelled))
+        }
+    }
+
     /// An optimized version of call_service that returns a persistent client that can be used to repeatedly call a service.
     /// Depending on backend this may provide a performance benefit over call_service.
     /// Dropping the returned client will perform all needed cleanup.
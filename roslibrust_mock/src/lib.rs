@@ -28,7 +28,9 @@
 //! ```
 use std::collections::BTreeMap;
 use std::sync::Arc;
+use std::time::{Duration, SystemTime};
 
+use roslibrust_common::clock::{Clock, ClockMessage, ClockProvider, ManualClock, CLOCK_TOPIC};
 use roslibrust_common::topic_name::{GlobalTopicName, ToGlobalTopicName};
 use roslibrust_common::*;
 
@@ -57,6 +59,8 @@ pub struct MockRos {
     // but this ends up being pretty simple
     topics: Arc<RwLock<BTreeMap<String, (Channel::Sender<Vec<u8>>, Channel::Receiver<Vec<u8>>)>>>,
     services: Arc<ServiceStore>,
+    // Time seen through [MockRos::clock], only moves when the test moves it
+    clock: ManualClock,
 }
 
 impl Default for MockRos {
@@ -70,8 +74,30 @@ impl MockRos {
         Self {
             topics: Arc::new(RwLock::new(BTreeMap::new())),
             services: Arc::new(RwLock::new(BTreeMap::new())),
+            clock: ManualClock::default(),
         }
     }
+
+    /// The mock's clock, which reads [std::time::UNIX_EPOCH] until moved with [MockRos::set_time] or
+    /// [MockRos::advance_time]. Hand it to code under test to drive "ROS time" deterministically.
+    pub fn clock(&self) -> Clock {
+        Clock::new(self.clock.clone())
+    }
+
+    /// Sets the mock's time, and publishes it on /clock so that a
+    /// [SimClock](roslibrust_common::clock::SimClock) created on this mock follows it too.
+    pub async fn set_time(&self, time: SystemTime) -> Result<()> {
+        self.clock.set(time);
+        self.advertise::<ClockMessage>(CLOCK_TOPIC)
+            .await?
+            .publish(&time.into())
+            .await
+    }
+
+    /// Moves the mock's time forward by `duration`, see [MockRos::set_time]
+    pub async fn advance_time(&self, duration: Duration) -> Result<()> {
+        self.set_time(self.clock.now() + duration).await
+    }
 }
 
 // This is a very basic mocking of sending and receiving messages over topics
@@ -325,6 +351,459 @@ mod tests {
         assert_eq!(msg, received_msg);
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_subscriber_adapters() {
+        let mock_ros = MockRos::new();
+
+        let pub_handle = mock_ros
+            .advertise::<std_msgs::String>("/test_topic")
+            .await
+            .unwrap();
+        let mut filtered = mock_ros
+            .subscribe::<std_msgs::String>("/test_topic")
+            .await
+            .unwrap()
+            .filter(|msg: &std_msgs::String| msg.data.starts_with('a'));
+        let mut sampled = mock_ros
+            .subscribe::<std_msgs::String>("/test_topic")
+            .await
+            .unwrap()
+            .sample_every(2);
+        let mut mapped = mock_ros
+            .subscribe::<std_msgs::String>("/test_topic")
+            .await
+            .unwrap()
+            .map_msg(|msg: std_msgs::String| msg.data.len());
+
+        for data in ["a1", "b22", "a333", "b4444"] {
+            pub_handle
+                .publish(&std_msgs::String {
+                    data: data.to_string(),
+                })
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(filtered.next().await.unwrap().data, "a1");
+        assert_eq!(filtered.next().await.unwrap().data, "a333");
+
+        assert_eq!(sampled.next().await.unwrap().data, "a1");
+        assert_eq!(sampled.next().await.unwrap().data, "a333");
+
+        assert_eq!(mapped.next().await.unwrap(), 2);
+        assert_eq!(mapped.next().await.unwrap(), 3);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_fan_out() {
+        let mock_ros = MockRos::new();
+
+        let pub_handle = mock_ros
+            .advertise::<std_msgs::String>("/test_topic")
+            .await
+            .unwrap();
+        let fan_out = mock_ros
+            .subscribe::<std_msgs::String>("/test_topic")
+            .await
+            .unwrap()
+            .fan_out(10);
+        let mut first = fan_out.subscribe();
+        let mut second = fan_out.subscribe();
+
+        pub_handle
+            .publish(&std_msgs::String {
+                data: "shared".to_string(),
+            })
+            .await
+            .unwrap();
+
+        let a = first.next_shared().await.unwrap();
+        let b = second.next_shared().await.unwrap();
+        assert_eq!(a.data, "shared");
+        // Both consumers received the same deserialized message
+        assert!(Arc::ptr_eq(&a, &b));
+
+        drop(fan_out);
+        assert!(matches!(first.next().await, Err(Error::Disconnected)));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_subscribe_mapped() {
+        use roslibrust_common::adapters::MappedError;
+
+        // A domain type which can't be empty
+        #[derive(Debug, PartialEq)]
+        struct Name(String);
+
+        impl TryFrom<std_msgs::String> for Name {
+            type Error = &'static str;
+            fn try_from(msg: std_msgs::String) -> std::result::Result<Self, Self::Error> {
+                if msg.data.is_empty() {
+                    return Err("name is empty");
+                }
+                Ok(Name(msg.data))
+            }
+        }
+
+        let mock_ros = MockRos::new();
+        let pub_handle = mock_ros
+            .advertise::<std_msgs::String>("/test_topic")
+            .await
+            .unwrap();
+        let mut names = mock_ros
+            .subscribe_mapped::<std_msgs::String, Name>("/test_topic")
+            .await
+            .unwrap();
+
+        for data in ["alice", "", "bob"] {
+            pub_handle
+                .publish(&std_msgs::String {
+                    data: data.to_string(),
+                })
+                .await
+                .unwrap();
+        }
+
+        assert_eq!(names.next().await.unwrap(), Name("alice".to_string()));
+        assert!(matches!(
+            names.next().await,
+            Err(MappedError::Conversion("name is empty"))
+        ));
+        assert_eq!(names.next().await.unwrap(), Name("bob".to_string()));
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rosout_logger() {
+        use roslibrust_common::logging::RosoutLogger;
+        use roslibrust_common::rosout::{Log, LogLevel, ROSOUT_TOPIC};
+
+        let mock_ros = MockRos::new();
+        let mut rosout = mock_ros.subscribe::<Log>(ROSOUT_TOPIC).await.unwrap();
+        let logger = RosoutLogger::new(&mock_ros, "/test_node").await.unwrap();
+
+        // Below the minimum level, only logged locally
+        roslibrust_common::ros_debug!("not published");
+        roslibrust_common::ros_info!("published {}", 1);
+        let log = rosout.next().await.unwrap();
+        assert_eq!(log.msg, "published 1");
+        assert_eq!(log.name, "/test_node");
+        assert_eq!(log.log_level(), Some(LogLevel::Info));
+        assert_eq!(log.line, line!() - 5);
+
+        logger.set_min_level(LogLevel::Debug);
+        for _ in 0..2 {
+            roslibrust_common::ros_warn_once!("published once");
+        }
+        roslibrust_common::ros_debug!("published 2");
+        assert_eq!(rosout.next().await.unwrap().msg, "published once");
+        let log = rosout.next().await.unwrap();
+        assert_eq!(log.msg, "published 2");
+        assert_eq!(log.header.seq, 2);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_next_is_cancel_safe() {
+        use std::time::Duration;
+        use tokio::time::timeout;
+
+        let mock_ros = MockRos::new();
+        let pub_handle = mock_ros
+            .advertise::<std_msgs::String>("/test_topic")
+            .await
+            .unwrap();
+        let mut filtered = mock_ros
+            .subscribe::<std_msgs::String>("/test_topic")
+            .await
+            .unwrap()
+            .filter(|msg: &std_msgs::String| msg.data.starts_with('a'));
+        let mut sampled = mock_ros
+            .subscribe::<std_msgs::String>("/test_topic")
+            .await
+            .unwrap()
+            .sample_every(2)
+            .boxed();
+        let msg = |data: &str| std_msgs::String {
+            data: data.to_string(),
+        };
+
+        // Cancelled while waiting for a message
+        assert!(timeout(Duration::from_millis(10), filtered.next())
+            .await
+            .is_err());
+        pub_handle.publish(&msg("a1")).await.unwrap();
+        // Yielded without waiting for the messages to be discarded after it
+        let first = timeout(Duration::from_millis(10), sampled.next()).await;
+        assert_eq!(first.unwrap().unwrap().data, "a1");
+        // Cancelled after discarding "b2" while waiting for the next message to yield
+        pub_handle.publish(&msg("b2")).await.unwrap();
+        assert!(timeout(Duration::from_millis(10), sampled.next())
+            .await
+            .is_err());
+        pub_handle.publish(&msg("a3")).await.unwrap();
+        pub_handle.publish(&msg("b4")).await.unwrap();
+
+        assert_eq!(sampled.next().await.unwrap().data, "a3");
+        assert_eq!(filtered.next().await.unwrap().data, "a1");
+        assert_eq!(filtered.next().await.unwrap().data, "a3");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_boxed_publisher_and_subscriber() {
+        let mock_ros = MockRos::new();
+
+        let mut subscribers: Vec<BoxSubscriber<std_msgs::String>> = vec![
+            mock_ros
+                .subscribe::<std_msgs::String>("/a")
+                .await
+                .unwrap()
+                .boxed(),
+            // Adapters can be boxed into the same collection as plain subscribers
+            mock_ros
+                .subscribe::<std_msgs::String>("/b")
+                .await
+                .unwrap()
+                .filter(|msg: &std_msgs::String| msg.data != "skip")
+                .boxed(),
+        ];
+        let publishers: Vec<BoxPublisher<std_msgs::String>> = vec![
+            mock_ros
+                .advertise::<std_msgs::String>("/a")
+                .await
+                .unwrap()
+                .boxed(),
+            BoxPublisher::new(mock_ros.advertise::<std_msgs::String>("/b").await.unwrap()),
+        ];
+
+        let skip = std_msgs::String {
+            data: "skip".to_string(),
+        };
+        let msg = std_msgs::String {
+            data: "Hello, world!".to_string(),
+        };
+        for publisher in &publishers {
+            publisher.publish(&skip).await.unwrap();
+            publisher.publish(&msg).await.unwrap();
+        }
+
+        assert_eq!(subscribers[0].next().await.unwrap(), skip);
+        assert_eq!(subscribers[1].next().await.unwrap(), msg);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_dyn_topic_provider() {
+        let mock_ros = MockRos::new();
+        let provider = DynTopicProvider::builder(mock_ros)
+            .with_message::<std_msgs::String>()
+            .build();
+        assert!(provider.supports::<std_msgs::String>());
+        assert!(!provider.supports::<std_msgs::Header>());
+
+        let publisher = provider
+            .advertise::<std_msgs::String>("/test_topic")
+            .await
+            .unwrap();
+        let mut subscriber = provider
+            .subscribe::<std_msgs::String>("/test_topic")
+            .await
+            .unwrap();
+
+        let msg = std_msgs::String {
+            data: "Hello, world!".to_string(),
+        };
+        publisher.publish(&msg).await.unwrap();
+        assert_eq!(subscriber.next().await.unwrap(), msg);
+
+        assert!(provider
+            .advertise::<std_msgs::Header>("/header")
+            .await
+            .is_err());
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_either_backend() {
+        use roslibrust_common::either::Either;
+
+        // Generic code written against Ros accepts either backend
+        async fn echo(ros: impl Ros) -> std_msgs::String {
+            let publisher = ros
+                .advertise::<std_msgs::String>("/test_topic")
+                .await
+                .unwrap();
+            let mut subscriber = ros
+                .subscribe::<std_msgs::String>("/test_topic")
+                .await
+                .unwrap();
+            let _server = ros
+                .advertise_service::<std_srvs::Trigger, _>("/test_service", |_| {
+                    Ok(std_srvs::TriggerResponse {
+                        success: true,
+                        message: "triggered".to_string(),
+                    })
+                })
+                .await
+                .unwrap();
+            let response = ros
+                .call_service::<std_srvs::Trigger>("/test_service", std_srvs::TriggerRequest {})
+                .await
+                .unwrap();
+            publisher
+                .publish(&std_msgs::String {
+                    data: response.message,
+                })
+                .await
+                .unwrap();
+            subscriber.next().await.unwrap()
+        }
+
+        let left = MockRos::new();
+        let right = MockRos::new();
+        let ros: Either<MockRos, MockRos> = Either::Left(left);
+        assert_eq!(echo(ros).await.data, "triggered");
+        let ros: Either<MockRos, MockRos> = Either::Right(right);
+        assert_eq!(echo(ros).await.data, "triggered");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_call_service_with_progress() {
+        use roslibrust_common::progress::{call_service_with_progress, ProgressReporter};
+        use roslibrust_test::ros1::rosapi;
+
+        let mock_ros = MockRos::new();
+        let server_ros = mock_ros.clone();
+        // The request's topic field carries the feedback topic
+        let server = move |request: rosapi::PublishersRequest| {
+            let progress = ProgressReporter::new(&server_ros, &request.topic);
+            for step in 0..3 {
+                progress.report(std_msgs::String {
+                    data: format!("step {step}"),
+                });
+            }
+            // Give the reports time to be published before responding
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            Ok(rosapi::PublishersResponse {
+                publishers: vec!["/done".to_string()],
+            })
+        };
+        mock_ros
+            .advertise_service::<rosapi::Publishers, _>("/long_running", server)
+            .await
+            .unwrap();
+
+        let mut call = call_service_with_progress::<_, rosapi::Publishers, std_msgs::String>(
+            &mock_ros,
+            "/long_running",
+            |topic| rosapi::PublishersRequest {
+                topic: topic.to_string(),
+            },
+        )
+        .await
+        .unwrap();
+        assert!(call.feedback_topic().starts_with("/long_running/progress_"));
+
+        let mut progress = vec![];
+        while let Some(msg) = call.next_progress().await {
+            progress.push(msg.data);
+        }
+        assert_eq!(progress, ["step 0", "step 1", "step 2"]);
+        assert_eq!(call.response().await.unwrap().publishers, ["/done"]);
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_rosout_reader() {
+        use roslibrust_common::rosout::{Log, LogLevel, RosoutFilter, ROSOUT_AGG_TOPIC};
+
+        let mock_ros = MockRos::new();
+        let mut reader = RosoutFilter::new()
+            .node("/camera/*")
+            .unwrap()
+            .min_level(LogLevel::Warn)
+            .subscribe(&mock_ros)
+            .await
+            .unwrap();
+        let publisher = mock_ros.advertise::<Log>(ROSOUT_AGG_TOPIC).await.unwrap();
+
+        for (name, level, msg) in [
+            ("/camera/driver", LogLevel::Info, "started"),
+            ("/lidar", LogLevel::Error, "no data"),
+            ("/camera/driver", LogLevel::Warn, "dropped frame"),
+        ] {
+            publisher
+                .publish(&Log {
+                    name: name.to_string(),
+                    level: level.as_byte(),
+                    msg: msg.to_string(),
+                    ..Default::default()
+                })
+                .await
+                .unwrap();
+        }
+
+        let log = reader.next().await.unwrap();
+        assert_eq!(log.name, "/camera/driver");
+        assert_eq!(log.msg, "dropped frame");
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mux() {
+        use roslibrust_common::mux::{Mux, MuxList, MuxListRequest, MuxSelect, MuxSelectRequest};
+
+        let mock_ros = MockRos::new();
+        let mux = Mux::new::<std_msgs::String>(&mock_ros, "/mux", "/out", &["/joy", "/nav"])
+            .await
+            .unwrap();
+        let mut output = mock_ros
+            .subscribe::<std_msgs::String>("/out")
+            .await
+            .unwrap();
+        let joy = mock_ros
+            .advertise::<std_msgs::String>("/joy")
+            .await
+            .unwrap();
+        let nav = mock_ros
+            .advertise::<std_msgs::String>("/nav")
+            .await
+            .unwrap();
+        let msg = |data: &str| std_msgs::String {
+            data: data.to_string(),
+        };
+
+        joy.publish(&msg("joy 1")).await.unwrap();
+        assert_eq!(output.next().await.unwrap().data, "joy 1");
+
+        let response = mock_ros
+            .call_service::<MuxSelect>(
+                "/mux/select",
+                MuxSelectRequest {
+                    topic: "/nav".to_string(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(response.prev_topic, "/joy");
+        assert_eq!(mux.selected().as_deref(), Some("/nav"));
+
+        // Messages from inputs which aren't selected are dropped
+        joy.publish(&msg("joy 2")).await.unwrap();
+        nav.publish(&msg("nav 2")).await.unwrap();
+        assert_eq!(output.next().await.unwrap().data, "nav 2");
+
+        // Only inputs can be selected
+        assert!(mock_ros
+            .call_service::<MuxSelect>(
+                "/mux/select",
+                MuxSelectRequest {
+                    topic: "/other".to_string(),
+                },
+            )
+            .await
+            .is_err());
+        let list = mock_ros
+            .call_service::<MuxList>("/mux/list", MuxListRequest {})
+            .await
+            .unwrap();
+        assert_eq!(list.topics, ["/joy", "/nav"]);
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_mock_services() {
         let mock_topics = MockRos::new();
@@ -353,6 +832,47 @@ mod tests {
         assert_eq!(response.message, "You set my bool!");
     }
 
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_service_call_with_cancel() {
+        let mock_ros = MockRos::new();
+        let server_fn = |request: std_srvs::SetBoolRequest| {
+            std::thread::sleep(std::time::Duration::from_millis(100));
+            Ok(std_srvs::SetBoolResponse {
+                success: request.data,
+                message: String::new(),
+            })
+        };
+        mock_ros
+            .advertise_service::<std_srvs::SetBool, _>("/test_service", server_fn)
+            .await
+            .unwrap();
+        let request = std_srvs::SetBoolRequest { data: true };
+
+        // An uncancelled token doesn't affect the call
+        let cancel = CancellationToken::new();
+        let response = mock_ros
+            .call_service_with_cancel::<std_srvs::SetBool>(
+                "/test_service",
+                request.clone(),
+                &cancel,
+            )
+            .await
+            .unwrap();
+        assert!(response.success);
+
+        let client = mock_ros
+            .service_client::<std_srvs::SetBool>("/test_service")
+            .await
+            .unwrap();
+        let canceller = cancel.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(std::time::Duration::from_millis(10)).await;
+            canceller.cancel();
+        });
+        let res = client.call_with_cancel(&request, &cancel).await;
+        assert!(matches!(res, Err(Error::Cancelled)));
+    }
+
     #[tokio::test(flavor = "multi_thread")]
     async fn test_mock_node() {
         // Proves that MockRos impls the Ros trait (via auto impl in roslibrust_common)
@@ -452,4 +972,34 @@ mod tests {
         assert!(response.success);
         assert_eq!(response.message, "You set my bool!");
     }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn test_mock_clock() {
+        use roslibrust_common::clock::SimClock;
+        use std::time::UNIX_EPOCH;
+
+        let mock_ros = MockRos::new();
+        let clock = mock_ros.clock();
+        let sim_clock = Clock::new(SimClock::new(&mock_ros).await.unwrap());
+        assert_eq!(clock.now(), UNIX_EPOCH);
+
+        let sleeper = tokio::spawn({
+            let sim_clock = sim_clock.clone();
+            async move {
+                sim_clock
+                    .sleep_until(UNIX_EPOCH + Duration::from_secs(10))
+                    .await
+            }
+        });
+        mock_ros.advance_time(Duration::from_secs(4)).await.unwrap();
+        assert_eq!(clock.now(), UNIX_EPOCH + Duration::from_secs(4));
+        assert!(!sleeper.is_finished());
+
+        mock_ros.advance_time(Duration::from_secs(6)).await.unwrap();
+        tokio::time::timeout(Duration::from_secs(1), sleeper)
+            .await
+            .expect("Sleeping on the sim clock should end once /clock reaches the deadline")
+            .unwrap();
+        assert_eq!(sim_clock.now(), UNIX_EPOCH + Duration::from_secs(10));
+    }
 }
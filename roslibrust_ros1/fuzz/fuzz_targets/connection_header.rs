@@ -0,0 +1,11 @@
+//! Feeds arbitrary bytes to the TCPROS connection header parser.
+//! Run with `cargo +nightly fuzz run connection_header` from roslibrust_ros1/
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use roslibrust_ros1::ConnectionHeader;
+
+fuzz_target!(|data: &[u8]| {
+    // Any input must produce either a header or an error, never a panic
+    let _ = ConnectionHeader::parse(data);
+});
@@ -0,0 +1,478 @@
+use crate::{NodeError, NodeHandle, PublisherAny, SubscriberAny};
+use abort_on_drop::ChildTask;
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use bytes::Bytes;
+use std::collections::HashMap;
+use std::io::{Cursor, Read};
+use std::sync::{
+    atomic::{AtomicU64, Ordering},
+    Arc, Mutex,
+};
+use tokio::sync::{mpsc, oneshot, watch};
+
+const SEPARATOR: &str =
+    "================================================================================";
+const HEADER_DEFINITION: &str = "uint32 seq\ntime stamp\nstring frame_id";
+const GOAL_ID_DEFINITION: &str = "time stamp\nstring id";
+const GOAL_STATUS_ARRAY_TYPE: &str = "actionlib_msgs/GoalStatusArray";
+
+/// Describes an actionlib action server discovered through the ROS master.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ActionInfo {
+    /// Namespace the action's topics live under e.g. "/fibonacci"
+    pub namespace: String,
+    /// Name of the action type e.g. "actionlib_tutorials/Fibonacci"
+    pub action_type: String,
+}
+
+impl ActionInfo {
+    /// Finds every action server currently known to the ROS master
+    ///
+    /// An action server is identified by a namespace with both a `status` topic of type
+    /// `actionlib_msgs/GoalStatusArray` and a `result` topic of an `...ActionResult` type.
+    pub async fn list(nh: &NodeHandle) -> Result<Vec<ActionInfo>, NodeError> {
+        let topic_types = nh.master_client().await?.get_topic_types().await?;
+        let types: HashMap<&str, &str> = topic_types
+            .iter()
+            .map(|(topic, topic_type)| (topic.as_str(), topic_type.as_str()))
+            .collect();
+        let mut actions: Vec<ActionInfo> = types
+            .iter()
+            .filter_map(|(topic, topic_type)| {
+                let namespace = topic.strip_suffix("/result")?;
+                let action_type = topic_type.strip_suffix("ActionResult")?;
+                let status_type = types.get(format!("{namespace}/status").as_str())?;
+                (*status_type == GOAL_STATUS_ARRAY_TYPE).then(|| ActionInfo {
+                    namespace: namespace.to_owned(),
+                    action_type: action_type.to_owned(),
+                })
+            })
+            .collect();
+        actions.sort_by(|a, b| a.namespace.cmp(&b.namespace));
+        Ok(actions)
+    }
+
+    /// Finds the action server under the given namespace, if there is one
+    pub async fn lookup(nh: &NodeHandle, namespace: &str) -> Result<Option<ActionInfo>, NodeError> {
+        let namespace = namespace.trim_end_matches('/');
+        Ok(Self::list(nh)
+            .await?
+            .into_iter()
+            .find(|action| action.namespace == namespace))
+    }
+
+    /// Name of the goal message type e.g. "actionlib_tutorials/FibonacciGoal"
+    pub fn goal_type(&self) -> String {
+        format!("{}Goal", self.action_type)
+    }
+
+    /// Name of the result message type e.g. "actionlib_tutorials/FibonacciResult"
+    pub fn result_type(&self) -> String {
+        format!("{}Result", self.action_type)
+    }
+
+    /// Name of the feedback message type e.g. "actionlib_tutorials/FibonacciFeedback"
+    pub fn feedback_type(&self) -> String {
+        format!("{}Feedback", self.action_type)
+    }
+}
+
+/// The state of a goal as reported by an action server, equivalent of `actionlib_msgs/GoalStatus`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GoalStatus {
+    pub goal_id: String,
+    /// One of the constants defined on this type e.g. [GoalStatus::SUCCEEDED]
+    pub status: u8,
+    pub text: String,
+}
+
+impl GoalStatus {
+    pub const PENDING: u8 = 0;
+    pub const ACTIVE: u8 = 1;
+    pub const PREEMPTED: u8 = 2;
+    pub const SUCCEEDED: u8 = 3;
+    pub const ABORTED: u8 = 4;
+    pub const REJECTED: u8 = 5;
+    pub const PREEMPTING: u8 = 6;
+    pub const RECALLING: u8 = 7;
+    pub const RECALLED: u8 = 8;
+    pub const LOST: u8 = 9;
+
+    /// True if the goal has finished and no further updates will be sent for it
+    pub fn is_terminal(&self) -> bool {
+        matches!(
+            self.status,
+            Self::PREEMPTED | Self::SUCCEEDED | Self::ABORTED | Self::REJECTED | Self::RECALLED
+        )
+    }
+}
+
+// Where the client's background task routes updates for a single goal
+struct GoalChannels {
+    status: watch::Sender<Option<GoalStatus>>,
+    feedback: mpsc::UnboundedSender<Bytes>,
+    result: Option<oneshot::Sender<(GoalStatus, Bytes)>>,
+}
+
+type GoalMap = Arc<Mutex<HashMap<String, GoalChannels>>>;
+
+/// An actionlib client for action types that are only known at runtime, e.g. for command line tools.
+///
+/// Goals, feedback, and results are exchanged as serialized ROS1 message bodies (without the leading length field)
+/// of the action's `Goal`, `Feedback`, and `Result` types, the client takes care of the surrounding
+/// `ActionGoal` / `ActionFeedback` / `ActionResult` wrapping.
+pub struct DynamicActionClient {
+    info: ActionInfo,
+    caller_id: String,
+    goal_publisher: PublisherAny,
+    cancel_publisher: Arc<PublisherAny>,
+    goals: GoalMap,
+    goal_count: AtomicU64,
+    _task: ChildTask<()>,
+}
+
+impl DynamicActionClient {
+    /// Connects to the action server described by `info`.
+    ///
+    /// `goal_definition` is the full text of the goal message's definition, as would be produced by
+    /// `gendeps --cat` for the `Goal` type, and is needed to advertise the goal topic with the correct md5sum.
+    pub async fn new(
+        nh: &NodeHandle,
+        info: ActionInfo,
+        goal_definition: &str,
+    ) -> Result<DynamicActionClient, NodeError> {
+        let namespace = &info.namespace;
+        let goal_type = info.goal_type();
+        let action_goal_definition = format!(
+            "Header header\nactionlib_msgs/GoalID goal_id\n{goal_type} goal\n\
+             {SEPARATOR}\nMSG: std_msgs/Header\n{HEADER_DEFINITION}\n\
+             {SEPARATOR}\nMSG: actionlib_msgs/GoalID\n{GOAL_ID_DEFINITION}\n\
+             {SEPARATOR}\nMSG: {goal_type}\n{goal_definition}"
+        );
+        let goal_publisher = nh
+            .advertise_any(
+                &format!("{namespace}/goal"),
+                &format!("{}ActionGoal", info.action_type),
+                &action_goal_definition,
+                10,
+                false,
+            )
+            .await?;
+        let cancel_publisher = nh
+            .advertise_any(
+                &format!("{namespace}/cancel"),
+                "actionlib_msgs/GoalID",
+                GOAL_ID_DEFINITION,
+                10,
+                false,
+            )
+            .await?
+            .into();
+        let status = nh.subscribe_any(&format!("{namespace}/status"), 10).await?;
+        let feedback = nh
+            .subscribe_any(&format!("{namespace}/feedback"), 100)
+            .await?;
+        let result = nh.subscribe_any(&format!("{namespace}/result"), 10).await?;
+
+        let goals = GoalMap::default();
+        let task = tokio::spawn(Self::route_updates(status, feedback, result, goals.clone()));
+        let caller_id = nh.master_client().await?.caller_id().to_owned();
+
+        Ok(DynamicActionClient {
+            info,
+            caller_id,
+            goal_publisher,
+            cancel_publisher,
+            goals,
+            goal_count: AtomicU64::new(0),
+            _task: task.into(),
+        })
+    }
+
+    pub fn info(&self) -> &ActionInfo {
+        &self.info
+    }
+
+    /// Sends a goal to the action server, `goal` is the serialized body of the action's `Goal` message.
+    pub async fn send_goal(&self, goal: &[u8]) -> Result<DynamicGoalHandle, NodeError> {
+        let (secs, nsecs) = now();
+        let count = self.goal_count.fetch_add(1, Ordering::Relaxed) + 1;
+        // Same id format as actionlib
+        let goal_id = format!("{}-{count}-{secs}.{nsecs:09}", self.caller_id);
+
+        let (status_sender, status) = watch::channel(None);
+        let (feedback_sender, feedback) = mpsc::unbounded_channel();
+        let (result_sender, result) = oneshot::channel();
+        self.goals.lock().unwrap().insert(
+            goal_id.clone(),
+            GoalChannels {
+                status: status_sender,
+                feedback: feedback_sender,
+                result: Some(result_sender),
+            },
+        );
+
+        let mut body = vec![];
+        write_header(&mut body, secs, nsecs)?;
+        write_goal_id(&mut body, secs, nsecs, &goal_id)?;
+        body.extend_from_slice(goal);
+        if let Err(e) = self.goal_publisher.publish(with_length(body)?).await {
+            self.goals.lock().unwrap().remove(&goal_id);
+            return Err(NodeError::IoError(std::io::Error::other(e.to_string())));
+        }
+
+        Ok(DynamicGoalHandle {
+            goal_id,
+            status,
+            feedback,
+            result: Some(result),
+            cancel_publisher: self.cancel_publisher.clone(),
+            goals: self.goals.clone(),
+        })
+    }
+
+    /// Asks the action server to cancel every goal, including those sent by other clients
+    pub async fn cancel_all_goals(&self) -> Result<(), NodeError> {
+        cancel(&self.cancel_publisher, "").await
+    }
+
+    // Parses the action server's messages and hands them to the goal they concern
+    async fn route_updates(
+        mut status: SubscriberAny,
+        mut feedback: SubscriberAny,
+        mut result: SubscriberAny,
+        goals: GoalMap,
+    ) {
+        // The subscriptions only close when the node shuts down
+        loop {
+            tokio::select! {
+                msg = status.next() => {
+                    let Some(msg) = msg else { return };
+                    let Ok(msg) = msg else { continue };
+                    let Ok(statuses) = parse_status_array(&msg) else {
+                        log::warn!("Received malformed actionlib_msgs/GoalStatusArray");
+                        continue;
+                    };
+                    let goals = goals.lock().unwrap();
+                    for status in statuses {
+                        if let Some(channels) = goals.get(&status.goal_id) {
+                            channels.status.send_replace(Some(status));
+                        }
+                    }
+                }
+                msg = feedback.next() => {
+                    let Some(msg) = msg else { return };
+                    let Ok(msg) = msg else { continue };
+                    let Ok((status, body)) = parse_with_status(&msg) else {
+                        log::warn!("Received malformed action feedback");
+                        continue;
+                    };
+                    if let Some(channels) = goals.lock().unwrap().get(&status.goal_id) {
+                        channels.status.send_replace(Some(status));
+                        let _ = channels.feedback.send(body);
+                    }
+                }
+                msg = result.next() => {
+                    let Some(msg) = msg else { return };
+                    let Ok(msg) = msg else { continue };
+                    let Ok((status, body)) = parse_with_status(&msg) else {
+                        log::warn!("Received malformed action result");
+                        continue;
+                    };
+                    if let Some(channels) = goals.lock().unwrap().get_mut(&status.goal_id) {
+                        channels.status.send_replace(Some(status.clone()));
+                        if let Some(sender) = channels.result.take() {
+                            let _ = sender.send((status, body));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
+/// Tracks a single goal sent with [DynamicActionClient::send_goal].
+///
+/// Dropping the handle stops tracking the goal, but does not cancel it.
+pub struct DynamicGoalHandle {
+    goal_id: String,
+    status: watch::Receiver<Option<GoalStatus>>,
+    feedback: mpsc::UnboundedReceiver<Bytes>,
+    result: Option<oneshot::Receiver<(GoalStatus, Bytes)>>,
+    cancel_publisher: Arc<PublisherAny>,
+    goals: GoalMap,
+}
+
+impl DynamicGoalHandle {
+    pub fn goal_id(&self) -> &str {
+        &self.goal_id
+    }
+
+    /// The latest status reported by the action server, None until the server has acknowledged the goal
+    pub fn status(&self) -> Option<GoalStatus> {
+        self.status.borrow().clone()
+    }
+
+    /// Returns the serialized body of the next `Feedback` message for this goal,
+    /// or None once the result has been received and no more feedback is queued.
+    pub async fn next_feedback(&mut self) -> Option<Bytes> {
+        tokio::select! {
+            feedback = self.feedback.recv() => feedback,
+            _ = self.status.wait_for(|status| status.as_ref().is_some_and(GoalStatus::is_terminal)) => {
+                self.feedback.try_recv().ok()
+            }
+        }
+    }
+
+    /// Waits for the goal to finish, returning its final status and the serialized body of the `Result` message
+    pub async fn result(&mut self) -> Result<(GoalStatus, Bytes), NodeError> {
+        let receiver = self.result.take().ok_or(NodeError::ChannelClosedError)?;
+        Ok(receiver.await?)
+    }
+
+    /// Asks the action server to cancel this goal
+    pub async fn cancel(&self) -> Result<(), NodeError> {
+        cancel(&self.cancel_publisher, &self.goal_id).await
+    }
+}
+
+impl Drop for DynamicGoalHandle {
+    fn drop(&mut self) {
+        self.goals.lock().unwrap().remove(&self.goal_id);
+    }
+}
+
+// An empty id with a zero stamp cancels all goals
+async fn cancel(publisher: &PublisherAny, goal_id: &str) -> Result<(), NodeError> {
+    let mut body = vec![];
+    write_goal_id(&mut body, 0, 0, goal_id)?;
+    publisher
+        .publish(with_length(body)?)
+        .await
+        .map_err(|e| NodeError::IoError(std::io::Error::other(e.to_string())))
+}
+
+pub(crate) fn now() -> (u32, u32) {
+    let now = std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .unwrap_or_default();
+    (now.as_secs() as u32, now.subsec_nanos())
+}
+
+pub(crate) fn write_string(out: &mut Vec<u8>, s: &str) -> std::io::Result<()> {
+    out.write_u32::<LittleEndian>(s.len() as u32)?;
+    out.extend_from_slice(s.as_bytes());
+    Ok(())
+}
+
+fn write_header(out: &mut Vec<u8>, secs: u32, nsecs: u32) -> std::io::Result<()> {
+    out.write_u32::<LittleEndian>(0)?;
+    out.write_u32::<LittleEndian>(secs)?;
+    out.write_u32::<LittleEndian>(nsecs)?;
+    write_string(out, "")
+}
+
+fn write_goal_id(out: &mut Vec<u8>, secs: u32, nsecs: u32, id: &str) -> std::io::Result<()> {
+    out.write_u32::<LittleEndian>(secs)?;
+    out.write_u32::<LittleEndian>(nsecs)?;
+    write_string(out, id)
+}
+
+// PublisherAny expects the body to be prefixed with its length
+fn with_length(body: Vec<u8>) -> std::io::Result<Vec<u8>> {
+    let mut framed = Vec::with_capacity(body.len() + 4);
+    framed.write_u32::<LittleEndian>(body.len() as u32)?;
+    framed.extend_from_slice(&body);
+    Ok(framed)
+}
+
+pub(crate) fn read_string(cursor: &mut Cursor<&[u8]>) -> std::io::Result<String> {
+    let len = cursor.read_u32::<LittleEndian>()? as usize;
+    let mut buf = vec![0u8; len];
+    cursor.read_exact(&mut buf)?;
+    String::from_utf8(buf).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+fn skip_header(cursor: &mut Cursor<&[u8]>) -> std::io::Result<()> {
+    // seq, secs, nsecs
+    cursor.set_position(cursor.position() + 12);
+    read_string(cursor)?;
+    Ok(())
+}
+
+fn read_goal_status(cursor: &mut Cursor<&[u8]>) -> std::io::Result<GoalStatus> {
+    // goal_id.stamp
+    cursor.set_position(cursor.position() + 8);
+    let goal_id = read_string(cursor)?;
+    let status = cursor.read_u8()?;
+    let text = read_string(cursor)?;
+    Ok(GoalStatus {
+        goal_id,
+        status,
+        text,
+    })
+}
+
+fn parse_status_array(msg: &[u8]) -> std::io::Result<Vec<GoalStatus>> {
+    let mut cursor = Cursor::new(msg);
+    skip_header(&mut cursor)?;
+    let count = cursor.read_u32::<LittleEndian>()?;
+    (0..count).map(|_| read_goal_status(&mut cursor)).collect()
+}
+
+// Splits an ActionFeedback or ActionResult into its status and the body of the wrapped message
+fn parse_with_status(msg: &Bytes) -> std::io::Result<(GoalStatus, Bytes)> {
+    let mut cursor = Cursor::new(msg.as_ref());
+    skip_header(&mut cursor)?;
+    let status = read_goal_status(&mut cursor)?;
+    Ok((status, msg.slice(cursor.position() as usize..)))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn status_bytes(out: &mut Vec<u8>, goal_id: &str, status: u8, text: &str) {
+        write_goal_id(out, 1, 2, goal_id).unwrap();
+        out.push(status);
+        write_string(out, text).unwrap();
+    }
+
+    #[test]
+    fn parses_action_result() {
+        let mut msg = vec![];
+        write_header(&mut msg, 10, 20).unwrap();
+        status_bytes(
+            &mut msg,
+            "/client-1-10.000000020",
+            GoalStatus::SUCCEEDED,
+            "done",
+        );
+        msg.extend_from_slice(&[1, 2, 3]);
+
+        let (status, body) = parse_with_status(&Bytes::from(msg)).unwrap();
+        assert_eq!(status.goal_id, "/client-1-10.000000020");
+        assert_eq!(status.status, GoalStatus::SUCCEEDED);
+        assert_eq!(status.text, "done");
+        assert!(status.is_terminal());
+        assert_eq!(body.as_ref(), &[1, 2, 3]);
+    }
+
+    #[test]
+    fn parses_status_array() {
+        let mut msg = vec![];
+        write_header(&mut msg, 10, 20).unwrap();
+        msg.write_u32::<LittleEndian>(2).unwrap();
+        status_bytes(&mut msg, "a", GoalStatus::ACTIVE, "");
+        status_bytes(&mut msg, "b", GoalStatus::ABORTED, "failed");
+
+        let statuses = parse_status_array(&msg).unwrap();
+        assert_eq!(statuses.len(), 2);
+        assert_eq!(statuses[0].goal_id, "a");
+        assert!(!statuses[0].is_terminal());
+        assert_eq!(statuses[1].text, "failed");
+
+        // Truncated messages are rejected rather than panicking
+        assert!(parse_status_array(&msg[..msg.len() - 3]).is_err());
+    }
+}
@@ -0,0 +1,520 @@
+//! A structured lifecycle for nodes, so node logic is written as a [NodeApp] rather than a hand-assembled main.
+//!
+//! [run] takes an app through:
+//! 1. Parsing the command line into [NodeArgs], following rosrun's `name:=value` conventions
+//! 2. Creating the node, with the remappings and private parameters given on the command line
+//! 3. [NodeApp::init], where the app declares its publishers, subscribers and parameters through an [AppContext],
+//!    which records them in a [Manifest]
+//! 4. Calling [NodeApp::step] at [NodeApp::rate] until it breaks, fails, or the process is interrupted
+//! 5. [NodeApp::shutdown], then the hooks registered with [AppContext::on_shutdown] in reverse order
+//!
+//! ```no_run
+//! use roslibrust_ros1::app::{AppContext, NodeApp};
+//! use roslibrust_ros1::{DeclaredParam, NodeError, Publisher};
+//! use roslibrust_test::ros1::std_msgs;
+//! use std::ops::ControlFlow;
+//!
+//! struct Counter {
+//!     publisher: Publisher<std_msgs::Int32>,
+//!     step: DeclaredParam<i32>,
+//!     count: i32,
+//! }
+//!
+//! impl NodeApp for Counter {
+//!     const NAME: &'static str = "counter";
+//!     const DESCRIPTION: &'static str = "Publishes an increasing count";
+//!
+//!     async fn init(ctx: &mut AppContext) -> Result<Self, NodeError> {
+//!         Ok(Counter {
+//!             publisher: ctx.advertise("count", 1).await?,
+//!             step: ctx.param("~step", 1).await?,
+//!             count: 0,
+//!         })
+//!     }
+//!
+//!     async fn step(&mut self) -> Result<ControlFlow<()>, NodeError> {
+//!         self.count += self.step.get();
+//!         self.publisher
+//!             .publish(&std_msgs::Int32 { data: self.count })
+//!             .await
+//!             .map_err(|e| std::io::Error::other(e.to_string()))?;
+//!         Ok(ControlFlow::Continue(()))
+//!     }
+//! }
+//!
+//! #[tokio::main]
+//! async fn main() -> Result<(), NodeError> {
+//!     // e.g. `counter __ns:=/robot _step:=2 count:=/robot/ticks`
+//!     roslibrust_ros1::app::run::<Counter>().await
+//! }
+//! ```
+
+use crate::{
+    names::Name, node::remap::Remappings, DeclaredParam, NodeError, NodeHandle, Publisher,
+    Subscriber,
+};
+use roslibrust_common::{clock::Rate, RosMessageType};
+use std::{collections::HashMap, fmt::Display, future::Future, ops::ControlFlow, pin::Pin};
+
+/// Master the node connects to when neither `__master:=` nor ROS_MASTER_URI is given
+const DEFAULT_MASTER_URI: &str = "http://localhost:11311";
+
+/// Node logic run through the lifecycle described in the [module docs](self) by [run].
+pub trait NodeApp: Sized + Send {
+    /// Name of the node, unless overridden with `__name:=`
+    const NAME: &'static str;
+    /// Description printed by `--help`
+    const DESCRIPTION: &'static str = "";
+
+    /// Creates the app, declaring the publishers, subscribers and parameters it uses through `ctx`
+    fn init(ctx: &mut AppContext) -> impl Future<Output = Result<Self, NodeError>> + Send;
+
+    /// How many times per second [NodeApp::step] is called, read once after [NodeApp::init]
+    fn rate(&self) -> f64 {
+        10.0
+    }
+
+    /// One iteration of the node's loop, returning [ControlFlow::Break] to shut the node down.
+    ///
+    /// A step in progress when the process is interrupted is dropped, like the losing branch of a `tokio::select!`.
+    fn step(&mut self) -> impl Future<Output = Result<ControlFlow<()>, NodeError>> + Send;
+
+    /// Called once the loop has ended, whether or not it ended with an error
+    fn shutdown(&mut self) -> impl Future<Output = ()> + Send {
+        async {}
+    }
+}
+
+/// Command line arguments of a node, parsed by [NodeArgs::parse].
+///
+/// Follows rosrun's conventions, where `__name:=`, `__ns:=` and `__master:=` configure the node, `_param:=value`
+/// sets a private parameter and any other `from:=to` remaps a name. A ROS2 style `--ros-args` section is also
+/// accepted, containing `-r from:=to` remappings and `-p param:=value` private parameters up to an optional `--`.
+/// Parameter values are read as a bool, integer or float where they look like one, and as a string otherwise.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct NodeArgs {
+    /// Set with `__name:=` (or `__node:=`)
+    pub name: Option<String>,
+    /// Set with `__ns:=`, the ROS_NAMESPACE environment variable is used when it isn't given
+    pub namespace: Option<String>,
+    /// Set with `__master:=`
+    pub master_uri: Option<String>,
+    /// Name remappings, from -> to
+    pub remappings: HashMap<String, String>,
+    /// Parameters to set before the node starts, in the order given
+    pub params: Vec<(String, serde_json::Value)>,
+    /// Whether `-h` or `--help` was given
+    pub help: bool,
+    /// Whether `--print-manifest` was given
+    pub print_manifest: bool,
+    /// Every argument which isn't one of the above, for the app itself
+    pub args: Vec<String>,
+}
+
+impl NodeArgs {
+    /// Parses the arguments the process was started with, excluding the program name
+    pub fn from_env() -> Result<NodeArgs, NodeError> {
+        NodeArgs::parse(std::env::args().skip(1))
+    }
+
+    /// Parses command line arguments, not including the program name
+    pub fn parse(args: impl IntoIterator<Item = impl Into<String>>) -> Result<NodeArgs, NodeError> {
+        let mut parsed = NodeArgs::default();
+        let mut args = args.into_iter().map(Into::into);
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "-h" | "--help" => parsed.help = true,
+                "--print-manifest" => parsed.print_manifest = true,
+                "--ros-args" => {
+                    while let Some(arg) = args.next() {
+                        match arg.as_str() {
+                            "--" => break,
+                            "-r" | "--remap" => {
+                                let value = args.next().ok_or_else(|| invalid_arg(&arg))?;
+                                let (from, to) = split_assignment(&value)?;
+                                parsed.assign(from, to);
+                            }
+                            "-p" | "--param" => {
+                                let value = args.next().ok_or_else(|| invalid_arg(&arg))?;
+                                let (key, value) = split_assignment(&value)?;
+                                parsed.params.push((format!("~{key}"), parse_value(value)));
+                            }
+                            _ => return Err(invalid_arg(&arg)),
+                        }
+                    }
+                }
+                _ => match arg.split_once(":=") {
+                    Some((from, to)) => parsed.assign(from, to),
+                    None => parsed.args.push(arg),
+                },
+            }
+        }
+        Ok(parsed)
+    }
+
+    // Applies a `from:=to` argument
+    fn assign(&mut self, from: &str, to: &str) {
+        match from {
+            "__name" | "__node" => self.name = Some(to.to_string()),
+            "__ns" => self.namespace = Some(to.to_string()),
+            "__master" => self.master_uri = Some(to.to_string()),
+            _ => match from.strip_prefix('_').filter(|key| !key.starts_with('_')) {
+                Some(key) => self.params.push((format!("~{key}"), parse_value(to))),
+                None => {
+                    self.remappings.insert(from.to_string(), to.to_string());
+                }
+            },
+        }
+    }
+}
+
+fn invalid_arg(arg: &str) -> NodeError {
+    NodeError::IoError(std::io::Error::new(
+        std::io::ErrorKind::InvalidInput,
+        format!("Invalid command line argument {arg:?}"),
+    ))
+}
+
+fn split_assignment(arg: &str) -> Result<(&str, &str), NodeError> {
+    arg.split_once(":=").ok_or_else(|| invalid_arg(arg))
+}
+
+// Reads a parameter value given on the command line like a YAML scalar
+fn parse_value(raw: &str) -> serde_json::Value {
+    if let Ok(value) = raw.parse::<bool>() {
+        return value.into();
+    }
+    if let Ok(value) = raw.parse::<i64>() {
+        return value.into();
+    }
+    match raw.parse::<f64>() {
+        Ok(value) if value.is_finite() => value.into(),
+        _ => {
+            let unquoted = ['"', '\'']
+                .iter()
+                .find_map(|quote| raw.strip_prefix(*quote)?.strip_suffix(*quote));
+            unquoted.unwrap_or(raw).into()
+        }
+    }
+}
+
+/// Something a node declared through its [AppContext]
+#[derive(Debug, Clone, PartialEq)]
+pub enum ManifestEntry {
+    Publisher {
+        topic: String,
+        msg_type: String,
+    },
+    Subscriber {
+        topic: String,
+        msg_type: String,
+    },
+    /// `default` is the default value as JSON
+    Param {
+        key: String,
+        default: String,
+    },
+}
+
+/// The publishers, subscribers and parameters a node declared in [NodeApp::init], with names fully resolved.
+///
+/// Logged once the node has started, and printed by `--print-manifest`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Manifest {
+    pub entries: Vec<ManifestEntry>,
+}
+
+impl Display for Manifest {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for entry in &self.entries {
+            match entry {
+                ManifestEntry::Publisher { topic, msg_type } => {
+                    writeln!(f, "publishes {topic} [{msg_type}]")?
+                }
+                ManifestEntry::Subscriber { topic, msg_type } => {
+                    writeln!(f, "subscribes {topic} [{msg_type}]")?
+                }
+                ManifestEntry::Param { key, default } => {
+                    writeln!(f, "param {key} (default {default})")?
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+type ShutdownHook = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+/// Given to [NodeApp::init] to declare what the node uses, recording each declaration in its [Manifest]
+pub struct AppContext {
+    nh: NodeHandle,
+    node_name: Name,
+    args: Vec<String>,
+    manifest: Manifest,
+    shutdown_hooks: Vec<ShutdownHook>,
+}
+
+impl AppContext {
+    /// The node's handle, scoped to the namespace given with `__ns:=` or ROS_NAMESPACE.
+    ///
+    /// Anything created through it directly isn't recorded in the manifest.
+    pub fn node_handle(&self) -> &NodeHandle {
+        &self.nh
+    }
+
+    /// The command line arguments which weren't ROS arguments
+    pub fn args(&self) -> &[String] {
+        &self.args
+    }
+
+    /// Everything declared so far
+    pub fn manifest(&self) -> &Manifest {
+        &self.manifest
+    }
+
+    /// Advertises a topic, see [NodeHandle::advertise]
+    pub async fn advertise<T: RosMessageType>(
+        &mut self,
+        topic: &str,
+        queue_size: usize,
+    ) -> Result<Publisher<T>, NodeError> {
+        let publisher = self.nh.advertise::<T>(topic, queue_size, false).await?;
+        self.manifest.entries.push(ManifestEntry::Publisher {
+            topic: self.resolve_name(topic),
+            msg_type: T::ROS_TYPE_NAME.to_string(),
+        });
+        Ok(publisher)
+    }
+
+    /// Subscribes to a topic, see [NodeHandle::subscribe]
+    pub async fn subscribe<T: RosMessageType>(
+        &mut self,
+        topic: &str,
+        queue_size: usize,
+    ) -> Result<Subscriber<T>, NodeError> {
+        let subscriber = self.nh.subscribe::<T>(topic, queue_size).await?;
+        self.manifest.entries.push(ManifestEntry::Subscriber {
+            topic: self.resolve_name(topic),
+            msg_type: T::ROS_TYPE_NAME.to_string(),
+        });
+        Ok(subscriber)
+    }
+
+    /// Declares a parameter which any value of type `T` is valid for, see [NodeHandle::declare_param]
+    pub async fn param<T>(&mut self, key: &str, default: T) -> Result<DeclaredParam<T>, NodeError>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        let default_json = serde_json::to_string(&default).unwrap_or_default();
+        let param = self.nh.declare_param(key, default, |_: &T| Ok(())).await?;
+        self.manifest.entries.push(ManifestEntry::Param {
+            key: param.key().to_string(),
+            default: default_json,
+        });
+        Ok(param)
+    }
+
+    /// Registers `hook` to be run when the node shuts down, after [NodeApp::shutdown].
+    ///
+    /// Hooks run in the reverse of the order they were registered, so later declarations are torn down first.
+    pub fn on_shutdown(&mut self, hook: impl Future<Output = ()> + Send + 'static) {
+        self.shutdown_hooks.push(Box::pin(hook));
+    }
+
+    // Resolves a name the handle accepted to the global name it refers to, for the manifest
+    fn resolve_name(&self, name: &str) -> String {
+        let name = self.nh.resolve_name(name);
+        match Name::new(&name) {
+            Ok(resolved) => resolved.resolve_to_global(&self.node_name).to_string(),
+            Err(_) => name,
+        }
+    }
+
+    async fn run_shutdown_hooks(&mut self) {
+        while let Some(hook) = self.shutdown_hooks.pop() {
+            hook.await;
+        }
+    }
+}
+
+/// Runs `A` with the arguments the process was started with, see the [module docs](self)
+pub async fn run<A: NodeApp>() -> Result<(), NodeError> {
+    run_with_args::<A>(NodeArgs::from_env()?).await
+}
+
+/// Runs `A` with already parsed arguments, see the [module docs](self)
+pub async fn run_with_args<A: NodeApp>(args: NodeArgs) -> Result<(), NodeError> {
+    if args.help {
+        print!("{}", usage::<A>());
+        return Ok(());
+    }
+
+    let namespace = args
+        .namespace
+        .clone()
+        .or_else(|| std::env::var("ROS_NAMESPACE").ok())
+        .filter(|ns| !ns.is_empty())
+        .map(|ns| global_namespace(&ns));
+    let name = args.name.as_deref().unwrap_or(A::NAME);
+    let name = match &namespace {
+        Some(ns) => format!("{}/{name}", ns.trim_end_matches('/')),
+        None => name.to_string(),
+    };
+    let master_uri = args
+        .master_uri
+        .clone()
+        .or_else(|| std::env::var("ROS_MASTER_URI").ok())
+        .unwrap_or_else(|| DEFAULT_MASTER_URI.to_string());
+
+    let node_name = crate::node::resolve_node_name(&name)?;
+    let mut nh = NodeHandle::new(&master_uri, &name).await?;
+    if !args.remappings.is_empty() {
+        nh.add_remappings(Remappings::new(node_name.clone(), args.remappings)?);
+    }
+    for (key, value) in &args.params {
+        nh.set_param(key, value).await?;
+    }
+    if let Some(ns) = &namespace {
+        nh = nh.scoped(ns)?;
+    }
+
+    let mut ctx = AppContext {
+        nh,
+        node_name,
+        args: args.args,
+        manifest: Manifest::default(),
+        shutdown_hooks: vec![],
+    };
+    let mut app = match A::init(&mut ctx).await {
+        Ok(app) => app,
+        Err(e) => {
+            log::error!("Failed to initialize {name}: {e}");
+            ctx.run_shutdown_hooks().await;
+            return Err(e);
+        }
+    };
+    log::info!("Started {name}, declaring:\n{}", ctx.manifest);
+    if args.print_manifest {
+        print!("{}", ctx.manifest);
+        app.shutdown().await;
+        ctx.run_shutdown_hooks().await;
+        return Ok(());
+    }
+
+    let mut rate = Rate::new(app.rate());
+    let interrupted = tokio::signal::ctrl_c();
+    tokio::pin!(interrupted);
+    let result = loop {
+        let step = tokio::select! {
+            _ = &mut interrupted => break Ok(()),
+            step = app.step() => step,
+        };
+        match step {
+            Ok(ControlFlow::Continue(())) => {}
+            Ok(ControlFlow::Break(())) => break Ok(()),
+            Err(e) => {
+                log::error!("{name} stopped after a failed step: {e}");
+                break Err(e);
+            }
+        }
+        tokio::select! {
+            _ = &mut interrupted => break Ok(()),
+            _ = rate.sleep() => {}
+        }
+    };
+
+    log::info!("Shutting down {name}");
+    app.shutdown().await;
+    ctx.run_shutdown_hooks().await;
+    result
+}
+
+// Namespaces given to a node are resolved from the root like rosrun does, so `robot` is `/robot`
+fn global_namespace(ns: &str) -> String {
+    format!("/{}", ns.trim_matches('/'))
+}
+
+/// Help text printed by `--help`
+fn usage<A: NodeApp>() -> String {
+    let description = match A::DESCRIPTION {
+        "" => String::new(),
+        description => format!(" - {description}"),
+    };
+    format!(
+        "{name}{description}
+
+Usage: {name} [ARGS...] [from:=to...] [_param:=value...] [--ros-args [-r from:=to] [-p param:=value] [--]]
+
+Options:
+  __name:=NAME        Name of the node (default {name})
+  __ns:=NAMESPACE     Namespace to run the node in (default ROS_NAMESPACE)
+  __master:=URI       ROS master to connect to (default ROS_MASTER_URI, or {DEFAULT_MASTER_URI})
+  _param:=VALUE       Sets the private parameter ~param before the node starts
+  from:=to            Remaps the name `from` to `to`
+  --print-manifest    Prints the topics and parameters the node declares, then exits
+  -h, --help          Prints this help
+",
+        name = A::NAME,
+    )
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parses_ros_arguments() {
+        let args = NodeArgs::parse([
+            "input.bag",
+            "__name:=talker",
+            "__ns:=/robot",
+            "image:=/camera/image_raw",
+            "_rate:=2.5",
+            "_enabled:=true",
+            "--verbose",
+            "--ros-args",
+            "-r",
+            "~out:=processed",
+            "-p",
+            "label:='left arm'",
+            "--",
+            "--print-manifest",
+        ])
+        .unwrap();
+        assert_eq!(args.name.as_deref(), Some("talker"));
+        assert_eq!(args.namespace.as_deref(), Some("/robot"));
+        assert_eq!(args.master_uri, None);
+        assert_eq!(
+            args.remappings,
+            HashMap::from([
+                ("image".to_string(), "/camera/image_raw".to_string()),
+                ("~out".to_string(), "processed".to_string()),
+            ])
+        );
+        assert_eq!(
+            args.params,
+            vec![
+                ("~rate".to_string(), serde_json::json!(2.5)),
+                ("~enabled".to_string(), serde_json::json!(true)),
+                ("~label".to_string(), serde_json::json!("left arm")),
+            ]
+        );
+        assert!(args.print_manifest);
+        assert!(!args.help);
+        assert_eq!(args.args, ["input.bag", "--verbose"]);
+
+        assert_eq!(parse_value("7"), serde_json::json!(7));
+        assert_eq!(parse_value("inf"), serde_json::json!("inf"));
+        assert!(NodeArgs::parse(["--ros-args", "-p"]).is_err());
+        assert!(NodeArgs::parse(["--ros-args", "-p", "missing_value"]).is_err());
+        assert!(NodeArgs::parse(["--ros-args", "--unknown"]).is_err());
+    }
+
+    #[test]
+    fn namespaces_are_global() {
+        assert_eq!(global_namespace("robot"), "/robot");
+        assert_eq!(global_namespace("robot/arm/"), "/robot/arm");
+        assert_eq!(global_namespace("/robot"), "/robot");
+        assert_eq!(global_namespace("/"), "/");
+    }
+}
@@ -0,0 +1,363 @@
+use crate::{AdvertiseOptions, NodeError, NodeHandle, Publisher, Subscriber};
+use roslibrust_common::rosout::Header;
+use roslibrust_common::RosMessageType;
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+use std::time::Duration;
+use tokio::sync::{mpsc, watch};
+use tokio::time::Instant;
+
+/// Equivalent of `bond/Status`, the heartbeat message exchanged by both sides of a bond
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub struct Status {
+    pub header: Header,
+    /// Id of the bond, shared by both sides
+    pub id: String,
+    /// Unique id of the side of the bond which sent this message
+    pub instance_id: String,
+    /// False once the sender has broken the bond
+    pub active: bool,
+    pub heartbeat_timeout: f32,
+    pub heartbeat_period: f32,
+}
+
+impl RosMessageType for Status {
+    const ROS_TYPE_NAME: &'static str = "bond/Status";
+    const MD5SUM: &'static str = "eacc84bf5d65b6777d4c50f463dfb9c8";
+    const DEFINITION: &'static str = r####"Header header
+string id  # ID of the bond
+string instance_id  # Unique ID for an individual in a bond
+bool active
+
+# Including the timeouts for the bond makes it easier to debug mis-matches
+# between the two sides.
+float32 heartbeat_timeout
+float32 heartbeat_period
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data
+# in a particular coordinate frame.
+#
+# sequence ID: consecutively increasing ID
+uint32 seq
+#Two-integer timestamp that is expressed as:
+# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
+# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
+# time-handling sugar is provided by the client library
+time stamp
+#Frame this data is associated with
+string frame_id"####;
+}
+
+/// Timing used by a [Bond], the defaults match those of bondcpp and bondpy.
+#[derive(Clone, Debug)]
+pub struct BondOptions {
+    pub(crate) heartbeat_period: Duration,
+    pub(crate) heartbeat_timeout: Duration,
+    pub(crate) connect_timeout: Duration,
+    pub(crate) disconnect_timeout: Duration,
+}
+
+impl Default for BondOptions {
+    fn default() -> Self {
+        BondOptions {
+            heartbeat_period: Duration::from_secs(1),
+            heartbeat_timeout: Duration::from_secs(4),
+            connect_timeout: Duration::from_secs(10),
+            disconnect_timeout: Duration::from_secs(2),
+        }
+    }
+}
+
+impl BondOptions {
+    /// How often a heartbeat is published
+    pub fn heartbeat_period(mut self, period: Duration) -> BondOptions {
+        self.heartbeat_period = period;
+        self
+    }
+
+    /// How long without a heartbeat from the other side before the bond is considered broken
+    pub fn heartbeat_timeout(mut self, timeout: Duration) -> BondOptions {
+        self.heartbeat_timeout = timeout;
+        self
+    }
+
+    /// How long to wait for the other side to appear before giving up on forming the bond
+    pub fn connect_timeout(mut self, timeout: Duration) -> BondOptions {
+        self.connect_timeout = timeout;
+        self
+    }
+
+    /// How long to wait for the other side to acknowledge the bond being broken
+    pub fn disconnect_timeout(mut self, timeout: Duration) -> BondOptions {
+        self.disconnect_timeout = timeout;
+        self
+    }
+}
+
+/// The stages of a bond's life, mirroring the states of bondcpp's state machine
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BondState {
+    /// No heartbeat has been received from the other side yet
+    WaitingForSister,
+    /// Both sides are exchanging heartbeats
+    Alive,
+    /// The bond is being broken and is waiting for the other side to acknowledge it
+    AwaitSisterDeath,
+    /// The bond is broken, it can't be re-formed
+    Dead,
+}
+
+/// A liveness bond with another node using the ROS1 bond protocol (bond_core).
+///
+/// Both sides publish `bond/Status` heartbeats on a shared topic, identifying the bond with a common id and
+/// themselves with a unique instance id. The bond is broken if either side stops sending heartbeats, or
+/// explicitly breaks it, which allows e.g. nodelet managers and their clients to notice each other dying.
+///
+/// Dropping a Bond breaks it, the other side is notified in the background.
+pub struct Bond {
+    id: String,
+    instance_id: String,
+    state: watch::Receiver<BondState>,
+    formed: Arc<AtomicBool>,
+    // Dropping or sending on this requests the bond is broken
+    break_sender: mpsc::UnboundedSender<()>,
+}
+
+impl Bond {
+    /// Starts forming the bond `id` with whatever other instance uses the same id on `topic`.
+    pub async fn new(
+        nh: &NodeHandle,
+        topic: &str,
+        id: &str,
+        options: BondOptions,
+    ) -> Result<Bond, NodeError> {
+        let publisher = nh
+            .advertise_with_options::<Status>(topic, AdvertiseOptions::new(5).stamp_on_send(true))
+            .await?;
+        let subscriber = nh.subscribe::<Status>(topic, 30).await?;
+
+        let instance_id = new_instance_id();
+        let (state_sender, state) = watch::channel(BondState::WaitingForSister);
+        let (break_sender, break_receiver) = mpsc::unbounded_channel();
+        let formed = Arc::new(AtomicBool::new(false));
+
+        // Not aborted when the Bond is dropped, the task finishes on its own once the bond is dead
+        tokio::spawn(Self::run(
+            publisher,
+            subscriber,
+            id.to_owned(),
+            instance_id.clone(),
+            options,
+            state_sender,
+            formed.clone(),
+            break_receiver,
+        ));
+
+        Ok(Bond {
+            id: id.to_owned(),
+            instance_id,
+            state,
+            formed,
+            break_sender,
+        })
+    }
+
+    /// The id shared by both sides of the bond
+    pub fn id(&self) -> &str {
+        &self.id
+    }
+
+    /// The unique id of this side of the bond
+    pub fn instance_id(&self) -> &str {
+        &self.instance_id
+    }
+
+    pub fn state(&self) -> BondState {
+        *self.state.borrow()
+    }
+
+    /// True once the bond has been broken, either by either side or by a timeout
+    pub fn is_broken(&self) -> bool {
+        self.state() == BondState::Dead
+    }
+
+    /// Waits until the other side has been heard from, returns false if the bond broke before forming
+    pub async fn wait_until_formed(&self) -> bool {
+        let mut state = self.state.clone();
+        let _ = state
+            .wait_for(|state| *state != BondState::WaitingForSister)
+            .await;
+        self.formed.load(Ordering::SeqCst)
+    }
+
+    /// Waits until the bond is broken
+    pub async fn wait_until_broken(&self) {
+        let mut state = self.state.clone();
+        let _ = state.wait_for(|state| *state == BondState::Dead).await;
+    }
+
+    /// Calls `callback` from a background task once the bond is formed, it is never called if the bond doesn't form.
+    pub fn on_formed(&self, callback: impl FnOnce() + Send + 'static) {
+        let mut state = self.state.clone();
+        let formed = self.formed.clone();
+        tokio::spawn(async move {
+            let _ = state
+                .wait_for(|state| *state != BondState::WaitingForSister)
+                .await;
+            if formed.load(Ordering::SeqCst) {
+                callback();
+            }
+        });
+    }
+
+    /// Calls `callback` from a background task once the bond is broken
+    pub fn on_broken(&self, callback: impl FnOnce() + Send + 'static) {
+        let mut state = self.state.clone();
+        tokio::spawn(async move {
+            let _ = state.wait_for(|state| *state == BondState::Dead).await;
+            callback();
+        });
+    }
+
+    /// Breaks the bond, completing once the other side has acknowledged it or the disconnect timeout expired
+    pub async fn break_bond(self) {
+        let _ = self.break_sender.send(());
+        self.wait_until_broken().await;
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    async fn run(
+        publisher: Publisher<Status>,
+        mut subscriber: Subscriber<Status>,
+        id: String,
+        instance_id: String,
+        options: BondOptions,
+        state_sender: watch::Sender<BondState>,
+        formed: Arc<AtomicBool>,
+        mut break_receiver: mpsc::UnboundedReceiver<()>,
+    ) {
+        let (publisher, id_ref, instance_id_ref) = (&publisher, &id, &instance_id);
+        let heartbeat_timeout = options.heartbeat_timeout.as_secs_f32();
+        let heartbeat_period = options.heartbeat_period.as_secs_f32();
+        let publish = move |active: bool| {
+            let status = Status {
+                header: Header::default(),
+                id: id_ref.clone(),
+                instance_id: instance_id_ref.clone(),
+                active,
+                heartbeat_timeout,
+                heartbeat_period,
+            };
+            async move {
+                if let Err(e) = publisher.publish(&status).await {
+                    log::error!("Failed to publish heartbeat for bond {}: {e}", status.id);
+                }
+            }
+        };
+
+        let mut heartbeat = tokio::time::interval(options.heartbeat_period);
+        let mut deadline = Instant::now() + options.connect_timeout;
+        let mut state = BondState::WaitingForSister;
+        let mut break_requested = false;
+        while state != BondState::Dead {
+            tokio::select! {
+                _ = heartbeat.tick() => {
+                    publish(state != BondState::AwaitSisterDeath).await;
+                }
+                msg = subscriber.next() => {
+                    let status = match msg {
+                        Some(Ok(status)) => status,
+                        Some(Err(e)) => {
+                            log::warn!("Error receiving heartbeat for bond {id}: {e}");
+                            continue;
+                        }
+                        None => {
+                            log::error!("Heartbeat subscription for bond {id} closed, bond is broken");
+                            state = BondState::Dead;
+                            state_sender.send_replace(state);
+                            continue;
+                        }
+                    };
+                    // Ignore our own heartbeats and those of other bonds on the topic
+                    if status.id != id || status.instance_id == instance_id {
+                        continue;
+                    }
+                    match (state, status.active) {
+                        (BondState::WaitingForSister, true) => {
+                            log::debug!("Bond {id} formed with {}", status.instance_id);
+                            formed.store(true, Ordering::SeqCst);
+                            state = BondState::Alive;
+                            deadline = Instant::now() + options.heartbeat_timeout;
+                        }
+                        (BondState::Alive, true) => {
+                            deadline = Instant::now() + options.heartbeat_timeout;
+                        }
+                        (BondState::AwaitSisterDeath, true) => {}
+                        // A dead bond can't be re-formed, late heartbeats are ignored
+                        (BondState::Dead, true) => {}
+                        (BondState::Alive, false) => {
+                            // Acknowledge the other side breaking the bond
+                            log::debug!("Bond {id} broken by {}", status.instance_id);
+                            state = BondState::AwaitSisterDeath;
+                            deadline = Instant::now() + options.disconnect_timeout;
+                            publish(false).await;
+                        }
+                        (_, false) => {
+                            state = BondState::Dead;
+                        }
+                    }
+                }
+                _ = tokio::time::sleep_until(deadline) => {
+                    match state {
+                        BondState::WaitingForSister => {
+                            log::warn!("Bond {id} was not formed within the connect timeout");
+                        }
+                        BondState::Alive => {
+                            log::warn!("Bond {id} broken, no heartbeat received within the heartbeat timeout");
+                        }
+                        _ => {}
+                    }
+                    state = BondState::Dead;
+                }
+                // Receiving None means the Bond was dropped, which also breaks it
+                _ = break_receiver.recv(), if !break_requested => {
+                    break_requested = true;
+                    match state {
+                        BondState::Alive => {
+                            state = BondState::AwaitSisterDeath;
+                            deadline = Instant::now() + options.disconnect_timeout;
+                            publish(false).await;
+                        }
+                        BondState::WaitingForSister => {
+                            state = BondState::Dead;
+                        }
+                        _ => {}
+                    }
+                }
+            }
+            state_sender.send_replace(state);
+        }
+        // Final notice so the other side doesn't have to wait for a timeout
+        publish(false).await;
+    }
+}
+
+// bond instance ids are conventionally uuids, we only need them to be unique so build one from std's random hasher keys
+fn new_instance_id() -> String {
+    use std::hash::BuildHasher;
+    let time = std::time::SystemTime::now();
+    let high = std::hash::RandomState::new().hash_one(time);
+    let low = std::hash::RandomState::new().hash_one((time, std::process::id()));
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0xffff,
+        low >> 48,
+        low & 0xffff_ffff_ffff
+    )
+}
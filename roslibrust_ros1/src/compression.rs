@@ -0,0 +1,193 @@
+use bytes::{BufMut, Bytes, BytesMut};
+
+// Compression of TCPROS message data between roslibrust nodes.
+// This is not part of the ROS protocol: subscribers list the algorithms they can decompress in a
+// `compression` connection header field, and a publisher configured to compress responds with the one it
+// picked in the same field. Other ROS clients ignore the unknown field and so always get plain TCPROS.
+// Once negotiated each message is sent as a 4 byte length followed by the compressed message.
+
+/// Compression algorithms for messages sent between roslibrust nodes, see [crate::AdvertiseOptions::compression].
+///
+/// Each algorithm is only available when the cargo feature of the same name is enabled.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Compression {
+    /// Fast with a moderate ratio, a good default for images on a local network
+    Lz4,
+    /// Slower with a better ratio, for constrained links
+    Zstd,
+}
+
+impl Compression {
+    /// Name of the algorithm as used in the connection header
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Compression::Lz4 => "lz4",
+            Compression::Zstd => "zstd",
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "lz4" => Some(Compression::Lz4),
+            "zstd" => Some(Compression::Zstd),
+            _ => None,
+        }
+    }
+
+    /// Whether support for this algorithm was compiled in
+    pub fn is_available(&self) -> bool {
+        match self {
+            Compression::Lz4 => cfg!(feature = "lz4"),
+            Compression::Zstd => cfg!(feature = "zstd"),
+        }
+    }
+}
+
+/// The connection header value a subscriber sends, listing every available algorithm.
+/// None if no compression support was compiled in.
+pub(crate) fn offer() -> Option<String> {
+    let available: Vec<_> = [Compression::Lz4, Compression::Zstd]
+        .into_iter()
+        .filter(Compression::is_available)
+        .map(|compression| compression.as_str())
+        .collect();
+    (!available.is_empty()).then(|| available.join(","))
+}
+
+/// Returns `preferred` if it is available and included in the subscriber's offer
+pub(crate) fn negotiate(preferred: Compression, offer: Option<&str>) -> Option<Compression> {
+    let offer = offer?;
+    (preferred.is_available() && offer.split(',').any(|name| name == preferred.as_str()))
+        .then_some(preferred)
+}
+
+/// Parses the algorithm a publisher picked from its connection header.
+/// Fails if it isn't one we can decompress, which would mean it wasn't one we offered.
+pub(crate) fn accepted(value: Option<&str>) -> std::io::Result<Option<Compression>> {
+    let Some(value) = value else {
+        return Ok(None);
+    };
+    match Compression::from_name(value) {
+        Some(compression) if compression.is_available() => Ok(Some(compression)),
+        _ => {
+            log::error!("Publisher picked compression {value} which we did not offer");
+            Err(std::io::ErrorKind::Unsupported.into())
+        }
+    }
+}
+
+/// Compresses a serialized message, which starts with its length as sent over TCPROS.
+/// The returned frame also starts with its length.
+pub(crate) fn compress(compression: Compression, message: &[u8]) -> std::io::Result<Bytes> {
+    // The match evaluates to a Result so it still has a type when no algorithm is compiled in
+    let compressed: Vec<u8> = match compression {
+        #[cfg(feature = "lz4")]
+        Compression::Lz4 => Ok(lz4_flex::compress_prepend_size(message)),
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => zstd::bulk::compress(message, zstd::DEFAULT_COMPRESSION_LEVEL),
+        #[allow(unreachable_patterns)]
+        _ => {
+            let _ = message;
+            Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+        }
+    }?;
+    let mut frame = BytesMut::with_capacity(4 + compressed.len());
+    frame.put_u32_le(compressed.len() as u32);
+    frame.put_slice(&compressed);
+    Ok(frame.freeze())
+}
+
+/// Reverses [compress], taking a frame as returned by [crate::tcpros::receive_body] and returning the message
+/// including its length.
+///
+/// A small frame can decompress to a huge message, so decompression stops once the message would be larger than
+/// `max_message_size`.
+pub(crate) fn decompress(
+    compression: Compression,
+    frame: &[u8],
+    max_message_size: usize,
+) -> std::io::Result<Bytes> {
+    let compressed = frame
+        .get(4..)
+        .ok_or(std::io::Error::from(std::io::ErrorKind::InvalidData))?;
+    // The decompressed message includes its own 4 byte length
+    let max_len = max_message_size.saturating_add(4);
+    let message: Vec<u8> = match compression {
+        #[cfg(feature = "lz4")]
+        Compression::Lz4 => {
+            // lz4_flex allocates the size the sender prepended up front, check it first
+            let size = compressed
+                .first_chunk::<4>()
+                .map(|size| u32::from_le_bytes(*size) as usize)
+                .ok_or(std::io::Error::from(std::io::ErrorKind::InvalidData))?;
+            if size > max_len {
+                return Err(crate::tcpros::message_too_large(
+                    size.saturating_sub(4),
+                    max_message_size,
+                ));
+            }
+            lz4_flex::decompress_size_prepended(compressed)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+        }
+        #[cfg(feature = "zstd")]
+        Compression::Zstd => {
+            use std::io::Read;
+            let mut message = Vec::new();
+            zstd::stream::read::Decoder::new(compressed)?
+                .take(max_len as u64 + 1)
+                .read_to_end(&mut message)?;
+            if message.len() > max_len {
+                return Err(crate::tcpros::message_too_large(
+                    message.len() - 4,
+                    max_message_size,
+                ));
+            }
+            Ok(message)
+        }
+        #[allow(unreachable_patterns)]
+        _ => {
+            let _ = (compressed, max_len);
+            Err(std::io::Error::from(std::io::ErrorKind::Unsupported))
+        }
+    }?;
+    Ok(Bytes::from(message))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn negotiates_only_offered_algorithms() {
+        assert_eq!(negotiate(Compression::Lz4, None), None);
+        assert_eq!(negotiate(Compression::Lz4, Some("zstd")), None);
+        assert_eq!(
+            negotiate(Compression::Lz4, Some("zstd,lz4")),
+            Compression::Lz4.is_available().then_some(Compression::Lz4)
+        );
+        assert!(accepted(Some("snappy")).is_err());
+        assert_eq!(accepted(None).unwrap(), None);
+    }
+
+    #[test]
+    fn round_trips() {
+        let message = [vec![8, 0, 0, 0, 4, 0, 0, 0], vec![b'a'; 4]].concat();
+        for compression in [Compression::Lz4, Compression::Zstd] {
+            if !compression.is_available() {
+                assert!(compress(compression, &message).is_err());
+                continue;
+            }
+            let frame = compress(compression, &message).unwrap();
+            assert_eq!(
+                u32::from_le_bytes(frame[0..4].try_into().unwrap()) as usize,
+                frame.len() - 4
+            );
+            assert_eq!(
+                &decompress(compression, &frame, 8).unwrap()[..],
+                &message[..]
+            );
+            let err = decompress(compression, &frame, 7).unwrap_err();
+            assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        }
+    }
+}
@@ -29,6 +29,7 @@
 //! }
 //! ```
 
+use roslibrust_common::params::ParameterProvider;
 use roslibrust_common::topic_name::{GlobalTopicName, ToGlobalTopicName};
 use roslibrust_common::Error;
 use roslibrust_common::{
@@ -47,16 +48,71 @@ mod node;
 pub use node::*;
 
 mod publisher;
+pub use publisher::AdvertiseOptions;
+pub use publisher::DynamicPublisher;
+pub use publisher::Priority;
 pub use publisher::Publisher;
 pub use publisher::PublisherAny;
+pub use publisher::PublisherBuilder;
+pub use publisher::PublisherError;
+mod qos;
+pub use qos::Liveliness;
 mod service_client;
 pub use service_client::ServiceClient;
 mod subscriber;
+pub use subscriber::Duplicates;
+pub use subscriber::Stamped;
 pub use subscriber::Subscriber;
 pub use subscriber::SubscriberAny;
+pub use subscriber::SubscriberBuilder;
+pub use subscriber::Transport;
+pub use subscriber::{MessageAlternatives, OneOf, SubscriberMulti};
 mod service_server;
+pub use service_server::ServiceInfo;
 pub use service_server::ServiceServer;
+pub use service_server::ServiceServerHandle;
+/// [compression] module contains optional compression of messages sent between roslibrust nodes
+mod compression;
 mod tcpros;
+pub use compression::Compression;
+pub use tcpros::{HeaderError, DEFAULT_MAX_MESSAGE_SIZE, MAX_HEADER_LENGTH};
+// Only exposed so the cargo-fuzz targets in fuzz/ can reach the parser
+#[cfg(feature = "fuzzing")]
+#[doc(hidden)]
+pub use tcpros::ConnectionHeader;
+/// [shm] module contains the optional shared memory transport between roslibrust nodes on the same host
+mod shm;
+/// [supervisor] module contains tracking of the tasks a node spawns
+mod supervisor;
+pub use supervisor::{ConnectionEvent, TaskFailure, TaskInfo, TaskKind, TaskSupervisor};
+/// [type_registry] module contains a registry for looking up message types by name at runtime
+pub mod type_registry;
+pub use type_registry::{RegisteredType, TypeRegistry};
+mod udpros;
+mod watermarks;
+pub use watermarks::{QueuePressure, Watermarks};
+
+/// [wire_recorder] module contains a debugging tool for capturing raw TCPROS traffic
+pub mod wire_recorder;
+pub use wire_recorder::WireRecorder;
+
+/// [xmlrpc_recorder] module contains tools for recording xmlrpc traffic and replaying it in tests
+pub mod xmlrpc_recorder;
+
+/// [bond] module contains an implementation of the bond_core liveness protocol
+pub mod bond;
+pub use bond::{Bond, BondOptions, BondState};
+
+/// [action] module contains introspection of actionlib servers and a client for action types only known at runtime
+pub mod action;
+
+/// [move_base] module contains a typed client for the navigation stack's `move_base` action
+pub mod move_base;
+pub use move_base::{MoveBaseClient, NavigationGoal, NavigationPose};
+
+/// [app] module contains the [NodeApp] lifecycle for structuring a node and parsing its command line
+pub mod app;
+pub use app::{AppContext, NodeApp, NodeArgs};
 
 /// Provides a common type alias for type erased service server functions.
 /// Internally we use this type to store collections of server functions.
@@ -102,7 +158,7 @@ impl<T: RosServiceType> Service<T> for ServiceClient<T> {
 
 impl ServiceProvider for crate::NodeHandle {
     type ServiceClient<T: RosServiceType> = crate::ServiceClient<T>;
-    type ServiceServer = crate::ServiceServer;
+    type ServiceServer = crate::ServiceServerHandle;
 
     async fn call_service<SrvType: RosServiceType>(
         &self,
@@ -143,6 +199,9 @@ impl<T: RosMessageType> Subscribe<T> for crate::Subscriber<T> {
         let res = crate::Subscriber::next(self).await;
         match res {
             Some(Ok(msg)) => Ok(msg),
+            Some(Err(subscriber::SubscriberError::DeserializeError(e))) => {
+                Err(Error::SerializationError(e))
+            }
             Some(Err(e)) => {
                 log::error!("Subscriber got error: {e:?}");
                 // TODO gotta do better error conversion / error types here
@@ -150,12 +209,8 @@ impl<T: RosMessageType> Subscribe<T> for crate::Subscriber<T> {
                     "Subscriber got error: {e:?}"
                 )))
             }
-            None => {
-                log::error!("Subscriber hit dropped channel");
-                Err(Error::Unexpected(anyhow::anyhow!(
-                    "Channel closed, something was dropped?"
-                )))
-            }
+            // The node has shut down, or the topic was unsubscribed from
+            None => Err(Error::Disconnected),
         }
     }
 }
@@ -164,9 +219,50 @@ impl<T: RosMessageType> Subscribe<T> for crate::Subscriber<T> {
 impl<T: RosMessageType> Publish<T> for Publisher<T> {
     async fn publish(&self, data: &T) -> roslibrust_common::Result<()> {
         // TODO error type conversion here is terrible and we need to standardize error stuff badly
-        self.publish(data)
+        self.publish(data).await.map_err(|e| match e {
+            PublisherError::MessageTooLarge { size, max } => Error::MessageTooLarge { size, max },
+            e => Error::SerializationError(e.to_string()),
+        })
+    }
+}
+
+// Parameters are read and written through the master's parameter server
+impl ParameterProvider for crate::NodeHandle {
+    async fn get_param<T: serde::de::DeserializeOwned + Send + 'static>(
+        &self,
+        name: &str,
+    ) -> roslibrust_common::Result<Option<T>> {
+        NodeHandle::get_param(self, name)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    async fn set_param<T: serde::Serialize + Sync>(
+        &self,
+        name: &str,
+        value: &T,
+    ) -> roslibrust_common::Result<()> {
+        NodeHandle::set_param(self, name, value)
+            .await
+            .map_err(|e| e.into())
+    }
+
+    async fn list_params(&self, namespace: &str) -> roslibrust_common::Result<Vec<String>> {
+        let namespace = self.resolve_name(namespace);
+        let namespace = namespace.trim_end_matches('/');
+        let names = self
+            .master_client()
+            .await?
+            .get_param_names()
             .await
-            .map_err(|e| Error::SerializationError(e.to_string()))
+            .map_err(NodeError::from)?;
+        Ok(names
+            .into_iter()
+            .filter(|name| {
+                name.strip_prefix(namespace)
+                    .is_some_and(|rest| rest.starts_with('/'))
+            })
+            .collect())
     }
 }
 
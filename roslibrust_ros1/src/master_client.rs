@@ -1,6 +1,24 @@
 //! This module is concerned with direct communication over xmlprc between the master
 
 use log::*;
+use std::time::Duration;
+
+// How long an idle keep-alive connection is held open in a node's client pool
+const XMLRPC_POOL_IDLE_TIMEOUT: Duration = Duration::from_secs(90);
+
+/// Builds the HTTP client a node uses for all of its xmlrpc requests
+/// reqwest keeps a pool of keep-alive connections per host, so repeated calls to the master, or to the same peer node,
+/// reuse an open connection rather than paying for a new one each time.
+/// Each node gets its own client: pooled connections are driven by tasks on the runtime that opened them, so a client
+/// shared between runtimes fails once the runtime that opened a connection is dropped.
+pub(crate) fn xmlrpc_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .pool_idle_timeout(XMLRPC_POOL_IDLE_TIMEOUT)
+        // xmlrpc calls are small request / response pairs, don't let Nagle's algorithm delay them
+        .tcp_nodelay(true)
+        .build()
+        .expect("Failed to construct xmlrpc http client")
+}
 
 #[derive(thiserror::Error, Debug)]
 pub enum RosMasterError {
@@ -19,6 +37,8 @@ pub enum RosMasterError {
 }
 
 /// A client that exposes the API hosted by the [rosmaster](http://wiki.ros.org/ROS/Master_API)
+///
+/// Clones of a client share its pool of keep-alive connections.
 // TODO consider exposing this type publicly
 #[derive(Clone)] // Note is clone to support an odd case in Node::drop
 pub struct MasterClient {
@@ -91,7 +111,7 @@ impl MasterClient {
         // Create a client, but we want to verify a valid connection before handing control back,
         // so we make an initial request and confirm with works before returning
         let client = MasterClient {
-            client: reqwest::Client::new(),
+            client: xmlrpc_client(),
             master_uri: master_uri.into(),
             client_uri: client_uri.into(),
             id: id.into(),
@@ -103,6 +123,11 @@ impl MasterClient {
         }
     }
 
+    /// The pooled http client used to reach the master, also used for this node's requests to peer nodes
+    pub(crate) fn xmlrpc_client(&self) -> &reqwest::Client {
+        &self.client
+    }
+
     async fn post<T: serde::de::DeserializeOwned + std::fmt::Debug>(
         &self,
         request: String,
@@ -111,12 +136,18 @@ impl MasterClient {
         let response = self
             .client
             .post(&self.master_uri)
-            .body(request)
+            .body(request.clone())
             .send()
             .await?
             .text()
             .await?;
         trace!("Got response: {response}");
+        crate::xmlrpc_recorder::record(
+            crate::xmlrpc_recorder::XmlRpcDirection::Outgoing,
+            &self.master_uri,
+            &request,
+            &response,
+        );
         let (status_code, msg, data) =
             serde_xmlrpc::response_from_str::<(i8, String, T)>(&response)?;
         match status_code {
@@ -130,6 +161,11 @@ impl MasterClient {
         Ok(data)
     }
 
+    /// Returns the caller id this client identifies itself to the master with
+    pub fn caller_id(&self) -> &str {
+        &self.id
+    }
+
     /// Returns the master uri this client is configured to reach
     pub fn get_master_uri(&self) -> &str {
         &self.master_uri
@@ -311,6 +347,104 @@ impl MasterClient {
         self.post(body).await
     }
 
+    /// Hits the master's xmlrpc endpoint "getParam" and returns the parameter's value.
+    ///
+    /// Returns `None` if the parameter is not set. `key` is resolved by the master relative to this client's
+    /// caller id, so private names like "~gain" refer to this node's parameters.
+    pub async fn get_param(
+        &self,
+        key: impl Into<String>,
+    ) -> Result<Option<serde_xmlrpc::Value>, RosMasterError> {
+        let key = key.into();
+        // The master reports an unset parameter as an error, so check first to tell the two apart
+        if !self.has_param(key.clone()).await? {
+            return Ok(None);
+        }
+        let body =
+            serde_xmlrpc::request_to_string("getParam", vec![self.id.clone().into(), key.into()])?;
+        self.post(body).await.map(Some)
+    }
+
+    /// Hits the master's xmlrpc endpoint "setParam", setting the parameter to `value`.
+    ///
+    /// Setting a map (e.g. a struct) sets each of its fields as parameters under `key`.
+    pub async fn set_param<T: serde::Serialize>(
+        &self,
+        key: impl Into<String>,
+        value: &T,
+    ) -> Result<(), RosMasterError> {
+        let body = serde_xmlrpc::request_to_string(
+            "setParam",
+            vec![
+                self.id.clone().into(),
+                key.into().into(),
+                serde_xmlrpc::to_value(value)?,
+            ],
+        )?;
+        // Third response parameter is ignored
+        let _: u8 = self.post(body).await?;
+        Ok(())
+    }
+
+    /// Hits the master's xmlrpc endpoint "hasParam", returns true if the parameter is set
+    pub async fn has_param(&self, key: impl Into<String>) -> Result<bool, RosMasterError> {
+        let body = serde_xmlrpc::request_to_string(
+            "hasParam",
+            vec![self.id.clone().into(), key.into().into()],
+        )?;
+        self.post(body).await
+    }
+
+    /// Hits the master's xmlrpc endpoint "deleteParam", fails if the parameter was not set
+    pub async fn delete_param(&self, key: impl Into<String>) -> Result<(), RosMasterError> {
+        let body = serde_xmlrpc::request_to_string(
+            "deleteParam",
+            vec![self.id.clone().into(), key.into().into()],
+        )?;
+        let _: u8 = self.post(body).await?;
+        Ok(())
+    }
+
+    /// Hits the master's xmlrpc endpoint "subscribeParam", after which the master calls "paramUpdate" on this
+    /// node's xmlrpc server whenever the parameter changes.
+    ///
+    /// Returns the current value of the parameter, which the master reports as an empty map if it is not set.
+    pub async fn subscribe_param(
+        &self,
+        key: impl Into<String>,
+    ) -> Result<serde_xmlrpc::Value, RosMasterError> {
+        let body = serde_xmlrpc::request_to_string(
+            "subscribeParam",
+            vec![
+                self.id.clone().into(),
+                self.client_uri.clone().into(),
+                key.into().into(),
+            ],
+        )?;
+        self.post(body).await
+    }
+
+    /// Hits the master's xmlrpc endpoint "unsubscribeParam", returns true if this node was subscribed to the
+    /// parameter
+    pub async fn unsubscribe_param(&self, key: impl Into<String>) -> Result<bool, RosMasterError> {
+        let body = serde_xmlrpc::request_to_string(
+            "unsubscribeParam",
+            vec![
+                self.id.clone().into(),
+                self.client_uri.clone().into(),
+                key.into().into(),
+            ],
+        )?;
+        let x: u8 = self.post(body).await?;
+        Ok(x.eq(&1))
+    }
+
+    /// Hits the master's xmlrpc endpoint "getParamNames", returns the names of every parameter that is set
+    pub async fn get_param_names(&self) -> Result<Vec<String>, RosMasterError> {
+        let body = serde_xmlrpc::request_to_string("getParamNames", vec![self.id.clone().into()])?;
+        self.post(body).await
+    }
+
     /// Returns where this client believes its own node's xmlrpc server is hosted at.
     /// This is simply a getter for the client_uri passed in while constructing this client.
     pub fn client_uri(&self) -> &str {
@@ -466,4 +600,22 @@ mod test {
         let topics = client.get_published_topics(subgraph).await.unwrap();
         assert!(!topics.is_empty());
     }
+
+    #[test_log::test(tokio::test)]
+    async fn test_set_get_and_delete_param() {
+        let client = test_client().await.unwrap();
+        // Private names are resolved by the master under the caller id
+        let key = "~test_set_get_and_delete_param";
+        client.set_param(key, &1.5).await.unwrap();
+        assert!(client
+            .has_param("/native_ros1_test/test_set_get_and_delete_param")
+            .await
+            .unwrap());
+        let value = client.get_param(key).await.unwrap().unwrap();
+        assert_eq!(serde_xmlrpc::from_value::<f64>(value).unwrap(), 1.5);
+
+        client.delete_param(key).await.unwrap();
+        assert!(client.get_param(key).await.unwrap().is_none());
+        assert!(client.delete_param(key).await.is_err());
+    }
 }
@@ -0,0 +1,239 @@
+use crate::action::{
+    now, read_string, write_string, ActionInfo, DynamicActionClient, DynamicGoalHandle, GoalStatus,
+};
+use crate::{NodeError, NodeHandle};
+use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use std::io::Cursor;
+
+/// The full definition of `move_base_msgs/MoveBaseGoal`
+const GOAL_DEFINITION: &str = r####"geometry_msgs/PoseStamped target_pose
+================================================================================
+MSG: geometry_msgs/PoseStamped
+std_msgs/Header header
+Pose pose
+================================================================================
+MSG: std_msgs/Header
+uint32 seq
+time stamp
+string frame_id
+================================================================================
+MSG: geometry_msgs/Pose
+Point position
+Quaternion orientation
+================================================================================
+MSG: geometry_msgs/Point
+float64 x
+float64 y
+float64 z
+================================================================================
+MSG: geometry_msgs/Quaternion
+float64 x
+float64 y
+float64 z
+float64 w"####;
+
+/// A pose within a frame, the contents of a `geometry_msgs/PoseStamped` other than its stamp
+#[derive(Debug, Clone, PartialEq)]
+pub struct NavigationPose {
+    pub frame_id: String,
+    /// x, y, z in meters
+    pub position: [f64; 3],
+    /// x, y, z, w of a unit quaternion
+    pub orientation: [f64; 4],
+}
+
+impl NavigationPose {
+    /// A pose on the ground plane of `frame_id`, facing `yaw` radians counterclockwise from its x axis
+    pub fn planar(frame_id: impl Into<String>, x: f64, y: f64, yaw: f64) -> Self {
+        Self {
+            frame_id: frame_id.into(),
+            position: [x, y, 0.0],
+            orientation: [0.0, 0.0, (yaw / 2.0).sin(), (yaw / 2.0).cos()],
+        }
+    }
+
+    // Serializes as a PoseStamped stamped with the current time
+    fn write(&self, out: &mut Vec<u8>) -> std::io::Result<()> {
+        let (secs, nsecs) = now();
+        out.write_u32::<LittleEndian>(0)?;
+        out.write_u32::<LittleEndian>(secs)?;
+        out.write_u32::<LittleEndian>(nsecs)?;
+        write_string(out, &self.frame_id)?;
+        for value in self.position.iter().chain(&self.orientation) {
+            out.write_f64::<LittleEndian>(*value)?;
+        }
+        Ok(())
+    }
+
+    fn read(cursor: &mut Cursor<&[u8]>) -> std::io::Result<Self> {
+        // seq, secs, nsecs
+        cursor.set_position(cursor.position() + 12);
+        let frame_id = read_string(cursor)?;
+        let mut values = [0.0; 7];
+        for value in &mut values {
+            *value = cursor.read_f64::<LittleEndian>()?;
+        }
+        let [x, y, z, qx, qy, qz, qw] = values;
+        Ok(Self {
+            frame_id,
+            position: [x, y, z],
+            orientation: [qx, qy, qz, qw],
+        })
+    }
+}
+
+/// A typed client for the ROS1 navigation stack's `move_base` action (`move_base_msgs/MoveBase`).
+///
+/// ```ignore
+/// let move_base = MoveBaseClient::new(&nh, "/move_base").await?;
+/// let mut goal = move_base.send_goal(&NavigationPose::planar("map", 2.0, 1.0, 0.0)).await?;
+/// while let Some(pose) = goal.next_feedback().await {
+///     println!("Robot at {:?}", pose.position);
+/// }
+/// let arrived = goal.result().await?.status == GoalStatus::SUCCEEDED;
+/// ```
+pub struct MoveBaseClient {
+    client: DynamicActionClient,
+}
+
+impl MoveBaseClient {
+    /// Connects to the move_base action server under `namespace`, usually "/move_base"
+    pub async fn new(nh: &NodeHandle, namespace: &str) -> Result<MoveBaseClient, NodeError> {
+        let info = ActionInfo {
+            namespace: namespace.trim_end_matches('/').to_owned(),
+            action_type: "move_base_msgs/MoveBase".to_owned(),
+        };
+        Ok(MoveBaseClient {
+            client: DynamicActionClient::new(nh, info, GOAL_DEFINITION).await?,
+        })
+    }
+
+    /// Sends the robot to `target`.
+    ///
+    /// The goal is cancelled if the returned handle is dropped before the goal finishes.
+    pub async fn send_goal(&self, target: &NavigationPose) -> Result<NavigationGoal, NodeError> {
+        let mut goal = vec![];
+        target.write(&mut goal)?;
+        Ok(NavigationGoal {
+            handle: Some(self.client.send_goal(&goal).await?),
+        })
+    }
+
+    /// Asks move_base to cancel every goal, including those sent by other clients
+    pub async fn cancel_all_goals(&self) -> Result<(), NodeError> {
+        self.client.cancel_all_goals().await
+    }
+}
+
+/// Tracks a goal sent with [MoveBaseClient::send_goal].
+///
+/// Unlike a [DynamicGoalHandle], dropping this handle cancels the goal if it hasn't finished, so the robot doesn't
+/// keep driving after the code waiting on it has gone. Use [NavigationGoal::detach] to let the goal run on.
+pub struct NavigationGoal {
+    // Only None once dropped or detached
+    handle: Option<DynamicGoalHandle>,
+}
+
+impl NavigationGoal {
+    fn handle(&self) -> &DynamicGoalHandle {
+        self.handle
+            .as_ref()
+            .expect("Goal handle is only taken on drop")
+    }
+
+    pub fn goal_id(&self) -> &str {
+        self.handle().goal_id()
+    }
+
+    /// The latest status reported by move_base, None until it has acknowledged the goal
+    pub fn status(&self) -> Option<GoalStatus> {
+        self.handle().status()
+    }
+
+    /// Returns the robot's pose from the next feedback message, or None once the goal has finished
+    /// and no more feedback is queued.
+    pub async fn next_feedback(&mut self) -> Option<NavigationPose> {
+        let handle = self.handle.as_mut()?;
+        loop {
+            let feedback = handle.next_feedback().await?;
+            match NavigationPose::read(&mut Cursor::new(feedback.as_ref())) {
+                Ok(pose) => return Some(pose),
+                Err(_) => log::warn!("Received malformed move_base_msgs/MoveBaseFeedback"),
+            }
+        }
+    }
+
+    /// Waits for the goal to finish, check for [GoalStatus::SUCCEEDED] to see if the robot arrived
+    pub async fn result(&mut self) -> Result<GoalStatus, NodeError> {
+        let handle = self.handle.as_mut().ok_or(NodeError::ChannelClosedError)?;
+        // MoveBaseResult is empty
+        Ok(handle.result().await?.0)
+    }
+
+    /// Asks move_base to cancel this goal
+    pub async fn cancel(&self) -> Result<(), NodeError> {
+        self.handle().cancel().await
+    }
+
+    /// Stops tracking the goal without cancelling it
+    pub fn detach(mut self) {
+        self.handle.take();
+    }
+}
+
+impl Drop for NavigationGoal {
+    fn drop(&mut self) {
+        let Some(handle) = self.handle.take() else {
+            return;
+        };
+        if handle.status().is_some_and(|status| status.is_terminal()) {
+            return;
+        }
+        // Cancelling publishes, which has to happen on the runtime
+        let Ok(runtime) = tokio::runtime::Handle::try_current() else {
+            log::warn!(
+                "Navigation goal {} dropped outside of a tokio runtime and could not be cancelled",
+                handle.goal_id()
+            );
+            return;
+        };
+        runtime.spawn(async move {
+            if let Err(e) = handle.cancel().await {
+                log::warn!("Failed to cancel navigation goal {}: {e}", handle.goal_id());
+            }
+        });
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn goal_definition_md5sum() {
+        assert_eq!(
+            roslibrust_common::md5sum::from_message_definition(
+                "move_base_msgs/MoveBaseGoal",
+                GOAL_DEFINITION
+            )
+            .unwrap(),
+            "257d089627d7eb7136c24d3593d05a16"
+        );
+    }
+
+    #[test]
+    fn pose_round_trips() {
+        let pose = NavigationPose::planar("map", 2.0, -1.0, std::f64::consts::PI);
+        assert!((pose.orientation[2] - 1.0).abs() < 1e-12);
+        assert!(pose.orientation[3].abs() < 1e-12);
+
+        let mut bytes = vec![];
+        pose.write(&mut bytes).unwrap();
+        // Header of 12 bytes plus the frame_id, then 7 float64s
+        assert_eq!(bytes.len(), 12 + 4 + 3 + 7 * 8);
+        let read = NavigationPose::read(&mut Cursor::new(bytes.as_slice())).unwrap();
+        assert_eq!(read, pose);
+
+        assert!(NavigationPose::read(&mut Cursor::new(&bytes[..bytes.len() - 1])).is_err());
+    }
+}
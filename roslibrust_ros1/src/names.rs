@@ -1,137 +1,21 @@
-use std::fmt::Display;
+// Graph resource names are implemented in roslibrust_common so they can be shared with the other backends
+pub use roslibrust_common::names::Name;
 
-// TODO Carter `26
-// This module predated roslibrust_common::topic_name, but now is somewhat redundant
-// We should overhaul usage in ros1 to use the new type and then remove this module.
-// We may want to implement a LocalName type that supports ~ and relative names, but I'm not sure we really do?
+use crate::NodeError;
 
-lazy_static::lazy_static! {
-    // See: https://wiki.ros.org/Names
-    // TODO this Regex has diverged from the one in roslibrust_common:topic_name and should be unified
-    static ref GRAPH_NAME_REGEX: regex::Regex = regex::Regex::new(r"^([/~a-zA-Z]){1}([a-zA-Z0-9_/])*([A-z0-9_])$").unwrap();
-}
-
-#[derive(Clone, Debug, PartialEq)]
-pub struct Name {
-    inner: String,
-}
-
-impl Name {
-    pub fn new(name: impl Into<String>) -> Result<Name, InvalidNameError> {
-        let name: String = name.into();
-        if is_valid(&name) {
-            Ok(Self { inner: name })
-        } else {
-            Err(InvalidNameError(name))
-        }
-    }
+#[derive(thiserror::Error, Debug)]
+#[error("Invalid Name: {0}")]
+pub struct InvalidNameError(String);
 
-    pub fn resolve_to_global(&self, node_name: &Name) -> Self {
-        if self.inner.starts_with('/') {
-            self.clone()
-        } else if self.inner.starts_with('~') {
-            Name {
-                inner: format!("{}/{}", node_name.inner, &self.inner[1..]),
-            }
-        } else {
-            let components = node_name.inner.split("/").collect::<Vec<_>>();
-            match components.len() {
-                0..=1 => unreachable!("Node name {} must have at least one /", node_name.inner),
-                2 => Name {
-                    inner: format!("/{}", self.inner),
-                },
-                len => Name {
-                    inner: format!(
-                        "{}/{}",
-                        components[1..len - 1]
-                            .iter()
-                            .fold(String::new(), |mut name, component| {
-                                name.push('/');
-                                name.push_str(component);
-                                name
-                            },),
-                        self.inner
-                    ),
-                },
+// roslibrust_common reports invalid names as Error::InvalidName, which is the only error Name::new produces
+impl From<roslibrust_common::Error> for NodeError {
+    fn from(value: roslibrust_common::Error) -> Self {
+        match value {
+            roslibrust_common::Error::InvalidName(msg) => {
+                NodeError::InvalidName(InvalidNameError(msg))
             }
+            roslibrust_common::Error::IoError(e) => NodeError::IoError(e),
+            other => NodeError::IoError(std::io::Error::other(other.to_string())),
         }
     }
 }
-
-impl Display for Name {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        self.inner.fmt(f)
-    }
-}
-
-fn is_valid(name: &str) -> bool {
-    GRAPH_NAME_REGEX.is_match(name)
-}
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-
-    #[test]
-    fn test_name_valid() {
-        assert!(is_valid("base"));
-        assert!(is_valid("relative/name"));
-        assert!(is_valid("/global/name"));
-        assert!(is_valid("~private/name"));
-
-        // These are invalid names
-        assert!(!is_valid("~"));
-        assert!(!is_valid("~~"));
-        assert!(!is_valid("_leading"));
-    }
-
-    // Examples pulled from http://wiki.ros.org/Names
-    #[test]
-    fn resolve_name() {
-        let node1 = Name::new("/node1").unwrap();
-        assert_eq!(
-            Name::new("bar").unwrap().resolve_to_global(&node1),
-            Name::new("/bar").unwrap()
-        );
-        assert_eq!(
-            Name::new("/bar").unwrap().resolve_to_global(&node1),
-            Name::new("/bar").unwrap()
-        );
-        assert_eq!(
-            Name::new("~bar").unwrap().resolve_to_global(&node1),
-            Name::new("/node1/bar").unwrap()
-        );
-
-        let node2 = Name::new("/wg/node2").unwrap();
-        assert_eq!(
-            Name::new("bar").unwrap().resolve_to_global(&node2),
-            Name::new("/wg/bar").unwrap()
-        );
-        assert_eq!(
-            Name::new("/bar").unwrap().resolve_to_global(&node2),
-            Name::new("/bar").unwrap()
-        );
-        assert_eq!(
-            Name::new("~bar").unwrap().resolve_to_global(&node2),
-            Name::new("/wg/node2/bar").unwrap()
-        );
-
-        let node3 = Name::new("/wg/node3").unwrap();
-        assert_eq!(
-            Name::new("foo/bar").unwrap().resolve_to_global(&node3),
-            Name::new("/wg/foo/bar").unwrap()
-        );
-        assert_eq!(
-            Name::new("/foo/bar").unwrap().resolve_to_global(&node3),
-            Name::new("/foo/bar").unwrap()
-        );
-        assert_eq!(
-            Name::new("~foo/bar").unwrap().resolve_to_global(&node3),
-            Name::new("/wg/node3/foo/bar").unwrap()
-        );
-    }
-}
-
-#[derive(thiserror::Error, Debug)]
-#[error("Invalid Name: {0}")]
-pub struct InvalidNameError(String);
@@ -1,10 +1,17 @@
 use crate::{
     names::Name,
-    node::{XmlRpcServer, XmlRpcServerHandle},
-    publisher::Publication,
+    node::{
+        manager::{IntraProcess, IntraProcessPublication},
+        param::ParamUpdate,
+        NodeStats, XmlRpcError, XmlRpcHandler, XmlRpcServer, XmlRpcServerHandle, BUILTIN_METHODS,
+    },
+    publisher::{AdvertiseOptions, Publication, PublicationHandle},
     service_client::ServiceClientLink,
-    service_server::ServiceServerLink,
-    subscriber::Subscription,
+    service_server::{ServiceInfo, ServiceServerLink, ServiceStats},
+    subscriber::{ReceivedMessage, Subscription, Transport},
+    supervisor::TaskSupervisor,
+    tcpros::DEFAULT_MAX_MESSAGE_SIZE,
+    wire_recorder::WireRecorder,
     MasterClient, NodeError, ProtocolParams, ServiceClient, TypeErasedCallback,
 };
 use abort_on_drop::ChildTask;
@@ -20,6 +27,9 @@ use tokio::sync::{broadcast, mpsc, oneshot};
 // I feel like someone was afraid of deadlocks or didn't know how to mutex safely?
 // We should be able to just call the function and get a result back instead of doing
 // this odd message passing indirection?
+// Parameter updates are rare, a cache that falls this far behind just drops everything it has cached
+const PARAM_UPDATE_QUEUE_SIZE: usize = 64;
+
 #[allow(clippy::type_complexity)]
 pub enum NodeMsg {
     GetMasterUri {
@@ -28,38 +38,81 @@ pub enum NodeMsg {
     GetClientUri {
         reply: oneshot::Sender<String>,
     },
+    GetMasterClient {
+        reply: oneshot::Sender<MasterClient>,
+    },
     GetSubscriptions {
         reply: oneshot::Sender<Vec<(String, String)>>,
     },
     GetPublications {
         reply: oneshot::Sender<Vec<(String, String)>>,
     },
+    GetStats {
+        reply: oneshot::Sender<NodeStats>,
+    },
+    GetSupervisor {
+        reply: oneshot::Sender<TaskSupervisor>,
+    },
     SetPeerPublishers {
         topic: String,
         publishers: Vec<String>,
     },
+    NotifyParamUpdate {
+        update: ParamUpdate,
+    },
+    GetParamUpdates {
+        reply: oneshot::Sender<broadcast::Receiver<ParamUpdate>>,
+    },
+    SubscribeParam {
+        reply: oneshot::Sender<Result<(), String>>,
+        key: String,
+    },
+    UnsubscribeParam {
+        key: String,
+    },
     // This function exists because "shutdown" is one of the XmlRpc Client APIs that is
     // technically part of the ROS ecosystem (never really seen it used)
     // This results in the node's task ending and the node being dropped.
     Shutdown,
+    SetWireRecorder {
+        recorder: Option<WireRecorder>,
+    },
+    SetTransportPreference {
+        transports: Vec<Transport>,
+    },
+    SetMaxMessageSize {
+        max_message_size: usize,
+    },
+    RegisterXmlRpcHandler {
+        reply: oneshot::Sender<Result<(), String>>,
+        method: String,
+        handler: XmlRpcHandler,
+    },
+    UnregisterXmlRpcHandler {
+        method: String,
+    },
+    GetXmlRpcHandler {
+        reply: oneshot::Sender<Option<XmlRpcHandler>>,
+        method: String,
+    },
     RegisterPublisher {
         // Uses Bytes for efficient cloning (reference counted) when there are multiple subscribers
-        reply: oneshot::Sender<Result<(broadcast::Sender<Bytes>, mpsc::Sender<()>), String>>,
+        reply: oneshot::Sender<Result<PublicationHandle, String>>,
         topic: String,
         topic_type: String,
-        queue_size: usize,
         msg_definition: String,
         md5sum: String,
-        latching: bool,
+        options: AdvertiseOptions,
     },
     RegisterSubscriber {
-        // Uses Bytes for efficient cloning (reference counted) when there are multiple subscribers
-        reply: oneshot::Sender<Result<broadcast::Receiver<Bytes>, String>>,
+        reply: oneshot::Sender<Result<broadcast::Receiver<ReceivedMessage>, String>>,
         topic: String,
         topic_type: String,
         queue_size: usize,
         msg_definition: String,
         md5sum: String,
+        // Overrides the node's transport preference if the subscription is created
+        transports: Option<Vec<Transport>>,
     },
     RegisterServiceClient {
         reply: oneshot::Sender<Result<ServiceClientLink, String>>,
@@ -69,7 +122,7 @@ pub enum NodeMsg {
         md5sum: String,
     },
     RegisterServiceServer {
-        reply: oneshot::Sender<Result<(), String>>,
+        reply: oneshot::Sender<Result<Arc<ServiceStats>, String>>,
         service: Name,
         service_type: String,
         srv_definition: String,
@@ -80,6 +133,9 @@ pub enum NodeMsg {
         reply: oneshot::Sender<Result<(), String>>,
         service_name: String,
     },
+    GetServices {
+        reply: oneshot::Sender<Vec<ServiceInfo>>,
+    },
     RequestTopic {
         reply: oneshot::Sender<Result<ProtocolParams, String>>,
         topic: String,
@@ -89,6 +145,10 @@ pub enum NodeMsg {
         reply: oneshot::Sender<Result<(), String>>,
         topic: String,
     },
+    UnregisterSubscriber {
+        reply: oneshot::Sender<Result<(), String>>,
+        topic: String,
+    },
 }
 
 /// Represents a communication handle to an underlying node server
@@ -140,6 +200,38 @@ impl NodeServerHandle {
         Ok(receiver.await?)
     }
 
+    /// Gets connection counts and message ages for every topic the node publishes or subscribes to.
+    pub(crate) async fn get_stats(&self) -> Result<NodeStats, NodeError> {
+        let (sender, receiver) = oneshot::channel();
+        self.node_server_sender
+            .send(NodeMsg::GetStats { reply: sender })?;
+        Ok(receiver.await?)
+    }
+
+    /// Gets the supervisor running the node's tasks
+    pub(crate) async fn get_supervisor(&self) -> Result<TaskSupervisor, NodeError> {
+        let (sender, receiver) = oneshot::channel();
+        self.node_server_sender
+            .send(NodeMsg::GetSupervisor { reply: sender })?;
+        Ok(receiver.await?)
+    }
+
+    /// Gets a copy of the client the node uses to talk to rosmaster
+    pub(crate) async fn get_master_client(&self) -> Result<MasterClient, NodeError> {
+        let (sender, receiver) = oneshot::channel();
+        self.node_server_sender
+            .send(NodeMsg::GetMasterClient { reply: sender })?;
+        Ok(receiver.await?)
+    }
+
+    /// Gets the list of services advertised by the node server
+    pub(crate) async fn get_services(&self) -> Result<Vec<ServiceInfo>, NodeError> {
+        let (sender, receiver) = oneshot::channel();
+        self.node_server_sender
+            .send(NodeMsg::GetServices { reply: sender })?;
+        Ok(receiver.await?)
+    }
+
     /// Updates the list of know publishers for a given topic
     /// This is used to know who to reach out to for updates
     pub(crate) fn set_peer_publishers(
@@ -152,6 +244,44 @@ impl NodeServerHandle {
             .send(NodeMsg::SetPeerPublishers { topic, publishers })?)
     }
 
+    /// Passes on a parameter update the master sent through the paramUpdate xmlrpc method
+    pub(crate) fn notify_param_update(&self, update: ParamUpdate) -> Result<(), NodeError> {
+        Ok(self
+            .node_server_sender
+            .send(NodeMsg::NotifyParamUpdate { update })?)
+    }
+
+    /// Returns a receiver of every parameter update the master sends this node
+    pub(crate) async fn param_updates(
+        &self,
+    ) -> Result<broadcast::Receiver<ParamUpdate>, NodeError> {
+        let (sender, receiver) = oneshot::channel();
+        self.node_server_sender
+            .send(NodeMsg::GetParamUpdates { reply: sender })?;
+        Ok(receiver.await?)
+    }
+
+    /// Asks the master to send updates for the (global) parameter `key`
+    /// Subscriptions are counted, each call should be matched by a call to [NodeServerHandle::unsubscribe_param]
+    pub(crate) async fn subscribe_param(&self, key: &str) -> Result<(), NodeError> {
+        let (sender, receiver) = oneshot::channel();
+        self.node_server_sender.send(NodeMsg::SubscribeParam {
+            reply: sender,
+            key: key.to_owned(),
+        })?;
+        receiver.await?.map_err(|err| {
+            warn!("Failure while subscribing to parameter: {err:?}");
+            NodeError::IoError(io::Error::from(io::ErrorKind::ConnectionAborted))
+        })
+    }
+
+    /// Releases a subscription made with [NodeServerHandle::subscribe_param]
+    pub(crate) fn unsubscribe_param(&self, key: &str) -> Result<(), NodeError> {
+        Ok(self.node_server_sender.send(NodeMsg::UnsubscribeParam {
+            key: key.to_owned(),
+        })?)
+    }
+
     /// Informs the underlying node server to shutdown
     /// This will stop all ROS functionality and poison all NodeHandles connected
     /// to the underlying node server.
@@ -160,24 +290,90 @@ impl NodeServerHandle {
         Ok(())
     }
 
+    /// Adds a user provided method to the node's xmlrpc server
+    /// Fails if the method is part of ROS's API or already has a handler
+    pub(crate) async fn register_xmlrpc_handler(
+        &self,
+        method: &str,
+        handler: XmlRpcHandler,
+    ) -> Result<(), NodeError> {
+        let (sender, receiver) = oneshot::channel();
+        self.node_server_sender
+            .send(NodeMsg::RegisterXmlRpcHandler {
+                reply: sender,
+                method: method.to_owned(),
+                handler,
+            })?;
+        receiver
+            .await?
+            .map_err(|method| NodeError::XmlRpcError(XmlRpcError::MethodAlreadyRegistered(method)))
+    }
+
+    /// Removes a user provided method from the node's xmlrpc server
+    pub(crate) fn unregister_xmlrpc_handler(&self, method: &str) -> Result<(), NodeError> {
+        self.node_server_sender
+            .send(NodeMsg::UnregisterXmlRpcHandler {
+                method: method.to_owned(),
+            })?;
+        Ok(())
+    }
+
+    /// Looks up the user provided handler for an xmlrpc method, if there is one
+    pub(crate) async fn get_xmlrpc_handler(
+        &self,
+        method: &str,
+    ) -> Result<Option<XmlRpcHandler>, NodeError> {
+        let (sender, receiver) = oneshot::channel();
+        self.node_server_sender.send(NodeMsg::GetXmlRpcHandler {
+            reply: sender,
+            method: method.to_owned(),
+        })?;
+        Ok(receiver.await?)
+    }
+
+    /// Sets the recorder used for publications and subscriptions created after this call
+    pub(crate) fn set_wire_recorder(
+        &self,
+        recorder: Option<WireRecorder>,
+    ) -> Result<(), NodeError> {
+        self.node_server_sender
+            .send(NodeMsg::SetWireRecorder { recorder })?;
+        Ok(())
+    }
+
+    /// Sets the transports offered to publishers by subscriptions created after this call
+    pub(crate) fn set_transport_preference(
+        &self,
+        transports: Vec<Transport>,
+    ) -> Result<(), NodeError> {
+        self.node_server_sender
+            .send(NodeMsg::SetTransportPreference { transports })?;
+        Ok(())
+    }
+
+    /// Sets the largest message accepted by subscriptions and services created after this call
+    pub(crate) fn set_max_message_size(&self, max_message_size: usize) -> Result<(), NodeError> {
+        self.node_server_sender
+            .send(NodeMsg::SetMaxMessageSize { max_message_size })?;
+        Ok(())
+    }
+
     /// Registers a publisher with the underlying node server
     /// Returns a channel that the raw bytes of a publish can be shoved into to queue the publish
     /// Uses Bytes for efficient cloning (reference counted) when there are multiple subscribers
     pub(crate) async fn register_publisher<T: RosMessageType>(
         &self,
         topic: &str,
-        queue_size: usize,
-        latching: bool,
-    ) -> Result<(broadcast::Sender<Bytes>, mpsc::Sender<()>), NodeError> {
+        options: AdvertiseOptions,
+    ) -> Result<PublicationHandle, NodeError> {
         let (sender, receiver) = oneshot::channel();
         self.node_server_sender.send(NodeMsg::RegisterPublisher {
             reply: sender,
             topic: topic.to_owned(),
             topic_type: T::ROS_TYPE_NAME.to_owned(),
-            queue_size,
             msg_definition: T::DEFINITION.to_owned(),
             md5sum: T::MD5SUM.to_owned(),
-            latching,
+            options,
         })?;
         let received = receiver.await?;
         received
@@ -192,9 +388,8 @@ impl NodeServerHandle {
         topic: &str,
         topic_type: &str,
         msg_definition: &str,
-        queue_size: usize,
-        latching: bool,
-    ) -> Result<(broadcast::Sender<Bytes>, mpsc::Sender<()>), NodeError> {
+        options: AdvertiseOptions,
+    ) -> Result<PublicationHandle, NodeError> {
         let (sender, receiver) = oneshot::channel();
 
         let md5sum_res =
@@ -214,10 +409,9 @@ impl NodeServerHandle {
             reply: sender,
             topic: topic.to_owned(),
             topic_type: topic_type.to_owned(),
-            queue_size,
             msg_definition: msg_definition.to_owned(),
             md5sum,
-            latching,
+            options,
         })?;
         let received = receiver.await?;
         received
@@ -237,6 +431,21 @@ impl NodeServerHandle {
         })
     }
 
+    /// Unregisters the subscription to a topic if no subscribers to it remain
+    pub(crate) async fn unregister_subscriber(&self, topic: &str) -> Result<(), NodeError> {
+        let (sender, receiver) = oneshot::channel();
+        self.node_server_sender
+            .send(NodeMsg::UnregisterSubscriber {
+                reply: sender,
+                topic: topic.to_owned(),
+            })?;
+        let rx = receiver.await?;
+        rx.map_err(|err| {
+            warn!("Failure while unregistering subscriber: {err:?}");
+            NodeError::IoError(io::Error::from(io::ErrorKind::ConnectionAborted))
+        })
+    }
+
     /// Registers a service client with the underlying node server
     /// This returns a channel that can be used for making service calls
     /// service calls will be queued in the channel and resolved when able.
@@ -273,7 +482,7 @@ impl NodeServerHandle {
         &self,
         service_name: &Name,
         server: F,
-    ) -> Result<(), NodeError>
+    ) -> Result<Arc<ServiceStats>, NodeError>
     where
         T: RosServiceType,
         F: ServiceFn<T>,
@@ -332,22 +541,46 @@ impl NodeServerHandle {
     /// If this is the first time the given topic has been subscribed to (by this node)
     /// rosmaster will be informed.
     /// Otherwise, a new rx handle will simply be returned to the existing channel.
-    /// Uses Bytes for efficient cloning (reference counted) when there are multiple subscribers
     pub(crate) async fn register_subscriber<T: RosMessageType>(
         &self,
         topic: &str,
         queue_size: usize,
-    ) -> Result<broadcast::Receiver<Bytes>, NodeError> {
+    ) -> Result<broadcast::Receiver<ReceivedMessage>, NodeError> {
+        self.register_subscriber_with_type(
+            topic,
+            T::ROS_TYPE_NAME,
+            T::DEFINITION,
+            T::MD5SUM,
+            queue_size,
+            None,
+        )
+        .await
+    }
+
+    /// Registers a subscription described by its type name, definition and md5sum rather than a type,
+    /// see [NodeServerHandle::register_subscriber].
+    ///
+    /// `transports` replaces the node's transport preference if this creates the subscription.
+    pub(crate) async fn register_subscriber_with_type(
+        &self,
+        topic: &str,
+        topic_type: &str,
+        msg_definition: &str,
+        md5sum: &str,
+        queue_size: usize,
+        transports: Option<Vec<Transport>>,
+    ) -> Result<broadcast::Receiver<ReceivedMessage>, NodeError> {
         // Type here is complicated, this is a channel that we're sending a channel receiver over
         // This channel is used to fire back the receiver of the underlying subscription
         let (sender, receiver) = oneshot::channel();
         self.node_server_sender.send(NodeMsg::RegisterSubscriber {
             reply: sender,
             topic: topic.to_owned(),
-            topic_type: T::ROS_TYPE_NAME.to_owned(),
+            topic_type: topic_type.to_owned(),
             queue_size,
-            msg_definition: T::DEFINITION.to_owned(),
-            md5sum: T::MD5SUM.to_owned(),
+            msg_definition: msg_definition.to_owned(),
+            md5sum: md5sum.to_owned(),
+            transports,
         })?;
         let received = receiver.await?;
         received.map_err(|err| {
@@ -389,7 +622,8 @@ pub(crate) struct Node {
     // The xmlrpc client this node uses to make requests to master
     client: MasterClient,
     // Server which handles updates from the rosmaster and other ROS nodes
-    _xmlrpc_server: XmlRpcServerHandle,
+    // Shared with other nodes when the node is hosted by a NodeManager
+    _xmlrpc_server: Arc<XmlRpcServerHandle>,
     // Receiver for requests to the Node actor
     node_msg_rx: mpsc::UnboundedReceiver<NodeMsg>,
     // Map of topic names to the publishing channels associated with the topic
@@ -411,6 +645,22 @@ pub(crate) struct Node {
     node_name: Name,
     // Store a handle to ourself so that we can pass it out later
     node_handle: NodeServerHandle,
+    // Debugging recorder handed to new publications and subscriptions for the topics it records
+    wire_recorder: Option<WireRecorder>,
+    // Transports offered to publishers by new subscriptions, in order of preference
+    transports: Vec<Transport>,
+    // Largest message new subscriptions and services accept from peers
+    max_message_size: usize,
+    // User provided xmlrpc methods, keyed by method name
+    xmlrpc_handlers: HashMap<String, XmlRpcHandler>,
+    // Registry of publications in this process, only present when the node is hosted by a NodeManager
+    intra_process: Option<IntraProcess>,
+    // Runs the tasks of publications, subscriptions and services as well as user tasks
+    supervisor: TaskSupervisor,
+    // Number of handles interested in updates to each parameter subscribed to with the master
+    param_subscriptions: HashMap<String, usize>,
+    // Parameter updates from the master, handed to anything caching parameters
+    param_updates: broadcast::Sender<ParamUpdate>,
 }
 
 impl Node {
@@ -420,16 +670,45 @@ impl Node {
         hostname: &str,
         node_name: &Name,
         addr: Ipv4Addr,
+    ) -> Result<NodeServerHandle, NodeError> {
+        // Create our xmlrpc server and bind our socket so we know our port and can determine our local URI
+        let xmlrpc_server = XmlRpcServer::new(addr, Default::default())?;
+        Self::new_with_xmlrpc_server(
+            master_uri,
+            hostname,
+            node_name,
+            addr,
+            Arc::new(xmlrpc_server),
+            None,
+        )
+        .await
+    }
+
+    /// Creates a node which is served by an existing xmlrpc server, used by [crate::NodeManager]
+    pub(crate) async fn new_with_xmlrpc_server(
+        master_uri: &str,
+        hostname: &str,
+        node_name: &Name,
+        addr: Ipv4Addr,
+        xmlrpc_server: Arc<XmlRpcServerHandle>,
+        intra_process: Option<IntraProcess>,
     ) -> Result<NodeServerHandle, NodeError> {
         let (node_sender, node_receiver) = mpsc::unbounded_channel();
-        let xml_server_handle = NodeServerHandle {
-            node_server_sender: node_sender.clone(),
-            // None here because this handle should not keep task alive
-            _node_task: None,
+        // Nodes sharing a server (those of a NodeManager) are each served at the path of their name,
+        // so the master and peers address calls to the right node
+        let path = match intra_process {
+            Some(_) => node_name.to_string(),
+            None => String::new(),
         };
-        // Create our xmlrpc server and bind our socket so we know our port and can determine our local URI
-        let xmlrpc_server = XmlRpcServer::new(addr, xml_server_handle)?;
-        let client_uri = format!("http://{hostname}:{}", xmlrpc_server.port());
+        let client_uri = format!("http://{hostname}:{}{path}", xmlrpc_server.port());
+        xmlrpc_server.add_node(
+            path,
+            NodeServerHandle {
+                node_server_sender: node_sender.clone(),
+                // None here because this handle should not keep task alive
+                _node_task: None,
+            },
+        );
 
         let rosmaster_client =
             MasterClient::new(master_uri, client_uri, node_name.to_string()).await?;
@@ -448,6 +727,14 @@ impl Node {
             hostname: hostname.to_owned(),
             node_name: node_name.to_owned(),
             node_handle: weak_handle,
+            wire_recorder: None,
+            transports: vec![Transport::Tcpros],
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            xmlrpc_handlers: HashMap::new(),
+            intra_process,
+            supervisor: TaskSupervisor::new(),
+            param_subscriptions: HashMap::new(),
+            param_updates: broadcast::channel(PARAM_UPDATE_QUEUE_SIZE).0,
         };
 
         let t = Arc::new(
@@ -487,6 +774,9 @@ impl Node {
             NodeMsg::GetClientUri { reply } => {
                 let _ = reply.send(self.client.client_uri().to_owned());
             }
+            NodeMsg::GetMasterClient { reply } => {
+                let _ = reply.send(self.client.clone());
+            }
             NodeMsg::GetSubscriptions { reply } => {
                 let _ = reply.send(
                     self.subscriptions
@@ -505,6 +795,79 @@ impl Node {
                         .collect(),
                 );
             }
+            NodeMsg::GetStats { reply } => {
+                let mut publications: Vec<_> = self
+                    .publishers
+                    .iter()
+                    .map(|(topic, publication)| publication.stats(topic))
+                    .collect();
+                publications.sort_by(|a, b| a.topic.cmp(&b.topic));
+                let mut subscriptions: Vec<_> = self
+                    .subscriptions
+                    .iter()
+                    .map(|(topic, subscription)| subscription.stats(topic))
+                    .collect();
+                subscriptions.sort_by(|a, b| a.topic.cmp(&b.topic));
+                let mut services: Vec<_> = self
+                    .service_servers
+                    .values()
+                    .map(|link| link.call_stats())
+                    .collect();
+                services.sort_by(|a, b| a.service.cmp(&b.service));
+                let _ = reply.send(NodeStats {
+                    node_name: self.node_name.to_string(),
+                    publications,
+                    subscriptions,
+                    services,
+                });
+            }
+            NodeMsg::GetSupervisor { reply } => {
+                let _ = reply.send(self.supervisor.clone());
+            }
+            NodeMsg::GetServices { reply } => {
+                let _ = reply.send(
+                    self.service_servers
+                        .values()
+                        .map(|link| {
+                            link.info(format!("rosrpc://{}:{}", self.hostname, link.port()))
+                        })
+                        .collect(),
+                );
+            }
+            NodeMsg::NotifyParamUpdate { update } => {
+                // Errors only if nothing is currently interested in updates
+                let _ = self.param_updates.send(update);
+            }
+            NodeMsg::GetParamUpdates { reply } => {
+                let _ = reply.send(self.param_updates.subscribe());
+            }
+            NodeMsg::SubscribeParam { reply, key } => {
+                let res = match self.param_subscriptions.get_mut(&key) {
+                    Some(count) => {
+                        *count += 1;
+                        Ok(())
+                    }
+                    None => match self.client.subscribe_param(&key).await {
+                        Ok(_value) => {
+                            self.param_subscriptions.insert(key, 1);
+                            Ok(())
+                        }
+                        Err(err) => Err(err.to_string()),
+                    },
+                };
+                let _ = reply.send(res);
+            }
+            NodeMsg::UnsubscribeParam { key } => {
+                if let Some(count) = self.param_subscriptions.get_mut(&key) {
+                    *count -= 1;
+                    if *count == 0 {
+                        self.param_subscriptions.remove(&key);
+                        if let Err(err) = self.client.unsubscribe_param(&key).await {
+                            warn!("Failed to unsubscribe from parameter {key}: {err}");
+                        }
+                    }
+                }
+            }
             NodeMsg::SetPeerPublishers { topic, publishers } => {
                 if let Some(subscription) = self.subscriptions.get_mut(&topic) {
                     for publisher_uri in publishers {
@@ -524,26 +887,15 @@ impl Node {
                 reply,
                 topic,
                 topic_type,
-                queue_size,
                 msg_definition,
                 md5sum,
-                latching,
+                options,
             } => {
-                let res = self
-                    .register_publisher(
-                        topic,
-                        &topic_type,
-                        queue_size,
-                        msg_definition,
-                        md5sum,
-                        latching,
-                    )
-                    .await;
-                match res {
-                    Ok(handle) => reply.send(Ok(handle)),
-                    Err(err) => reply.send(Err(err.to_string())),
-                }
-                .expect("Failed to reply on oneshot");
+                let _ = reply.send(
+                    self.register_publisher(topic, &topic_type, msg_definition, md5sum, options)
+                        .await
+                        .map_err(|err| err.to_string()),
+                );
             }
             NodeMsg::UnregisterPublisher { reply, topic } => {
                 let _ = reply.send(
@@ -552,6 +904,13 @@ impl Node {
                         .map_err(|err| err.to_string()),
                 );
             }
+            NodeMsg::UnregisterSubscriber { reply, topic } => {
+                let _ = reply.send(
+                    self.unregister_subscriber(&topic)
+                        .await
+                        .map_err(|err| err.to_string()),
+                );
+            }
             NodeMsg::RegisterSubscriber {
                 reply,
                 topic,
@@ -559,18 +918,27 @@ impl Node {
                 queue_size,
                 msg_definition,
                 md5sum,
+                transports,
             } => {
-                let _ = reply.send(
-                    self.register_subscriber(
+                let result = self
+                    .register_subscriber(
                         &topic,
                         &topic_type,
                         queue_size,
                         &msg_definition,
                         &md5sum,
+                        transports,
                     )
                     .await
-                    .map_err(|err| err.to_string()),
-                );
+                    .map_err(|err| err.to_string());
+                // The subscriber stopped waiting, e.g. it was cancelled while connecting to publishers,
+                // don't leave a subscription nobody is using behind
+                if let Err(Ok(receiver)) = reply.send(result) {
+                    drop(receiver);
+                    if let Err(err) = self.unregister_subscriber(&topic).await {
+                        warn!("Failed to unregister abandoned subscription to {topic}: {err}");
+                    }
+                }
             }
             NodeMsg::RegisterServiceClient {
                 reply,
@@ -647,9 +1015,46 @@ impl Node {
             NodeMsg::Shutdown => {
                 unreachable!("This node msg is handled in the wrapping handling code");
             }
+            NodeMsg::SetWireRecorder { recorder } => {
+                self.wire_recorder = recorder;
+            }
+            NodeMsg::SetTransportPreference { transports } => {
+                self.transports = transports;
+            }
+            NodeMsg::SetMaxMessageSize { max_message_size } => {
+                self.max_message_size = max_message_size;
+            }
+            NodeMsg::RegisterXmlRpcHandler {
+                reply,
+                method,
+                handler,
+            } => {
+                if BUILTIN_METHODS.contains(&method.as_str())
+                    || self.xmlrpc_handlers.contains_key(&method)
+                {
+                    let _ = reply.send(Err(method));
+                } else {
+                    self.xmlrpc_handlers.insert(method, handler);
+                    let _ = reply.send(Ok(()));
+                }
+            }
+            NodeMsg::UnregisterXmlRpcHandler { method } => {
+                self.xmlrpc_handlers.remove(&method);
+            }
+            NodeMsg::GetXmlRpcHandler { reply, method } => {
+                let _ = reply.send(self.xmlrpc_handlers.get(&method).cloned());
+            }
         }
     }
 
+    // Returns the wire recorder to use for a topic, if any
+    fn wire_recorder_for(&self, topic: &str) -> Option<WireRecorder> {
+        self.wire_recorder
+            .as_ref()
+            .filter(|recorder| recorder.records(topic))
+            .cloned()
+    }
+
     async fn register_subscriber(
         &mut self,
         topic: &str,
@@ -657,7 +1062,8 @@ impl Node {
         queue_size: usize,
         msg_definition: &str,
         md5sum: &str,
-    ) -> Result<broadcast::Receiver<Bytes>, NodeError> {
+        transports: Option<Vec<Transport>>,
+    ) -> Result<broadcast::Receiver<ReceivedMessage>, NodeError> {
         match self.subscriptions.iter().find(|(key, _)| *key == topic) {
             Some((_topic, subscription)) => Ok(subscription.get_receiver()),
             None => {
@@ -668,6 +1074,13 @@ impl Node {
                     queue_size,
                     msg_definition.to_owned(),
                     md5sum.to_owned(),
+                    self.wire_recorder_for(topic),
+                    self.intra_process.clone(),
+                    &self.hostname,
+                    transports.unwrap_or_else(|| self.transports.clone()),
+                    self.client.xmlrpc_client().clone(),
+                    self.max_message_size,
+                    self.supervisor.clone(),
                 );
                 let current_publishers = self.client.register_subscriber(topic, topic_type).await?;
                 for publisher in current_publishers {
@@ -686,11 +1099,10 @@ impl Node {
         &mut self,
         topic: String,
         topic_type: &str,
-        queue_size: usize,
         msg_definition: String,
         md5sum: String,
-        latching: bool,
-    ) -> Result<(broadcast::Sender<Bytes>, mpsc::Sender<()>), NodeError> {
+        options: AdvertiseOptions,
+    ) -> Result<PublicationHandle, NodeError> {
         // Return handle to existing Publication if it exists
         let existing_entry = {
             self.publishers.iter().find_map(|(key, value)| {
@@ -704,11 +1116,8 @@ impl Node {
                         std::io::ErrorKind::AddrInUse,
                     ))));
                 }
-                let (sender, shutdown) = value.get_senders();
-                match shutdown.upgrade() {
-                    Some(shutdown) => {
-                        Some(Ok((sender, shutdown)))
-                    }
+                match value.get_handle() {
+                    Some(handle) => Some(Ok(handle)),
                     None => {
                         error!("We still have an entry for a publication, but it has been shutdown");
                         // TODO MAJOR: this is a terrible error type to return...
@@ -721,21 +1130,21 @@ impl Node {
         };
         // If we found an existing publication return the handle to it
         if let Some(handle) = existing_entry {
-            let (sender, shutdown) = handle?;
-            return Ok((sender, shutdown));
+            return handle;
         }
 
         // Otherwise create a new Publication and advertise
-        let (channel, sender, shutdown) = Publication::new(
+        let (channel, handle) = Publication::new(
             &self.node_name,
-            latching,
+            options,
             &topic,
             self.host_addr,
-            queue_size,
             &msg_definition,
             &md5sum,
             topic_type,
             self.node_handle.clone(),
+            self.wire_recorder_for(&topic),
+            &self.supervisor,
         )
         .await
         .map_err(|err| {
@@ -743,8 +1152,21 @@ impl Node {
             err
         })?;
         self.publishers.insert(topic.clone(), channel);
+        // Make the publication visible to other nodes in this process before master tells them about it
+        if let Some(intra_process) = &self.intra_process {
+            intra_process.add_publication(
+                &topic,
+                IntraProcessPublication {
+                    node_name: self.node_name.to_string(),
+                    md5sum,
+                    sender: handle.sender.clone(),
+                    priority_sender: handle.priority_sender.clone(),
+                    latched: handle.latched.clone(),
+                },
+            );
+        }
         let _ = self.client.register_publisher(&topic, topic_type).await?;
-        Ok((sender, shutdown))
+        Ok(handle)
     }
 
     async fn unregister_publisher(&mut self, topic: &str) -> Result<(), NodeError> {
@@ -752,6 +1174,13 @@ impl Node {
         let err1 = self.client.unregister_publisher(topic).await;
         // Remove the publication from our internal state
         let err2 = self.publishers.remove(topic);
+        // Let anyone waiting in unadvertise know how unregistering went
+        if let Some(publication) = &err2 {
+            publication.set_unadvertised(err1.as_ref().map(|_| ()).map_err(|e| e.to_string()));
+        }
+        if let Some(intra_process) = &self.intra_process {
+            intra_process.remove_publication(topic, self.node_name.as_str());
+        }
         if err1.is_err() || err2.is_none() {
             error!(
                 "Failure unregistering publisher: {err1:?}, {}",
@@ -765,6 +1194,20 @@ impl Node {
         Ok(())
     }
 
+    async fn unregister_subscriber(&mut self, topic: &str) -> Result<(), NodeError> {
+        match self.subscriptions.get(topic) {
+            // Subscribers that share the subscription are still using it
+            Some(subscription) if subscription.subscriber_count() > 0 => return Ok(()),
+            Some(_) => {}
+            // Already unregistered, e.g. by another subscriber racing us
+            None => return Ok(()),
+        }
+        // Dropping the subscription closes its connections to publishers
+        self.subscriptions.remove(topic);
+        self.client.unregister_subscriber(topic).await?;
+        Ok(())
+    }
+
     /// Checks the internal state of the NodeServer to see if it has a service client registered for this service already
     /// If it does, it returns a Sender to the existing service client
     /// Otherwise, it creates a new service client and returns a Sender to the new service client
@@ -789,6 +1232,8 @@ impl Node {
             &service_uri,
             srv_definition,
             md5sum,
+            self.max_message_size,
+            &self.supervisor,
         )
         .await?;
 
@@ -803,7 +1248,7 @@ impl Node {
         srv_definition: &str,
         server: Box<TypeErasedCallback>,
         md5sum: &str,
-    ) -> Result<(), Box<dyn std::error::Error>> {
+    ) -> Result<Arc<ServiceStats>, Box<dyn std::error::Error>> {
         let found = self.service_servers.get_mut(service_type);
 
         // Create a new service server link
@@ -816,9 +1261,12 @@ impl Node {
             service_type.to_string(),
             md5sum.to_string(),
             srv_definition.to_string(),
+            self.max_message_size,
+            &self.supervisor,
         )
         .await?;
         let port = link.port();
+        let stats = link.stats();
 
         // Replace the existing entry or create a new one
         if let Some(server_in_map) = found {
@@ -836,7 +1284,7 @@ impl Node {
                 .await?;
         }
 
-        Ok(())
+        Ok(stats)
     }
 
     async fn unregister_service_server(
@@ -861,12 +1309,15 @@ impl Node {
     // Clears any extant node connections with the ros master
     // This is not expected to be called anywhere other than the drop impl
     fn shutdown(&mut self) {
+        // User tasks don't outlive the node
+        self.supervisor.shutdown();
         // Based on this answer: 3b https://stackoverflow.com/questions/71541765/rust-async-drop
         // Make copies of what we need to shut down
         let client = self.client.clone();
         let subscriptions = std::mem::take(&mut self.subscriptions);
         let publishers = std::mem::take(&mut self.publishers);
         let service_servers = std::mem::take(&mut self.service_servers);
+        let param_subscriptions = std::mem::take(&mut self.param_subscriptions);
         // Use hostname for unregistering services (must match what was registered)
         let hostname = self.hostname.clone();
 
@@ -897,6 +1348,13 @@ impl Node {
                     error!("Failed to unregister server server for topic: {topic} while shutting down node.");
                 });
             }
+
+            for key in param_subscriptions.keys() {
+                debug!("Node shutdown is cleaning up parameter subscription: {key}");
+                let _ = client.unsubscribe_param(key).await.inspect_err(|_e| {
+                    error!("Failed to unsubscribe from parameter: {key} while shutting down node.");
+                });
+            }
         };
         // Spawn shutdown operation in a separate task
         tokio::spawn(future);
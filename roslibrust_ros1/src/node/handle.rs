@@ -1,9 +1,28 @@
-use super::actor::{Node, NodeServerHandle};
+use super::{
+    actor::{Node, NodeServerHandle},
+    health::{self, HealthServer, NodeStats, StatsReporter},
+    param::{self, CachedParams, DeclaredParam, ParamUpdate, ReloadingParams},
+    remap::Remappings,
+    XmlRpcHandler, XmlRpcHandlerFuture, XmlRpcValue,
+};
 use crate::{
-    names::Name, publisher::Publisher, publisher::PublisherAny, service_client::ServiceClient,
-    subscriber::Subscriber, subscriber::SubscriberAny, NodeError, ServiceServer,
+    names::Name,
+    publisher::{
+        starts_with_header, AdvertiseOptions, DynamicPublisher, Publisher, PublisherAny,
+        PublisherBuilder,
+    },
+    service_client::ServiceClient,
+    subscriber::Subscriber,
+    subscriber::SubscriberAny,
+    subscriber::SubscriberBuilder,
+    subscriber::Transport,
+    subscriber::{MessageAlternatives, SubscriberMulti},
+    wire_recorder::WireRecorder,
+    MasterClient, NodeError, RosMasterError, ServiceInfo, ServiceServerHandle, TaskSupervisor,
+    TypeRegistry,
 };
 use roslibrust_common::ServiceFn;
+use std::{collections::HashMap, future::Future, net::SocketAddr, sync::Arc, time::Duration};
 
 /// Represents a handle to an underlying Node. NodeHandle's can be freely cloned, moved, copied, etc.
 /// This class provides the user facing API for interacting with ROS.
@@ -11,6 +30,10 @@ use roslibrust_common::ServiceFn;
 #[derive(Clone)]
 pub struct NodeHandle {
     inner: NodeServerHandle,
+    // Namespace that relative names used with this handle are placed under, see [NodeHandle::scoped]
+    namespace: Option<String>,
+    // Remappings loaded from the node's `~remappings` parameter, None if it wasn't set
+    remappings: Option<Arc<Remappings>>,
 }
 
 impl NodeHandle {
@@ -21,21 +44,36 @@ impl NodeHandle {
     ///   - master_uri: Expects a fully resolved http uri for the master e.g. "http://my_host_name:11311"
     ///   - name: The name of the node, expected to be a valid ros name, all names are interpreted as 'global' in
     ///     ROS's namespace system. e.g. "my_node" -> "/my_node". "~my_node" is not supported
+    ///
+    /// If the parameter `~remappings` is set to a map of names, e.g. `{in: /camera/image_raw}`, names used with the
+    /// node are remapped through it, allowing topics to be re-routed at launch-time. Returns an error if the table
+    /// isn't a map of valid ROS names.
     pub async fn new(master_uri: &str, name: &str) -> Result<NodeHandle, NodeError> {
-        let name = if name.starts_with("/") {
-            Name::new(name)?
-        } else {
-            Name::new(format!("/{}", name))?
-        };
-
-        // Extra safety check that our name resolves now
-        let _ = Name::new("test").unwrap().resolve_to_global(&name);
+        let name = super::resolve_node_name(name)?;
 
         // Follow ROS rules and determine our IP and hostname
         let (addr, hostname) = super::determine_addr(master_uri).await?;
 
         let node = Node::new(master_uri, &hostname, &name, addr).await?;
-        let nh = NodeHandle { inner: node };
+        let mut nh = NodeHandle {
+            inner: node,
+            namespace: None,
+            remappings: None,
+        };
+
+        if let Some(raw) = nh
+            .get_param::<HashMap<String, String>>(Remappings::PARAM)
+            .await?
+        {
+            let remappings = Remappings::new(name, raw)?;
+            if !remappings.is_empty() {
+                log::info!(
+                    "Using remappings from {}: {remappings:?}",
+                    Remappings::PARAM
+                );
+                nh.remappings = Some(Arc::new(remappings));
+            }
+        }
 
         Ok(nh)
     }
@@ -49,6 +87,100 @@ impl NodeHandle {
                 node_server_sender: self.inner.node_server_sender.clone(),
                 _node_task: None,
             },
+            namespace: self.namespace.clone(),
+            remappings: self.remappings.clone(),
+        }
+    }
+
+    /// Wraps a handle to the node server in a NodeHandle, used when handing the node out to user callbacks
+    pub(crate) fn from_server_handle(inner: NodeServerHandle) -> NodeHandle {
+        NodeHandle {
+            inner,
+            namespace: None,
+            remappings: None,
+        }
+    }
+
+    /// Creates a handle to the same node which places relative names under the namespace `ns`.
+    ///
+    /// Mirrors roscpp's `NodeHandle(parent, ns)`: topics and services passed to the returned handle that are relative
+    /// (e.g. "chatter") are prefixed with the namespace, while global ("/chatter") and private ("~chatter") names are
+    /// left untouched. Scoping an already scoped handle with a relative namespace nests the namespaces, a global
+    /// namespace replaces the existing one.
+    /// ```no_run
+    /// # async fn example(nh: roslibrust_ros1::NodeHandle) -> Result<(), roslibrust_ros1::NodeError> {
+    /// let arm = nh.scoped("/robot")?.scoped("arm")?;
+    /// // Subscribes to /robot/arm/joint_states
+    /// let sub = arm.subscribe_any("joint_states", 1).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    /// Returns an error if `ns` is not a valid ROS name.
+    pub fn scoped(&self, ns: &str) -> Result<NodeHandle, NodeError> {
+        // Normalizing would turn an empty namespace into the parent's namespace, so it is rejected up front
+        if ns.trim().is_empty() {
+            return Err(roslibrust_common::Error::InvalidName(format!(
+                "Invalid name: {ns:?}, reasons: namespace is empty"
+            ))
+            .into());
+        }
+        // Scoping to "/" yields an empty namespace, which places relative names at the root
+        let is_root = ns.starts_with('/') && ns.trim_end_matches('/').is_empty();
+        let ns = ns.trim_end_matches('/');
+        let namespace = match &self.namespace {
+            Some(parent) if !is_root && !ns.starts_with('/') && !ns.starts_with('~') => {
+                format!("{parent}/{ns}")
+            }
+            _ => ns.to_owned(),
+        };
+        // Validate and normalize the combined namespace so errors surface here instead of at first use
+        let namespace = if is_root {
+            namespace
+        } else {
+            Name::new(namespace)?.to_string()
+        };
+        Ok(NodeHandle {
+            inner: self.inner.clone(),
+            namespace: Some(namespace),
+            remappings: self.remappings.clone(),
+        })
+    }
+
+    /// Applies `remappings` on top of those loaded from `~remappings`, e.g. ones given on the command line
+    pub(crate) fn add_remappings(&mut self, remappings: Remappings) {
+        let merged = match self.remappings.take() {
+            Some(existing) => {
+                let mut merged = Arc::unwrap_or_clone(existing);
+                merged.extend(remappings);
+                merged
+            }
+            None => remappings,
+        };
+        if !merged.is_empty() {
+            self.remappings = Some(Arc::new(merged));
+        }
+    }
+
+    /// Returns the namespace set with [NodeHandle::scoped], if any
+    pub fn namespace(&self) -> Option<&str> {
+        self.namespace.as_deref()
+    }
+
+    // Applies this handle's namespace, then the node's remappings, to a topic, service or parameter name
+    pub(crate) fn resolve_name(&self, name: &str) -> String {
+        let name = match &self.namespace {
+            Some(namespace) if !name.starts_with('/') && !name.starts_with('~') => {
+                format!("{namespace}/{name}")
+            }
+            _ => name.to_owned(),
+        };
+        match self
+            .remappings
+            .as_ref()
+            .and_then(|remappings| remappings.remap(&name))
+        {
+            Some(remapped) => remapped.to_owned(),
+            None => name,
         }
     }
 
@@ -66,6 +198,244 @@ impl NodeHandle {
         self.inner.get_client_uri().await
     }
 
+    /// Returns a client for calling the ROS master's API directly, identified as this node.
+    pub async fn master_client(&self) -> Result<MasterClient, NodeError> {
+        self.inner.get_master_client().await
+    }
+
+    /// Returns a snapshot of activity on every topic this node publishes or subscribes to, including message
+    /// rates, bytes and dropped messages, and of the requests handled by each of its services.
+    pub async fn stats(&self) -> Result<NodeStats, NodeError> {
+        self.inner.get_stats().await
+    }
+
+    /// Publishes the node's [NodeStats] every `period` on `<node name>/statistics`, as JSON in a latched
+    /// std_msgs/String, so a fleet of nodes can be monitored with ROS tooling alone.
+    ///
+    /// Doesn't keep the node alive, and stops when the returned handle is dropped.
+    pub async fn publish_stats(&self, period: Duration) -> Result<StatsReporter, NodeError> {
+        health::report(self.weak_clone(), period).await
+    }
+
+    /// Starts an HTTP server on the given address exposing liveness, readiness and [NodeStats] as JSON,
+    /// so containers can probe the node without ROS tooling. See [HealthServer] for the endpoints.
+    ///
+    /// The server does not keep the node alive, and stops when the returned handle is dropped.
+    pub fn serve_health(&self, addr: SocketAddr) -> Result<HealthServer, NodeError> {
+        health::serve(self.weak_clone().inner, addr)
+    }
+
+    /// Returns the [TaskSupervisor] running this node's publisher, subscriber and service tasks.
+    ///
+    /// It reports any of those tasks which panic through [TaskSupervisor::failures], and can run user tasks
+    /// which are aborted when the node shuts down.
+    pub async fn supervisor(&self) -> Result<TaskSupervisor, NodeError> {
+        self.inner.get_supervisor().await
+    }
+
+    /// Returns a description of each service currently advertised by this node.
+    pub async fn services(&self) -> Result<Vec<ServiceInfo>, NodeError> {
+        self.inner.get_services().await
+    }
+
+    /// Returns a description of the named service if it is advertised by this node.
+    ///
+    /// Relative names are resolved the same way as in [NodeHandle::advertise_service].
+    pub async fn service_info(&self, service_name: &str) -> Result<Option<ServiceInfo>, NodeError> {
+        let service_name = Name::new(self.resolve_name(service_name))?.to_string();
+        Ok(self
+            .services()
+            .await?
+            .into_iter()
+            .find(|info| info.name == service_name))
+    }
+
+    /// Reads a parameter from the master's parameter server, returning `None` if it isn't set.
+    ///
+    /// Relative names are placed in this handle's namespace, then resolved by the master like topic names,
+    /// "~gain" is a private parameter of this node. See [NodeHandle::cached_params] for reading parameters often.
+    pub async fn get_param<T: serde::de::DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, NodeError> {
+        let value = self
+            .master_client()
+            .await?
+            .get_param(self.resolve_name(key))
+            .await?;
+        value
+            .map(serde_xmlrpc::from_value)
+            .transpose()
+            .map_err(|e| RosMasterError::from(e).into())
+    }
+
+    /// Sets a parameter on the master's parameter server, names are resolved as in [NodeHandle::get_param]
+    pub async fn set_param<T: serde::Serialize>(
+        &self,
+        key: &str,
+        value: &T,
+    ) -> Result<(), NodeError> {
+        Ok(self
+            .master_client()
+            .await?
+            .set_param(self.resolve_name(key), value)
+            .await?)
+    }
+
+    /// Returns true if the parameter is set, names are resolved as in [NodeHandle::get_param]
+    pub async fn has_param(&self, key: &str) -> Result<bool, NodeError> {
+        Ok(self
+            .master_client()
+            .await?
+            .has_param(self.resolve_name(key))
+            .await?)
+    }
+
+    /// Removes a parameter, names are resolved as in [NodeHandle::get_param]. Fails if it wasn't set.
+    pub async fn delete_param(&self, key: &str) -> Result<(), NodeError> {
+        Ok(self
+            .master_client()
+            .await?
+            .delete_param(self.resolve_name(key))
+            .await?)
+    }
+
+    /// Returns a cache of parameter reads which is kept up to date by the master, see [CachedParams].
+    ///
+    /// Relative names read through the cache are placed in this handle's namespace.
+    pub async fn cached_params(&self) -> Result<CachedParams, NodeError> {
+        CachedParams::new(self.weak_clone()).await
+    }
+
+    /// Declares a typed parameter, returning a handle whose value follows changes made on the parameter server.
+    ///
+    /// If the parameter isn't set it is set to `default`. Otherwise its current value must deserialize as `T` and
+    /// pass `validator`, or [NodeError::InvalidParam] is returned. Later changes that fail either check are ignored.
+    /// Names are resolved as in [NodeHandle::get_param].
+    /// ```no_run
+    /// # async fn example(nh: roslibrust_ros1::NodeHandle) -> Result<(), roslibrust_ros1::NodeError> {
+    /// let gain = nh
+    ///     .declare_param("~gain", 1.0, |gain: &f64| {
+    ///         (*gain > 0.0).then_some(()).ok_or("must be positive".to_string())
+    ///     })
+    ///     .await?;
+    /// // Always the latest valid value
+    /// let current = gain.get();
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn declare_param<T>(
+        &self,
+        key: &str,
+        default: T,
+        validator: impl Fn(&T) -> Result<(), String> + Send + Sync + 'static,
+    ) -> Result<DeclaredParam<T>, NodeError>
+    where
+        T: serde::Serialize + serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+    {
+        DeclaredParam::new(self.weak_clone(), key, default, Arc::new(validator)).await
+    }
+
+    /// Reads every parameter under `namespace` into a config struct, e.g. "~" for this node's private parameters.
+    ///
+    /// Nested structs are read from nested namespaces. If a parameter is missing, or has the wrong type, the
+    /// returned [NodeError::InvalidParam] names the field, fields with `#[serde(default)]` may be left unset.
+    /// ```no_run
+    /// #[derive(serde::Deserialize)]
+    /// struct Config {
+    ///     gain: f64,
+    ///     #[serde(default)]
+    ///     verbose: bool,
+    /// }
+    /// # async fn example(nh: roslibrust_ros1::NodeHandle) -> Result<(), roslibrust_ros1::NodeError> {
+    /// let config: Config = nh.load_params("~").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn load_params<C: serde::de::DeserializeOwned>(
+        &self,
+        namespace: &str,
+    ) -> Result<C, NodeError> {
+        let client = self.master_client().await?;
+        param::load(&client, &param::resolve_key(self, &client, namespace)?).await
+    }
+
+    /// Like [NodeHandle::load_params], but keeps reloading the config whenever a parameter under `namespace`
+    /// changes, see [ReloadingParams].
+    pub async fn load_params_live<C>(
+        &self,
+        namespace: &str,
+    ) -> Result<ReloadingParams<C>, NodeError>
+    where
+        C: serde::de::DeserializeOwned + Send + Sync + 'static,
+    {
+        ReloadingParams::new(self.weak_clone(), namespace).await
+    }
+
+    /// Adds a custom method to this node's xmlrpc server (ROS's "slave API").
+    ///
+    /// When another process calls `method` on this node, `handler` is invoked with a handle to this node and the
+    /// call's arguments. Returning `Ok(value)` responds with ROS's standard `[1, "", value]` triple, while returning
+    /// `Err(msg)` responds with `[0, msg, 0]`. This allows custom node control protocols, or compatibility shims
+    /// such as nodelet style load / unload commands, to be built on top of a node.
+    ///
+    /// Fails if `method` is part of ROS's API or already has a handler registered.
+    pub async fn register_xmlrpc_handler<F, Fut>(
+        &self,
+        method: &str,
+        handler: F,
+    ) -> Result<(), NodeError>
+    where
+        F: Fn(NodeHandle, Vec<XmlRpcValue>) -> Fut + Send + Sync + 'static,
+        Fut: Future<Output = Result<XmlRpcValue, String>> + Send + 'static,
+    {
+        let handler: XmlRpcHandler = Arc::new(move |nh: NodeHandle, args: Vec<XmlRpcValue>| {
+            Box::pin(handler(nh, args)) as XmlRpcHandlerFuture
+        });
+        self.inner.register_xmlrpc_handler(method, handler).await
+    }
+
+    /// Removes a method previously added with [NodeHandle::register_xmlrpc_handler]
+    pub fn unregister_xmlrpc_handler(&self, method: &str) -> Result<(), NodeError> {
+        self.inner.unregister_xmlrpc_handler(method)
+    }
+
+    /// Debugging aid which records the raw TCPROS traffic of this node to a file, see [WireRecorder].
+    ///
+    /// Only affects publishers and subscribers for topics that are first advertised or subscribed after this call.
+    /// Passing None stops recording for topics created afterwards.
+    pub fn set_wire_recorder(&self, recorder: Option<WireRecorder>) -> Result<(), NodeError> {
+        self.inner.set_wire_recorder(recorder)
+    }
+
+    /// Sets the transports subscribers offer to publishers, in order of preference. Defaults to only TCPROS.
+    ///
+    /// Each publisher picks one of the offered transports, if a connection can't be established over the one it
+    /// picked that transport is dropped and the publisher is asked again with the rest.
+    /// Only affects topics that are first subscribed to after this call, an empty list is treated as only TCPROS.
+    /// ```ignore
+    /// nh.set_transport_preference(vec![Transport::Udpros, Transport::Tcpros])?;
+    /// ```
+    pub fn set_transport_preference(&self, transports: Vec<Transport>) -> Result<(), NodeError> {
+        let transports = if transports.is_empty() {
+            vec![Transport::Tcpros]
+        } else {
+            transports
+        };
+        self.inner.set_transport_preference(transports)
+    }
+
+    /// Sets the largest message, in bytes, that subscribers and services accept from other nodes.
+    /// Defaults to [crate::DEFAULT_MAX_MESSAGE_SIZE].
+    ///
+    /// A connection whose peer declares a larger message is closed without allocating for it and reported as a
+    /// failed [crate::ConnectionEvent], service calls fail with [roslibrust_common::Error::MessageTooLarge].
+    /// Only affects topics first subscribed to, and services advertised or connected to, after this call.
+    /// The limit on messages sent is set per topic with [AdvertiseOptions::max_message_size].
+    pub fn set_max_message_size(&self, max_message_size: usize) -> Result<(), NodeError> {
+        self.inner.set_max_message_size(max_message_size)
+    }
+
     /// Create a new publisher any arbitrary message type.
     ///
     /// This function is intended to be used when a message definition was not available at compile time,
@@ -81,11 +451,45 @@ impl NodeHandle {
         queue_size: usize,
         latching: bool,
     ) -> Result<PublisherAny, NodeError> {
-        let (sender, shutdown) = self
+        let topic_name = &self.resolve_name(topic_name);
+        let handle = self
             .inner
-            .register_publisher_any(topic_name, topic_type, msg_definition, queue_size, latching)
+            .register_publisher_any(
+                topic_name,
+                topic_type,
+                msg_definition,
+                AdvertiseOptions::new(queue_size).latching(latching),
+            )
+            .await?;
+        Ok(PublisherAny::new(topic_name, handle))
+    }
+
+    /// Create a new publisher for a message type looked up by name in the given registry.
+    ///
+    /// Behaves like [NodeHandle::advertise_any] using the definition from the registry,
+    /// and returns a publisher which accepts messages as JSON.
+    /// Fails with [NodeError::UnknownType] if the type is not registered.
+    pub async fn advertise_by_type_name(
+        &self,
+        registry: &TypeRegistry,
+        type_name: &str,
+        topic_name: &str,
+        queue_size: usize,
+        latching: bool,
+    ) -> Result<DynamicPublisher, NodeError> {
+        let message_type = *registry
+            .get(type_name)
+            .ok_or_else(|| NodeError::UnknownType(type_name.to_owned()))?;
+        let publisher = self
+            .advertise_any(
+                topic_name,
+                message_type.type_name,
+                message_type.definition,
+                queue_size,
+                latching,
+            )
             .await?;
-        Ok(PublisherAny::new(topic_name, sender, shutdown))
+        Ok(DynamicPublisher::new(publisher, message_type))
     }
 
     /// Create a new publisher for the given type.
@@ -101,11 +505,78 @@ impl NodeHandle {
         queue_size: usize,
         latching: bool,
     ) -> Result<Publisher<T>, NodeError> {
-        let (sender, shutdown) = self
+        self.advertise_with_options(
+            topic_name,
+            AdvertiseOptions::new(queue_size).latching(latching),
+        )
+        .await
+    }
+
+    /// Create a new publisher for the given type, configured by [AdvertiseOptions].
+    ///
+    /// As with [NodeHandle::advertise] the FIRST call for a topic establishes the options used for that topic.
+    /// Returns [NodeError::MissingHeader] if header filling options are requested for a message type whose first field is not a Header.
+    pub async fn advertise_with_options<T: roslibrust_common::RosMessageType>(
+        &self,
+        topic_name: &str,
+        options: AdvertiseOptions,
+    ) -> Result<Publisher<T>, NodeError> {
+        let topic_name = &self.resolve_name(topic_name);
+        if (options.fill_seq || options.stamp_on_send) && !starts_with_header(T::DEFINITION) {
+            log::error!(
+                "Cannot fill header fields for {topic_name}, {} does not start with a Header",
+                T::ROS_TYPE_NAME
+            );
+            return Err(NodeError::MissingHeader {
+                msg_type: T::ROS_TYPE_NAME.to_owned(),
+            });
+        }
+        if options.strict_type_check {
+            self.check_topic_type(topic_name, T::ROS_TYPE_NAME).await?;
+        }
+        let handle = self
             .inner
-            .register_publisher::<T>(topic_name, queue_size, latching)
+            .register_publisher::<T>(topic_name, options)
             .await?;
-        Ok(Publisher::new(topic_name, sender, shutdown))
+        Ok(Publisher::new(topic_name, handle))
+    }
+
+    /// Fails with [NodeError::TopicTypeMismatch] if the master already has `topic_name` with a type other than
+    /// `topic_type`, relative names are resolved against the node's name as the master reports global names
+    async fn check_topic_type(&self, topic_name: &str, topic_type: &str) -> Result<(), NodeError> {
+        let client = self.master_client().await?;
+        let node_name = Name::new(client.caller_id())?;
+        let topic_name = Name::new(topic_name)?
+            .resolve_to_global(&node_name)
+            .to_string();
+        let topic_types = client.get_topic_types().await?;
+        match topic_types
+            .into_iter()
+            .find(|(name, _)| *name == topic_name)
+        {
+            Some((_, existing)) if existing != topic_type => {
+                log::error!(
+                    "Refusing to advertise {topic_name} as {topic_type}, it already exists as {existing}"
+                );
+                Err(NodeError::TopicTypeMismatch {
+                    topic: topic_name.to_string(),
+                    existing,
+                    requested: topic_type.to_string(),
+                })
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Starts building a publisher for the given type, see [PublisherBuilder].
+    ///
+    /// Offers the options of [NodeHandle::advertise_with_options] along with hooks applied to each message
+    /// as it is published.
+    pub fn advertise_builder<T: roslibrust_common::RosMessageType>(
+        &self,
+        topic_name: &str,
+    ) -> PublisherBuilder<T> {
+        PublisherBuilder::new(self.clone(), topic_name)
     }
 
     /// Subscribe to a topic as a raw byte stream with no automatic deserialization.
@@ -122,11 +593,13 @@ impl NodeHandle {
         topic_name: &str,
         queue_size: usize,
     ) -> Result<SubscriberAny, NodeError> {
+        let topic_name = &self.resolve_name(topic_name);
         let receiver = self
             .inner
             .register_subscriber::<roslibrust_common::ShapeShifter>(topic_name, queue_size)
             .await?;
-        Ok(SubscriberAny::new(receiver))
+        // Don't clone self or the subscriber would keep the node alive
+        Ok(SubscriberAny::new(receiver, topic_name, self.weak_clone()))
     }
 
     /// Subscribe to a topic with automatic deserialization to the given type.
@@ -140,18 +613,62 @@ impl NodeHandle {
         topic_name: &str,
         queue_size: usize,
     ) -> Result<Subscriber<T>, NodeError> {
+        let topic_name = &self.resolve_name(topic_name);
         let receiver = self
             .inner
             .register_subscriber::<T>(topic_name, queue_size)
             .await?;
-        Ok(Subscriber::new(receiver))
+        Ok(Subscriber::new(receiver, topic_name, self.weak_clone()))
+    }
+
+    /// Starts building a subscriber for the given type, see [SubscriberBuilder].
+    ///
+    /// Offers waiting for the first message, skipping the md5sum check, and choosing transports per topic.
+    pub fn subscribe_builder<T: roslibrust_common::RosMessageType>(
+        &self,
+        topic_name: &str,
+    ) -> SubscriberBuilder<T> {
+        SubscriberBuilder::new(self.clone(), topic_name)
+    }
+
+    /// Subscribe to a topic whose publishers may be sending either of two message types, e.g. `(v1::Pose, v2::Pose)`.
+    ///
+    /// Intended for fleets running mixed message versions during a migration.
+    /// Publishers of any md5sum are connected to, and each message is decoded as the type matching the md5sum its
+    /// publisher gave, trying the first type and then the second if it matches neither.
+    ///
+    /// Subscriptions are shared by all subscribers on a topic within a node, and the first one decides which
+    /// publishers are accepted. Create this subscriber before any [NodeHandle::subscribe] to the same topic,
+    /// otherwise publishers of the second type will be refused.
+    pub async fn subscribe_multi<M: MessageAlternatives>(
+        &self,
+        topic_name: &str,
+        queue_size: usize,
+    ) -> Result<SubscriberMulti<M>, NodeError> {
+        let topic_name = &self.resolve_name(topic_name);
+        let receiver = self
+            .inner
+            .register_subscriber_with_type(
+                topic_name,
+                M::ROS_TYPE_NAME,
+                M::DEFINITION,
+                "*",
+                queue_size,
+                None,
+            )
+            .await?;
+        Ok(SubscriberMulti::new(
+            receiver,
+            topic_name,
+            self.weak_clone(),
+        ))
     }
 
     pub async fn service_client<T: roslibrust_common::RosServiceType>(
         &self,
         service_name: &str,
     ) -> Result<ServiceClient<T>, NodeError> {
-        let service_name = Name::new(service_name)?;
+        let service_name = Name::new(self.resolve_name(service_name))?;
         let sender = self
             .inner
             .register_service_client::<T>(&service_name)
@@ -163,23 +680,79 @@ impl NodeHandle {
         &self,
         service_name: &str,
         server: F,
-    ) -> Result<ServiceServer, NodeError>
+    ) -> Result<ServiceServerHandle, NodeError>
     where
         T: roslibrust_common::RosServiceType,
         F: ServiceFn<T>,
     {
-        let service_name = Name::new(service_name)?;
-        self.inner
+        let service_name = Name::new(self.resolve_name(service_name))?;
+        let stats = self
+            .inner
             .register_service_server::<T, F>(&service_name, server)
             .await?;
         // Super important. Don't clone self or we create a STRONG NodeHandle that keeps the node alive
-        Ok(ServiceServer::new(service_name, self.weak_clone()))
+        Ok(ServiceServerHandle::new(
+            service_name,
+            self.weak_clone(),
+            stats,
+        ))
+    }
+
+    /// Called by [ServiceServerHandle::stop], un-advertises the service and waits for rosmaster to be informed
+    pub(crate) async fn stop_service_server(&self, service_name: &str) -> Result<(), NodeError> {
+        self.inner.unadvertise_service(service_name).await
+    }
+
+    /// Called by [SubscriberBuilder::build], subscribes accepting publishers giving `md5sum` and, if this creates the
+    /// subscription, offering them `transports` instead of the node's preference
+    pub(crate) async fn subscribe_with_transports<T: roslibrust_common::RosMessageType>(
+        &self,
+        topic_name: &str,
+        md5sum: &str,
+        queue_size: usize,
+        transports: Option<Vec<Transport>>,
+    ) -> Result<Subscriber<T>, NodeError> {
+        let topic_name = &self.resolve_name(topic_name);
+        let receiver = self
+            .inner
+            .register_subscriber_with_type(
+                topic_name,
+                T::ROS_TYPE_NAME,
+                T::DEFINITION,
+                md5sum,
+                queue_size,
+                transports,
+            )
+            .await?;
+        Ok(Subscriber::new(receiver, topic_name, self.weak_clone()))
+    }
+
+    /// Used by [CachedParams] to learn when parameters change
+    pub(crate) async fn param_updates(
+        &self,
+    ) -> Result<tokio::sync::broadcast::Receiver<ParamUpdate>, NodeError> {
+        self.inner.param_updates().await
+    }
+
+    /// Asks the master for updates to the global parameter `key`, see [NodeHandle::param_updates]
+    pub(crate) async fn subscribe_param(&self, key: &str) -> Result<(), NodeError> {
+        self.inner.subscribe_param(key).await
+    }
+
+    /// Releases a subscription made with [NodeHandle::subscribe_param]
+    pub(crate) fn unsubscribe_param(&self, key: &str) -> Result<(), NodeError> {
+        self.inner.unsubscribe_param(key)
+    }
+
+    /// Called by [Subscriber::unsubscribe] once it has released its receiver
+    pub(crate) async fn unregister_subscriber(&self, topic_name: &str) -> Result<(), NodeError> {
+        self.inner.unregister_subscriber(topic_name).await
     }
 
     // TODO Major: This should probably be moved to NodeServerHandle?
     /// Not intended to be called manually
     /// Stops hosting the specified server.
-    /// This is automatically called when dropping the ServiceServer returned by [advertise_service]
+    /// This is automatically called when dropping the ServiceServerHandle returned by [advertise_service]
     pub(crate) fn unadvertise_service_server(&self, service_name: &str) -> Result<(), NodeError> {
         // TODO should we be using Name as the type of service_name here?
         // I don't love Name's API at the moment
@@ -197,3 +770,57 @@ impl NodeHandle {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn detached_handle() -> NodeHandle {
+        let (node_server_sender, _) = tokio::sync::mpsc::unbounded_channel();
+        NodeHandle::from_server_handle(NodeServerHandle {
+            node_server_sender,
+            _node_task: None,
+        })
+    }
+
+    #[test]
+    fn scoped_handles_resolve_relative_names() {
+        let nh = detached_handle();
+        assert_eq!(nh.resolve_name("chatter"), "chatter");
+
+        let robot = nh.scoped("/robot/").unwrap();
+        assert_eq!(robot.namespace(), Some("/robot"));
+        assert_eq!(robot.resolve_name("chatter"), "/robot/chatter");
+        assert_eq!(robot.resolve_name("/chatter"), "/chatter");
+        assert_eq!(robot.resolve_name("~chatter"), "~chatter");
+
+        let arm = robot.scoped("arm").unwrap();
+        assert_eq!(arm.resolve_name("joint_states"), "/robot/arm/joint_states");
+        assert_eq!(arm.scoped("/other").unwrap().namespace(), Some("/other"));
+        assert_eq!(arm.scoped("/").unwrap().resolve_name("chatter"), "/chatter");
+
+        assert!(nh.scoped("_invalid").is_err());
+        assert!(robot.scoped("").is_err());
+        assert!(robot.scoped("  ").is_err());
+        assert!(nh.scoped("").is_err());
+    }
+
+    #[test]
+    fn remappings_apply_after_namespace() {
+        let mut nh = detached_handle();
+        let raw = HashMap::from([
+            ("in".to_string(), "/camera/image_raw".to_string()),
+            ("/robot/out".to_string(), "/processed".to_string()),
+        ]);
+        nh.remappings = Some(Arc::new(
+            Remappings::new(Name::new("/my_node").unwrap(), raw).unwrap(),
+        ));
+        assert_eq!(nh.resolve_name("in"), "/camera/image_raw");
+        assert_eq!(nh.resolve_name("/in"), "/camera/image_raw");
+        assert_eq!(nh.resolve_name("out"), "out");
+
+        let robot = nh.scoped("/robot").unwrap();
+        assert_eq!(robot.resolve_name("out"), "/processed");
+        assert_eq!(robot.resolve_name("in"), "/robot/in");
+    }
+}
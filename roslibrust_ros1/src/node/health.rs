@@ -0,0 +1,286 @@
+use super::{NodeError, NodeServerHandle};
+use abort_on_drop::ChildTask;
+use hyper::{Body, Method, Request, Response, StatusCode};
+use log::*;
+use roslibrust_common::RosMessageType;
+use std::{
+    convert::Infallible,
+    net::SocketAddr,
+    sync::{
+        atomic::{AtomicU64, AtomicUsize, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant},
+};
+
+/// Activity on one of the node's topics as reported by [crate::NodeHandle::stats]
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct TopicStats {
+    pub topic: String,
+    pub topic_type: String,
+    /// Number of subscribers connected to a publication, or publishers a subscription is connected to
+    pub connections: usize,
+    /// Seconds since a message was last published or received, None if there hasn't been one
+    pub last_message_age_secs: Option<f64>,
+    /// Messages published or received since the topic was advertised or subscribed to
+    pub message_count: u64,
+    /// Total serialized size of those messages
+    pub byte_count: u64,
+    /// Recent messages per second, falling towards zero once messages stop. None until two messages have been seen
+    pub rate_hz: Option<f64>,
+    /// For publications, the messages the slowest subscriber's connection has yet to write. None for subscriptions
+    pub queue_depth: Option<usize>,
+    /// For publications, messages skipped by subscriber connections which fell behind or dropped by the
+    /// [bandwidth](crate::AdvertiseOptions::bandwidth_limit) or [rate](crate::AdvertiseOptions::rate_limit) limit.
+    /// For subscriptions, messages skipped by intra-process connections which fell behind, a subscriber falling
+    /// behind its own queue is reported to it as a lagged error instead.
+    pub dropped_count: u64,
+}
+
+/// Requests handled by one of the node's services as reported by [crate::NodeHandle::stats]
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct ServiceCallStats {
+    pub service: String,
+    pub service_type: String,
+    /// Number of requests handled, including those which returned an error
+    pub request_count: u64,
+    /// Number of requests for which the service returned an error or panicked
+    pub error_count: u64,
+    /// Average seconds taken to handle a request, None until one has been handled
+    pub average_latency_secs: Option<f64>,
+}
+
+/// A snapshot of a node's topics and services, served as JSON by [crate::NodeHandle::serve_health]
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct NodeStats {
+    pub node_name: String,
+    pub publications: Vec<TopicStats>,
+    pub subscriptions: Vec<TopicStats>,
+    pub services: Vec<ServiceCallStats>,
+}
+
+// Weight given to each new interval between messages in the average used for TopicStats::rate_hz
+const RATE_SMOOTHING: f64 = 0.1;
+
+// Arrival times used to estimate a topic's rate
+#[derive(Debug, Default)]
+struct MessageTiming {
+    last: Option<Instant>,
+    // Exponentially weighted average of the seconds between messages
+    mean_interval: Option<f64>,
+}
+
+// Tracks activity for a publication or subscription, shared with the tasks handling its connections
+#[derive(Debug, Default)]
+pub(crate) struct TopicActivity {
+    connections: AtomicUsize,
+    messages: AtomicU64,
+    bytes: AtomicU64,
+    dropped: AtomicU64,
+    timing: Mutex<MessageTiming>,
+}
+
+impl TopicActivity {
+    /// Counts a message of `len` serialized bytes
+    pub(crate) fn message(&self, len: usize) {
+        self.messages.fetch_add(1, Ordering::Relaxed);
+        self.bytes.fetch_add(len as u64, Ordering::Relaxed);
+        let now = Instant::now();
+        let mut timing = self.timing.lock().unwrap();
+        if let Some(last) = timing.last {
+            let interval = now.duration_since(last).as_secs_f64();
+            timing.mean_interval = Some(match timing.mean_interval {
+                Some(mean) => mean + RATE_SMOOTHING * (interval - mean),
+                None => interval,
+            });
+        }
+        timing.last = Some(now);
+    }
+
+    pub(crate) fn dropped(&self, count: u64) {
+        self.dropped.fetch_add(count, Ordering::Relaxed);
+    }
+
+    /// Counts a connection until the returned guard is dropped
+    pub(crate) fn connected(self: &Arc<Self>) -> ConnectionGuard {
+        self.connections.fetch_add(1, Ordering::Relaxed);
+        ConnectionGuard(self.clone())
+    }
+
+    pub(crate) fn stats(
+        &self,
+        topic: &str,
+        topic_type: &str,
+        queue_depth: Option<usize>,
+    ) -> TopicStats {
+        let timing = self.timing.lock().unwrap();
+        let last_message_age_secs = timing.last.map(|last| last.elapsed().as_secs_f64());
+        // Once messages stop the time since the last one outgrows the average interval, bringing the rate down
+        let rate_hz = timing
+            .mean_interval
+            .zip(last_message_age_secs)
+            .map(|(mean, age)| mean.max(age))
+            .filter(|interval| *interval > 0.0)
+            .map(|interval| 1.0 / interval);
+        TopicStats {
+            topic: topic.to_owned(),
+            topic_type: topic_type.to_owned(),
+            connections: self.connections.load(Ordering::Relaxed),
+            last_message_age_secs,
+            message_count: self.messages.load(Ordering::Relaxed),
+            byte_count: self.bytes.load(Ordering::Relaxed),
+            rate_hz,
+            queue_depth,
+            dropped_count: self.dropped.load(Ordering::Relaxed),
+        }
+    }
+}
+
+pub(crate) struct ConnectionGuard(Arc<TopicActivity>);
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.connections.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// The HTTP server started by [crate::NodeHandle::serve_health], stops when dropped.
+///
+/// Serves:
+/// - `GET /livez`: 200 while the node is running, 503 once it has shut down
+/// - `GET /readyz`: 200 while the node is running and the ROS master can be reached, 503 otherwise
+/// - `GET /stats`: the node's [NodeStats] as JSON
+pub struct HealthServer {
+    local_addr: SocketAddr,
+    _task: ChildTask<()>,
+}
+
+impl HealthServer {
+    /// Address the server is listening on, useful when it was bound to port 0
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+}
+
+/// Equivalent of `std_msgs/String`, carrying [NodeStats] as JSON on the statistics topic
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+struct StatsMessage {
+    data: String,
+}
+
+impl RosMessageType for StatsMessage {
+    const ROS_TYPE_NAME: &'static str = "std_msgs/String";
+    const MD5SUM: &'static str = "992ce8a1687cec8c8bd883ec73ca41d1";
+    const DEFINITION: &'static str = "string data";
+}
+
+/// Publishes the node's [NodeStats] periodically, started by [crate::NodeHandle::publish_stats]. Stops when dropped.
+pub struct StatsReporter {
+    _task: ChildTask<()>,
+}
+
+/// Starts publishing stats for the node every `period`, `node` should be a handle that doesn't keep the node alive
+pub(crate) async fn report(
+    node: crate::NodeHandle,
+    period: Duration,
+) -> Result<StatsReporter, NodeError> {
+    let node_name = node.stats().await?.node_name;
+    let publisher = node
+        .advertise::<StatsMessage>(&format!("{node_name}/statistics"), 1, true)
+        .await?;
+    let task = tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        loop {
+            interval.tick().await;
+            // Fails once the node has shut down
+            let Ok(stats) = node.stats().await else {
+                break;
+            };
+            let data = match serde_json::to_string(&stats) {
+                Ok(data) => data,
+                Err(e) => {
+                    error!("Failed to serialize node stats: {e}");
+                    continue;
+                }
+            };
+            if let Err(e) = publisher.publish(&StatsMessage { data }).await {
+                warn!("Failed to publish stats for {node_name}: {e}");
+            }
+        }
+    });
+    Ok(StatsReporter { _task: task.into() })
+}
+
+/// Starts serving health checks for the node, which should be a handle that doesn't keep the node alive
+pub(crate) fn serve(node: NodeServerHandle, addr: SocketAddr) -> Result<HealthServer, NodeError> {
+    let listener = std::net::TcpListener::bind(addr)?;
+    listener.set_nonblocking(true)?;
+    let local_addr = listener.local_addr()?;
+    let make_svc = hyper::service::make_service_fn(move |_connection| {
+        let node = node.clone();
+        async move {
+            Ok::<_, Infallible>(hyper::service::service_fn(move |req| {
+                respond(node.clone(), req)
+            }))
+        }
+    });
+    let server = hyper::server::Server::from_tcp(listener)
+        .map_err(std::io::Error::other)?
+        .serve(make_svc);
+    let task = tokio::spawn(async {
+        if let Err(err) = server.await {
+            log::error!("Health server encountered error: {err:?}");
+        }
+    });
+    Ok(HealthServer {
+        local_addr,
+        _task: task.into(),
+    })
+}
+
+async fn respond(node: NodeServerHandle, req: Request<Body>) -> Result<Response<Body>, Infallible> {
+    if req.method() != Method::GET {
+        return Ok(status(StatusCode::METHOD_NOT_ALLOWED, "method not allowed"));
+    }
+    let alive = !node.node_server_sender.is_closed();
+    let response = match req.uri().path() {
+        "/livez" if alive => status(StatusCode::OK, "ok"),
+        "/livez" => status(StatusCode::SERVICE_UNAVAILABLE, "node has shut down"),
+        "/readyz" => {
+            let master_reachable = match node.get_master_client().await {
+                Ok(client) => client.get_uri().await.is_ok(),
+                Err(_) => false,
+            };
+            if alive && master_reachable {
+                status(StatusCode::OK, "ok")
+            } else {
+                status(StatusCode::SERVICE_UNAVAILABLE, "ROS master unreachable")
+            }
+        }
+        "/stats" => match node.get_stats().await {
+            Ok(stats) => match serde_json::to_vec(&stats) {
+                Ok(body) => Response::builder()
+                    .header("Content-Type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+                Err(e) => {
+                    error!("Failed to serialize node stats: {e}");
+                    status(
+                        StatusCode::INTERNAL_SERVER_ERROR,
+                        "failed to serialize stats",
+                    )
+                }
+            },
+            Err(_) => status(StatusCode::SERVICE_UNAVAILABLE, "node has shut down"),
+        },
+        _ => status(StatusCode::NOT_FOUND, "not found"),
+    };
+    Ok(response)
+}
+
+fn status(code: StatusCode, body: &'static str) -> Response<Body> {
+    Response::builder()
+        .status(code)
+        .body(Body::from(body))
+        .unwrap()
+}
@@ -0,0 +1,135 @@
+//! Hosting multiple ROS1 nodes within one process.
+
+use super::{actor::Node, NodeError, NodeHandle, XmlRpcServer, XmlRpcServerHandle};
+use crate::publisher::LatchedMessage;
+use bytes::Bytes;
+use std::{
+    collections::HashMap,
+    net::Ipv4Addr,
+    sync::{Arc, Mutex},
+};
+use tokio::sync::broadcast;
+
+/// A publication made by one of the nodes of a [NodeManager], which other nodes of the manager can
+/// read from directly instead of connecting via TCPROS.
+#[derive(Clone)]
+pub(crate) struct IntraProcessPublication {
+    pub(crate) node_name: String,
+    pub(crate) md5sum: String,
+    pub(crate) sender: broadcast::Sender<Bytes>,
+    // Messages published with [crate::Priority::High], read before those on sender
+    pub(crate) priority_sender: broadcast::Sender<Bytes>,
+    pub(crate) latched: LatchedMessage,
+}
+
+/// Registry of the publications of all nodes sharing a [NodeManager], keyed by topic name.
+#[derive(Clone)]
+pub(crate) struct IntraProcess {
+    // The uri of the xmlrpc server shared by all nodes of the manager, each node is served at this uri followed by
+    // its name, so publishers at such a uri are in this process
+    pub(crate) client_uri: String,
+    publications: Arc<Mutex<HashMap<String, Vec<IntraProcessPublication>>>>,
+}
+
+impl IntraProcess {
+    pub(crate) fn add_publication(&self, topic: &str, publication: IntraProcessPublication) {
+        self.publications
+            .lock()
+            .unwrap()
+            .entry(topic.to_owned())
+            .or_default()
+            .push(publication);
+    }
+
+    pub(crate) fn remove_publication(&self, topic: &str, node_name: &str) {
+        let mut publications = self.publications.lock().unwrap();
+        if let Some(list) = publications.get_mut(topic) {
+            list.retain(|publication| publication.node_name != node_name);
+            if list.is_empty() {
+                publications.remove(topic);
+            }
+        }
+    }
+
+    /// Returns the name of the node in this process served at the given xmlrpc uri, if it is one of ours
+    pub(crate) fn node_at<'a>(&self, uri: &'a str) -> Option<&'a str> {
+        uri.strip_prefix(self.client_uri.as_str())
+            .filter(|node_name| node_name.starts_with('/'))
+    }
+
+    pub(crate) fn publications(&self, topic: &str) -> Vec<IntraProcessPublication> {
+        self.publications
+            .lock()
+            .unwrap()
+            .get(topic)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// Hosts several ROS1 nodes within one process.
+///
+/// All nodes created by a manager share a single xmlrpc server, each node is served at a path of its own name.
+/// When one node of a manager subscribes to a topic published by another node of the same manager, messages are
+/// handed over in memory instead of being sent through a TCPROS connection.
+/// Nodes outside the process still see each node as a normal ROS node and connect to it as usual.
+///
+/// ```no_run
+/// # async fn example() -> Result<(), roslibrust_ros1::NodeError> {
+/// let manager = roslibrust_ros1::NodeManager::new("http://localhost:11311").await?;
+/// let driver = manager.add_node("driver").await?;
+/// let controller = manager.add_node("controller").await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct NodeManager {
+    master_uri: String,
+    hostname: String,
+    addr: Ipv4Addr,
+    xmlrpc_server: Arc<XmlRpcServerHandle>,
+    intra_process: IntraProcess,
+}
+
+impl NodeManager {
+    /// Creates the shared xmlrpc server, no nodes are registered with the master until [NodeManager::add_node] is called.
+    ///   - master_uri: Expects a fully resolved http uri for the master e.g. "http://my_host_name:11311"
+    pub async fn new(master_uri: &str) -> Result<NodeManager, NodeError> {
+        // Follow ROS rules and determine our IP and hostname
+        let (addr, hostname) = super::determine_addr(master_uri).await?;
+        let xmlrpc_server = XmlRpcServer::new(addr, Default::default())?;
+        let client_uri = format!("http://{hostname}:{}", xmlrpc_server.port());
+        Ok(NodeManager {
+            master_uri: master_uri.to_owned(),
+            hostname,
+            addr,
+            xmlrpc_server: Arc::new(xmlrpc_server),
+            intra_process: IntraProcess {
+                client_uri,
+                publications: Default::default(),
+            },
+        })
+    }
+
+    /// Creates a new node hosted by this manager.
+    /// Follows the same naming rules as [NodeHandle::new].
+    /// The node remains alive while any handle to it exists, even if the manager is dropped.
+    pub async fn add_node(&self, name: &str) -> Result<NodeHandle, NodeError> {
+        let name = super::resolve_node_name(name)?;
+        let node = Node::new_with_xmlrpc_server(
+            &self.master_uri,
+            &self.hostname,
+            &name,
+            self.addr,
+            self.xmlrpc_server.clone(),
+            Some(self.intra_process.clone()),
+        )
+        .await?;
+        Ok(NodeHandle::from_server_handle(node))
+    }
+
+    /// Returns the uri of the shared xmlrpc server
+    /// Each node of this manager reports this uri followed by its own name to the master, e.g. "http://host:1234/driver"
+    pub fn client_uri(&self) -> &str {
+        &self.intra_process.client_uri
+    }
+}
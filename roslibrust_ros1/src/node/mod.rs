@@ -4,20 +4,32 @@
 use log::*;
 use roslibrust_common::Error;
 
-use super::{names::InvalidNameError, RosMasterError};
+use super::{
+    names::{InvalidNameError, Name},
+    RosMasterError,
+};
 use std::{
     io,
     net::{IpAddr, Ipv4Addr},
+    time::Duration,
 };
 
 pub(crate) mod actor;
 mod handle;
+pub(crate) mod health;
+pub use health::{HealthServer, NodeStats, ServiceCallStats, StatsReporter, TopicStats};
+pub(crate) mod manager;
+pub(crate) mod param;
+pub(crate) mod remap;
+pub use param::{CachedParams, DeclaredParam, ReloadingParams};
 mod xmlrpc;
 use actor::*;
 use anyhow::anyhow;
 pub use handle::NodeHandle;
+pub use manager::NodeManager;
 use tokio::sync::{mpsc, oneshot};
 use xmlrpc::*;
+pub use xmlrpc::{XmlRpcError, XmlRpcHandler, XmlRpcHandlerFuture, XmlRpcValue};
 
 #[derive(Debug)]
 pub struct ProtocolParams {
@@ -26,6 +38,20 @@ pub struct ProtocolParams {
     pub port: u16,
 }
 
+/// Converts a user provided node name into a global name, all node names are interpreted as global
+/// e.g. "my_node" -> "/my_node". "~my_node" is not supported
+pub(crate) fn resolve_node_name(name: &str) -> Result<Name, NodeError> {
+    let name = if name.starts_with("/") {
+        Name::new(name)?
+    } else {
+        Name::new(format!("/{}", name))?
+    };
+
+    // Extra safety check that our name resolves now
+    let _ = Name::new("test").unwrap().resolve_to_global(&name);
+    Ok(name)
+}
+
 // TODO at the end of the day I'd like to offer a builder pattern for configuration that allow manual setting of this or "ros idiomatic" behavior - Carter
 /// Following ROS's idiomatic address rules uses ROS_HOSTNAME and ROS_IP to determine the address that server should be hosted at.
 /// Returns both the resolved IpAddress of the host (used for actually opening the socket), and the String "hostname" which should
@@ -166,6 +192,29 @@ pub enum NodeError {
     XmlRpcError(#[from] XmlRpcError),
     #[error(transparent)]
     IoError(#[from] io::Error),
+    /// Header filling was requested for a message type whose first field is not a std_msgs/Header
+    #[error("{msg_type} does not start with a std_msgs/Header")]
+    MissingHeader { msg_type: String },
+    /// A message type was looked up by name but isn't registered
+    #[error("Message type {0} is not registered")]
+    UnknownType(String),
+    /// No message arrived on a subscribed topic within the timeout given to its builder
+    #[error("No message received on {topic} within {timeout:?} of subscribing")]
+    FirstMessageTimeout { topic: String, timeout: Duration },
+    /// The operation was aborted through the [roslibrust_common::CancellationToken] passed to it
+    #[error("operation was cancelled")]
+    Cancelled,
+    /// A parameter's value couldn't be deserialized as the declared type, or was rejected by its validator
+    #[error("parameter {key} is invalid: {reason}")]
+    InvalidParam { key: String, reason: String },
+    /// A topic was advertised with [crate::AdvertiseOptions::strict_type_check] but the master already has it with
+    /// a different type
+    #[error("topic {topic} already exists with type {existing}, not {requested}")]
+    TopicTypeMismatch {
+        topic: String,
+        existing: String,
+        requested: String,
+    },
 }
 
 impl From<oneshot::error::RecvError> for NodeError {
@@ -195,6 +244,13 @@ impl From<NodeError> for Error {
             NodeError::InvalidName(e) => Error::InvalidName(e.to_string()),
             NodeError::XmlRpcError(e) => Error::SerializationError(e.to_string()),
             NodeError::IoError(e) => Error::IoError(e),
+            e @ (NodeError::MissingHeader { .. } | NodeError::UnknownType(_)) => {
+                Error::SerializationError(e.to_string())
+            }
+            e @ NodeError::FirstMessageTimeout { .. } => Error::Timeout(e.to_string()),
+            NodeError::Cancelled => Error::Cancelled,
+            e @ NodeError::InvalidParam { .. } => Error::Unexpected(anyhow!(e.to_string())),
+            e @ NodeError::TopicTypeMismatch { .. } => Error::Unexpected(anyhow!(e.to_string())),
         }
     }
 }
@@ -0,0 +1,529 @@
+//! Caching of parameter server reads, typed parameters and config structs, kept up to date through the master's
+//! paramUpdate notifications.
+
+use super::{NodeError, NodeHandle, XmlRpcValue};
+use crate::{names::Name, MasterClient, RosMasterError};
+use abort_on_drop::ChildTask;
+use arc_swap::ArcSwap;
+use log::*;
+use std::{
+    collections::{HashMap, HashSet},
+    sync::{Arc, Mutex},
+};
+use tokio::sync::{
+    broadcast::{self, error::RecvError},
+    watch,
+};
+
+/// A change to a parameter, as reported by the master calling paramUpdate on the node's xmlrpc server
+#[derive(Clone, Debug)]
+pub(crate) struct ParamUpdate {
+    /// Global name of the parameter, or of a namespace containing the parameter
+    pub(crate) key: String,
+    /// The new value, an empty map if the parameter was deleted
+    pub(crate) value: XmlRpcValue,
+}
+
+impl ParamUpdate {
+    pub(crate) fn new(key: &str, value: XmlRpcValue) -> Self {
+        // The master reports keys with a trailing '/'
+        let key = match key.trim_end_matches('/') {
+            "" => "/",
+            key => key,
+        };
+        Self {
+            key: key.to_owned(),
+            value,
+        }
+    }
+
+    // True if this update may change the value of the parameter `key`,
+    // either by setting it directly, setting a namespace containing it, or setting something within it
+    fn affects(&self, key: &str) -> bool {
+        let within = |name: &str, namespace: &str| {
+            namespace == "/"
+                || name
+                    .strip_prefix(namespace)
+                    .is_some_and(|rest| rest.starts_with('/'))
+        };
+        self.key == key || within(key, &self.key) || within(&self.key, key)
+    }
+}
+
+/// Caches parameter reads, matching the semantics of roscpp's `param::getCached`.
+///
+/// The first read of a parameter fetches it from the master and subscribes to updates for it. Later reads
+/// are answered from the cache until the master reports that the parameter changed, which makes it
+/// reasonable to read parameters in a hot loop without loading the master.
+///
+/// Created with [NodeHandle::cached_params], clones share the same cache.
+/// ```no_run
+/// # async fn example(nh: roslibrust_ros1::NodeHandle) -> Result<(), roslibrust_ros1::NodeError> {
+/// let params = nh.cached_params().await?;
+/// loop {
+///     let gain = params.get::<f64>("~gain").await?.unwrap_or(1.0);
+///     // ...
+/// #   break;
+/// }
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct CachedParams {
+    inner: Arc<CacheInner>,
+}
+
+struct CacheInner {
+    nh: NodeHandle,
+    client: MasterClient,
+    state: Arc<Mutex<CacheState>>,
+    // Held while subscribing so each parameter is only subscribed to once
+    subscribing: tokio::sync::Mutex<()>,
+    // Applies parameter updates to the cache
+    _update_task: ChildTask<()>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    // Cached values keyed by global name, None if the parameter isn't set
+    values: HashMap<String, Option<XmlRpcValue>>,
+    // Parameters subscribed to with the master, i.e. every parameter that has been read
+    subscribed: HashSet<String>,
+    // Incremented whenever values are invalidated, a fetch started before an invalidation isn't cached
+    generation: u64,
+}
+
+impl CachedParams {
+    pub(crate) async fn new(nh: NodeHandle) -> Result<Self, NodeError> {
+        let client = nh.master_client().await?;
+        let state = Arc::new(Mutex::new(CacheState::default()));
+        let updates = nh.param_updates().await?;
+        let update_task = tokio::spawn(Self::update_task(updates, state.clone()));
+        Ok(Self {
+            inner: Arc::new(CacheInner {
+                nh,
+                client,
+                state,
+                subscribing: Default::default(),
+                _update_task: update_task.into(),
+            }),
+        })
+    }
+
+    async fn update_task(
+        mut updates: broadcast::Receiver<ParamUpdate>,
+        state: Arc<Mutex<CacheState>>,
+    ) {
+        loop {
+            let update = updates.recv().await;
+            let mut state = state.lock().unwrap();
+            state.generation += 1;
+            match update {
+                Ok(update) => {
+                    trace!("Invalidating cached parameters affected by {}", update.key);
+                    state.values.retain(|key, _| !update.affects(key));
+                    // Like roscpp, an update to a parameter that was read is cached right away.
+                    // Deletions are reported as an empty map, leave those to be fetched again
+                    let deleted =
+                        matches!(&update.value, XmlRpcValue::Struct(map) if map.is_empty());
+                    if !deleted && state.subscribed.contains(&update.key) {
+                        state.values.insert(update.key, Some(update.value));
+                    }
+                }
+                Err(RecvError::Lagged(num)) => {
+                    debug!("Parameter cache missed {num} updates, clearing it");
+                    state.values.clear();
+                }
+                Err(RecvError::Closed) => break,
+            }
+        }
+    }
+
+    /// Returns the value of the parameter `key`, or `None` if it isn't set.
+    ///
+    /// Names are resolved like topic names, "~gain" is a private parameter of this node.
+    /// The value is fetched from the master the first time it is read, or after it has changed.
+    pub async fn get<T: serde::de::DeserializeOwned>(
+        &self,
+        key: &str,
+    ) -> Result<Option<T>, NodeError> {
+        self.get_value(key)
+            .await?
+            .map(serde_xmlrpc::from_value)
+            .transpose()
+            .map_err(|e| RosMasterError::from(e).into())
+    }
+
+    /// Returns true if the parameter `key` is set, using the cache like [CachedParams::get]
+    pub async fn has(&self, key: &str) -> Result<bool, NodeError> {
+        Ok(self.get_value(key).await?.is_some())
+    }
+
+    async fn get_value(&self, key: &str) -> Result<Option<XmlRpcValue>, NodeError> {
+        let key = resolve_key(&self.inner.nh, &self.inner.client, key)?;
+        if let Some(value) = self.inner.state.lock().unwrap().values.get(&key) {
+            return Ok(value.clone());
+        }
+
+        // Subscribe before fetching so no update can be missed between the two
+        {
+            let _subscribing = self.inner.subscribing.lock().await;
+            if !self.inner.state.lock().unwrap().subscribed.contains(&key) {
+                self.inner.nh.subscribe_param(&key).await?;
+                self.inner
+                    .state
+                    .lock()
+                    .unwrap()
+                    .subscribed
+                    .insert(key.clone());
+            }
+        }
+        let generation = self.inner.state.lock().unwrap().generation;
+        let value = self.inner.client.get_param(&key).await?;
+        let mut state = self.inner.state.lock().unwrap();
+        if state.generation == generation {
+            state.values.insert(key, value.clone());
+        }
+        Ok(value)
+    }
+}
+
+impl Drop for CacheInner {
+    fn drop(&mut self) {
+        for key in self.state.lock().unwrap().subscribed.drain() {
+            // Only fails if the node has already shut down, which unsubscribes everything
+            let _ = self.nh.unsubscribe_param(&key);
+        }
+    }
+}
+
+/// Checks a new value of a [DeclaredParam], returning why it was rejected
+pub(crate) type ParamValidator<T> = Arc<dyn Fn(&T) -> Result<(), String> + Send + Sync>;
+
+/// A typed parameter whose value is kept up to date with the parameter server, see [NodeHandle::declare_param].
+///
+/// Values set on the parameter server which can't be deserialized as `T`, or which the validator rejects, are
+/// ignored with a warning and the previous value is kept. Clones share the same subscription to the parameter.
+pub struct DeclaredParam<T> {
+    value: watch::Receiver<T>,
+    inner: Arc<DeclaredInner<T>>,
+}
+
+impl<T> Clone for DeclaredParam<T> {
+    fn clone(&self) -> Self {
+        Self {
+            value: self.value.clone(),
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+struct DeclaredInner<T> {
+    subscription: ParamSubscription,
+    client: MasterClient,
+    validator: ParamValidator<T>,
+    sender: watch::Sender<T>,
+    // Applies parameter updates to the value
+    _update_task: ChildTask<()>,
+}
+
+impl<T> DeclaredParam<T>
+where
+    T: serde::Serialize + serde::de::DeserializeOwned + Clone + Send + Sync + 'static,
+{
+    pub(crate) async fn new(
+        nh: NodeHandle,
+        key: &str,
+        default: T,
+        validator: ParamValidator<T>,
+    ) -> Result<Self, NodeError> {
+        let client = nh.master_client().await?;
+        let key = resolve_key(&nh, &client, key)?;
+
+        // Subscribe before reading the current value so no update can be missed between the two
+        let updates = nh.param_updates().await?;
+        let subscription = ParamSubscription::new(nh, key.clone()).await?;
+        let initial = match client.get_param(&key).await? {
+            Some(value) => Self::decode(&key, value, &validator)?,
+            None => {
+                debug!("Parameter {key} is not set, setting it to its default");
+                client.set_param(&key, &default).await?;
+                default
+            }
+        };
+
+        let (sender, value) = watch::channel(initial);
+        let update_task = tokio::spawn(Self::update_task(
+            updates,
+            key.clone(),
+            client.clone(),
+            validator.clone(),
+            sender.clone(),
+        ));
+        Ok(Self {
+            value,
+            inner: Arc::new(DeclaredInner {
+                subscription,
+                client,
+                validator,
+                sender,
+                _update_task: update_task.into(),
+            }),
+        })
+    }
+
+    // Deserializes and validates a value from the parameter server
+    fn decode(
+        key: &str,
+        value: XmlRpcValue,
+        validator: &ParamValidator<T>,
+    ) -> Result<T, NodeError> {
+        let value = serde_xmlrpc::from_value(value).map_err(|e| NodeError::InvalidParam {
+            key: key.to_owned(),
+            reason: e.to_string(),
+        })?;
+        validator(&value).map_err(|reason| NodeError::InvalidParam {
+            key: key.to_owned(),
+            reason,
+        })?;
+        Ok(value)
+    }
+
+    async fn update_task(
+        mut updates: broadcast::Receiver<ParamUpdate>,
+        key: String,
+        client: MasterClient,
+        validator: ParamValidator<T>,
+        sender: watch::Sender<T>,
+    ) {
+        loop {
+            let value = match updates.recv().await {
+                Ok(update) if update.key == key => Some(update.value),
+                // A namespace containing the parameter, or something within it, changed
+                Ok(update) if update.affects(&key) => None,
+                Ok(_) => continue,
+                Err(RecvError::Lagged(num)) => {
+                    debug!("Parameter {key} missed {num} updates, fetching it again");
+                    None
+                }
+                Err(RecvError::Closed) => break,
+            };
+            let value = match value {
+                Some(value) => Some(value),
+                None => match client.get_param(&key).await {
+                    Ok(value) => value,
+                    Err(e) => {
+                        warn!("Failed to fetch updated value of parameter {key}: {e}");
+                        continue;
+                    }
+                },
+            };
+            let deleted = match &value {
+                None => true,
+                Some(XmlRpcValue::Struct(map)) => map.is_empty(),
+                Some(_) => false,
+            };
+            match value {
+                Some(value) if !deleted => match Self::decode(&key, value, &validator) {
+                    Ok(value) => {
+                        sender.send_replace(value);
+                    }
+                    Err(e) => warn!("Ignoring update to parameter {key}: {e}"),
+                },
+                _ => warn!("Parameter {key} was deleted, keeping its last value"),
+            }
+        }
+    }
+
+    /// Returns the current value of the parameter
+    pub fn get(&self) -> T {
+        self.value.borrow().clone()
+    }
+
+    /// Returns the global name of the parameter
+    pub fn key(&self) -> &str {
+        &self.inner.subscription.key
+    }
+
+    /// Waits until the parameter changes to a new valid value, then returns it
+    pub async fn changed(&mut self) -> Result<T, NodeError> {
+        self.value
+            .changed()
+            .await
+            .map_err(|_| NodeError::ChannelClosedError)?;
+        Ok(self.value.borrow_and_update().clone())
+    }
+
+    /// Validates `value` and sets it on the parameter server.
+    ///
+    /// Returns [NodeError::InvalidParam] without changing anything if the validator rejects it.
+    pub async fn set(&self, value: T) -> Result<(), NodeError> {
+        (self.inner.validator)(&value).map_err(|reason| NodeError::InvalidParam {
+            key: self.key().to_owned(),
+            reason,
+        })?;
+        self.inner.client.set_param(self.key(), &value).await?;
+        self.inner.sender.send_replace(value);
+        Ok(())
+    }
+}
+
+/// Reads the parameters under `namespace` into `C`, see [NodeHandle::load_params]
+pub(crate) async fn load<C: serde::de::DeserializeOwned>(
+    client: &MasterClient,
+    namespace: &str,
+) -> Result<C, NodeError> {
+    // Missing parameters are reported by deserialize as missing fields, unless they have defaults
+    let value = client
+        .get_param(namespace)
+        .await?
+        .unwrap_or_else(|| XmlRpcValue::Struct(Default::default()));
+    serde_xmlrpc::from_value(value).map_err(|e| NodeError::InvalidParam {
+        key: namespace.to_owned(),
+        reason: e.to_string(),
+    })
+}
+
+/// A config struct loaded from the parameter server which is reloaded whenever its parameters change,
+/// see [NodeHandle::load_params_live].
+///
+/// A reload that fails to deserialize is ignored with a warning and the previous config is kept.
+/// The config is held in an [ArcSwap] so it can be read from anywhere cheaply, and without blocking reloads.
+pub struct ReloadingParams<C> {
+    config: Arc<ArcSwap<C>>,
+    _inner: Arc<ReloadInner>,
+}
+
+impl<C> Clone for ReloadingParams<C> {
+    fn clone(&self) -> Self {
+        Self {
+            config: self.config.clone(),
+            _inner: self._inner.clone(),
+        }
+    }
+}
+
+struct ReloadInner {
+    _subscription: ParamSubscription,
+    // Reloads the config when its parameters change
+    _reload_task: ChildTask<()>,
+}
+
+impl<C> ReloadingParams<C>
+where
+    C: serde::de::DeserializeOwned + Send + Sync + 'static,
+{
+    pub(crate) async fn new(nh: NodeHandle, namespace: &str) -> Result<Self, NodeError> {
+        let client = nh.master_client().await?;
+        let namespace = resolve_key(&nh, &client, namespace)?;
+
+        // Subscribe before loading so no change can be missed between the two
+        let updates = nh.param_updates().await?;
+        let subscription = ParamSubscription::new(nh, namespace.clone()).await?;
+        let config = Arc::new(ArcSwap::from_pointee(load(&client, &namespace).await?));
+        let reload_task = tokio::spawn(Self::reload_task(
+            updates,
+            namespace,
+            client,
+            config.clone(),
+        ));
+        Ok(Self {
+            config,
+            _inner: Arc::new(ReloadInner {
+                _subscription: subscription,
+                _reload_task: reload_task.into(),
+            }),
+        })
+    }
+
+    async fn reload_task(
+        mut updates: broadcast::Receiver<ParamUpdate>,
+        namespace: String,
+        client: MasterClient,
+        config: Arc<ArcSwap<C>>,
+    ) {
+        loop {
+            match updates.recv().await {
+                Ok(update) if update.affects(&namespace) => {}
+                Ok(_) => continue,
+                Err(RecvError::Lagged(num)) => {
+                    debug!("Config under {namespace} missed {num} updates, reloading it");
+                }
+                Err(RecvError::Closed) => break,
+            }
+            match load(&client, &namespace).await {
+                Ok(reloaded) => {
+                    debug!("Reloaded config under {namespace}");
+                    config.store(Arc::new(reloaded));
+                }
+                Err(e) => warn!("Ignoring changes to config under {namespace}: {e}"),
+            }
+        }
+    }
+
+    /// Returns the current config
+    pub fn get(&self) -> Arc<C> {
+        self.config.load_full()
+    }
+
+    /// Returns the [ArcSwap] holding the current config, which stays up to date for as long as this handle
+    /// (or a clone of it) is alive
+    pub fn shared(&self) -> Arc<ArcSwap<C>> {
+        self.config.clone()
+    }
+}
+
+// Resolves a parameter name used with `nh` into a global name, "~" alone refers to the node's own namespace
+pub(crate) fn resolve_key(
+    nh: &NodeHandle,
+    client: &MasterClient,
+    key: &str,
+) -> Result<String, NodeError> {
+    let node_name = Name::new(client.caller_id())?;
+    Ok(match nh.resolve_name(key).as_str() {
+        "~" | "~/" => node_name.to_string(),
+        "/" => "/".to_owned(),
+        key => Name::new(key)?.resolve_to_global(&node_name).to_string(),
+    })
+}
+
+// Holds a subscription to a parameter with the master, released when dropped
+struct ParamSubscription {
+    key: String,
+    nh: NodeHandle,
+}
+
+impl ParamSubscription {
+    async fn new(nh: NodeHandle, key: String) -> Result<Self, NodeError> {
+        nh.subscribe_param(&key).await?;
+        Ok(Self { key, nh })
+    }
+}
+
+impl Drop for ParamSubscription {
+    fn drop(&mut self) {
+        // Only fails if the node has already shut down, which unsubscribes everything
+        let _ = self.nh.unsubscribe_param(&self.key);
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn updates_affect_parameters_and_namespaces() {
+        let update = ParamUpdate::new("/robot/arm/", XmlRpcValue::Int(1));
+        assert_eq!(update.key, "/robot/arm");
+        assert!(update.affects("/robot/arm"));
+        // Within the updated namespace
+        assert!(update.affects("/robot/arm/gain"));
+        // Namespaces containing the update
+        assert!(update.affects("/robot"));
+        assert!(!update.affects("/robot/arm_gain"));
+        assert!(!update.affects("/robot/leg"));
+
+        let root = ParamUpdate::new("/", XmlRpcValue::Int(1));
+        assert!(root.affects("/anything"));
+    }
+}
@@ -0,0 +1,71 @@
+use crate::{names::Name, NodeError};
+use std::collections::HashMap;
+
+/// A table of name remappings loaded from the node's `~remappings` parameter, applied by [super::NodeHandle] when
+/// resolving topic, service and parameter names.
+///
+/// Both sides of each remapping are resolved to global names relative to the node like roscpp does, so for node
+/// `/ns/my_node` the entry `in: image_raw` remaps `/ns/in` to `/ns/image_raw` and `~out: /out` remaps
+/// `/ns/my_node/out` to `/out`.
+#[derive(Debug, Clone)]
+pub(crate) struct Remappings {
+    node_name: Name,
+    // Resolved global name -> global name it is remapped to
+    table: HashMap<String, String>,
+}
+
+impl Remappings {
+    /// Parameter the remap table is read from when the node is created
+    pub(crate) const PARAM: &'static str = "~remappings";
+
+    /// Resolves the raw `from: to` pairs of a remap table for the node `node_name`
+    pub(crate) fn new(node_name: Name, raw: HashMap<String, String>) -> Result<Self, NodeError> {
+        let table = raw
+            .into_iter()
+            .map(|(from, to)| Ok((resolve(&from, &node_name)?, resolve(&to, &node_name)?)))
+            .collect::<Result<_, NodeError>>()?;
+        Ok(Self { node_name, table })
+    }
+
+    /// Returns the name `name` is remapped to, if it is remapped
+    pub(crate) fn remap(&self, name: &str) -> Option<&str> {
+        let name = resolve(name, &self.node_name).ok()?;
+        self.table.get(&name).map(String::as_str)
+    }
+
+    pub(crate) fn is_empty(&self) -> bool {
+        self.table.is_empty()
+    }
+
+    /// Adds the remappings in `other`, replacing any of these for the same names
+    pub(crate) fn extend(&mut self, other: Remappings) {
+        self.table.extend(other.table);
+    }
+}
+
+fn resolve(name: &str, node_name: &Name) -> Result<String, NodeError> {
+    Ok(Name::new(name)?.resolve_to_global(node_name).to_string())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn remaps_resolved_names() {
+        let raw = HashMap::from([
+            ("in".to_string(), "/camera/image_raw".to_string()),
+            ("~out".to_string(), "processed".to_string()),
+        ]);
+        let remappings = Remappings::new(Name::new("/ns/my_node").unwrap(), raw).unwrap();
+        assert_eq!(remappings.remap("in"), Some("/camera/image_raw"));
+        assert_eq!(remappings.remap("/ns/in"), Some("/camera/image_raw"));
+        assert_eq!(remappings.remap("~out"), Some("/ns/processed"));
+        assert_eq!(remappings.remap("/ns/my_node/out"), Some("/ns/processed"));
+        assert_eq!(remappings.remap("/in"), None);
+        assert_eq!(remappings.remap("other"), None);
+
+        let invalid = HashMap::from([("in".to_string(), "1nvalid".to_string())]);
+        assert!(Remappings::new(Name::new("/my_node").unwrap(), invalid).is_err());
+    }
+}
@@ -1,12 +1,44 @@
-use super::NodeServerHandle;
+use super::{param::ParamUpdate, NodeHandle, NodeServerHandle};
 use abort_on_drop::ChildTask;
 use hyper::{Body, Response, StatusCode};
 use log::*;
 use std::{
     convert::Infallible,
+    future::Future,
     net::{Ipv4Addr, SocketAddr},
+    pin::Pin,
+    sync::{Arc, RwLock},
 };
 
+/// The value type taken and returned by custom xmlrpc handlers
+pub use serde_xmlrpc::Value as XmlRpcValue;
+
+/// The boxed future returned by an [XmlRpcHandler]
+pub type XmlRpcHandlerFuture = Pin<Box<dyn Future<Output = Result<XmlRpcValue, String>> + Send>>;
+
+/// Type erased custom xmlrpc method, see [crate::NodeHandle::register_xmlrpc_handler]
+pub type XmlRpcHandler =
+    Arc<dyn Fn(NodeHandle, Vec<XmlRpcValue>) -> XmlRpcHandlerFuture + Send + Sync>;
+
+/// The nodes served by a single xmlrpc server, each with the path of its uri on the server
+/// Typically a server serves exactly one node at its root, but a [crate::NodeManager] shares one server between many,
+/// giving each node a path of its own name so calls meant for one node never reach another
+pub(crate) type XmlRpcNodes = Arc<RwLock<Vec<(String, NodeServerHandle)>>>;
+
+/// Methods of ROS's slave API, which custom handlers are not allowed to replace
+pub(crate) const BUILTIN_METHODS: &[&str] = &[
+    "getBusStats",
+    "getBusInfo",
+    "getMasterUri",
+    "shutdown",
+    "getPid",
+    "getSubscriptions",
+    "getPublications",
+    "paramUpdate",
+    "publisherUpdate",
+    "requestTopic",
+];
+
 #[allow(unused)]
 enum RosXmlStatusCode {
     Error,
@@ -34,6 +66,7 @@ pub(crate) struct XmlRpcServer {}
 
 pub(crate) struct XmlRpcServerHandle {
     port: u16,
+    nodes: XmlRpcNodes,
     _handle: ChildTask<()>,
 }
 
@@ -41,20 +74,26 @@ impl XmlRpcServerHandle {
     pub fn port(&self) -> u16 {
         self.port
     }
+
+    /// Starts routing calls made to `path` on this server to the given node, an empty path is the root of the server
+    /// Should be given a handle that doesn't keep the node alive
+    pub fn add_node(&self, path: String, node: NodeServerHandle) {
+        let mut nodes = self.nodes.write().unwrap();
+        nodes.retain(|(_, node)| !node.node_server_sender.is_closed());
+        nodes.push((path, node));
+    }
 }
 
 impl XmlRpcServer {
     #[allow(clippy::new_ret_no_self)]
-    pub fn new(
-        host_addr: Ipv4Addr,
-        node_server: NodeServerHandle,
-    ) -> Result<XmlRpcServerHandle, XmlRpcError> {
+    pub fn new(host_addr: Ipv4Addr, nodes: XmlRpcNodes) -> Result<XmlRpcServerHandle, XmlRpcError> {
+        let nodes_copy = nodes.clone();
         let make_svc = hyper::service::make_service_fn(move |connection| {
             debug!("New node xmlrpc connection {connection:?}");
-            let node_server = node_server.clone();
+            let nodes = nodes_copy.clone();
             async move {
                 Ok::<_, Infallible>(hyper::service::service_fn(move |req| {
-                    XmlRpcServer::respond(node_server.clone(), req)
+                    XmlRpcServer::respond(nodes.clone(), req)
                 }))
             }
         });
@@ -71,15 +110,28 @@ impl XmlRpcServer {
 
         Ok(XmlRpcServerHandle {
             port: addr.port(),
+            nodes,
             _handle: handle.into(),
         })
     }
 
+    // Returns the still running node served at the path a request was made to
+    fn route(nodes: &XmlRpcNodes, path: &str) -> Option<NodeServerHandle> {
+        let path = path.trim_end_matches('/');
+        nodes
+            .read()
+            .unwrap()
+            .iter()
+            .find(|(node_path, node)| node_path == path && !node.node_server_sender.is_closed())
+            .map(|(_, node)| node.clone())
+    }
+
     // Our actual service handler with our error type
     async fn respond_inner(
-        node_server: NodeServerHandle,
+        nodes: XmlRpcNodes,
         body: hyper::Request<Body>,
     ) -> Result<Response<Body>, Box<Response<Body>>> {
+        let path = body.uri().path().to_owned();
         // Await the bytes of the body
         let body = hyper::body::to_bytes(body).await.map_err(|e| {
             Box::new(Self::make_error_response(
@@ -107,6 +159,18 @@ impl XmlRpcServer {
             ))
         })?;
 
+        let Some(node_server) = Self::route(&nodes, &path) else {
+            warn!("Received xmlrpc call {method_name} for {path}, but no node is served there");
+            return Err(Box::new(
+                Response::builder()
+                    .status(StatusCode::NOT_FOUND)
+                    .body(Body::from(format!(
+                        "No node is served at {path} by this xmlrpc server"
+                    )))
+                    .unwrap(),
+            ));
+        };
+
         // Match on allowable functions
         match method_name.as_str() {
             "getMasterUri" => {
@@ -158,9 +222,19 @@ impl XmlRpcServer {
                 }
             }
             "paramUpdate" => {
-                // Not supporting params for first cut
                 debug!("paramUpdate called by {args:?}");
-                unimplemented!()
+                let (_caller_id, key, value): (String, String, XmlRpcValue) =
+                    serde_xmlrpc::from_values(args).map_err(|e| {
+                        Self::make_error_response(
+                            e,
+                            "Failed to parse arguments to paramUpdate",
+                            StatusCode::BAD_REQUEST,
+                        )
+                    })?;
+                // Only fails if the node is shutting down, in which case it no longer cares
+                let _ = node_server.notify_param_update(ParamUpdate::new(&key, value));
+                // ROS's API is for us to still return an int, but the value is literally named "ignore"...
+                Self::to_response(0)
             }
             "publisherUpdate" => {
                 debug!("publisherUpdate called by {args:?}");
@@ -187,7 +261,7 @@ impl XmlRpcServer {
             }
             "requestTopic" => {
                 debug!("requestTopic called by {args:?}");
-                let (caller_id, topic, protocols): (String, String, Vec<Vec<String>>) =
+                let (caller_id, topic, protocols): (String, String, Vec<Vec<serde_xmlrpc::Value>>) =
                     serde_xmlrpc::from_values(args).map_err(|e| {
                         Self::make_error_response(
                             e,
@@ -195,7 +269,14 @@ impl XmlRpcServer {
                             StatusCode::BAD_REQUEST,
                         )
                     })?;
-                let protocols = protocols.iter().flatten().cloned().collect::<Vec<_>>();
+                // Each protocol is its name followed by its parameters, e.g. UDPROS sends its connection header
+                let protocols = protocols
+                    .iter()
+                    .filter_map(|protocol| match protocol.first() {
+                        Some(serde_xmlrpc::Value::String(name)) => Some(name.clone()),
+                        _ => None,
+                    })
+                    .collect::<Vec<_>>();
                 debug!("Request for topic {topic} from {caller_id} via protocols {protocols:?}");
                 let params = node_server
                     .request_topic(&topic, &protocols)
@@ -241,6 +322,19 @@ impl XmlRpcServer {
             }
             // getBusStats, getBusInfo <= have decided not to impl these
             _ => {
+                // Give any user registered handler the chance to respond
+                if let Ok(Some(handler)) = node_server.get_xmlrpc_handler(&method_name).await {
+                    debug!("Custom xmlrpc method {method_name} called by {args:?}");
+                    let nh = NodeHandle::from_server_handle(node_server.clone());
+                    return Ok(match handler(nh, args).await {
+                        Ok(value) => {
+                            Self::make_success_response(RosXmlStatusCode::Success, "", value)
+                        }
+                        Err(msg) => {
+                            Self::make_success_response(RosXmlStatusCode::Failure, &msg, 0.into())
+                        }
+                    });
+                }
                 let error_str = format!("Client attempted call function {method_name} which is not implemented by the Node's xmlrpc server.");
                 warn!("{error_str}");
                 Ok(Response::builder()
@@ -319,19 +413,50 @@ impl XmlRpcServer {
 
     // Is the actual function we hand to hyper
     async fn respond(
-        node_server: NodeServerHandle,
+        nodes: XmlRpcNodes,
         body: hyper::Request<Body>,
     ) -> Result<Response<Body>, Infallible> {
+        if crate::xmlrpc_recorder::is_recording() {
+            return Ok(Self::respond_recorded(nodes, body).await);
+        }
         // Call our inner function and unwrap error type into response
-        match Self::respond_inner(node_server, body).await {
+        match Self::respond_inner(nodes, body).await {
             Ok(body) => Ok(body),
             Err(body) => Ok(*body),
         }
     }
+
+    // Same as respond, but buffers the request and response so they can be given to the xmlrpc recorder
+    async fn respond_recorded(nodes: XmlRpcNodes, req: hyper::Request<Body>) -> Response<Body> {
+        let uri = req
+            .headers()
+            .get(hyper::header::HOST)
+            .and_then(|host| host.to_str().ok())
+            .map(|host| format!("http://{host}"))
+            .unwrap_or_default();
+        let (parts, body) = req.into_parts();
+        let request = hyper::body::to_bytes(body).await.unwrap_or_default();
+        let req = hyper::Request::from_parts(parts, Body::from(request.clone()));
+        let response = match Self::respond_inner(nodes, req).await {
+            Ok(response) => response,
+            Err(response) => *response,
+        };
+        let (parts, body) = response.into_parts();
+        let response = hyper::body::to_bytes(body).await.unwrap_or_default();
+        crate::xmlrpc_recorder::record(
+            crate::xmlrpc_recorder::XmlRpcDirection::Incoming,
+            &uri,
+            &String::from_utf8_lossy(&request),
+            &String::from_utf8_lossy(&response),
+        );
+        Response::from_parts(parts, Body::from(response))
+    }
 }
 
 #[derive(thiserror::Error, Debug)]
 pub enum XmlRpcError {
     #[error(transparent)]
     HyperError(#[from] hyper::Error),
+    #[error("xmlrpc method {0} is already handled by this node")]
+    MethodAlreadyRegistered(String),
 }
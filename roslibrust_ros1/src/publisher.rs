@@ -1,21 +1,582 @@
 use crate::{
+    compression::{self, Compression},
     names::Name,
-    tcpros::{self, ConnectionHeader},
+    node::{health::TopicActivity, TopicStats},
+    qos::{self, Heartbeat, QosTask},
+    shm::{self, ShmWriter},
+    supervisor::{ConnectionEvent, TaskKind, TaskSupervisor},
+    tcpros::{self, ConnectionHeader, DEFAULT_MAX_MESSAGE_SIZE},
+    type_registry::RegisteredType,
+    watermarks::Watermarks,
+    wire_recorder::{WireDirection, WireRecordKind, WireRecorder},
 };
 use abort_on_drop::ChildTask;
-use bytes::Bytes;
+use bytes::{BufMut, Bytes, BytesMut};
 use log::*;
-use roslibrust_common::RosMessageType;
+use roslibrust_common::{clock::Clock, RosMessageType};
 use std::{
+    borrow::{Borrow, Cow},
     marker::PhantomData,
     net::{Ipv4Addr, SocketAddr},
+    sync::{
+        atomic::{AtomicU32, Ordering},
+        Arc, Mutex,
+    },
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
 };
 use tokio::{
     io::AsyncWriteExt,
-    sync::broadcast::{self, error::RecvError},
+    sync::{
+        broadcast::{
+            self,
+            error::{RecvError, TryRecvError},
+        },
+        mpsc, watch,
+    },
 };
 
 use super::actor::NodeServerHandle;
+use crate::{NodeError, NodeHandle};
+
+/// The message sent to new subscribers when they connect, shared between a [Publication] and its publishers.
+/// Only ever holds a message if the publication is latching.
+#[derive(Clone)]
+pub(crate) struct LatchedMessage {
+    latching: bool,
+    message: Arc<Mutex<Option<Bytes>>>,
+}
+
+impl LatchedMessage {
+    fn new(latching: bool) -> Self {
+        Self {
+            latching,
+            message: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn set(&self, message: Option<Bytes>) {
+        if self.latching {
+            *self.message.lock().unwrap() = message;
+        }
+    }
+
+    pub(crate) fn get(&self) -> Option<Bytes> {
+        self.message.lock().unwrap().clone()
+    }
+}
+
+// Token bucket refilled at `rate` tokens per second, holding at most `capacity`
+#[derive(Clone, Debug)]
+struct TokenBucket {
+    rate: f64,
+    capacity: f64,
+    tokens: f64,
+    last_refill: Option<Instant>,
+}
+
+impl TokenBucket {
+    fn new(rate: f64, capacity: f64) -> Self {
+        Self {
+            rate,
+            capacity,
+            tokens: capacity,
+            last_refill: None,
+        }
+    }
+
+    fn refill(&mut self, now: Instant) {
+        if let Some(last_refill) = self.last_refill {
+            let elapsed = now.saturating_duration_since(last_refill).as_secs_f64();
+            self.tokens = (self.tokens + elapsed * self.rate).min(self.capacity);
+        }
+        self.last_refill = Some(now);
+    }
+
+    // A full bucket admits anything, going into debt for requests larger than its capacity.
+    // This keeps the long term rate without refusing messages larger than the burst forever.
+    fn has(&self, tokens: f64) -> bool {
+        self.tokens >= tokens || self.tokens >= self.capacity
+    }
+}
+
+// The buckets of a [PublishLimiter], each None if that limit isn't set
+struct Limits {
+    bytes: Option<TokenBucket>,
+    messages: Option<TokenBucket>,
+}
+
+/// Limits the rate at which a publication queues messages, shared between a [Publication] and its publishers.
+/// Does nothing unless [AdvertiseOptions::bandwidth_limit] or [AdvertiseOptions::rate_limit] was set.
+#[derive(Clone)]
+pub(crate) struct PublishLimiter {
+    // None if the publication has no limits
+    limits: Option<Arc<Mutex<Limits>>>,
+    // The publication's activity, counts the messages dropped
+    activity: Arc<TopicActivity>,
+}
+
+impl PublishLimiter {
+    fn new(options: &AdvertiseOptions, activity: Arc<TopicActivity>) -> Self {
+        let limits = Limits {
+            bytes: options
+                .bandwidth_limit
+                .map(|(rate, burst)| TokenBucket::new(rate, burst)),
+            messages: options
+                .rate_limit
+                .map(|(rate, burst)| TokenBucket::new(rate, burst)),
+        };
+        Self {
+            limits: (limits.bytes.is_some() || limits.messages.is_some())
+                .then(|| Arc::new(Mutex::new(limits))),
+            activity,
+        }
+    }
+
+    /// Returns true if a message of `len` bytes may be sent at `now`, taking its tokens if so
+    fn admit(&self, len: usize, now: Instant) -> bool {
+        let Some(limits) = &self.limits else {
+            return true;
+        };
+        let mut limits = limits.lock().unwrap();
+        let Limits { bytes, messages } = &mut *limits;
+        let len = len as f64;
+        for bucket in [&mut *bytes, &mut *messages].into_iter().flatten() {
+            bucket.refill(now);
+        }
+        let admitted = bytes.as_ref().is_none_or(|bucket| bucket.has(len))
+            && messages.as_ref().is_none_or(|bucket| bucket.has(1.0));
+        if admitted {
+            if let Some(bucket) = bytes {
+                bucket.tokens -= len;
+            }
+            if let Some(bucket) = messages {
+                bucket.tokens -= 1.0;
+            }
+        } else {
+            self.activity.dropped(1);
+        }
+        admitted
+    }
+}
+
+// Set by the node once the publication has been unregistered with the master, None until then
+pub(crate) type UnadvertiseResult = Option<Result<(), String>>;
+
+// Releases one handle to a publication, and if it was the last handle waits for the node to unregister the topic
+async fn release_publication(
+    shutdown: mpsc::Sender<()>,
+    mut unadvertised: watch::Receiver<UnadvertiseResult>,
+) -> Result<(), PublisherError> {
+    let weak_shutdown = shutdown.downgrade();
+    drop(shutdown);
+    if weak_shutdown.upgrade().is_some() {
+        // Other handles are keeping the publication alive
+        return Ok(());
+    }
+    let result = match unadvertised.wait_for(Option::is_some).await {
+        Ok(result) => result.clone().unwrap_or(Ok(())),
+        // The publication was torn down without reporting, e.g. because the node shut down
+        Err(_) => Ok(()),
+    };
+    result.map_err(PublisherError::UnadvertiseFailed)
+}
+
+// How often [Publisher::publish_timeout] checks whether space has been freed in the queue
+const QUEUE_POLL_INTERVAL: Duration = Duration::from_millis(1);
+
+// Once this many bytes of already queued messages have been gathered they are written to a subscriber,
+// bounds the copy made to coalesce a batch of messages into a single write
+const MAX_WRITE_BATCH: usize = 64 * 1024;
+
+// Byte offsets of the fields of a std_msgs/Header that is the first field of a serialized message
+// The first 4 bytes of the serialized message are its overall length
+const HEADER_SEQ_OFFSET: usize = 4;
+const HEADER_STAMP_OFFSET: usize = 8;
+const HEADER_FRAME_ID_OFFSET: usize = 16;
+
+/// Hook applied to each message before it is published, see [PublisherBuilder::map_before_send]
+type MessageHook<T> = Arc<dyn Fn(&mut T) + Send + Sync>;
+
+/// Options for creating a publisher with [crate::NodeHandle::advertise_with_options].
+#[derive(Clone, Debug)]
+pub struct AdvertiseOptions {
+    pub(crate) queue_size: usize,
+    pub(crate) latching: bool,
+    pub(crate) fill_seq: bool,
+    pub(crate) stamp_on_send: bool,
+    pub(crate) clock: Clock,
+    pub(crate) compression: Option<Compression>,
+    pub(crate) shared_memory: bool,
+    pub(crate) max_message_size: usize,
+    pub(crate) tcp_nodelay: bool,
+    // Rate and burst, in bytes
+    pub(crate) bandwidth_limit: Option<(f64, f64)>,
+    // Rate and burst, in messages
+    pub(crate) rate_limit: Option<(f64, f64)>,
+    pub(crate) strict_type_check: bool,
+}
+
+impl AdvertiseOptions {
+    /// Creates options for a non-latching publisher with the given queue size
+    pub fn new(queue_size: usize) -> AdvertiseOptions {
+        AdvertiseOptions {
+            queue_size,
+            latching: false,
+            fill_seq: false,
+            stamp_on_send: false,
+            clock: Clock::wall(),
+            compression: None,
+            shared_memory: false,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            tcp_nodelay: false,
+            bandwidth_limit: None,
+            rate_limit: None,
+            strict_type_check: false,
+        }
+    }
+
+    /// Configures whether the last message published is sent to new subscribers when they connect
+    pub fn latching(mut self, latching: bool) -> AdvertiseOptions {
+        self.latching = latching;
+        self
+    }
+
+    /// Overwrites `header.seq` of every published message with a counter that increases by one per message,
+    /// starting from 0.
+    ///
+    /// Requires the message's first field to be a std_msgs/Header.
+    pub fn fill_seq(mut self, fill_seq: bool) -> AdvertiseOptions {
+        self.fill_seq = fill_seq;
+        self
+    }
+
+    /// Overwrites `header.stamp` with the current time as each message is written to each subscriber's
+    /// connection, rather than when it was queued. The time is read from [AdvertiseOptions::clock].
+    ///
+    /// Requires the message's first field to be a std_msgs/Header.
+    pub fn stamp_on_send(mut self, stamp_on_send: bool) -> AdvertiseOptions {
+        self.stamp_on_send = stamp_on_send;
+        self
+    }
+
+    /// Sets the clock [AdvertiseOptions::stamp_on_send] reads, e.g. a
+    /// [SimClock](roslibrust_common::clock::SimClock) when running in simulation. Defaults to the wall clock.
+    pub fn clock(mut self, clock: Clock) -> AdvertiseOptions {
+        self.clock = clock;
+        self
+    }
+
+    /// Compresses messages sent to subscribers which are also roslibrust nodes with the same algorithm enabled,
+    /// other subscribers receive plain TCPROS.
+    ///
+    /// Worthwhile for large compressible messages such as images sent between hosts.
+    /// Each subscriber's connection is compressed separately, so this costs CPU per subscriber.
+    /// Has no effect unless the cargo feature for the algorithm is enabled.
+    pub fn compression(mut self, compression: Compression) -> AdvertiseOptions {
+        self.compression = Some(compression);
+        self
+    }
+
+    /// Sends messages to subscribers on the same host which are also roslibrust nodes through shared memory
+    /// rather than over TCP, other subscribers receive plain TCPROS.
+    ///
+    /// Saves copying large messages such as images and point clouds through the kernel's socket buffers.
+    /// Messages are still serialized, and each subscriber's messages are written to /dev/shm separately.
+    /// Used in preference to [AdvertiseOptions::compression] where both are possible.
+    /// Requires the `shm` cargo feature and linux.
+    pub fn shared_memory(mut self, shared_memory: bool) -> AdvertiseOptions {
+        self.shared_memory = shared_memory;
+        self
+    }
+
+    /// Largest message, in bytes, that may be published. Publishing anything larger fails with
+    /// [PublisherError::MessageTooLarge] rather than sending subscribers a message they are likely to refuse.
+    ///
+    /// Defaults to [crate::DEFAULT_MAX_MESSAGE_SIZE], the limit subscribers apply is set with
+    /// [crate::NodeHandle::set_max_message_size].
+    pub fn max_message_size(mut self, max_message_size: usize) -> AdvertiseOptions {
+        self.max_message_size = max_message_size;
+        self
+    }
+
+    /// Disables Nagle's algorithm on every subscriber's connection, whether or not the subscriber asked for it,
+    /// trading bandwidth for lower latency on small messages.
+    pub fn tcp_nodelay(mut self, tcp_nodelay: bool) -> AdvertiseOptions {
+        self.tcp_nodelay = tcp_nodelay;
+        self
+    }
+
+    /// Caps the data published on the topic to `bytes_per_second`, allowing bursts of up to `burst_bytes`.
+    ///
+    /// Messages published while over the limit are dropped before they are queued for any subscriber,
+    /// and publishing still succeeds, so a bulky topic (e.g. a debug image) can be capped without changing the code
+    /// producing it. The limit is shared by every handle to the publication.
+    /// A message larger than `burst_bytes` is sent once the full burst is available, and the messages after it
+    /// are dropped until the rate has made up for it.
+    /// Messages published with [Priority::High] are never dropped, and don't count towards the limit.
+    pub fn bandwidth_limit(mut self, bytes_per_second: f64, burst_bytes: f64) -> AdvertiseOptions {
+        self.bandwidth_limit = Some((bytes_per_second, burst_bytes));
+        self
+    }
+
+    /// Caps the number of messages published on the topic to `messages_per_second`, allowing bursts of up to
+    /// `burst` messages.
+    ///
+    /// Behaves like [AdvertiseOptions::bandwidth_limit], and both may be set at once.
+    pub fn rate_limit(mut self, messages_per_second: f64, burst: f64) -> AdvertiseOptions {
+        self.rate_limit = Some((messages_per_second, burst));
+        self
+    }
+
+    /// Looks the topic up with the master's getTopicTypes before advertising, failing with
+    /// [NodeError::TopicTypeMismatch] if another node already publishes or subscribes to it with a different type.
+    ///
+    /// Catches misconfigured topic names at startup, rather than when subscribers fail to connect.
+    /// The master only records type names, so differing definitions of the same type are still only detected when
+    /// subscribers connect.
+    pub fn strict_type_check(mut self, strict_type_check: bool) -> AdvertiseOptions {
+        self.strict_type_check = strict_type_check;
+        self
+    }
+}
+
+/// Builds a [Publisher], created with [crate::NodeHandle::advertise_builder].
+///
+/// ```no_run
+/// # async fn example(nh: roslibrust_ros1::NodeHandle) -> Result<(), roslibrust_ros1::NodeError> {
+/// # use roslibrust_test::ros1::*;
+/// let publisher = nh
+///     .advertise_builder::<geometry_msgs::PointStamped>("/point")
+///     .latched()
+///     .queue_size(1)
+///     .frame_id("base_link")
+///     .map_before_send(|msg| msg.point.z = msg.point.z.max(0.0))
+///     .build()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct PublisherBuilder<T> {
+    nh: NodeHandle,
+    topic_name: String,
+    options: AdvertiseOptions,
+    frame_id: Option<String>,
+    map_before_send: Option<MessageHook<T>>,
+    watermarks: Option<Watermarks>,
+    heartbeat_period: Option<Duration>,
+}
+
+impl<T: RosMessageType> PublisherBuilder<T> {
+    pub(crate) fn new(nh: NodeHandle, topic_name: &str) -> Self {
+        Self {
+            nh,
+            topic_name: topic_name.to_owned(),
+            // Same default queue size as the generic TopicProvider::advertise
+            options: AdvertiseOptions::new(10),
+            frame_id: None,
+            map_before_send: None,
+            watermarks: None,
+            heartbeat_period: None,
+        }
+    }
+
+    /// Replaces all of the [AdvertiseOptions], settings from earlier calls to the other builder functions are lost
+    pub fn options(mut self, options: AdvertiseOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Number of messages queued for each subscriber before the oldest are dropped, defaults to 10
+    pub fn queue_size(mut self, queue_size: usize) -> Self {
+        self.options.queue_size = queue_size;
+        self
+    }
+
+    /// Sends the last message published to new subscribers when they connect
+    pub fn latched(mut self) -> Self {
+        self.options = self.options.latching(true);
+        self
+    }
+
+    /// Disables Nagle's algorithm on subscriber connections, see [AdvertiseOptions::tcp_nodelay]
+    pub fn nodelay(mut self) -> Self {
+        self.options = self.options.tcp_nodelay(true);
+        self
+    }
+
+    /// Drops messages published beyond `bytes_per_second`, see [AdvertiseOptions::bandwidth_limit]
+    pub fn bandwidth_limit(mut self, bytes_per_second: f64, burst_bytes: f64) -> Self {
+        self.options = self.options.bandwidth_limit(bytes_per_second, burst_bytes);
+        self
+    }
+
+    /// Drops messages published beyond `messages_per_second`, see [AdvertiseOptions::rate_limit]
+    pub fn rate_limit(mut self, messages_per_second: f64, burst: f64) -> Self {
+        self.options = self.options.rate_limit(messages_per_second, burst);
+        self
+    }
+
+    /// Fails to build if the topic already exists with a different type, see [AdvertiseOptions::strict_type_check]
+    pub fn strict_type_check(mut self) -> Self {
+        self.options = self.options.strict_type_check(true);
+        self
+    }
+
+    /// Fills in `header.frame_id` of published messages which leave it empty.
+    ///
+    /// Requires the message's first field to be a std_msgs/Header.
+    pub fn frame_id(mut self, frame_id: impl Into<String>) -> Self {
+        self.frame_id = Some(frame_id.into());
+        self
+    }
+
+    /// Applies `f` to a copy of each message as it is published, e.g. to timestamp it.
+    ///
+    /// The hook runs before any header fields are filled in by the publisher, and the caller's message is not modified.
+    pub fn map_before_send(mut self, f: impl Fn(&mut T) + Send + Sync + 'static) -> Self {
+        self.map_before_send = Some(Arc::new(f));
+        self
+    }
+
+    /// Reports when the publisher's queue fills up to and drains from the given watermarks, see [Watermarks]
+    pub fn queue_watermarks(mut self, watermarks: Watermarks) -> Self {
+        self.watermarks = Some(watermarks);
+        self
+    }
+
+    /// Asserts the publisher's liveliness by sending a heartbeat every `period` while it exists, emulating ROS2's
+    /// automatic liveliness.
+    ///
+    /// Heartbeats are sent as `std_msgs/Header` on `<topic>/heartbeat`, and are watched for by subscribers built with
+    /// [SubscriberBuilder::on_liveliness_changed](crate::SubscriberBuilder::on_liveliness_changed).
+    pub fn heartbeat(mut self, period: Duration) -> Self {
+        self.heartbeat_period = Some(period);
+        self
+    }
+
+    /// Advertises the topic, see [crate::NodeHandle::advertise_with_options]
+    pub async fn build(self) -> Result<Publisher<T>, NodeError> {
+        if self.frame_id.is_some() && !starts_with_header(T::DEFINITION) {
+            log::error!(
+                "Cannot fill header.frame_id for {}, {} does not start with a Header",
+                self.topic_name,
+                T::ROS_TYPE_NAME
+            );
+            return Err(NodeError::MissingHeader {
+                msg_type: T::ROS_TYPE_NAME.to_owned(),
+            });
+        }
+        let mut publisher = self
+            .nh
+            .advertise_with_options::<T>(&self.topic_name, self.options)
+            .await?;
+        publisher.frame_id = self.frame_id.map(|frame_id| {
+            let mut encoded = (frame_id.len() as u32).to_le_bytes().to_vec();
+            encoded.extend_from_slice(frame_id.as_bytes());
+            encoded
+        });
+        publisher.map_before_send = self.map_before_send;
+        publisher.watermarks = self.watermarks;
+        if let Some(period) = self.heartbeat_period {
+            let heartbeats = self
+                .nh
+                .advertise::<Heartbeat>(&qos::heartbeat_topic(&self.topic_name), 1, false)
+                .await?;
+            publisher.heartbeat = Some(qos::publish_heartbeats(heartbeats, period));
+        }
+        Ok(publisher)
+    }
+}
+
+// Refuses a serialized message (including its 4 byte length) larger than `max_message_size`
+fn check_message_size(data: &[u8], max_message_size: usize) -> Result<(), PublisherError> {
+    let size = data.len().saturating_sub(4);
+    if size > max_message_size {
+        return Err(PublisherError::MessageTooLarge {
+            size,
+            max: max_message_size,
+        });
+    }
+    Ok(())
+}
+
+// Confirms serialized data starts with the length of the rest of it, as every message on the wire does
+fn check_length_prefix(data: &[u8]) -> Result<(), PublisherError> {
+    let Some(prefix) = data.get(..4) else {
+        return Err(PublisherError::SerializingError(format!(
+            "serialized message of {} bytes is too short to contain its length",
+            data.len()
+        )));
+    };
+    let len = u32::from_le_bytes(prefix.try_into().unwrap()) as usize;
+    if len != data.len() - 4 {
+        return Err(PublisherError::SerializingError(format!(
+            "serialized message has a length prefix of {len} but a body of {} bytes",
+            data.len() - 4
+        )));
+    }
+    Ok(())
+}
+
+/// Returns true if the first field of the message described by `definition` is a std_msgs/Header
+pub(crate) fn starts_with_header(definition: &str) -> bool {
+    definition
+        .lines()
+        .map(|line| line.split('#').next().unwrap_or_default().trim())
+        .find(|line| !line.is_empty())
+        .and_then(|line| line.split_whitespace().next())
+        .is_some_and(|field_type| field_type == "Header" || field_type == "std_msgs/Header")
+}
+
+// Reads header.stamp from a serialized message starting with a header, None if it is too short
+pub(crate) fn header_stamp(msg: &[u8]) -> Option<SystemTime> {
+    let stamp = msg.get(HEADER_STAMP_OFFSET..HEADER_STAMP_OFFSET + 8)?;
+    let secs = u32::from_le_bytes(stamp[..4].try_into().unwrap());
+    let nsecs = u32::from_le_bytes(stamp[4..].try_into().unwrap());
+    Some(UNIX_EPOCH + Duration::new(secs as u64, nsecs))
+}
+
+// Returns a copy of a serialized message with header.stamp set to `now`
+fn stamp_message(msg: &Bytes, now: SystemTime) -> Bytes {
+    let mut msg = msg.to_vec();
+    if msg.len() >= HEADER_STAMP_OFFSET + 8 {
+        let now = now.duration_since(UNIX_EPOCH).unwrap_or_default();
+        msg[HEADER_STAMP_OFFSET..HEADER_STAMP_OFFSET + 4]
+            .copy_from_slice(&(now.as_secs() as u32).to_le_bytes());
+        msg[HEADER_STAMP_OFFSET + 4..HEADER_STAMP_OFFSET + 8]
+            .copy_from_slice(&now.subsec_nanos().to_le_bytes());
+    }
+    msg.into()
+}
+
+/// The channels needed to create a new publisher handle to an existing [Publication]
+pub(crate) struct PublicationHandle {
+    // Uses Bytes for efficient cloning (reference counted) when there are multiple subscribers
+    pub(crate) sender: broadcast::Sender<Bytes>,
+    // Messages sent here are written to subscribers ahead of any queued on sender
+    pub(crate) priority_sender: broadcast::Sender<Bytes>,
+    pub(crate) shutdown: tokio::sync::mpsc::Sender<()>,
+    // Capacity of sender, set by whichever call first created the publication
+    pub(crate) queue_size: usize,
+    pub(crate) latched: LatchedMessage,
+    pub(crate) limiter: PublishLimiter,
+    // Counter used to fill header.seq, only present if the publication was created with fill_seq
+    pub(crate) seq: Option<Arc<AtomicU32>>,
+    // Largest message that may be published, see [AdvertiseOptions::max_message_size]
+    pub(crate) max_message_size: usize,
+    // Reports the outcome of unregistering the publication with the master
+    pub(crate) unadvertised: watch::Receiver<UnadvertiseResult>,
+}
+
+/// Which queue a message is published through, see [Publisher::publish_with_priority].
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub enum Priority {
+    /// The queue used by [Publisher::publish] and all other publish functions
+    #[default]
+    Normal,
+    /// Written to each subscriber before any queued normal priority messages
+    High,
+}
 
 /// The regular Publisher representation returned by calling advertise on a [crate::NodeHandle].
 pub struct Publisher<T> {
@@ -24,42 +585,331 @@ pub struct Publisher<T> {
     // Actual channel on which messages are sent to be published
     // Uses Bytes for efficient cloning (reference counted) when there are multiple subscribers
     sender: broadcast::Sender<Bytes>,
+    // Channel for messages published with [Priority::High], drained by each subscriber's connection before sender
+    priority_sender: broadcast::Sender<Bytes>,
     // When the last publisher for a given topic is dropped, this channel is used to signal to cleanup
     // for the underlying publication
-    _shutdown_channel: tokio::sync::mpsc::Sender<()>,
+    shutdown_channel: tokio::sync::mpsc::Sender<()>,
+    // Shared with the publication, reports when the topic has been unregistered with the master
+    unadvertised: watch::Receiver<UnadvertiseResult>,
+    // Capacity of sender, used to detect when the queue is full
+    queue_size: usize,
+    // Shared with the publication, the message new subscribers receive if latching
+    latched: LatchedMessage,
+    // Shared with the publication, drops messages over its bandwidth or rate limit
+    limiter: PublishLimiter,
+    // Shared with the publication, present if header.seq should be filled in
+    seq: Option<Arc<AtomicU32>>,
+    // Largest message that may be published
+    max_message_size: usize,
+    // Serialized (length prefixed) frame_id written into messages published with an empty header.frame_id
+    frame_id: Option<Vec<u8>>,
+    // Applied to a copy of each message before it is serialized
+    map_before_send: Option<MessageHook<T>>,
+    // Checked against the depth of sender after each message is queued
+    watermarks: Option<Watermarks>,
+    // Sends heartbeats asserting this publisher's liveliness, see [PublisherBuilder::heartbeat]
+    heartbeat: Option<QosTask>,
     // Phantom data to ensure that the type is known at compile time
     phantom: PhantomData<T>,
 }
 
 impl<T: RosMessageType> Publisher<T> {
-    pub(crate) fn new(
-        topic_name: &str,
-        sender: broadcast::Sender<Bytes>,
-        shutdown_channel: tokio::sync::mpsc::Sender<()>,
-    ) -> Self {
+    pub(crate) fn new(topic_name: &str, handle: PublicationHandle) -> Self {
         Self {
             topic_name: topic_name.to_owned(),
-            sender,
-            _shutdown_channel: shutdown_channel,
+            sender: handle.sender,
+            priority_sender: handle.priority_sender,
+            shutdown_channel: handle.shutdown,
+            unadvertised: handle.unadvertised,
+            queue_size: handle.queue_size,
+            latched: handle.latched,
+            limiter: handle.limiter,
+            seq: handle.seq,
+            max_message_size: handle.max_message_size,
+            frame_id: None,
+            map_before_send: None,
+            watermarks: None,
+            heartbeat: None,
             phantom: PhantomData,
         }
     }
 
+    // Applies the map_before_send hook, only copying the message if there is one
+    fn prepare<'a>(&self, data: &'a T) -> Cow<'a, T> {
+        match &self.map_before_send {
+            Some(hook) => {
+                let mut data = data.clone();
+                hook(&mut data);
+                Cow::Owned(data)
+            }
+            None => Cow::Borrowed(data),
+        }
+    }
+
+    // Serializes a message for publishing, filling in header fields if configured to
+    fn serialize(&self, data: &T) -> Result<Bytes, PublisherError> {
+        self.serialize_prepared(&self.prepare(data))
+    }
+
+    // Serializes a message the map_before_send hook has already been applied to
+    fn serialize_prepared(&self, data: &T) -> Result<Bytes, PublisherError> {
+        let mut data = roslibrust_serde_rosmsg::to_vec(data)?;
+        if let Some(frame_id) = self.missing_frame_id(&data) {
+            data.splice(
+                HEADER_FRAME_ID_OFFSET..HEADER_FRAME_ID_OFFSET + 4,
+                frame_id.iter().copied(),
+            );
+            // The overall length prefix has to account for the inserted frame_id
+            let len = (data.len() - 4) as u32;
+            data[..4].copy_from_slice(&len.to_le_bytes());
+        }
+        check_message_size(&data, self.max_message_size)?;
+        self.fill_seq(&mut data);
+        Ok(data.into())
+    }
+
+    // Returns the frame_id to insert if configured with one and the serialized message's header.frame_id is empty
+    fn missing_frame_id(&self, data: &[u8]) -> Option<&[u8]> {
+        let frame_id = self.frame_id.as_deref()?;
+        let len = data.get(HEADER_FRAME_ID_OFFSET..HEADER_FRAME_ID_OFFSET + 4)?;
+        (len == [0, 0, 0, 0]).then_some(frame_id)
+    }
+
+    // Fills in header.seq of a serialized message if configured to
+    fn fill_seq(&self, data: &mut [u8]) {
+        if let Some(seq) = &self.seq {
+            if data.len() >= HEADER_SEQ_OFFSET + 4 {
+                let seq = seq.fetch_add(1, Ordering::Relaxed);
+                data[HEADER_SEQ_OFFSET..HEADER_SEQ_OFFSET + 4].copy_from_slice(&seq.to_le_bytes());
+            }
+        }
+    }
+
+    // Queues a serialized message, unless it is dropped by the bandwidth or rate limit, latching it if sent
+    fn send(&self, data: Bytes, priority: Priority) -> Result<(), PublisherError> {
+        let sender = match priority {
+            Priority::Normal => {
+                if !self.limiter.admit(data.len(), Instant::now()) {
+                    trace!(
+                        "Dropping message on topic {} over its limit",
+                        self.topic_name
+                    );
+                    return Ok(());
+                }
+                &self.sender
+            }
+            // Critical messages are exactly those a limit on bulk data shouldn't drop
+            Priority::High => &self.priority_sender,
+        };
+        self.latched.set(Some(data.clone()));
+        sender
+            .send(data)
+            .map_err(|_| PublisherError::StreamClosed)?;
+        if let (Priority::Normal, Some(watermarks)) = (priority, &self.watermarks) {
+            watermarks.observe(self.sender.len());
+        }
+        Ok(())
+    }
+
     /// Queues a message to be sent on the related topic.
     // TODO Major this no longer needs to be (or should be) async
     pub async fn publish(&self, data: &T) -> Result<(), PublisherError> {
-        let data = roslibrust_serde_rosmsg::to_vec(&data)?;
+        let data = self.serialize(data)?;
         // TODO this is a pretty dumb...
         // because of the internal channel used for re-direction this future doesn't
         // actually complete when the data is sent, but merely when it is queued to be sent
         // This function could probably be non-async
         // Or we should do some significant re-work to have it only yield when the data is sent.
-        self.sender
-            .send(data.into())
-            .map_err(|_| PublisherError::StreamClosed)?;
+        self.send(data, Priority::Normal)?;
+        debug!("Publishing data on topic {}", self.topic_name);
+        Ok(())
+    }
+
+    /// Queues a message to be sent on the related topic through the queue for `priority`.
+    ///
+    /// Each subscriber's connection writes every queued [Priority::High] message before any [Priority::Normal] one,
+    /// so low rate but critical messages (e.g. an e-stop or heartbeat) aren't held up behind a backlog of bulk
+    /// data on the same topic. A high priority message can still wait for one write of already queued messages
+    /// to finish, and may reach subscribers ahead of normal messages published before it.
+    /// The high priority queue is the same size as the publisher's queue size.
+    ///
+    /// Publishing with [Priority::Normal] is identical to [Publisher::publish].
+    pub fn publish_with_priority(
+        &self,
+        data: &T,
+        priority: Priority,
+    ) -> Result<(), PublisherError> {
+        let data = self.serialize(data)?;
+        self.send(data, priority)?;
+        debug!(
+            "Publishing {priority:?} priority data on topic {}",
+            self.topic_name
+        );
+        Ok(())
+    }
+
+    /// Identical to [Publisher::publish], but takes ownership of the message.
+    ///
+    /// When the publisher was built with [PublisherBuilder::map_before_send] the hook is applied to `data` in place,
+    /// avoiding the copy [Publisher::publish] has to make.
+    pub async fn publish_owned(&self, mut data: T) -> Result<(), PublisherError> {
+        if let Some(hook) = &self.map_before_send {
+            hook(&mut data);
+        }
+        let data = self.serialize_prepared(&data)?;
+        self.send(data, Priority::Normal)?;
+        debug!("Publishing data on topic {}", self.topic_name);
+        Ok(())
+    }
+
+    /// Queues a batch of messages to be sent on the related topic, in order.
+    ///
+    /// Accepts anything that iterates over messages or references to them, e.g. `&[T]` or `Vec<T>`.
+    /// Every message is serialized before any are queued, so if one fails to serialize none are sent.
+    /// The batch is queued back to back, letting each subscriber's connection write as much of it as it can
+    /// in a single write instead of one per message, which is significantly faster for dense data like bag replays.
+    ///
+    /// Batches larger than the publisher's queue size will cause slow subscribers to skip the oldest messages,
+    /// the same as publishing them one at a time would.
+    /// If latching, the last message of the batch becomes the latched message.
+    pub async fn publish_iter<I>(&self, msgs: I) -> Result<(), PublisherError>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<T>,
+    {
+        let batch = msgs
+            .into_iter()
+            .map(|msg| self.serialize(msg.borrow()))
+            .collect::<Result<Vec<_>, _>>()?;
+        let count = batch.len();
+        for data in batch {
+            self.send(data, Priority::Normal)?;
+        }
+        debug!("Publishing {count} messages on topic {}", self.topic_name);
+        Ok(())
+    }
+
+    /// Queues a message to be sent on the related topic, serializing it into `buffer` instead of a new allocation.
+    ///
+    /// The serialized message is split off of `buffer` and shared with the subscribers, once they have all been
+    /// sent it (and it is no longer the latched message) the next call reuses its memory. High rate publishers can
+    /// keep a single buffer around to avoid allocating for every message:
+    /// ```ignore
+    /// let mut buffer = bytes::BytesMut::with_capacity(1024);
+    /// loop {
+    ///     publisher.publish_into(&msg, &mut buffer)?;
+    /// }
+    /// ```
+    /// Like [Publisher::publish] the message is only queued when this returns.
+    pub fn publish_into(&self, data: &T, buffer: &mut BytesMut) -> Result<(), PublisherError> {
+        buffer.clear();
+        roslibrust_serde_rosmsg::to_writer(&mut (&mut *buffer).writer(), &self.prepare(data))?;
+        if let Some(frame_id) = self.missing_frame_id(buffer) {
+            let rest = buffer.split_off(HEADER_FRAME_ID_OFFSET + 4);
+            buffer.truncate(HEADER_FRAME_ID_OFFSET);
+            buffer.extend_from_slice(frame_id);
+            buffer.unsplit(rest);
+            let len = (buffer.len() - 4) as u32;
+            buffer[..4].copy_from_slice(&len.to_le_bytes());
+        }
+        check_message_size(buffer, self.max_message_size)?;
+        self.fill_seq(buffer);
+        let data = buffer.split().freeze();
+        self.send(data, Priority::Normal)?;
+        debug!("Publishing data on topic {}", self.topic_name);
+        Ok(())
+    }
+
+    /// Queues an already serialized message to be sent on the related topic, without decoding and re-encoding it.
+    ///
+    /// Intended for relays and bag players which already hold the wire bytes of a `T`. The bytes are sent as is,
+    /// in the same format as [PublisherAny::publish_bytes] expects: the message body preceded by its length.
+    /// Only the length prefix is checked, it is up to the caller to make sure the bytes are actually a `T`
+    /// (e.g. by checking the md5sum they were recorded with against `T::MD5SUM`).
+    /// The [PublisherBuilder::map_before_send] hook, [PublisherBuilder::frame_id] and filling in header.seq are
+    /// not applied.
+    ///
+    /// Like [Publisher::publish] the message is only queued when this returns.
+    pub fn publish_serialized(&self, data: impl Into<Bytes>) -> Result<(), PublisherError> {
+        let data = data.into();
+        check_length_prefix(&data)?;
+        check_message_size(&data, self.max_message_size)?;
+        self.send(data, Priority::Normal)?;
+        debug!("Publishing serialized data on topic {}", self.topic_name);
+        Ok(())
+    }
+
+    /// Queues a message to be sent on the related topic, unless the queue is full.
+    ///
+    /// [Publisher::publish] never waits for space in the queue, when the queue is full the oldest message is
+    /// dropped for any subscriber that hasn't received it yet. This function instead returns
+    /// [PublisherError::QueueFull] immediately, allowing callers to detect that subscribers aren't keeping up.
+    ///
+    /// The check is best effort: other handles to the same publication may queue messages concurrently.
+    pub fn try_publish(&self, data: &T) -> Result<(), PublisherError> {
+        if self.is_queue_full() {
+            return Err(PublisherError::QueueFull);
+        }
+        let data = self.serialize(data)?;
+        self.send(data, Priority::Normal)?;
         debug!("Publishing data on topic {}", self.topic_name);
         Ok(())
     }
+
+    /// Queues a message to be sent on the related topic, waiting up to `timeout` for space in the queue.
+    ///
+    /// Returns [PublisherError::QueueFull] if the queue is still full once the timeout has elapsed.
+    /// See [Publisher::try_publish] for details.
+    pub async fn publish_timeout(&self, data: &T, timeout: Duration) -> Result<(), PublisherError> {
+        let deadline = tokio::time::Instant::now() + timeout;
+        loop {
+            match self.try_publish(data) {
+                Err(PublisherError::QueueFull) if tokio::time::Instant::now() < deadline => {
+                    tokio::time::sleep(QUEUE_POLL_INTERVAL).await;
+                }
+                res => return res,
+            }
+        }
+    }
+
+    /// Returns true if every slot in the queue holds a message that hasn't yet been sent to all subscribers.
+    pub fn is_queue_full(&self) -> bool {
+        self.sender.len() >= self.queue_size
+    }
+
+    /// Returns the number of queued messages that haven't yet been sent to all subscribers.
+    ///
+    /// Like [Publisher::is_queue_full] this counts messages queued by every handle to the publication.
+    pub fn queue_len(&self) -> usize {
+        self.sender.len()
+    }
+
+    /// Replaces the message new subscribers receive when they connect, without sending it to existing subscribers.
+    ///
+    /// Has no effect if the topic was not advertised as latching.
+    /// The next call to publish will replace this message as usual.
+    /// The message is prepared the same way as by publish, including the frame_id default and map_before_send hook.
+    pub fn set_latched_message(&self, data: &T) -> Result<(), PublisherError> {
+        let data = self.serialize(data)?;
+        self.latched.set(Some(data));
+        Ok(())
+    }
+
+    /// Drops the latched message so that new subscribers receive nothing until the next publish.
+    pub fn clear_latched(&self) {
+        self.latched.set(None);
+    }
+
+    /// Releases this publisher, and if it is the last handle to the publication un-advertises the topic.
+    ///
+    /// Dropping a Publisher tears the publication down in the background, this instead completes once the
+    /// node has unregistered the topic with the ROS master and stopped accepting subscriber connections.
+    /// If other handles to the publication still exist (e.g. from calling advertise again for the same topic)
+    /// the topic remains advertised and this returns immediately.
+    pub async fn unadvertise(self) -> Result<(), PublisherError> {
+        release_publication(self.shutdown_channel, self.unadvertised).await
+    }
 }
 
 /// A specialty publisher used when message type is not known at compile time.
@@ -71,20 +921,24 @@ pub struct PublisherAny {
     // When the last publisher for a given topic is dropped, this channel is used to signal to cleanup
     // Don't need to send a message, simply dropping the last handle lets to node know to clean up
     // Note: this has to be used because tokio::sync::broadcast doesn't have a WeakSender
-    _shutdown: tokio::sync::mpsc::Sender<()>,
+    shutdown: tokio::sync::mpsc::Sender<()>,
+    unadvertised: watch::Receiver<UnadvertiseResult>,
+    latched: LatchedMessage,
+    limiter: PublishLimiter,
+    max_message_size: usize,
     phantom: PhantomData<Bytes>,
 }
 
 impl PublisherAny {
-    pub(crate) fn new(
-        topic_name: &str,
-        sender: broadcast::Sender<Bytes>,
-        shutdown: tokio::sync::mpsc::Sender<()>,
-    ) -> Self {
+    pub(crate) fn new(topic_name: &str, handle: PublicationHandle) -> Self {
         Self {
             topic_name: topic_name.to_owned(),
-            sender,
-            _shutdown: shutdown,
+            sender: handle.sender,
+            shutdown: handle.shutdown,
+            unadvertised: handle.unadvertised,
+            latched: handle.latched,
+            limiter: handle.limiter,
+            max_message_size: handle.max_message_size,
             phantom: PhantomData,
         }
     }
@@ -107,7 +961,16 @@ impl PublisherAny {
         // actually complete when the data is sent, but merely when it is queued to be sent
         // This function could probably be non-async
         // Or we should do some significant re-work to have it only yield when the data is sent.
+        check_message_size(data.as_ref(), self.max_message_size)?;
+        if !self.limiter.admit(data.as_ref().len(), Instant::now()) {
+            trace!(
+                "Dropping message on topic {} over its limit",
+                self.topic_name
+            );
+            return Ok(());
+        }
         let bytes = Bytes::copy_from_slice(data.as_ref());
+        self.latched.set(Some(bytes.clone()));
         self.sender
             .send(bytes)
             .map_err(|_| PublisherError::StreamClosed)?;
@@ -121,12 +984,87 @@ impl PublisherAny {
     /// as it avoids any copying.
     // TODO this no longer needs to be (or should be) async
     pub async fn publish_bytes(&self, data: Bytes) -> Result<(), PublisherError> {
+        check_message_size(&data, self.max_message_size)?;
+        if !self.limiter.admit(data.len(), Instant::now()) {
+            trace!(
+                "Dropping message on topic {} over its limit",
+                self.topic_name
+            );
+            return Ok(());
+        }
+        self.latched.set(Some(data.clone()));
         self.sender
             .send(data)
             .map_err(|_| PublisherError::StreamClosed)?;
         debug!("Publishing data on topic {}", self.topic_name);
         Ok(())
     }
+
+    /// Replaces the message new subscribers receive when they connect, without sending it to existing subscribers.
+    ///
+    /// Expects the same serialized format as [PublisherAny::publish].
+    /// Has no effect if the topic was not advertised as latching.
+    pub fn set_latched_message(&self, data: impl AsRef<[u8]>) -> Result<(), PublisherError> {
+        check_message_size(data.as_ref(), self.max_message_size)?;
+        self.latched
+            .set(Some(Bytes::copy_from_slice(data.as_ref())));
+        Ok(())
+    }
+
+    /// Drops the latched message so that new subscribers receive nothing until the next publish.
+    pub fn clear_latched(&self) {
+        self.latched.set(None);
+    }
+
+    /// Releases this publisher, and if it is the last handle to the publication un-advertises the topic.
+    ///
+    /// See [Publisher::unadvertise].
+    pub async fn unadvertise(self) -> Result<(), PublisherError> {
+        release_publication(self.shutdown, self.unadvertised).await
+    }
+}
+
+/// A publisher for a message type looked up by name at runtime, see [crate::NodeHandle::advertise_by_type_name].
+///
+/// Messages are given as JSON and serialized using the codec of the registered type.
+pub struct DynamicPublisher {
+    publisher: PublisherAny,
+    message_type: RegisteredType,
+}
+
+impl DynamicPublisher {
+    pub(crate) fn new(publisher: PublisherAny, message_type: RegisteredType) -> Self {
+        Self {
+            publisher,
+            message_type,
+        }
+    }
+
+    /// The registered type this publisher sends
+    pub fn message_type(&self) -> &RegisteredType {
+        &self.message_type
+    }
+
+    /// Serializes the JSON representation of a message and queues it to be sent.
+    ///
+    /// Fails with [PublisherError::SerializingError] if the value does not match the message type.
+    pub async fn publish(&self, msg: serde_json::Value) -> Result<(), PublisherError> {
+        let data = self
+            .message_type
+            .encode_json(msg)
+            .map_err(PublisherError::SerializingError)?;
+        self.publisher.publish_bytes(Bytes::from(data)).await
+    }
+
+    /// Queues already serialized message data to be sent, see [PublisherAny::publish_bytes].
+    pub async fn publish_bytes(&self, data: Bytes) -> Result<(), PublisherError> {
+        self.publisher.publish_bytes(data).await
+    }
+
+    /// Releases this publisher, see [Publisher::unadvertise].
+    pub async fn unadvertise(self) -> Result<(), PublisherError> {
+        self.publisher.unadvertise().await
+    }
 }
 
 pub(crate) struct Publication {
@@ -134,43 +1072,56 @@ pub(crate) struct Publication {
     listener_port: u16,
     _tcp_accept_task: ChildTask<()>,
     publish_sender: broadcast::Sender<Bytes>,
+    priority_sender: broadcast::Sender<Bytes>,
+    queue_size: usize,
+    latched: LatchedMessage,
+    limiter: PublishLimiter,
+    seq: Option<Arc<AtomicU32>>,
+    max_message_size: usize,
     // We store a weak handle to the shutdown channel
     // This allows us to create new Publisher with a shutdown sender, but doesn't keep the shutdown channel alive
     // Had to add this because broadcast doesn't have a weak sender equivalent
     weak_shutdown_channel: tokio::sync::mpsc::WeakSender<()>,
+    // Tells publishers waiting in unadvertise the outcome of unregistering with the master
+    unadvertised: watch::Sender<UnadvertiseResult>,
+    // Subscriber connections and the time of the last message, see [crate::NodeHandle::stats]
+    activity: Arc<TopicActivity>,
 }
 
 impl Publication {
     /// Spawns a new publication and sets up all tasks to run it
-    /// Returns a handle to the publication and a mpsc::Sender to send messages to be published
+    /// Returns the publication and a handle containing the channel to send messages to be published on
     /// Dropping the Sender will (eventually) result in the publication being dropped and all tasks being canceled
     #[allow(clippy::too_many_arguments)]
     pub(crate) async fn new(
         node_name: &Name,
-        latching: bool,
+        options: AdvertiseOptions,
         topic_name: &str,
         host_addr: Ipv4Addr,
-        queue_size: usize,
         msg_definition: &str,
         md5sum: &str,
         topic_type: &str,
         node_handle: NodeServerHandle,
-    ) -> Result<
-        (
-            Self,
-            broadcast::Sender<Bytes>,
-            tokio::sync::mpsc::Sender<()>,
-        ),
-        std::io::Error,
-    > {
+        wire_recorder: Option<WireRecorder>,
+        supervisor: &TaskSupervisor,
+    ) -> Result<(Self, PublicationHandle), std::io::Error> {
         // Get a socket for receiving connections on
         let host_addr = SocketAddr::from((host_addr, 0));
         let tcp_listener = tokio::net::TcpListener::bind(host_addr).await?;
-        let listener_port = tcp_listener.local_addr().unwrap().port();
+        let listener_port = tcp_listener.local_addr()?.port();
 
         // Setup the channel will will receive messages to be published on
         // Using Bytes for efficient cloning (reference counted) when there are multiple subscribers
+        let AdvertiseOptions {
+            queue_size,
+            latching,
+            fill_seq,
+            max_message_size,
+            ..
+        } = options;
         let (sender, receiver) = broadcast::channel::<Bytes>(queue_size);
+        // Second queue for messages published with Priority::High
+        let (priority_sender, priority_receiver) = broadcast::channel::<Bytes>(queue_size);
 
         // Setup the ROS connection header that we'll respond to all incoming connections with
         let responding_conn_header = ConnectionHeader {
@@ -183,6 +1134,9 @@ impl Publication {
             tcp_nodelay: false,
             service: None,
             persistent: None,
+            probe: false,
+            compression: None,
+            shared_memory: false,
         };
         trace!("Publisher connection header: {responding_conn_header:?}");
 
@@ -190,41 +1144,87 @@ impl Publication {
         let (shutdown_tx, shutdown_rx) = tokio::sync::mpsc::channel(1);
         let weak_shutdown_channel = shutdown_tx.downgrade();
 
+        let (unadvertised, unadvertised_rx) = watch::channel(None);
+        let latched = LatchedMessage::new(latching);
+        let seq = fill_seq.then(|| Arc::new(AtomicU32::new(0)));
+        let activity = Arc::new(TopicActivity::default());
+        let limiter = PublishLimiter::new(&options, activity.clone());
+
         // Create the task that will accept new TCP connections
         let topic_name_copy = topic_name.to_owned();
-        let tcp_accept_handle = tokio::spawn(async move {
-            Self::tcp_accept_task(
-                tcp_listener,
-                topic_name_copy,
-                responding_conn_header,
-                receiver,
-                shutdown_rx,
-                node_handle,
-            )
-            .await
-        });
+        let latched_copy = latched.clone();
+        let activity_copy = activity.clone();
+        let supervisor_copy = supervisor.clone();
+        let tcp_accept_handle =
+            supervisor.spawn_internal(TaskKind::Publisher, topic_name, async move {
+                Self::tcp_accept_task(
+                    tcp_listener,
+                    topic_name_copy,
+                    responding_conn_header,
+                    receiver,
+                    priority_receiver,
+                    shutdown_rx,
+                    latched_copy,
+                    options,
+                    node_handle,
+                    wire_recorder,
+                    activity_copy,
+                    supervisor_copy,
+                )
+                .await
+            });
 
         let sender_copy = sender.clone();
+        let priority_sender_copy = priority_sender.clone();
         Ok((
             Self {
                 topic_type: topic_type.to_owned(),
                 _tcp_accept_task: tcp_accept_handle.into(),
                 listener_port,
                 publish_sender: sender,
+                priority_sender,
+                queue_size,
+                latched: latched.clone(),
+                limiter: limiter.clone(),
+                seq: seq.clone(),
+                max_message_size,
                 weak_shutdown_channel,
+                unadvertised,
+                activity,
+            },
+            PublicationHandle {
+                sender: sender_copy,
+                priority_sender: priority_sender_copy,
+                shutdown: shutdown_tx,
+                queue_size,
+                latched,
+                limiter,
+                seq,
+                max_message_size,
+                unadvertised: unadvertised_rx,
             },
-            sender_copy,
-            shutdown_tx,
         ))
     }
 
-    pub(crate) fn get_senders(
-        &self,
-    ) -> (broadcast::Sender<Bytes>, tokio::sync::mpsc::WeakSender<()>) {
-        (
-            self.publish_sender.clone(),
-            self.weak_shutdown_channel.clone(),
-        )
+    /// Creates a new handle to this publication
+    /// Returns None if the publication has already been shutdown
+    pub(crate) fn get_handle(&self) -> Option<PublicationHandle> {
+        Some(PublicationHandle {
+            sender: self.publish_sender.clone(),
+            priority_sender: self.priority_sender.clone(),
+            shutdown: self.weak_shutdown_channel.upgrade()?,
+            queue_size: self.queue_size,
+            latched: self.latched.clone(),
+            limiter: self.limiter.clone(),
+            seq: self.seq.clone(),
+            max_message_size: self.max_message_size,
+            unadvertised: self.unadvertised.subscribe(),
+        })
+    }
+
+    /// Called by the node once it has unregistered this publication with the master
+    pub(crate) fn set_unadvertised(&self, result: Result<(), String>) {
+        self.unadvertised.send_replace(Some(result));
     }
 
     pub(crate) fn port(&self) -> u16 {
@@ -235,21 +1235,53 @@ impl Publication {
         &self.topic_type
     }
 
+    pub(crate) fn stats(&self, topic: &str) -> TopicStats {
+        self.activity
+            .stats(topic, &self.topic_type, Some(self.publish_sender.len()))
+    }
+
     /// Wraps the functionality that the publish task will perform
     /// this task is spawned by new, and canceled when the Publication is dropped
     /// This task constantly pulls new messages from the main publish buffer and
     /// sends them to all of the TCP Streams that are connected to the topic.
+    /// Messages in the priority buffer are always sent before those in the main buffer.
+    #[allow(clippy::too_many_arguments)]
     async fn publish_task(
         mut rx: broadcast::Receiver<Bytes>, // Receives messages to publish from the main buffer of messages
+        mut priority_rx: broadcast::Receiver<Bytes>, // Receives messages published with Priority::High
         mut stream: tokio::net::TcpStream,
         topic: String,
         last_message: Option<Bytes>, // If we're latching will contain a message to send right away (stored as Bytes for cheap cloning)
+        stamp_clock: Option<Clock>, // If present header.stamp is overwritten just before each message is written
+        mut encoding: Encoding,     // How messages are written to this subscriber
+        wire_recorder: Option<WireRecorder>, // If present every message written is also recorded, before encoding
+        activity: &TopicActivity, // Counts messages skipped because this subscriber fell behind
     ) {
         let peer = stream.peer_addr();
         debug!("Publish task has started for publication: {topic} connection to {peer:?}");
+        let record = |msg: &[u8]| {
+            if let Some(recorder) = &wire_recorder {
+                let peer = peer.as_ref().map(|p| p.to_string()).unwrap_or_default();
+                recorder.record(
+                    &topic,
+                    &peer,
+                    WireDirection::Outgoing,
+                    WireRecordKind::Message,
+                    msg,
+                );
+            }
+        };
 
-        if let Some(ref last_message) = last_message {
-            let res = stream.write_all(last_message).await;
+        if let Some(mut last_message) = last_message {
+            if let Some(clock) = &stamp_clock {
+                last_message = stamp_message(&last_message, clock.now());
+            }
+            record(&last_message);
+            let res = match encoding.encode(last_message).await {
+                Ok(Some(last_message)) => stream.write_all(&last_message).await,
+                Ok(None) => Ok(()),
+                Err(e) => Err(e),
+            };
             match res {
                 Ok(_) => {}
                 Err(e) => {
@@ -258,30 +1290,95 @@ impl Publication {
             }
         }
 
-        loop {
-            match rx.recv().await {
-                Ok(msg_to_publish) => {
-                    trace!("Publish task got message to publish for topic: {topic}");
-                    let send_result = stream.write_all(&msg_to_publish[..]).await;
-                    match send_result {
-                        Ok(_) => {
-                            trace!("Publish task sent message to topic: {topic}");
-                        }
-                        Err(err) => {
-                            // Shut down this TCP connection if we can't write a whole message
-                            debug!("Failed to send data to subscriber: {err}, removing");
-                            break;
-                        }
-                    }
+        // Stamps, records and encodes a message, returning what should be written to the stream if anything
+        let mut prepare = async |mut msg_to_publish: Bytes| {
+            trace!("Publish task got message to publish for topic: {topic}");
+            if let Some(clock) = &stamp_clock {
+                msg_to_publish = stamp_message(&msg_to_publish, clock.now());
+            }
+            record(&msg_to_publish);
+            match encoding.encode(msg_to_publish).await {
+                Ok(Some(msg)) => Some(msg),
+                Ok(None) => {
+                    debug!("Subscriber {peer:?} is behind on shared memory messages, skipping one");
+                    None
+                }
+                Err(err) => {
+                    error!("Failed to encode message for {topic}: {err}");
+                    None
                 }
+            }
+        };
+
+        let mut closed = false;
+        while !closed {
+            let received = tokio::select! {
+                biased;
+                msg = priority_rx.recv() => msg,
+                msg = rx.recv() => msg,
+            };
+            let mut batch = match received {
+                Ok(msg_to_publish) => Vec::from_iter(prepare(msg_to_publish).await),
                 Err(RecvError::Lagged(num)) => {
                     debug!("TCP for peer {peer:?} is lagging behind, {num} messages were skipped");
+                    activity.dropped(num);
                     continue;
                 }
                 Err(RecvError::Closed) => {
                     debug!("No more senders for the publisher channel, ending task");
                     break;
                 }
+            };
+            // Messages that are already queued, e.g. from Publisher::publish_iter, are coalesced into one write
+            let mut batch_len = batch.iter().map(Bytes::len).sum::<usize>();
+            while batch_len < MAX_WRITE_BATCH {
+                // Checked before every message so high priority messages jump ahead of the rest of the batch
+                let next = match priority_rx.try_recv() {
+                    Err(TryRecvError::Empty) => rx.try_recv(),
+                    next => next,
+                };
+                match next {
+                    Ok(msg_to_publish) => {
+                        if let Some(msg) = prepare(msg_to_publish).await {
+                            batch_len += msg.len();
+                            batch.push(msg);
+                        }
+                    }
+                    Err(TryRecvError::Lagged(num)) => {
+                        debug!(
+                            "TCP for peer {peer:?} is lagging behind, {num} messages were skipped"
+                        );
+                        activity.dropped(num);
+                    }
+                    Err(TryRecvError::Empty) => break,
+                    Err(TryRecvError::Closed) => {
+                        debug!("No more senders for the publisher channel, ending task");
+                        closed = true;
+                        break;
+                    }
+                }
+            }
+            let send_result = match batch.len() {
+                0 => continue,
+                1 => stream.write_all(&batch[0]).await,
+                _ => {
+                    let mut buffer = BytesMut::with_capacity(batch_len);
+                    batch.iter().for_each(|msg| buffer.extend_from_slice(msg));
+                    stream.write_all(&buffer).await
+                }
+            };
+            match send_result {
+                Ok(_) => {
+                    trace!(
+                        "Publish task sent {} message(s) to topic: {topic}",
+                        batch.len()
+                    );
+                }
+                Err(err) => {
+                    // Shut down this TCP connection if we can't write a whole message
+                    debug!("Failed to send data to subscriber: {err}, removing");
+                    break;
+                }
             }
         }
         debug!("Publish task has exited for publication: {topic} connection to {peer:?}");
@@ -290,17 +1387,22 @@ impl Publication {
     /// Wraps the functionality that the tcp_accept task will perform
     /// This task is spawned by new, and canceled when the Publication is dropped
     /// This task constantly accepts new TCP connections and adds them to the list of streams to send data to.
+    #[allow(clippy::too_many_arguments)]
     async fn tcp_accept_task(
         tcp_listener: tokio::net::TcpListener, // The TCP listener to accept connections on
         topic_name: String,                    // Only used for logging
         responding_conn_header: ConnectionHeader, // Header we respond with
         mut rx: broadcast::Receiver<Bytes>, // Receives messages to publish from the main buffer of messages
+        mut priority_rx: broadcast::Receiver<Bytes>, // Receives messages published with Priority::High
         mut shutdown_rx: tokio::sync::mpsc::Receiver<()>, // Channel to signal to the publication to clean itself up
+        latched: LatchedMessage, // Message to send to new subscribers, kept up to date by our publishers
+        options: AdvertiseOptions, // Decides how messages are written to each subscriber
         nh: NodeServerHandle,
+        wire_recorder: Option<WireRecorder>, // If present connection headers and messages are recorded
+        activity: Arc<TopicActivity>,        // Updated with each message and subscriber connection
+        supervisor: TaskSupervisor,          // Runs the task writing to each subscriber
     ) {
         debug!("TCP accept task has started for publication: {topic_name}");
-        // Store latching message as Bytes for cheap cloning when new subscribers connect
-        let mut last_message: Option<Bytes> = None;
         loop {
             let result = tokio::select! {
                 shutdown = shutdown_rx.recv() => {
@@ -309,7 +1411,9 @@ impl Publication {
                         None => debug!("TCP accept task has received shutdown signal for publication: {topic_name}"),
                     }
                     // Notify our Node that we're shutting down
-                    nh.unregister_publisher(&topic_name).await.unwrap();
+                    if let Err(e) = nh.unregister_publisher(&topic_name).await {
+                        error!("Failed to unregister publisher for {topic_name}: {e}");
+                    }
                     // Exit our loop and shutdown this task
                     break;
                 }
@@ -317,16 +1421,11 @@ impl Publication {
                     // Process the new TCP connection
                     result
                 },
-                // TODO this can be optimized
-                // We shouldn't even call recv() if we're not latching
+                // Our root receivers have to be drained so that they don't hold messages in the queue
+                // The latched message is recorded by the publishers themselves
                 msg = rx.recv() => {
                     match msg {
-                        Ok(msg) =>{
-                            // If we're latching save the message
-                            if responding_conn_header.latching {
-                              last_message = Some(msg);
-                            }
-                        },
+                        Ok(msg) => activity.message(msg.len()),
                         Err(RecvError::Lagged(num)) => {
                             debug!("TCP accept task for {topic_name} is lagging behind, {num} messages were skipped");
                             continue;
@@ -338,9 +1437,23 @@ impl Publication {
                     }
                     continue;
                 }
+                msg = priority_rx.recv() => {
+                    match msg {
+                        Ok(msg) => activity.message(msg.len()),
+                        Err(RecvError::Lagged(num)) => {
+                            debug!("TCP accept task for {topic_name} is lagging behind, {num} priority messages were skipped");
+                            continue;
+                        }
+                        Err(RecvError::Closed) => {
+                            debug!("No more senders for the publisher channel, ending task");
+                            break;
+                        }
+                    }
+                    continue;
+                }
             };
 
-            let (mut stream, peer_addr) = match result {
+            let (stream, peer_addr) = match result {
                 Ok(result) => result,
                 Err(e) => {
                     error!("Error accepting TCP connection for topic {topic_name}: {e:?}");
@@ -349,74 +1462,204 @@ impl Publication {
             };
 
             info!("Received connection from subscriber at {peer_addr} for topic {topic_name}");
-            // Read the connection header:
-            let connection_header = match tcpros::receive_header(&mut stream).await {
-                Ok(header) => header,
-                Err(e) => {
-                    error!("Failed to read connection header: {e:?}");
-                    stream
-                        .shutdown()
-                        .await
-                        .expect("Unable to shutdown tcpstream");
-                    continue;
-                }
-            };
-
-            debug!(
-                "Received subscribe request for {:?} with md5sum {:?}",
-                connection_header.topic, connection_header.md5sum
+            // The handshake happens in the connection's own task so a slow or misbehaving subscriber
+            // can't hold up or take down the publication
+            supervisor.spawn_internal(
+                TaskKind::Publisher,
+                &topic_name,
+                Self::subscriber_task(
+                    stream,
+                    peer_addr.to_string(),
+                    topic_name.clone(),
+                    responding_conn_header.clone(),
+                    // Note: we continue to hold on to a root "rx" in this accept task that means that we
+                    // always keep the channel open from the receive side.
+                    rx.resubscribe(),
+                    priority_rx.resubscribe(),
+                    latched.clone(),
+                    options.clone(),
+                    wire_recorder.clone(),
+                    activity.clone(),
+                    supervisor.clone(),
+                ),
             );
-            // I can't find documentation for this anywhere, but when using
-            // `rostopic hz` with one of our publishers I discovered that the rospy code sent "*" as the md5sum
-            // To indicate a "generic subscription"...
-            // I also discovered that `rostopic echo` does not send a md5sum (even thou ros documentation says its required)
-            if let Some(connection_md5sum) = connection_header.md5sum {
-                if connection_md5sum != "*" {
-                    if let Some(local_md5sum) = &responding_conn_header.md5sum {
-                        // TODO(lucasw) is it ok to match any with "*"?
-                        // if local_md5sum != "*" && connection_md5sum != *local_md5sum {
-                        if connection_md5sum != *local_md5sum {
-                            warn!(
-                                    "Got subscribe request for {}, but md5sums do not match. Expected {:?}, received {:?}",
-                                    topic_name,
-                                    local_md5sum,
-                                    connection_md5sum,
-                                    );
-                            // Close the TCP connection
-                            stream
-                                .shutdown()
-                                .await
-                                .expect("Unable to shutdown tcpstream");
-                            continue;
-                        }
-                    }
-                }
-            }
-            // Write our own connection header in response
-            let response_header_bytes = responding_conn_header
-                .to_bytes(false)
-                .expect("Couldn't serialize connection header");
-            stream
-                .write_all(&response_header_bytes[..])
-                .await
-                .expect("Unable to respond on tcpstream");
+        }
+    }
 
-            // Create a new task to handle writing to the TCP stream
-            // Note: we continue to hold on to a root "rx" in this accept task that means that we
-            // always keep the channel open from the receive side.
-            let rx_copy = rx.resubscribe();
-            let topic_name_copy = topic_name.clone();
+    /// Performs the TCPROS handshake with a newly connected subscriber then publishes to it until it disconnects.
+    /// A failed handshake closes the connection and is reported as a [ConnectionEvent::Failed].
+    #[allow(clippy::too_many_arguments)]
+    async fn subscriber_task(
+        mut stream: tokio::net::TcpStream,
+        peer: String,
+        topic_name: String,
+        responding_conn_header: ConnectionHeader,
+        rx: broadcast::Receiver<Bytes>,
+        priority_rx: broadcast::Receiver<Bytes>,
+        latched: LatchedMessage,
+        options: AdvertiseOptions,
+        wire_recorder: Option<WireRecorder>,
+        activity: Arc<TopicActivity>,
+        supervisor: TaskSupervisor,
+    ) {
+        if options.tcp_nodelay {
+            if let Err(e) = stream.set_nodelay(true) {
+                warn!("Failed to set tcp_nodelay on connection from subscriber at {peer} for topic {topic_name}: {e}");
+            }
+        }
+        let encoding = match Self::handshake(
+            &mut stream,
+            &peer,
+            &topic_name,
+            &responding_conn_header,
+            &options,
+            wire_recorder.as_ref(),
+        )
+        .await
+        {
+            Ok(encoding) => encoding,
+            Err(e) => {
+                warn!("Closing connection from subscriber at {peer} for topic {topic_name}: {e}");
+                let _ = stream.shutdown().await;
+                supervisor.connection_event(ConnectionEvent::Failed {
+                    name: topic_name,
+                    kind: TaskKind::Publisher,
+                    peer,
+                    error: e.to_string(),
+                });
+                return;
+            }
+        };
+        debug!("Added stream for topic {topic_name} to subscriber {peer}");
+        supervisor.connection_event(ConnectionEvent::Connected {
+            name: topic_name.clone(),
+            kind: TaskKind::Publisher,
+            peer,
+        });
+        let _connection = activity.connected();
+        Self::publish_task(
+            rx,
+            priority_rx,
+            stream,
+            topic_name,
             // Cloning Bytes is cheap (just increments ref count)
-            let last_message_copy = last_message.clone();
-            tokio::spawn(async move {
-                Self::publish_task(rx_copy, stream, topic_name_copy, last_message_copy).await;
-            });
+            latched.get(),
+            options.stamp_on_send.then(|| options.clock.clone()),
+            encoding,
+            wire_recorder,
+            &activity,
+        )
+        .await;
+    }
 
-            debug!(
-                "Added stream for topic {:?} to subscriber {}",
-                connection_header.topic, peer_addr
+    /// Reads a subscriber's connection header and responds with ours, returning how messages will be written to it
+    async fn handshake(
+        stream: &mut tokio::net::TcpStream,
+        peer: &str,
+        topic_name: &str,
+        responding_conn_header: &ConnectionHeader,
+        options: &AdvertiseOptions,
+        wire_recorder: Option<&WireRecorder>,
+    ) -> std::io::Result<Encoding> {
+        let header_bytes = tcpros::receive_header_bytes(stream).await?;
+        if let Some(recorder) = wire_recorder {
+            recorder.record_received_header(topic_name, peer, &header_bytes);
+        }
+        let connection_header = ConnectionHeader::from_bytes(&header_bytes)?;
+
+        debug!(
+            "Received subscribe request for {:?} with md5sum {:?}",
+            connection_header.topic, connection_header.md5sum
+        );
+        // I can't find documentation for this anywhere, but when using
+        // `rostopic hz` with one of our publishers I discovered that the rospy code sent "*" as the md5sum
+        // To indicate a "generic subscription"...
+        // I also discovered that `rostopic echo` does not send a md5sum (even thou ros documentation says its required)
+        if let (Some(connection_md5sum), Some(local_md5sum)) =
+            (&connection_header.md5sum, &responding_conn_header.md5sum)
+        {
+            // TODO(lucasw) is it ok to match any with "*"?
+            // if local_md5sum != "*" && connection_md5sum != *local_md5sum {
+            if connection_md5sum != "*" && connection_md5sum != local_md5sum {
+                return Err(std::io::Error::new(
+                    std::io::ErrorKind::InvalidData,
+                    format!(
+                        "md5sums do not match, expected {local_md5sum}, received {connection_md5sum}. {}",
+                        responding_conn_header.describe_definition_mismatch(&connection_header)
+                    ),
+                ));
+            }
+        }
+
+        // Write our own connection header in response, telling the subscriber how we'll encode messages
+        let encoding = Encoding::negotiate(options, &connection_header, stream);
+        let response_header = ConnectionHeader {
+            compression: match &encoding {
+                Encoding::Compressed(compression) => Some(compression.as_str().to_owned()),
+                _ => None,
+            },
+            shared_memory: matches!(encoding, Encoding::SharedMemory(_)),
+            ..responding_conn_header.clone()
+        };
+        let response_header_bytes = response_header.to_bytes(false)?;
+        if let Some(recorder) = wire_recorder {
+            recorder.record(
+                topic_name,
+                peer,
+                WireDirection::Outgoing,
+                WireRecordKind::ConnectionHeader,
+                &response_header_bytes,
             );
         }
+        stream.write_all(&response_header_bytes[..]).await?;
+        Ok(encoding)
+    }
+}
+
+// How messages are written to a subscriber's connection, negotiated when it connects
+enum Encoding {
+    Plain,
+    Compressed(Compression),
+    SharedMemory(ShmWriter),
+}
+
+impl Encoding {
+    fn negotiate(
+        options: &AdvertiseOptions,
+        connection_header: &ConnectionHeader,
+        stream: &tokio::net::TcpStream,
+    ) -> Self {
+        let same_host = matches!(
+            (stream.local_addr(), stream.peer_addr()),
+            (Ok(local), Ok(peer)) if local.ip() == peer.ip()
+        );
+        if options.shared_memory
+            && connection_header.shared_memory
+            && shm::is_available()
+            && same_host
+        {
+            match ShmWriter::new(options.queue_size) {
+                Ok(writer) => return Encoding::SharedMemory(writer),
+                Err(e) => warn!("Unable to use shared memory, falling back to TCPROS: {e}"),
+            }
+        }
+        options
+            .compression
+            .and_then(|compression| {
+                compression::negotiate(compression, connection_header.compression.as_deref())
+            })
+            .map_or(Encoding::Plain, Encoding::Compressed)
+    }
+
+    // Returns what to write in place of the message, None if it has to be skipped
+    async fn encode(&mut self, msg: Bytes) -> std::io::Result<Option<Bytes>> {
+        match self {
+            Encoding::Plain => Ok(Some(msg)),
+            Encoding::Compressed(compression) => {
+                compression::compress(*compression, &msg).map(Some)
+            }
+            Encoding::SharedMemory(writer) => writer.write(msg).await,
+        }
     }
 }
 
@@ -433,6 +1676,12 @@ pub enum PublisherError {
     SerializingError(String),
     #[error("connection closed, no further messages can be sent")]
     StreamClosed,
+    #[error("publish queue is full, subscribers are not keeping up")]
+    QueueFull,
+    #[error("failed to unadvertise topic: {0}")]
+    UnadvertiseFailed(String),
+    #[error("message of {size} bytes exceeds the maximum message size of {max} bytes")]
+    MessageTooLarge { size: usize, max: usize },
 }
 
 impl From<roslibrust_serde_rosmsg::Error> for PublisherError {
@@ -440,3 +1689,96 @@ impl From<roslibrust_serde_rosmsg::Error> for PublisherError {
         Self::SerializingError(value.to_string())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn checks_length_prefix() {
+        assert!(check_length_prefix(&[4, 0, 0, 0, 1, 2, 3, 4]).is_ok());
+        assert!(check_length_prefix(&[0, 0, 0, 0]).is_ok());
+        assert!(check_length_prefix(&[5, 0, 0, 0, 1, 2, 3, 4]).is_err());
+        assert!(check_length_prefix(&[4, 0, 0]).is_err());
+    }
+
+    #[test]
+    fn detects_leading_header() {
+        assert!(starts_with_header("Header header\nfloat64 x\n"));
+        assert!(starts_with_header(
+            "# A comment first\n\nstd_msgs/Header header # trailing\nfloat64 x\n"
+        ));
+        assert!(!starts_with_header("float64 x\nHeader header\n"));
+        assert!(!starts_with_header("string data\n"));
+        assert!(!starts_with_header(""));
+    }
+
+    #[test]
+    fn refuses_messages_over_limit() {
+        let msg = [4, 0, 0, 0, 1, 2, 3, 4];
+        assert!(check_message_size(&msg, 4).is_ok());
+        assert!(matches!(
+            check_message_size(&msg, 3),
+            Err(PublisherError::MessageTooLarge { size: 4, max: 3 })
+        ));
+    }
+
+    #[test]
+    fn limits_bandwidth_with_burst() {
+        let activity = Arc::new(TopicActivity::default());
+        let limiter = PublishLimiter::new(
+            &AdvertiseOptions::new(1).bandwidth_limit(1000.0, 2000.0),
+            activity.clone(),
+        );
+        let start = Instant::now();
+        let at = |millis| start + Duration::from_millis(millis);
+        // The whole burst is available straight away
+        assert!(limiter.admit(1500, at(0)));
+        assert!(!limiter.admit(1000, at(0)));
+        assert!(limiter.admit(500, at(0)));
+        // Then refills at the sustained rate
+        assert!(!limiter.admit(600, at(500)));
+        assert!(limiter.admit(500, at(500)));
+        // A message larger than the burst waits for a full bucket, then has to be paid back
+        assert!(!limiter.admit(5000, at(1500)));
+        assert!(limiter.admit(5000, at(2500)));
+        assert!(!limiter.admit(100, at(5000)));
+        assert!(limiter.admit(100, at(5600)));
+        assert_eq!(activity.stats("/image", "", None).dropped_count, 4);
+    }
+
+    #[test]
+    fn limits_message_rate() {
+        let start = Instant::now();
+        let unlimited = PublishLimiter::new(&AdvertiseOptions::new(1), Arc::default());
+        assert!((0..100).all(|_| unlimited.admit(1_000_000, start)));
+
+        let limiter = PublishLimiter::new(
+            &AdvertiseOptions::new(1).rate_limit(10.0, 2.0),
+            Arc::default(),
+        );
+        assert!(limiter.admit(1, start));
+        assert!(limiter.admit(1, start));
+        assert!(!limiter.admit(1, start));
+        assert!(limiter.admit(1, start + Duration::from_millis(100)));
+        assert!(!limiter.admit(1, start + Duration::from_millis(150)));
+    }
+
+    #[test]
+    fn stamps_serialized_header() {
+        // length, seq, stamp secs, stamp nsecs, empty frame_id
+        let mut msg = vec![16, 0, 0, 0];
+        msg.extend_from_slice(&[7, 0, 0, 0]);
+        msg.extend_from_slice(&[0; 8]);
+        msg.extend_from_slice(&[0; 4]);
+        let now = UNIX_EPOCH + Duration::new(5, 6);
+        let stamped = stamp_message(&Bytes::from(msg.clone()), now);
+        assert_eq!(stamped.len(), msg.len());
+        // Only the stamp should have changed
+        assert_eq!(stamped[..8], msg[..8]);
+        assert_eq!(stamped[16..], msg[16..]);
+        assert_eq!(stamped[8..16], [5, 0, 0, 0, 6, 0, 0, 0]);
+        assert_eq!(header_stamp(&stamped), Some(now));
+        assert_eq!(header_stamp(&stamped[..15]), None);
+    }
+}
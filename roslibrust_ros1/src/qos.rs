@@ -0,0 +1,168 @@
+// Emulation of ROS2's deadline and liveliness QoS policies, which ROS1 has no equivalent of.
+// Liveliness is asserted by publishers sending heartbeats on a side topic, so only works between roslibrust nodes.
+
+use crate::{subscriber::ReceivedMessage, Publisher, Subscriber};
+use roslibrust_common::rosout::{Header, Time};
+use roslibrust_common::RosMessageType;
+use std::{
+    collections::HashMap,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tokio::{
+    sync::broadcast::{self, error::RecvError},
+    task::JoinHandle,
+    time::Instant,
+};
+
+/// Whether a publisher is still asserting its liveliness, reported by
+/// [SubscriberBuilder::on_liveliness_changed](crate::SubscriberBuilder::on_liveliness_changed)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Liveliness {
+    /// A heartbeat has been received from the publisher
+    Alive,
+    /// No heartbeat has been received from the publisher within the lease duration
+    NotAlive,
+}
+
+pub(crate) type DeadlineCallback = Arc<dyn Fn(Duration) + Send + Sync>;
+pub(crate) type LivelinessCallback = Arc<dyn Fn(&str, Liveliness) + Send + Sync>;
+
+/// A background task sending heartbeats or watching for messages, aborted when dropped
+pub(crate) struct QosTask(JoinHandle<()>);
+
+impl QosTask {
+    /// Stops the task, waiting until it has dropped the receiver or subscriber it holds
+    pub(crate) async fn stop(mut self) {
+        self.0.abort();
+        let _ = (&mut self.0).await;
+    }
+}
+
+impl Drop for QosTask {
+    fn drop(&mut self) {
+        self.0.abort();
+    }
+}
+
+/// Topic the heartbeats of publishers on `topic` are sent on
+pub(crate) fn heartbeat_topic(topic: &str) -> String {
+    format!("{}/heartbeat", topic.trim_end_matches('/'))
+}
+
+/// Equivalent of `std_msgs/Header`, published on a topic's heartbeat topic
+#[derive(::serde::Deserialize, ::serde::Serialize, Debug, Default, Clone, PartialEq)]
+pub(crate) struct Heartbeat {
+    header: Header,
+}
+
+impl RosMessageType for Heartbeat {
+    const ROS_TYPE_NAME: &'static str = "std_msgs/Header";
+    const MD5SUM: &'static str = "2176decaecbce78abc3b96ef049fabed";
+    const DEFINITION: &'static str = r####"# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data
+# in a particular coordinate frame.
+#
+# sequence ID: consecutively increasing ID
+uint32 seq
+#Two-integer timestamp that is expressed as:
+# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
+# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
+# time-handling sugar is provided by the client library
+time stamp
+#Frame this data is associated with
+string frame_id"####;
+}
+
+/// Publishes a heartbeat every `period` until the returned task is dropped
+pub(crate) fn publish_heartbeats(publisher: Publisher<Heartbeat>, period: Duration) -> QosTask {
+    QosTask(tokio::spawn(async move {
+        let mut interval = tokio::time::interval(period);
+        let mut seq: u32 = 0;
+        loop {
+            interval.tick().await;
+            let now = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default();
+            let heartbeat = Heartbeat {
+                header: Header {
+                    seq,
+                    stamp: Time {
+                        secs: now.as_secs() as u32,
+                        nsecs: now.subsec_nanos(),
+                    },
+                    frame_id: String::new(),
+                },
+            };
+            if let Err(e) = publisher.publish(&heartbeat).await {
+                log::error!("Failed to publish heartbeat: {e}");
+            }
+            seq = seq.wrapping_add(1);
+        }
+    }))
+}
+
+/// Calls `callback` with the time since the last message each time `period` passes without one arriving,
+/// until the returned task is dropped
+pub(crate) fn monitor_deadline(
+    mut receiver: broadcast::Receiver<ReceivedMessage>,
+    period: Duration,
+    callback: DeadlineCallback,
+) -> QosTask {
+    QosTask(tokio::spawn(async move {
+        let mut last_message = Instant::now();
+        let mut deadline = last_message + period;
+        loop {
+            match tokio::time::timeout_at(deadline, receiver.recv()).await {
+                // Messages were still received if the monitor fell behind
+                Ok(Ok(_)) | Ok(Err(RecvError::Lagged(_))) => {
+                    last_message = Instant::now();
+                    deadline = last_message + period;
+                }
+                Ok(Err(RecvError::Closed)) => break,
+                Err(_elapsed) => {
+                    callback(last_message.elapsed());
+                    deadline += period;
+                }
+            }
+        }
+    }))
+}
+
+/// Tracks the heartbeats of each publisher, calling `callback` when one becomes alive or misses its lease,
+/// until the returned task is dropped
+pub(crate) fn monitor_liveliness(
+    mut heartbeats: Subscriber<Heartbeat>,
+    lease: Duration,
+    callback: LivelinessCallback,
+) -> QosTask {
+    QosTask(tokio::spawn(async move {
+        // Caller id of each live publisher -> when its last heartbeat arrived
+        let mut last_seen: HashMap<String, Instant> = HashMap::new();
+        loop {
+            let expiry = last_seen.values().min().map(|seen| *seen + lease);
+            tokio::select! {
+                heartbeat = heartbeats.next_stamped() => match heartbeat {
+                    Some(Ok(heartbeat)) => {
+                        log::trace!("Heartbeat {} from {}", heartbeat.msg.header.seq, heartbeat.caller_id);
+                        if last_seen.insert(heartbeat.caller_id.clone(), Instant::now()).is_none() {
+                            callback(&heartbeat.caller_id, Liveliness::Alive);
+                        }
+                    }
+                    Some(Err(e)) => log::warn!("Error receiving heartbeat: {e}"),
+                    None => break,
+                },
+                _ = tokio::time::sleep_until(expiry.unwrap_or_else(Instant::now)), if expiry.is_some() => {
+                    let now = Instant::now();
+                    last_seen.retain(|caller_id, seen| {
+                        let alive = now < *seen + lease;
+                        if !alive {
+                            callback(caller_id, Liveliness::NotAlive);
+                        }
+                        alive
+                    });
+                }
+            }
+        }
+    }))
+}
@@ -1,10 +1,11 @@
 use crate::{
     names::Name,
+    supervisor::{TaskKind, TaskSupervisor},
     tcpros::{establish_connection, ConnectionHeader},
 };
 use abort_on_drop::ChildTask;
 use bytes::Bytes;
-use roslibrust_common::{Error, RosServiceType};
+use roslibrust_common::{CancellationToken, Error, RosServiceType};
 use std::{marker::PhantomData, sync::Arc};
 use tokio::{
     io::{AsyncReadExt, AsyncWriteExt},
@@ -75,6 +76,21 @@ impl<T: RosServiceType> ServiceClient<T> {
             Err(_err) => Err(Error::Disconnected),
         }
     }
+
+    /// Calls the service, returning [Error::Cancelled] if `cancel` is cancelled before the response arrives.
+    ///
+    /// A call cancelled while queued behind other calls on this client is never sent to the service.
+    /// One already sent still has its response read, so the connection stays usable for later calls.
+    pub async fn call_with_cancel(
+        &self,
+        request: &T::Request,
+        cancel: &CancellationToken,
+    ) -> std::result::Result<T::Response, Error> {
+        cancel
+            .run_until_cancelled(self.call(request))
+            .await
+            .unwrap_or(Err(Error::Cancelled))
+    }
 }
 
 pub struct ServiceClientLink {
@@ -83,6 +99,7 @@ pub struct ServiceClientLink {
 }
 
 impl ServiceClientLink {
+    #[allow(clippy::too_many_arguments)]
     pub async fn new(
         node_name: &Name,
         service_name: &str,
@@ -90,6 +107,8 @@ impl ServiceClientLink {
         service_uri: &str,
         srv_definition: &str,
         md5sum: &str,
+        max_message_size: usize,
+        supervisor: &TaskSupervisor,
     ) -> roslibrust_common::Result<Self> {
         let header = ConnectionHeader {
             caller_id: node_name.to_string(),
@@ -104,6 +123,9 @@ impl ServiceClientLink {
             tcp_nodelay: false,
             // We do want a persistent connection to our service clients
             persistent: Some(true),
+            probe: false,
+            compression: None,
+            shared_memory: false,
         };
 
         let (call_tx, call_rx) = mpsc::unbounded_channel::<CallServiceRequest>();
@@ -113,9 +135,11 @@ impl ServiceClientLink {
             Error::from(err)
         })?;
 
-        let actor_context = Self::actor_context(stream, service_name.to_owned(), call_rx);
+        let actor_context =
+            Self::actor_context(stream, service_name.to_owned(), max_message_size, call_rx);
 
-        let handle = tokio::spawn(actor_context);
+        let handle =
+            supervisor.spawn_internal(TaskKind::ServiceClient, service_name, actor_context);
 
         Ok(Self {
             call_sender: call_tx,
@@ -130,11 +154,18 @@ impl ServiceClientLink {
     async fn actor_context(
         mut stream: TcpStream,
         service_name: String,
+        max_message_size: usize,
         mut call_rx: UnboundedReceiver<CallServiceRequest>,
     ) {
         // Listen on a receiver for calls to forward to the service
         while let Some(request) = call_rx.recv().await {
-            Self::handle_service_call(&mut stream, &service_name, request).await
+            if request.1.is_closed() {
+                log::debug!(
+                    "Skipping call to service {service_name} which was cancelled while queued"
+                );
+                continue;
+            }
+            Self::handle_service_call(&mut stream, &service_name, max_message_size, request).await
         }
     }
 
@@ -143,18 +174,20 @@ impl ServiceClientLink {
     async fn handle_service_call(
         stream: &mut TcpStream,
         service_name: &str,
+        max_message_size: usize,
         (request, response_sender): CallServiceRequest,
     ) {
-        let response = Self::handle_service_call_fallible(stream, request).await;
+        let response = Self::handle_service_call_fallible(stream, request, max_message_size).await;
         let response: roslibrust_common::Result<Bytes> = response.map_err(|err| {
             log::error!(
                 "Failed to send and receive service call for service {service_name}: {err:?}"
             );
-            Error::from(err)
+            tcpros::into_error(err)
         });
         let send_result = response_sender.send(response);
         if let Err(_err) = send_result {
-            log::error!("Failed to send service call result back to handle for service {service_name}, channel closed");
+            // Expected when the caller was cancelled while the call was in flight
+            log::debug!("Caller of service {service_name} stopped waiting for the result");
         }
     }
 
@@ -164,6 +197,7 @@ impl ServiceClientLink {
     async fn handle_service_call_fallible(
         stream: &mut TcpStream,
         request: Vec<u8>,
+        max_message_size: usize,
     ) -> Result<Bytes, std::io::Error> {
         // Send the bytes of the request to the service
         stream.write_all(&request).await?;
@@ -185,11 +219,11 @@ impl ServiceClientLink {
 
         if success {
             // Parse length of the payload body
-            let body = tcpros::receive_body(stream).await?;
+            let body = tcpros::receive_body(stream, max_message_size).await?;
             Ok(body)
         } else {
             // Parse an error message as the body
-            let error_body = tcpros::receive_body(stream).await?;
+            let error_body = tcpros::receive_body(stream, max_message_size).await?;
             let err_msg: String =
                 roslibrust_serde_rosmsg::from_slice(&error_body).map_err(|err| {
                     log::error!("Failed to parse service call error message: {err}");
@@ -1,44 +1,131 @@
 use std::{
     net::{Ipv4Addr, SocketAddr},
-    sync::Arc,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc,
+    },
+    time::Duration,
 };
 
 use abort_on_drop::ChildTask;
 use log::*;
 use tokio::io::AsyncWriteExt;
 
+use crate::supervisor::{ConnectionEvent, TaskKind, TaskSupervisor};
 use crate::tcpros::{self, ConnectionHeader};
 
-use super::{names::Name, NodeHandle, TypeErasedCallback};
+use super::{names::Name, NodeError, NodeHandle, ServiceCallStats, TypeErasedCallback};
 
-/// ServiceServer is simply a lifetime control
-/// The underlying ServiceServer is kept alive while object is kept alive.
-/// Dropping this object, un-advertises the underlying service with rosmaster
-// TODO: is this actually the API we want to provide?
-// Maybe we should just let people manually call an unadvertise_service method?
-pub struct ServiceServer {
+/// Previous name of [ServiceServerHandle], kept so existing code continues to compile
+pub type ServiceServer = ServiceServerHandle;
+
+/// Returned by [NodeHandle::advertise_service], the service is hosted for as long as this handle is kept alive.
+///
+/// Dropping the handle, or calling [ServiceServerHandle::stop], un-advertises the service with rosmaster.
+/// The handle also reports statistics about the requests the service has handled.
+pub struct ServiceServerHandle {
     service_name: Name,
     node_handle: NodeHandle,
+    stats: Arc<ServiceStats>,
+    // Set once stop() has un-advertised the service so drop doesn't do it again
+    stopped: bool,
 }
 
-impl ServiceServer {
-    pub fn new(service_name: Name, node_handle: NodeHandle) -> Self {
+impl ServiceServerHandle {
+    pub(crate) fn new(
+        service_name: Name,
+        node_handle: NodeHandle,
+        stats: Arc<ServiceStats>,
+    ) -> Self {
         Self {
             service_name,
             node_handle,
+            stats,
+            stopped: false,
         }
     }
+
+    /// Number of requests the service has handled, including those which returned an error
+    pub fn request_count(&self) -> u64 {
+        self.stats.request_count.load(Ordering::Relaxed)
+    }
+
+    /// Number of requests for which the service returned an error or panicked
+    pub fn error_count(&self) -> u64 {
+        self.stats.error_count.load(Ordering::Relaxed)
+    }
+
+    /// Average time taken to handle a request, from receiving it to having the response ready.
+    /// None until the first request has been handled.
+    pub fn average_latency(&self) -> Option<Duration> {
+        self.stats.average_latency()
+    }
+
+    /// Un-advertises the service, completing once rosmaster has been informed.
+    pub async fn stop(mut self) -> Result<(), NodeError> {
+        self.stopped = true;
+        self.node_handle
+            .stop_service_server(self.service_name.as_str())
+            .await
+    }
 }
 
-impl Drop for ServiceServer {
+impl Drop for ServiceServerHandle {
     fn drop(&mut self) {
+        if self.stopped {
+            return;
+        }
         debug!("Dropping service server: {:?}", self.service_name);
         let _ = self
             .node_handle
-            .unadvertise_service_server(&self.service_name.to_string());
+            .unadvertise_service_server(self.service_name.as_str());
     }
 }
 
+/// Counters updated by a service server's connection tasks and read by its [ServiceServerHandle]
+#[derive(Default)]
+pub(crate) struct ServiceStats {
+    request_count: AtomicU64,
+    error_count: AtomicU64,
+    // Sum of the time taken to handle every request
+    handling_nanos: AtomicU64,
+}
+
+impl ServiceStats {
+    fn record(&self, handling_time: Duration, is_error: bool) {
+        if is_error {
+            self.error_count.fetch_add(1, Ordering::Relaxed);
+        }
+        self.request_count.fetch_add(1, Ordering::Relaxed);
+        self.handling_nanos.fetch_add(
+            handling_time.as_nanos().try_into().unwrap_or(u64::MAX),
+            Ordering::Release,
+        );
+    }
+
+    fn average_latency(&self) -> Option<Duration> {
+        // Loaded in the opposite order they are stored in, so the count is never behind the total
+        let total_nanos = self.handling_nanos.load(Ordering::Acquire);
+        let count = self.request_count.load(Ordering::Relaxed);
+        (count > 0).then(|| Duration::from_nanos(total_nanos / count))
+    }
+}
+
+/// Describes a service advertised by a node, returned by [NodeHandle::services]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ServiceInfo {
+    /// Name of the service as registered with the ROS master
+    pub name: String,
+    /// Name of the service type e.g. "std_srvs/Trigger"
+    pub service_type: String,
+    /// md5sum of the service type
+    pub md5sum: String,
+    /// The rosrpc:// uri the service is reachable at, as registered with the ROS master
+    pub uri: String,
+    /// Number of requests the service has handled since it was advertised
+    pub request_count: u64,
+}
+
 /// Internal type held by the NodeServer to keep track of a given service server
 pub(crate) struct ServiceServerLink {
     // Handle to internal task that is accepting and processing new requests
@@ -46,6 +133,10 @@ pub(crate) struct ServiceServerLink {
     _child_task: ChildTask<()>,
     port: u16,
     service_name: String,
+    service_type: String,
+    md5sum: String,
+    // Updated by the connection tasks each time a request is handled
+    stats: Arc<ServiceStats>,
 }
 
 impl Drop for ServiceServerLink {
@@ -55,6 +146,7 @@ impl Drop for ServiceServerLink {
 }
 
 impl ServiceServerLink {
+    #[allow(clippy::too_many_arguments)]
     pub(crate) async fn new(
         method: Box<TypeErasedCallback>,
         host_addr: Ipv4Addr,
@@ -63,6 +155,8 @@ impl ServiceServerLink {
         service_type: String, // name of the message type e.g. "std_srvs/Trigger"
         md5sum: String,       // md5sum of the service message type
         srv_definition: String, // Full text of the service message type definition
+        max_message_size: usize, // Largest request accepted from clients
+        supervisor: &TaskSupervisor,
     ) -> Result<Self, std::io::Error> {
         // TODO A lot of this is duplicated with publisher
         // We could probably move chunks into tcpros.rs and re-use
@@ -70,26 +164,36 @@ impl ServiceServerLink {
         // Setup a socket for receiving service requests on:
         let host_addr = SocketAddr::from((host_addr, 0));
         let tcp_listener = tokio::net::TcpListener::bind(host_addr).await?;
-        let port = tcp_listener
-            .local_addr()
-            .expect("Bound tcp address did not have local address")
-            .port();
+        let port = tcp_listener.local_addr()?.port();
         let service_name_copy = service_name.to_string();
+        let service_type_copy = service_type.clone();
+        let md5sum_copy = md5sum.clone();
+        let stats = Arc::new(ServiceStats::default());
 
-        let task = tokio::spawn(Self::actor(
-            tcp_listener,
-            service_name,
-            node_name,
-            method,
-            service_type,
-            md5sum,
-            srv_definition,
-        ));
+        let task = supervisor.spawn_internal(
+            TaskKind::ServiceServer,
+            &service_name_copy,
+            Self::actor(
+                tcp_listener,
+                service_name,
+                node_name,
+                method,
+                service_type,
+                md5sum,
+                srv_definition,
+                max_message_size,
+                stats.clone(),
+                supervisor.clone(),
+            ),
+        );
 
         Ok(Self {
             _child_task: task.into(),
             port,
             service_name: service_name_copy,
+            service_type: service_type_copy,
+            md5sum: md5sum_copy,
+            stats,
         })
     }
 
@@ -99,8 +203,39 @@ impl ServiceServerLink {
         self.port
     }
 
+    /// Describes this service, `uri` is the address that was registered with rosmaster
+    pub(crate) fn info(&self, uri: String) -> ServiceInfo {
+        ServiceInfo {
+            name: self.service_name.clone(),
+            service_type: self.service_type.clone(),
+            md5sum: self.md5sum.clone(),
+            uri,
+            request_count: self.stats.request_count.load(Ordering::Relaxed),
+        }
+    }
+
+    /// Summarizes the requests this service has handled, see [crate::NodeHandle::stats]
+    pub(crate) fn call_stats(&self) -> ServiceCallStats {
+        ServiceCallStats {
+            service: self.service_name.clone(),
+            service_type: self.service_type.clone(),
+            request_count: self.stats.request_count.load(Ordering::Relaxed),
+            error_count: self.stats.error_count.load(Ordering::Relaxed),
+            average_latency_secs: self
+                .stats
+                .average_latency()
+                .map(|latency| latency.as_secs_f64()),
+        }
+    }
+
+    /// Statistics shared with the [ServiceServerHandle] for this service
+    pub(crate) fn stats(&self) -> Arc<ServiceStats> {
+        self.stats.clone()
+    }
+
     /// Internal static function that actually operates the service server
     /// When new() is called as task is spawned that runs this function
+    #[allow(clippy::too_many_arguments)]
     async fn actor(
         listener: tokio::net::TcpListener,
         service_name: Name, // Service path of the this service
@@ -109,6 +244,9 @@ impl ServiceServerLink {
         service_type: String,
         md5sum: String,
         srv_definition: String,
+        max_message_size: usize,
+        stats: Arc<ServiceStats>,
+        supervisor: TaskSupervisor,
     ) {
         // We have to move our callback into an Arc so the separately spawned tasks for each service connection
         // can access it in parrallel and not worry about the lifetime.
@@ -120,16 +258,23 @@ impl ServiceServerLink {
             // Accept new TCP connections
             match listener.accept().await {
                 Ok((stream, peer_addr)) => {
-                    let task = tokio::spawn(Self::handle_tcp_connection(
-                        stream,
-                        peer_addr,
-                        service_name.clone(),
-                        node_name.clone(),
-                        arc_method.clone(),
-                        service_type.clone(),
-                        md5sum.clone(),
-                        srv_definition.clone(),
-                    ));
+                    let task = supervisor.spawn_internal(
+                        TaskKind::ServiceServer,
+                        service_name.as_str(),
+                        Self::handle_tcp_connection(
+                            stream,
+                            peer_addr,
+                            service_name.clone(),
+                            node_name.clone(),
+                            arc_method.clone(),
+                            service_type.clone(),
+                            md5sum.clone(),
+                            srv_definition.clone(),
+                            max_message_size,
+                            stats.clone(),
+                            supervisor.clone(),
+                        ),
+                    );
                     // Add spawned task to child task list to ensure dropping shuts down server
                     tasks.push(task.into());
                 }
@@ -154,7 +299,18 @@ impl ServiceServerLink {
         service_type: String,
         md5sum: String,
         srv_definition: String,
+        max_message_size: usize,
+        stats: Arc<ServiceStats>,
+        supervisor: TaskSupervisor,
     ) {
+        let report_failure = |error: String| {
+            supervisor.connection_event(ConnectionEvent::Failed {
+                name: service_name.to_string(),
+                kind: TaskKind::ServiceServer,
+                peer: peer_addr.to_string(),
+                error,
+            })
+        };
         // TODO for a bunch of the error branches in this handling
         // it is unclear whether we should respond over the socket
         // with an error or not?
@@ -168,6 +324,7 @@ impl ServiceServerLink {
             }
             Err(e) => {
                 warn!("Communication error while handling service request connection for {service_name}, could not parse header: {e:?}");
+                report_failure(e.to_string());
                 // TODO returning here simply closes the socket? Should we respond with an error instead?
                 return;
             }
@@ -185,18 +342,41 @@ impl ServiceServerLink {
             topic_type: service_type.to_string(),
             tcp_nodelay: false,
             persistent: None,
+            probe: false,
+            compression: None,
+            shared_memory: false,
         };
-        let bytes = response_header.to_bytes(false).unwrap();
-        if let Err(e) = stream.write_all(&bytes).await {
+        let written = match response_header.to_bytes(false) {
+            Ok(bytes) => stream.write_all(&bytes).await,
+            Err(e) => Err(e),
+        };
+        if let Err(e) = written {
             warn!("Communication error while handling service request connection for {service_name}, could not write response header: {e:?}");
+            report_failure(e.to_string());
             // TODO returning here simply closes the socket? Should we respond with an error instead?
             return;
         }
+        supervisor.connection_event(ConnectionEvent::Connected {
+            name: service_name.to_string(),
+            kind: TaskKind::ServiceServer,
+            peer: peer_addr.to_string(),
+        });
+
+        // Tools like rosservice connect only to read our header to discover the service type, no request follows
+        if connection_header.probe {
+            debug!("Responded to probe of service {service_name} from {peer_addr}");
+            return;
+        }
 
         // Each loop is one body:
         loop {
-            let full_body = match tcpros::receive_body(&mut stream).await {
+            let full_body = match tcpros::receive_body(&mut stream, max_message_size).await {
                 Ok(body) => body,
+                Err(e) if e.kind() == std::io::ErrorKind::InvalidData => {
+                    warn!("Refusing request to {service_name} from {peer_addr}: {e}");
+                    report_failure(e.to_string());
+                    return;
+                }
                 Err(e) => {
                     // Note this was degraded to debug! from warn! as every single use client produces this message
                     debug!("Communication error while handling service request connection for {service_name}, could not read body: {e:?}");
@@ -208,41 +388,42 @@ impl ServiceServerLink {
             // This is the actual invocation of the service function registered by the user
             // Because the user could register a function that blocks we want to wrap it in a tokio::spawn_blocking
             let method_clone = method.clone();
+            let tick = tokio::time::Instant::now();
             let response = tokio::task::spawn_blocking(move || (method_clone)(full_body)).await;
+            stats.record(tick.elapsed(), !matches!(response, Ok(Ok(_))));
 
-            match response {
+            let written = match response {
                 // User's function worked
                 Ok(Ok(response)) => {
-                    // MAJOR TODO: handle error here
-
                     // Another funky thing here
                     // services have to respond with one extra byte at the front
                     // to indicate success
-                    // Use separate writes instead of concat() to avoid allocation
-                    stream.write_all(&[1u8]).await.unwrap();
-                    stream.write_all(&response).await.unwrap();
-                    debug!("Wrote full service response for {service_name}");
+                    write_response(&mut stream, true, &response).await
                 }
                 // Error from user's function
                 Ok(Err(e)) => {
                     warn!("Error from user service method for {service_name}: {e:?}");
-
-                    let error_string = format!("{:?}", e);
-                    let error_bytes = roslibrust_serde_rosmsg::to_vec(&error_string).unwrap();
-                    // Use separate writes instead of concat() to avoid allocation
-                    stream.write_all(&[0u8]).await.unwrap();
-                    stream.write_all(&error_bytes).await.unwrap();
+                    write_error(&mut stream, &format!("{:?}", e)).await
+                }
+                // The user's function panicked, the caller still gets a response and the connection stays usable
+                Err(e) if e.is_panic() => {
+                    error!("Service function for {service_name} panicked: {e:?}");
+                    write_error(&mut stream, "Service function panicked").await
                 }
                 // Error from tokio
                 Err(e) => {
                     // We do not expect this to be recoverable
-                    error!("Server error executing {service_name}, task was canceled or panicked: {e:?}");
+                    error!("Server error executing {service_name}, task was canceled: {e:?}");
                     // Returning here closes the socket
                     return;
-                    // TODO consider de-registering the service_server here?
-                    // TODO consider crashing here?
                 }
+            };
+            if let Err(e) = written {
+                warn!("Communication error while handling service request connection for {service_name}, could not write response: {e:?}");
+                report_failure(e.to_string());
+                return;
             }
+            debug!("Wrote full service response for {service_name}");
 
             // If a persistent service connection was requested keep requesting bodies
             if let Some(true) = connection_header.persistent {
@@ -255,3 +436,21 @@ impl ServiceServerLink {
         }
     }
 }
+
+// Writes a service response prefixed with the byte indicating whether the call succeeded
+async fn write_response(
+    stream: &mut tokio::net::TcpStream,
+    success: bool,
+    body: &[u8],
+) -> std::io::Result<()> {
+    // Use separate writes instead of concat() to avoid allocation
+    stream.write_all(&[success as u8]).await?;
+    stream.write_all(body).await
+}
+
+// Writes a failed service response carrying an error message for the caller
+async fn write_error(stream: &mut tokio::net::TcpStream, message: &str) -> std::io::Result<()> {
+    let error_bytes = roslibrust_serde_rosmsg::to_vec(&message)
+        .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e.to_string()))?;
+    write_response(stream, false, &error_bytes).await
+}
@@ -0,0 +1,198 @@
+use bytes::{BufMut, Bytes, BytesMut};
+use std::{
+    path::{Path, PathBuf},
+    sync::atomic::{AtomicU64, Ordering},
+};
+
+// Shared memory transport between roslibrust nodes on the same host.
+// Like [crate::compression] this is negotiated with a roslibrust specific connection header field, and the
+// TCPROS connection is kept for framing: instead of a message the publisher writes a file holding the message
+// to a directory in /dev/shm and sends its path. The subscriber maps the file into memory, so receiving a large
+// message doesn't copy it, and removes it. Each connection has its own directory which the publisher removes
+// when the connection closes, cleaning up any messages that were never read.
+
+const SHM_ROOT: &str = "/dev/shm";
+const DIR_PREFIX: &str = "roslibrust_";
+
+// Distinguishes the directories of connections within this process
+static NEXT_CONNECTION: AtomicU64 = AtomicU64::new(0);
+
+/// Whether support for the shared memory transport was compiled in, requires the `shm` feature on linux
+pub(crate) fn is_available() -> bool {
+    cfg!(all(feature = "shm", target_os = "linux"))
+}
+
+/// Writes messages for a single subscriber's connection into shared memory
+pub(crate) struct ShmWriter {
+    dir: PathBuf,
+    next_message: u64,
+    // Messages the subscriber hasn't picked up yet beyond which new messages are dropped
+    max_pending: usize,
+}
+
+impl ShmWriter {
+    pub(crate) fn new(max_pending: usize) -> std::io::Result<Self> {
+        if !is_available() {
+            return Err(std::io::ErrorKind::Unsupported.into());
+        }
+        let dir = Path::new(SHM_ROOT).join(format!(
+            "{DIR_PREFIX}{}_{}",
+            std::process::id(),
+            NEXT_CONNECTION.fetch_add(1, Ordering::Relaxed)
+        ));
+        create_private_dir(&dir)?;
+        Ok(Self {
+            dir,
+            next_message: 0,
+            max_pending: max_pending.max(1),
+        })
+    }
+
+    /// Writes a serialized message, including its length, to shared memory.
+    /// Returns the frame to send the subscriber in its place, or None if the subscriber is too far behind
+    /// and the message was dropped.
+    /// The filesystem work happens on tokio's blocking pool so large messages don't stall the publish task.
+    pub(crate) async fn write(&mut self, message: Bytes) -> std::io::Result<Option<Bytes>> {
+        let dir = self.dir.clone();
+        let path = self.dir.join(self.next_message.to_string());
+        let max_pending = self.max_pending;
+        let file = path.clone();
+        let written = tokio::task::spawn_blocking(move || {
+            // The subscriber removes each file once it has read it, so what's left is still pending
+            if std::fs::read_dir(&dir)?.count() >= max_pending {
+                return Ok(false);
+            }
+            std::fs::write(&file, &message).map(|_| true)
+        })
+        .await
+        .map_err(std::io::Error::other)??;
+        if !written {
+            return Ok(None);
+        }
+        self.next_message += 1;
+        let path = path.to_string_lossy();
+        let mut frame = BytesMut::with_capacity(4 + path.len());
+        frame.put_u32_le(path.len() as u32);
+        frame.put_slice(path.as_bytes());
+        Ok(Some(frame.freeze()))
+    }
+}
+
+impl Drop for ShmWriter {
+    fn drop(&mut self) {
+        if let Err(e) = std::fs::remove_dir_all(&self.dir) {
+            log::warn!(
+                "Failed to remove shared memory directory {:?}: {e}",
+                self.dir
+            );
+        }
+    }
+}
+
+// Only this user's processes may read the messages or place files among them
+#[cfg(unix)]
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    use std::os::unix::fs::DirBuilderExt;
+    std::fs::DirBuilder::new().mode(0o700).create(dir)
+}
+
+#[cfg(not(unix))]
+fn create_private_dir(dir: &Path) -> std::io::Result<()> {
+    std::fs::create_dir(dir)
+}
+
+/// Maps the message named by a frame from [ShmWriter::write] into memory and removes its file.
+/// Returns the message including its length, messages larger than `max_message_size` are refused.
+pub(crate) fn read(frame: &[u8], max_message_size: usize) -> std::io::Result<Bytes> {
+    let path = frame
+        .get(4..)
+        .and_then(|path| std::str::from_utf8(path).ok())
+        .map(Path::new)
+        .ok_or(std::io::Error::from(std::io::ErrorKind::InvalidData))?;
+    // The path comes from the publisher, don't let it point us at files we didn't agree to remove
+    if !is_message_path(path) {
+        log::error!("Publisher sent shared memory path {path:?} outside of {SHM_ROOT}");
+        return Err(std::io::ErrorKind::InvalidData.into());
+    }
+    let message = map(path)?;
+    std::fs::remove_file(path)?;
+    if message.len() < 4
+        || u32::from_le_bytes(message[0..4].try_into().unwrap()) as usize != message.len() - 4
+    {
+        return Err(std::io::ErrorKind::InvalidData.into());
+    }
+    if message.len() - 4 > max_message_size {
+        return Err(crate::tcpros::message_too_large(
+            message.len() - 4,
+            max_message_size,
+        ));
+    }
+    Ok(message)
+}
+
+// Paths written by ShmWriter are /dev/shm/roslibrust_<pid>_<connection>/<message>
+fn is_message_path(path: &Path) -> bool {
+    let mut components = path.components();
+    let root = components
+        .by_ref()
+        .take(3)
+        .map(|c| c.as_os_str())
+        .collect::<PathBuf>();
+    let dir = components.next().and_then(|c| c.as_os_str().to_str());
+    let file = components.next().and_then(|c| c.as_os_str().to_str());
+    root == Path::new(SHM_ROOT)
+        && dir.is_some_and(|dir| dir.starts_with(DIR_PREFIX))
+        && file.is_some_and(|file| file.chars().all(|c| c.is_ascii_digit()))
+        && components.next().is_none()
+}
+
+#[cfg(feature = "shm")]
+fn map(path: &Path) -> std::io::Result<Bytes> {
+    let file = std::fs::File::open(path)?;
+    // Safety: the publisher never writes to a file again once it has sent its path
+    let mmap = unsafe { memmap2::Mmap::map(&file)? };
+    Ok(Bytes::from_owner(mmap))
+}
+
+#[cfg(not(feature = "shm"))]
+fn map(_path: &Path) -> std::io::Result<Bytes> {
+    Err(std::io::ErrorKind::Unsupported.into())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn only_reads_message_paths() {
+        assert!(is_message_path(Path::new("/dev/shm/roslibrust_12_0/3")));
+        assert!(!is_message_path(Path::new("/dev/shm/roslibrust_12_0/../x")));
+        assert!(!is_message_path(Path::new("/dev/shm/other/3")));
+        assert!(!is_message_path(Path::new("/etc/roslibrust_12_0/3")));
+        assert!(!is_message_path(Path::new("/dev/shm/roslibrust_12_0/3/4")));
+    }
+
+    #[tokio::test]
+    async fn round_trips() {
+        if !is_available() {
+            assert!(ShmWriter::new(1).is_err());
+            return;
+        }
+        let mut writer = ShmWriter::new(1).unwrap();
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&writer.dir).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o700);
+        }
+        let message = Bytes::from_static(&[4, 0, 0, 0, 1, 2, 3, 4]);
+        let frame = writer.write(message.clone()).await.unwrap().unwrap();
+        // Dropped while the subscriber hasn't read the previous message
+        assert!(writer.write(message.clone()).await.unwrap().is_none());
+        assert_eq!(read(&frame, 4).unwrap(), message);
+        assert!(writer.write(message).await.unwrap().is_some());
+        let dir = writer.dir.clone();
+        drop(writer);
+        assert!(!dir.exists());
+    }
+}
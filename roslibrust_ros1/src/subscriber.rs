@@ -1,12 +1,32 @@
-use crate::{names::Name, tcpros::ConnectionHeader};
+use crate::{
+    compression::{self, Compression},
+    names::Name,
+    node::manager::{IntraProcess, IntraProcessPublication},
+    node::{health::TopicActivity, TopicStats},
+    publisher::{header_stamp, starts_with_header},
+    qos::{self, DeadlineCallback, Heartbeat, Liveliness, LivelinessCallback, QosTask},
+    shm,
+    supervisor::{ConnectionEvent, TaskKind, TaskSupervisor},
+    tcpros::ConnectionHeader,
+    udpros::{self, UdprosConnection},
+    watermarks::Watermarks,
+    wire_recorder::{WireDirection, WireRecordKind, WireRecorder},
+    NodeError, NodeHandle,
+};
 use abort_on_drop::ChildTask;
 use bytes::Bytes;
 use log::*;
-use roslibrust_common::{RosMessageType, ShapeShifter};
-use std::{marker::PhantomData, sync::Arc};
+use roslibrust_common::{clock::Clock, evolution, CancellationToken, RosMessageType, ShapeShifter};
+use std::{
+    collections::VecDeque,
+    hash::{Hash, Hasher},
+    marker::PhantomData,
+    sync::Arc,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 use tokio::{
     io::AsyncWriteExt,
-    net::TcpStream,
+    net::{TcpStream, UdpSocket},
     sync::{
         broadcast::{self, error::RecvError},
         RwLock,
@@ -15,88 +35,863 @@ use tokio::{
 
 use super::tcpros;
 
-pub struct Subscriber<T> {
+/// A message along with details of how it was received, returned by [Subscriber::next_stamped] and
+/// [SubscriberAny::next_stamped].
+///
+/// Comparing `receive_time` against the stamp of a message's header gives its transport latency, and
+/// grouping by `caller_id` gives per-publisher statistics.
+#[derive(Clone, Debug)]
+pub struct Stamped<T> {
+    pub msg: T,
+    /// When the message was read off of the publisher's connection
+    pub receive_time: SystemTime,
+    /// Name of the node which published the message
+    pub caller_id: String,
+    /// Size of the message in bytes as it was sent over the wire
+    pub size: usize,
+}
+
+// What a subscription hands to its subscribers for each message
+#[derive(Clone, Debug)]
+pub(crate) struct ReceivedMessage {
     // Uses Bytes for efficient cloning (reference counted) when there are multiple subscribers
-    receiver: broadcast::Receiver<Bytes>,
+    pub(crate) body: Bytes,
+    pub(crate) receive_time: SystemTime,
+    pub(crate) source: Arc<PublisherSource>,
+}
+
+impl ReceivedMessage {
+    fn new(body: Bytes, source: Arc<PublisherSource>) -> Self {
+        Self {
+            body,
+            receive_time: SystemTime::now(),
+            source,
+        }
+    }
+}
+
+// Identifies the publisher a message came from, shared by every message received over a connection
+#[derive(Debug)]
+pub(crate) struct PublisherSource {
+    pub(crate) caller_id: String,
+    // md5sum the publisher gave for the topic's type, "*" if it didn't say
+    pub(crate) md5sum: String,
+}
+
+impl PublisherSource {
+    fn from_header(header: &ConnectionHeader) -> Arc<Self> {
+        Arc::new(Self {
+            caller_id: header.caller_id.clone(),
+            md5sum: header.md5sum.clone().unwrap_or_else(|| "*".to_string()),
+        })
+    }
+}
+
+/// How [Subscriber::drop_duplicates] decides two messages are the same
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Duplicates {
+    /// Messages with the same `header.seq` and `header.stamp` are duplicates.
+    /// Message types without a header are compared by content instead.
+    Header,
+    /// Messages which serialize to the same bytes are duplicates
+    Content,
+}
+
+// Remembers hashes of the most recent messages to spot copies of them
+#[derive(Clone, Debug)]
+struct DuplicateFilter {
+    key: Duplicates,
+    window: usize,
+    // Oldest first
+    recent: VecDeque<u64>,
+}
+
+impl DuplicateFilter {
+    fn new(key: Duplicates, window: usize) -> Self {
+        Self {
+            key,
+            window,
+            recent: VecDeque::with_capacity(window),
+        }
+    }
+
+    /// Returns true if `body` repeats one of the last `window` messages, otherwise remembers it
+    fn is_duplicate(&mut self, body: &[u8]) -> bool {
+        // Skip the length prefix, seq and stamp are the 12 bytes following it
+        let identity = match self.key {
+            Duplicates::Header => body.get(4..16).unwrap_or(body),
+            Duplicates::Content => body,
+        };
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        identity.hash(&mut hasher);
+        let hash = hasher.finish();
+        if self.recent.contains(&hash) {
+            return true;
+        }
+        if self.recent.len() == self.window {
+            self.recent.pop_front();
+        }
+        if self.window > 0 {
+            self.recent.push_back(hash);
+        }
+        false
+    }
+}
+
+pub struct Subscriber<T> {
+    receiver: broadcast::Receiver<ReceivedMessage>,
+    topic_name: String,
+    // Weak handle used to unsubscribe, doesn't keep the node alive
+    node_handle: NodeHandle,
+    // Messages at least this large are deserialized on tokio's blocking pool
+    offload_threshold: Option<usize>,
+    // Messages older than this are dropped, see [Subscriber::reject_older_than]
+    max_age: Option<Duration>,
+    age_clock: Clock,
+    duplicates: Option<DuplicateFilter>,
+    // Checked against the depth of receiver as each message is read
+    watermarks: Option<Watermarks>,
+    // Deadline and liveliness monitors registered on this subscriber, stopped when it is dropped
+    qos_tasks: Vec<QosTask>,
+    // Messages which don't match T exactly are fitted to its definition, see [SubscriberBuilder::tolerate_trailing_changes]
+    tolerate_trailing_changes: bool,
+    // Message being deserialized on the blocking pool, kept here so it isn't lost if next() is cancelled
+    offloaded: Option<Offloaded<T>>,
     _phantom: PhantomData<T>,
 }
 
 impl<T: RosMessageType> Subscriber<T> {
-    pub(crate) fn new(receiver: broadcast::Receiver<Bytes>) -> Self {
+    pub(crate) fn new(
+        receiver: broadcast::Receiver<ReceivedMessage>,
+        topic_name: &str,
+        node_handle: NodeHandle,
+    ) -> Self {
         Self {
             receiver,
+            topic_name: topic_name.to_owned(),
+            node_handle,
+            offload_threshold: None,
+            max_age: None,
+            age_clock: Clock::wall(),
+            duplicates: None,
+            watermarks: None,
+            qos_tasks: vec![],
+            tolerate_trailing_changes: false,
+            offloaded: None,
             _phantom: PhantomData,
         }
     }
 
+    /// Deserializes messages of at least `min_size` bytes on tokio's blocking thread pool instead of the task
+    /// calling [Subscriber::next].
+    ///
+    /// Decoding a very large message (e.g. a 50MB point cloud) can take long enough to stall every other task
+    /// scheduled on the same runtime thread, including the node's other subscriptions. Messages are still
+    /// returned in the order they were received, [Subscriber::next] waits for each one to finish deserializing.
+    /// Pass 0 to offload every message.
+    pub fn offload_deserialization(mut self, min_size: usize) -> Self {
+        self.offload_threshold = Some(min_size);
+        self
+    }
+
+    /// Silently drops messages older than `max_age` instead of returning them, so a control loop doesn't act on stale
+    /// data after a network hiccup or a jump in bag playback.
+    ///
+    /// For message types starting with a header the age is measured from `header.stamp` to the current ROS time,
+    /// read from the wall clock unless another is given with [Subscriber::age_clock]. Messages without a header, or
+    /// with an unset stamp, are instead aged from when they were received, which only catches messages left waiting
+    /// in the subscriber's queue.
+    pub fn reject_older_than(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets the clock [Subscriber::reject_older_than] compares header stamps against, e.g. a
+    /// [SimClock](roslibrust_common::clock::SimClock) when running in simulation.
+    pub fn age_clock(mut self, clock: Clock) -> Self {
+        self.age_clock = clock;
+        self
+    }
+
+    // Whether a message should be dropped by reject_older_than
+    fn is_stale(&self, received: &ReceivedMessage) -> bool {
+        let Some(max_age) = self.max_age else {
+            return false;
+        };
+        // An unset stamp is treated the same as a message without a header
+        let stamp = starts_with_header(T::DEFINITION)
+            .then(|| header_stamp(&received.body))
+            .flatten()
+            .filter(|stamp| *stamp != UNIX_EPOCH);
+        let age = match stamp {
+            Some(stamp) => self.age_clock.now().duration_since(stamp),
+            None => SystemTime::now().duration_since(received.receive_time),
+        };
+        // Stamps from the future are not stale
+        age.is_ok_and(|age| age > max_age)
+    }
+
+    /// Drops messages which repeat one of the last `window` messages received, e.g. when several redundant bridges
+    /// or relays republish the same source onto the topic.
+    ///
+    /// With [Duplicates::Header] messages are identified by `header.seq` and `header.stamp`, so publishers must fill
+    /// in both for distinct messages not to be mistaken for duplicates. Message types without a header fall back to
+    /// [Duplicates::Content].
+    pub fn drop_duplicates(mut self, key: Duplicates, window: usize) -> Self {
+        let key = match key {
+            Duplicates::Header if !starts_with_header(T::DEFINITION) => Duplicates::Content,
+            key => key,
+        };
+        self.duplicates = Some(DuplicateFilter::new(key, window));
+        self
+    }
+
+    /// Reports when the subscriber's queue fills up to and drains from the given watermarks, see [Watermarks]
+    pub fn queue_watermarks(mut self, watermarks: Watermarks) -> Self {
+        self.watermarks = Some(watermarks);
+        self
+    }
+
+    /// Returns the number of received messages waiting to be read by this subscriber
+    pub fn queue_len(&self) -> usize {
+        self.receiver.len()
+    }
+
+    /// Calls `callback` from a background task each time `period` passes without a message arriving on the topic,
+    /// emulating ROS2's deadline QoS. The callback is given the time since the last message, or since this was
+    /// called if none has arrived yet.
+    ///
+    /// Arrivals are watched independently of [Subscriber::next], so a deadline isn't missed because messages are
+    /// waiting in the queue. Monitoring stops when the subscriber is dropped, and isn't carried over by
+    /// [Subscriber::fork].
+    pub fn on_deadline_missed(
+        mut self,
+        period: Duration,
+        callback: impl Fn(Duration) + Send + Sync + 'static,
+    ) -> Self {
+        self.watch_deadline(period, Arc::new(callback));
+        self
+    }
+
+    fn watch_deadline(&mut self, period: Duration, callback: DeadlineCallback) {
+        self.qos_tasks.push(qos::monitor_deadline(
+            self.receiver.resubscribe(),
+            period,
+            callback,
+        ));
+    }
+
+    /// Creates another subscriber to the topic, fed from the same connections to publishers as this one.
+    ///
+    /// Unlike calling [NodeHandle::subscribe] again this doesn't contact the ROS master or wait on the node. The fork
+    /// receives the messages arriving after it is created, and the topic stays subscribed while any subscriber to it
+    /// remains. Each subscriber deserializes its own copy of each message, see
+    /// [Subscribe::fan_out](roslibrust_common::Subscribe::fan_out) to share one deserialized copy instead.
+    pub fn fork(&self) -> Self {
+        Self {
+            receiver: self.receiver.resubscribe(),
+            topic_name: self.topic_name.clone(),
+            node_handle: self.node_handle.clone(),
+            offload_threshold: self.offload_threshold,
+            max_age: self.max_age,
+            age_clock: self.age_clock.clone(),
+            // The fork starts with an empty window, as it only receives messages from now on
+            duplicates: self
+                .duplicates
+                .as_ref()
+                .map(|filter| DuplicateFilter::new(filter.key, filter.window)),
+            watermarks: self.watermarks.as_ref().map(Watermarks::detached),
+            qos_tasks: vec![],
+            tolerate_trailing_changes: self.tolerate_trailing_changes,
+            offloaded: None,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Releases this subscriber, and if no other subscribers to the topic remain on this node unsubscribes from it.
+    ///
+    /// Completes once the node has closed its connections to the topic's publishers and unregistered with the ROS master.
+    pub async fn unsubscribe(self) -> Result<(), NodeError> {
+        // A deadline monitor's receiver would otherwise count as another subscriber to the topic
+        for task in self.qos_tasks {
+            task.stop().await;
+        }
+        unsubscribe(self.receiver, &self.topic_name, &self.node_handle).await
+    }
+
+    /// Gets the next message from the subscriber.
+    ///
+    /// Cancel safe: if the returned future is dropped before completing, e.g. by losing a `tokio::select!`,
+    /// no message is lost and the next call returns the message the dropped one would have.
     pub async fn next(&mut self) -> Option<Result<T, SubscriberError>> {
-        trace!("Subscriber of type {:?} awaiting recv()", T::ROS_TYPE_NAME);
-        let data = match self.receiver.recv().await {
-            Ok(v) => {
-                trace!("Subscriber of type {:?} received data", T::ROS_TYPE_NAME);
-                v
+        Some(self.next_stamped().await?.map(|stamped| stamped.msg))
+    }
+
+    /// Gets the next message from the subscriber along with when it was received, who published it and its size.
+    ///
+    /// Cancel safe, see [Subscriber::next].
+    pub async fn next_stamped(&mut self) -> Option<Result<Stamped<T>, SubscriberError>> {
+        // A message still being deserialized for an earlier call that was cancelled is returned first
+        if self.offloaded.is_none() {
+            trace!("Subscriber of type {:?} awaiting recv()", T::ROS_TYPE_NAME);
+            let received = loop {
+                if let Some(watermarks) = &self.watermarks {
+                    // Checked before receiving so an empty queue counts as drained
+                    watermarks.observe(self.receiver.len());
+                }
+                match self.receiver.recv().await {
+                    Ok(v) if self.is_stale(&v) => {
+                        debug!(
+                            "Subscriber to {} dropped a stale message from {}",
+                            self.topic_name, v.source.caller_id
+                        );
+                    }
+                    Ok(v)
+                        if self
+                            .duplicates
+                            .as_mut()
+                            .is_some_and(|filter| filter.is_duplicate(&v.body)) =>
+                    {
+                        trace!(
+                            "Subscriber to {} dropped a duplicate message from {}",
+                            self.topic_name,
+                            v.source.caller_id
+                        );
+                    }
+                    Ok(v) => {
+                        trace!("Subscriber of type {:?} received data", T::ROS_TYPE_NAME);
+                        break v;
+                    }
+                    Err(RecvError::Closed) => return None,
+                    Err(RecvError::Lagged(n)) => return Some(Err(SubscriberError::Lagged(n))),
+                }
+            };
+            trace!(
+                "Subscriber of type {:?} deserializing data",
+                T::ROS_TYPE_NAME
+            );
+            let tick = tokio::time::Instant::now();
+            match self.offload_threshold {
+                Some(min_size) if received.body.len() >= min_size => {
+                    let data = received.body.clone();
+                    let tolerant = self.tolerate_trailing_changes;
+                    let task =
+                        tokio::task::spawn_blocking(move || deserialize::<T>(&data[..], tolerant));
+                    self.offloaded = Some(Offloaded {
+                        received,
+                        tick,
+                        task,
+                    });
+                }
+                _ => {
+                    let result =
+                        deserialize::<T>(&received.body[..], self.tolerate_trailing_changes);
+                    return Some(Self::stamp(received, tick, result));
+                }
             }
-            Err(RecvError::Closed) => return None,
-            Err(RecvError::Lagged(n)) => return Some(Err(SubscriberError::Lagged(n))),
-        };
+        }
+        // Only taken once the task has finished, so cancelling here leaves the message for the next call
+        let offloaded = self.offloaded.as_mut().expect("Set above if not already");
+        let result = (&mut offloaded.task).await;
+        let Offloaded { received, tick, .. } = self.offloaded.take().expect("Checked above");
+        match result {
+            Ok(result) => Some(Self::stamp(received, tick, result)),
+            Err(e) => Some(Err(SubscriberError::DeserializeError(e.to_string()))),
+        }
+    }
+
+    fn stamp(
+        received: ReceivedMessage,
+        tick: tokio::time::Instant,
+        result: Result<T, roslibrust_serde_rosmsg::Error>,
+    ) -> Result<Stamped<T>, SubscriberError> {
+        let msg = result?;
         trace!(
-            "Subscriber of type {:?} deserializing data",
-            T::ROS_TYPE_NAME
+            "Subscriber of type {:?} deserialized data in {:?}",
+            T::ROS_TYPE_NAME,
+            tick.elapsed()
         );
-        let tick = tokio::time::Instant::now();
-        match roslibrust_serde_rosmsg::from_slice::<T>(&data[..]) {
-            Ok(p) => {
-                let duration = tick.elapsed();
-                trace!(
-                    "Subscriber of type {:?} deserialized data in {duration:?}",
-                    T::ROS_TYPE_NAME
+        Ok(Stamped {
+            msg,
+            receive_time: received.receive_time,
+            caller_id: received.source.caller_id.clone(),
+            size: received.body.len(),
+        })
+    }
+}
+
+// Deserializes a message, falling back to fitting it to T's definition if `tolerant` and it doesn't match exactly
+fn deserialize<T: RosMessageType>(
+    data: &[u8],
+    tolerant: bool,
+) -> Result<T, roslibrust_serde_rosmsg::Error> {
+    let result = roslibrust_serde_rosmsg::from_slice::<T>(data);
+    if result.is_err() && tolerant {
+        if let Ok(fitted) = evolution::fit_to_definition(T::ROS_TYPE_NAME, T::DEFINITION, data) {
+            return roslibrust_serde_rosmsg::from_slice::<T>(&fitted);
+        }
+    }
+    result
+}
+
+// A message being deserialized on tokio's blocking pool, see [Subscriber::offload_deserialization]
+struct Offloaded<T> {
+    received: ReceivedMessage,
+    tick: tokio::time::Instant,
+    task: tokio::task::JoinHandle<Result<T, roslibrust_serde_rosmsg::Error>>,
+}
+
+/// Builds a [Subscriber], created with [NodeHandle::subscribe_builder].
+///
+/// ```no_run
+/// # async fn example(nh: roslibrust_ros1::NodeHandle) -> Result<(), roslibrust_ros1::NodeError> {
+/// # use roslibrust_test::ros1::*;
+/// let mut subscriber = nh
+///     .subscribe_builder::<std_msgs::String>("/chatter")
+///     .queue(100)
+///     .udp_ok()
+///     .first_message_timeout(std::time::Duration::from_secs(5))
+///     .build()
+///     .await?;
+/// // A message is already waiting
+/// let msg = subscriber.next().await;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// Subscriptions are shared by all subscribers on a topic within a node, the md5sum check and transports only take
+/// effect if this is the first subscriber to the topic.
+pub struct SubscriberBuilder<T> {
+    nh: NodeHandle,
+    topic_name: String,
+    queue_size: usize,
+    check_md5sum: bool,
+    tolerate_trailing_changes: bool,
+    first_message_timeout: Option<Duration>,
+    transports: Option<Vec<Transport>>,
+    max_age: Option<Duration>,
+    age_clock: Clock,
+    duplicates: Option<(Duplicates, usize)>,
+    watermarks: Option<Watermarks>,
+    deadline: Option<(Duration, DeadlineCallback)>,
+    liveliness: Option<(Duration, LivelinessCallback)>,
+    cancel: CancellationToken,
+    _phantom: PhantomData<T>,
+}
+
+impl<T: RosMessageType> SubscriberBuilder<T> {
+    pub(crate) fn new(nh: NodeHandle, topic_name: &str) -> Self {
+        Self {
+            nh,
+            topic_name: topic_name.to_owned(),
+            // Same default queue size as the generic TopicProvider::subscribe
+            queue_size: 10,
+            check_md5sum: true,
+            tolerate_trailing_changes: false,
+            first_message_timeout: None,
+            transports: None,
+            max_age: None,
+            age_clock: Clock::wall(),
+            duplicates: None,
+            watermarks: None,
+            deadline: None,
+            liveliness: None,
+            cancel: CancellationToken::new(),
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Number of messages buffered for the subscriber before the oldest are dropped, defaults to 10
+    pub fn queue(mut self, queue_size: usize) -> Self {
+        self.queue_size = queue_size;
+        self
+    }
+
+    /// Connects to publishers regardless of the md5sum they give for the topic's type.
+    ///
+    /// Messages are still deserialized as `T`, so this is only useful when the publisher's type is known to be
+    /// wire compatible, e.g. a message package rebuilt with changed comments.
+    pub fn no_md5_check(mut self) -> Self {
+        self.check_md5sum = false;
+        self
+    }
+
+    /// Accepts messages from publishers whose definition of the type has fields added to or removed from the end
+    /// compared to `T`, easing rolling out a changed message across a fleet that isn't updated all at once.
+    ///
+    /// Implies [SubscriberBuilder::no_md5_check]. Messages that don't deserialize as `T` are fitted to its definition
+    /// with [fit_to_definition](roslibrust_common::evolution::fit_to_definition): fields `T` doesn't have are
+    /// ignored, and fields the publisher didn't send are left at their default values. Messages whose types differ in
+    /// any other way fail to deserialize, or worse deserialize to nonsense, so this should only be used with types
+    /// that are known to evolve by appending fields.
+    pub fn tolerate_trailing_changes(mut self) -> Self {
+        self.check_md5sum = false;
+        self.tolerate_trailing_changes = true;
+        self
+    }
+
+    /// Makes [SubscriberBuilder::build] fail with [NodeError::FirstMessageTimeout] if no message is received
+    /// within `timeout` of subscribing.
+    ///
+    /// The message waited for is not consumed, it is returned by the first call to [Subscriber::next].
+    pub fn first_message_timeout(mut self, timeout: Duration) -> Self {
+        self.first_message_timeout = Some(timeout);
+        self
+    }
+
+    /// Only offers TCPROS to publishers, regardless of [NodeHandle::set_transport_preference]
+    pub fn reliable_only(mut self) -> Self {
+        self.transports = Some(vec![Transport::Tcpros]);
+        self
+    }
+
+    /// Offers UDPROS to publishers, falling back to TCPROS, regardless of [NodeHandle::set_transport_preference]
+    pub fn udp_ok(mut self) -> Self {
+        self.transports = Some(vec![Transport::Udpros, Transport::Tcpros]);
+        self
+    }
+
+    /// Drops messages older than `max_age` rather than returning them, see [Subscriber::reject_older_than]
+    pub fn reject_older_than(mut self, max_age: Duration) -> Self {
+        self.max_age = Some(max_age);
+        self
+    }
+
+    /// Sets the clock message ages are measured with, see [Subscriber::age_clock]
+    pub fn age_clock(mut self, clock: Clock) -> Self {
+        self.age_clock = clock;
+        self
+    }
+
+    /// Drops repeats of recently received messages, see [Subscriber::drop_duplicates]
+    pub fn drop_duplicates(mut self, key: Duplicates, window: usize) -> Self {
+        self.duplicates = Some((key, window));
+        self
+    }
+
+    /// Reports when the subscriber's queue fills up to and drains from the given watermarks, see [Watermarks]
+    pub fn queue_watermarks(mut self, watermarks: Watermarks) -> Self {
+        self.watermarks = Some(watermarks);
+        self
+    }
+
+    /// Calls `callback` when `period` passes without a message, see [Subscriber::on_deadline_missed]
+    pub fn on_deadline_missed(
+        mut self,
+        period: Duration,
+        callback: impl Fn(Duration) + Send + Sync + 'static,
+    ) -> Self {
+        self.deadline = Some((period, Arc::new(callback)));
+        self
+    }
+
+    /// Calls `callback` from a background task with a publisher's caller id when it starts asserting its liveliness,
+    /// and again when no heartbeat is received from it within `lease`, emulating ROS2's liveliness QoS.
+    ///
+    /// Only publishers built with [PublisherBuilder::heartbeat](crate::PublisherBuilder::heartbeat) send heartbeats,
+    /// so `lease` should be a few times longer than their heartbeat period. Monitoring stops when the subscriber is
+    /// dropped.
+    pub fn on_liveliness_changed(
+        mut self,
+        lease: Duration,
+        callback: impl Fn(&str, Liveliness) + Send + Sync + 'static,
+    ) -> Self {
+        self.liveliness = Some((lease, Arc::new(callback)));
+        self
+    }
+
+    /// Makes [SubscriberBuilder::build] fail with [NodeError::Cancelled] if `cancel` is cancelled while connecting
+    /// to the topic's publishers or waiting for the first message.
+    ///
+    /// A subscription abandoned this way is cleaned up if no other subscribers are using it.
+    pub fn cancel_on(mut self, cancel: &CancellationToken) -> Self {
+        self.cancel = cancel.clone();
+        self
+    }
+
+    /// Subscribes to the topic, see [NodeHandle::subscribe]
+    pub async fn build(self) -> Result<Subscriber<T>, NodeError> {
+        let md5sum = if self.check_md5sum { T::MD5SUM } else { "*" };
+        let subscriber = self
+            .cancel
+            .run_until_cancelled(self.nh.subscribe_with_transports::<T>(
+                &self.topic_name,
+                md5sum,
+                self.queue_size,
+                self.transports,
+            ))
+            .await
+            .ok_or(NodeError::Cancelled)??
+            .age_clock(self.age_clock);
+        let subscriber = match self.max_age {
+            Some(max_age) => subscriber.reject_older_than(max_age),
+            None => subscriber,
+        };
+        let subscriber = match self.duplicates {
+            Some((key, window)) => subscriber.drop_duplicates(key, window),
+            None => subscriber,
+        };
+        let mut subscriber = match self.watermarks {
+            Some(watermarks) => subscriber.queue_watermarks(watermarks),
+            None => subscriber,
+        };
+        subscriber.tolerate_trailing_changes = self.tolerate_trailing_changes;
+        if let Some((period, callback)) = self.deadline {
+            subscriber.watch_deadline(period, callback);
+        }
+        if let Some((lease, callback)) = self.liveliness {
+            let heartbeats = self
+                .nh
+                .subscribe::<Heartbeat>(&qos::heartbeat_topic(&self.topic_name), 10)
+                .await?;
+            subscriber
+                .qos_tasks
+                .push(qos::monitor_liveliness(heartbeats, lease, callback));
+        }
+        let Some(timeout) = self.first_message_timeout else {
+            return Ok(subscriber);
+        };
+        // Wait on a second receiver so the first message stays queued for the subscriber
+        let mut probe = subscriber.receiver.resubscribe();
+        if !subscriber.receiver.is_empty() {
+            return Ok(subscriber);
+        }
+        let error = match self
+            .cancel
+            .run_until_cancelled(tokio::time::timeout(timeout, probe.recv()))
+            .await
+        {
+            Some(Ok(_)) => return Ok(subscriber),
+            Some(Err(_elapsed)) => {
+                log::error!(
+                    "No message received on {} within {timeout:?} of subscribing",
+                    subscriber.topic_name
                 );
-                Some(Ok(p))
+                NodeError::FirstMessageTimeout {
+                    topic: subscriber.topic_name.clone(),
+                    timeout,
+                }
             }
-            Err(e) => Some(Err(e.into())),
+            None => NodeError::Cancelled,
+        };
+        drop(probe);
+        subscriber.unsubscribe().await?;
+        Err(error)
+    }
+}
+
+/// A message received by a [SubscriberMulti], decoded as whichever of its two types the publisher sent.
+#[derive(Clone, Debug, PartialEq)]
+pub enum OneOf<A, B> {
+    First(A),
+    Second(B),
+}
+
+/// Message types which can be subscribed to together on one topic, see [NodeHandle::subscribe_multi].
+///
+/// Implemented for pairs of message types `(A, B)`.
+pub trait MessageAlternatives: 'static + Send {
+    type Output: Send + 'static;
+
+    /// Type name the subscription is registered under with the ROS master
+    const ROS_TYPE_NAME: &'static str;
+    /// Definition sent to publishers when connecting to them
+    const DEFINITION: &'static str;
+
+    /// Decodes a message, using the md5sum the publisher gave for the topic to pick the type.
+    /// If the md5sum matches neither type each is tried in order.
+    fn decode(md5sum: &str, data: &[u8]) -> Result<Self::Output, SubscriberError>;
+}
+
+impl<A: RosMessageType, B: RosMessageType> MessageAlternatives for (A, B) {
+    type Output = OneOf<A, B>;
+
+    const ROS_TYPE_NAME: &'static str = A::ROS_TYPE_NAME;
+    const DEFINITION: &'static str = A::DEFINITION;
+
+    fn decode(md5sum: &str, data: &[u8]) -> Result<Self::Output, SubscriberError> {
+        if md5sum == A::MD5SUM {
+            return Ok(OneOf::First(roslibrust_serde_rosmsg::from_slice(data)?));
         }
+        if md5sum == B::MD5SUM {
+            return Ok(OneOf::Second(roslibrust_serde_rosmsg::from_slice(data)?));
+        }
+        match roslibrust_serde_rosmsg::from_slice::<A>(data) {
+            Ok(msg) => Ok(OneOf::First(msg)),
+            Err(_) => Ok(OneOf::Second(roslibrust_serde_rosmsg::from_slice(data)?)),
+        }
+    }
+}
+
+/// Subscriber which accepts publishers of any of several message types on one topic, see [NodeHandle::subscribe_multi].
+pub struct SubscriberMulti<M> {
+    receiver: broadcast::Receiver<ReceivedMessage>,
+    topic_name: String,
+    // Weak handle used to unsubscribe, doesn't keep the node alive
+    node_handle: NodeHandle,
+    _phantom: PhantomData<M>,
+}
+
+impl<M: MessageAlternatives> SubscriberMulti<M> {
+    pub(crate) fn new(
+        receiver: broadcast::Receiver<ReceivedMessage>,
+        topic_name: &str,
+        node_handle: NodeHandle,
+    ) -> Self {
+        Self {
+            receiver,
+            topic_name: topic_name.to_owned(),
+            node_handle,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Releases this subscriber, see [Subscriber::unsubscribe].
+    pub async fn unsubscribe(self) -> Result<(), NodeError> {
+        unsubscribe(self.receiver, &self.topic_name, &self.node_handle).await
+    }
+
+    pub async fn next(&mut self) -> Option<Result<M::Output, SubscriberError>> {
+        Some(self.next_stamped().await?.map(|stamped| stamped.msg))
+    }
+
+    /// Gets the next message from the subscriber, see [Subscriber::next_stamped].
+    pub async fn next_stamped(&mut self) -> Option<Result<Stamped<M::Output>, SubscriberError>> {
+        let received = match self.receiver.recv().await {
+            Ok(v) => v,
+            Err(RecvError::Closed) => return None,
+            Err(RecvError::Lagged(n)) => return Some(Err(SubscriberError::Lagged(n))),
+        };
+        Some(
+            M::decode(&received.source.md5sum, &received.body).map(|msg| Stamped {
+                msg,
+                receive_time: received.receive_time,
+                caller_id: received.source.caller_id.clone(),
+                size: received.body.len(),
+            }),
+        )
     }
 }
 
 pub struct SubscriberAny {
-    // Uses Bytes for efficient cloning (reference counted) when there are multiple subscribers
-    receiver: broadcast::Receiver<Bytes>,
+    receiver: broadcast::Receiver<ReceivedMessage>,
+    topic_name: String,
+    // Weak handle used to unsubscribe, doesn't keep the node alive
+    node_handle: NodeHandle,
     _phantom: PhantomData<ShapeShifter>,
 }
 
 impl SubscriberAny {
-    pub(crate) fn new(receiver: broadcast::Receiver<Bytes>) -> Self {
+    pub(crate) fn new(
+        receiver: broadcast::Receiver<ReceivedMessage>,
+        topic_name: &str,
+        node_handle: NodeHandle,
+    ) -> Self {
         Self {
             receiver,
+            topic_name: topic_name.to_owned(),
+            node_handle,
             _phantom: PhantomData,
         }
     }
 
+    /// Creates another subscriber to the topic sharing this one's connections, see [Subscriber::fork].
+    ///
+    /// The message bodies returned are shared between the forks rather than copied.
+    pub fn fork(&self) -> Self {
+        Self::new(
+            self.receiver.resubscribe(),
+            &self.topic_name,
+            self.node_handle.clone(),
+        )
+    }
+
+    /// Releases this subscriber, see [Subscriber::unsubscribe].
+    pub async fn unsubscribe(self) -> Result<(), NodeError> {
+        unsubscribe(self.receiver, &self.topic_name, &self.node_handle).await
+    }
+
     /// Gets the next message from the subscriber.
     /// Uniquely for SubscriberAny, this returns the raw bytes of the message as Bytes.
     /// Note: over the wire ros messages include a 4 byte length header before the message body.
     /// This function does not return that header, merely the message body.
     /// The returned Bytes is reference counted and cheap to clone.
     pub async fn next(&mut self) -> Option<Result<Bytes, SubscriberError>> {
-        let data = match self.receiver.recv().await {
+        Some(self.next_stamped().await?.map(|stamped| stamped.msg))
+    }
+
+    /// Gets the next message from the subscriber as raw bytes, see [Subscriber::next_stamped].
+    pub async fn next_stamped(&mut self) -> Option<Result<Stamped<Bytes>, SubscriberError>> {
+        let received = match self.receiver.recv().await {
             Ok(v) => v,
             Err(RecvError::Closed) => return None,
             Err(RecvError::Lagged(n)) => return Some(Err(SubscriberError::Lagged(n))),
         };
-        Some(Ok(data))
+        Some(Ok(Stamped {
+            size: received.body.len(),
+            msg: received.body,
+            receive_time: received.receive_time,
+            caller_id: received.source.caller_id.clone(),
+        }))
+    }
+}
+
+// Drops a subscriber's receiver and then asks the node to clean up the subscription if it was the last one
+async fn unsubscribe(
+    receiver: broadcast::Receiver<ReceivedMessage>,
+    topic_name: &str,
+    node_handle: &NodeHandle,
+) -> Result<(), NodeError> {
+    drop(receiver);
+    node_handle.unregister_subscriber(topic_name).await
+}
+
+/// Transports a ROS1 subscription can receive messages over, see [NodeHandle::set_transport_preference].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Transport {
+    Tcpros,
+    /// Avoids TCP's retransmission delays, but a message missing any of its datagrams is dropped.
+    /// Not every publisher supports it, rospy and roslibrust publishers only offer TCPROS.
+    /// There is no connection to close, so a publisher going away isn't noticed until the topic is unsubscribed.
+    Udpros,
+}
+
+impl Transport {
+    /// Name of the transport as used in ROS's xmlrpc API
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Transport::Tcpros => "TCPROS",
+            Transport::Udpros => "UDPROS",
+        }
     }
 }
 
 pub struct Subscription {
     subscription_tasks: Vec<ChildTask<()>>,
-    // Uses Bytes for efficient cloning (reference counted) when there are multiple subscribers
-    _msg_receiver: broadcast::Receiver<Bytes>,
-    msg_sender: broadcast::Sender<Bytes>,
+    _msg_receiver: broadcast::Receiver<ReceivedMessage>,
+    msg_sender: broadcast::Sender<ReceivedMessage>,
     connection_header: ConnectionHeader,
     known_publishers: Arc<RwLock<Vec<String>>>,
+    // If present all traffic on this subscription's connections is recorded
+    wire_recorder: Option<WireRecorder>,
+    // If present publishers in this process are read from directly rather than over TCPROS
+    intra_process: Option<IntraProcess>,
+    // Address publishers are told to send UDPROS datagrams to
+    hostname: String,
+    // Transports offered to publishers, in order of preference
+    transports: Vec<Transport>,
+    // The node's client, used to request topics from publishers
+    xmlrpc_client: reqwest::Client,
+    // Largest message accepted from a publisher, see [crate::NodeHandle::set_max_message_size]
+    max_message_size: usize,
+    // Publisher connections and the time of the last message, see [crate::NodeHandle::stats]
+    activity: Arc<TopicActivity>,
+    // Runs the task receiving from each publisher
+    supervisor: TaskSupervisor,
 }
 
 impl Subscription {
+    #[allow(clippy::too_many_arguments)]
     pub fn new(
         node_name: &Name,
         topic_name: &str,
@@ -104,9 +899,15 @@ impl Subscription {
         queue_size: usize,
         msg_definition: String,
         md5sum: String,
+        wire_recorder: Option<WireRecorder>,
+        intra_process: Option<IntraProcess>,
+        hostname: &str,
+        transports: Vec<Transport>,
+        xmlrpc_client: reqwest::Client,
+        max_message_size: usize,
+        supervisor: TaskSupervisor,
     ) -> Self {
-        // Using Bytes for efficient cloning (reference counted) when there are multiple subscribers
-        let (sender, receiver) = broadcast::channel::<Bytes>(queue_size);
+        let (sender, receiver) = broadcast::channel::<ReceivedMessage>(queue_size);
         let connection_header = ConnectionHeader {
             caller_id: node_name.to_string(),
             latching: false,
@@ -117,6 +918,9 @@ impl Subscription {
             tcp_nodelay: false,
             service: None,
             persistent: None,
+            probe: false,
+            compression: compression::offer(),
+            shared_memory: shm::is_available(),
         };
 
         Self {
@@ -125,6 +929,14 @@ impl Subscription {
             msg_sender: sender,
             connection_header,
             known_publishers: Arc::new(RwLock::new(vec![])),
+            wire_recorder,
+            intra_process,
+            hostname: hostname.to_owned(),
+            transports,
+            xmlrpc_client,
+            max_message_size,
+            activity: Arc::new(TopicActivity::default()),
+            supervisor,
         }
     }
 
@@ -132,14 +944,33 @@ impl Subscription {
         self.connection_header.topic_type.as_str()
     }
 
-    pub fn get_receiver(&self) -> broadcast::Receiver<Bytes> {
+    pub(crate) fn stats(&self, topic: &str) -> TopicStats {
+        self.activity.stats(topic, self.topic_type(), None)
+    }
+
+    pub(crate) fn get_receiver(&self) -> broadcast::Receiver<ReceivedMessage> {
         self.msg_sender.subscribe()
     }
 
+    /// Number of receivers handed out by [Subscription::get_receiver] which are still alive
+    pub fn subscriber_count(&self) -> usize {
+        // Our own receiver is always counted
+        self.msg_sender.receiver_count() - 1
+    }
+
     pub async fn add_publisher_source(
         &mut self,
         publisher_uri: &str,
     ) -> Result<(), std::io::Error> {
+        if let Some(intra_process) = &self.intra_process {
+            if let Some(node_name) = intra_process.node_at(publisher_uri) {
+                let node_name = node_name.to_owned();
+                self.add_intra_process_sources(publisher_uri, &node_name)
+                    .await;
+                return Ok(());
+            }
+        }
+
         let is_new_connection = {
             !self
                 .known_publishers
@@ -156,78 +987,471 @@ impl Subscription {
             let sender = self.msg_sender.clone();
             let publisher_list = self.known_publishers.clone();
             let publisher_uri = publisher_uri.to_owned();
+            let wire_recorder = self.wire_recorder.clone();
+            let hostname = self.hostname.clone();
+            let transports = self.transports.clone();
+            let xmlrpc_client = self.xmlrpc_client.clone();
+            let max_message_size = self.max_message_size;
+            let activity = self.activity.clone();
             trace!("Creating new subscription connection for {publisher_uri} on {topic_name}");
-            let handle = tokio::spawn(async move {
-                if let Ok(mut stream) = establish_publisher_connection(
+            let task_name = topic_name.clone();
+            let supervisor = self.supervisor.clone();
+            let connection_task = async move {
+                let (mut connection, peer, source) = match connect_to_publisher(
+                    &xmlrpc_client,
                     &node_name,
                     &topic_name,
                     &publisher_uri,
-                    connection_header,
+                    &connection_header,
+                    &hostname,
+                    &transports,
+                    wire_recorder.as_ref(),
                 )
                 .await
                 {
-                    publisher_list.write().await.push(publisher_uri.to_owned());
-                    // Repeatedly read from the stream until its dry
-                    loop {
-                        trace!(
-                            "Subscription to {} receiving from {} is awaiting next body",
-                            topic_name,
-                            publisher_uri
+                    Ok(connected) => connected,
+                    Err(e) => {
+                        warn!(
+                            "Failed to connect to publisher {publisher_uri} for {topic_name}: {e}"
                         );
-                        match tcpros::receive_body(&mut stream).await {
-                            Ok(body) => {
-                                trace!(
-                                    "Subscription to {} receiving from {} received body",
-                                    topic_name,
-                                    publisher_uri
+                        supervisor.connection_event(ConnectionEvent::Failed {
+                            name: topic_name,
+                            kind: TaskKind::Subscriber,
+                            peer: publisher_uri,
+                            error: e.to_string(),
+                        });
+                        return;
+                    }
+                };
+                publisher_list.write().await.push(publisher_uri.to_owned());
+                supervisor.connection_event(ConnectionEvent::Connected {
+                    name: topic_name.clone(),
+                    kind: TaskKind::Subscriber,
+                    peer: publisher_uri.clone(),
+                });
+                let _connection = activity.connected();
+                // Repeatedly read from the connection until its dry
+                loop {
+                    trace!(
+                        "Subscription to {} receiving from {} is awaiting next body",
+                        topic_name,
+                        publisher_uri
+                    );
+                    match connection.receive_body(max_message_size).await {
+                        Ok(body) => {
+                            trace!(
+                                "Subscription to {} receiving from {} received body",
+                                topic_name,
+                                publisher_uri
+                            );
+                            if let Some(recorder) = &wire_recorder {
+                                recorder.record(
+                                    &topic_name,
+                                    &peer,
+                                    WireDirection::Incoming,
+                                    WireRecordKind::Message,
+                                    &body,
                                 );
-                                let send_result = sender.send(body);
-                                if let Err(err) = send_result {
-                                    log::error!("Unable to send message data due to dropped channel, closing connection: {err}");
-                                    break;
-                                }
                             }
-                            Err(e) => {
-                                log::debug!("Failed to read body from publisher connection: {e}, closing connection");
+                            activity.message(body.len());
+                            let send_result =
+                                sender.send(ReceivedMessage::new(body, source.clone()));
+                            if let Err(err) = send_result {
+                                log::error!("Unable to send message data due to dropped channel, closing connection: {err}");
                                 break;
                             }
                         }
+                        // The publisher going away is the normal end of a connection
+                        Err(e)
+                            if matches!(
+                                e.kind(),
+                                std::io::ErrorKind::UnexpectedEof
+                                    | std::io::ErrorKind::ConnectionReset
+                            ) =>
+                        {
+                            log::debug!("Publisher connection closed: {e}, closing connection");
+                            break;
+                        }
+                        Err(e) => {
+                            log::warn!("Failed to read body from publisher connection: {e}, closing connection");
+                            supervisor.connection_event(ConnectionEvent::Failed {
+                                name: topic_name.clone(),
+                                kind: TaskKind::Subscriber,
+                                peer: publisher_uri.clone(),
+                                error: e.to_string(),
+                            });
+                            break;
+                        }
                     }
                 }
-            });
+            };
+            let handle =
+                self.supervisor
+                    .spawn_internal(TaskKind::Subscriber, &task_name, connection_task);
             self.subscription_tasks.push(handle.into());
         }
 
         Ok(())
     }
+
+    // Connects directly to the publication of another node sharing our xmlrpc server
+    async fn add_intra_process_sources(&mut self, publisher_uri: &str, node_name: &str) {
+        let Some(intra_process) = &self.intra_process else {
+            return;
+        };
+        let topic_name = self.connection_header.topic.as_ref().unwrap();
+        let md5sum = self.connection_header.md5sum.as_deref().unwrap_or("*");
+        for publication in intra_process.publications(topic_name) {
+            if publication.node_name != node_name {
+                continue;
+            }
+            let mut known_publishers = self.known_publishers.write().await;
+            if known_publishers.iter().any(|known| known == publisher_uri) {
+                continue;
+            }
+            if md5sum != "*" && publication.md5sum != "*" && publication.md5sum != md5sum {
+                log::error!(
+                    "Not connecting to {} for {topic_name}, md5sum {} does not match ours of {md5sum}",
+                    publication.node_name,
+                    publication.md5sum
+                );
+                continue;
+            }
+            trace!(
+                "Creating intra-process subscription to {} on {topic_name}",
+                publication.node_name
+            );
+            known_publishers.push(publisher_uri.to_owned());
+            let handle = self.supervisor.spawn_internal(
+                TaskKind::Subscriber,
+                topic_name,
+                forward_intra_process(
+                    topic_name.clone(),
+                    publication,
+                    self.msg_sender.clone(),
+                    self.activity.clone(),
+                ),
+            );
+            self.subscription_tasks.push(handle.into());
+        }
+    }
 }
 
-async fn establish_publisher_connection(
+// Forwards messages from a publication in this process to a subscription, standing in for a TCPROS connection
+async fn forward_intra_process(
+    topic_name: String,
+    publication: IntraProcessPublication,
+    sender: broadcast::Sender<ReceivedMessage>,
+    activity: Arc<TopicActivity>,
+) {
+    let _connection = activity.connected();
+    let source = Arc::new(PublisherSource {
+        caller_id: publication.node_name.clone(),
+        md5sum: publication.md5sum.clone(),
+    });
+    // Subscribe before reading the latched message so nothing published in between is missed
+    let mut receiver = publication.sender.subscribe();
+    let mut priority_receiver = publication.priority_sender.subscribe();
+    if let Some(latched) = publication.latched.get() {
+        activity.message(latched.len());
+        if sender
+            .send(ReceivedMessage::new(latched, source.clone()))
+            .is_err()
+        {
+            return;
+        }
+    }
+    loop {
+        let received = tokio::select! {
+            biased;
+            body = priority_receiver.recv() => body,
+            body = receiver.recv() => body,
+        };
+        match received {
+            Ok(body) => {
+                activity.message(body.len());
+                if let Err(err) = sender.send(ReceivedMessage::new(body, source.clone())) {
+                    log::error!("Unable to send message data due to dropped channel, closing intra-process connection: {err}");
+                    break;
+                }
+            }
+            Err(RecvError::Lagged(n)) => {
+                activity.dropped(n);
+                warn!(
+                    "Intra-process subscription to {topic_name} from {} dropped {n} messages",
+                    publication.node_name
+                );
+            }
+            Err(RecvError::Closed) => {
+                log::debug!(
+                    "Intra-process publication of {topic_name} by {} closed",
+                    publication.node_name
+                );
+                break;
+            }
+        }
+    }
+}
+
+// An established connection to a publisher over one of the supported transports
+enum PublisherConnection {
+    Tcpros {
+        stream: TcpStream,
+        // Set if the publisher agreed to compress messages, see [crate::compression]
+        compression: Option<Compression>,
+        // Set if the publisher agreed to send messages through shared memory, see [crate::shm]
+        shared_memory: bool,
+    },
+    Udpros(UdprosConnection),
+}
+
+impl PublisherConnection {
+    async fn receive_body(&mut self, max_message_size: usize) -> Result<Bytes, std::io::Error> {
+        match self {
+            PublisherConnection::Tcpros {
+                stream,
+                shared_memory: true,
+                ..
+            } => {
+                let frame = tcpros::receive_body(stream, max_message_size).await?;
+                shm::read(&frame, max_message_size)
+            }
+            PublisherConnection::Tcpros {
+                stream,
+                compression: None,
+                ..
+            } => tcpros::receive_body(stream, max_message_size).await,
+            PublisherConnection::Tcpros {
+                stream,
+                compression: Some(compression),
+                ..
+            } => {
+                let frame = tcpros::receive_body(stream, max_message_size).await?;
+                compression::decompress(*compression, &frame, max_message_size)
+            }
+            PublisherConnection::Udpros(connection) => {
+                connection.receive_body(max_message_size).await
+            }
+        }
+    }
+}
+
+// Where the publisher told us to receive the topic from in its response to requestTopic
+enum TopicEndpoint {
+    Tcpros {
+        address: String,
+    },
+    Udpros {
+        address: String,
+        connection_id: u32,
+        max_datagram_size: u16,
+        header: Vec<u8>,
+    },
+}
+
+impl TopicEndpoint {
+    fn transport(&self) -> Transport {
+        match self {
+            TopicEndpoint::Tcpros { .. } => Transport::Tcpros,
+            TopicEndpoint::Udpros { .. } => Transport::Udpros,
+        }
+    }
+}
+
+// Offers the publisher every transport in order of preference and connects with the one it picks.
+// If that connection can't be established the transport is no longer offered and the publisher is asked again.
+// Returns the connection along with the address of the publisher's end of it and who the publisher is.
+#[allow(clippy::too_many_arguments)]
+async fn connect_to_publisher(
+    xmlrpc_client: &reqwest::Client,
     node_name: &str,
     topic_name: &str,
     publisher_uri: &str,
-    conn_header: ConnectionHeader,
-) -> Result<TcpStream, std::io::Error> {
-    let publisher_channel_uri = send_topic_request(node_name, topic_name, publisher_uri).await?;
+    conn_header: &ConnectionHeader,
+    hostname: &str,
+    transports: &[Transport],
+    wire_recorder: Option<&WireRecorder>,
+) -> Result<(PublisherConnection, String, Arc<PublisherSource>), std::io::Error> {
+    let mut transports = transports.to_vec();
+    loop {
+        // Bound before asking so the publisher can be told where to send datagrams
+        let mut udp_socket = None;
+        if transports.contains(&Transport::Udpros) {
+            match udpros::bind().await {
+                Ok(socket) => udp_socket = Some(socket),
+                Err(e) => {
+                    log::warn!(
+                        "Unable to bind a socket for UDPROS, only offering other transports: {e}"
+                    );
+                    transports.retain(|transport| *transport != Transport::Udpros);
+                }
+            }
+        }
+        if transports.is_empty() {
+            log::error!("No transports left to offer {publisher_uri} for {topic_name}");
+            return Err(std::io::ErrorKind::Unsupported.into());
+        }
+
+        let offers = transport_offers(&transports, conn_header, hostname, udp_socket.as_ref())?;
+        let endpoint =
+            send_topic_request(xmlrpc_client, node_name, topic_name, publisher_uri, offers).await?;
+        let transport = endpoint.transport();
+        if !transports.contains(&transport) {
+            log::error!("Publisher {publisher_uri} picked {transport:?} for {topic_name} which wasn't offered");
+            return Err(std::io::ErrorKind::Unsupported.into());
+        }
+        let result = match (endpoint, udp_socket) {
+            (TopicEndpoint::Tcpros { address }, _) => {
+                establish_publisher_connection(topic_name, &address, conn_header, wire_recorder)
+                    .await
+                    .map(|(stream, (compression, shared_memory), source)| {
+                        let connection = PublisherConnection::Tcpros {
+                            stream,
+                            compression,
+                            shared_memory,
+                        };
+                        (connection, address, source)
+                    })
+            }
+            (
+                TopicEndpoint::Udpros {
+                    address,
+                    connection_id,
+                    max_datagram_size,
+                    header,
+                },
+                Some(socket),
+            ) => establish_udpros_connection(
+                topic_name,
+                &address,
+                conn_header,
+                &header,
+                wire_recorder,
+            )
+            .map(|source| {
+                let connection = UdprosConnection::new(socket, connection_id, max_datagram_size);
+                (PublisherConnection::Udpros(connection), address, source)
+            }),
+            (TopicEndpoint::Udpros { .. }, None) => {
+                unreachable!("UDPROS is only offered with a socket")
+            }
+        };
+        match result {
+            Ok(connection) => return Ok(connection),
+            Err(e) if transports.len() > 1 => {
+                log::warn!(
+                    "Failed to establish {transport:?} connection to {publisher_uri} for {topic_name}, falling back to other transports: {e}"
+                );
+                transports.retain(|t| *t != transport);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+// Builds the list of protocols sent with requestTopic, see https://wiki.ros.org/ROS/Slave_API
+fn transport_offers(
+    transports: &[Transport],
+    conn_header: &ConnectionHeader,
+    hostname: &str,
+    udp_socket: Option<&UdpSocket>,
+) -> Result<serde_xmlrpc::Value, std::io::Error> {
+    let mut offers = vec![];
+    for transport in transports {
+        match (transport, udp_socket) {
+            (Transport::Tcpros, _) => {
+                offers.push(serde_xmlrpc::Value::Array(vec!["TCPROS".into()]));
+            }
+            (Transport::Udpros, Some(socket)) => {
+                // Unlike TCPROS the header is sent without its length in front
+                let header = conn_header.to_bytes(false)?;
+                offers.push(serde_xmlrpc::Value::Array(vec![
+                    "UDPROS".into(),
+                    serde_xmlrpc::Value::Base64(header[4..].to_vec()),
+                    hostname.into(),
+                    serde_xmlrpc::Value::Int(socket.local_addr()?.port() as i32),
+                    serde_xmlrpc::Value::Int(udpros::MAX_DATAGRAM_SIZE as i32),
+                ]));
+            }
+            (Transport::Udpros, None) => {}
+        }
+    }
+    Ok(serde_xmlrpc::Value::Array(offers))
+}
+
+// UDPROS has no handshake, the publisher's connection header is sent back in its response to requestTopic
+fn establish_udpros_connection(
+    topic_name: &str,
+    publisher_channel_uri: &str,
+    conn_header: &ConnectionHeader,
+    responded_header_bytes: &[u8],
+    wire_recorder: Option<&WireRecorder>,
+) -> Result<Arc<PublisherSource>, std::io::Error> {
+    if let Some(recorder) = wire_recorder {
+        recorder.record_received_header(topic_name, publisher_channel_uri, responded_header_bytes);
+    }
+    let responded_header = parse_responded_header(responded_header_bytes)?;
+    check_md5sum(topic_name, conn_header, &responded_header)?;
+    Ok(PublisherSource::from_header(&responded_header))
+}
+
+async fn establish_publisher_connection(
+    topic_name: &str,
+    publisher_channel_uri: &str,
+    conn_header: &ConnectionHeader,
+    wire_recorder: Option<&WireRecorder>,
+) -> Result<(TcpStream, (Option<Compression>, bool), Arc<PublisherSource>), std::io::Error> {
     let mut stream = TcpStream::connect(publisher_channel_uri).await?;
 
     let conn_header_bytes = conn_header.to_bytes(true)?;
+    if let Some(recorder) = wire_recorder {
+        recorder.record(
+            topic_name,
+            publisher_channel_uri,
+            WireDirection::Outgoing,
+            WireRecordKind::ConnectionHeader,
+            &conn_header_bytes,
+        );
+    }
     stream.write_all(&conn_header_bytes[..]).await?;
 
     let Ok(responded_header_bytes) = tcpros::receive_header_bytes(&mut stream).await else {
         // Some ROS tools appear to "probe" where they start a connection just to get the header
-        log::trace!("Could not read connection header bytes from publisher: {publisher_uri:?}");
+        log::trace!(
+            "Could not read connection header bytes from publisher: {publisher_channel_uri:?}"
+        );
         return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof));
     };
+    if let Some(recorder) = wire_recorder {
+        recorder.record_received_header(topic_name, publisher_channel_uri, &responded_header_bytes);
+    }
 
-    let responded_header = match ConnectionHeader::from_bytes(&responded_header_bytes) {
-        Ok(header) => header,
-        Err(e) => {
-            log::error!("Could not parse connection header data sent by publisher: {e:?}");
-            return Err(std::io::Error::from(std::io::ErrorKind::InvalidData));
-        }
-    };
+    let responded_header = parse_responded_header(&responded_header_bytes)?;
+    check_md5sum(topic_name, conn_header, &responded_header)?;
+    let compression = compression::accepted(responded_header.compression.as_deref())?;
+    if responded_header.shared_memory && !shm::is_available() {
+        log::error!("Publisher picked shared memory for {topic_name} which we did not offer");
+        return Err(std::io::ErrorKind::Unsupported.into());
+    }
+    Ok((
+        stream,
+        (compression, responded_header.shared_memory),
+        PublisherSource::from_header(&responded_header),
+    ))
+}
+
+fn parse_responded_header(header_bytes: &[u8]) -> Result<ConnectionHeader, std::io::Error> {
+    ConnectionHeader::from_bytes(header_bytes).map_err(|e| {
+        log::error!("Could not parse connection header data sent by publisher: {e:?}");
+        std::io::Error::from(std::io::ErrorKind::InvalidData)
+    })
+}
 
+fn check_md5sum(
+    topic_name: &str,
+    conn_header: &ConnectionHeader,
+    responded_header: &ConnectionHeader,
+) -> Result<(), std::io::Error> {
     if conn_header.md5sum == Some("*".to_string())
         || responded_header.md5sum == Some("*".to_string())
         || conn_header.md5sum == responded_header.md5sum
@@ -236,13 +1460,14 @@ async fn establish_publisher_connection(
             "Established connection with publisher for {:?}",
             conn_header.topic
         );
-        Ok(stream)
+        Ok(())
     } else {
         log::error!(
-            "Tried to subscribe to {}, but md5sums do not match. Expected {:?}, received {:?}",
+            "Tried to subscribe to {}, but md5sums do not match. Expected {:?}, received {:?}. {}",
             topic_name,
             conn_header.md5sum,
-            responded_header.md5sum
+            responded_header.md5sum,
+            conn_header.describe_definition_mismatch(responded_header)
         );
         Err(std::io::ErrorKind::InvalidData)
     }
@@ -250,24 +1475,21 @@ async fn establish_publisher_connection(
 }
 
 async fn send_topic_request(
+    xmlrpc_client: &reqwest::Client,
     node_name: &str,
     topic_name: &str,
     publisher_uri: &str,
-) -> Result<String, std::io::Error> {
-    let xmlrpc_client = reqwest::Client::new();
+    offers: serde_xmlrpc::Value,
+) -> Result<TopicEndpoint, std::io::Error> {
     let body = serde_xmlrpc::request_to_string(
         "requestTopic",
-        vec![
-            node_name.into(),
-            topic_name.into(),
-            serde_xmlrpc::Value::Array(vec![serde_xmlrpc::Value::Array(vec!["TCPROS".into()])]),
-        ],
+        vec![node_name.into(), topic_name.into(), offers],
     )
     .unwrap();
 
     let response = xmlrpc_client
         .post(publisher_uri)
-        .body(body)
+        .body(body.clone())
         .send()
         .await
         .map_err(|err| {
@@ -276,19 +1498,18 @@ async fn send_topic_request(
         })?;
     if response.status().is_success() {
         if let Ok(response_data) = response.text().await {
-            if let Ok((_code, _description, (protocol, hostname, port))) =
-                serde_xmlrpc::response_from_str::<(i8, String, (String, String, u16))>(
+            crate::xmlrpc_recorder::record(
+                crate::xmlrpc_recorder::XmlRpcDirection::Outgoing,
+                publisher_uri,
+                &body,
+                &response_data,
+            );
+            if let Ok((_code, _description, params)) =
+                serde_xmlrpc::response_from_str::<(i32, String, Vec<serde_xmlrpc::Value>)>(
                     &response_data,
                 )
             {
-                if protocol == "TCPROS" {
-                    let tcpros_endpoint = format!("{hostname}:{port}");
-                    log::debug!("Got a TCPROS publisher endpoint at {tcpros_endpoint}");
-                    Ok(tcpros_endpoint)
-                } else {
-                    log::error!("Got unsupported protocol {protocol}");
-                    Err(std::io::ErrorKind::Unsupported.into())
-                }
+                parse_topic_endpoint(&params)
             } else {
                 log::error!("Failed to deserialize requestTopic response {response_data}");
                 Err(std::io::ErrorKind::InvalidData.into())
@@ -305,6 +1526,42 @@ async fn send_topic_request(
     }
 }
 
+// Parses the protocol params from a publisher's response to requestTopic
+fn parse_topic_endpoint(params: &[serde_xmlrpc::Value]) -> Result<TopicEndpoint, std::io::Error> {
+    use serde_xmlrpc::Value;
+    match params {
+        [Value::String(protocol), Value::String(hostname), Value::Int(port)]
+            if protocol == "TCPROS" =>
+        {
+            let address = format!("{hostname}:{port}");
+            log::debug!("Got a TCPROS publisher endpoint at {address}");
+            Ok(TopicEndpoint::Tcpros { address })
+        }
+        [Value::String(protocol), Value::String(hostname), Value::Int(port), Value::Int(connection_id), Value::Int(max_datagram_size), Value::Base64(header)]
+            if protocol == "UDPROS" =>
+        {
+            let address = format!("{hostname}:{port}");
+            log::debug!("Got a UDPROS publisher endpoint at {address}");
+            Ok(TopicEndpoint::Udpros {
+                address,
+                connection_id: *connection_id as u32,
+                max_datagram_size: (*max_datagram_size)
+                    .try_into()
+                    .unwrap_or(udpros::MAX_DATAGRAM_SIZE),
+                header: header.clone(),
+            })
+        }
+        [Value::String(protocol), ..] => {
+            log::error!("Got unsupported protocol {protocol}");
+            Err(std::io::ErrorKind::Unsupported.into())
+        }
+        _ => {
+            log::error!("Failed to parse protocol params of requestTopic response {params:?}");
+            Err(std::io::ErrorKind::InvalidData.into())
+        }
+    }
+}
+
 #[derive(thiserror::Error, Debug)]
 pub enum SubscriberError {
     /// Deserialize Error from `serde_rosmsg::Error` (stored as String because of dyn Error)
@@ -319,3 +1576,37 @@ impl From<roslibrust_serde_rosmsg::Error> for SubscriberError {
         Self::DeserializeError(value.to_string())
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    // A serialized std_msgs/Header with an empty frame_id
+    fn header(seq: u32, secs: u32) -> Vec<u8> {
+        let mut msg = vec![16, 0, 0, 0];
+        msg.extend_from_slice(&seq.to_le_bytes());
+        msg.extend_from_slice(&secs.to_le_bytes());
+        msg.extend_from_slice(&[0; 8]);
+        msg
+    }
+
+    #[test]
+    fn drops_duplicates_within_window() {
+        let mut filter = DuplicateFilter::new(Duplicates::Header, 2);
+        assert!(!filter.is_duplicate(&header(1, 10)));
+        assert!(filter.is_duplicate(&header(1, 10)));
+        assert!(!filter.is_duplicate(&header(1, 11)));
+        assert!(!filter.is_duplicate(&header(2, 10)));
+        // Pushed out of the window
+        assert!(!filter.is_duplicate(&header(1, 10)));
+
+        // Only seq and stamp are compared
+        let mut renamed = header(2, 10);
+        renamed[16..].copy_from_slice(&[1, 0, 0, 0]);
+        assert!(filter.is_duplicate(&renamed));
+        let mut filter = DuplicateFilter::new(Duplicates::Content, 2);
+        assert!(!filter.is_duplicate(&header(2, 10)));
+        assert!(!filter.is_duplicate(&renamed));
+        assert!(filter.is_duplicate(&renamed));
+    }
+}
@@ -0,0 +1,393 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    future::Future,
+    panic::AssertUnwindSafe,
+    pin::Pin,
+    sync::{
+        atomic::{AtomicBool, AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+    task::{Context, Poll},
+    time::Duration,
+};
+use tokio::{
+    sync::broadcast,
+    task::{AbortHandle, JoinHandle},
+};
+
+// Tracking of the tasks a node spawns.
+// Panics inside a tokio task only abort that task, which for the background tasks behind publishers, subscribers
+// and services means the node silently stops doing part of its job. Every such task is run through a
+// TaskSupervisor which catches the panic, logs it and reports it to anyone watching TaskSupervisor::failures.
+// User tasks can be run the same way, optionally being restarted, and are aborted when the node shuts down.
+// Errors with individual peers are handled within the task serving that peer and reported as ConnectionEvents
+// rather than panicking, so a single misbehaving peer only loses its own connection.
+
+/// Delay before restarting a task spawned with [TaskSupervisor::spawn_restarting]
+const RESTART_DELAY: Duration = Duration::from_millis(100);
+
+/// What a supervised task is doing work for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TaskKind {
+    Publisher,
+    Subscriber,
+    ServiceServer,
+    ServiceClient,
+    /// Spawned with [TaskSupervisor::spawn] or [TaskSupervisor::spawn_restarting]
+    User,
+}
+
+/// A task currently running under a [TaskSupervisor]
+#[derive(Debug, Clone)]
+pub struct TaskInfo {
+    /// The topic or service for internal tasks, or the name given when spawning a user task
+    pub name: String,
+    pub kind: TaskKind,
+    /// Number of times the task has been restarted after panicking
+    pub restarts: usize,
+}
+
+/// Reported by [TaskSupervisor::failures] when a supervised task panics
+#[derive(Debug, Clone)]
+pub struct TaskFailure {
+    pub name: String,
+    pub kind: TaskKind,
+    /// The panic message, if it was a string
+    pub message: String,
+    /// True if the task is going to be restarted
+    pub restarting: bool,
+}
+
+/// Reported by [TaskSupervisor::connection_events] as peers connect to the node's topics and services
+#[derive(Debug, Clone)]
+pub enum ConnectionEvent {
+    /// A connection with a peer was established
+    Connected {
+        /// The topic or service
+        name: String,
+        kind: TaskKind,
+        /// Address or URI of the peer
+        peer: String,
+    },
+    /// A connection with a peer failed or was rejected, other connections for the topic or service carry on
+    Failed {
+        name: String,
+        kind: TaskKind,
+        peer: String,
+        error: String,
+    },
+}
+
+struct Entry {
+    info: TaskInfo,
+    // Only present for user tasks, internal tasks are stopped by dropping what owns them
+    abort: Option<AbortHandle>,
+}
+
+struct Inner {
+    tasks: Mutex<HashMap<u64, Entry>>,
+    next_id: AtomicU64,
+    failures: broadcast::Sender<TaskFailure>,
+    connection_events: broadcast::Sender<ConnectionEvent>,
+    shut_down: AtomicBool,
+}
+
+/// Tracks the tasks of a node, reporting any that panic.
+///
+/// Each node has one, see [crate::NodeHandle::supervisor], which runs the internal tasks of its publishers,
+/// subscribers and services and can also run tasks for the user.
+/// User tasks are aborted when the node shuts down or [TaskSupervisor::shutdown] is called.
+///
+/// ```ignore
+/// let supervisor = nh.supervisor().await?;
+/// let mut failures = supervisor.failures();
+/// supervisor.spawn_restarting("camera_watchdog", 3, || async { /* ... */ });
+/// while let Ok(failure) = failures.recv().await {
+///     log::error!("{} crashed: {}", failure.name, failure.message);
+/// }
+/// ```
+#[derive(Clone)]
+pub struct TaskSupervisor {
+    inner: Arc<Inner>,
+}
+
+impl Default for TaskSupervisor {
+    fn default() -> Self {
+        Self {
+            inner: Arc::new(Inner {
+                tasks: Mutex::new(HashMap::new()),
+                next_id: AtomicU64::new(0),
+                failures: broadcast::channel(32).0,
+                connection_events: broadcast::channel(64).0,
+                shut_down: AtomicBool::new(false),
+            }),
+        }
+    }
+}
+
+impl std::fmt::Debug for TaskSupervisor {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TaskSupervisor")
+            .field("tasks", &self.tasks())
+            .finish_non_exhaustive()
+    }
+}
+
+impl TaskSupervisor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Spawns a user task, which is aborted when the supervisor shuts down.
+    /// A panic in the task is logged and reported to [TaskSupervisor::failures] rather than lost.
+    pub fn spawn<F>(&self, name: &str, future: F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let mut future = Some(future);
+        self.spawn_restarting(name, 0, move || future.take().expect("Task restarted"))
+    }
+
+    /// Spawns a user task created by `factory`, calling it again to restart the task up to `max_restarts` times
+    /// if it panics. A task which completes without panicking is not restarted.
+    pub fn spawn_restarting<F, Fut>(
+        &self,
+        name: &str,
+        max_restarts: usize,
+        factory: F,
+    ) -> JoinHandle<()>
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        if self.is_shut_down() {
+            log::warn!("Task {name} was not started as its supervisor has shut down");
+            return tokio::spawn(async {});
+        }
+        let (id, handle) = self.spawn_tracked(TaskKind::User, name, max_restarts, factory);
+        if let Some(entry) = self.inner.tasks.lock().unwrap().get_mut(&id) {
+            entry.abort = Some(handle.abort_handle());
+        }
+        // Covers a shutdown which raced with us adding the abort handle
+        if self.is_shut_down() {
+            handle.abort();
+        }
+        handle
+    }
+
+    /// Spawns one of the node's own tasks, which is stopped by dropping or aborting the returned handle as usual
+    pub(crate) fn spawn_internal<F>(&self, kind: TaskKind, name: &str, future: F) -> JoinHandle<()>
+    where
+        F: Future<Output = ()> + Send + 'static,
+    {
+        let mut future = Some(future);
+        self.spawn_tracked(kind, name, 0, move || {
+            future.take().expect("Task restarted")
+        })
+        .1
+    }
+
+    fn spawn_tracked<F, Fut>(
+        &self,
+        kind: TaskKind,
+        name: &str,
+        max_restarts: usize,
+        mut factory: F,
+    ) -> (u64, JoinHandle<()>)
+    where
+        F: FnMut() -> Fut + Send + 'static,
+        Fut: Future<Output = ()> + Send + 'static,
+    {
+        let id = self.inner.next_id.fetch_add(1, Ordering::Relaxed);
+        // Added before spawning so the task can't finish and remove its entry before it exists
+        self.inner.tasks.lock().unwrap().insert(
+            id,
+            Entry {
+                info: TaskInfo {
+                    name: name.to_owned(),
+                    kind,
+                    restarts: 0,
+                },
+                abort: None,
+            },
+        );
+        let tracked = Tracked {
+            inner: self.inner.clone(),
+            id,
+        };
+        let name = name.to_owned();
+        let handle = tokio::spawn(async move {
+            let tracked = tracked;
+            let mut restarts = 0;
+            loop {
+                let Err(panic) = CatchUnwind(Box::pin(factory())).await else {
+                    return;
+                };
+                let restarting =
+                    restarts < max_restarts && !tracked.inner.shut_down.load(Ordering::Relaxed);
+                let message = panic_message(&*panic);
+                log::error!(
+                    "{kind:?} task {name} panicked: {message}{}",
+                    if restarting { ", restarting" } else { "" }
+                );
+                // No receivers is fine, nobody is watching for failures
+                let _ = tracked.inner.failures.send(TaskFailure {
+                    name: name.clone(),
+                    kind,
+                    message,
+                    restarting,
+                });
+                if !restarting {
+                    return;
+                }
+                restarts += 1;
+                if let Some(entry) = tracked.inner.tasks.lock().unwrap().get_mut(&tracked.id) {
+                    entry.info.restarts = restarts;
+                }
+                tokio::time::sleep(RESTART_DELAY).await;
+            }
+        });
+        (id, handle)
+    }
+
+    /// The tasks currently running, in no particular order
+    pub fn tasks(&self) -> Vec<TaskInfo> {
+        self.inner
+            .tasks
+            .lock()
+            .unwrap()
+            .values()
+            .map(|entry| entry.info.clone())
+            .collect()
+    }
+
+    /// Receives a [TaskFailure] for every supervised task that panics from now on
+    pub fn failures(&self) -> broadcast::Receiver<TaskFailure> {
+        self.inner.failures.subscribe()
+    }
+
+    /// Receives a [ConnectionEvent] for every peer connection made or lost from now on
+    pub fn connection_events(&self) -> broadcast::Receiver<ConnectionEvent> {
+        self.inner.connection_events.subscribe()
+    }
+
+    pub(crate) fn connection_event(&self, event: ConnectionEvent) {
+        // No receivers is fine, nobody is watching for events
+        let _ = self.inner.connection_events.send(event);
+    }
+
+    /// Aborts all user tasks and stops any further ones from starting.
+    /// Called automatically when the node shuts down.
+    pub fn shutdown(&self) {
+        self.inner.shut_down.store(true, Ordering::Relaxed);
+        for entry in self.inner.tasks.lock().unwrap().values() {
+            if let Some(abort) = &entry.abort {
+                abort.abort();
+            }
+        }
+    }
+
+    pub fn is_shut_down(&self) -> bool {
+        self.inner.shut_down.load(Ordering::Relaxed)
+    }
+}
+
+// Removes a task's entry however it ends, including being aborted
+struct Tracked {
+    inner: Arc<Inner>,
+    id: u64,
+}
+
+impl Drop for Tracked {
+    fn drop(&mut self) {
+        self.inner.tasks.lock().unwrap().remove(&self.id);
+    }
+}
+
+// Resolves to Err with the panic payload if polling the inner future panics
+struct CatchUnwind<F>(Pin<Box<F>>);
+
+impl<F: Future> Future for CatchUnwind<F> {
+    type Output = Result<F::Output, Box<dyn Any + Send>>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let future = self.get_mut().0.as_mut();
+        match std::panic::catch_unwind(AssertUnwindSafe(|| future.poll(cx))) {
+            Ok(Poll::Pending) => Poll::Pending,
+            Ok(Poll::Ready(output)) => Poll::Ready(Ok(output)),
+            Err(panic) => Poll::Ready(Err(panic)),
+        }
+    }
+}
+
+fn panic_message(panic: &(dyn Any + Send)) -> String {
+    if let Some(message) = panic.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = panic.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic".to_string()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::atomic::AtomicUsize;
+
+    #[test_log::test(tokio::test)]
+    async fn reports_and_restarts_panics() {
+        let supervisor = TaskSupervisor::new();
+        let mut failures = supervisor.failures();
+
+        let attempts = Arc::new(AtomicUsize::new(0));
+        let attempts_copy = attempts.clone();
+        let handle = supervisor.spawn_restarting("flaky", 1, move || {
+            let attempt = attempts_copy.fetch_add(1, Ordering::Relaxed);
+            async move {
+                if attempt < 5 {
+                    panic!("attempt {attempt} failed");
+                }
+            }
+        });
+
+        let failure = failures.recv().await.unwrap();
+        assert_eq!(failure.name, "flaky");
+        assert_eq!(failure.kind, TaskKind::User);
+        assert_eq!(failure.message, "attempt 0 failed");
+        assert!(failure.restarting);
+        let failure = failures.recv().await.unwrap();
+        assert_eq!(failure.message, "attempt 1 failed");
+        assert!(!failure.restarting);
+
+        // The panic doesn't reach the JoinHandle and the task is no longer tracked once it gives up
+        handle.await.unwrap();
+        assert_eq!(attempts.load(Ordering::Relaxed), 2);
+        assert!(supervisor.tasks().is_empty());
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn shutdown_aborts_user_tasks() {
+        let supervisor = TaskSupervisor::new();
+        let user = supervisor.spawn("forever", std::future::pending());
+        let internal =
+            supervisor.spawn_internal(TaskKind::Publisher, "/chatter", std::future::pending());
+        assert_eq!(supervisor.tasks().len(), 2);
+
+        supervisor.shutdown();
+        assert!(user.await.unwrap_err().is_cancelled());
+        let tasks = supervisor.tasks();
+        assert_eq!(tasks.len(), 1);
+        assert_eq!(tasks[0].kind, TaskKind::Publisher);
+        assert_eq!(tasks[0].name, "/chatter");
+
+        internal.abort();
+        let _ = internal.await;
+        assert!(supervisor.tasks().is_empty());
+
+        // Nothing new is started after shutdown
+        supervisor.spawn("late", async { panic!("should not run") });
+        assert!(supervisor.tasks().is_empty());
+    }
+}
@@ -1,11 +1,71 @@
-use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+use byteorder::{LittleEndian, WriteBytesExt};
 use bytes::Bytes;
 use log::*;
-use std::io::{Cursor, Read, Write};
+use std::io::Write;
 use tokio::net::TcpStream;
 
 use super::names::Name;
 
+/// Largest connection header we will accept from a peer.
+/// Headers carry the full message definition so can legitimately be tens of kilobytes,
+/// but a peer declaring more than this is either broken or hostile and we refuse to allocate
+/// for it.
+pub const MAX_HEADER_LENGTH: usize = 4 * 1024 * 1024;
+
+/// Describes why a connection header received from a peer could not be parsed.
+///
+/// Surfaced to callers wrapped in a [std::io::Error] of kind [std::io::ErrorKind::InvalidData]
+/// and can be recovered with [std::io::Error::get_ref] and downcasting.
+#[derive(thiserror::Error, Debug, Clone, PartialEq, Eq)]
+pub enum HeaderError {
+    /// The peer declared a header (or field) longer than [MAX_HEADER_LENGTH]
+    #[error("connection header declares length {length} which exceeds the limit of {max} bytes")]
+    TooLong { length: usize, max: usize },
+    /// A field declared more bytes than remain in the header, or the header ended mid length prefix
+    #[error("connection header truncated at byte {offset}: needed {needed} bytes but only {remaining} remain")]
+    Truncated {
+        offset: usize,
+        needed: usize,
+        remaining: usize,
+    },
+    /// A field was not valid utf8
+    #[error("connection header field at byte {offset} is not valid utf8")]
+    InvalidUtf8 { offset: usize },
+}
+
+/// Default limit on the size of a single message received, matching roscpp which treats a length prefix over
+/// a gigabyte as lost protocol synchronization. See [crate::NodeHandle::set_max_message_size].
+pub const DEFAULT_MAX_MESSAGE_SIZE: usize = 1_000_000_000;
+
+/// The error returned when a message body is larger than the configured limit.
+/// Wraps a [roslibrust_common::Error::MessageTooLarge] in an [std::io::ErrorKind::InvalidData] io error.
+pub(crate) fn message_too_large(size: usize, max: usize) -> std::io::Error {
+    std::io::Error::new(
+        std::io::ErrorKind::InvalidData,
+        roslibrust_common::Error::MessageTooLarge { size, max },
+    )
+}
+
+/// Converts an io error into a [roslibrust_common::Error], recovering the structured
+/// [roslibrust_common::Error::MessageTooLarge] from errors made by [message_too_large].
+pub(crate) fn into_error(err: std::io::Error) -> roslibrust_common::Error {
+    match err
+        .get_ref()
+        .and_then(|inner| inner.downcast_ref::<roslibrust_common::Error>())
+    {
+        Some(&roslibrust_common::Error::MessageTooLarge { size, max }) => {
+            roslibrust_common::Error::MessageTooLarge { size, max }
+        }
+        _ => roslibrust_common::Error::IoError(err),
+    }
+}
+
+impl From<HeaderError> for std::io::Error {
+    fn from(e: HeaderError) -> Self {
+        std::io::Error::new(std::io::ErrorKind::InvalidData, e)
+    }
+}
+
 // Implementation of ConnectionHeader is based off of ROS documentation here:
 // https://wiki.ros.org/ROS/Connection%20Header
 // and here:
@@ -23,6 +83,12 @@ pub struct ConnectionHeader {
     pub topic_type: String,
     pub tcp_nodelay: bool, // TODO this field should be optional and None for service clients and servers
     pub persistent: Option<bool>,
+    // Set by ros tools (e.g. rosservice) that connect to a service server only to discover its type
+    pub probe: bool,
+    // roslibrust specific, see [crate::compression]
+    pub compression: Option<String>,
+    // roslibrust specific, see [crate::shm]
+    pub shared_memory: bool,
     // TODO service server only has to respond with caller_id (all other fields optional)
 }
 
@@ -31,7 +97,18 @@ impl ConnectionHeader {
     /// This specifically expects to start at the first byte of the first field
     /// of the header and bypass the bytes representing the length of the header
     pub fn from_bytes(header_data: &[u8]) -> std::io::Result<ConnectionHeader> {
-        let mut cursor = Cursor::new(header_data);
+        Ok(Self::parse(header_data)?)
+    }
+
+    /// Same as [ConnectionHeader::from_bytes] but returns the structured [HeaderError].
+    /// Never panics or allocates more than the length of `header_data`, whatever the input.
+    pub fn parse(header_data: &[u8]) -> Result<ConnectionHeader, HeaderError> {
+        if header_data.len() > MAX_HEADER_LENGTH {
+            return Err(HeaderError::TooLong {
+                length: header_data.len(),
+                max: MAX_HEADER_LENGTH,
+            });
+        }
 
         let mut msg_definition = String::new();
         let mut caller_id = String::new();
@@ -42,16 +119,36 @@ impl ConnectionHeader {
         let mut topic_type = String::new();
         let mut tcp_nodelay = false;
         let mut persistent = None;
+        let mut probe = false;
+        let mut compression = None;
+        let mut shared_memory = false;
 
         // TODO: Unhandled: error, persistent
-        while cursor.position() < header_data.len() as u64 {
-            let field_length = cursor.read_u32::<LittleEndian>()? as usize;
-            let mut field = vec![0u8; field_length];
-            cursor.read_exact(&mut field)?;
-            let field = String::from_utf8(field).map_err(|e| {
-                warn!("Failed to parse field in connection header as valid utf8: {e:#?}, Full header: {header_data:#?}");
-                std::io::ErrorKind::InvalidData
+        let mut offset = 0;
+        while offset < header_data.len() {
+            let remaining = &header_data[offset..];
+            let Some((len_bytes, remaining)) = remaining.split_first_chunk::<4>() else {
+                return Err(HeaderError::Truncated {
+                    offset,
+                    needed: 4,
+                    remaining: remaining.len(),
+                });
+            };
+            // Check the declared length against what we actually have before touching it,
+            // a peer can claim anything here
+            let field_length = u32::from_le_bytes(*len_bytes) as usize;
+            if field_length > remaining.len() {
+                return Err(HeaderError::Truncated {
+                    offset: offset + 4,
+                    needed: field_length,
+                    remaining: remaining.len(),
+                });
+            }
+            let field = std::str::from_utf8(&remaining[..field_length]).map_err(|e| {
+                warn!("Failed to parse field in connection header as valid utf8: {e}");
+                HeaderError::InvalidUtf8 { offset: offset + 4 }
             })?;
+            offset += 4 + field_length;
             let equals_pos = match field.find('=') {
                 Some(pos) => pos,
                 None => continue,
@@ -91,15 +188,19 @@ impl ConnectionHeader {
                 // by certain ros tools when they initiate a service_client connection to a service server
                 // for the purpose of discovering the service type
                 // If you do `rosservice call /my_service` and hit TAB you'll see this field in the connection header
-                // we can ignore it
+                probe = &field[equals_pos + 1..] != "0";
             } else if field.starts_with("response_type=") || field.starts_with("request_type=") {
                 // More undocumented fields!
                 // Discovered in testing that some roscpp service servers will set these on service responses
                 // We can ignore em
+            } else if field.starts_with("compression=") {
+                compression = Some(field[equals_pos + 1..].to_owned());
+            } else if field.starts_with("shared_memory=") {
+                shared_memory = &field[equals_pos + 1..] != "0";
             } else if field.starts_with("error=") {
-                log::error!("Error reported in TCPROS connection header: {field}, full header: {header_data:#?}");
+                log::error!("Error reported in TCPROS connection header: {field}");
             } else {
-                log::warn!("Encountered unhandled field in connection header: {field}, full header: {header_data:#?}");
+                log::warn!("Encountered unhandled field in connection header: {field}");
             }
         }
 
@@ -113,6 +214,9 @@ impl ConnectionHeader {
             topic_type,
             tcp_nodelay,
             persistent,
+            probe,
+            compression,
+            shared_memory,
         };
         trace!(
             "Got connection header: {header:?} for topic {:?}",
@@ -175,6 +279,18 @@ impl ConnectionHeader {
             header_data.write_all(persistent.as_bytes())?;
         }
 
+        if let Some(compression) = self.compression.as_ref() {
+            let compression = format!("compression={}", compression);
+            header_data.write_u32::<LittleEndian>(compression.len() as u32)?;
+            header_data.write_all(compression.as_bytes())?;
+        }
+
+        if self.shared_memory {
+            let shared_memory = "shared_memory=1";
+            header_data.write_u32::<LittleEndian>(shared_memory.len() as u32)?;
+            header_data.write_all(shared_memory.as_bytes())?;
+        }
+
         // Now that we know the length, stick its value in the first 4 bytes
         let total_length = (header_data.len() - 4) as u32;
         for (idx, byte) in total_length.to_le_bytes().iter().enumerate() {
@@ -183,6 +299,24 @@ impl ConnectionHeader {
 
         Ok(header_data)
     }
+
+    /// Describes how the message definition in `remote` differs from ours, field by field, to explain an md5sum
+    /// mismatch. See [roslibrust_common::md5sum::diff_message_definitions].
+    pub fn describe_definition_mismatch(&self, remote: &ConnectionHeader) -> String {
+        let differences = roslibrust_common::md5sum::diff_message_definitions(
+            &self.topic_type,
+            &self.msg_definition,
+            &remote.msg_definition,
+        );
+        if differences.is_empty() {
+            return "The message definitions have the same fields, but were hashed differently"
+                .to_string();
+        }
+        format!(
+            "The message definitions differ:\n  {}",
+            differences.join("\n  ")
+        )
+    }
 }
 
 /// Creates a new TCP connection to the given server URI and sends the connection header.
@@ -253,6 +387,13 @@ pub async fn receive_header_bytes(stream: &mut TcpStream) -> Result<Vec<u8>, std
     let _num_bytes_read = stream.read_exact(&mut header_len_bytes).await?;
     // This is the length of the header itself
     let header_len = u32::from_le_bytes(header_len_bytes) as usize;
+    if header_len > MAX_HEADER_LENGTH {
+        return Err(HeaderError::TooLong {
+            length: header_len,
+            max: MAX_HEADER_LENGTH,
+        }
+        .into());
+    }
 
     // Initialize a buffer to hold the header
     let mut header_bytes = vec![0u8; header_len];
@@ -269,11 +410,20 @@ pub async fn receive_header(stream: &mut TcpStream) -> Result<ConnectionHeader,
 /// Reads the body of a message from the given stream.
 /// It first reads the length of the body, then reads the body itself.
 /// The returned Bytes includes the length of the body at the front as serde_rosmsg expects.
-pub async fn receive_body(stream: &mut TcpStream) -> Result<Bytes, std::io::Error> {
+///
+/// Bodies declaring a length over `max_message_size` are refused before anything is allocated for them,
+/// the stream can't be resynchronized after that so the connection should be closed.
+pub async fn receive_body(
+    stream: &mut TcpStream,
+    max_message_size: usize,
+) -> Result<Bytes, std::io::Error> {
     use bytes::{BufMut, BytesMut};
     use tokio::io::AsyncReadExt;
 
     let body_len = stream.read_u32_le().await? as usize;
+    if body_len > max_message_size {
+        return Err(message_too_large(body_len, max_message_size));
+    }
     let total_len = 4 + body_len;
 
     let mut buf = BytesMut::with_capacity(total_len);
@@ -292,7 +442,7 @@ pub async fn receive_body(stream: &mut TcpStream) -> Result<Bytes, std::io::Erro
 
 #[cfg(test)]
 mod test {
-    use super::ConnectionHeader;
+    use super::{ConnectionHeader, HeaderError, MAX_HEADER_LENGTH};
 
     // From ROS website: http://wiki.ros.org/ROS/Connection%20Header
     #[test_log::test]
@@ -344,4 +494,52 @@ mod test {
             Some("992ce8a1687cec8c8bd883ec73ca41d1".to_string())
         );
     }
+
+    #[test_log::test]
+    fn huge_field_length_is_rejected_without_allocating() {
+        // Declares a ~4GB field but only carries a few bytes
+        let bytes: Vec<u8> = vec![0xff, 0xff, 0xff, 0xff, b'a', b'=', b'b'];
+        assert_eq!(
+            ConnectionHeader::parse(&bytes).unwrap_err(),
+            HeaderError::Truncated {
+                offset: 4,
+                needed: u32::MAX as usize,
+                remaining: 3
+            }
+        );
+    }
+
+    #[test_log::test]
+    fn truncated_length_prefix_is_rejected() {
+        let mut bytes = vec![3, 0, 0, 0, b'a', b'=', b'b'];
+        bytes.extend_from_slice(&[1, 0]);
+        assert_eq!(
+            ConnectionHeader::parse(&bytes).unwrap_err(),
+            HeaderError::Truncated {
+                offset: 7,
+                needed: 4,
+                remaining: 2
+            }
+        );
+    }
+
+    #[test_log::test]
+    fn non_utf8_field_is_rejected() {
+        let bytes: Vec<u8> = vec![3, 0, 0, 0, b'a', b'=', 0xff];
+        let err = ConnectionHeader::from_bytes(&bytes).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::InvalidData);
+        assert_eq!(
+            err.get_ref().unwrap().downcast_ref::<HeaderError>(),
+            Some(&HeaderError::InvalidUtf8 { offset: 4 })
+        );
+    }
+
+    #[test_log::test]
+    fn oversized_header_is_rejected() {
+        let bytes = vec![0u8; MAX_HEADER_LENGTH + 1];
+        assert!(matches!(
+            ConnectionHeader::parse(&bytes),
+            Err(HeaderError::TooLong { .. })
+        ));
+    }
 }
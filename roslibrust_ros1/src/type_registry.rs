@@ -0,0 +1,134 @@
+use roslibrust_common::{MessageTypeVisitor, RosMessageType};
+use std::collections::HashMap;
+
+// Runtime lookup of message types by their ROS type name.
+// Generated code can populate a registry through `visit_message_types` when built with
+// `CodegenOptions::generate_type_visitor`, after which publishers can be created from a type name alone.
+
+/// Everything needed to publish or decode a message type only known by name at runtime
+#[derive(Clone, Copy)]
+pub struct RegisteredType {
+    /// The ROS type name e.g. "std_msgs/String"
+    pub type_name: &'static str,
+    pub md5sum: &'static str,
+    /// Full expanded message definition, as found in the generated DEFINITION constant
+    pub definition: &'static str,
+    encode: fn(serde_json::Value) -> Result<Vec<u8>, String>,
+    decode: fn(&[u8]) -> Result<serde_json::Value, String>,
+}
+
+impl RegisteredType {
+    /// Captures the information for a generated message type
+    pub fn of<T: RosMessageType>() -> Self {
+        Self {
+            type_name: T::ROS_TYPE_NAME,
+            md5sum: T::MD5SUM,
+            definition: T::DEFINITION,
+            encode: encode::<T>,
+            decode: decode::<T>,
+        }
+    }
+
+    /// Serializes a JSON representation of the message into the bytes sent on the wire.
+    /// The result includes the length prefix, matching what [crate::PublisherAny::publish] expects.
+    pub fn encode_json(&self, value: serde_json::Value) -> Result<Vec<u8>, String> {
+        (self.encode)(value)
+    }
+
+    /// Deserializes the bytes of a message, as received by [crate::SubscriberAny], into JSON
+    pub fn decode_json(&self, data: &[u8]) -> Result<serde_json::Value, String> {
+        (self.decode)(data)
+    }
+}
+
+impl std::fmt::Debug for RegisteredType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RegisteredType")
+            .field("type_name", &self.type_name)
+            .field("md5sum", &self.md5sum)
+            .finish_non_exhaustive()
+    }
+}
+
+fn encode<T: RosMessageType>(value: serde_json::Value) -> Result<Vec<u8>, String> {
+    let msg: T = serde_json::from_value(value).map_err(|e| e.to_string())?;
+    roslibrust_serde_rosmsg::to_vec(&msg).map_err(|e| e.to_string())
+}
+
+fn decode<T: RosMessageType>(data: &[u8]) -> Result<serde_json::Value, String> {
+    let msg: T = roslibrust_serde_rosmsg::from_slice(data).map_err(|e| e.to_string())?;
+    serde_json::to_value(&msg).map_err(|e| e.to_string())
+}
+
+/// A collection of message types which can be looked up by their ROS type name.
+///
+/// ```ignore
+/// let mut registry = TypeRegistry::new();
+/// // Registers every type generated with CodegenOptions::generate_type_visitor enabled
+/// my_messages::visit_message_types(&mut registry);
+/// let publisher = nh.advertise_by_type_name(&registry, "std_msgs/String", "/chatter", 1, false).await?;
+/// ```
+#[derive(Clone, Debug, Default)]
+pub struct TypeRegistry {
+    types: HashMap<String, RegisteredType>,
+}
+
+impl TypeRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a type to the registry, replacing any type previously registered with the same name
+    pub fn register<T: RosMessageType>(&mut self) {
+        self.types
+            .insert(T::ROS_TYPE_NAME.to_string(), RegisteredType::of::<T>());
+    }
+
+    /// Looks up a type by its ROS type name e.g. "std_msgs/String"
+    pub fn get(&self, type_name: &str) -> Option<&RegisteredType> {
+        self.types.get(type_name)
+    }
+
+    /// Names of all registered types, in no particular order
+    pub fn type_names(&self) -> impl Iterator<Item = &str> {
+        self.types.keys().map(String::as_str)
+    }
+}
+
+impl MessageTypeVisitor for TypeRegistry {
+    fn visit<T: RosMessageType>(&mut self) {
+        self.register::<T>();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use roslibrust_test::ros1::std_msgs;
+
+    #[test]
+    fn round_trips_json() {
+        let mut registry = TypeRegistry::new();
+        registry.register::<std_msgs::String>();
+        assert!(registry.get("std_msgs/Header").is_none());
+
+        let registered = registry.get("std_msgs/String").unwrap();
+        assert_eq!(registered.md5sum, std_msgs::String::MD5SUM);
+
+        let bytes = registered
+            .encode_json(serde_json::json!({ "data": "hello" }))
+            .unwrap();
+        let expected = roslibrust_serde_rosmsg::to_vec(&std_msgs::String {
+            data: "hello".to_string(),
+        })
+        .unwrap();
+        assert_eq!(bytes, expected);
+        assert_eq!(
+            registered.decode_json(&bytes).unwrap(),
+            serde_json::json!({ "data": "hello" })
+        );
+        assert!(registered
+            .encode_json(serde_json::json!({ "data": 5 }))
+            .is_err());
+    }
+}
@@ -0,0 +1,162 @@
+use bytes::{Bytes, BytesMut};
+use std::net::Ipv4Addr;
+use tokio::net::UdpSocket;
+
+// Receiving side of UDPROS, see https://wiki.ros.org/ROS/UDPROS
+// Messages are split into datagrams by the publisher, each prefixed with an 8 byte header carrying the
+// connection id, an op code, a message id and a block number. The reassembled data is the same length
+// prefixed message as sent over TCPROS.
+
+/// Largest datagram we ask publishers to send, matches roscpp's default
+pub const MAX_DATAGRAM_SIZE: u16 = 1500;
+
+const HEADER_LEN: usize = 8;
+// First datagram of a message, its block number is the total number of datagrams in the message
+const OP_DATA0: u8 = 0;
+// Following datagrams of a message, their block number is their index within the message
+const OP_DATAN: u8 = 1;
+
+/// Binds the socket a publisher will be asked to send datagrams to
+pub async fn bind() -> std::io::Result<UdpSocket> {
+    UdpSocket::bind((Ipv4Addr::UNSPECIFIED, 0)).await
+}
+
+/// A UDPROS connection from a single publisher, reassembles its datagrams into messages
+pub struct UdprosConnection {
+    socket: UdpSocket,
+    connection_id: u32,
+    datagram: Vec<u8>,
+    message: BytesMut,
+    // Id of the message being reassembled, None while waiting for the start of the next message
+    message_id: Option<u8>,
+    blocks: u16,
+    next_block: u16,
+}
+
+impl UdprosConnection {
+    pub fn new(socket: UdpSocket, connection_id: u32, max_datagram_size: u16) -> Self {
+        Self {
+            socket,
+            connection_id,
+            datagram: vec![0; max_datagram_size.max(MAX_DATAGRAM_SIZE) as usize],
+            message: BytesMut::new(),
+            message_id: None,
+            blocks: 0,
+            next_block: 0,
+        }
+    }
+
+    /// Reads datagrams until a full message has been received.
+    /// Like [crate::tcpros::receive_body] the returned Bytes includes the length of the body at the front.
+    ///
+    /// UDPROS has no retransmission, a message missing any of its datagrams is dropped.
+    /// Messages larger than `max_message_size` are dropped as soon as they grow past it.
+    pub async fn receive_body(&mut self, max_message_size: usize) -> std::io::Result<Bytes> {
+        loop {
+            let (len, _addr) = self.socket.recv_from(&mut self.datagram).await?;
+            if len < HEADER_LEN {
+                continue;
+            }
+            let connection_id = u32::from_le_bytes(self.datagram[0..4].try_into().unwrap());
+            let op_code = self.datagram[4];
+            let message_id = self.datagram[5];
+            let block = u16::from_le_bytes(self.datagram[6..8].try_into().unwrap());
+            if connection_id != self.connection_id {
+                continue;
+            }
+            let payload = &self.datagram[HEADER_LEN..len];
+            match op_code {
+                OP_DATA0 => {
+                    if self.message_id.is_some() {
+                        log::debug!("Dropping partially received UDPROS message");
+                    }
+                    self.message.clear();
+                    self.message.extend_from_slice(payload);
+                    self.message_id = Some(message_id);
+                    self.blocks = block;
+                    self.next_block = 1;
+                }
+                OP_DATAN if self.message_id == Some(message_id) && block == self.next_block => {
+                    self.message.extend_from_slice(payload);
+                    self.next_block += 1;
+                }
+                OP_DATAN => {
+                    if self.message_id.take().is_some() {
+                        log::debug!("Dropping UDPROS message {message_id} which lost a datagram");
+                    }
+                    continue;
+                }
+                // Pings and errors carry no message data
+                _ => continue,
+            }
+            if self.message.len() > max_message_size.saturating_add(4) {
+                log::warn!("Dropping UDPROS message larger than the maximum message size of {max_message_size} bytes");
+                self.message_id = None;
+                self.message.clear();
+                continue;
+            }
+            if self.message_id.is_some() && self.next_block >= self.blocks {
+                self.message_id = None;
+                let message = self.message.split().freeze();
+                if message.len() >= 4
+                    && u32::from_le_bytes(message[0..4].try_into().unwrap()) as usize
+                        == message.len() - 4
+                {
+                    return Ok(message);
+                }
+                log::warn!("Dropping UDPROS message whose length does not match its length header");
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn datagram(
+        connection_id: u32,
+        op_code: u8,
+        message_id: u8,
+        block: u16,
+        data: &[u8],
+    ) -> Vec<u8> {
+        let mut datagram = connection_id.to_le_bytes().to_vec();
+        datagram.push(op_code);
+        datagram.push(message_id);
+        datagram.extend_from_slice(&block.to_le_bytes());
+        datagram.extend_from_slice(data);
+        datagram
+    }
+
+    #[tokio::test]
+    async fn reassembles_messages() {
+        let receiver = bind().await.unwrap();
+        let port = receiver.local_addr().unwrap().port();
+        let mut connection = UdprosConnection::new(receiver, 7, MAX_DATAGRAM_SIZE);
+        let sender = UdpSocket::bind((Ipv4Addr::LOCALHOST, 0)).await.unwrap();
+        let send = |datagram: Vec<u8>| {
+            let sender = &sender;
+            async move {
+                sender
+                    .send_to(&datagram, (Ipv4Addr::LOCALHOST, port))
+                    .await
+                    .unwrap();
+            }
+        };
+
+        // Another connection's datagram is ignored
+        send(datagram(8, OP_DATA0, 0, 1, &[1, 0, 0, 0, 9])).await;
+        // A message missing its second datagram is dropped
+        send(datagram(7, OP_DATA0, 1, 3, &[3, 0, 0, 0, 1])).await;
+        send(datagram(7, OP_DATAN, 1, 2, &[3])).await;
+        // A message larger than the limit is dropped
+        send(datagram(7, OP_DATA0, 3, 1, &[3, 0, 0, 0, 1, 2, 3])).await;
+        // A message split over two datagrams
+        send(datagram(7, OP_DATA0, 2, 2, &[2, 0, 0, 0, 4])).await;
+        send(datagram(7, OP_DATAN, 2, 1, &[5])).await;
+
+        let body = connection.receive_body(2).await.unwrap();
+        assert_eq!(&body[..], &[2, 0, 0, 0, 4, 5]);
+    }
+}
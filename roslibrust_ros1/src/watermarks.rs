@@ -0,0 +1,133 @@
+use std::sync::{
+    atomic::{AtomicBool, Ordering},
+    Arc,
+};
+
+/// Which watermark of a [Watermarks] a queue has crossed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum QueuePressure {
+    /// The queue has filled to the high watermark, messages are arriving faster than they are being sent or read
+    High,
+    /// The queue has drained back down to the low watermark after reaching the high one
+    Low,
+}
+
+type PressureCallback = Arc<dyn Fn(QueuePressure, usize) + Send + Sync>;
+
+/// Reports when a publisher's or subscriber's queue crosses a high or low watermark, so an application can degrade
+/// gracefully (e.g. skip frames or reduce its rate) based on actual transport pressure.
+///
+/// The callback is given the crossed watermark and the queue's depth. It is called once when the depth reaches
+/// `high`, and not again until the depth has fallen to `low` and reached `high` once more, so a queue hovering
+/// around one watermark doesn't produce a stream of events.
+///
+/// A publisher's queue is checked each time a message is published, and a subscriber's each time a message is read
+/// from it. The callback is called from within those calls, so should return quickly.
+///
+/// ```no_run
+/// # async fn example(nh: roslibrust_ros1::NodeHandle) -> Result<(), roslibrust_ros1::NodeError> {
+/// # use roslibrust_test::ros1::*;
+/// use roslibrust_ros1::{QueuePressure, Watermarks};
+/// use std::sync::{atomic::{AtomicBool, Ordering}, Arc};
+///
+/// let skip_frames = Arc::new(AtomicBool::new(false));
+/// let flag = skip_frames.clone();
+/// let publisher = nh
+///     .advertise_builder::<sensor_msgs::Image>("/camera/image_raw")
+///     .queue_size(20)
+///     .queue_watermarks(Watermarks::new(15, 5, move |pressure, _depth| {
+///         flag.store(pressure == QueuePressure::High, Ordering::Relaxed);
+///     }))
+///     .build()
+///     .await?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct Watermarks {
+    high: usize,
+    low: usize,
+    callback: PressureCallback,
+    // Whether the queue has reached the high watermark and not yet drained to the low one
+    above: Arc<AtomicBool>,
+}
+
+impl Watermarks {
+    /// Calls `callback` when the queue's depth reaches `high`, then when it falls back to `low`.
+    ///
+    /// `low` is expected to be below `high`, otherwise it is treated as one less than `high`.
+    pub fn new(
+        high: usize,
+        low: usize,
+        callback: impl Fn(QueuePressure, usize) + Send + Sync + 'static,
+    ) -> Self {
+        let high = high.max(1);
+        Self {
+            high,
+            low: low.min(high - 1),
+            callback: Arc::new(callback),
+            above: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// The same watermarks and callback, watching a different queue
+    pub(crate) fn detached(&self) -> Self {
+        Self {
+            above: Arc::new(AtomicBool::new(false)),
+            ..self.clone()
+        }
+    }
+
+    /// Checks the queue's current depth against the watermarks, calling the callback if one was crossed
+    pub(crate) fn observe(&self, depth: usize) {
+        if depth >= self.high {
+            if !self.above.swap(true, Ordering::Relaxed) {
+                (self.callback)(QueuePressure::High, depth);
+            }
+        } else if depth <= self.low && self.above.swap(false, Ordering::Relaxed) {
+            (self.callback)(QueuePressure::Low, depth);
+        }
+    }
+}
+
+impl std::fmt::Debug for Watermarks {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Watermarks")
+            .field("high", &self.high)
+            .field("low", &self.low)
+            .finish_non_exhaustive()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::sync::Mutex;
+
+    #[test]
+    fn reports_each_crossing_once() {
+        let events = Arc::new(Mutex::new(vec![]));
+        let recorded = events.clone();
+        let watermarks = Watermarks::new(4, 1, move |pressure, depth| {
+            recorded.lock().unwrap().push((pressure, depth));
+        });
+        for depth in [0, 2, 4, 5, 3, 4, 2, 1, 0, 3, 6] {
+            watermarks.observe(depth);
+        }
+        assert_eq!(
+            *events.lock().unwrap(),
+            vec![
+                (QueuePressure::High, 4),
+                (QueuePressure::Low, 1),
+                (QueuePressure::High, 6)
+            ]
+        );
+
+        // A detached copy tracks its own queue
+        let detached = watermarks.detached();
+        detached.observe(0);
+        assert_eq!(events.lock().unwrap().len(), 3);
+        detached.observe(4);
+        assert_eq!(events.lock().unwrap().len(), 4);
+    }
+}
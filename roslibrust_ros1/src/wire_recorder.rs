@@ -0,0 +1,351 @@
+//! Debugging tool for capturing the raw bytes exchanged over TCPROS connections.
+//!
+//! When interoperating with roscpp or rospy nodes it is sometimes necessary to see exactly what went over the wire.
+//! A [WireRecorder] can be attached to a node with [crate::NodeHandle::set_wire_recorder], after which the connection
+//! headers and message frames of selected topics are appended to a file as they are sent and received.
+//!
+//! The file format is intentionally simple so it can be inspected with other tools:
+//! - The file starts with the 8 byte magic `RLTCPROS` followed by a little endian u32 format version (currently 1).
+//! - Each record is then (all integers little endian):
+//!   - u64 nanoseconds since the unix epoch when the record was captured
+//!   - u8 direction, 0 for data we sent and 1 for data we received
+//!   - u8 kind, 0 for a connection header and 1 for a message frame
+//!   - u16 length followed by the utf8 topic name
+//!   - u16 length followed by the utf8 address of the remote peer
+//!   - u32 length followed by the bytes exactly as they appeared on the wire, including TCPROS's own length prefix
+//!
+//! Records can be read back with [WireRecord::read_all].
+//!
+//! Writing happens on a dedicated thread, so recording never blocks the tasks doing the communication.
+
+use std::{
+    collections::HashSet,
+    fs::File,
+    io::{BufReader, BufWriter, Read, Write},
+    path::Path,
+    sync::{mpsc, Arc},
+    time::{SystemTime, UNIX_EPOCH},
+};
+
+const MAGIC: &[u8; 8] = b"RLTCPROS";
+const FORMAT_VERSION: u32 = 1;
+
+/// Whether a recorded frame was sent or received by this node
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WireDirection {
+    Outgoing,
+    Incoming,
+}
+
+/// What kind of data a recorded frame contains
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum WireRecordKind {
+    ConnectionHeader,
+    Message,
+}
+
+/// A single frame read back from a recording
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct WireRecord {
+    /// Nanoseconds since the unix epoch when the frame was captured
+    pub timestamp_ns: u64,
+    pub direction: WireDirection,
+    pub kind: WireRecordKind,
+    pub topic: String,
+    /// Address of the remote end of the connection
+    pub peer: String,
+    /// The frame exactly as it appeared on the wire, including its 4 byte length prefix
+    pub data: Vec<u8>,
+}
+
+impl WireRecord {
+    /// Reads every record from a file produced by a [WireRecorder]
+    pub fn read_all(path: impl AsRef<Path>) -> std::io::Result<Vec<WireRecord>> {
+        let mut reader = BufReader::new(File::open(path)?);
+        let mut magic = [0u8; 8];
+        reader.read_exact(&mut magic)?;
+        let version = read_u32(&mut reader)?;
+        if &magic != MAGIC || version != FORMAT_VERSION {
+            return Err(std::io::Error::new(
+                std::io::ErrorKind::InvalidData,
+                "Not a roslibrust wire recording, or unsupported version",
+            ));
+        }
+
+        let mut records = vec![];
+        loop {
+            // A clean end of file can only occur at a record boundary
+            let mut timestamp = [0u8; 8];
+            match reader.read_exact(&mut timestamp) {
+                Ok(()) => {}
+                Err(e) if e.kind() == std::io::ErrorKind::UnexpectedEof => break,
+                Err(e) => return Err(e),
+            }
+            let mut flags = [0u8; 2];
+            reader.read_exact(&mut flags)?;
+            let direction = match flags[0] {
+                0 => WireDirection::Outgoing,
+                _ => WireDirection::Incoming,
+            };
+            let kind = match flags[1] {
+                0 => WireRecordKind::ConnectionHeader,
+                _ => WireRecordKind::Message,
+            };
+            let topic = read_string(&mut reader)?;
+            let peer = read_string(&mut reader)?;
+            let data_len = read_u32(&mut reader)? as usize;
+            let mut data = vec![0u8; data_len];
+            reader.read_exact(&mut data)?;
+            records.push(WireRecord {
+                timestamp_ns: u64::from_le_bytes(timestamp),
+                direction,
+                kind,
+                topic,
+                peer,
+                data,
+            });
+        }
+        Ok(records)
+    }
+}
+
+/// Appends raw TCPROS traffic to a file for debugging, see the [module level docs](self) for the file format.
+///
+/// Cloning a WireRecorder produces another handle to the same file.
+/// The file is closed once every handle has been dropped and all recorded frames are written.
+#[derive(Clone)]
+pub struct WireRecorder {
+    writer: BackgroundWriter,
+    // If None all topics are recorded
+    topics: Option<Arc<HashSet<String>>>,
+}
+
+impl WireRecorder {
+    /// Creates (or truncates) the file at `path` and prepares to record all topics to it
+    pub fn create(path: impl AsRef<Path>) -> std::io::Result<WireRecorder> {
+        let mut file = BufWriter::new(File::create(path)?);
+        file.write_all(MAGIC)?;
+        file.write_all(&FORMAT_VERSION.to_le_bytes())?;
+        file.flush()?;
+        Ok(WireRecorder {
+            writer: BackgroundWriter::spawn("wire recording", file)?,
+            topics: None,
+        })
+    }
+
+    /// Blocks until every frame recorded so far has been written to the file
+    pub fn flush(&self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Limits recording to only the given topics, which are expected to be fully resolved names
+    pub fn with_topics<S: Into<String>>(mut self, topics: impl IntoIterator<Item = S>) -> Self {
+        self.topics = Some(Arc::new(topics.into_iter().map(Into::into).collect()));
+        self
+    }
+
+    /// True if traffic for the given topic should be recorded
+    pub(crate) fn records(&self, topic: &str) -> bool {
+        self.topics
+            .as_ref()
+            .is_none_or(|topics| topics.contains(topic))
+    }
+
+    /// Appends one frame to the recording
+    /// Failures are logged rather than returned, recording should never interfere with communication
+    pub(crate) fn record(
+        &self,
+        topic: &str,
+        peer: &str,
+        direction: WireDirection,
+        kind: WireRecordKind,
+        data: &[u8],
+    ) {
+        let timestamp_ns = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_nanos() as u64;
+        let direction = match direction {
+            WireDirection::Outgoing => 0u8,
+            WireDirection::Incoming => 1u8,
+        };
+        let kind = match kind {
+            WireRecordKind::ConnectionHeader => 0u8,
+            WireRecordKind::Message => 1u8,
+        };
+
+        let mut record = Vec::with_capacity(data.len() + topic.len() + peer.len() + 18);
+        // Writing into a Vec can't fail
+        let _ = write_record(
+            &mut record,
+            timestamp_ns,
+            [direction, kind],
+            topic,
+            peer,
+            data,
+        );
+        self.writer.write(record);
+    }
+
+    /// Records a connection header received via [crate::tcpros::receive_header_bytes]
+    /// That function strips the length prefix, so it is restored here to keep the record identical to the wire
+    pub(crate) fn record_received_header(&self, topic: &str, peer: &str, header_bytes: &[u8]) {
+        let mut frame = (header_bytes.len() as u32).to_le_bytes().to_vec();
+        frame.extend_from_slice(header_bytes);
+        self.record(
+            topic,
+            peer,
+            WireDirection::Incoming,
+            WireRecordKind::ConnectionHeader,
+            &frame,
+        );
+    }
+}
+
+enum WriterCommand {
+    Write(Vec<u8>),
+    Flush(mpsc::SyncSender<std::io::Result<()>>),
+}
+
+/// Writes to a file from a dedicated thread, fed through a channel
+/// Queued writes are batched, and the file is flushed whenever the queue runs empty so it stays useful even if the
+/// process doesn't exit cleanly. The thread exits once every handle has been dropped.
+#[derive(Clone)]
+pub(crate) struct BackgroundWriter {
+    sender: mpsc::Sender<WriterCommand>,
+}
+
+impl BackgroundWriter {
+    /// Starts the writer thread, `name` describes the file in thread names and error logs
+    pub(crate) fn spawn(
+        name: &'static str,
+        file: BufWriter<File>,
+    ) -> std::io::Result<BackgroundWriter> {
+        let (sender, receiver) = mpsc::channel();
+        std::thread::Builder::new()
+            .name(format!("roslibrust {name}"))
+            .spawn(move || run_writer(name, file, receiver))?;
+        Ok(BackgroundWriter { sender })
+    }
+
+    /// Queues data to be appended to the file
+    /// Failures are logged rather than returned, recording should never interfere with communication
+    pub(crate) fn write(&self, data: Vec<u8>) {
+        // The thread only exits once all senders are gone, so this can't fail while we hold one
+        let _ = self.sender.send(WriterCommand::Write(data));
+    }
+
+    /// Blocks until everything queued before this call has been written and flushed
+    pub(crate) fn flush(&self) -> std::io::Result<()> {
+        let (reply, result) = mpsc::sync_channel(1);
+        let _ = self.sender.send(WriterCommand::Flush(reply));
+        result
+            .recv()
+            .unwrap_or_else(|_| Err(std::io::ErrorKind::BrokenPipe.into()))
+    }
+}
+
+fn run_writer(name: &str, mut file: BufWriter<File>, receiver: mpsc::Receiver<WriterCommand>) {
+    while let Ok(command) = receiver.recv() {
+        // Write out everything that is already queued before paying for a flush
+        let mut next = Some(command);
+        while let Some(command) = next {
+            match command {
+                WriterCommand::Write(data) => {
+                    if let Err(e) = file.write_all(&data) {
+                        log::error!("Failed to write {name}: {e:?}");
+                    }
+                }
+                WriterCommand::Flush(reply) => {
+                    let _ = reply.send(file.flush());
+                }
+            }
+            next = receiver.try_recv().ok();
+        }
+        if let Err(e) = file.flush() {
+            log::error!("Failed to flush {name}: {e:?}");
+        }
+    }
+}
+
+fn write_record(
+    writer: &mut impl Write,
+    timestamp_ns: u64,
+    flags: [u8; 2],
+    topic: &str,
+    peer: &str,
+    data: &[u8],
+) -> std::io::Result<()> {
+    writer.write_all(&timestamp_ns.to_le_bytes())?;
+    writer.write_all(&flags)?;
+    write_string(writer, topic)?;
+    write_string(writer, peer)?;
+    writer.write_all(&(data.len() as u32).to_le_bytes())?;
+    writer.write_all(data)
+}
+
+fn write_string(writer: &mut impl Write, s: &str) -> std::io::Result<()> {
+    let bytes = &s.as_bytes()[..s.len().min(u16::MAX as usize)];
+    writer.write_all(&(bytes.len() as u16).to_le_bytes())?;
+    writer.write_all(bytes)
+}
+
+fn read_u32(reader: &mut impl Read) -> std::io::Result<u32> {
+    let mut bytes = [0u8; 4];
+    reader.read_exact(&mut bytes)?;
+    Ok(u32::from_le_bytes(bytes))
+}
+
+fn read_string(reader: &mut impl Read) -> std::io::Result<String> {
+    let mut len = [0u8; 2];
+    reader.read_exact(&mut len)?;
+    let mut bytes = vec![0u8; u16::from_le_bytes(len) as usize];
+    reader.read_exact(&mut bytes)?;
+    String::from_utf8(bytes).map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn round_trip_recording() {
+        let path = std::env::temp_dir().join(format!(
+            "roslibrust_wire_recording_{}.bin",
+            std::process::id()
+        ));
+        let recorder = WireRecorder::create(&path)
+            .unwrap()
+            .with_topics(["/chatter"]);
+        assert!(recorder.records("/chatter"));
+        assert!(!recorder.records("/other"));
+
+        recorder.record(
+            "/chatter",
+            "127.0.0.1:1234",
+            WireDirection::Incoming,
+            WireRecordKind::ConnectionHeader,
+            &[4, 0, 0, 0, 1, 2, 3, 4],
+        );
+        recorder.record(
+            "/chatter",
+            "127.0.0.1:1234",
+            WireDirection::Outgoing,
+            WireRecordKind::Message,
+            &[0, 0, 0, 0],
+        );
+        recorder.flush().unwrap();
+
+        let records = WireRecord::read_all(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(records.len(), 2);
+        assert_eq!(records[0].direction, WireDirection::Incoming);
+        assert_eq!(records[0].kind, WireRecordKind::ConnectionHeader);
+        assert_eq!(records[0].topic, "/chatter");
+        assert_eq!(records[0].peer, "127.0.0.1:1234");
+        assert_eq!(records[0].data, vec![4, 0, 0, 0, 1, 2, 3, 4]);
+        assert_eq!(records[1].direction, WireDirection::Outgoing);
+        assert_eq!(records[1].kind, WireRecordKind::Message);
+        assert!(records[1].timestamp_ns >= records[0].timestamp_ns);
+    }
+}
@@ -0,0 +1,407 @@
+//! Record and replay of the XML-RPC traffic between nodes and the master, for turning interop bugs into offline tests.
+//!
+//! Negotiation problems with particular roscpp / rospy versions usually come down to the exact XML-RPC they send.
+//! Installing an [XmlRpcRecorder] with [set_xmlrpc_recorder] captures every XML-RPC exchange this process makes
+//! with the master and other nodes, as well as the calls other nodes make to our xmlrpc servers.
+//! The recording can then be checked in as a fixture and served by an [XmlRpcReplay] in place of the master
+//! and peers, so the same negotiation happens in a test without any ROS installation.
+//!
+//! Recordings are JSON lines, one [XmlRpcExchange] per line, so fixtures are easy to read, trim and hand edit.
+//!
+//! ```no_run
+//! # async fn example() -> Result<(), Box<dyn std::error::Error>> {
+//! use roslibrust_ros1::xmlrpc_recorder::*;
+//! // While reproducing the bug against real ROS
+//! set_xmlrpc_recorder(Some(XmlRpcRecorder::create("chatter_negotiation.jsonl")?));
+//! // ... later, in a regression test
+//! let replay = XmlRpcReplay::serve(XmlRpcExchange::read_all("chatter_negotiation.jsonl")?)?;
+//! let nh = roslibrust_ros1::NodeHandle::new(replay.uri(), "/listener").await?;
+//! # Ok(())
+//! # }
+//! ```
+
+use crate::{wire_recorder::BackgroundWriter, RosMasterError, XmlRpcError};
+use abort_on_drop::ChildTask;
+use hyper::{Body, Response, StatusCode};
+use std::{
+    collections::{HashMap, VecDeque},
+    convert::Infallible,
+    fs::File,
+    io::{BufRead, BufReader, BufWriter},
+    net::{Ipv4Addr, SocketAddr},
+    path::Path,
+    sync::{Arc, Mutex, RwLock},
+};
+
+/// The recorder installed for this process, if any
+static RECORDER: RwLock<Option<XmlRpcRecorder>> = RwLock::new(None);
+
+/// Whether an exchange was a call this process made or a call made to it
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum XmlRpcDirection {
+    /// We called the master or another node
+    Outgoing,
+    /// Another node, or the master, called our xmlrpc server
+    Incoming,
+}
+
+/// A single XML-RPC request and the response to it
+#[derive(Clone, Debug, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub struct XmlRpcExchange {
+    pub direction: XmlRpcDirection,
+    /// The server that was called, for incoming calls this is our own xmlrpc server as the caller addressed it
+    pub uri: String,
+    /// Name of the method called, empty if the request could not be parsed
+    pub method: String,
+    /// The request body exactly as sent
+    pub request: String,
+    /// The response body exactly as received
+    pub response: String,
+}
+
+impl XmlRpcExchange {
+    /// Reads every exchange from a file produced by an [XmlRpcRecorder]
+    pub fn read_all(path: impl AsRef<Path>) -> std::io::Result<Vec<XmlRpcExchange>> {
+        let mut exchanges = vec![];
+        for line in BufReader::new(File::open(path)?).lines() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            exchanges.push(serde_json::from_str(&line)?);
+        }
+        Ok(exchanges)
+    }
+}
+
+/// Appends XML-RPC exchanges to a file as JSON lines, see the [module level docs](self).
+///
+/// Cloning an XmlRpcRecorder produces another handle to the same file.
+/// The file is closed once every handle has been dropped and all recorded exchanges are written.
+#[derive(Clone)]
+pub struct XmlRpcRecorder {
+    writer: BackgroundWriter,
+}
+
+impl XmlRpcRecorder {
+    /// Creates (or truncates) the file at `path` to record to
+    pub fn create(path: impl AsRef<Path>) -> std::io::Result<XmlRpcRecorder> {
+        Ok(XmlRpcRecorder {
+            writer: BackgroundWriter::spawn(
+                "xmlrpc recording",
+                BufWriter::new(File::create(path)?),
+            )?,
+        })
+    }
+
+    /// Blocks until every exchange recorded so far has been written to the file
+    pub fn flush(&self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    /// Appends one exchange to the recording
+    /// Failures are logged rather than returned, recording should never interfere with communication
+    fn record(&self, exchange: &XmlRpcExchange) {
+        match serde_json::to_vec(exchange) {
+            Ok(mut line) => {
+                line.push(b'\n');
+                self.writer.write(line);
+            }
+            Err(e) => log::error!("Failed to encode xmlrpc recording: {e:?}"),
+        }
+    }
+}
+
+/// Installs a recorder which captures all XML-RPC traffic of every node in this process, or removes it with None
+pub fn set_xmlrpc_recorder(recorder: Option<XmlRpcRecorder>) {
+    *RECORDER.write().unwrap() = recorder;
+}
+
+/// True if an [XmlRpcRecorder] is installed, used to skip the work of capturing bodies when not recording
+pub(crate) fn is_recording() -> bool {
+    RECORDER.read().unwrap().is_some()
+}
+
+/// Records an exchange with the installed recorder, if there is one
+pub(crate) fn record(direction: XmlRpcDirection, uri: &str, request: &str, response: &str) {
+    let Some(recorder) = RECORDER.read().unwrap().clone() else {
+        return;
+    };
+    let method = serde_xmlrpc::request_from_str(request)
+        .map(|(method, _args)| method)
+        .unwrap_or_default();
+    recorder.record(&XmlRpcExchange {
+        direction,
+        uri: uri.to_string(),
+        method,
+        request: request.to_string(),
+        response: response.to_string(),
+    });
+}
+
+/// Serves recorded responses in place of the master and the peers a recording was made against.
+///
+/// Each call is answered with the next recorded response to an outgoing call of the same method, in the order they
+/// were recorded. Any URI of a server called in the recording is rewritten to point at the replay, so follow up
+/// calls to peers (e.g. requestTopic) are answered by it as well. Calls with no recorded response left are answered
+/// with a ROS error response and can be checked for with [XmlRpcReplay::unanswered].
+pub struct XmlRpcReplay {
+    uri: String,
+    state: Arc<ReplayState>,
+    incoming: Vec<XmlRpcExchange>,
+    _handle: ChildTask<()>,
+}
+
+struct ReplayState {
+    // Method name -> responses not yet given, in recorded order
+    responses: Mutex<HashMap<String, VecDeque<String>>>,
+    // Methods called after their recorded responses ran out
+    unanswered: Mutex<Vec<String>>,
+}
+
+impl XmlRpcReplay {
+    /// Starts serving the given exchanges on a random port of localhost
+    pub fn serve(exchanges: Vec<XmlRpcExchange>) -> Result<XmlRpcReplay, XmlRpcError> {
+        let incoming =
+            hyper::server::conn::AddrIncoming::bind(&SocketAddr::from((Ipv4Addr::LOCALHOST, 0)))?;
+        let uri = format!("http://127.0.0.1:{}", incoming.local_addr().port());
+
+        // Every server called during the recording is now played by us
+        let mut recorded_uris: Vec<String> = exchanges
+            .iter()
+            .filter(|exchange| exchange.direction == XmlRpcDirection::Outgoing)
+            .map(|exchange| exchange.uri.trim_end_matches('/').to_string())
+            .collect();
+        recorded_uris.sort();
+        recorded_uris.dedup();
+        let rewrite = |body: &str| {
+            recorded_uris
+                .iter()
+                .fold(body.to_string(), |body, recorded| {
+                    body.replace(recorded, &uri)
+                })
+        };
+
+        let mut responses: HashMap<String, VecDeque<String>> = HashMap::new();
+        let mut incoming_calls = vec![];
+        for mut exchange in exchanges {
+            match exchange.direction {
+                XmlRpcDirection::Outgoing => responses
+                    .entry(exchange.method)
+                    .or_default()
+                    .push_back(rewrite(&exchange.response)),
+                XmlRpcDirection::Incoming => {
+                    exchange.request = rewrite(&exchange.request);
+                    incoming_calls.push(exchange);
+                }
+            }
+        }
+        let state = Arc::new(ReplayState {
+            responses: Mutex::new(responses),
+            unanswered: Mutex::new(vec![]),
+        });
+
+        let state_copy = state.clone();
+        let make_svc = hyper::service::make_service_fn(move |_connection| {
+            let state = state_copy.clone();
+            async move {
+                Ok::<_, Infallible>(hyper::service::service_fn(move |req| {
+                    Self::respond(state.clone(), req)
+                }))
+            }
+        });
+        let server = hyper::server::Server::builder(incoming).serve(make_svc);
+        let handle = tokio::spawn(async {
+            if let Err(err) = server.await {
+                log::error!("xmlrpc replay server encountered error: {err:?}");
+            }
+        });
+
+        Ok(XmlRpcReplay {
+            uri,
+            state,
+            incoming: incoming_calls,
+            _handle: handle.into(),
+        })
+    }
+
+    /// URI to use as the master URI of the node under test
+    pub fn uri(&self) -> &str {
+        &self.uri
+    }
+
+    /// Number of recorded responses which haven't been requested yet
+    ///
+    /// Reaching zero shows the node under test made at least the calls that were recorded.
+    pub fn remaining(&self) -> usize {
+        self.state
+            .responses
+            .lock()
+            .unwrap()
+            .values()
+            .map(VecDeque::len)
+            .sum()
+    }
+
+    /// Methods which were called with no recorded response left to give, in the order they were called
+    pub fn unanswered(&self) -> Vec<String> {
+        self.state.unanswered.lock().unwrap().clone()
+    }
+
+    /// Sends the recorded incoming calls, in order, to the xmlrpc server at `node_uri`.
+    ///
+    /// Returns each recorded exchange together with the response actually received from the node under test,
+    /// allowing e.g. a recorded roscpp `publisherUpdate` to be delivered to a roslibrust node.
+    pub async fn replay_incoming(
+        &self,
+        node_uri: &str,
+    ) -> Result<Vec<(XmlRpcExchange, String)>, RosMasterError> {
+        let client = crate::master_client::xmlrpc_client();
+        let mut results = vec![];
+        for exchange in &self.incoming {
+            let response = client
+                .post(node_uri)
+                .body(exchange.request.clone())
+                .send()
+                .await?
+                .text()
+                .await?;
+            results.push((exchange.clone(), response));
+        }
+        Ok(results)
+    }
+
+    async fn respond(
+        state: Arc<ReplayState>,
+        req: hyper::Request<Body>,
+    ) -> Result<Response<Body>, Infallible> {
+        let method = match hyper::body::to_bytes(req.into_body()).await {
+            Ok(body) => serde_xmlrpc::request_from_str(&String::from_utf8_lossy(&body))
+                .map(|(method, _args)| method)
+                .unwrap_or_default(),
+            Err(_) => String::new(),
+        };
+        let response = state
+            .responses
+            .lock()
+            .unwrap()
+            .get_mut(&method)
+            .and_then(VecDeque::pop_front);
+        let body = match response {
+            Some(response) => response,
+            None => {
+                log::warn!("xmlrpc replay has no recorded response for {method}");
+                state.unanswered.lock().unwrap().push(method.clone());
+                serde_xmlrpc::response_to_string(
+                    vec![serde_xmlrpc::Value::Array(vec![
+                        (-1).into(),
+                        format!("No recorded response for {method}").into(),
+                        0.into(),
+                    ])]
+                    .into_iter(),
+                )
+                .unwrap_or_default()
+            }
+        };
+        Ok(Response::builder()
+            .status(StatusCode::OK)
+            .body(Body::from(body))
+            .unwrap())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn exchange(
+        direction: XmlRpcDirection,
+        uri: &str,
+        method: &str,
+        response: &str,
+    ) -> XmlRpcExchange {
+        XmlRpcExchange {
+            direction,
+            uri: uri.to_string(),
+            method: method.to_string(),
+            request: serde_xmlrpc::request_to_string(method, vec!["/caller".into()]).unwrap(),
+            response: response.to_string(),
+        }
+    }
+
+    #[test]
+    fn recordings_round_trip() {
+        let path =
+            std::env::temp_dir().join(format!("xmlrpc_recording_{}.jsonl", std::process::id()));
+        let recorder = XmlRpcRecorder::create(&path).unwrap();
+        let recorded = exchange(
+            XmlRpcDirection::Outgoing,
+            "http://localhost:11311",
+            "getUri",
+            "<methodResponse/>",
+        );
+        recorder.record(&recorded);
+        recorder.record(&XmlRpcExchange {
+            direction: XmlRpcDirection::Incoming,
+            ..recorded.clone()
+        });
+        recorder.flush().unwrap();
+        let read = XmlRpcExchange::read_all(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        assert_eq!(read.len(), 2);
+        assert_eq!(read[0], recorded);
+        assert_eq!(read[1].direction, XmlRpcDirection::Incoming);
+    }
+
+    #[tokio::test]
+    async fn replay_answers_in_recorded_order_and_rewrites_uris() {
+        let peer = "http://talker:41234/";
+        let replay = XmlRpcReplay::serve(vec![
+            exchange(
+                XmlRpcDirection::Outgoing,
+                "http://localhost:11311",
+                "lookupNode",
+                "first",
+            ),
+            exchange(
+                XmlRpcDirection::Outgoing,
+                "http://localhost:11311",
+                "lookupNode",
+                peer,
+            ),
+            exchange(XmlRpcDirection::Outgoing, peer, "getPid", "pid"),
+        ])
+        .unwrap();
+        assert_eq!(replay.remaining(), 3);
+
+        let client = crate::master_client::xmlrpc_client();
+        let call = |method: &'static str| {
+            let client = client.clone();
+            let uri = replay.uri().to_string();
+            async move {
+                let body = serde_xmlrpc::request_to_string(method, vec!["/caller".into()]).unwrap();
+                client
+                    .post(uri)
+                    .body(body)
+                    .send()
+                    .await
+                    .unwrap()
+                    .text()
+                    .await
+                    .unwrap()
+            }
+        };
+        assert_eq!(call("lookupNode").await, "first");
+        // The peer's uri now points at the replay
+        assert_eq!(call("lookupNode").await, format!("{}/", replay.uri()));
+        assert_eq!(call("getPid").await, "pid");
+        assert_eq!(replay.remaining(), 0);
+
+        // Ran out of responses
+        let (code, _msg, _value): (i32, String, i32) =
+            serde_xmlrpc::response_from_str(&call("getPid").await).unwrap();
+        assert_eq!(code, -1);
+        assert_eq!(replay.unanswered(), vec!["getPid".to_string()]);
+    }
+}
@@ -0,0 +1,103 @@
+#[cfg(feature = "ros1_test")]
+mod tests {
+    use byteorder::{LittleEndian, ReadBytesExt, WriteBytesExt};
+    use roslibrust_ros1::action::{ActionInfo, DynamicActionClient, GoalStatus};
+    use roslibrust_ros1::NodeHandle;
+    use std::io::{Cursor, Read};
+    use tokio::time::{timeout, Duration};
+
+    fn write_string(out: &mut Vec<u8>, s: &str) {
+        out.write_u32::<LittleEndian>(s.len() as u32).unwrap();
+        out.extend_from_slice(s.as_bytes());
+    }
+
+    fn read_string(cursor: &mut Cursor<&[u8]>) -> String {
+        let len = cursor.read_u32::<LittleEndian>().unwrap() as usize;
+        let mut buf = vec![0u8; len];
+        cursor.read_exact(&mut buf).unwrap();
+        String::from_utf8(buf).unwrap()
+    }
+
+    // Builds the length prefixed body of an ActionResult reporting the goal as succeeded
+    fn action_result(goal_id: &str, result: &[u8]) -> Vec<u8> {
+        let mut body = vec![];
+        // Header
+        body.extend_from_slice(&[0; 12]);
+        write_string(&mut body, "");
+        // GoalStatus
+        body.extend_from_slice(&[0; 8]);
+        write_string(&mut body, goal_id);
+        body.push(GoalStatus::SUCCEEDED);
+        write_string(&mut body, "done");
+        body.extend_from_slice(result);
+
+        let mut framed = vec![];
+        framed.write_u32::<LittleEndian>(body.len() as u32).unwrap();
+        framed.extend_from_slice(&body);
+        framed
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn dynamic_action_client_round_trip(
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let server_nh = NodeHandle::new("http://localhost:11311", "fake_action_server").await?;
+        let client_nh = NodeHandle::new("http://localhost:11311", "dynamic_action_client").await?;
+
+        // A minimal stand in for an action server, advertising the topics used to discover it
+        let _status = server_nh
+            .advertise_any(
+                "/test_dynamic_action/status",
+                "actionlib_msgs/GoalStatusArray",
+                "uint8[] data",
+                1,
+                false,
+            )
+            .await?;
+        let result_publisher = server_nh
+            .advertise_any(
+                "/test_dynamic_action/result",
+                "actionlib_tutorials/FibonacciActionResult",
+                "uint8[] data",
+                1,
+                false,
+            )
+            .await?;
+        let mut goals = server_nh
+            .subscribe_any("/test_dynamic_action/goal", 1)
+            .await?;
+
+        let info = ActionInfo::lookup(&client_nh, "/test_dynamic_action/")
+            .await?
+            .expect("Action server should be discovered");
+        assert_eq!(info.action_type, "actionlib_tutorials/Fibonacci");
+        assert_eq!(info.goal_type(), "actionlib_tutorials/FibonacciGoal");
+        assert!(ActionInfo::list(&client_nh).await?.contains(&info));
+
+        let client = DynamicActionClient::new(&client_nh, info, "int32 order").await?;
+        // Give the connections time to form
+        tokio::time::sleep(Duration::from_millis(500)).await;
+        let mut goal = client.send_goal(&5i32.to_le_bytes()).await?;
+
+        let received = timeout(Duration::from_secs(2), goals.next())
+            .await?
+            .unwrap()?;
+        let mut cursor = Cursor::new(received.as_ref());
+        // Skip the header and goal_id stamp
+        cursor.set_position(12);
+        assert_eq!(read_string(&mut cursor), "");
+        cursor.set_position(cursor.position() + 8);
+        assert_eq!(read_string(&mut cursor), goal.goal_id());
+        assert_eq!(cursor.read_i32::<LittleEndian>()?, 5);
+
+        result_publisher
+            .publish(action_result(goal.goal_id(), &[1, 2, 3]))
+            .await?;
+        let (status, result) = timeout(Duration::from_secs(2), goal.result()).await??;
+        assert_eq!(status.status, GoalStatus::SUCCEEDED);
+        assert_eq!(status.text, "done");
+        assert_eq!(result.as_ref(), &[1, 2, 3]);
+        assert_eq!(goal.status(), Some(status));
+
+        Ok(())
+    }
+}
@@ -0,0 +1,76 @@
+#[cfg(feature = "ros1_test")]
+mod tests {
+    use roslibrust_ros1::{Bond, BondOptions, BondState, NodeHandle};
+    use std::sync::{
+        atomic::{AtomicBool, Ordering},
+        Arc,
+    };
+    use tokio::time::{timeout, Duration};
+
+    fn fast_options() -> BondOptions {
+        BondOptions::default()
+            .heartbeat_period(Duration::from_millis(100))
+            .heartbeat_timeout(Duration::from_millis(500))
+            .connect_timeout(Duration::from_secs(5))
+            .disconnect_timeout(Duration::from_millis(500))
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn bond_forms_and_breaks() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let nh_a = NodeHandle::new("http://localhost:11311", "bond_node_a").await?;
+        let nh_b = NodeHandle::new("http://localhost:11311", "bond_node_b").await?;
+
+        let bond_a =
+            Bond::new(&nh_a, "/test_bond", "bond_forms_and_breaks", fast_options()).await?;
+        let bond_b =
+            Bond::new(&nh_b, "/test_bond", "bond_forms_and_breaks", fast_options()).await?;
+        assert_ne!(bond_a.instance_id(), bond_b.instance_id());
+
+        let broken = Arc::new(AtomicBool::new(false));
+        let broken_copy = broken.clone();
+        bond_b.on_broken(move || broken_copy.store(true, Ordering::SeqCst));
+
+        assert!(timeout(Duration::from_secs(5), bond_a.wait_until_formed()).await?);
+        assert!(timeout(Duration::from_secs(5), bond_b.wait_until_formed()).await?);
+        assert_eq!(bond_b.state(), BondState::Alive);
+
+        // Breaking one side is noticed by the other without waiting for the heartbeat timeout
+        timeout(Duration::from_secs(2), bond_a.break_bond()).await?;
+        timeout(Duration::from_secs(2), bond_b.wait_until_broken()).await?;
+        tokio::time::sleep(Duration::from_millis(50)).await;
+        assert!(broken.load(Ordering::SeqCst));
+        Ok(())
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn bond_breaks_when_dropped() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let nh_a = NodeHandle::new("http://localhost:11311", "bond_dropped_a").await?;
+        let nh_b = NodeHandle::new("http://localhost:11311", "bond_dropped_b").await?;
+
+        let bond_a = Bond::new(&nh_a, "/test_bond", "bond_dropped", fast_options()).await?;
+        let bond_b = Bond::new(&nh_b, "/test_bond", "bond_dropped", fast_options()).await?;
+        assert!(timeout(Duration::from_secs(5), bond_b.wait_until_formed()).await?);
+
+        // Dropping a bond breaks it in the background
+        drop(bond_a);
+        timeout(Duration::from_secs(3), bond_b.wait_until_broken()).await?;
+        assert!(bond_b.is_broken());
+        Ok(())
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn bond_without_sister_times_out() -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    {
+        let nh = NodeHandle::new("http://localhost:11311", "bond_lonely").await?;
+        let bond = Bond::new(
+            &nh,
+            "/test_bond",
+            "bond_lonely",
+            fast_options().connect_timeout(Duration::from_millis(300)),
+        )
+        .await?;
+        assert!(!timeout(Duration::from_secs(2), bond.wait_until_formed()).await?);
+        assert!(bond.is_broken());
+        Ok(())
+    }
+}
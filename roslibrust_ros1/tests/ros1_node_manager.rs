@@ -0,0 +1,121 @@
+#[cfg(feature = "ros1_test")]
+mod tests {
+    use roslibrust_ros1::{NodeHandle, NodeManager};
+    use roslibrust_test::ros1::*;
+    use tokio::time::{timeout, Duration};
+
+    #[test_log::test(tokio::test)]
+    async fn managed_nodes_share_uri_and_topics(
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let manager = NodeManager::new("http://localhost:11311").await?;
+        let talker = manager.add_node("managed_talker").await?;
+        let listener = manager.add_node("managed_listener").await?;
+
+        // Both nodes are served by the manager's single xmlrpc server, each at its own path
+        assert_eq!(
+            talker.get_client_uri().await?,
+            format!("{}/managed_talker", manager.client_uri())
+        );
+        assert_eq!(
+            listener.get_client_uri().await?,
+            format!("{}/managed_listener", manager.client_uri())
+        );
+
+        let publisher = talker
+            .advertise::<std_msgs::String>("/managed_chatter", 1, true)
+            .await?;
+        publisher
+            .publish(&std_msgs::String {
+                data: "latched".to_owned(),
+            })
+            .await?;
+
+        let mut subscriber = listener
+            .subscribe::<std_msgs::String>("/managed_chatter", 1)
+            .await?;
+        let msg = timeout(Duration::from_secs(2), subscriber.next())
+            .await?
+            .unwrap()?;
+        assert_eq!(msg.data, "latched");
+
+        publisher
+            .publish(&std_msgs::String {
+                data: "live".to_owned(),
+            })
+            .await?;
+        let msg = timeout(Duration::from_secs(2), subscriber.next())
+            .await?
+            .unwrap()?;
+        assert_eq!(msg.data, "live");
+        Ok(())
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn outside_subscriber_reaches_every_managed_publisher(
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let manager = NodeManager::new("http://localhost:11311").await?;
+        let first = manager.add_node("managed_first_talker").await?;
+        let second = manager.add_node("managed_second_talker").await?;
+
+        let first_publisher = first
+            .advertise::<std_msgs::String>("/managed_shared_chatter", 1, true)
+            .await?;
+        let second_publisher = second
+            .advertise::<std_msgs::String>("/managed_shared_chatter", 1, true)
+            .await?;
+        first_publisher
+            .publish(&std_msgs::String {
+                data: "first".to_owned(),
+            })
+            .await?;
+        second_publisher
+            .publish(&std_msgs::String {
+                data: "second".to_owned(),
+            })
+            .await?;
+
+        // A node outside the manager has to request the topic from each publisher separately
+        let listener =
+            NodeHandle::new("http://localhost:11311", "managed_outside_listener").await?;
+        let mut subscriber = listener
+            .subscribe::<std_msgs::String>("/managed_shared_chatter", 2)
+            .await?;
+        let mut received = vec![];
+        for _ in 0..2 {
+            let msg = timeout(Duration::from_secs(2), subscriber.next())
+                .await?
+                .unwrap()?;
+            received.push(msg.data);
+        }
+        received.sort();
+        assert_eq!(received, vec!["first", "second"]);
+        Ok(())
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn shutdown_only_reaches_the_addressed_node(
+    ) -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+        let manager = NodeManager::new("http://localhost:11311").await?;
+        let stopped = manager.add_node("managed_stopped").await?;
+        let running = manager.add_node("managed_running").await?;
+
+        // What the master does when another node registers with the same name
+        let body = serde_xmlrpc::request_to_string(
+            "shutdown",
+            vec![
+                "/master".into(),
+                "new node registered with same name".into(),
+            ],
+        )?;
+        reqwest::Client::new()
+            .post(stopped.get_client_uri().await?)
+            .body(body)
+            .send()
+            .await?;
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        assert!(stopped.get_client_uri().await.is_err());
+        assert!(running.get_client_uri().await.is_ok());
+        Ok(())
+    }
+}
@@ -144,4 +144,51 @@ mod tests {
         assert!(!host.is_empty());
         assert!(port != 0);
     }
+
+    #[test_log::test(tokio::test)]
+    async fn verify_custom_xmlrpc_handler() -> Result<(), Box<dyn std::error::Error + Send + Sync>>
+    {
+        let node =
+            NodeHandle::new("http://localhost:11311", "verify_custom_xmlrpc_handler").await?;
+        let node_uri = node.get_client_uri().await?;
+
+        node.register_xmlrpc_handler("echoClientUri", |nh, args| async move {
+            if args.len() != 1 {
+                return Err("Expected exactly one argument".to_string());
+            }
+            // Handlers have access to the node itself
+            let uri = nh.get_client_uri().await.map_err(|e| e.to_string())?;
+            Ok(Value::Array(vec![args[0].clone(), uri.into()]))
+        })
+        .await?;
+
+        // Built in methods can't be replaced, and a method can only be registered once
+        assert!(node
+            .register_xmlrpc_handler("getPid", |_, _| async { Ok(0.into()) })
+            .await
+            .is_err());
+        assert!(node
+            .register_xmlrpc_handler("echoClientUri", |_, _| async { Ok(0.into()) })
+            .await
+            .is_err());
+
+        let (arg, uri) =
+            call_node_api::<(String, String)>(&node_uri, "echoClientUri", vec!["hello".into()])
+                .await;
+        assert_eq!(arg, "hello");
+        assert_eq!(uri, node_uri);
+
+        // Errors from the handler are reported as a failure status code
+        let response = call_node_api_raw(&node_uri, "echoClientUri", vec![]).await;
+        let (code, description, _): (i8, String, i32) =
+            serde_xmlrpc::response_from_str(&response).unwrap();
+        assert_eq!(code, 0);
+        assert_eq!(description, "Expected exactly one argument");
+
+        // Once removed the method is no longer available
+        node.unregister_xmlrpc_handler("echoClientUri")?;
+        let response = call_node_api_raw(&node_uri, "echoClientUri", vec!["hello".into()]).await;
+        assert!(serde_xmlrpc::response_from_str::<(i8, String, i32)>(&response).is_err());
+        Ok(())
+    }
 }
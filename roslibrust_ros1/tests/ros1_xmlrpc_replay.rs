@@ -0,0 +1,33 @@
+//! Regression tests replaying recorded xmlrpc negotiations, these don't need a running ros master
+
+use roslibrust_ros1::xmlrpc_recorder::{XmlRpcExchange, XmlRpcReplay};
+use roslibrust_ros1::NodeHandle;
+use roslibrust_test::ros1::*;
+use std::time::Duration;
+
+fn fixture(name: &str) -> Vec<XmlRpcExchange> {
+    let path = format!(
+        "{}/tests/fixtures/xmlrpc/{name}",
+        env!("CARGO_MANIFEST_DIR")
+    );
+    XmlRpcExchange::read_all(path).expect("Failed to read xmlrpc fixture")
+}
+
+/// Subscribing to a topic published by a roscpp node, whose XmlRpc++ responses leave strings untyped and write
+/// empty strings as an empty <value></value>
+#[test_log::test(tokio::test)]
+async fn subscribe_to_roscpp_publisher() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    let replay = XmlRpcReplay::serve(fixture("roscpp_talker_subscribe.jsonl"))?;
+    let nh = NodeHandle::new(replay.uri(), "/listener").await?;
+    let _subscriber = nh.subscribe::<std_msgs::String>("/chatter", 1).await?;
+
+    // The publisher's TCPROS port doesn't exist, but the xmlrpc negotiation with it should have completed
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while replay.remaining() > 0 {
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await?;
+    assert!(replay.unanswered().is_empty(), "{:?}", replay.unanswered());
+    Ok(())
+}
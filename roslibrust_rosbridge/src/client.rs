@@ -1,30 +1,57 @@
 use crate::comm::Ops;
 use crate::comm::RosBridgeComm;
-use crate::{Publisher, ServiceHandle, Subscriber};
+use crate::{
+    Publisher, PublisherRaw, ServiceHandle, StatusEvent, StatusLevel, SubscribeOptions, Subscriber,
+    SubscriberBuilder,
+};
 use anyhow::anyhow;
 use dashmap::DashMap;
 use futures::StreamExt;
 use log::*;
 use roslibrust_common::*;
+use serde::Deserialize;
 use serde_json::Value;
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::str::FromStr;
-use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::Arc;
 use tokio::sync::RwLock;
 use tokio::time::Duration;
+use tokio_tungstenite::tungstenite::protocol::WebSocketConfig;
 use tokio_tungstenite::tungstenite::Message;
 
 use super::{
-    MessageQueue, PublisherHandle, Reader, ServiceCallback, ServiceClient, Socket, Subscription,
-    Writer, QUEUE_SIZE,
+    MapError, MessageQueue, PublisherHandle, Reader, ServiceCallback, ServiceClient, Socket,
+    Subscription, Writer,
 };
 
+/// Number of status events that can be buffered for each status event stream before old events are dropped
+const STATUS_QUEUE_SIZE: usize = 100;
+
+/// Describes an operation that the client is sending to rosbridge_server.
+///
+/// Handed to the hook configured with [ClientHandleOptions::op_hook] for every outgoing op.
+#[derive(Clone, Debug)]
+pub struct OutgoingOp {
+    /// Name of the rosbridge operation e.g. "publish", "subscribe", "call_service"
+    pub op: String,
+    /// The `id` field sent with the op.
+    /// rosbridge_server includes this id in any `status` message it produces about the op.
+    pub id: String,
+    /// Name of the topic or service the op targets
+    pub target: String,
+}
+
+/// Type erased hook that is invoked for every op sent to rosbridge_server
+pub type OpHook = Arc<dyn Fn(&OutgoingOp) + Send + Sync>;
+
 /// Builder options for creating a client
 #[derive(Clone)]
 pub struct ClientHandleOptions {
     url: String,
     timeout: Option<Duration>,
+    op_hook: Option<OpHook>,
+    max_message_size: Option<usize>,
 }
 
 impl ClientHandleOptions {
@@ -33,6 +60,8 @@ impl ClientHandleOptions {
         ClientHandleOptions {
             url: url.into(),
             timeout: None,
+            op_hook: None,
+            max_message_size: None,
         }
     }
 
@@ -43,6 +72,43 @@ impl ClientHandleOptions {
         self.timeout = Some(duration.into());
         self
     }
+
+    /// Configures a hook that is called with each op sent to rosbridge_server, including the `id` assigned to it.
+    ///
+    /// Every op roslibrust sends carries a unique id of the form `<op>:<topic>:<counter>`, and rosbridge_server echos
+    /// that id back in any `status` message it produces. Recording ops from this hook allows server side warnings
+    /// and errors to be attributed to the call that caused them.
+    ///
+    /// The hook is called inline while sending, so it should return quickly.
+    pub fn op_hook<F>(mut self, hook: F) -> ClientHandleOptions
+    where
+        F: Fn(&OutgoingOp) + Send + Sync + 'static,
+    {
+        self.op_hook = Some(Arc::new(hook));
+        self
+    }
+
+    /// Configures the largest websocket message, in bytes, accepted from rosbridge_server.
+    ///
+    /// Messages arrive as JSON so this limits the JSON text, not the serialized ROS message.
+    /// A larger message (or a frame header claiming one) is refused before it is buffered, the connection is
+    /// dropped and re-established as with any other connection error, and the error is reported as
+    /// [Error::MessageTooLarge]. Defaults to the websocket library's limit of 64MiB.
+    pub fn max_message_size(mut self, max_message_size: usize) -> ClientHandleOptions {
+        self.max_message_size = Some(max_message_size);
+        self
+    }
+
+    // Websocket configuration applying these options, None uses the library defaults
+    fn websocket_config(&self) -> Option<WebSocketConfig> {
+        let max_message_size = self.max_message_size?;
+        Some(WebSocketConfig {
+            max_message_size: Some(max_message_size),
+            // A single frame can't be larger than the message it is part of
+            max_frame_size: Some(max_message_size),
+            ..Default::default()
+        })
+    }
 }
 
 /// The ClientHandle is the fundamental object through which users of this library are expected to interact with it.
@@ -118,33 +184,34 @@ impl ClientHandle {
     }
 
     // Internal implementation of subscribe
-    async fn _subscribe<Msg>(&self, topic_name: &str) -> Result<Subscriber<Msg>>
+    async fn _subscribe<Msg>(
+        &self,
+        topic_name: &str,
+        options: SubscribeOptions,
+    ) -> Result<Subscriber<Msg>>
     where
         Msg: RosMessageType,
     {
-        // Lookup / create a subscription entry for tracking
-        let client = self.inner.read().await;
-        let mut cbs = client
-            .subscriptions
-            .entry(topic_name.to_string())
-            .or_insert(Subscription {
-                handles: HashMap::new(),
-                topic_type: Msg::ROS_TYPE_NAME.to_string(),
-            });
-
-        // TODO Possible bug here? We send a subscribe message each time even if already subscribed
-        // Send subscribe message to rosbridge to initiate it sending us messages
-        let mut stream = client.writer.write().await;
-        stream.subscribe(topic_name, Msg::ROS_TYPE_NAME).await?;
-
-        // Create a new watch channel for this topic
-        let queue = Arc::new(MessageQueue::new(QUEUE_SIZE));
+        // Create a new queue for this subscriber
+        let queue = Arc::new(MessageQueue::new(options.queue_size));
 
         // Move the tx into a callback that takes raw string data
         // This allows us to store the callbacks generic on type, Msg conversion is embedded here
         let topic_name_copy = topic_name.to_string();
         let queue_copy = queue.clone();
+        let throttle_rate = options.throttle_rate;
+        let last_queued = std::sync::Mutex::new(None::<tokio::time::Instant>);
         let send_cb = Arc::new(move |data: &str| {
+            // Throttling is per subscriber, other subscribers to the topic still get every message
+            if let Some(throttle_rate) = throttle_rate {
+                let now = tokio::time::Instant::now();
+                let mut last_queued = last_queued.lock().unwrap();
+                if last_queued.is_some_and(|last| now.duration_since(last) < throttle_rate) {
+                    return;
+                }
+                *last_queued = Some(now);
+            }
+
             let converted = match serde_json::from_str::<Msg>(data) {
                 Err(e) => {
                     // TODO makes sense for callback to return Result<>, instead of this handling
@@ -188,13 +255,53 @@ impl ClientHandle {
             }
         });
 
-        // Create subscriber
-        let sub = Subscriber::new(self.clone(), queue, topic_name.to_string());
+        // Lookup / create a subscription entry for tracking and add our callback to it
+        // Only the first subscriber to a topic subscribes with rosbridge, later ones share its subscription
+        let client = self.inner.read().await;
+        let id = uuid::Uuid::new_v4();
+        let (subscribe_id, is_new) = {
+            let mut is_new = false;
+            let mut subscription = client
+                .subscriptions
+                .entry(topic_name.to_string())
+                .or_insert_with(|| {
+                    is_new = true;
+                    Subscription {
+                        handles: HashMap::new(),
+                        topic_type: Msg::ROS_TYPE_NAME.to_string(),
+                        subscribe_id: client.next_op_id(Ops::Subscribe, topic_name),
+                    }
+                });
+            if subscription.topic_type != Msg::ROS_TYPE_NAME {
+                debug!(
+                    "Subscribing to {topic_name} as {} which is already subscribed to as {}",
+                    Msg::ROS_TYPE_NAME,
+                    subscription.topic_type
+                );
+            }
+            subscription.handles.insert(id, send_cb);
+            (subscription.subscribe_id.clone(), is_new)
+        };
+        // Created before sending so that dropping this future, e.g. when cancelled or timed out, removes the handle
+        let subscriber = Subscriber::new(
+            self.clone(),
+            queue,
+            topic_name.to_string(),
+            id,
+            subscribe_id.clone(),
+        );
 
-        // Store callback in map under the subscriber's id
-        cbs.handles.insert(*sub.get_id(), send_cb);
+        if is_new {
+            // Send subscribe message to rosbridge to initiate it sending us messages
+            // On failure dropping the subscriber removes it, if others have subscribed meanwhile the subscription
+            // is kept for them and reconnecting re-sends it
+            let mut stream = client.writer.write().await;
+            stream
+                .subscribe(topic_name, Msg::ROS_TYPE_NAME, &subscribe_id)
+                .await?;
+        }
 
-        Ok(sub)
+        Ok(subscriber)
     }
 
     /// Subscribe to a given topic expecting msgs of provided type.
@@ -213,6 +320,8 @@ impl ClientHandle {
     /// ```
     /// This function returns after a subscribe message has been sent to rosbridge, it will
     /// return immediately with an error if call while currently disconnected.
+    /// Only the first subscriber to a topic sends a subscribe message, later subscribers (of any type) share its
+    /// subscription and the unsubscribe message is sent once the last of them is dropped.
     ///
     /// It does not error if subscribed type does not match the topic type or check this in anyway.
     /// If a type different that what is expected on the topic is published the deserialization of that message will fail,
@@ -239,37 +348,85 @@ impl ClientHandle {
     /// # }
     /// ```
     pub async fn subscribe<Msg>(&self, topic_name: &str) -> Result<Subscriber<Msg>>
+    where
+        Msg: RosMessageType,
+    {
+        self.subscribe_with_options(topic_name, SubscribeOptions::default())
+            .await
+    }
+
+    /// Subscribe to a given topic expecting msgs of provided type, configured by [SubscribeOptions].
+    ///
+    /// Behaves like [ClientHandle::subscribe], the options only apply to the returned subscriber and not to other
+    /// subscribers to the same topic.
+    /// ```no_run
+    /// # use roslibrust_test::ros1::*;
+    /// # use roslibrust_rosbridge::SubscribeOptions;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust_rosbridge::ClientHandle::new("ws://localhost:9090").await?;
+    ///   // Every message for logging
+    ///   let all = handle.subscribe::<std_msgs::Header>("/topic").await?;
+    ///   // At most one message a second for display, only keeping the latest
+    ///   let display = handle
+    ///       .subscribe_with_options::<std_msgs::Header>(
+    ///           "/topic",
+    ///           SubscribeOptions::new().queue_size(1).throttle_rate(std::time::Duration::from_secs(1)),
+    ///       )
+    ///       .await?;
+    ///   # Ok(())
+    /// # }
+    /// ```
+    pub async fn subscribe_with_options<Msg>(
+        &self,
+        topic_name: &str,
+        options: SubscribeOptions,
+    ) -> Result<Subscriber<Msg>>
     where
         Msg: RosMessageType,
     {
         self.check_for_disconnect()?;
+        let topic_name = names::Name::new(topic_name)?;
         timeout(
             self.inner.read().await.opts.timeout,
-            self._subscribe(topic_name),
+            self._subscribe(topic_name.as_str(), options),
         )
         .await
     }
 
+    /// Starts building a subscriber for the given type, see [SubscriberBuilder].
+    ///
+    /// Offers the [SubscribeOptions] along with waiting for the first message before returning.
+    pub fn subscribe_builder<Msg>(&self, topic_name: &str) -> SubscriberBuilder<Msg>
+    where
+        Msg: RosMessageType,
+    {
+        SubscriberBuilder::new(self.clone(), topic_name)
+    }
+
     // Publishes a message
     // Fails immediately(ish) if disconnected
-    // Returns success when message is put on websocket (no confirmation of receipt)
-    pub(crate) async fn publish<T>(&self, topic: &str, msg: &T) -> Result<()>
+    // Returns the id of the publish op when message is put on websocket (no confirmation of receipt)
+    pub(crate) async fn publish<T>(&self, topic: &str, topic_type: &str, msg: &T) -> Result<String>
     where
-        T: RosMessageType,
+        T: serde::Serialize + Sync,
     {
         self.check_for_disconnect()?;
         let client = self.inner.read().await;
+        let id = client.next_op_id(Ops::Publish, topic);
         let mut stream = client.writer.write().await;
         debug!("Publish got write lock on comm");
-        stream.publish(topic, msg).await?;
-        Ok(())
+        stream.publish(topic, topic_type, msg, &id).await?;
+        Ok(id)
     }
 
     /// Advertises a topic to be published to and returns a type specific publisher to use.
     ///
-    /// Dropping the publisher will automatically un-advertise the topic. Publisher is not clone,
-    /// and calling advertise multiple times targeting the same topic is not currently supported and
-    /// will result in an error.
+    /// Dropping the publisher will automatically un-advertise the topic. Calling advertise multiple times
+    /// targeting the same topic with the same type is allowed, the publishers share a single advertisement
+    /// with rosbridge_server, and the topic is only un-advertised once the last of them is dropped or
+    /// explicitly [unadvertised](Publisher::unadvertise). Advertising a topic again with a different type
+    /// while it is still advertised results in an error.
     ///
     /// This function returns with a failure if currently disconnected when called.
     ///
@@ -294,28 +451,127 @@ impl ClientHandle {
     where
         T: RosMessageType,
     {
+        let (topic, id, advertise_id) = self.advertise_as(topic, T::ROS_TYPE_NAME).await?;
+        Ok(Publisher::new(topic, self.clone(), id, advertise_id))
+    }
+
+    /// Advertises a topic of the type named `topic_type`, e.g. "std_msgs/String", returning a publisher of
+    /// messages given as JSON.
+    ///
+    /// For config driven bridges and quick prototypes which don't have generated types for the topic. Advertising
+    /// behaves as with [ClientHandle::advertise], a raw publisher shares its topic's advertisement with typed
+    /// publishers of the same type. Messages are forwarded to rosbridge_server unchecked, one that doesn't match
+    /// `topic_type` is only reported in rosbridge's logs or as a [StatusEvent].
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust_rosbridge::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let publisher = handle.advertise_raw("/chatter", "std_msgs/String").await?;
+    ///   publisher.publish(&serde_json::json!({"data": "hello"})).await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn advertise_raw(&self, topic: &str, topic_type: &str) -> Result<PublisherRaw> {
+        let (topic, id, advertise_id) = self.advertise_as(topic, topic_type).await?;
+        Ok(PublisherRaw::new(
+            topic,
+            topic_type.to_string(),
+            self.clone(),
+            id,
+            advertise_id,
+        ))
+    }
+
+    // Adds a publisher of `topic_type` to the topic's advertisement, advertising it with rosbridge if it is the first.
+    // Returns the resolved topic name, the id of the new publisher and the id of the advertise op.
+    async fn advertise_as(
+        &self,
+        topic: &str,
+        topic_type: &str,
+    ) -> Result<(String, uuid::Uuid, String)> {
         self.check_for_disconnect()?;
+        let topic = names::Name::new(topic)?;
+        let topic = topic.as_str();
         let client = self.inner.read().await;
-        if client.publishers.contains_key(topic) {
-            // TODO if we ever remove this restriction we should still check types match
-            return Err(Error::Unexpected(anyhow!(
-                "Attempted to create two publisher to same topic, this is not supported"
-            )));
-        } else {
-            client.publishers.insert(
-                topic.to_string(),
-                PublisherHandle {
-                    topic_type: T::ROS_TYPE_NAME.to_string(),
-                },
-            );
-        }
+        // Only the first publisher to a topic advertises with rosbridge, later ones share its advertisement
+        let id = uuid::Uuid::new_v4();
+        let (advertise_id, is_new) = {
+            let mut is_new = false;
+            let mut publisher = client
+                .publishers
+                .entry(topic.to_string())
+                .or_insert_with(|| {
+                    is_new = true;
+                    PublisherHandle {
+                        topic_type: topic_type.to_string(),
+                        advertise_id: client.next_op_id(Ops::Advertise, topic),
+                        handles: HashSet::new(),
+                    }
+                });
+            if publisher.topic_type != topic_type {
+                return Err(Error::Unexpected(anyhow!(
+                    "Attempted to advertise {topic} as {topic_type} while it is already advertised as {}",
+                    publisher.topic_type
+                )));
+            }
+            publisher.handles.insert(id);
+            (publisher.advertise_id.clone(), is_new)
+        };
 
-        {
+        if is_new {
             let mut stream = client.writer.write().await;
             debug!("Advertise got lock on comm");
-            stream.advertise::<T>(topic).await?;
+            if let Err(e) = stream.advertise_str(topic, topic_type, &advertise_id).await {
+                // If others have advertised meanwhile the advertisement is kept for them, reconnecting re-sends it
+                let _ = client.remove_publisher(topic, &id);
+                return Err(e);
+            }
         }
-        Ok(Publisher::new(topic.to_string(), self.clone()))
+        Ok((topic.to_string(), id, advertise_id))
+    }
+
+    /// Requests that rosbridge_server send `status` messages at or above the given level.
+    ///
+    /// rosbridge_server defaults to only sending errors, use this to additionally receive warnings and info messages,
+    /// or pass [StatusLevel::None] to silence status messages entirely.
+    /// The level is remembered and re-sent if the client reconnects.
+    pub async fn set_status_level(&self, level: StatusLevel) -> Result<()> {
+        self.check_for_disconnect()?;
+        let client = self.inner.read().await;
+        *client.status_level.lock().unwrap() = Some(level);
+        let id = client.next_op_id(Ops::SetLevel, "");
+        let mut stream = client.writer.write().await;
+        stream.set_level(level, &id).await
+    }
+
+    /// Returns a stream of the `status` messages sent by rosbridge_server that are at least as severe as `level`.
+    ///
+    /// Status messages report server side problems such as failing to publish or subscribe.
+    /// Each [StatusEvent] carries the `id` of the op that caused it when the server provides one,
+    /// see [ClientHandleOptions::op_hook] for correlating these with the calls that produced them.
+    ///
+    /// Only events received after this function is called are delivered. Note that rosbridge_server only sends
+    /// errors by default, see [ClientHandle::set_status_level].
+    /// The stream ends when the client is dropped.
+    pub async fn status_events(
+        &self,
+        level: StatusLevel,
+    ) -> impl futures::Stream<Item = StatusEvent> + Send + 'static {
+        use tokio::sync::broadcast::error::RecvError;
+        let receiver = self.inner.read().await.status_sender.subscribe();
+        futures::stream::unfold(receiver, move |mut receiver| async move {
+            loop {
+                match receiver.recv().await {
+                    Ok(event) if event.level >= level => return Some((event, receiver)),
+                    Ok(_) => continue,
+                    Err(RecvError::Lagged(n)) => {
+                        warn!("Status event stream is lagging, {n} status events were skipped");
+                    }
+                    Err(RecvError::Closed) => return None,
+                }
+            }
+        })
     }
 
     /// Calls a ros service and returns the response
@@ -343,62 +599,204 @@ impl ClientHandle {
         service: &str,
         req: S::Request,
     ) -> Result<S::Response> {
+        self.call_service_with_cancel::<S>(service, req, &CancellationToken::new())
+            .await
+    }
+
+    /// Calls a ros service like [ClientHandle::call_service], returning [Error::Cancelled] if `cancel` is cancelled
+    /// before the response arrives.
+    ///
+    /// The call stops being tracked by the client when cancelled, a late response from rosbridge_server is ignored.
+    pub async fn call_service_with_cancel<S: RosServiceType>(
+        &self,
+        service: &str,
+        req: S::Request,
+        cancel: &CancellationToken,
+    ) -> Result<S::Response> {
+        let msg = self.call_service_json(service, req, cancel).await?;
+
+        // Attempt to convert data to response type
+        match serde_json::from_value(msg.clone()) {
+            Ok(val) => Ok(val),
+            Err(e) => {
+                // We failed to parse the value as an expected type, before just giving up, try to parse as string
+                // if we got a string it indicates a server side error, otherwise we got the wrong datatype back
+                match serde_json::from_value(msg) {
+                    Ok(s) => Err(Error::ServerError(s)),
+                    Err(_) => {
+                        // Return the error from the original parse
+                        Err(Error::SerializationError(e.to_string()))
+                    }
+                }
+            }
+        }
+    }
+
+    /// Calls a ros service with a request given as JSON, returning the response as JSON.
+    ///
+    /// For tooling and scripting layers which don't have generated types for the service. The request is sent as
+    /// is, rosbridge_server reports a request which doesn't match the service's type as an [Error::ServerError].
+    /// If the service's type was given to [ClientHandle::register_service_type] the response is checked against it,
+    /// otherwise it is returned unchecked.
+    ///
+    /// ```no_run
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust_rosbridge::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let response = handle
+    ///       .call_service_raw("/rosapi/get_param", serde_json::json!({"name": "/rate"}))
+    ///       .await?;
+    ///   println!("rate is {}", response["value"]);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn call_service_raw(&self, service: &str, request: Value) -> Result<Value> {
+        let response = self
+            .call_service_json(service, request, &CancellationToken::new())
+            .await?;
+        if let Value::String(error) = response {
+            return Err(Error::ServerError(error));
+        }
+        let check = self
+            .inner
+            .read()
+            .await
+            .response_checks
+            .get(names::Name::new(service)?.as_str())
+            .map(|check| *check);
+        if let Some(check) = check {
+            check(&response).map_err(|e| {
+                Error::SerializationError(format!("Invalid response from {service}: {e}"))
+            })?;
+        }
+        Ok(response)
+    }
+
+    /// Registers `S` as the type of `service`, so responses returned by [ClientHandle::call_service_raw] for it are
+    /// checked to be valid responses of `S`.
+    pub async fn register_service_type<S: RosServiceType>(&self, service: &str) -> Result<()> {
+        let service = names::Name::new(service)?;
+        self.inner
+            .read()
+            .await
+            .response_checks
+            .insert(service.as_str().to_owned(), check_response::<S>);
+        Ok(())
+    }
+
+    // Sends a call_service op and waits for the values of the response
+    async fn call_service_json<Req: serde::Serialize + Send>(
+        &self,
+        service: &str,
+        req: Req,
+        cancel: &CancellationToken,
+    ) -> Result<Value> {
         self.check_for_disconnect()?;
+        if cancel.is_cancelled() {
+            return Err(Error::Cancelled);
+        }
+        let service = names::Name::new(service)?;
+        let service = service.as_str();
         let (tx, rx) = tokio::sync::oneshot::channel();
-        let rand_string: String = uuid::Uuid::new_v4().to_string();
         let client = self.inner.read().await;
+        let id = client.next_op_id(Ops::CallService, service);
         {
-            if client
-                .service_calls
-                .insert(rand_string.clone(), tx)
-                .is_some()
-            {
+            if client.service_calls.insert(id.clone(), tx).is_some() {
                 error!("ID collision encountered in call_service");
             }
         }
         {
             let mut comm = client.writer.write().await;
-            timeout(
-                client.opts.timeout,
-                comm.call_service(service, &rand_string, req),
-            )
-            .await?;
+            timeout(client.opts.timeout, comm.call_service(service, &id, req)).await?;
         }
 
         // Having to do manual timeout logic here because of error types
-        let recv = if let Some(timeout) = client.opts.timeout {
-            tokio::time::timeout(timeout, rx)
-                .await
-                .map_err(|e| Error::Timeout(format!("Service call timed out: {e:?}")))?
-        } else {
-            rx.await
+        let recv = async {
+            if let Some(timeout) = client.opts.timeout {
+                tokio::time::timeout(timeout, rx)
+                    .await
+                    .map_err(|e| Error::Timeout(format!("Service call timed out: {e:?}")))
+            } else {
+                Ok(rx.await)
+            }
+        };
+        let recv = match cancel.run_until_cancelled(recv).await {
+            Some(Ok(recv)) => recv,
+            // Stop tracking the call so the pending entry doesn't outlive the caller
+            Some(Err(e)) => {
+                client.service_calls.remove(&id);
+                return Err(e);
+            }
+            None => {
+                debug!("Service call {id} cancelled");
+                client.service_calls.remove(&id);
+                return Err(Error::Cancelled);
+            }
         };
 
         // Attempt to actually pull data out
-        let msg = match recv {
-            Ok(msg) => msg,
+        match recv {
+            Ok(msg) => Ok(msg),
             Err(e) =>
             // TODO remove panic! here, this could result from dropping communication, need to handle disconnect better
             panic!("The sender end of a service channel was dropped while rx was being awaited, this should not be possible: {}", e),
-        };
-
-        // Attempt to convert data to response type
-        match serde_json::from_value(msg.clone()) {
-            Ok(val) => Ok(val),
-            Err(e) => {
-                // We failed to parse the value as an expected type, before just giving up, try to parse as string
-                // if we got a string it indicates a server side error, otherwise we got the wrong datatype back
-                match serde_json::from_value(msg) {
-                    Ok(s) => Err(Error::ServerError(s)),
-                    Err(_) => {
-                        // Return the error from the original parse
-                        Err(Error::SerializationError(e.to_string()))
-                    }
-                }
-            }
         }
     }
 
+    /// Calls many services of the same type without waiting for each response before sending the next request.
+    ///
+    /// Up to `max_in_flight` calls are outstanding at once, the rest are sent as earlier calls complete.
+    /// Results are returned in the same order as `calls`, a failed call doesn't stop the others.
+    /// Useful for e.g. populating dozens of values at startup where calling one at a time would pay a round
+    /// trip to rosbridge_server for every call.
+    ///
+    /// ```no_run
+    /// # use roslibrust_test::ros1::*;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust_rosbridge::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let calls = ["/gain", "/offset", "/rate"].map(|name| {
+    ///       ("/rosapi/get_param", rosapi::GetParamRequest { name: name.to_string(), default: String::new() })
+    ///   });
+    ///   let values = handle.call_services_concurrently::<rosapi::GetParam, _>(calls, 8).await;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn call_services_concurrently<S: RosServiceType, N: AsRef<str>>(
+        &self,
+        calls: impl IntoIterator<Item = (N, S::Request)>,
+        max_in_flight: usize,
+    ) -> Vec<Result<S::Response>> {
+        futures::stream::iter(calls)
+            .map(
+                |(service, req)| async move { self.call_service::<S>(service.as_ref(), req).await },
+            )
+            .buffered(max_in_flight.max(1))
+            .collect()
+            .await
+    }
+
+    /// Returns a [params::ParameterProvider] for the parameters of ROS2 nodes, when connected to a ROS2
+    /// rosbridge_server.
+    ///
+    /// Parameters are read and written by calling each node's parameter services, so they are named
+    /// `/node_name/parameter_name`.
+    ///
+    /// ```no_run
+    /// # use roslibrust_common::params::ParameterProvider;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error + Send + Sync>> {
+    ///   let handle = roslibrust_rosbridge::ClientHandle::new("ws://localhost:9090").await?;
+    ///   let params = handle.ros2_parameters();
+    ///   params.set_param("/talker/rate", &10.0).await?;
+    ///   let rate: Option<f64> = params.get_param("/talker/rate").await?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn ros2_parameters(&self) -> params::Ros2Parameters<ClientHandle> {
+        params::Ros2Parameters::new(self.clone())
+    }
+
     /// Advertises a service and returns a handle that manages the lifetime of the service.
     /// Service will be active until the handle is dropped!
     ///
@@ -409,6 +807,8 @@ impl ClientHandle {
         F: ServiceFn<T>,
     {
         self.check_for_disconnect()?;
+        let topic = names::Name::new(topic)?;
+        let topic = topic.as_str();
         {
             let client = self.inner.read().await;
             let mut writer = client.writer.write().await;
@@ -440,7 +840,10 @@ impl ClientHandle {
                 error!("This should not be possible, but somehow you managed to double advertise a service despite the guard...");
             }
             // Don't advertise the service until we've reached this point, otherwise we'll double advertise
-            writer.advertise_service(topic, T::ROS_SERVICE_NAME).await?;
+            let id = client.next_op_id(Ops::AdvertiseService, topic);
+            writer
+                .advertise_service(topic, T::ROS_SERVICE_NAME, &id)
+                .await?;
         } // Drop client lock here so we can clone without creating an issue
 
         Ok(ServiceHandle {
@@ -457,10 +860,11 @@ impl ClientHandle {
     where
         T: RosServiceType,
     {
+        let topic = names::Name::new(topic)?;
         Ok(ServiceClient {
             _marker: Default::default(),
             client: self.clone(),
-            topic: topic.to_string(),
+            topic: topic.into(),
         })
     }
 
@@ -481,8 +885,9 @@ impl ClientHandle {
             }
 
             // Regardless of whether we found an entry we should still send he unadvertise_service message to rosbridge
+            let id = client.next_op_id(Ops::UnadvertiseService, &topic);
             let mut writer = client.writer.write().await;
-            let res = writer.unadvertise_service(&topic).await;
+            let res = writer.unadvertise_service(&topic, &id).await;
             if let Err(e) = res {
                 error!("Failed to send unadvertise_service message when service handle was dropped for `{topic}`: {e}");
             }
@@ -492,26 +897,36 @@ impl ClientHandle {
     // This function is not async specifically so it can be called from drop
     // same reason why it doesn't return anything
     // Called automatically when Publisher is dropped
-    pub(crate) fn unadvertise(&self, topic_name: &str) {
+    pub(crate) fn unadvertise(&self, topic_name: &str, id: &uuid::Uuid) {
         let copy = self.clone();
         let topic_name_copy = topic_name.to_string();
+        let id_copy = *id;
         tokio::spawn(async move {
-            // Remove publisher from our records
-            let client = copy.inner.read().await;
-            client.publishers.remove(&topic_name_copy);
-
-            // Send unadvertise message
-            {
-                debug!("Unadvertise waiting for comm lock");
-                let mut comm = client.writer.write().await;
-                debug!("Unadvertise got comm lock");
-                if let Err(e) = comm.unadvertise(&topic_name_copy).await {
-                    error!("Failed to send unadvertise in comm layer: {:?}", e);
-                }
+            if let Err(e) = copy.unadvertise_now(&topic_name_copy, &id_copy).await {
+                error!("Failed to send unadvertise in comm layer: {:?}", e);
             }
         });
     }
 
+    // Removes the publisher from our records and, if it was the last publisher for the topic,
+    // sends the unadvertise message. Completes once the message has been written to rosbridge
+    pub(crate) async fn unadvertise_now(&self, topic_name: &str, id: &uuid::Uuid) -> Result<()> {
+        // Remove publisher from our records
+        let client = self.inner.read().await;
+        let Some(publisher) = client.remove_publisher(topic_name, id)? else {
+            // Other publishers are still using the advertisement
+            return Ok(());
+        };
+
+        // Send unadvertise message with the advertisement's id so rosbridge removes it
+        let id = publisher.advertise_id;
+        client.report_op(Ops::Unadvertise, &id, topic_name);
+        debug!("Unadvertise waiting for comm lock");
+        let mut comm = client.writer.write().await;
+        debug!("Unadvertise got comm lock");
+        comm.unadvertise(topic_name, &id).await
+    }
+
     // This function removes the entry for a subscriber in from the client, and if it is the last
     // subscriber for a given topic then dispatches an unsubscribe message to the master/bridge
     pub(crate) fn unsubscribe(&self, topic_name: &str, id: &uuid::Uuid) -> Result<()> {
@@ -521,34 +936,36 @@ impl ClientHandle {
         let id = *id;
         // Actually send the unsubscribe message in a task so subscriber::Drop can call this function
         tokio::spawn(async move {
-            // Identify the subscription entry for the subscriber
-            let client = client.inner.read().await;
-            let mut subscription = match client.subscriptions.get_mut(&topic_name) {
-                Some(subscription) => subscription,
-                None => {
-                    error!("Topic not found in subscriptions upon dropping. This should be impossible and indicates a bug in the roslibrust crate. Topic: {topic_name} UUID: {id:?}");
-                    return;
-                }
-            };
-            if subscription.value_mut().handles.remove(&id).is_none() {
-                error!("Subscriber id {id:?} was not found in handles list for topic {topic_name:?} while unsubscribing");
-                return;
-            }
-
-            if subscription.handles.is_empty() {
-                // This is the last subscriber for that topic and we need to unsubscribe now
-                let mut stream = client.writer.write().await;
-                match stream.unsubscribe(&topic_name).await {
-                    Ok(_) => {}
-                    Err(e) => error!(
-                        "Failed to send unsubscribe while dropping subscriber: {:?}",
-                        e
-                    ),
-                }
+            if let Err(e) = client.unsubscribe_now(&topic_name, &id).await {
+                error!("Failed to unsubscribe while dropping subscriber: {e:?}");
             }
         });
         Ok(())
     }
+
+    // Async version of unsubscribe, completes once the unsubscribe message (if needed) has been written to rosbridge
+    pub(crate) async fn unsubscribe_now(&self, topic_name: &str, id: &uuid::Uuid) -> Result<()> {
+        let client = self.inner.read().await;
+        let Some(subscription) = client.remove_subscriber(topic_name, id)? else {
+            return Ok(());
+        };
+        // This is the last subscriber for that topic and we need to unsubscribe now
+        // rosbridge_server tracks subscriptions by the id they were made with, so we have to send the same one
+        client.report_op(Ops::Unsubscribe, &subscription.subscribe_id, topic_name);
+        let mut stream = client.writer.write().await;
+        stream
+            .unsubscribe(topic_name, &subscription.subscribe_id)
+            .await
+    }
+}
+
+// Checks a JSON service response is a valid response of a known service type
+type ResponseCheck = fn(&Value) -> std::result::Result<(), String>;
+
+fn check_response<S: RosServiceType>(response: &Value) -> std::result::Result<(), String> {
+    <S::Response as Deserialize>::deserialize(response)
+        .map(|_| ())
+        .map_err(|e| e.to_string())
 }
 
 /// A client connection to the rosbridge_server that allows for publishing and subscribing to topics
@@ -562,13 +979,21 @@ pub(crate) struct Client {
     // Contains any outstanding service calls we're waiting for a response on
     // Map key will be a uniquely generated id for each call
     service_calls: DashMap<String, tokio::sync::oneshot::Sender<Value>>,
+    // Checks for the responses of services whose type was registered, see ClientHandle::register_service_type
+    response_checks: DashMap<String, ResponseCheck>,
+    // Incremented for every op we send so each op is given a unique id
+    op_counter: AtomicU64,
+    // Status messages received from the server are broadcast to all status event streams
+    status_sender: tokio::sync::broadcast::Sender<StatusEvent>,
+    // Last status level requested by the user, re-sent on reconnect
+    status_level: std::sync::Mutex<Option<StatusLevel>>,
     opts: ClientHandleOptions,
 }
 
 impl Client {
     // internal implementation of new
     async fn new(opts: ClientHandleOptions) -> Result<Self> {
-        let (writer, reader) = stubborn_connect(&opts.url).await;
+        let (writer, reader) = stubborn_connect(&opts.url, opts.websocket_config()).await;
         let client = Self {
             reader: RwLock::new(reader),
             writer: RwLock::new(writer),
@@ -576,12 +1001,97 @@ impl Client {
             services: DashMap::new(),
             subscriptions: DashMap::new(),
             service_calls: DashMap::new(),
+            response_checks: DashMap::new(),
+            op_counter: AtomicU64::new(0),
+            status_sender: tokio::sync::broadcast::channel(STATUS_QUEUE_SIZE).0,
+            status_level: std::sync::Mutex::new(None),
             opts,
         };
 
         Ok(client)
     }
 
+    /// Generates the `id` for an outgoing op and reports the op to the configured op hook (if any).
+    ///
+    /// Ids follow the `<op>:<target>:<counter>` scheme used by roslibjs so they are easy to spot in rosbridge_server's logs.
+    fn next_op_id(&self, op: Ops, target: &str) -> String {
+        let count = self.op_counter.fetch_add(1, Ordering::Relaxed);
+        let id = format!("{op}:{target}:{count}");
+        trace!("Assigned id {id} to outgoing op");
+        self.report_op(op, &id, target);
+        id
+    }
+
+    /// Reports an outgoing op to the configured op hook (if any)
+    fn report_op(&self, op: Ops, id: &str, target: &str) {
+        if let Some(hook) = &self.opts.op_hook {
+            hook(&OutgoingOp {
+                op: op.to_string(),
+                id: id.to_string(),
+                target: target.to_string(),
+            });
+        }
+    }
+
+    /// Removes a publisher from its topic's advertisement.
+    ///
+    /// Returns the advertisement if that was its last publisher, in which case it has been removed and the caller
+    /// should unadvertise from rosbridge_server.
+    fn remove_publisher(
+        &self,
+        topic_name: &str,
+        id: &uuid::Uuid,
+    ) -> Result<Option<PublisherHandle>> {
+        {
+            let Some(mut publisher) = self.publishers.get_mut(topic_name) else {
+                return Err(Error::Unexpected(anyhow!(
+                    "No publisher found for topic {topic_name}"
+                )));
+            };
+            if !publisher.value_mut().handles.remove(id) {
+                return Err(Error::Unexpected(anyhow!(
+                    "Publisher {id:?} not found for topic {topic_name}"
+                )));
+            }
+        }
+        // Checked again under the entry lock in case another publisher was added in the meantime
+        Ok(self
+            .publishers
+            .remove_if(topic_name, |_, publisher| publisher.handles.is_empty())
+            .map(|(_, publisher)| publisher))
+    }
+
+    /// Removes a subscriber's callback from its topic's subscription.
+    ///
+    /// Returns the subscription if that was its last subscriber, in which case it has been removed and the caller
+    /// should unsubscribe from rosbridge_server.
+    fn remove_subscriber(&self, topic_name: &str, id: &uuid::Uuid) -> Result<Option<Subscription>> {
+        {
+            let mut subscription = match self.subscriptions.get_mut(topic_name) {
+                Some(subscription) => subscription,
+                None => {
+                    error!("Topic not found in subscriptions upon dropping. This should be impossible and indicates a bug in the roslibrust crate. Topic: {topic_name} UUID: {id:?}");
+                    return Err(Error::Unexpected(anyhow!(
+                        "No subscription found for topic {topic_name}"
+                    )));
+                }
+            };
+            if subscription.value_mut().handles.remove(id).is_none() {
+                error!("Subscriber id {id:?} was not found in handles list for topic {topic_name:?} while unsubscribing");
+                return Err(Error::Unexpected(anyhow!(
+                    "Subscriber {id:?} not found for topic {topic_name}"
+                )));
+            }
+        }
+        // Checked again under the entry lock in case another subscriber was added in the meantime
+        Ok(self
+            .subscriptions
+            .remove_if(topic_name, |_, subscription| {
+                subscription.handles.is_empty()
+            })
+            .map(|(_, subscription)| subscription))
+    }
+
     async fn handle_message(&self, msg: Message) -> Result<()> {
         match msg {
             Message::Text(text) => {
@@ -610,6 +1120,10 @@ impl Client {
                         trace!("handling call_service for {:?}", &parsed);
                         self.handle_service(parsed).await;
                     }
+                    Ops::Status => {
+                        trace!("handling status for {:?}", &parsed);
+                        self.handle_status(parsed);
+                    }
                     _ => {
                         warn!("Unhandled op type {}", op)
                     }
@@ -637,9 +1151,13 @@ impl Client {
     async fn handle_response(&self, data: Value) {
         // TODO lots of error handling!
         let id = data.get("id").unwrap().as_str().unwrap();
-        let (_id, call) = self.service_calls.remove(id).unwrap();
+        let Some((_id, call)) = self.service_calls.remove(id) else {
+            debug!("Ignoring response to service call {id} which is no longer waiting for it");
+            return;
+        };
         let res = data.get("values").unwrap();
-        call.send(res.clone()).unwrap();
+        // Fails if the caller stopped waiting, there is nobody to give the response to
+        let _ = call.send(res.clone());
     }
 
     /// Response handler for receiving a service call looks up if we have a service
@@ -682,13 +1200,32 @@ impl Client {
         // Now we need to send the service_response back
     }
 
+    /// Handler for received status messages
+    /// Logs the status and forwards it to any status event streams
+    fn handle_status(&self, data: Value) {
+        let event = match StatusEvent::from_json(&data) {
+            Ok(event) => event,
+            Err(e) => {
+                warn!("Failed to parse status message from rosbridge_server: {e:?}, message: {data:?}");
+                return;
+            }
+        };
+        match event.level {
+            StatusLevel::Error => error!("rosbridge_server reported error: {event:?}"),
+            StatusLevel::Warning => warn!("rosbridge_server reported warning: {event:?}"),
+            _ => info!("rosbridge_server reported status: {event:?}"),
+        }
+        // Sending only fails if there are no streams currently listening, which is fine
+        let _ = self.status_sender.send(event);
+    }
+
     async fn spin_once(&self) -> Result<()> {
         let read = {
             let mut stream = self.reader.write().await;
             match stream.next().await {
                 Some(Ok(msg)) => msg,
                 Some(Err(e)) => {
-                    return Err(Error::IoError(std::io::Error::other(e)));
+                    return Err::<(), _>(e).map_to_roslibrust();
                 }
                 None => {
                     return Err(Error::Unexpected(anyhow!("Wtf does none mean here?")));
@@ -701,15 +1238,16 @@ impl Client {
 
     /// Response handler for received publish messages
     /// Converts the return message to the subscribed type and calls any callbacks
-    /// Panics if publish is received for unexpected topic
     async fn handle_publish(&self, data: Value) {
         // TODO lots of error handling!
-        let callbacks = self
-            .subscriptions
-            .get(data.get("topic").unwrap().as_str().unwrap());
-        let callbacks = match callbacks {
+        let topic = data.get("topic").unwrap().as_str().unwrap();
+        let callbacks = match self.subscriptions.get(topic) {
             Some(callbacks) => callbacks,
-            _ => panic!("Received publish message for unsubscribed topic!"), // TODO probably shouldn't be a panic?
+            None => {
+                // Messages already in flight when the last subscriber unsubscribed
+                debug!("Dropping message received for unsubscribed topic {topic}");
+                return;
+            }
         };
         for callback in callbacks.handles.values() {
             callback(
@@ -723,31 +1261,49 @@ impl Client {
 
     async fn reconnect(&mut self) -> Result<()> {
         // Reconnect stream
-        let (writer, reader) = stubborn_connect(&self.opts.url).await;
+        let (writer, reader) = stubborn_connect(&self.opts.url, self.opts.websocket_config()).await;
         self.reader = RwLock::new(reader);
         self.writer = RwLock::new(writer);
 
         // TODO re-establish service servers?
 
-        // Re-advertise all publishers
-        for publisher in self.publishers.iter() {
-            let topic = publisher.key();
-            let topic_type = &publisher.value().topic_type;
+        // Re-advertise all publishers, each advertisement gets a new id
+        let mut pubs: Vec<(String, String, String)> = vec![];
+        for mut publisher in self.publishers.iter_mut() {
+            let topic = publisher.key().clone();
+            let id = self.next_op_id(Ops::Advertise, &topic);
+            publisher.value_mut().advertise_id = id.clone();
+            pubs.push((topic, publisher.value().topic_type.clone(), id));
+        }
+        {
             let mut lock = self.writer.write().await;
-            lock.advertise_str(topic, topic_type).await?;
+            for (topic, topic_type, id) in &pubs {
+                lock.advertise_str(topic, topic_type, id).await?;
+            }
         }
 
         // Resend rosbridge our subscription requests to re-establish inflight subscriptions
+        // Each gets a new id which later unsubscribes must use
         // Clone here is dumb, but required due to async
-        let mut subs: Vec<(String, String)> = vec![];
+        let mut subs: Vec<(String, String, String)> = vec![];
         {
-            for sub in self.subscriptions.iter() {
-                subs.push((sub.key().clone(), sub.value().topic_type.clone()))
+            for mut sub in self.subscriptions.iter_mut() {
+                let topic = sub.key().clone();
+                let id = self.next_op_id(Ops::Subscribe, &topic);
+                sub.value_mut().subscribe_id = id.clone();
+                subs.push((topic, sub.value().topic_type.clone(), id))
             }
         }
         let mut stream = self.writer.write().await;
-        for (topic, topic_type) in &subs {
-            stream.subscribe(topic, topic_type).await?;
+        for (topic, topic_type, id) in &subs {
+            stream.subscribe(topic, topic_type, id).await?;
+        }
+
+        // Restore the status level the user requested
+        let status_level = *self.status_level.lock().unwrap();
+        if let Some(level) = status_level {
+            let id = self.next_op_id(Ops::SetLevel, "");
+            stream.set_level(level, &id).await?;
         }
 
         Ok(())
@@ -801,10 +1357,10 @@ where
 }
 
 // Connects to websocket at specified URL, retries indefinitely
-async fn stubborn_connect(url: &str) -> (Writer, Reader) {
+async fn stubborn_connect(url: &str, config: Option<WebSocketConfig>) -> (Writer, Reader) {
     loop {
         debug!("Starting a stubborn_connect attempt to {url}");
-        match connect(url).await {
+        match connect(url, config).await {
             Err(e) => {
                 warn!("Failed to reconnect: {:?}", e);
                 // TODO configurable rate?
@@ -820,8 +1376,8 @@ async fn stubborn_connect(url: &str) -> (Writer, Reader) {
 }
 
 // Basic connection attempt and error wrapping
-async fn connect(url: &str) -> Result<Socket> {
-    let attempt = tokio_tungstenite::connect_async(url).await;
+async fn connect(url: &str, config: Option<WebSocketConfig>) -> Result<Socket> {
+    let attempt = tokio_tungstenite::connect_async_with_config(url, config).await;
     match attempt {
         Ok((stream, _response)) => Ok(stream),
         Err(e) => Err(Error::IoError(std::io::Error::other(e))),
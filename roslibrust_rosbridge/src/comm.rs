@@ -1,9 +1,10 @@
 use crate::MapError;
+use crate::StatusLevel;
 use crate::Writer;
 use anyhow::bail;
 use futures_util::SinkExt;
 use log::debug;
-use roslibrust_common::{Error, Result, RosMessageType};
+use roslibrust_common::{Error, Result};
 use serde_json::json;
 use std::{fmt::Display, str::FromStr, string::ToString};
 use tokio_tungstenite::tungstenite::Message;
@@ -12,14 +13,12 @@ use tokio_tungstenite::tungstenite::Message;
 pub(crate) enum Ops {
     // These are in the definition, but not used right now
     #[allow(dead_code)]
-    Status,
-    #[allow(dead_code)]
-    SetLevel,
-    #[allow(dead_code)]
     Fragment,
     #[allow(dead_code)]
     Auth,
     // Below here are in use
+    Status,
+    SetLevel,
     Advertise,
     Unadvertise,
     Publish,
@@ -45,10 +44,10 @@ impl From<&Ops> for &str {
             // These are unimplemented features of the library right now
             // Leaving them unimplemented here to try to catch bugs
             // TODO implement these
-            Ops::Status => unimplemented!(),
-            Ops::SetLevel => unimplemented!(),
             Ops::Fragment => unimplemented!(),
             Ops::Auth => unimplemented!(),
+            Ops::Status => "status",
+            Ops::SetLevel => "set_level",
             Ops::Advertise => "advertise",
             Ops::Unadvertise => "unadvertise",
             Ops::Publish => "publish",
@@ -67,6 +66,8 @@ impl FromStr for Ops {
     type Err = anyhow::Error;
     fn from_str(s: &str) -> std::result::Result<Self, anyhow::Error> {
         Ok(match s {
+            "status" => Ops::Status,
+            "set_level" => Ops::SetLevel,
             "advertise" => Ops::Advertise,
             "unadvertise" => Ops::Unadvertise,
             "publish" => Ops::Publish,
@@ -89,20 +90,26 @@ impl FromStr for Ops {
 /// using this trait for mocking. I'm inclined to replace it, and move the
 /// impls directly into some wrapper around [Writer]
 pub(crate) trait RosBridgeComm {
-    async fn subscribe(&mut self, topic: &str, msg_type: &str) -> Result<()>;
-    async fn unsubscribe(&mut self, topic: &str) -> Result<()>;
-    async fn publish<T: RosMessageType>(&mut self, topic: &str, msg: &T) -> Result<()>;
-    async fn advertise<T: RosMessageType>(&mut self, topic: &str) -> Result<()>;
-    async fn advertise_str(&mut self, topic: &str, msg_type: &str) -> Result<()>;
-    async fn call_service<Req: RosMessageType>(
+    async fn subscribe(&mut self, topic: &str, msg_type: &str, id: &str) -> Result<()>;
+    async fn unsubscribe(&mut self, topic: &str, id: &str) -> Result<()>;
+    async fn set_level(&mut self, level: StatusLevel, id: &str) -> Result<()>;
+    async fn publish<T: serde::Serialize + Sync>(
+        &mut self,
+        topic: &str,
+        msg_type: &str,
+        msg: &T,
+        id: &str,
+    ) -> Result<()>;
+    async fn advertise_str(&mut self, topic: &str, msg_type: &str, id: &str) -> Result<()>;
+    async fn call_service<Req: serde::Serialize + Send>(
         &mut self,
         service: &str,
         id: &str,
         req: Req,
     ) -> Result<()>;
-    async fn unadvertise(&mut self, topic: &str) -> Result<()>;
-    async fn advertise_service(&mut self, topic: &str, srv_type: &str) -> Result<()>;
-    async fn unadvertise_service(&mut self, topic: &str) -> Result<()>;
+    async fn unadvertise(&mut self, topic: &str, id: &str) -> Result<()>;
+    async fn advertise_service(&mut self, topic: &str, srv_type: &str, id: &str) -> Result<()>;
+    async fn unadvertise_service(&mut self, topic: &str, id: &str) -> Result<()>;
     async fn service_response(
         &mut self,
         topic: &str,
@@ -113,10 +120,11 @@ pub(crate) trait RosBridgeComm {
 }
 
 impl RosBridgeComm for Writer {
-    async fn subscribe(&mut self, topic: &str, msg_type: &str) -> Result<()> {
+    async fn subscribe(&mut self, topic: &str, msg_type: &str, id: &str) -> Result<()> {
         let msg = json!(
         {
         "op": Ops::Subscribe.to_string(),
+        "id": id,
         "topic": topic,
         "type": msg_type,
         }
@@ -127,10 +135,11 @@ impl RosBridgeComm for Writer {
         Ok(())
     }
 
-    async fn unsubscribe(&mut self, topic: &str) -> Result<()> {
+    async fn unsubscribe(&mut self, topic: &str, id: &str) -> Result<()> {
         let msg = json!(
         {
         "op": Ops::Unsubscribe.to_string(),
+        "id": id,
         "topic": topic,
         }
         );
@@ -140,12 +149,33 @@ impl RosBridgeComm for Writer {
         Ok(())
     }
 
-    async fn publish<T: RosMessageType>(&mut self, topic: &str, msg: &T) -> Result<()> {
+    async fn set_level(&mut self, level: StatusLevel, id: &str) -> Result<()> {
+        let msg = json!(
+        {
+        "op": Ops::SetLevel.to_string(),
+        "id": id,
+        "level": level.to_string(),
+        }
+        );
+        let msg = Message::Text(msg.to_string());
+        debug!("Sending set_level: {:?}", &msg);
+        self.send(msg).await.map_to_roslibrust()?;
+        Ok(())
+    }
+
+    async fn publish<T: serde::Serialize + Sync>(
+        &mut self,
+        topic: &str,
+        msg_type: &str,
+        msg: &T,
+        id: &str,
+    ) -> Result<()> {
         let msg = json!(
             {
                 "op": Ops::Publish.to_string(),
+                "id": id,
                 "topic": topic,
-                "type": T::ROS_TYPE_NAME,
+                "type": msg_type,
                 "msg": &msg,
             }
         );
@@ -155,17 +185,13 @@ impl RosBridgeComm for Writer {
         Ok(())
     }
 
-    async fn advertise<T: RosMessageType>(&mut self, topic: &str) -> Result<()> {
-        self.advertise_str(topic, T::ROS_TYPE_NAME).await
-    }
-
-    // Identical to advertise, but allows providing a string argument for the topic type
-    // This is important as the type is erased in our list of publishers, and not available
-    // when we try to reconnect
-    async fn advertise_str(&mut self, topic: &str, topic_type: &str) -> Result<()> {
+    // Takes the topic type as a string, as the type is erased in our list of publishers and not available
+    // when we try to reconnect, and isn't known at compile time for raw publishers
+    async fn advertise_str(&mut self, topic: &str, topic_type: &str, id: &str) -> Result<()> {
         let msg = json!(
             {
                 "op": Ops::Advertise.to_string(),
+                "id": id,
                 "topic": topic.to_string(),
                 "type": topic_type,
             }
@@ -176,7 +202,7 @@ impl RosBridgeComm for Writer {
         Ok(())
     }
 
-    async fn call_service<Req: RosMessageType>(
+    async fn call_service<Req: serde::Serialize + Send>(
         &mut self,
         service: &str,
         id: &str,
@@ -196,11 +222,12 @@ impl RosBridgeComm for Writer {
         Ok(())
     }
 
-    async fn unadvertise(&mut self, topic: &str) -> Result<()> {
+    async fn unadvertise(&mut self, topic: &str, id: &str) -> Result<()> {
         debug!("Sending unadvertise on {}", topic);
         let msg = json! {
             {
                 "op": Ops::Unadvertise.to_string(),
+                "id": id,
                 "topic": topic
             }
         };
@@ -210,11 +237,12 @@ impl RosBridgeComm for Writer {
         Ok(())
     }
 
-    async fn advertise_service(&mut self, srv_name: &str, srv_type: &str) -> Result<()> {
+    async fn advertise_service(&mut self, srv_name: &str, srv_type: &str, id: &str) -> Result<()> {
         debug!("Sending advertise service on {} w/ {}", srv_name, srv_type);
         let msg = json! {
             {
                 "op": Ops::AdvertiseService.to_string(),
+                "id": id,
                 "type": srv_type,
                 "service": srv_name
             }
@@ -224,11 +252,12 @@ impl RosBridgeComm for Writer {
         Ok(())
     }
 
-    async fn unadvertise_service(&mut self, topic: &str) -> Result<()> {
+    async fn unadvertise_service(&mut self, topic: &str, id: &str) -> Result<()> {
         debug!("Sending unadvertise service on {topic}");
         let msg = json! {
             {
                 "op": Ops::UnadvertiseService.to_string(),
+                "id": id,
                 "service": &topic
             }
         };
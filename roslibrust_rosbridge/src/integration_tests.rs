@@ -9,8 +9,11 @@ mod integration_tests {
 
     use std::sync::Arc;
 
-    use crate::{ClientHandle, ClientHandleOptions, Error, Subscriber, TestResult};
+    use crate::{
+        ClientHandle, ClientHandleOptions, Error, SubscribeOptions, Subscriber, TestResult,
+    };
     use log::debug;
+    use roslibrust_common::RosMessageType;
     use tokio::time::{timeout, Duration};
     // On my laptop test was ~90% reliable at 10ms
     // Had 1 spurious github failure at 100
@@ -72,10 +75,13 @@ mod integration_tests {
             frame_id: "self_publish".to_string(),
         };
 
-        timeout(TIMEOUT, client.publish(TOPIC, &msg_out))
-            .await
-            .expect("Failed to publish in time")
-            .unwrap();
+        timeout(
+            TIMEOUT,
+            client.publish(TOPIC, Header::ROS_TYPE_NAME, &msg_out),
+        )
+        .await
+        .expect("Failed to publish in time")
+        .unwrap();
 
         let msg_in = timeout(TIMEOUT, rx.next())
             .await
@@ -84,6 +90,29 @@ mod integration_tests {
         assert_eq!(msg_in, msg_out);
     }
 
+    #[test_log::test(tokio::test)]
+    async fn self_publish_raw() -> TestResult {
+        const TOPIC: &str = "/self_publish_raw";
+        let client =
+            ClientHandle::new_with_options(ClientHandleOptions::new(LOCAL_WS).timeout(TIMEOUT))
+                .await?;
+
+        let publisher = client.advertise_raw(TOPIC, "std_msgs/String").await?;
+        assert_eq!(publisher.topic_type(), "std_msgs/String");
+        // Typed publishers share the advertisement, as long as the type matches
+        let _typed = client.advertise::<std_msgs::String>(TOPIC).await?;
+        assert!(client.advertise::<Header>(TOPIC).await.is_err());
+        let rx = client.subscribe::<std_msgs::String>(TOPIC).await?;
+        tokio::time::sleep(TIMEOUT).await;
+
+        publisher
+            .publish(&serde_json::json!({ "data": "raw" }))
+            .await?;
+        let msg = timeout(TIMEOUT, rx.next()).await?;
+        assert_eq!(msg.data, "raw");
+        Ok(())
+    }
+
     #[test_log::test(tokio::test)]
     /// Designed to test behavior when receiving a message of unexpected type on a topic
     // TODO this test is good, but actually shows how bad the ergonomics are and how we want to improve them!
@@ -200,7 +229,7 @@ mod integration_tests {
         let sub = client.subscribe::<Header>(TOPIC).await?;
         // manually publishing using private api
         let msg = Header::default();
-        client.publish(TOPIC, &msg).await?;
+        client.publish(TOPIC, Header::ROS_TYPE_NAME, &msg).await?;
 
         match timeout(TIMEOUT, sub.next()).await {
             Ok(_msg) => {
@@ -260,6 +289,75 @@ mod integration_tests {
         Ok(())
     }
 
+    #[cfg(feature = "ros1_test")]
+    #[test_log::test(tokio::test)]
+    async fn service_call_raw() -> TestResult {
+        let opt = ClientHandleOptions::new(LOCAL_WS).timeout(TIMEOUT);
+        let client = ClientHandle::new_with_options(opt).await?;
+
+        let cb = |req: SetBoolRequest| {
+            Ok(SetBoolResponse {
+                success: req.data,
+                message: "raw".to_string(),
+            })
+        };
+        let topic = "/service_call_raw";
+        let _handle = client.advertise_service::<SetBool, _>(topic, cb).await?;
+        tokio::time::sleep(TIMEOUT).await;
+
+        let response = client
+            .call_service_raw(topic, serde_json::json!({ "data": true }))
+            .await?;
+        assert_eq!(response["success"], true);
+        assert_eq!(response["message"], "raw");
+
+        // Once a type is registered for the service responses not matching it are rejected
+        client
+            .register_service_type::<rosapi::GetTime>(topic)
+            .await?;
+        let response = client
+            .call_service_raw(topic, serde_json::json!({ "data": true }))
+            .await;
+        assert!(matches!(response, Err(Error::SerializationError(_))));
+
+        Ok(())
+    }
+
+    #[cfg(feature = "ros1_test")]
+    #[test_log::test(tokio::test)]
+    async fn service_calls_concurrently() -> TestResult {
+        let opt = ClientHandleOptions::new(LOCAL_WS).timeout(TIMEOUT);
+        let client = ClientHandle::new_with_options(opt).await?;
+
+        let cb = |req: SetBoolRequest| {
+            Ok(SetBoolResponse {
+                success: req.data,
+                message: "".to_string(),
+            })
+        };
+        let topic = "/service_calls_concurrently";
+        let _handle = client.advertise_service::<SetBool, _>(topic, cb).await?;
+        tokio::time::sleep(TIMEOUT).await;
+
+        let calls = (0..20).map(|i| {
+            let service = if i == 5 { "/not_real" } else { topic };
+            (service, SetBoolRequest { data: i % 2 == 0 })
+        });
+        let responses = client
+            .call_services_concurrently::<SetBool, _>(calls, 4)
+            .await;
+        assert_eq!(responses.len(), 20);
+        for (i, response) in responses.into_iter().enumerate() {
+            match response {
+                // A failed call doesn't affect the others
+                Err(_) => assert_eq!(i, 5),
+                Ok(response) => assert_eq!(response.success, i % 2 == 0),
+            }
+        }
+
+        Ok(())
+    }
+
     #[test_log::test(tokio::test)]
     async fn test_strong_and_weak_client_counts() -> TestResult {
         let opt = ClientHandleOptions::new(LOCAL_WS).timeout(TIMEOUT);
@@ -528,4 +626,172 @@ mod integration_tests {
 
         assert_eq!(received, msg, "Messages do not match");
     }
+
+    #[test_log::test(tokio::test)]
+    async fn subscribers_share_one_subscription() -> TestResult {
+        const TOPIC: &str = "/subscribers_share_one_subscription";
+        let ops = Arc::new(std::sync::Mutex::new(vec![]));
+        let ops_copy = ops.clone();
+        let opt = ClientHandleOptions::new(LOCAL_WS)
+            .timeout(TIMEOUT)
+            .op_hook(move |op| ops_copy.lock().unwrap().push(op.clone()));
+        let client = ClientHandle::new_with_options(opt).await?;
+
+        let publisher = client.advertise::<std_msgs::Int32>(TOPIC).await?;
+        let all = client.subscribe::<std_msgs::Int32>(TOPIC).await?;
+        let latest = client
+            .subscribe_with_options::<std_msgs::Int32>(TOPIC, SubscribeOptions::new().queue_size(1))
+            .await?;
+        let throttled = client
+            .subscribe_with_options::<std_msgs::Int32>(
+                TOPIC,
+                SubscribeOptions::new().throttle_rate(Duration::from_secs(60)),
+            )
+            .await?;
+        assert_eq!(all.subscribe_id(), latest.subscribe_id());
+        assert_eq!(all.subscribe_id(), throttled.subscribe_id());
+        tokio::time::sleep(TIMEOUT).await;
+
+        for data in 0..3 {
+            publisher.publish(&std_msgs::Int32 { data }).await?;
+        }
+        tokio::time::sleep(TIMEOUT).await;
+        assert_eq!(all.len(), 3);
+        assert_eq!(latest.len(), 1);
+        assert_eq!(latest.next().await.data, 2);
+        assert_eq!(throttled.len(), 1);
+        assert_eq!(throttled.next().await.data, 0);
+
+        // Dropping some subscribers leaves the others subscribed
+        latest.unsubscribe().await?;
+        throttled.unsubscribe().await?;
+        publisher.publish(&std_msgs::Int32::default()).await?;
+        timeout(TIMEOUT, all.most_recent()).await?;
+
+        // The last subscriber unsubscribes with the id the subscription was made with
+        let subscribe_id = all.subscribe_id().to_owned();
+        all.unsubscribe().await?;
+        {
+            let ops = ops.lock().unwrap();
+            assert_eq!(ops.iter().filter(|op| op.op == "subscribe").count(), 1);
+            assert!(ops
+                .iter()
+                .any(|op| op.op == "unsubscribe" && op.id == subscribe_id));
+        }
+
+        // Subscribing again after the last subscriber is gone subscribes anew
+        let again = client.subscribe::<std_msgs::Int32>(TOPIC).await?;
+        assert_ne!(again.subscribe_id(), subscribe_id);
+        Ok(())
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn subscriber_builder_first_message_timeout() -> TestResult {
+        const TOPIC: &str = "/subscriber_builder_first_message_timeout";
+        let opt = ClientHandleOptions::new(LOCAL_WS).timeout(TIMEOUT);
+        let client = ClientHandle::new_with_options(opt).await?;
+
+        // Nothing is published yet
+        let res = client
+            .subscribe_builder::<std_msgs::Int32>(TOPIC)
+            .first_message_timeout(TIMEOUT)
+            .build()
+            .await;
+        assert!(matches!(res, Err(Error::Timeout(_))));
+
+        let publisher = client.advertise::<std_msgs::Int32>(TOPIC).await?;
+        let publish = tokio::spawn(async move {
+            loop {
+                let _ = publisher.publish(&std_msgs::Int32 { data: 7 }).await;
+                tokio::time::sleep(Duration::from_millis(50)).await;
+            }
+        });
+        let subscriber = client
+            .subscribe_builder::<std_msgs::Int32>(TOPIC)
+            .queue(1)
+            .no_md5_check()
+            .first_message_timeout(Duration::from_secs(5))
+            .build()
+            .await;
+        publish.abort();
+        let subscriber = subscriber?;
+        // The message waited for is still queued
+        assert!(!subscriber.is_empty());
+        assert_eq!(subscriber.next().await.data, 7);
+        Ok(())
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn publishers_share_one_advertisement() -> TestResult {
+        const TOPIC: &str = "/publishers_share_one_advertisement";
+        let ops = Arc::new(std::sync::Mutex::new(vec![]));
+        let ops_copy = ops.clone();
+        let opt = ClientHandleOptions::new(LOCAL_WS)
+            .timeout(TIMEOUT)
+            .op_hook(move |op| ops_copy.lock().unwrap().push(op.clone()));
+        let client = ClientHandle::new_with_options(opt).await?;
+
+        let first = client.advertise::<std_msgs::Int32>(TOPIC).await?;
+        let second = client.advertise::<std_msgs::Int32>(TOPIC).await?;
+        assert_eq!(first.advertise_id(), second.advertise_id());
+        // A different type can't share the advertisement
+        assert!(client.advertise::<std_msgs::String>(TOPIC).await.is_err());
+
+        let subscriber = client.subscribe::<std_msgs::Int32>(TOPIC).await?;
+        tokio::time::sleep(TIMEOUT).await;
+
+        // Dropping one publisher leaves the topic advertised for the other
+        drop(first);
+        tokio::time::sleep(TIMEOUT).await;
+        second.publish(&std_msgs::Int32 { data: 1 }).await?;
+        let msg = timeout(TIMEOUT, subscriber.next()).await?;
+        assert_eq!(msg.data, 1);
+
+        second.unadvertise().await?;
+        let ops = ops.lock().unwrap();
+        assert_eq!(ops.iter().filter(|op| op.op == "advertise").count(), 1);
+        assert_eq!(ops.iter().filter(|op| op.op == "unadvertise").count(), 1);
+        Ok(())
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn op_ids_are_reported_to_hook() -> TestResult {
+        const TOPIC: &str = "/op_ids_are_reported_to_hook";
+        let ops = Arc::new(std::sync::Mutex::new(vec![]));
+        let ops_copy = ops.clone();
+        let opt = ClientHandleOptions::new(LOCAL_WS)
+            .timeout(TIMEOUT)
+            .op_hook(move |op| ops_copy.lock().unwrap().push(op.clone()));
+        let client = ClientHandle::new_with_options(opt).await?;
+
+        let publisher = client.advertise::<Header>(TOPIC).await?;
+        let subscriber = client.subscribe::<Header>(TOPIC).await?;
+        let publish_id = publisher.publish_with_id(&Header::default()).await?;
+        let advertise_id = publisher.advertise_id().to_string();
+        let subscribe_id = subscriber.subscribe_id().to_string();
+
+        // Unadvertise and unsubscribe are sent from tasks spawned on drop
+        std::mem::drop(publisher);
+        std::mem::drop(subscriber);
+        tokio::time::sleep(TIMEOUT).await;
+
+        let ops = ops.lock().unwrap();
+        assert!(ops
+            .iter()
+            .any(|op| op.op == "advertise" && op.id == advertise_id));
+        assert!(ops
+            .iter()
+            .any(|op| op.op == "subscribe" && op.id == subscribe_id));
+        assert!(ops
+            .iter()
+            .any(|op| op.op == "publish" && op.id == publish_id && op.target == TOPIC));
+        // Teardown ops must reuse the ids of the ops they undo for rosbridge to match them
+        assert!(ops
+            .iter()
+            .any(|op| op.op == "unadvertise" && op.id == advertise_id));
+        assert!(ops
+            .iter()
+            .any(|op| op.op == "unsubscribe" && op.id == subscribe_id));
+        Ok(())
+    }
 }
@@ -50,6 +50,11 @@ pub use publisher::*;
 mod client;
 pub use client::*;
 
+// Status is a transparent module, we directly expose internal types
+// Module exists only to organize source code
+mod status;
+pub use status::*;
+
 // Tests are fully private module
 #[cfg(test)]
 mod integration_tests;
@@ -62,7 +67,7 @@ type TestResult = std::result::Result<(), anyhow::Error>;
 mod comm;
 
 use futures_util::stream::{SplitSink, SplitStream};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use tokio::net::TcpStream;
 use tokio_tungstenite::*;
 use tungstenite::Message;
@@ -120,6 +125,17 @@ impl<T: RosServiceType> ServiceClient<T> {
             .call_service::<T>(self.topic.as_str(), request)
             .await
     }
+
+    /// Calls the service, see [ClientHandle::call_service_with_cancel]
+    pub async fn call_with_cancel(
+        &self,
+        request: T::Request,
+        cancel: &CancellationToken,
+    ) -> Result<T::Response> {
+        self.client
+            .call_service_with_cancel::<T>(self.topic.as_str(), request, cancel)
+            .await
+    }
 }
 
 /// Our underlying communication socket type (maybe move to comm?)
@@ -136,7 +152,7 @@ type Writer = SplitSink<Socket, Message>;
 /// Topics have a fundamental queue *per subscriber* this is te queue type used for each subscriber.
 type MessageQueue<T> = deadqueue::limited::Queue<T>;
 
-// TODO queue size should be configurable for subscribers
+/// Default queue size of each subscriber, see [SubscribeOptions::queue_size]
 const QUEUE_SIZE: usize = 1_000;
 
 /// Internal tracking structure used to maintain information about each subscription our client has
@@ -148,11 +164,20 @@ pub(crate) struct Subscription {
     // Note: don't need dashmap here as the subscription is already inside a dashmap
     pub(crate) handles: HashMap<uuid::Uuid, Callback>,
     /// Name of ros type (package_name/message_name), used for re-subscribes
+    /// This is the type of the first subscriber, others may deserialize the messages as different types
     pub(crate) topic_type: String,
+    /// Id of the subscribe op sent to rosbridge_server, the unsubscribe op must carry the same id
+    pub(crate) subscribe_id: String,
 }
 
 pub(crate) struct PublisherHandle {
+    /// Name of ros type (package_name/message_name), all publishers on a topic must share it
     pub(crate) topic_type: String,
+    /// Id of the advertise op sent to rosbridge_server, replaced when re-advertising after a reconnect
+    pub(crate) advertise_id: String,
+    /// Ids of the [Publisher]s currently sharing this advertisement
+    /// The topic is only un-advertised once the last of them is gone
+    pub(crate) handles: HashSet<uuid::Uuid>,
 }
 
 // Implement the generic Service trait for our ServiceClient
@@ -161,6 +186,14 @@ impl<T: RosServiceType> Service<T> for crate::ServiceClient<T> {
         // TODO sort out the reference vs clone stuff here
         ServiceClient::call(self, request.clone()).await
     }
+
+    async fn call_with_cancel(
+        &self,
+        request: &T::Request,
+        cancel: &CancellationToken,
+    ) -> Result<T::Response> {
+        ServiceClient::call_with_cancel(self, request.clone(), cancel).await
+    }
 }
 
 impl ServiceProvider for crate::ClientHandle {
@@ -176,6 +209,17 @@ impl ServiceProvider for crate::ClientHandle {
         ClientHandle::call_service::<SrvType>(self, service.as_ref(), request).await
     }
 
+    async fn call_service_with_cancel<SrvType: RosServiceType>(
+        &self,
+        service: impl ToGlobalTopicName,
+        request: SrvType::Request,
+        cancel: &CancellationToken,
+    ) -> Result<SrvType::Response> {
+        let service: GlobalTopicName = service.to_global_name()?;
+        ClientHandle::call_service_with_cancel::<SrvType>(self, service.as_ref(), request, cancel)
+            .await
+    }
+
     async fn service_client<SrvType: RosServiceType + 'static>(
         &self,
         service: impl ToGlobalTopicName,
@@ -241,8 +285,15 @@ trait MapError {
 impl<T: Send + Sync> MapError for std::result::Result<T, tokio_tungstenite::tungstenite::Error> {
     type T = T;
     fn map_to_roslibrust(self) -> Result<T> {
+        use tokio_tungstenite::tungstenite::error::CapacityError;
         match self {
             Ok(t) => Ok(t),
+            Err(tungstenite::Error::Capacity(CapacityError::MessageTooLong { size, max_size })) => {
+                Err(Error::MessageTooLarge {
+                    size,
+                    max: max_size,
+                })
+            }
             Err(e) => Err(Error::IoError(std::io::Error::other(e))),
         }
     }
@@ -5,14 +5,11 @@ use roslibrust_common::RosMessageType;
 ///
 /// Publisher's have a single core function [publish](Publisher::publish) which provides the ability
 /// to send message on the associated topic. Publishers automatically un-advertise the topic when
-/// they are dropped. Currently, only one publisher is allowed to be created per topic, and
-/// subsequent calls to advertise() on the same topic will fail.
+/// they are dropped. Multiple publishers may be created for the same topic by calling advertise() again
+/// with the same type, they share one advertisement which is only un-advertised once the last of them is gone.
 ///
 /// Roadmap for Publisher:
-///   - Support clone() / multiple advertise
 ///   - Ability for publish to by const
-// Instead of giving back a publisher should we give back a reference to one, and give back the
-// same reference when you advertise multiple times? Would require non-mut references?
 pub struct Publisher<T: RosMessageType> {
     topic: String,
     // auto incrementing sequence number increased once per publish
@@ -24,24 +21,50 @@ pub struct Publisher<T: RosMessageType> {
     // seq: usize,
     // Stores a copy of the client so that we can de-register ourselves
     client: ClientHandle,
+    // Uniquely identifies this publisher amongst those sharing the topic's advertisement
+    id: uuid::Uuid,
+    // Id of the advertise op that created this publisher
+    advertise_id: String,
+    // Set once the topic has been explicitly un-advertised so drop doesn't do it again
+    unadvertised: bool,
     _marker: std::marker::PhantomData<T>,
 }
 
 /// Publisher will un-advertise its topic automatically on drop
 impl<T: RosMessageType> Drop for Publisher<T> {
     fn drop(&mut self) {
-        self.client.unadvertise(&self.topic);
+        if !self.unadvertised {
+            self.client.unadvertise(&self.topic, &self.id);
+        }
     }
 }
 
 impl<T: RosMessageType> Publisher<T> {
-    pub(crate) fn new(topic: String, client: ClientHandle) -> Self {
+    pub(crate) fn new(
+        topic: String,
+        client: ClientHandle,
+        id: uuid::Uuid,
+        advertise_id: String,
+    ) -> Self {
         Publisher {
             topic,
             client,
+            id,
+            advertise_id,
+            unadvertised: false,
             _marker: Default::default(),
         }
     }
+
+    /// The `id` of the advertise op that created this publisher's advertisement.
+    ///
+    /// rosbridge_server includes this id in any `status` message related to the advertisement.
+    /// Publishers sharing a topic share the same advertisement and so the same id.
+    /// Note: if the client reconnects the topic is re-advertised with a new id.
+    pub fn advertise_id(&self) -> &str {
+        &self.advertise_id
+    }
+
     /// The "standard" publish function sends the message out, returns when publish succeeds
     ///
     /// The publish will be abandoned if the connection to the server is lost while in flight.
@@ -49,6 +72,98 @@ impl<T: RosMessageType> Publisher<T> {
     /// rosbridge_server, rosbridge_server will fail to re-transmit if the type of the message does not
     /// match the topic's definition on roscore.
     pub async fn publish(&self, msg: &T) -> roslibrust_common::Result<()> {
-        self.client.publish(&self.topic, msg).await
+        self.publish_with_id(msg).await.map(|_id| ())
+    }
+
+    /// Identical to [Publisher::publish], but returns the `id` that was sent with the publish op.
+    ///
+    /// The id can be used to correlate this publish with any `status` message rosbridge_server reports about it.
+    pub async fn publish_with_id(&self, msg: &T) -> roslibrust_common::Result<String> {
+        self.client
+            .publish(&self.topic, T::ROS_TYPE_NAME, msg)
+            .await
+    }
+
+    /// Releases this publisher's share of the topic's advertisement.
+    ///
+    /// If this was the last publisher for the topic the unadvertise op is sent to rosbridge_server before this returns.
+    /// Dropping the publisher does the same in a background task, this allows waiting for the teardown
+    /// to finish, e.g. before advertising the topic again with a different type.
+    pub async fn unadvertise(mut self) -> roslibrust_common::Result<()> {
+        self.unadvertised = true;
+        self.client.unadvertise_now(&self.topic, &self.id).await
+    }
+}
+
+/// A publisher of messages given as JSON, returned by [ClientHandle::advertise_raw].
+///
+/// Behaves like a [Publisher], sharing its topic's advertisement and un-advertising the topic when the last
+/// publisher sharing it is dropped.
+pub struct PublisherRaw {
+    topic: String,
+    // Name of the ROS type the topic was advertised as
+    topic_type: String,
+    client: ClientHandle,
+    id: uuid::Uuid,
+    advertise_id: String,
+    // Set once the topic has been explicitly un-advertised so drop doesn't do it again
+    unadvertised: bool,
+}
+
+impl Drop for PublisherRaw {
+    fn drop(&mut self) {
+        if !self.unadvertised {
+            self.client.unadvertise(&self.topic, &self.id);
+        }
+    }
+}
+
+impl PublisherRaw {
+    pub(crate) fn new(
+        topic: String,
+        topic_type: String,
+        client: ClientHandle,
+        id: uuid::Uuid,
+        advertise_id: String,
+    ) -> Self {
+        PublisherRaw {
+            topic,
+            topic_type,
+            client,
+            id,
+            advertise_id,
+            unadvertised: false,
+        }
+    }
+
+    /// The name of the ROS type the topic was advertised as
+    pub fn topic_type(&self) -> &str {
+        &self.topic_type
+    }
+
+    /// The `id` of the advertise op that created this publisher's advertisement, see [Publisher::advertise_id].
+    pub fn advertise_id(&self) -> &str {
+        &self.advertise_id
+    }
+
+    /// Sends the JSON representation of a message, see [Publisher::publish].
+    pub async fn publish(&self, msg: &serde_json::Value) -> roslibrust_common::Result<()> {
+        self.publish_with_id(msg).await.map(|_id| ())
+    }
+
+    /// Identical to [PublisherRaw::publish], but returns the `id` that was sent with the publish op.
+    pub async fn publish_with_id(
+        &self,
+        msg: &serde_json::Value,
+    ) -> roslibrust_common::Result<String> {
+        self.client
+            .publish(&self.topic, &self.topic_type, msg)
+            .await
+    }
+
+    /// Releases this publisher's share of the topic's advertisement, see [Publisher::unadvertise].
+    pub async fn unadvertise(mut self) -> roslibrust_common::Result<()> {
+        self.unadvertised = true;
+        self.client.unadvertise_now(&self.topic, &self.id).await
     }
 }
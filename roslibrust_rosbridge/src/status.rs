@@ -0,0 +1,119 @@
+// This file / module covers the `status` messages rosbridge_server sends to report warnings and errors
+// and the `set_level` op used to control which of those messages the server sends
+
+use serde_json::Value;
+use std::{fmt::Display, str::FromStr};
+
+/// The verbosity levels used by rosbridge_server's `status` and `set_level` operations.
+///
+/// Levels are ordered by severity, so `StatusLevel::Info < StatusLevel::Error`.
+/// When used as a filter a level includes itself and everything more severe.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum StatusLevel {
+    Info,
+    Warning,
+    Error,
+    /// Only meaningful with [crate::ClientHandle::set_status_level], requests that the server send no status messages
+    None,
+}
+
+impl Display for StatusLevel {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let s = match self {
+            StatusLevel::Info => "info",
+            StatusLevel::Warning => "warning",
+            StatusLevel::Error => "error",
+            StatusLevel::None => "none",
+        };
+        write!(f, "{s}")
+    }
+}
+
+impl FromStr for StatusLevel {
+    type Err = anyhow::Error;
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        Ok(match s {
+            "info" => StatusLevel::Info,
+            "warning" => StatusLevel::Warning,
+            "error" => StatusLevel::Error,
+            "none" => StatusLevel::None,
+            _ => anyhow::bail!("Un-recognized status level: {s}"),
+        })
+    }
+}
+
+/// A `status` message received from rosbridge_server.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StatusEvent {
+    pub level: StatusLevel,
+    /// Human readable description of the event provided by the server
+    pub msg: String,
+    /// The id of the op this status relates to, if the server provided one.
+    ///
+    /// This matches the ids reported by [crate::ClientHandleOptions::op_hook].
+    pub id: Option<String>,
+}
+
+impl StatusEvent {
+    /// Parses a status event out of the full json body of a `status` op
+    pub(crate) fn from_json(data: &Value) -> anyhow::Result<Self> {
+        let level = data
+            .get("level")
+            .and_then(|level| level.as_str())
+            .ok_or(anyhow::anyhow!(
+                "Status message is missing string field `level`"
+            ))?;
+        let msg = data
+            .get("msg")
+            .and_then(|msg| msg.as_str())
+            .unwrap_or_default()
+            .to_string();
+        let id = data
+            .get("id")
+            .and_then(|id| id.as_str())
+            .map(|id| id.to_string());
+        Ok(StatusEvent {
+            level: level.parse()?,
+            msg,
+            id,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn parse_status_event() {
+        let data = serde_json::json!({
+            "op": "status",
+            "id": "publish:/chatter:3",
+            "level": "error",
+            "msg": "Unable to publish",
+        });
+        let event = StatusEvent::from_json(&data).unwrap();
+        assert_eq!(event.level, StatusLevel::Error);
+        assert_eq!(event.msg, "Unable to publish");
+        assert_eq!(event.id.as_deref(), Some("publish:/chatter:3"));
+
+        let data = serde_json::json!({"op": "status", "level": "warning", "msg": "no id"});
+        let event = StatusEvent::from_json(&data).unwrap();
+        assert_eq!(event.level, StatusLevel::Warning);
+        assert_eq!(event.id, None);
+
+        let data = serde_json::json!({"op": "status", "level": "loud", "msg": "bad level"});
+        assert!(StatusEvent::from_json(&data).is_err());
+    }
+
+    #[test]
+    fn status_levels_are_ordered_by_severity() {
+        assert!(StatusLevel::Info < StatusLevel::Warning);
+        assert!(StatusLevel::Warning < StatusLevel::Error);
+        assert_eq!(
+            "warning".parse::<StatusLevel>().unwrap(),
+            StatusLevel::Warning
+        );
+        assert_eq!(StatusLevel::None.to_string(), "none");
+    }
+}
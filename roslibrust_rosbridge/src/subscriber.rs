@@ -4,21 +4,166 @@
 
 use log::error;
 use std::sync::Arc;
+use std::time::Duration;
 
-use crate::{ClientHandle, MessageQueue};
-use roslibrust_common::RosMessageType;
+use crate::{ClientHandle, MessageQueue, QUEUE_SIZE};
+use roslibrust_common::{CancellationToken, RosMessageType};
+
+/// Options for a single subscriber created with [ClientHandle::subscribe_with_options].
+///
+/// All subscribers to a topic on a client share one subscription with rosbridge_server, these options are applied
+/// client side to each subscriber independently.
+#[derive(Clone, Debug)]
+pub struct SubscribeOptions {
+    pub(crate) queue_size: usize,
+    pub(crate) throttle_rate: Option<Duration>,
+}
+
+impl Default for SubscribeOptions {
+    fn default() -> Self {
+        SubscribeOptions {
+            queue_size: QUEUE_SIZE,
+            throttle_rate: None,
+        }
+    }
+}
+
+impl SubscribeOptions {
+    /// Creates options with the default queue size of 1_000 messages and no throttling
+    pub fn new() -> SubscribeOptions {
+        SubscribeOptions::default()
+    }
+
+    /// Sets how many messages the subscriber queues before dropping the oldest, must be at least 1
+    pub fn queue_size(mut self, queue_size: usize) -> SubscribeOptions {
+        self.queue_size = queue_size.max(1);
+        self
+    }
+
+    /// Only queue a message if at least this long has passed since the last message queued for this subscriber,
+    /// like rosbridge_server's `throttle_rate` but without affecting other subscribers to the topic.
+    pub fn throttle_rate(mut self, throttle_rate: Duration) -> SubscribeOptions {
+        self.throttle_rate = Some(throttle_rate);
+        self
+    }
+}
+
+/// Builds a [Subscriber], created with [ClientHandle::subscribe_builder].
+///
+/// Mirrors the subscriber builder of the ROS1 backend so code can be written against either.
+/// rosbridge_server handles type checking and transports itself, so [SubscriberBuilder::no_md5_check],
+/// [SubscriberBuilder::reliable_only] and [SubscriberBuilder::udp_ok] have no effect on this backend.
+pub struct SubscriberBuilder<T> {
+    client: ClientHandle,
+    topic_name: String,
+    options: SubscribeOptions,
+    first_message_timeout: Option<Duration>,
+    cancel: CancellationToken,
+    _phantom: std::marker::PhantomData<T>,
+}
+
+impl<T: RosMessageType> SubscriberBuilder<T> {
+    pub(crate) fn new(client: ClientHandle, topic_name: &str) -> Self {
+        SubscriberBuilder {
+            client,
+            topic_name: topic_name.to_owned(),
+            options: SubscribeOptions::default(),
+            first_message_timeout: None,
+            cancel: CancellationToken::new(),
+            _phantom: std::marker::PhantomData,
+        }
+    }
+
+    /// Replaces all of the [SubscribeOptions], the queue size set by an earlier call to [SubscriberBuilder::queue]
+    /// is lost
+    pub fn options(mut self, options: SubscribeOptions) -> Self {
+        self.options = options;
+        self
+    }
+
+    /// Sets the subscriber's queue size, see [SubscribeOptions::queue_size]
+    pub fn queue(mut self, queue_size: usize) -> Self {
+        self.options = self.options.queue_size(queue_size);
+        self
+    }
+
+    /// Has no effect, rosbridge_server doesn't check message types against publishers
+    pub fn no_md5_check(self) -> Self {
+        self
+    }
+
+    /// Makes [SubscriberBuilder::build] fail with [roslibrust_common::Error::Timeout] if no message is received
+    /// within `timeout` of subscribing.
+    ///
+    /// The message waited for is not consumed, it is returned by the first call to [Subscriber::next].
+    pub fn first_message_timeout(mut self, timeout: Duration) -> Self {
+        self.first_message_timeout = Some(timeout);
+        self
+    }
+
+    /// Has no effect, messages always arrive over the client's websocket
+    pub fn reliable_only(self) -> Self {
+        self
+    }
+
+    /// Has no effect, messages always arrive over the client's websocket
+    pub fn udp_ok(self) -> Self {
+        self
+    }
+
+    /// Makes [SubscriberBuilder::build] fail with [roslibrust_common::Error::Cancelled] if `cancel` is cancelled
+    /// while subscribing or waiting for the first message.
+    pub fn cancel_on(mut self, cancel: &CancellationToken) -> Self {
+        self.cancel = cancel.clone();
+        self
+    }
+
+    /// Subscribes to the topic, see [ClientHandle::subscribe_with_options]
+    pub async fn build(self) -> roslibrust_common::Result<Subscriber<T>> {
+        let subscriber = self
+            .cancel
+            .run_until_cancelled(
+                self.client
+                    .subscribe_with_options::<T>(&self.topic_name, self.options),
+            )
+            .await
+            .ok_or(roslibrust_common::Error::Cancelled)??;
+        let Some(timeout) = self.first_message_timeout else {
+            return Ok(subscriber);
+        };
+        // The queue can't be waited on without popping from it, so poll until a message arrives
+        let first_message = async {
+            while subscriber.is_empty() {
+                tokio::time::sleep(Duration::from_millis(10)).await;
+            }
+        };
+        match self
+            .cancel
+            .run_until_cancelled(tokio::time::timeout(timeout, first_message))
+            .await
+        {
+            Some(Ok(())) => Ok(subscriber),
+            Some(Err(_elapsed)) => Err(roslibrust_common::Error::Timeout(format!(
+                "No message received on {} within {timeout:?} of subscribing",
+                self.topic_name
+            ))),
+            None => Err(roslibrust_common::Error::Cancelled),
+        }
+    }
+}
 
 /// Represents a single instance of listening to a topic, and provides the ability to extract messages
 ///
-/// A single topic can be subscribed to multiple times and each subscriber will get a unique message queue.
-/// All subscribers will receive a copy of the incoming message anytime one is received.
+/// A single topic can be subscribed to multiple times, with the same or different types and [SubscribeOptions],
+/// and each subscriber will get a unique message queue.
+/// Only the first subscriber to a topic subscribes with rosbridge_server, all subscribers will receive a copy of
+/// each incoming message that deserializes as their type.
 /// When the last subscriber is dropped the topic is automatically un-subscribed to.
-/// The internal message queue is currently fixed sized at 1_000 item maximum.
+/// The internal message queue defaults to a 1_000 item maximum, see [SubscribeOptions::queue_size].
 ///
 /// The internal message queue is internally mutex'ed meaning const access to this class is sufficient for use.
 ///
 /// Roadmap:
-///  - Expose queue size control
 ///  - Provide unlimited queue (maybe?)
 ///  - Provide automatic alerting mechanism on queue growth / fullness
 pub struct Subscriber<T: RosMessageType> {
@@ -29,20 +174,41 @@ pub struct Subscriber<T: RosMessageType> {
     // Holds an internal copy of client to reference back to when being drop'ed
     client: ClientHandle,
     queue: Arc<MessageQueue<T>>,
+    // Id of the subscribe op of the subscription to rosbridge_server this subscriber shares
+    subscribe_id: String,
+    // Set once explicitly unsubscribed so drop doesn't unsubscribe again
+    unsubscribed: bool,
 }
 
 impl<T: RosMessageType> Subscriber<T> {
     // External API is accessed through ClientHandle::subscribe
     // This function is just a convenience wrapper for our internal API
-    pub(crate) fn new(client: ClientHandle, queue: Arc<MessageQueue<T>>, topic: String) -> Self {
+    pub(crate) fn new(
+        client: ClientHandle,
+        queue: Arc<MessageQueue<T>>,
+        topic: String,
+        id: uuid::Uuid,
+        subscribe_id: String,
+    ) -> Self {
         Subscriber {
-            id: uuid::Uuid::new_v4(),
+            id,
             topic,
             client,
             queue,
+            subscribe_id,
+            unsubscribed: false,
         }
     }
 
+    /// The `id` of the subscribe op sent to rosbridge_server for this subscriber's topic.
+    ///
+    /// Subscribers to the same topic share one subscription and so report the same id, that of the subscribe op
+    /// sent for the first of them. The subscription is re-sent with a new id if the client reconnects.
+    /// rosbridge_server includes this id in any `status` message related to the subscription.
+    pub fn subscribe_id(&self) -> &str {
+        &self.subscribe_id
+    }
+
     /// Returns the number of messages currently queued in the subscriber
     pub fn len(&self) -> usize {
         self.queue.len()
@@ -55,7 +221,7 @@ impl<T: RosMessageType> Subscriber<T> {
 
     /// aka pop(). Returns the oldest message in the internal message queue.
     ///
-    /// Blocks if queue is empty
+    /// Blocks if queue is empty. Cancel safe, a message is only removed from the queue once it is returned.
     ///
     /// Warning: failing to call next() fast enough can cause messages to accumulate in the internal queue,
     /// this can cause latency to build-up and may not be desirable.
@@ -73,9 +239,13 @@ impl<T: RosMessageType> Subscriber<T> {
         self.queue.pop().await
     }
 
-    // Used internally to track subscribers within the ClientHandle
-    pub(crate) fn get_id(&self) -> &uuid::Uuid {
-        &self.id
+    /// Removes this subscriber, completing once the client has processed the removal.
+    ///
+    /// If this was the last subscriber to the topic the unsubscribe op is sent to rosbridge_server before this returns.
+    /// Dropping the subscriber does the same in a background task, this allows waiting for the teardown to finish.
+    pub async fn unsubscribe(mut self) -> roslibrust_common::Result<()> {
+        self.unsubscribed = true;
+        self.client.unsubscribe_now(&self.topic, &self.id).await
     }
 }
 
@@ -83,6 +253,9 @@ impl<T: RosMessageType> Subscriber<T> {
 /// the client can track when the last subscriber for a topic is dropped
 impl<T: RosMessageType> Drop for Subscriber<T> {
     fn drop(&mut self) {
+        if self.unsubscribed {
+            return;
+        }
         match self.client.unsubscribe(&self.topic, &self.id) {
             Ok(_) => {}
             Err(e) => {
@@ -0,0 +1,51 @@
+//! Compares regular and compact codegen of the ROS1 interfaces in assets/
+//!
+//! Besides the time taken to generate the code, which is spent in the codegen macros on every build,
+//! the size of the generated code is printed as it drives the time taken to compile it.
+use criterion::{criterion_group, criterion_main, Criterion};
+use roslibrust::codegen::CodegenOptions;
+use std::{hint::black_box, path::PathBuf};
+
+fn search_paths() -> Vec<PathBuf> {
+    vec![
+        concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/../assets/ros1_common_interfaces"
+        )
+        .into(),
+        concat!(env!("CARGO_MANIFEST_DIR"), "/../assets/ros1_test_msgs").into(),
+    ]
+}
+
+fn generate(options: &CodegenOptions) -> String {
+    let (source, _) =
+        roslibrust::codegen::find_and_generate_ros_messages_with_options(search_paths(), options)
+            .unwrap();
+    source.to_string()
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    env_logger::init();
+
+    let full = CodegenOptions::default();
+    let compact = CodegenOptions {
+        compact: true,
+        ..Default::default()
+    };
+    for (name, options) in [("full", &full), ("compact", &compact)] {
+        let source = generate(options);
+        println!(
+            "{name} codegen: {} bytes, {} tokens",
+            source.len(),
+            source.split_whitespace().count()
+        );
+    }
+
+    c.bench_function("codegen_full", |b| b.iter(|| black_box(generate(&full))));
+    c.bench_function("codegen_compact", |b| {
+        b.iter(|| black_box(generate(&compact)))
+    });
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
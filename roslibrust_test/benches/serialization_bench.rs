@@ -0,0 +1,94 @@
+//! Measures rosmsg serialization throughput of large common message types
+//!
+//! Unlike image_bench this doesn't need a ROS master, only the (de)serialization used on every publish and
+//! receive is measured.
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use roslibrust::codegen::{DeserializeOwned, Serialize};
+use roslibrust_test::ros1::{sensor_msgs, std_msgs};
+use std::{
+    f32::consts::{PI, TAU},
+    hint::black_box,
+};
+
+fn header() -> std_msgs::Header {
+    std_msgs::Header {
+        seq: 1,
+        stamp: Default::default(),
+        frame_id: "sensor".to_owned(),
+    }
+}
+
+fn image() -> sensor_msgs::Image {
+    sensor_msgs::Image {
+        header: header(),
+        height: 1080,
+        width: 1920,
+        encoding: "rgb8".to_owned(),
+        is_bigendian: 0,
+        step: 1920 * 3,
+        data: vec![7; 1920 * 1080 * 3],
+    }
+}
+
+fn point_cloud() -> sensor_msgs::PointCloud2 {
+    const POINTS: u32 = 100_000;
+    let fields = ["x", "y", "z", "intensity"]
+        .into_iter()
+        .enumerate()
+        .map(|(i, name)| sensor_msgs::PointField {
+            name: name.to_owned(),
+            offset: i as u32 * 4,
+            datatype: sensor_msgs::PointField::FLOAT32,
+            count: 1,
+        })
+        .collect();
+    sensor_msgs::PointCloud2 {
+        header: header(),
+        height: 1,
+        width: POINTS,
+        fields,
+        is_bigendian: false,
+        point_step: 16,
+        row_step: 16 * POINTS,
+        data: vec![3; 16 * POINTS as usize],
+        is_dense: true,
+    }
+}
+
+fn laser_scan() -> sensor_msgs::LaserScan {
+    const RAYS: usize = 10_000;
+    sensor_msgs::LaserScan {
+        header: header(),
+        angle_min: -PI,
+        angle_max: PI,
+        angle_increment: TAU / RAYS as f32,
+        time_increment: 0.0,
+        scan_time: 0.1,
+        range_min: 0.1,
+        range_max: 30.0,
+        ranges: (0..RAYS).map(|i| i as f32 * 0.001).collect(),
+        intensities: vec![100.0; RAYS],
+    }
+}
+
+fn bench_message<T: Serialize + DeserializeOwned>(c: &mut Criterion, name: &str, msg: T) {
+    let bytes = roslibrust_serde_rosmsg::to_vec(&msg).unwrap();
+    let mut group = c.benchmark_group(name);
+    group.throughput(Throughput::Bytes(bytes.len() as u64));
+    group.bench_function(BenchmarkId::new("serialize", bytes.len()), |b| {
+        b.iter(|| roslibrust_serde_rosmsg::to_vec(black_box(&msg)).unwrap())
+    });
+    group.bench_function(BenchmarkId::new("deserialize", bytes.len()), |b| {
+        b.iter(|| roslibrust_serde_rosmsg::from_slice::<T>(black_box(&bytes)).unwrap())
+    });
+    group.finish();
+}
+
+fn criterion_benchmark(c: &mut Criterion) {
+    bench_message(c, "image", image());
+    bench_message(c, "point_cloud2", point_cloud());
+    bench_message(c, "laser_scan", laser_scan());
+}
+
+criterion_group!(benches, criterion_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,2839 @@
+impl ::std::convert::From<crate::ros1::actionlib_msgs::GoalID>
+    for crate::ros2::actionlib_msgs::GoalID
+{
+    fn from(msg: crate::ros1::actionlib_msgs::GoalID) -> Self {
+        Self {
+            r#stamp: {
+                let (sec, nanosec) = msg.r#stamp.to_sec_nanosec();
+                crate::ros2::builtin_interfaces::Time { sec, nanosec }
+            },
+            r#id: msg.r#id,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::actionlib_msgs::GoalID>
+    for crate::ros1::actionlib_msgs::GoalID
+{
+    fn from(msg: crate::ros2::actionlib_msgs::GoalID) -> Self {
+        Self {
+            r#stamp: ::roslibrust::codegen::integral_types::Time::from_sec_nanosec(
+                msg.r#stamp.r#sec,
+                msg.r#stamp.r#nanosec,
+            ),
+            r#id: msg.r#id,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::actionlib_msgs::GoalStatus>
+    for crate::ros2::actionlib_msgs::GoalStatus
+{
+    fn from(msg: crate::ros1::actionlib_msgs::GoalStatus) -> Self {
+        Self {
+            r#goal_id: ::std::convert::From::from(msg.r#goal_id),
+            r#status: msg.r#status,
+            r#text: msg.r#text,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::actionlib_msgs::GoalStatus>
+    for crate::ros1::actionlib_msgs::GoalStatus
+{
+    fn from(msg: crate::ros2::actionlib_msgs::GoalStatus) -> Self {
+        Self {
+            r#goal_id: ::std::convert::From::from(msg.r#goal_id),
+            r#status: msg.r#status,
+            r#text: msg.r#text,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::actionlib_msgs::GoalStatusArray>
+    for crate::ros2::actionlib_msgs::GoalStatusArray
+{
+    fn from(msg: crate::ros1::actionlib_msgs::GoalStatusArray) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#status_list: msg
+                .r#status_list
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::actionlib_msgs::GoalStatusArray>
+    for crate::ros1::actionlib_msgs::GoalStatusArray
+{
+    fn from(msg: crate::ros2::actionlib_msgs::GoalStatusArray) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#status_list: msg
+                .r#status_list
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::builtin_interfaces::Duration>
+    for crate::ros2::builtin_interfaces::Duration
+{
+    fn from(msg: crate::ros1::builtin_interfaces::Duration) -> Self {
+        Self {
+            r#sec: msg.r#sec,
+            r#nanosec: msg.r#nanosec,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::builtin_interfaces::Duration>
+    for crate::ros1::builtin_interfaces::Duration
+{
+    fn from(msg: crate::ros2::builtin_interfaces::Duration) -> Self {
+        Self {
+            r#sec: msg.r#sec,
+            r#nanosec: msg.r#nanosec,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::builtin_interfaces::Time>
+    for crate::ros2::builtin_interfaces::Time
+{
+    fn from(msg: crate::ros1::builtin_interfaces::Time) -> Self {
+        Self {
+            r#sec: msg.r#sec,
+            r#nanosec: msg.r#nanosec,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::builtin_interfaces::Time>
+    for crate::ros1::builtin_interfaces::Time
+{
+    fn from(msg: crate::ros2::builtin_interfaces::Time) -> Self {
+        Self {
+            r#sec: msg.r#sec,
+            r#nanosec: msg.r#nanosec,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::diagnostic_msgs::DiagnosticArray>
+    for crate::ros2::diagnostic_msgs::DiagnosticArray
+{
+    fn from(msg: crate::ros1::diagnostic_msgs::DiagnosticArray) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#status: msg
+                .r#status
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::diagnostic_msgs::DiagnosticArray>
+    for crate::ros1::diagnostic_msgs::DiagnosticArray
+{
+    fn from(msg: crate::ros2::diagnostic_msgs::DiagnosticArray) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#status: msg
+                .r#status
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::diagnostic_msgs::DiagnosticStatus>
+    for crate::ros2::diagnostic_msgs::DiagnosticStatus
+{
+    fn from(msg: crate::ros1::diagnostic_msgs::DiagnosticStatus) -> Self {
+        Self {
+            r#level: msg.r#level,
+            r#name: msg.r#name,
+            r#message: msg.r#message,
+            r#hardware_id: msg.r#hardware_id,
+            r#values: msg
+                .r#values
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::diagnostic_msgs::DiagnosticStatus>
+    for crate::ros1::diagnostic_msgs::DiagnosticStatus
+{
+    fn from(msg: crate::ros2::diagnostic_msgs::DiagnosticStatus) -> Self {
+        Self {
+            r#level: msg.r#level,
+            r#name: msg.r#name,
+            r#message: msg.r#message,
+            r#hardware_id: msg.r#hardware_id,
+            r#values: msg
+                .r#values
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::diagnostic_msgs::KeyValue>
+    for crate::ros2::diagnostic_msgs::KeyValue
+{
+    fn from(msg: crate::ros1::diagnostic_msgs::KeyValue) -> Self {
+        Self {
+            r#key: msg.r#key,
+            r#value: msg.r#value,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::diagnostic_msgs::KeyValue>
+    for crate::ros1::diagnostic_msgs::KeyValue
+{
+    fn from(msg: crate::ros2::diagnostic_msgs::KeyValue) -> Self {
+        Self {
+            r#key: msg.r#key,
+            r#value: msg.r#value,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::Accel> for crate::ros2::geometry_msgs::Accel {
+    fn from(msg: crate::ros1::geometry_msgs::Accel) -> Self {
+        Self {
+            r#linear: ::std::convert::From::from(msg.r#linear),
+            r#angular: ::std::convert::From::from(msg.r#angular),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::Accel> for crate::ros1::geometry_msgs::Accel {
+    fn from(msg: crate::ros2::geometry_msgs::Accel) -> Self {
+        Self {
+            r#linear: ::std::convert::From::from(msg.r#linear),
+            r#angular: ::std::convert::From::from(msg.r#angular),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::AccelStamped>
+    for crate::ros2::geometry_msgs::AccelStamped
+{
+    fn from(msg: crate::ros1::geometry_msgs::AccelStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#accel: ::std::convert::From::from(msg.r#accel),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::AccelStamped>
+    for crate::ros1::geometry_msgs::AccelStamped
+{
+    fn from(msg: crate::ros2::geometry_msgs::AccelStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#accel: ::std::convert::From::from(msg.r#accel),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::AccelWithCovariance>
+    for crate::ros2::geometry_msgs::AccelWithCovariance
+{
+    fn from(msg: crate::ros1::geometry_msgs::AccelWithCovariance) -> Self {
+        Self {
+            r#accel: ::std::convert::From::from(msg.r#accel),
+            r#covariance: msg.r#covariance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::AccelWithCovariance>
+    for crate::ros1::geometry_msgs::AccelWithCovariance
+{
+    fn from(msg: crate::ros2::geometry_msgs::AccelWithCovariance) -> Self {
+        Self {
+            r#accel: ::std::convert::From::from(msg.r#accel),
+            r#covariance: msg.r#covariance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::AccelWithCovarianceStamped>
+    for crate::ros2::geometry_msgs::AccelWithCovarianceStamped
+{
+    fn from(msg: crate::ros1::geometry_msgs::AccelWithCovarianceStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#accel: ::std::convert::From::from(msg.r#accel),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::AccelWithCovarianceStamped>
+    for crate::ros1::geometry_msgs::AccelWithCovarianceStamped
+{
+    fn from(msg: crate::ros2::geometry_msgs::AccelWithCovarianceStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#accel: ::std::convert::From::from(msg.r#accel),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::Inertia>
+    for crate::ros2::geometry_msgs::Inertia
+{
+    fn from(msg: crate::ros1::geometry_msgs::Inertia) -> Self {
+        Self {
+            r#m: msg.r#m,
+            r#com: ::std::convert::From::from(msg.r#com),
+            r#ixx: msg.r#ixx,
+            r#ixy: msg.r#ixy,
+            r#ixz: msg.r#ixz,
+            r#iyy: msg.r#iyy,
+            r#iyz: msg.r#iyz,
+            r#izz: msg.r#izz,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::Inertia>
+    for crate::ros1::geometry_msgs::Inertia
+{
+    fn from(msg: crate::ros2::geometry_msgs::Inertia) -> Self {
+        Self {
+            r#m: msg.r#m,
+            r#com: ::std::convert::From::from(msg.r#com),
+            r#ixx: msg.r#ixx,
+            r#ixy: msg.r#ixy,
+            r#ixz: msg.r#ixz,
+            r#iyy: msg.r#iyy,
+            r#iyz: msg.r#iyz,
+            r#izz: msg.r#izz,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::InertiaStamped>
+    for crate::ros2::geometry_msgs::InertiaStamped
+{
+    fn from(msg: crate::ros1::geometry_msgs::InertiaStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#inertia: ::std::convert::From::from(msg.r#inertia),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::InertiaStamped>
+    for crate::ros1::geometry_msgs::InertiaStamped
+{
+    fn from(msg: crate::ros2::geometry_msgs::InertiaStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#inertia: ::std::convert::From::from(msg.r#inertia),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::Point> for crate::ros2::geometry_msgs::Point {
+    fn from(msg: crate::ros1::geometry_msgs::Point) -> Self {
+        Self {
+            r#x: msg.r#x,
+            r#y: msg.r#y,
+            r#z: msg.r#z,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::Point> for crate::ros1::geometry_msgs::Point {
+    fn from(msg: crate::ros2::geometry_msgs::Point) -> Self {
+        Self {
+            r#x: msg.r#x,
+            r#y: msg.r#y,
+            r#z: msg.r#z,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::Point32>
+    for crate::ros2::geometry_msgs::Point32
+{
+    fn from(msg: crate::ros1::geometry_msgs::Point32) -> Self {
+        Self {
+            r#x: msg.r#x,
+            r#y: msg.r#y,
+            r#z: msg.r#z,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::Point32>
+    for crate::ros1::geometry_msgs::Point32
+{
+    fn from(msg: crate::ros2::geometry_msgs::Point32) -> Self {
+        Self {
+            r#x: msg.r#x,
+            r#y: msg.r#y,
+            r#z: msg.r#z,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::PointStamped>
+    for crate::ros2::geometry_msgs::PointStamped
+{
+    fn from(msg: crate::ros1::geometry_msgs::PointStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#point: ::std::convert::From::from(msg.r#point),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::PointStamped>
+    for crate::ros1::geometry_msgs::PointStamped
+{
+    fn from(msg: crate::ros2::geometry_msgs::PointStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#point: ::std::convert::From::from(msg.r#point),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::Polygon>
+    for crate::ros2::geometry_msgs::Polygon
+{
+    fn from(msg: crate::ros1::geometry_msgs::Polygon) -> Self {
+        Self {
+            r#points: msg
+                .r#points
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::Polygon>
+    for crate::ros1::geometry_msgs::Polygon
+{
+    fn from(msg: crate::ros2::geometry_msgs::Polygon) -> Self {
+        Self {
+            r#points: msg
+                .r#points
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::PolygonStamped>
+    for crate::ros2::geometry_msgs::PolygonStamped
+{
+    fn from(msg: crate::ros1::geometry_msgs::PolygonStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#polygon: ::std::convert::From::from(msg.r#polygon),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::PolygonStamped>
+    for crate::ros1::geometry_msgs::PolygonStamped
+{
+    fn from(msg: crate::ros2::geometry_msgs::PolygonStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#polygon: ::std::convert::From::from(msg.r#polygon),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::Pose> for crate::ros2::geometry_msgs::Pose {
+    fn from(msg: crate::ros1::geometry_msgs::Pose) -> Self {
+        Self {
+            r#position: ::std::convert::From::from(msg.r#position),
+            r#orientation: ::std::convert::From::from(msg.r#orientation),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::Pose> for crate::ros1::geometry_msgs::Pose {
+    fn from(msg: crate::ros2::geometry_msgs::Pose) -> Self {
+        Self {
+            r#position: ::std::convert::From::from(msg.r#position),
+            r#orientation: ::std::convert::From::from(msg.r#orientation),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::Pose2D>
+    for crate::ros2::geometry_msgs::Pose2D
+{
+    fn from(msg: crate::ros1::geometry_msgs::Pose2D) -> Self {
+        Self {
+            r#x: msg.r#x,
+            r#y: msg.r#y,
+            r#theta: msg.r#theta,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::Pose2D>
+    for crate::ros1::geometry_msgs::Pose2D
+{
+    fn from(msg: crate::ros2::geometry_msgs::Pose2D) -> Self {
+        Self {
+            r#x: msg.r#x,
+            r#y: msg.r#y,
+            r#theta: msg.r#theta,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::PoseArray>
+    for crate::ros2::geometry_msgs::PoseArray
+{
+    fn from(msg: crate::ros1::geometry_msgs::PoseArray) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#poses: msg
+                .r#poses
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::PoseArray>
+    for crate::ros1::geometry_msgs::PoseArray
+{
+    fn from(msg: crate::ros2::geometry_msgs::PoseArray) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#poses: msg
+                .r#poses
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::PoseStamped>
+    for crate::ros2::geometry_msgs::PoseStamped
+{
+    fn from(msg: crate::ros1::geometry_msgs::PoseStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#pose: ::std::convert::From::from(msg.r#pose),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::PoseStamped>
+    for crate::ros1::geometry_msgs::PoseStamped
+{
+    fn from(msg: crate::ros2::geometry_msgs::PoseStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#pose: ::std::convert::From::from(msg.r#pose),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::PoseWithCovariance>
+    for crate::ros2::geometry_msgs::PoseWithCovariance
+{
+    fn from(msg: crate::ros1::geometry_msgs::PoseWithCovariance) -> Self {
+        Self {
+            r#pose: ::std::convert::From::from(msg.r#pose),
+            r#covariance: msg.r#covariance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::PoseWithCovariance>
+    for crate::ros1::geometry_msgs::PoseWithCovariance
+{
+    fn from(msg: crate::ros2::geometry_msgs::PoseWithCovariance) -> Self {
+        Self {
+            r#pose: ::std::convert::From::from(msg.r#pose),
+            r#covariance: msg.r#covariance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::PoseWithCovarianceStamped>
+    for crate::ros2::geometry_msgs::PoseWithCovarianceStamped
+{
+    fn from(msg: crate::ros1::geometry_msgs::PoseWithCovarianceStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#pose: ::std::convert::From::from(msg.r#pose),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::PoseWithCovarianceStamped>
+    for crate::ros1::geometry_msgs::PoseWithCovarianceStamped
+{
+    fn from(msg: crate::ros2::geometry_msgs::PoseWithCovarianceStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#pose: ::std::convert::From::from(msg.r#pose),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::Quaternion>
+    for crate::ros2::geometry_msgs::Quaternion
+{
+    fn from(msg: crate::ros1::geometry_msgs::Quaternion) -> Self {
+        Self {
+            r#x: msg.r#x,
+            r#y: msg.r#y,
+            r#z: msg.r#z,
+            r#w: msg.r#w,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::Quaternion>
+    for crate::ros1::geometry_msgs::Quaternion
+{
+    fn from(msg: crate::ros2::geometry_msgs::Quaternion) -> Self {
+        Self {
+            r#x: msg.r#x,
+            r#y: msg.r#y,
+            r#z: msg.r#z,
+            r#w: msg.r#w,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::QuaternionStamped>
+    for crate::ros2::geometry_msgs::QuaternionStamped
+{
+    fn from(msg: crate::ros1::geometry_msgs::QuaternionStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#quaternion: ::std::convert::From::from(msg.r#quaternion),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::QuaternionStamped>
+    for crate::ros1::geometry_msgs::QuaternionStamped
+{
+    fn from(msg: crate::ros2::geometry_msgs::QuaternionStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#quaternion: ::std::convert::From::from(msg.r#quaternion),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::Transform>
+    for crate::ros2::geometry_msgs::Transform
+{
+    fn from(msg: crate::ros1::geometry_msgs::Transform) -> Self {
+        Self {
+            r#translation: ::std::convert::From::from(msg.r#translation),
+            r#rotation: ::std::convert::From::from(msg.r#rotation),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::Transform>
+    for crate::ros1::geometry_msgs::Transform
+{
+    fn from(msg: crate::ros2::geometry_msgs::Transform) -> Self {
+        Self {
+            r#translation: ::std::convert::From::from(msg.r#translation),
+            r#rotation: ::std::convert::From::from(msg.r#rotation),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::TransformStamped>
+    for crate::ros2::geometry_msgs::TransformStamped
+{
+    fn from(msg: crate::ros1::geometry_msgs::TransformStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#child_frame_id: msg.r#child_frame_id,
+            r#transform: ::std::convert::From::from(msg.r#transform),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::TransformStamped>
+    for crate::ros1::geometry_msgs::TransformStamped
+{
+    fn from(msg: crate::ros2::geometry_msgs::TransformStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#child_frame_id: msg.r#child_frame_id,
+            r#transform: ::std::convert::From::from(msg.r#transform),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::Twist> for crate::ros2::geometry_msgs::Twist {
+    fn from(msg: crate::ros1::geometry_msgs::Twist) -> Self {
+        Self {
+            r#linear: ::std::convert::From::from(msg.r#linear),
+            r#angular: ::std::convert::From::from(msg.r#angular),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::Twist> for crate::ros1::geometry_msgs::Twist {
+    fn from(msg: crate::ros2::geometry_msgs::Twist) -> Self {
+        Self {
+            r#linear: ::std::convert::From::from(msg.r#linear),
+            r#angular: ::std::convert::From::from(msg.r#angular),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::TwistStamped>
+    for crate::ros2::geometry_msgs::TwistStamped
+{
+    fn from(msg: crate::ros1::geometry_msgs::TwistStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#twist: ::std::convert::From::from(msg.r#twist),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::TwistStamped>
+    for crate::ros1::geometry_msgs::TwistStamped
+{
+    fn from(msg: crate::ros2::geometry_msgs::TwistStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#twist: ::std::convert::From::from(msg.r#twist),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::TwistWithCovariance>
+    for crate::ros2::geometry_msgs::TwistWithCovariance
+{
+    fn from(msg: crate::ros1::geometry_msgs::TwistWithCovariance) -> Self {
+        Self {
+            r#twist: ::std::convert::From::from(msg.r#twist),
+            r#covariance: msg.r#covariance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::TwistWithCovariance>
+    for crate::ros1::geometry_msgs::TwistWithCovariance
+{
+    fn from(msg: crate::ros2::geometry_msgs::TwistWithCovariance) -> Self {
+        Self {
+            r#twist: ::std::convert::From::from(msg.r#twist),
+            r#covariance: msg.r#covariance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::TwistWithCovarianceStamped>
+    for crate::ros2::geometry_msgs::TwistWithCovarianceStamped
+{
+    fn from(msg: crate::ros1::geometry_msgs::TwistWithCovarianceStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#twist: ::std::convert::From::from(msg.r#twist),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::TwistWithCovarianceStamped>
+    for crate::ros1::geometry_msgs::TwistWithCovarianceStamped
+{
+    fn from(msg: crate::ros2::geometry_msgs::TwistWithCovarianceStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#twist: ::std::convert::From::from(msg.r#twist),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::Vector3>
+    for crate::ros2::geometry_msgs::Vector3
+{
+    fn from(msg: crate::ros1::geometry_msgs::Vector3) -> Self {
+        Self {
+            r#x: msg.r#x,
+            r#y: msg.r#y,
+            r#z: msg.r#z,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::Vector3>
+    for crate::ros1::geometry_msgs::Vector3
+{
+    fn from(msg: crate::ros2::geometry_msgs::Vector3) -> Self {
+        Self {
+            r#x: msg.r#x,
+            r#y: msg.r#y,
+            r#z: msg.r#z,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::Vector3Stamped>
+    for crate::ros2::geometry_msgs::Vector3Stamped
+{
+    fn from(msg: crate::ros1::geometry_msgs::Vector3Stamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#vector: ::std::convert::From::from(msg.r#vector),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::Vector3Stamped>
+    for crate::ros1::geometry_msgs::Vector3Stamped
+{
+    fn from(msg: crate::ros2::geometry_msgs::Vector3Stamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#vector: ::std::convert::From::from(msg.r#vector),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::Wrench>
+    for crate::ros2::geometry_msgs::Wrench
+{
+    fn from(msg: crate::ros1::geometry_msgs::Wrench) -> Self {
+        Self {
+            r#force: ::std::convert::From::from(msg.r#force),
+            r#torque: ::std::convert::From::from(msg.r#torque),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::Wrench>
+    for crate::ros1::geometry_msgs::Wrench
+{
+    fn from(msg: crate::ros2::geometry_msgs::Wrench) -> Self {
+        Self {
+            r#force: ::std::convert::From::from(msg.r#force),
+            r#torque: ::std::convert::From::from(msg.r#torque),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::geometry_msgs::WrenchStamped>
+    for crate::ros2::geometry_msgs::WrenchStamped
+{
+    fn from(msg: crate::ros1::geometry_msgs::WrenchStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#wrench: ::std::convert::From::from(msg.r#wrench),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::geometry_msgs::WrenchStamped>
+    for crate::ros1::geometry_msgs::WrenchStamped
+{
+    fn from(msg: crate::ros2::geometry_msgs::WrenchStamped) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#wrench: ::std::convert::From::from(msg.r#wrench),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::nav_msgs::GridCells> for crate::ros2::nav_msgs::GridCells {
+    fn from(msg: crate::ros1::nav_msgs::GridCells) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#cell_width: msg.r#cell_width,
+            r#cell_height: msg.r#cell_height,
+            r#cells: msg
+                .r#cells
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::nav_msgs::GridCells> for crate::ros1::nav_msgs::GridCells {
+    fn from(msg: crate::ros2::nav_msgs::GridCells) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#cell_width: msg.r#cell_width,
+            r#cell_height: msg.r#cell_height,
+            r#cells: msg
+                .r#cells
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::nav_msgs::MapMetaData>
+    for crate::ros2::nav_msgs::MapMetaData
+{
+    fn from(msg: crate::ros1::nav_msgs::MapMetaData) -> Self {
+        Self {
+            r#map_load_time: {
+                let (sec, nanosec) = msg.r#map_load_time.to_sec_nanosec();
+                crate::ros2::builtin_interfaces::Time { sec, nanosec }
+            },
+            r#resolution: msg.r#resolution,
+            r#width: msg.r#width,
+            r#height: msg.r#height,
+            r#origin: ::std::convert::From::from(msg.r#origin),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::nav_msgs::MapMetaData>
+    for crate::ros1::nav_msgs::MapMetaData
+{
+    fn from(msg: crate::ros2::nav_msgs::MapMetaData) -> Self {
+        Self {
+            r#map_load_time: ::roslibrust::codegen::integral_types::Time::from_sec_nanosec(
+                msg.r#map_load_time.r#sec,
+                msg.r#map_load_time.r#nanosec,
+            ),
+            r#resolution: msg.r#resolution,
+            r#width: msg.r#width,
+            r#height: msg.r#height,
+            r#origin: ::std::convert::From::from(msg.r#origin),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::nav_msgs::OccupancyGrid>
+    for crate::ros2::nav_msgs::OccupancyGrid
+{
+    fn from(msg: crate::ros1::nav_msgs::OccupancyGrid) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#info: ::std::convert::From::from(msg.r#info),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::nav_msgs::OccupancyGrid>
+    for crate::ros1::nav_msgs::OccupancyGrid
+{
+    fn from(msg: crate::ros2::nav_msgs::OccupancyGrid) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#info: ::std::convert::From::from(msg.r#info),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::nav_msgs::Odometry> for crate::ros2::nav_msgs::Odometry {
+    fn from(msg: crate::ros1::nav_msgs::Odometry) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#child_frame_id: msg.r#child_frame_id,
+            r#pose: ::std::convert::From::from(msg.r#pose),
+            r#twist: ::std::convert::From::from(msg.r#twist),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::nav_msgs::Odometry> for crate::ros1::nav_msgs::Odometry {
+    fn from(msg: crate::ros2::nav_msgs::Odometry) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#child_frame_id: msg.r#child_frame_id,
+            r#pose: ::std::convert::From::from(msg.r#pose),
+            r#twist: ::std::convert::From::from(msg.r#twist),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::nav_msgs::Path> for crate::ros2::nav_msgs::Path {
+    fn from(msg: crate::ros1::nav_msgs::Path) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#poses: msg
+                .r#poses
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::nav_msgs::Path> for crate::ros1::nav_msgs::Path {
+    fn from(msg: crate::ros2::nav_msgs::Path) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#poses: msg
+                .r#poses
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::BatteryState>
+    for crate::ros2::sensor_msgs::BatteryState
+{
+    fn from(msg: crate::ros1::sensor_msgs::BatteryState) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#voltage: msg.r#voltage,
+            r#temperature: msg.r#temperature,
+            r#current: msg.r#current,
+            r#charge: msg.r#charge,
+            r#capacity: msg.r#capacity,
+            r#design_capacity: msg.r#design_capacity,
+            r#percentage: msg.r#percentage,
+            r#power_supply_status: msg.r#power_supply_status,
+            r#power_supply_health: msg.r#power_supply_health,
+            r#power_supply_technology: msg.r#power_supply_technology,
+            r#present: msg.r#present,
+            r#cell_voltage: msg.r#cell_voltage,
+            r#cell_temperature: msg.r#cell_temperature,
+            r#location: msg.r#location,
+            r#serial_number: msg.r#serial_number,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::BatteryState>
+    for crate::ros1::sensor_msgs::BatteryState
+{
+    fn from(msg: crate::ros2::sensor_msgs::BatteryState) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#voltage: msg.r#voltage,
+            r#temperature: msg.r#temperature,
+            r#current: msg.r#current,
+            r#charge: msg.r#charge,
+            r#capacity: msg.r#capacity,
+            r#design_capacity: msg.r#design_capacity,
+            r#percentage: msg.r#percentage,
+            r#power_supply_status: msg.r#power_supply_status,
+            r#power_supply_health: msg.r#power_supply_health,
+            r#power_supply_technology: msg.r#power_supply_technology,
+            r#present: msg.r#present,
+            r#cell_voltage: msg.r#cell_voltage,
+            r#cell_temperature: msg.r#cell_temperature,
+            r#location: msg.r#location,
+            r#serial_number: msg.r#serial_number,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::CameraInfo>
+    for crate::ros2::sensor_msgs::CameraInfo
+{
+    fn from(msg: crate::ros1::sensor_msgs::CameraInfo) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#height: msg.r#height,
+            r#width: msg.r#width,
+            r#distortion_model: msg.r#distortion_model,
+            r#binning_x: msg.r#binning_x,
+            r#binning_y: msg.r#binning_y,
+            r#roi: ::std::convert::From::from(msg.r#roi),
+            ..::std::default::Default::default()
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::CameraInfo>
+    for crate::ros1::sensor_msgs::CameraInfo
+{
+    fn from(msg: crate::ros2::sensor_msgs::CameraInfo) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#height: msg.r#height,
+            r#width: msg.r#width,
+            r#distortion_model: msg.r#distortion_model,
+            r#binning_x: msg.r#binning_x,
+            r#binning_y: msg.r#binning_y,
+            r#roi: ::std::convert::From::from(msg.r#roi),
+            ..::std::default::Default::default()
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::ChannelFloat32>
+    for crate::ros2::sensor_msgs::ChannelFloat32
+{
+    fn from(msg: crate::ros1::sensor_msgs::ChannelFloat32) -> Self {
+        Self {
+            r#name: msg.r#name,
+            r#values: msg.r#values,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::ChannelFloat32>
+    for crate::ros1::sensor_msgs::ChannelFloat32
+{
+    fn from(msg: crate::ros2::sensor_msgs::ChannelFloat32) -> Self {
+        Self {
+            r#name: msg.r#name,
+            r#values: msg.r#values,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::CompressedImage>
+    for crate::ros2::sensor_msgs::CompressedImage
+{
+    fn from(msg: crate::ros1::sensor_msgs::CompressedImage) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#format: msg.r#format,
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::CompressedImage>
+    for crate::ros1::sensor_msgs::CompressedImage
+{
+    fn from(msg: crate::ros2::sensor_msgs::CompressedImage) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#format: msg.r#format,
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::FluidPressure>
+    for crate::ros2::sensor_msgs::FluidPressure
+{
+    fn from(msg: crate::ros1::sensor_msgs::FluidPressure) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#fluid_pressure: msg.r#fluid_pressure,
+            r#variance: msg.r#variance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::FluidPressure>
+    for crate::ros1::sensor_msgs::FluidPressure
+{
+    fn from(msg: crate::ros2::sensor_msgs::FluidPressure) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#fluid_pressure: msg.r#fluid_pressure,
+            r#variance: msg.r#variance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::Illuminance>
+    for crate::ros2::sensor_msgs::Illuminance
+{
+    fn from(msg: crate::ros1::sensor_msgs::Illuminance) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#illuminance: msg.r#illuminance,
+            r#variance: msg.r#variance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::Illuminance>
+    for crate::ros1::sensor_msgs::Illuminance
+{
+    fn from(msg: crate::ros2::sensor_msgs::Illuminance) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#illuminance: msg.r#illuminance,
+            r#variance: msg.r#variance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::Image> for crate::ros2::sensor_msgs::Image {
+    fn from(msg: crate::ros1::sensor_msgs::Image) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#height: msg.r#height,
+            r#width: msg.r#width,
+            r#encoding: msg.r#encoding,
+            r#is_bigendian: msg.r#is_bigendian,
+            r#step: msg.r#step,
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::Image> for crate::ros1::sensor_msgs::Image {
+    fn from(msg: crate::ros2::sensor_msgs::Image) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#height: msg.r#height,
+            r#width: msg.r#width,
+            r#encoding: msg.r#encoding,
+            r#is_bigendian: msg.r#is_bigendian,
+            r#step: msg.r#step,
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::Imu> for crate::ros2::sensor_msgs::Imu {
+    fn from(msg: crate::ros1::sensor_msgs::Imu) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#orientation: ::std::convert::From::from(msg.r#orientation),
+            r#orientation_covariance: msg.r#orientation_covariance,
+            r#angular_velocity: ::std::convert::From::from(msg.r#angular_velocity),
+            r#angular_velocity_covariance: msg.r#angular_velocity_covariance,
+            r#linear_acceleration: ::std::convert::From::from(msg.r#linear_acceleration),
+            r#linear_acceleration_covariance: msg.r#linear_acceleration_covariance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::Imu> for crate::ros1::sensor_msgs::Imu {
+    fn from(msg: crate::ros2::sensor_msgs::Imu) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#orientation: ::std::convert::From::from(msg.r#orientation),
+            r#orientation_covariance: msg.r#orientation_covariance,
+            r#angular_velocity: ::std::convert::From::from(msg.r#angular_velocity),
+            r#angular_velocity_covariance: msg.r#angular_velocity_covariance,
+            r#linear_acceleration: ::std::convert::From::from(msg.r#linear_acceleration),
+            r#linear_acceleration_covariance: msg.r#linear_acceleration_covariance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::JointState>
+    for crate::ros2::sensor_msgs::JointState
+{
+    fn from(msg: crate::ros1::sensor_msgs::JointState) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#name: msg.r#name,
+            r#position: msg.r#position,
+            r#velocity: msg.r#velocity,
+            r#effort: msg.r#effort,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::JointState>
+    for crate::ros1::sensor_msgs::JointState
+{
+    fn from(msg: crate::ros2::sensor_msgs::JointState) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#name: msg.r#name,
+            r#position: msg.r#position,
+            r#velocity: msg.r#velocity,
+            r#effort: msg.r#effort,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::Joy> for crate::ros2::sensor_msgs::Joy {
+    fn from(msg: crate::ros1::sensor_msgs::Joy) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#axes: msg.r#axes,
+            r#buttons: msg.r#buttons,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::Joy> for crate::ros1::sensor_msgs::Joy {
+    fn from(msg: crate::ros2::sensor_msgs::Joy) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#axes: msg.r#axes,
+            r#buttons: msg.r#buttons,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::JoyFeedback>
+    for crate::ros2::sensor_msgs::JoyFeedback
+{
+    fn from(msg: crate::ros1::sensor_msgs::JoyFeedback) -> Self {
+        Self {
+            r#type: msg.r#type,
+            r#id: msg.r#id,
+            r#intensity: msg.r#intensity,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::JoyFeedback>
+    for crate::ros1::sensor_msgs::JoyFeedback
+{
+    fn from(msg: crate::ros2::sensor_msgs::JoyFeedback) -> Self {
+        Self {
+            r#type: msg.r#type,
+            r#id: msg.r#id,
+            r#intensity: msg.r#intensity,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::JoyFeedbackArray>
+    for crate::ros2::sensor_msgs::JoyFeedbackArray
+{
+    fn from(msg: crate::ros1::sensor_msgs::JoyFeedbackArray) -> Self {
+        Self {
+            r#array: msg
+                .r#array
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::JoyFeedbackArray>
+    for crate::ros1::sensor_msgs::JoyFeedbackArray
+{
+    fn from(msg: crate::ros2::sensor_msgs::JoyFeedbackArray) -> Self {
+        Self {
+            r#array: msg
+                .r#array
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::LaserEcho>
+    for crate::ros2::sensor_msgs::LaserEcho
+{
+    fn from(msg: crate::ros1::sensor_msgs::LaserEcho) -> Self {
+        Self {
+            r#echoes: msg.r#echoes,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::LaserEcho>
+    for crate::ros1::sensor_msgs::LaserEcho
+{
+    fn from(msg: crate::ros2::sensor_msgs::LaserEcho) -> Self {
+        Self {
+            r#echoes: msg.r#echoes,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::LaserScan>
+    for crate::ros2::sensor_msgs::LaserScan
+{
+    fn from(msg: crate::ros1::sensor_msgs::LaserScan) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#angle_min: msg.r#angle_min,
+            r#angle_max: msg.r#angle_max,
+            r#angle_increment: msg.r#angle_increment,
+            r#time_increment: msg.r#time_increment,
+            r#scan_time: msg.r#scan_time,
+            r#range_min: msg.r#range_min,
+            r#range_max: msg.r#range_max,
+            r#ranges: msg.r#ranges,
+            r#intensities: msg.r#intensities,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::LaserScan>
+    for crate::ros1::sensor_msgs::LaserScan
+{
+    fn from(msg: crate::ros2::sensor_msgs::LaserScan) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#angle_min: msg.r#angle_min,
+            r#angle_max: msg.r#angle_max,
+            r#angle_increment: msg.r#angle_increment,
+            r#time_increment: msg.r#time_increment,
+            r#scan_time: msg.r#scan_time,
+            r#range_min: msg.r#range_min,
+            r#range_max: msg.r#range_max,
+            r#ranges: msg.r#ranges,
+            r#intensities: msg.r#intensities,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::MagneticField>
+    for crate::ros2::sensor_msgs::MagneticField
+{
+    fn from(msg: crate::ros1::sensor_msgs::MagneticField) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#magnetic_field: ::std::convert::From::from(msg.r#magnetic_field),
+            r#magnetic_field_covariance: msg.r#magnetic_field_covariance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::MagneticField>
+    for crate::ros1::sensor_msgs::MagneticField
+{
+    fn from(msg: crate::ros2::sensor_msgs::MagneticField) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#magnetic_field: ::std::convert::From::from(msg.r#magnetic_field),
+            r#magnetic_field_covariance: msg.r#magnetic_field_covariance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::MultiDOFJointState>
+    for crate::ros2::sensor_msgs::MultiDOFJointState
+{
+    fn from(msg: crate::ros1::sensor_msgs::MultiDOFJointState) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#joint_names: msg.r#joint_names,
+            r#transforms: msg
+                .r#transforms
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#twist: msg
+                .r#twist
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#wrench: msg
+                .r#wrench
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::MultiDOFJointState>
+    for crate::ros1::sensor_msgs::MultiDOFJointState
+{
+    fn from(msg: crate::ros2::sensor_msgs::MultiDOFJointState) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#joint_names: msg.r#joint_names,
+            r#transforms: msg
+                .r#transforms
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#twist: msg
+                .r#twist
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#wrench: msg
+                .r#wrench
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::MultiEchoLaserScan>
+    for crate::ros2::sensor_msgs::MultiEchoLaserScan
+{
+    fn from(msg: crate::ros1::sensor_msgs::MultiEchoLaserScan) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#angle_min: msg.r#angle_min,
+            r#angle_max: msg.r#angle_max,
+            r#angle_increment: msg.r#angle_increment,
+            r#time_increment: msg.r#time_increment,
+            r#scan_time: msg.r#scan_time,
+            r#range_min: msg.r#range_min,
+            r#range_max: msg.r#range_max,
+            r#ranges: msg
+                .r#ranges
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#intensities: msg
+                .r#intensities
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::MultiEchoLaserScan>
+    for crate::ros1::sensor_msgs::MultiEchoLaserScan
+{
+    fn from(msg: crate::ros2::sensor_msgs::MultiEchoLaserScan) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#angle_min: msg.r#angle_min,
+            r#angle_max: msg.r#angle_max,
+            r#angle_increment: msg.r#angle_increment,
+            r#time_increment: msg.r#time_increment,
+            r#scan_time: msg.r#scan_time,
+            r#range_min: msg.r#range_min,
+            r#range_max: msg.r#range_max,
+            r#ranges: msg
+                .r#ranges
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#intensities: msg
+                .r#intensities
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::NavSatFix>
+    for crate::ros2::sensor_msgs::NavSatFix
+{
+    fn from(msg: crate::ros1::sensor_msgs::NavSatFix) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#status: ::std::convert::From::from(msg.r#status),
+            r#latitude: msg.r#latitude,
+            r#longitude: msg.r#longitude,
+            r#altitude: msg.r#altitude,
+            r#position_covariance: msg.r#position_covariance,
+            r#position_covariance_type: msg.r#position_covariance_type,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::NavSatFix>
+    for crate::ros1::sensor_msgs::NavSatFix
+{
+    fn from(msg: crate::ros2::sensor_msgs::NavSatFix) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#status: ::std::convert::From::from(msg.r#status),
+            r#latitude: msg.r#latitude,
+            r#longitude: msg.r#longitude,
+            r#altitude: msg.r#altitude,
+            r#position_covariance: msg.r#position_covariance,
+            r#position_covariance_type: msg.r#position_covariance_type,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::NavSatStatus>
+    for crate::ros2::sensor_msgs::NavSatStatus
+{
+    fn from(msg: crate::ros1::sensor_msgs::NavSatStatus) -> Self {
+        Self {
+            r#status: msg.r#status,
+            r#service: msg.r#service,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::NavSatStatus>
+    for crate::ros1::sensor_msgs::NavSatStatus
+{
+    fn from(msg: crate::ros2::sensor_msgs::NavSatStatus) -> Self {
+        Self {
+            r#status: msg.r#status,
+            r#service: msg.r#service,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::PointCloud>
+    for crate::ros2::sensor_msgs::PointCloud
+{
+    fn from(msg: crate::ros1::sensor_msgs::PointCloud) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#points: msg
+                .r#points
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#channels: msg
+                .r#channels
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::PointCloud>
+    for crate::ros1::sensor_msgs::PointCloud
+{
+    fn from(msg: crate::ros2::sensor_msgs::PointCloud) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#points: msg
+                .r#points
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#channels: msg
+                .r#channels
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::PointCloud2>
+    for crate::ros2::sensor_msgs::PointCloud2
+{
+    fn from(msg: crate::ros1::sensor_msgs::PointCloud2) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#height: msg.r#height,
+            r#width: msg.r#width,
+            r#fields: msg
+                .r#fields
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#is_bigendian: msg.r#is_bigendian,
+            r#point_step: msg.r#point_step,
+            r#row_step: msg.r#row_step,
+            r#data: msg.r#data,
+            r#is_dense: msg.r#is_dense,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::PointCloud2>
+    for crate::ros1::sensor_msgs::PointCloud2
+{
+    fn from(msg: crate::ros2::sensor_msgs::PointCloud2) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#height: msg.r#height,
+            r#width: msg.r#width,
+            r#fields: msg
+                .r#fields
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#is_bigendian: msg.r#is_bigendian,
+            r#point_step: msg.r#point_step,
+            r#row_step: msg.r#row_step,
+            r#data: msg.r#data,
+            r#is_dense: msg.r#is_dense,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::PointField>
+    for crate::ros2::sensor_msgs::PointField
+{
+    fn from(msg: crate::ros1::sensor_msgs::PointField) -> Self {
+        Self {
+            r#name: msg.r#name,
+            r#offset: msg.r#offset,
+            r#datatype: msg.r#datatype,
+            r#count: msg.r#count,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::PointField>
+    for crate::ros1::sensor_msgs::PointField
+{
+    fn from(msg: crate::ros2::sensor_msgs::PointField) -> Self {
+        Self {
+            r#name: msg.r#name,
+            r#offset: msg.r#offset,
+            r#datatype: msg.r#datatype,
+            r#count: msg.r#count,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::Range> for crate::ros2::sensor_msgs::Range {
+    fn from(msg: crate::ros1::sensor_msgs::Range) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#radiation_type: msg.r#radiation_type,
+            r#field_of_view: msg.r#field_of_view,
+            r#min_range: msg.r#min_range,
+            r#max_range: msg.r#max_range,
+            r#range: msg.r#range,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::Range> for crate::ros1::sensor_msgs::Range {
+    fn from(msg: crate::ros2::sensor_msgs::Range) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#radiation_type: msg.r#radiation_type,
+            r#field_of_view: msg.r#field_of_view,
+            r#min_range: msg.r#min_range,
+            r#max_range: msg.r#max_range,
+            r#range: msg.r#range,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::RegionOfInterest>
+    for crate::ros2::sensor_msgs::RegionOfInterest
+{
+    fn from(msg: crate::ros1::sensor_msgs::RegionOfInterest) -> Self {
+        Self {
+            r#x_offset: msg.r#x_offset,
+            r#y_offset: msg.r#y_offset,
+            r#height: msg.r#height,
+            r#width: msg.r#width,
+            r#do_rectify: msg.r#do_rectify,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::RegionOfInterest>
+    for crate::ros1::sensor_msgs::RegionOfInterest
+{
+    fn from(msg: crate::ros2::sensor_msgs::RegionOfInterest) -> Self {
+        Self {
+            r#x_offset: msg.r#x_offset,
+            r#y_offset: msg.r#y_offset,
+            r#height: msg.r#height,
+            r#width: msg.r#width,
+            r#do_rectify: msg.r#do_rectify,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::RelativeHumidity>
+    for crate::ros2::sensor_msgs::RelativeHumidity
+{
+    fn from(msg: crate::ros1::sensor_msgs::RelativeHumidity) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#relative_humidity: msg.r#relative_humidity,
+            r#variance: msg.r#variance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::RelativeHumidity>
+    for crate::ros1::sensor_msgs::RelativeHumidity
+{
+    fn from(msg: crate::ros2::sensor_msgs::RelativeHumidity) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#relative_humidity: msg.r#relative_humidity,
+            r#variance: msg.r#variance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::Temperature>
+    for crate::ros2::sensor_msgs::Temperature
+{
+    fn from(msg: crate::ros1::sensor_msgs::Temperature) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#temperature: msg.r#temperature,
+            r#variance: msg.r#variance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::Temperature>
+    for crate::ros1::sensor_msgs::Temperature
+{
+    fn from(msg: crate::ros2::sensor_msgs::Temperature) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#temperature: msg.r#temperature,
+            r#variance: msg.r#variance,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::sensor_msgs::TimeReference>
+    for crate::ros2::sensor_msgs::TimeReference
+{
+    fn from(msg: crate::ros1::sensor_msgs::TimeReference) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#time_ref: {
+                let (sec, nanosec) = msg.r#time_ref.to_sec_nanosec();
+                crate::ros2::builtin_interfaces::Time { sec, nanosec }
+            },
+            r#source: msg.r#source,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::sensor_msgs::TimeReference>
+    for crate::ros1::sensor_msgs::TimeReference
+{
+    fn from(msg: crate::ros2::sensor_msgs::TimeReference) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#time_ref: ::roslibrust::codegen::integral_types::Time::from_sec_nanosec(
+                msg.r#time_ref.r#sec,
+                msg.r#time_ref.r#nanosec,
+            ),
+            r#source: msg.r#source,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::service_msgs::ServiceEventInfo>
+    for crate::ros2::service_msgs::ServiceEventInfo
+{
+    fn from(msg: crate::ros1::service_msgs::ServiceEventInfo) -> Self {
+        Self {
+            r#event_type: msg.r#event_type,
+            r#client_gid: msg.r#client_gid,
+            r#sequence_number: msg.r#sequence_number,
+            ..::std::default::Default::default()
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::service_msgs::ServiceEventInfo>
+    for crate::ros1::service_msgs::ServiceEventInfo
+{
+    fn from(msg: crate::ros2::service_msgs::ServiceEventInfo) -> Self {
+        Self {
+            r#event_type: msg.r#event_type,
+            r#client_gid: msg.r#client_gid,
+            r#sequence_number: msg.r#sequence_number,
+            ..::std::default::Default::default()
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::shape_msgs::Mesh> for crate::ros2::shape_msgs::Mesh {
+    fn from(msg: crate::ros1::shape_msgs::Mesh) -> Self {
+        Self {
+            r#triangles: msg
+                .r#triangles
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#vertices: msg
+                .r#vertices
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::shape_msgs::Mesh> for crate::ros1::shape_msgs::Mesh {
+    fn from(msg: crate::ros2::shape_msgs::Mesh) -> Self {
+        Self {
+            r#triangles: msg
+                .r#triangles
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#vertices: msg
+                .r#vertices
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::shape_msgs::MeshTriangle>
+    for crate::ros2::shape_msgs::MeshTriangle
+{
+    fn from(msg: crate::ros1::shape_msgs::MeshTriangle) -> Self {
+        Self {
+            r#vertex_indices: msg.r#vertex_indices,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::shape_msgs::MeshTriangle>
+    for crate::ros1::shape_msgs::MeshTriangle
+{
+    fn from(msg: crate::ros2::shape_msgs::MeshTriangle) -> Self {
+        Self {
+            r#vertex_indices: msg.r#vertex_indices,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::shape_msgs::Plane> for crate::ros2::shape_msgs::Plane {
+    fn from(msg: crate::ros1::shape_msgs::Plane) -> Self {
+        Self { r#coef: msg.r#coef }
+    }
+}
+impl ::std::convert::From<crate::ros2::shape_msgs::Plane> for crate::ros1::shape_msgs::Plane {
+    fn from(msg: crate::ros2::shape_msgs::Plane) -> Self {
+        Self { r#coef: msg.r#coef }
+    }
+}
+impl ::std::convert::From<crate::ros1::shape_msgs::SolidPrimitive>
+    for crate::ros2::shape_msgs::SolidPrimitive
+{
+    fn from(msg: crate::ros1::shape_msgs::SolidPrimitive) -> Self {
+        Self {
+            r#type: msg.r#type,
+            r#dimensions: msg.r#dimensions,
+            ..::std::default::Default::default()
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::shape_msgs::SolidPrimitive>
+    for crate::ros1::shape_msgs::SolidPrimitive
+{
+    fn from(msg: crate::ros2::shape_msgs::SolidPrimitive) -> Self {
+        Self {
+            r#type: msg.r#type,
+            r#dimensions: msg.r#dimensions,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Bool> for crate::ros2::std_msgs::Bool {
+    fn from(msg: crate::ros1::std_msgs::Bool) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Bool> for crate::ros1::std_msgs::Bool {
+    fn from(msg: crate::ros2::std_msgs::Bool) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Byte> for crate::ros2::std_msgs::Byte {
+    fn from(msg: crate::ros1::std_msgs::Byte) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Byte> for crate::ros1::std_msgs::Byte {
+    fn from(msg: crate::ros2::std_msgs::Byte) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::ByteMultiArray>
+    for crate::ros2::std_msgs::ByteMultiArray
+{
+    fn from(msg: crate::ros1::std_msgs::ByteMultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::ByteMultiArray>
+    for crate::ros1::std_msgs::ByteMultiArray
+{
+    fn from(msg: crate::ros2::std_msgs::ByteMultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Char> for crate::ros2::std_msgs::Char {
+    fn from(msg: crate::ros1::std_msgs::Char) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Char> for crate::ros1::std_msgs::Char {
+    fn from(msg: crate::ros2::std_msgs::Char) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::ColorRGBA> for crate::ros2::std_msgs::ColorRGBA {
+    fn from(msg: crate::ros1::std_msgs::ColorRGBA) -> Self {
+        Self {
+            r#r: msg.r#r,
+            r#g: msg.r#g,
+            r#b: msg.r#b,
+            r#a: msg.r#a,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::ColorRGBA> for crate::ros1::std_msgs::ColorRGBA {
+    fn from(msg: crate::ros2::std_msgs::ColorRGBA) -> Self {
+        Self {
+            r#r: msg.r#r,
+            r#g: msg.r#g,
+            r#b: msg.r#b,
+            r#a: msg.r#a,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Empty> for crate::ros2::std_msgs::Empty {
+    fn from(_msg: crate::ros1::std_msgs::Empty) -> Self {
+        Self {}
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Empty> for crate::ros1::std_msgs::Empty {
+    fn from(_msg: crate::ros2::std_msgs::Empty) -> Self {
+        Self {}
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Float32> for crate::ros2::std_msgs::Float32 {
+    fn from(msg: crate::ros1::std_msgs::Float32) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Float32> for crate::ros1::std_msgs::Float32 {
+    fn from(msg: crate::ros2::std_msgs::Float32) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Float32MultiArray>
+    for crate::ros2::std_msgs::Float32MultiArray
+{
+    fn from(msg: crate::ros1::std_msgs::Float32MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Float32MultiArray>
+    for crate::ros1::std_msgs::Float32MultiArray
+{
+    fn from(msg: crate::ros2::std_msgs::Float32MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Float64> for crate::ros2::std_msgs::Float64 {
+    fn from(msg: crate::ros1::std_msgs::Float64) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Float64> for crate::ros1::std_msgs::Float64 {
+    fn from(msg: crate::ros2::std_msgs::Float64) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Float64MultiArray>
+    for crate::ros2::std_msgs::Float64MultiArray
+{
+    fn from(msg: crate::ros1::std_msgs::Float64MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Float64MultiArray>
+    for crate::ros1::std_msgs::Float64MultiArray
+{
+    fn from(msg: crate::ros2::std_msgs::Float64MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Header> for crate::ros2::std_msgs::Header {
+    fn from(msg: crate::ros1::std_msgs::Header) -> Self {
+        Self {
+            r#stamp: {
+                let (sec, nanosec) = msg.r#stamp.to_sec_nanosec();
+                crate::ros2::builtin_interfaces::Time { sec, nanosec }
+            },
+            r#frame_id: msg.r#frame_id,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Header> for crate::ros1::std_msgs::Header {
+    fn from(msg: crate::ros2::std_msgs::Header) -> Self {
+        Self {
+            r#stamp: ::roslibrust::codegen::integral_types::Time::from_sec_nanosec(
+                msg.r#stamp.r#sec,
+                msg.r#stamp.r#nanosec,
+            ),
+            r#frame_id: msg.r#frame_id,
+            ..::std::default::Default::default()
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Int16> for crate::ros2::std_msgs::Int16 {
+    fn from(msg: crate::ros1::std_msgs::Int16) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Int16> for crate::ros1::std_msgs::Int16 {
+    fn from(msg: crate::ros2::std_msgs::Int16) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Int16MultiArray>
+    for crate::ros2::std_msgs::Int16MultiArray
+{
+    fn from(msg: crate::ros1::std_msgs::Int16MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Int16MultiArray>
+    for crate::ros1::std_msgs::Int16MultiArray
+{
+    fn from(msg: crate::ros2::std_msgs::Int16MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Int32> for crate::ros2::std_msgs::Int32 {
+    fn from(msg: crate::ros1::std_msgs::Int32) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Int32> for crate::ros1::std_msgs::Int32 {
+    fn from(msg: crate::ros2::std_msgs::Int32) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Int32MultiArray>
+    for crate::ros2::std_msgs::Int32MultiArray
+{
+    fn from(msg: crate::ros1::std_msgs::Int32MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Int32MultiArray>
+    for crate::ros1::std_msgs::Int32MultiArray
+{
+    fn from(msg: crate::ros2::std_msgs::Int32MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Int64> for crate::ros2::std_msgs::Int64 {
+    fn from(msg: crate::ros1::std_msgs::Int64) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Int64> for crate::ros1::std_msgs::Int64 {
+    fn from(msg: crate::ros2::std_msgs::Int64) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Int64MultiArray>
+    for crate::ros2::std_msgs::Int64MultiArray
+{
+    fn from(msg: crate::ros1::std_msgs::Int64MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Int64MultiArray>
+    for crate::ros1::std_msgs::Int64MultiArray
+{
+    fn from(msg: crate::ros2::std_msgs::Int64MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Int8> for crate::ros2::std_msgs::Int8 {
+    fn from(msg: crate::ros1::std_msgs::Int8) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Int8> for crate::ros1::std_msgs::Int8 {
+    fn from(msg: crate::ros2::std_msgs::Int8) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::Int8MultiArray>
+    for crate::ros2::std_msgs::Int8MultiArray
+{
+    fn from(msg: crate::ros1::std_msgs::Int8MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::Int8MultiArray>
+    for crate::ros1::std_msgs::Int8MultiArray
+{
+    fn from(msg: crate::ros2::std_msgs::Int8MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::MultiArrayDimension>
+    for crate::ros2::std_msgs::MultiArrayDimension
+{
+    fn from(msg: crate::ros1::std_msgs::MultiArrayDimension) -> Self {
+        Self {
+            r#label: msg.r#label,
+            r#size: msg.r#size,
+            r#stride: msg.r#stride,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::MultiArrayDimension>
+    for crate::ros1::std_msgs::MultiArrayDimension
+{
+    fn from(msg: crate::ros2::std_msgs::MultiArrayDimension) -> Self {
+        Self {
+            r#label: msg.r#label,
+            r#size: msg.r#size,
+            r#stride: msg.r#stride,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::MultiArrayLayout>
+    for crate::ros2::std_msgs::MultiArrayLayout
+{
+    fn from(msg: crate::ros1::std_msgs::MultiArrayLayout) -> Self {
+        Self {
+            r#dim: msg
+                .r#dim
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#data_offset: msg.r#data_offset,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::MultiArrayLayout>
+    for crate::ros1::std_msgs::MultiArrayLayout
+{
+    fn from(msg: crate::ros2::std_msgs::MultiArrayLayout) -> Self {
+        Self {
+            r#dim: msg
+                .r#dim
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#data_offset: msg.r#data_offset,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::String> for crate::ros2::std_msgs::String {
+    fn from(msg: crate::ros1::std_msgs::String) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::String> for crate::ros1::std_msgs::String {
+    fn from(msg: crate::ros2::std_msgs::String) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::UInt16> for crate::ros2::std_msgs::UInt16 {
+    fn from(msg: crate::ros1::std_msgs::UInt16) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::UInt16> for crate::ros1::std_msgs::UInt16 {
+    fn from(msg: crate::ros2::std_msgs::UInt16) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::UInt16MultiArray>
+    for crate::ros2::std_msgs::UInt16MultiArray
+{
+    fn from(msg: crate::ros1::std_msgs::UInt16MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::UInt16MultiArray>
+    for crate::ros1::std_msgs::UInt16MultiArray
+{
+    fn from(msg: crate::ros2::std_msgs::UInt16MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::UInt32> for crate::ros2::std_msgs::UInt32 {
+    fn from(msg: crate::ros1::std_msgs::UInt32) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::UInt32> for crate::ros1::std_msgs::UInt32 {
+    fn from(msg: crate::ros2::std_msgs::UInt32) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::UInt32MultiArray>
+    for crate::ros2::std_msgs::UInt32MultiArray
+{
+    fn from(msg: crate::ros1::std_msgs::UInt32MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::UInt32MultiArray>
+    for crate::ros1::std_msgs::UInt32MultiArray
+{
+    fn from(msg: crate::ros2::std_msgs::UInt32MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::UInt64> for crate::ros2::std_msgs::UInt64 {
+    fn from(msg: crate::ros1::std_msgs::UInt64) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::UInt64> for crate::ros1::std_msgs::UInt64 {
+    fn from(msg: crate::ros2::std_msgs::UInt64) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::UInt64MultiArray>
+    for crate::ros2::std_msgs::UInt64MultiArray
+{
+    fn from(msg: crate::ros1::std_msgs::UInt64MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::UInt64MultiArray>
+    for crate::ros1::std_msgs::UInt64MultiArray
+{
+    fn from(msg: crate::ros2::std_msgs::UInt64MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::UInt8> for crate::ros2::std_msgs::UInt8 {
+    fn from(msg: crate::ros1::std_msgs::UInt8) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::UInt8> for crate::ros1::std_msgs::UInt8 {
+    fn from(msg: crate::ros2::std_msgs::UInt8) -> Self {
+        Self { r#data: msg.r#data }
+    }
+}
+impl ::std::convert::From<crate::ros1::std_msgs::UInt8MultiArray>
+    for crate::ros2::std_msgs::UInt8MultiArray
+{
+    fn from(msg: crate::ros1::std_msgs::UInt8MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::std_msgs::UInt8MultiArray>
+    for crate::ros1::std_msgs::UInt8MultiArray
+{
+    fn from(msg: crate::ros2::std_msgs::UInt8MultiArray) -> Self {
+        Self {
+            r#layout: ::std::convert::From::from(msg.r#layout),
+            r#data: msg.r#data,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::stereo_msgs::DisparityImage>
+    for crate::ros2::stereo_msgs::DisparityImage
+{
+    fn from(msg: crate::ros1::stereo_msgs::DisparityImage) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#image: ::std::convert::From::from(msg.r#image),
+            r#f: msg.r#f,
+            r#valid_window: ::std::convert::From::from(msg.r#valid_window),
+            r#min_disparity: msg.r#min_disparity,
+            r#max_disparity: msg.r#max_disparity,
+            r#delta_d: msg.r#delta_d,
+            ..::std::default::Default::default()
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::stereo_msgs::DisparityImage>
+    for crate::ros1::stereo_msgs::DisparityImage
+{
+    fn from(msg: crate::ros2::stereo_msgs::DisparityImage) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#image: ::std::convert::From::from(msg.r#image),
+            r#f: msg.r#f,
+            r#valid_window: ::std::convert::From::from(msg.r#valid_window),
+            r#min_disparity: msg.r#min_disparity,
+            r#max_disparity: msg.r#max_disparity,
+            r#delta_d: msg.r#delta_d,
+            ..::std::default::Default::default()
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::trajectory_msgs::JointTrajectory>
+    for crate::ros2::trajectory_msgs::JointTrajectory
+{
+    fn from(msg: crate::ros1::trajectory_msgs::JointTrajectory) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#joint_names: msg.r#joint_names,
+            r#points: msg
+                .r#points
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::trajectory_msgs::JointTrajectory>
+    for crate::ros1::trajectory_msgs::JointTrajectory
+{
+    fn from(msg: crate::ros2::trajectory_msgs::JointTrajectory) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#joint_names: msg.r#joint_names,
+            r#points: msg
+                .r#points
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::trajectory_msgs::JointTrajectoryPoint>
+    for crate::ros2::trajectory_msgs::JointTrajectoryPoint
+{
+    fn from(msg: crate::ros1::trajectory_msgs::JointTrajectoryPoint) -> Self {
+        Self {
+            r#positions: msg.r#positions,
+            r#velocities: msg.r#velocities,
+            r#accelerations: msg.r#accelerations,
+            r#effort: msg.r#effort,
+            r#time_from_start: {
+                let (sec, nanosec) = msg.r#time_from_start.to_sec_nanosec();
+                crate::ros2::builtin_interfaces::Duration { sec, nanosec }
+            },
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::trajectory_msgs::JointTrajectoryPoint>
+    for crate::ros1::trajectory_msgs::JointTrajectoryPoint
+{
+    fn from(msg: crate::ros2::trajectory_msgs::JointTrajectoryPoint) -> Self {
+        Self {
+            r#positions: msg.r#positions,
+            r#velocities: msg.r#velocities,
+            r#accelerations: msg.r#accelerations,
+            r#effort: msg.r#effort,
+            r#time_from_start: ::roslibrust::codegen::integral_types::Duration::from_sec_nanosec(
+                msg.r#time_from_start.r#sec,
+                msg.r#time_from_start.r#nanosec,
+            ),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::trajectory_msgs::MultiDOFJointTrajectory>
+    for crate::ros2::trajectory_msgs::MultiDOFJointTrajectory
+{
+    fn from(msg: crate::ros1::trajectory_msgs::MultiDOFJointTrajectory) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#joint_names: msg.r#joint_names,
+            r#points: msg
+                .r#points
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::trajectory_msgs::MultiDOFJointTrajectory>
+    for crate::ros1::trajectory_msgs::MultiDOFJointTrajectory
+{
+    fn from(msg: crate::ros2::trajectory_msgs::MultiDOFJointTrajectory) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#joint_names: msg.r#joint_names,
+            r#points: msg
+                .r#points
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::trajectory_msgs::MultiDOFJointTrajectoryPoint>
+    for crate::ros2::trajectory_msgs::MultiDOFJointTrajectoryPoint
+{
+    fn from(msg: crate::ros1::trajectory_msgs::MultiDOFJointTrajectoryPoint) -> Self {
+        Self {
+            r#transforms: msg
+                .r#transforms
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#velocities: msg
+                .r#velocities
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#accelerations: msg
+                .r#accelerations
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#time_from_start: {
+                let (sec, nanosec) = msg.r#time_from_start.to_sec_nanosec();
+                crate::ros2::builtin_interfaces::Duration { sec, nanosec }
+            },
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::trajectory_msgs::MultiDOFJointTrajectoryPoint>
+    for crate::ros1::trajectory_msgs::MultiDOFJointTrajectoryPoint
+{
+    fn from(msg: crate::ros2::trajectory_msgs::MultiDOFJointTrajectoryPoint) -> Self {
+        Self {
+            r#transforms: msg
+                .r#transforms
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#velocities: msg
+                .r#velocities
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#accelerations: msg
+                .r#accelerations
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#time_from_start: ::roslibrust::codegen::integral_types::Duration::from_sec_nanosec(
+                msg.r#time_from_start.r#sec,
+                msg.r#time_from_start.r#nanosec,
+            ),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::visualization_msgs::ImageMarker>
+    for crate::ros2::visualization_msgs::ImageMarker
+{
+    fn from(msg: crate::ros1::visualization_msgs::ImageMarker) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#ns: msg.r#ns,
+            r#id: msg.r#id,
+            r#type: msg.r#type,
+            r#action: msg.r#action,
+            r#position: ::std::convert::From::from(msg.r#position),
+            r#scale: msg.r#scale,
+            r#outline_color: ::std::convert::From::from(msg.r#outline_color),
+            r#filled: msg.r#filled,
+            r#fill_color: ::std::convert::From::from(msg.r#fill_color),
+            r#lifetime: {
+                let (sec, nanosec) = msg.r#lifetime.to_sec_nanosec();
+                crate::ros2::builtin_interfaces::Duration { sec, nanosec }
+            },
+            r#points: msg
+                .r#points
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#outline_colors: msg
+                .r#outline_colors
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::visualization_msgs::ImageMarker>
+    for crate::ros1::visualization_msgs::ImageMarker
+{
+    fn from(msg: crate::ros2::visualization_msgs::ImageMarker) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#ns: msg.r#ns,
+            r#id: msg.r#id,
+            r#type: msg.r#type,
+            r#action: msg.r#action,
+            r#position: ::std::convert::From::from(msg.r#position),
+            r#scale: msg.r#scale,
+            r#outline_color: ::std::convert::From::from(msg.r#outline_color),
+            r#filled: msg.r#filled,
+            r#fill_color: ::std::convert::From::from(msg.r#fill_color),
+            r#lifetime: ::roslibrust::codegen::integral_types::Duration::from_sec_nanosec(
+                msg.r#lifetime.r#sec,
+                msg.r#lifetime.r#nanosec,
+            ),
+            r#points: msg
+                .r#points
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#outline_colors: msg
+                .r#outline_colors
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::visualization_msgs::InteractiveMarker>
+    for crate::ros2::visualization_msgs::InteractiveMarker
+{
+    fn from(msg: crate::ros1::visualization_msgs::InteractiveMarker) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#pose: ::std::convert::From::from(msg.r#pose),
+            r#name: msg.r#name,
+            r#description: msg.r#description,
+            r#scale: msg.r#scale,
+            r#menu_entries: msg
+                .r#menu_entries
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#controls: msg
+                .r#controls
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::visualization_msgs::InteractiveMarker>
+    for crate::ros1::visualization_msgs::InteractiveMarker
+{
+    fn from(msg: crate::ros2::visualization_msgs::InteractiveMarker) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#pose: ::std::convert::From::from(msg.r#pose),
+            r#name: msg.r#name,
+            r#description: msg.r#description,
+            r#scale: msg.r#scale,
+            r#menu_entries: msg
+                .r#menu_entries
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#controls: msg
+                .r#controls
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::visualization_msgs::InteractiveMarkerControl>
+    for crate::ros2::visualization_msgs::InteractiveMarkerControl
+{
+    fn from(msg: crate::ros1::visualization_msgs::InteractiveMarkerControl) -> Self {
+        Self {
+            r#name: msg.r#name,
+            r#orientation: ::std::convert::From::from(msg.r#orientation),
+            r#orientation_mode: msg.r#orientation_mode,
+            r#interaction_mode: msg.r#interaction_mode,
+            r#always_visible: msg.r#always_visible,
+            r#markers: msg
+                .r#markers
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#independent_marker_orientation: msg.r#independent_marker_orientation,
+            r#description: msg.r#description,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::visualization_msgs::InteractiveMarkerControl>
+    for crate::ros1::visualization_msgs::InteractiveMarkerControl
+{
+    fn from(msg: crate::ros2::visualization_msgs::InteractiveMarkerControl) -> Self {
+        Self {
+            r#name: msg.r#name,
+            r#orientation: ::std::convert::From::from(msg.r#orientation),
+            r#orientation_mode: msg.r#orientation_mode,
+            r#interaction_mode: msg.r#interaction_mode,
+            r#always_visible: msg.r#always_visible,
+            r#markers: msg
+                .r#markers
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#independent_marker_orientation: msg.r#independent_marker_orientation,
+            r#description: msg.r#description,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::visualization_msgs::InteractiveMarkerFeedback>
+    for crate::ros2::visualization_msgs::InteractiveMarkerFeedback
+{
+    fn from(msg: crate::ros1::visualization_msgs::InteractiveMarkerFeedback) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#client_id: msg.r#client_id,
+            r#marker_name: msg.r#marker_name,
+            r#control_name: msg.r#control_name,
+            r#event_type: msg.r#event_type,
+            r#pose: ::std::convert::From::from(msg.r#pose),
+            r#menu_entry_id: msg.r#menu_entry_id,
+            r#mouse_point: ::std::convert::From::from(msg.r#mouse_point),
+            r#mouse_point_valid: msg.r#mouse_point_valid,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::visualization_msgs::InteractiveMarkerFeedback>
+    for crate::ros1::visualization_msgs::InteractiveMarkerFeedback
+{
+    fn from(msg: crate::ros2::visualization_msgs::InteractiveMarkerFeedback) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#client_id: msg.r#client_id,
+            r#marker_name: msg.r#marker_name,
+            r#control_name: msg.r#control_name,
+            r#event_type: msg.r#event_type,
+            r#pose: ::std::convert::From::from(msg.r#pose),
+            r#menu_entry_id: msg.r#menu_entry_id,
+            r#mouse_point: ::std::convert::From::from(msg.r#mouse_point),
+            r#mouse_point_valid: msg.r#mouse_point_valid,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::visualization_msgs::InteractiveMarkerInit>
+    for crate::ros2::visualization_msgs::InteractiveMarkerInit
+{
+    fn from(msg: crate::ros1::visualization_msgs::InteractiveMarkerInit) -> Self {
+        Self {
+            r#server_id: msg.r#server_id,
+            r#seq_num: msg.r#seq_num,
+            r#markers: msg
+                .r#markers
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::visualization_msgs::InteractiveMarkerInit>
+    for crate::ros1::visualization_msgs::InteractiveMarkerInit
+{
+    fn from(msg: crate::ros2::visualization_msgs::InteractiveMarkerInit) -> Self {
+        Self {
+            r#server_id: msg.r#server_id,
+            r#seq_num: msg.r#seq_num,
+            r#markers: msg
+                .r#markers
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::visualization_msgs::InteractiveMarkerPose>
+    for crate::ros2::visualization_msgs::InteractiveMarkerPose
+{
+    fn from(msg: crate::ros1::visualization_msgs::InteractiveMarkerPose) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#pose: ::std::convert::From::from(msg.r#pose),
+            r#name: msg.r#name,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::visualization_msgs::InteractiveMarkerPose>
+    for crate::ros1::visualization_msgs::InteractiveMarkerPose
+{
+    fn from(msg: crate::ros2::visualization_msgs::InteractiveMarkerPose) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#pose: ::std::convert::From::from(msg.r#pose),
+            r#name: msg.r#name,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::visualization_msgs::InteractiveMarkerUpdate>
+    for crate::ros2::visualization_msgs::InteractiveMarkerUpdate
+{
+    fn from(msg: crate::ros1::visualization_msgs::InteractiveMarkerUpdate) -> Self {
+        Self {
+            r#server_id: msg.r#server_id,
+            r#seq_num: msg.r#seq_num,
+            r#type: msg.r#type,
+            r#markers: msg
+                .r#markers
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#poses: msg
+                .r#poses
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#erases: msg.r#erases,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::visualization_msgs::InteractiveMarkerUpdate>
+    for crate::ros1::visualization_msgs::InteractiveMarkerUpdate
+{
+    fn from(msg: crate::ros2::visualization_msgs::InteractiveMarkerUpdate) -> Self {
+        Self {
+            r#server_id: msg.r#server_id,
+            r#seq_num: msg.r#seq_num,
+            r#type: msg.r#type,
+            r#markers: msg
+                .r#markers
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#poses: msg
+                .r#poses
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#erases: msg.r#erases,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::visualization_msgs::Marker>
+    for crate::ros2::visualization_msgs::Marker
+{
+    fn from(msg: crate::ros1::visualization_msgs::Marker) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#ns: msg.r#ns,
+            r#id: msg.r#id,
+            r#type: msg.r#type,
+            r#action: msg.r#action,
+            r#pose: ::std::convert::From::from(msg.r#pose),
+            r#scale: ::std::convert::From::from(msg.r#scale),
+            r#color: ::std::convert::From::from(msg.r#color),
+            r#lifetime: {
+                let (sec, nanosec) = msg.r#lifetime.to_sec_nanosec();
+                crate::ros2::builtin_interfaces::Duration { sec, nanosec }
+            },
+            r#frame_locked: msg.r#frame_locked,
+            r#points: msg
+                .r#points
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#colors: msg
+                .r#colors
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#text: msg.r#text,
+            r#mesh_resource: msg.r#mesh_resource,
+            r#mesh_use_embedded_materials: msg.r#mesh_use_embedded_materials,
+            ..::std::default::Default::default()
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::visualization_msgs::Marker>
+    for crate::ros1::visualization_msgs::Marker
+{
+    fn from(msg: crate::ros2::visualization_msgs::Marker) -> Self {
+        Self {
+            r#header: ::std::convert::From::from(msg.r#header),
+            r#ns: msg.r#ns,
+            r#id: msg.r#id,
+            r#type: msg.r#type,
+            r#action: msg.r#action,
+            r#pose: ::std::convert::From::from(msg.r#pose),
+            r#scale: ::std::convert::From::from(msg.r#scale),
+            r#color: ::std::convert::From::from(msg.r#color),
+            r#lifetime: ::roslibrust::codegen::integral_types::Duration::from_sec_nanosec(
+                msg.r#lifetime.r#sec,
+                msg.r#lifetime.r#nanosec,
+            ),
+            r#frame_locked: msg.r#frame_locked,
+            r#points: msg
+                .r#points
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#colors: msg
+                .r#colors
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+            r#text: msg.r#text,
+            r#mesh_resource: msg.r#mesh_resource,
+            r#mesh_use_embedded_materials: msg.r#mesh_use_embedded_materials,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::visualization_msgs::MarkerArray>
+    for crate::ros2::visualization_msgs::MarkerArray
+{
+    fn from(msg: crate::ros1::visualization_msgs::MarkerArray) -> Self {
+        Self {
+            r#markers: msg
+                .r#markers
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::visualization_msgs::MarkerArray>
+    for crate::ros1::visualization_msgs::MarkerArray
+{
+    fn from(msg: crate::ros2::visualization_msgs::MarkerArray) -> Self {
+        Self {
+            r#markers: msg
+                .r#markers
+                .into_iter()
+                .map(::std::convert::From::from)
+                .collect(),
+        }
+    }
+}
+impl ::std::convert::From<crate::ros1::visualization_msgs::MenuEntry>
+    for crate::ros2::visualization_msgs::MenuEntry
+{
+    fn from(msg: crate::ros1::visualization_msgs::MenuEntry) -> Self {
+        Self {
+            r#id: msg.r#id,
+            r#parent_id: msg.r#parent_id,
+            r#title: msg.r#title,
+            r#command: msg.r#command,
+            r#command_type: msg.r#command_type,
+        }
+    }
+}
+impl ::std::convert::From<crate::ros2::visualization_msgs::MenuEntry>
+    for crate::ros1::visualization_msgs::MenuEntry
+{
+    fn from(msg: crate::ros2::visualization_msgs::MenuEntry) -> Self {
+        Self {
+            r#id: msg.r#id,
+            r#parent_id: msg.r#parent_id,
+            r#title: msg.r#title,
+            r#command: msg.r#command,
+            r#command_type: msg.r#command_type,
+        }
+    }
+}
@@ -1,3 +1,5 @@
 // These are kept in separate namespaces to prevent name collisions
 pub mod ros1;
 pub mod ros2;
+// From impls between the two, generated by roslibrust_codegen::dialect
+pub mod dialect;
@@ -35,22 +35,50 @@ lazy_static! {
     ];
 }
 
-/// This main function is used to generate the contents of ros1.rs, ros2.rs
+// Doc comments are left out to keep the committed generated files compact
+fn generate(paths: &[PathBuf]) -> Result<(String, Vec<PathBuf>), Box<dyn std::error::Error>> {
+    let options = roslibrust::codegen::CodegenOptions {
+        generate_docs: false,
+        ..Default::default()
+    };
+    let (source, paths) =
+        roslibrust::codegen::find_and_generate_ros_messages_with_options(paths.to_vec(), &options)?;
+    Ok((source.to_string(), paths))
+}
+
+// Conversions between the two dialects of the messages in ros1.rs and ros2.rs
+fn generate_dialect_conversions() -> Result<String, Box<dyn std::error::Error>> {
+    let parse = |paths: &[PathBuf]| -> Result<_, Box<dyn std::error::Error>> {
+        let (messages, services, _actions) =
+            roslibrust::codegen::find_and_parse_ros_messages(paths)?;
+        Ok(roslibrust::codegen::resolve_dependency_graph(messages, services)?.0)
+    };
+    let conversions = roslibrust::codegen::dialect::generate_conversions(
+        &parse(&ROS_1_PATHS)?,
+        &parse(&ROS_2_PATHS)?,
+        "crate::ros1",
+        "crate::ros2",
+    )?;
+    Ok(conversions.to_string())
+}
+
+/// This main function is used to generate the contents of ros1.rs, ros2.rs and dialect.rs
 fn main() -> Result<(), Box<dyn std::error::Error>> {
     env_logger::init();
-    let (source, _paths) =
-        roslibrust::codegen::find_and_generate_ros_messages_without_ros_package_path(
-            (*ROS_1_PATHS).clone(),
-        )?;
-    let source = format_rust_source(source.to_string().as_str()).to_string();
+    let (source, _paths) = generate(&ROS_1_PATHS)?;
+    let source = format_rust_source(&source).to_string();
     std::fs::write(concat!(env!("CARGO_MANIFEST_DIR"), "/src/ros1.rs"), source)?;
 
-    let (source, _paths) =
-        roslibrust::codegen::find_and_generate_ros_messages_without_ros_package_path(
-            (*ROS_2_PATHS).clone(),
-        )?;
-    let source = format_rust_source(source.to_string().as_str()).to_string();
+    let (source, _paths) = generate(&ROS_2_PATHS)?;
+    let source = format_rust_source(&source).to_string();
     std::fs::write(concat!(env!("CARGO_MANIFEST_DIR"), "/src/ros2.rs"), source)?;
+
+    let source = generate_dialect_conversions()?;
+    let source = format_rust_source(&source).to_string();
+    std::fs::write(
+        concat!(env!("CARGO_MANIFEST_DIR"), "/src/dialect.rs"),
+        source,
+    )?;
     Ok(())
 }
 
@@ -86,12 +114,8 @@ mod test {
     /// Confirms that codegen has been run and changes committed
     #[test]
     fn ros1_lib_is_up_to_date() {
-        let (source, _paths) =
-            roslibrust::codegen::find_and_generate_ros_messages_without_ros_package_path(
-                (*ROS_1_PATHS).clone(),
-            )
-            .unwrap();
-        let source = format_rust_source(source.to_string().as_str()).to_string();
+        let (source, _paths) = generate(&ROS_1_PATHS).unwrap();
+        let source = format_rust_source(&source).to_string();
         let lib_path = env!("CARGO_MANIFEST_DIR").to_string() + "/src/ros1.rs";
         let lib_contents =
             std::fs::read_to_string(lib_path).expect("Failed to load current ros1.rs contents");
@@ -108,12 +132,8 @@ mod test {
     /// Confirms that codegen has been run and changes committed
     #[test]
     fn ros2_lib_is_up_to_date() {
-        let (source, _paths) =
-            roslibrust::codegen::find_and_generate_ros_messages_without_ros_package_path(
-                (*ROS_2_PATHS).clone(),
-            )
-            .unwrap();
-        let source = format_rust_source(source.to_string().as_str()).to_string();
+        let (source, _paths) = generate(&ROS_2_PATHS).unwrap();
+        let source = format_rust_source(&source).to_string();
         let lib_path = env!("CARGO_MANIFEST_DIR").to_string() + "/src/ros2.rs";
         let lib_contents =
             std::fs::read_to_string(lib_path).expect("Failed to load current ros2.rs contents");
@@ -126,4 +146,22 @@ mod test {
             panic!("Changes detected see diff!");
         }
     }
+
+    /// Confirms that codegen has been run and changes committed
+    #[test]
+    fn dialect_lib_is_up_to_date() {
+        let source = generate_dialect_conversions().unwrap();
+        let source = format_rust_source(&source).to_string();
+        let lib_path = env!("CARGO_MANIFEST_DIR").to_string() + "/src/dialect.rs";
+        let lib_contents =
+            std::fs::read_to_string(lib_path).expect("Failed to load current dialect.rs contents");
+
+        // Creating a diff so if there are changes output in CI is sane
+        let diff = diffy::create_patch(&source, &lib_contents);
+        println!("Diff is \n{}", diff);
+
+        if source.trim() != lib_contents.trim() {
+            panic!("Changes detected see diff!");
+        }
+    }
 }
@@ -0,0 +1,80 @@
+use roslibrust::codegen::integral_types::Time;
+use roslibrust_test::{ros1, ros2};
+
+#[test]
+fn header_round_trips() {
+    let header = ros1::std_msgs::Header {
+        seq: 7,
+        stamp: Time {
+            secs: 12,
+            nsecs: 345,
+        },
+        frame_id: "base_link".to_string(),
+    };
+    let converted: ros2::std_msgs::Header = header.clone().into();
+    assert_eq!(
+        converted,
+        ros2::std_msgs::Header {
+            stamp: ros2::builtin_interfaces::Time {
+                sec: 12,
+                nanosec: 345,
+            },
+            frame_id: "base_link".to_string(),
+        }
+    );
+    // ROS2 headers have no seq, so it comes back defaulted
+    let header_again: ros1::std_msgs::Header = converted.into();
+    assert_eq!(header_again, ros1::std_msgs::Header { seq: 0, ..header });
+}
+
+#[test]
+fn negative_nsecs_are_normalized() {
+    let header = ros1::std_msgs::Header {
+        stamp: Time { secs: 5, nsecs: -1 },
+        ..Default::default()
+    };
+    let converted: ros2::std_msgs::Header = header.into();
+    assert_eq!(
+        converted.stamp,
+        ros2::builtin_interfaces::Time {
+            sec: 4,
+            nanosec: 999_999_999,
+        }
+    );
+    let header_again: ros1::std_msgs::Header = converted.into();
+    assert_eq!(
+        header_again.stamp,
+        Time {
+            secs: 4,
+            nsecs: 999_999_999
+        }
+    );
+}
+
+#[test]
+fn nested_messages_convert() {
+    let pose = ros1::geometry_msgs::PoseStamped {
+        header: ros1::std_msgs::Header {
+            frame_id: "map".to_string(),
+            ..Default::default()
+        },
+        pose: ros1::geometry_msgs::Pose {
+            position: ros1::geometry_msgs::Point {
+                x: 1.0,
+                y: 2.0,
+                z: 3.0,
+            },
+            ..Default::default()
+        },
+    };
+    let path = ros1::nav_msgs::Path {
+        header: pose.header.clone(),
+        poses: vec![pose.clone(), pose],
+    };
+    let converted: ros2::nav_msgs::Path = path.clone().into();
+    assert_eq!(converted.poses.len(), 2);
+    assert_eq!(converted.poses[1].pose.position.z, 3.0);
+    assert_eq!(converted.poses[1].header.frame_id, "map");
+    let path_again: ros1::nav_msgs::Path = converted.into();
+    assert_eq!(path_again, path);
+}
@@ -0,0 +1,508 @@
+//! Launches roscore, rosbridge_server, or other ROS nodes in docker for end-to-end tests of roslibrust nodes.
+//!
+//! A [RosFixture] describes what should be running, and [RosFixture::start] launches it in a fresh container,
+//! returning once everything is ready. The container is removed when the returned [RunningFixture] is dropped.
+//!
+//! ```no_run
+//! use roslibrust_test_util::{Distro, RosFixture};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let ros = RosFixture::new(Distro::Noetic)
+//!     .with_roscore()
+//!     .with_rosbridge()
+//!     .start()
+//!     .await?;
+//! // Point the code under test at the fixture
+//! let master_uri = ros.master_uri().unwrap();
+//! let rosbridge_url = ros.rosbridge_url().unwrap();
+//! // Drive the system from the ROS side
+//! ros.exec("rostopic pub -1 /chatter std_msgs/String 'data: hello'").await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! The `docker` cli must be installed and usable by the user running the tests.
+//! Containers use host networking, as is required for native ROS1 communication, so fixtures only work on Linux hosts
+//! and fixtures running at the same time must be given different ports.
+//!
+//! [ChaosProxy] can be placed between a node and the fixture to inject latency, dropped connections and resets.
+
+use log::*;
+use std::process::Stdio;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use tokio::process::Command;
+
+mod proxy;
+pub use proxy::ChaosProxy;
+
+/// Default port roscore is started on, same as ROS's own default
+pub const DEFAULT_MASTER_PORT: u16 = 11311;
+/// Default port rosbridge_server listens on, same as rosbridge's own default
+pub const DEFAULT_ROSBRIDGE_PORT: u16 = 9090;
+/// Default amount of time [RosFixture::start] waits for everything to come up
+pub const DEFAULT_STARTUP_TIMEOUT: Duration = Duration::from_secs(30);
+
+/// Log line roscore prints once the master is up and running
+const ROSCORE_READY: &str = "started core service [/rosout]";
+/// Log line rosbridge_server prints once it is accepting websocket connections (on both ROS1 and ROS2)
+const ROSBRIDGE_READY: &str = "Rosbridge WebSocket server started";
+
+/// Used to give each container a unique name
+static CONTAINER_COUNT: AtomicUsize = AtomicUsize::new(0);
+
+/// Errors that can occur launching or interacting with a fixture
+#[derive(thiserror::Error, Debug)]
+pub enum Error {
+    /// The docker cli could not be run at all
+    #[error("Failed to run docker, is it installed and on the PATH? {0}")]
+    Docker(#[from] std::io::Error),
+    /// A docker command ran but returned an error
+    #[error("`{command}` failed: {stderr}")]
+    CommandFailed { command: String, stderr: String },
+    /// The container stopped before it became ready
+    #[error("Container exited before becoming ready, logs:\n{logs}")]
+    ContainerExited { logs: String },
+    /// The expected log message did not appear in time
+    #[error("Timed out after {timeout:?} waiting for `{message}` in container logs")]
+    Timeout { message: String, timeout: Duration },
+    /// The fixture asked for something the chosen distro doesn't have, e.g. roscore on ROS2
+    #[error("{0} is not available on {1:?}")]
+    Unsupported(&'static str, Distro),
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+/// The ROS distributions roslibrust is tested against, each has a CI image with rosbridge_server installed
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Distro {
+    Noetic,
+    Galactic,
+    Humble,
+    Iron,
+    Kilted,
+    Rolling,
+}
+
+impl Distro {
+    /// Name of the distro as used in `/opt/ros/<name>`
+    pub fn name(&self) -> &'static str {
+        match self {
+            Distro::Noetic => "noetic",
+            Distro::Galactic => "galactic",
+            Distro::Humble => "humble",
+            Distro::Iron => "iron",
+            Distro::Kilted => "kilted",
+            Distro::Rolling => "rolling",
+        }
+    }
+
+    /// True for ROS1 distros
+    pub fn is_ros1(&self) -> bool {
+        matches!(self, Distro::Noetic)
+    }
+
+    /// The image roslibrust's CI uses for this distro, see the `docker` folder of the repo
+    pub fn default_image(&self) -> String {
+        let tag = match self {
+            Distro::Rolling => "rust_1_91",
+            _ => "rust_1_90",
+        };
+        format!("carter12s/roslibrust-ci-{}:{tag}", self.name())
+    }
+}
+
+/// Describes the ROS processes that should be running for a test.
+///
+/// Built up with the `with_*` functions and then launched with [RosFixture::start].
+#[derive(Debug, Clone)]
+pub struct RosFixture {
+    distro: Distro,
+    image: String,
+    roscore: bool,
+    rosbridge: bool,
+    master_port: u16,
+    rosbridge_port: u16,
+    nodes: Vec<String>,
+    env: Vec<(String, String)>,
+    startup_timeout: Duration,
+}
+
+impl RosFixture {
+    /// Creates a fixture for the given distro which runs nothing until configured
+    pub fn new(distro: Distro) -> Self {
+        Self {
+            distro,
+            image: distro.default_image(),
+            roscore: false,
+            rosbridge: false,
+            master_port: DEFAULT_MASTER_PORT,
+            rosbridge_port: DEFAULT_ROSBRIDGE_PORT,
+            nodes: vec![],
+            env: vec![],
+            startup_timeout: DEFAULT_STARTUP_TIMEOUT,
+        }
+    }
+
+    /// Use a different docker image, it must have the distro installed in `/opt/ros/<distro>`
+    pub fn image(mut self, image: impl Into<String>) -> Self {
+        self.image = image.into();
+        self
+    }
+
+    /// Run a roscore, only available on ROS1 distros
+    pub fn with_roscore(mut self) -> Self {
+        self.roscore = true;
+        self
+    }
+
+    /// Run rosbridge_server and the rosapi node
+    pub fn with_rosbridge(mut self) -> Self {
+        self.rosbridge = true;
+        self
+    }
+
+    /// Run an additional node, or any other command, in the container e.g. `rosrun turtlesim turtlesim_node`
+    ///
+    /// The command is run with the distro's setup.bash sourced after roscore and rosbridge are started.
+    /// Use [RunningFixture::wait_for_log] to wait for the node to be ready if needed.
+    pub fn with_node(mut self, command: impl Into<String>) -> Self {
+        self.nodes.push(command.into());
+        self
+    }
+
+    /// Sets an environment variable in the container, e.g. `ROS_DOMAIN_ID`
+    pub fn env(mut self, key: impl Into<String>, value: impl Into<String>) -> Self {
+        self.env.push((key.into(), value.into()));
+        self
+    }
+
+    /// Port roscore is started on, defaults to [DEFAULT_MASTER_PORT]
+    pub fn master_port(mut self, port: u16) -> Self {
+        self.master_port = port;
+        self
+    }
+
+    /// Port rosbridge_server listens on, defaults to [DEFAULT_ROSBRIDGE_PORT]
+    pub fn rosbridge_port(mut self, port: u16) -> Self {
+        self.rosbridge_port = port;
+        self
+    }
+
+    /// How long [RosFixture::start] waits for roscore and rosbridge to come up, defaults to [DEFAULT_STARTUP_TIMEOUT]
+    ///
+    /// Note: the first start of a distro may also have to pull the image, which is not covered by this timeout.
+    pub fn startup_timeout(mut self, timeout: Duration) -> Self {
+        self.startup_timeout = timeout;
+        self
+    }
+
+    /// Launches the container and waits until roscore and rosbridge_server (if requested) are ready.
+    ///
+    /// If waiting fails the container is removed before the error is returned.
+    pub async fn start(self) -> Result<RunningFixture> {
+        if self.roscore && !self.distro.is_ros1() {
+            return Err(Error::Unsupported("roscore", self.distro));
+        }
+        let name = format!(
+            "roslibrust_test_util_{}_{}",
+            std::process::id(),
+            CONTAINER_COUNT.fetch_add(1, Ordering::Relaxed)
+        );
+
+        let mut args: Vec<String> = vec![
+            "run".into(),
+            "--detach".into(),
+            // Allocating a tty keeps the ROS tools from buffering their output, which we watch for readiness
+            "--tty".into(),
+            "--network".into(),
+            "host".into(),
+            "--name".into(),
+            name.clone(),
+            "--env".into(),
+            "PYTHONUNBUFFERED=1".into(),
+        ];
+        for (key, value) in self.container_env() {
+            args.push("--env".into());
+            args.push(format!("{key}={value}"));
+        }
+        args.extend([
+            self.image.clone(),
+            "bash".into(),
+            "-c".into(),
+            self.script(),
+        ]);
+        debug!("Starting test fixture container {name} from {}", self.image);
+        let id = docker(&args).await?.trim().to_string();
+
+        let fixture = RunningFixture {
+            id,
+            distro: self.distro,
+            env: self.container_env(),
+            master_uri: self
+                .roscore
+                .then(|| format!("http://localhost:{}", self.master_port)),
+            rosbridge_url: self
+                .rosbridge
+                .then(|| format!("ws://localhost:{}", self.rosbridge_port)),
+        };
+        let deadline = Instant::now() + self.startup_timeout;
+        if self.roscore {
+            fixture
+                .wait_for_log(
+                    ROSCORE_READY,
+                    deadline.saturating_duration_since(Instant::now()),
+                )
+                .await?;
+        }
+        if self.rosbridge {
+            fixture
+                .wait_for_log(
+                    ROSBRIDGE_READY,
+                    deadline.saturating_duration_since(Instant::now()),
+                )
+                .await?;
+        }
+        Ok(fixture)
+    }
+
+    /// Environment the processes in the container are run with
+    fn container_env(&self) -> Vec<(String, String)> {
+        let mut env = vec![];
+        if self.distro.is_ros1() {
+            env.push((
+                "ROS_MASTER_URI".to_string(),
+                format!("http://localhost:{}", self.master_port),
+            ));
+        }
+        env.extend(self.env.iter().cloned());
+        env
+    }
+
+    /// The bash script run as the container's command
+    fn script(&self) -> String {
+        let mut lines = vec![format!("source /opt/ros/{}/setup.bash", self.distro.name())];
+        if self.roscore {
+            lines.push(format!("roscore -p {} &", self.master_port));
+            // Otherwise roslaunch may start its own master while roscore is still coming up
+            lines.push("until rosnode list > /dev/null 2>&1; do sleep 0.1; done".to_string());
+        }
+        if self.rosbridge {
+            if self.distro.is_ros1() {
+                lines.push(format!(
+                    "roslaunch rosbridge_server rosbridge_websocket.launch port:={} &",
+                    self.rosbridge_port
+                ));
+                lines.push("rosrun rosapi rosapi_node &".to_string());
+            } else {
+                lines.push(format!(
+                    "ros2 launch rosbridge_server rosbridge_websocket_launch.xml port:={} &",
+                    self.rosbridge_port
+                ));
+                lines.push("ros2 run rosapi rosapi_node &".to_string());
+            }
+        }
+        for node in &self.nodes {
+            lines.push(format!("{node} &"));
+        }
+        // Keep the container alive for as long as anything is running in it
+        lines.push("wait".to_string());
+        lines.join("\n")
+    }
+}
+
+/// A running fixture container, created with [RosFixture::start].
+///
+/// The container is removed when this is dropped, or explicitly with [RunningFixture::stop].
+#[derive(Debug)]
+pub struct RunningFixture {
+    id: String,
+    distro: Distro,
+    env: Vec<(String, String)>,
+    master_uri: Option<String>,
+    rosbridge_url: Option<String>,
+}
+
+impl RunningFixture {
+    /// Id of the docker container
+    pub fn container_id(&self) -> &str {
+        &self.id
+    }
+
+    /// URI of the fixture's roscore, if it was started with one
+    pub fn master_uri(&self) -> Option<&str> {
+        self.master_uri.as_deref()
+    }
+
+    /// URL of the fixture's rosbridge_server, if it was started with one
+    pub fn rosbridge_url(&self) -> Option<&str> {
+        self.rosbridge_url.as_deref()
+    }
+
+    /// Everything the container has printed so far
+    pub async fn logs(&self) -> Result<String> {
+        docker(&["logs", self.id.as_str()]).await
+    }
+
+    /// Waits until the container has printed a line containing `message`.
+    ///
+    /// Useful for waiting on nodes started with [RosFixture::with_node].
+    /// Fails early if the container exits while waiting.
+    pub async fn wait_for_log(&self, message: &str, timeout: Duration) -> Result<()> {
+        let deadline = Instant::now() + timeout;
+        loop {
+            let logs = self.logs().await?;
+            if logs.contains(message) {
+                return Ok(());
+            }
+            let running = docker(&[
+                "inspect",
+                "--format",
+                "{{.State.Running}}",
+                self.id.as_str(),
+            ])
+            .await?;
+            if running.trim() != "true" {
+                return Err(Error::ContainerExited { logs });
+            }
+            if Instant::now() >= deadline {
+                return Err(Error::Timeout {
+                    message: message.to_string(),
+                    timeout,
+                });
+            }
+            tokio::time::sleep(Duration::from_millis(100)).await;
+        }
+    }
+
+    /// Runs a command inside the container with the distro's setup.bash sourced, returning its stdout.
+    ///
+    /// Useful for driving the system under test with the standard ROS tools, e.g. `rostopic pub` or `ros2 service call`.
+    pub async fn exec(&self, command: &str) -> Result<String> {
+        let mut args = vec!["exec".to_string()];
+        for (key, value) in &self.env {
+            args.push("--env".into());
+            args.push(format!("{key}={value}"));
+        }
+        args.extend([
+            self.id.clone(),
+            "bash".into(),
+            "-c".into(),
+            format!(
+                "source /opt/ros/{}/setup.bash; {command}",
+                self.distro.name()
+            ),
+        ]);
+        docker(&args).await
+    }
+
+    /// Removes the container, waiting for it to be gone
+    pub async fn stop(mut self) -> Result<()> {
+        let id = std::mem::take(&mut self.id);
+        docker(&["rm", "--force", id.as_str()]).await.map(|_| ())
+    }
+}
+
+/// Removes the container when the fixture goes out of scope
+impl Drop for RunningFixture {
+    fn drop(&mut self) {
+        // Already removed by stop()
+        if self.id.is_empty() {
+            return;
+        }
+        // Blocking here is intentional, otherwise the container could outlive a test that panicked
+        let res = std::process::Command::new("docker")
+            .args(["rm", "--force", &self.id])
+            .stdout(Stdio::null())
+            .stderr(Stdio::null())
+            .status();
+        if let Err(e) = res {
+            error!("Failed to remove test fixture container {}: {e}", self.id);
+        }
+    }
+}
+
+/// Runs the docker cli with the given arguments returning its stdout
+async fn docker<S: AsRef<std::ffi::OsStr>>(args: &[S]) -> Result<String> {
+    let output = Command::new("docker")
+        .args(args)
+        .stdin(Stdio::null())
+        .output()
+        .await?;
+    if !output.status.success() {
+        let command = std::iter::once("docker".to_string())
+            .chain(
+                args.iter()
+                    .map(|a| a.as_ref().to_string_lossy().into_owned()),
+            )
+            .collect::<Vec<_>>()
+            .join(" ");
+        return Err(Error::CommandFailed {
+            command,
+            stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+        });
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).into_owned())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn ros1_script_starts_roscore_before_rosbridge() {
+        let fixture = RosFixture::new(Distro::Noetic)
+            .with_rosbridge()
+            .with_roscore()
+            .with_node("rosrun turtlesim turtlesim_node")
+            .master_port(11411)
+            .rosbridge_port(9091);
+        let script = fixture.script();
+        let roscore = script.find("roscore -p 11411 &").unwrap();
+        let wait_master = script.find("until rosnode list").unwrap();
+        let rosbridge = script
+            .find("roslaunch rosbridge_server rosbridge_websocket.launch port:=9091 &")
+            .unwrap();
+        let node = script.find("rosrun turtlesim turtlesim_node &").unwrap();
+        assert!(script.starts_with("source /opt/ros/noetic/setup.bash\n"));
+        assert!(roscore < wait_master && wait_master < rosbridge && rosbridge < node);
+        assert!(script.ends_with("\nwait"));
+        assert_eq!(
+            fixture.container_env(),
+            vec![(
+                "ROS_MASTER_URI".to_string(),
+                "http://localhost:11411".to_string()
+            )]
+        );
+    }
+
+    #[test]
+    fn ros2_script_uses_ros2_tools() {
+        let fixture = RosFixture::new(Distro::Humble)
+            .with_rosbridge()
+            .env("ROS_DOMAIN_ID", "7");
+        let script = fixture.script();
+        assert!(script
+            .contains("ros2 launch rosbridge_server rosbridge_websocket_launch.xml port:=9090 &"));
+        assert!(script.contains("ros2 run rosapi rosapi_node &"));
+        assert!(!script.contains("roscore"));
+        assert_eq!(
+            fixture.container_env(),
+            vec![("ROS_DOMAIN_ID".to_string(), "7".to_string())]
+        );
+        assert_eq!(
+            Distro::Humble.default_image(),
+            "carter12s/roslibrust-ci-humble:rust_1_90"
+        );
+    }
+
+    #[tokio::test]
+    async fn roscore_is_unsupported_on_ros2() {
+        let res = RosFixture::new(Distro::Humble).with_roscore().start().await;
+        assert!(matches!(
+            res,
+            Err(Error::Unsupported("roscore", Distro::Humble))
+        ));
+    }
+}
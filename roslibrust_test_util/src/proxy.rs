@@ -0,0 +1,321 @@
+//! A TCP proxy that injects faults between a node under test and the master or its peers.
+
+use log::*;
+use std::net::SocketAddr;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::tcp::{OwnedReadHalf, OwnedWriteHalf};
+use tokio::net::{TcpListener, TcpStream, ToSocketAddrs};
+use tokio::sync::{broadcast, mpsc, watch};
+use tokio::time::Instant;
+
+/// Forwards TCP connections to a target address while letting a test inject latency, refused connections,
+/// stalls and resets.
+///
+/// Used to check that a backend really is "self-healing" as the docs of roslibrust's `Error::Disconnected` promise,
+/// e.g. by pointing a ROS1 node at the proxy instead of the master:
+///
+/// ```no_run
+/// # use roslibrust_test_util::ChaosProxy;
+/// # use std::time::Duration;
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let proxy = ChaosProxy::new("localhost:11311").await?;
+/// let master_uri = format!("http://{}", proxy.local_addr());
+/// // ... create a node using master_uri
+/// // Simulate the master going away for a while
+/// proxy.set_refusing(true);
+/// proxy.reset_connections();
+/// tokio::time::sleep(Duration::from_secs(5)).await;
+/// proxy.set_refusing(false);
+/// // ... check the node recovers
+/// # Ok(())
+/// # }
+/// ```
+///
+/// All faults can be changed at any time and apply to existing connections as well as new ones.
+/// Dropping the proxy stops it listening and closes all connections through it.
+pub struct ChaosProxy {
+    local_addr: SocketAddr,
+    state: Arc<ProxyState>,
+    accept_task: tokio::task::JoinHandle<()>,
+}
+
+struct ProxyState {
+    target: SocketAddr,
+    latency: Mutex<Duration>,
+    refusing: AtomicBool,
+    stalled: watch::Sender<bool>,
+    reset: broadcast::Sender<()>,
+    connection_count: AtomicUsize,
+    active_connections: AtomicUsize,
+}
+
+impl ChaosProxy {
+    /// Starts a proxy to `target` listening on a random port on localhost
+    pub async fn new(target: impl ToSocketAddrs) -> std::io::Result<Self> {
+        let target = tokio::net::lookup_host(target)
+            .await?
+            .next()
+            .ok_or_else(|| {
+                std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "Proxy target did not resolve to any address",
+                )
+            })?;
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let local_addr = listener.local_addr()?;
+        let state = Arc::new(ProxyState {
+            target,
+            latency: Mutex::new(Duration::ZERO),
+            refusing: AtomicBool::new(false),
+            stalled: watch::channel(false).0,
+            reset: broadcast::channel(1).0,
+            connection_count: AtomicUsize::new(0),
+            active_connections: AtomicUsize::new(0),
+        });
+        let accept_task = tokio::spawn(accept_loop(listener, state.clone()));
+        debug!("Chaos proxy listening on {local_addr} forwarding to {target}");
+        Ok(Self {
+            local_addr,
+            state,
+            accept_task,
+        })
+    }
+
+    /// The address to connect to instead of the target
+    pub fn local_addr(&self) -> SocketAddr {
+        self.local_addr
+    }
+
+    /// Delays all data passing through the proxy, in either direction, by `latency`
+    pub fn set_latency(&self, latency: Duration) {
+        *self.state.latency.lock().unwrap() = latency;
+    }
+
+    /// While refusing, new connections are closed as soon as they are accepted, as if the target were down
+    pub fn set_refusing(&self, refusing: bool) {
+        self.state.refusing.store(refusing, Ordering::Relaxed);
+    }
+
+    /// While stalled, connections stay open but no data is forwarded, as if the network were partitioned.
+    ///
+    /// Data received while stalled is delivered once un-stalled.
+    pub fn set_stalled(&self, stalled: bool) {
+        self.state.stalled.send_replace(stalled);
+    }
+
+    /// Closes every connection currently open through the proxy, new connections are unaffected
+    pub fn reset_connections(&self) {
+        // Errors only if there are no connections to reset
+        let _ = self.state.reset.send(());
+    }
+
+    /// Total number of connections accepted and forwarded, useful for checking a client reconnected
+    pub fn connection_count(&self) -> usize {
+        self.state.connection_count.load(Ordering::Relaxed)
+    }
+
+    /// Number of connections currently open through the proxy
+    pub fn active_connections(&self) -> usize {
+        self.state.active_connections.load(Ordering::Relaxed)
+    }
+}
+
+/// Stops accepting and closes everything still open through the proxy
+impl Drop for ChaosProxy {
+    fn drop(&mut self) {
+        self.accept_task.abort();
+        self.reset_connections();
+    }
+}
+
+async fn accept_loop(listener: TcpListener, state: Arc<ProxyState>) {
+    loop {
+        let (client, peer) = match listener.accept().await {
+            Ok(accepted) => accepted,
+            Err(e) => {
+                warn!("Chaos proxy failed to accept connection: {e}");
+                continue;
+            }
+        };
+        if state.refusing.load(Ordering::Relaxed) {
+            debug!("Chaos proxy refusing connection from {peer}");
+            continue;
+        }
+        let state = state.clone();
+        // Subscribed before spawning so a reset issued right after accepting still applies
+        let reset = state.reset.subscribe();
+        tokio::spawn(async move {
+            let upstream = match TcpStream::connect(state.target).await {
+                Ok(upstream) => upstream,
+                Err(e) => {
+                    debug!("Chaos proxy failed to connect to {}: {e}", state.target);
+                    return;
+                }
+            };
+            state.connection_count.fetch_add(1, Ordering::Relaxed);
+            state.active_connections.fetch_add(1, Ordering::Relaxed);
+            proxy_connection(client, upstream, reset, &state).await;
+            state.active_connections.fetch_sub(1, Ordering::Relaxed);
+        });
+    }
+}
+
+/// Forwards both directions until either side closes or errors, or the connection is reset
+async fn proxy_connection(
+    client: TcpStream,
+    upstream: TcpStream,
+    mut reset: broadcast::Receiver<()>,
+    state: &Arc<ProxyState>,
+) {
+    let _ = client.set_nodelay(true);
+    let _ = upstream.set_nodelay(true);
+    let (client_read, client_write) = client.into_split();
+    let (upstream_read, upstream_write) = upstream.into_split();
+    tokio::select! {
+        _ = reset.recv() => {
+            debug!("Chaos proxy resetting connection to {}", state.target);
+        }
+        res = async {
+            tokio::try_join!(
+                forward(client_read, upstream_write, state.clone()),
+                forward(upstream_read, client_write, state.clone())
+            )
+        } => {
+            if let Err(e) = res {
+                debug!("Chaos proxy connection to {} closed: {e}", state.target);
+            }
+        }
+    }
+    // Dropping the halves here closes both sides
+}
+
+/// Copies data from one side to the other, holding each chunk until its latency has elapsed
+async fn forward(
+    mut from: OwnedReadHalf,
+    mut to: OwnedWriteHalf,
+    state: Arc<ProxyState>,
+) -> std::io::Result<()> {
+    // Reading and writing are decoupled so latency delays data without limiting throughput
+    let (tx, mut rx) = mpsc::unbounded_channel::<(Instant, Vec<u8>)>();
+    let read_state = state.clone();
+    let read = async move {
+        let mut buf = vec![0; 16 * 1024];
+        loop {
+            let n = from.read(&mut buf).await?;
+            if n == 0 {
+                return Ok::<(), std::io::Error>(());
+            }
+            let deadline = Instant::now() + *read_state.latency.lock().unwrap();
+            // Only fails if the write side has already errored, which ends the connection anyway
+            let _ = tx.send((deadline, buf[..n].to_vec()));
+        }
+    };
+    let write = async move {
+        let mut stalled = state.stalled.subscribe();
+        while let Some((deadline, data)) = rx.recv().await {
+            tokio::time::sleep_until(deadline).await;
+            let _ = stalled.wait_for(|stalled| !*stalled).await;
+            to.write_all(&data).await?;
+        }
+        to.shutdown().await
+    };
+    tokio::try_join!(read, write).map(|_| ())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    /// Starts a server that echoes back anything sent to it
+    async fn echo_server() -> SocketAddr {
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move {
+            while let Ok((mut stream, _)) = listener.accept().await {
+                tokio::spawn(async move {
+                    let (mut read, mut write) = stream.split();
+                    let _ = tokio::io::copy(&mut read, &mut write).await;
+                });
+            }
+        });
+        addr
+    }
+
+    async fn echo(stream: &mut TcpStream, msg: &[u8]) -> std::io::Result<Vec<u8>> {
+        stream.write_all(msg).await?;
+        let mut buf = vec![0; msg.len()];
+        stream.read_exact(&mut buf).await?;
+        Ok(buf)
+    }
+
+    #[tokio::test]
+    async fn forwards_with_latency() {
+        let proxy = ChaosProxy::new(echo_server().await).await.unwrap();
+        let mut stream = TcpStream::connect(proxy.local_addr()).await.unwrap();
+        assert_eq!(echo(&mut stream, b"hello").await.unwrap(), b"hello");
+        assert_eq!(proxy.connection_count(), 1);
+
+        proxy.set_latency(Duration::from_millis(100));
+        let start = std::time::Instant::now();
+        assert_eq!(echo(&mut stream, b"slow").await.unwrap(), b"slow");
+        // Applied once in each direction
+        assert!(start.elapsed() >= Duration::from_millis(200));
+    }
+
+    #[tokio::test]
+    async fn reset_closes_connections() {
+        let proxy = ChaosProxy::new(echo_server().await).await.unwrap();
+        let mut stream = TcpStream::connect(proxy.local_addr()).await.unwrap();
+        echo(&mut stream, b"hello").await.unwrap();
+        assert_eq!(proxy.active_connections(), 1);
+
+        proxy.reset_connections();
+        let mut buf = [0; 1];
+        let res = tokio::time::timeout(Duration::from_secs(1), stream.read(&mut buf))
+            .await
+            .unwrap();
+        assert!(matches!(res, Ok(0) | Err(_)));
+
+        // New connections work as normal
+        let mut stream = TcpStream::connect(proxy.local_addr()).await.unwrap();
+        assert_eq!(echo(&mut stream, b"again").await.unwrap(), b"again");
+        assert_eq!(proxy.connection_count(), 2);
+    }
+
+    #[tokio::test]
+    async fn refusing_closes_new_connections() {
+        let proxy = ChaosProxy::new(echo_server().await).await.unwrap();
+        proxy.set_refusing(true);
+        let mut stream = TcpStream::connect(proxy.local_addr()).await.unwrap();
+        let res = tokio::time::timeout(Duration::from_secs(1), echo(&mut stream, b"hello"))
+            .await
+            .unwrap();
+        assert!(res.is_err());
+        assert_eq!(proxy.connection_count(), 0);
+    }
+
+    #[tokio::test]
+    async fn stalled_holds_data_until_resumed() {
+        let proxy = ChaosProxy::new(echo_server().await).await.unwrap();
+        let mut stream = TcpStream::connect(proxy.local_addr()).await.unwrap();
+        proxy.set_stalled(true);
+        stream.write_all(b"held").await.unwrap();
+        let mut buf = [0; 4];
+        assert!(
+            tokio::time::timeout(Duration::from_millis(200), stream.read_exact(&mut buf))
+                .await
+                .is_err()
+        );
+
+        proxy.set_stalled(false);
+        tokio::time::timeout(Duration::from_secs(1), stream.read_exact(&mut buf))
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(&buf, b"held");
+    }
+}
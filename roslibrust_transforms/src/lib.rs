@@ -52,6 +52,7 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use chrono::{DateTime, Utc};
+use roslibrust_common::clock::Clock;
 use roslibrust_common::{Publish, RosMessageType, Subscribe, TopicProvider};
 use tokio::sync::{broadcast, RwLock};
 use tokio_util::sync::CancellationToken;
@@ -113,6 +114,8 @@ pub trait TFMessageType: RosMessageType + Send + Clone + 'static {
 pub struct TransformManager<M: TFMessageType, P: Publish<M> + Send + Sync> {
     registry: Arc<RwLock<Registry>>,
     buffer_duration: Duration,
+    /// Clock that timeouts are measured on
+    clock: Clock,
     /// Broadcast channel to notify waiters when transforms are added
     transform_notify: broadcast::Sender<()>,
     /// Cancellation token to shut down background tasks when dropped
@@ -199,6 +202,7 @@ impl<M: TFMessageType, P: Publish<M> + Send + Sync> TransformManager<M, P> {
         Ok(TransformManager {
             registry,
             buffer_duration,
+            clock: Clock::wall(),
             transform_notify,
             cancel_token,
             tf_publisher,
@@ -207,6 +211,28 @@ impl<M: TFMessageType, P: Publish<M> + Send + Sync> TransformManager<M, P> {
         })
     }
 
+    /// Measures [Self::wait_for_transform] timeouts and [Self::now] on `clock` rather than the wall clock, e.g. a
+    /// [SimClock](roslibrust_common::clock::SimClock) when running in simulation.
+    ///
+    /// Note: expiry of old transforms from the buffer is handled by the `transforms` crate, which uses the wall
+    /// clock.
+    pub fn with_clock(mut self, clock: Clock) -> Self {
+        self.clock = clock;
+        self
+    }
+
+    /// The current time of the manager's clock, for looking up the latest transforms.
+    pub fn now(&self) -> Timestamp {
+        let since_epoch = self
+            .clock
+            .now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default();
+        Timestamp {
+            t: since_epoch.as_nanos(),
+        }
+    }
+
     /// Background tokio task to process incoming TF messages.
     async fn process_tf_messages<S: Subscribe<M>>(
         mut subscriber: S,
@@ -323,7 +349,7 @@ impl<M: TFMessageType, P: Publish<M> + Send + Sync> TransformManager<M, P> {
     ///
     /// This method will poll the registry until the transform is available or until the timeout
     /// is reached. If `timeout` is `None`, the method will use the buffer duration configured
-    /// in the constructor as the timeout.
+    /// in the constructor as the timeout. The timeout is measured on the clock set with [Self::with_clock].
     ///
     /// # Arguments
     ///
@@ -369,7 +395,7 @@ impl<M: TFMessageType, P: Publish<M> + Send + Sync> TransformManager<M, P> {
         timeout: Option<Duration>,
     ) -> Result<transforms::Transform, TransformManagerError> {
         let timeout_duration = timeout.unwrap_or(self.buffer_duration);
-        let deadline = tokio::time::Instant::now() + timeout_duration;
+        let deadline = self.clock.now() + timeout_duration;
 
         // Subscribe to transform notifications
         let mut receiver = self.transform_notify.subscribe();
@@ -384,8 +410,7 @@ impl<M: TFMessageType, P: Publish<M> + Send + Sync> TransformManager<M, P> {
             }
 
             // Wait for either a notification or timeout
-            let remaining = deadline.saturating_duration_since(tokio::time::Instant::now());
-            if remaining.is_zero() {
+            if self.clock.now() >= deadline {
                 return Err(TransformManagerError::Timeout(
                     target_frame.to_string(),
                     source_frame.to_string(),
@@ -395,7 +420,7 @@ impl<M: TFMessageType, P: Publish<M> + Send + Sync> TransformManager<M, P> {
 
             // Wait for either the final deadline to occur, or for a notification that a transform has been added
             tokio::select! {
-                _ = tokio::time::sleep(remaining) => {
+                _ = self.clock.sleep_until(deadline) => {
                     // Timeout expired - do one final check then return error
                     let mut registry = self.registry.write().await;
                     if let Ok(transform) = registry.get_transform(target_frame, source_frame, time) {
@@ -44,13 +44,16 @@ int32 sec
 # e.g.
 # The duration -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The duration 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+uint32 nanosec
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xe8, 0xd0, 0x09, 0xf6, 0x59, 0x81, 0x6f, 0x75, 0x8b, 0x75, 0x33, 0x4e, 0xe1, 0xa9,
                 0xca, 0x5b, 0x5c, 0x0b, 0x85, 0x98, 0x43, 0x26, 0x1f, 0x14, 0xc7, 0xf9, 0x37, 0x34,
                 0x95, 0x99, 0xd9, 0x3b,
             ];
             const ROS2_TYPE_NAME: &'static str = "builtin_interfaces::msg::dds_::Duration_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(8usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -80,13 +83,16 @@ int32 sec
 # e.g.
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+uint32 nanosec
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xb1, 0x06, 0x23, 0x5e, 0x25, 0xa4, 0xc5, 0xed, 0x35, 0x09, 0x8a, 0xa0, 0xa6, 0x1a,
                 0x3e, 0xe9, 0xc9, 0xb1, 0x8d, 0x19, 0x7f, 0x39, 0x8b, 0x0e, 0x42, 0x06, 0xce, 0xa9,
                 0xac, 0xf9, 0xc1, 0x97,
             ];
             const ROS2_TYPE_NAME: &'static str = "builtin_interfaces::msg::dds_::Time_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(8usize);
         }
     }
     #[allow(unused_imports)]
@@ -115,6 +121,7 @@ uint32 nanosec"####;
             const DEFINITION: &'static str = r####"# This expresses acceleration in free space broken into its linear and angular parts.
 Vector3  linear
 Vector3  angular
+
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space. 
@@ -126,13 +133,16 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xdc, 0x44, 0x82, 0x43, 0xde, 0xd9, 0xb1, 0xfc, 0xbc, 0xca, 0x24, 0xab, 0xa0, 0xc2,
                 0x2f, 0x01, 0x3d, 0xae, 0x06, 0xc3, 0x54, 0xba, 0x2d, 0x84, 0x95, 0x71, 0xc0, 0xa2,
                 0xa3, 0xf5, 0x7c, 0xa0,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Accel_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(48usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -155,23 +165,29 @@ float64 z"####;
             const DEFINITION: &'static str = r####"# An accel with reference coordinate frame and timestamp
 Header header
 Accel accel
+
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data 
+# in a particular coordinate frame.
+# 
+# sequence ID: consecutively increasing ID 
+uint32 seq
+#Two-integer timestamp that is expressed as:
+# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
+# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
+# time-handling sugar is provided by the client library
+time stamp
+#Frame this data is associated with
+string frame_id
+
 ================================================================================
 MSG: geometry_msgs/Accel
 # This expresses acceleration in free space broken into its linear and angular parts.
 Vector3  linear
 Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space. 
@@ -184,27 +200,19 @@ MSG: geometry_msgs/Vector3
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data 
-# in a particular coordinate frame.
-# 
-# sequence ID: consecutively increasing ID 
-uint32 seq
-#Two-integer timestamp that is expressed as:
-# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
-# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
-# time-handling sugar is provided by the client library
-time stamp
-#Frame this data is associated with
-string frame_id"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x47, 0xf7, 0x47, 0xd2, 0x60, 0x91, 0xb8, 0x05, 0x7b, 0xd8, 0xe4, 0xc4, 0x9d, 0x9d,
                 0x5c, 0xea, 0xcf, 0x6a, 0xe3, 0x92, 0xc1, 0xcc, 0xea, 0x53, 0x22, 0x3f, 0x34, 0x6f,
                 0x9c, 0x59, 0x53, 0x2b,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::AccelStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#accel)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -235,23 +243,13 @@ Accel accel
 # In order, the parameters are:
 # (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)
 float64[36] covariance
+
 ================================================================================
 MSG: geometry_msgs/Accel
 # This expresses acceleration in free space broken into its linear and angular parts.
 Vector3  linear
 Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space. 
@@ -263,13 +261,16 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x23, 0x0d, 0x51, 0xbd, 0x53, 0xbc, 0x36, 0xf2, 0x60, 0x57, 0x4e, 0x73, 0xb4, 0x29,
                 0x41, 0xce, 0xfe, 0x44, 0x68, 0x47, 0x53, 0x48, 0x0b, 0x6f, 0xc3, 0x30, 0xc0, 0x32,
                 0xc5, 0xdb, 0x59, 0x97,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::AccelWithCovariance_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(336usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -292,23 +293,23 @@ float64 z"####;
             const DEFINITION: &'static str = r####"# This represents an estimated accel with reference coordinate frame and timestamp.
 Header header
 AccelWithCovariance accel
+
 ================================================================================
-MSG: geometry_msgs/Accel
-# This expresses acceleration in free space broken into its linear and angular parts.
-Vector3  linear
-Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data 
+# in a particular coordinate frame.
+# 
+# sequence ID: consecutively increasing ID 
+uint32 seq
+#Two-integer timestamp that is expressed as:
+# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
+# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
+# time-handling sugar is provided by the client library
+time stamp
+#Frame this data is associated with
+string frame_id
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/AccelWithCovariance
 # This expresses acceleration in free space with uncertainty.
@@ -320,35 +321,13 @@ Accel accel
 # In order, the parameters are:
 # (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)
 float64[36] covariance
+
 ================================================================================
 MSG: geometry_msgs/Accel
 # This expresses acceleration in free space broken into its linear and angular parts.
 Vector3  linear
 Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
-
-float64 x
-float64 y
-float64 z
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space. 
@@ -361,21 +340,7 @@ MSG: geometry_msgs/Vector3
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data 
-# in a particular coordinate frame.
-# 
-# sequence ID: consecutively increasing ID 
-uint32 seq
-#Two-integer timestamp that is expressed as:
-# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
-# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
-# time-handling sugar is provided by the client library
-time stamp
-#Frame this data is associated with
-string frame_id"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xa3, 0x03, 0x65, 0xcc, 0x01, 0x7d, 0x2c, 0x85, 0xeb, 0x2d, 0x31, 0x0b, 0xb4, 0xf7,
                 0x81, 0xce, 0x0d, 0x36, 0x76, 0x22, 0xb0, 0x6e, 0x78, 0x50, 0x8f, 0x00, 0x0f, 0x37,
@@ -383,6 +348,12 @@ string frame_id"####;
             ];
             const ROS2_TYPE_NAME: &'static str =
                 "geometry_msgs::msg::dds_::AccelWithCovarianceStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#accel)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -424,6 +395,7 @@ float64 ixz
 float64 iyy
 float64 iyz
 float64 izz
+
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space. 
@@ -435,13 +407,16 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x2d, 0xdd, 0x5d, 0xab, 0x5c, 0x34, 0x78, 0x25, 0xba, 0x2e, 0x56, 0xc8, 0x95, 0xdd,
                 0xcc, 0xfd, 0x0b, 0x8e, 0xfe, 0x53, 0xae, 0x93, 0x1b, 0xf6, 0x7f, 0x90, 0x55, 0x29,
                 0x93, 0x0b, 0x4b, 0xd7,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Inertia_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(80usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -463,6 +438,23 @@ float64 z"####;
             const MD5SUM: &'static str = "ddee48caeab5a966c5e8d166654a9ac7";
             const DEFINITION: &'static str = r####"Header header
 Inertia inertia
+
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data 
+# in a particular coordinate frame.
+# 
+# sequence ID: consecutively increasing ID 
+uint32 seq
+#Two-integer timestamp that is expressed as:
+# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
+# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
+# time-handling sugar is provided by the client library
+time stamp
+#Frame this data is associated with
+string frame_id
+
 ================================================================================
 MSG: geometry_msgs/Inertia
 # Mass [kg]
@@ -481,18 +473,7 @@ float64 ixz
 float64 iyy
 float64 iyz
 float64 izz
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space. 
@@ -505,27 +486,19 @@ MSG: geometry_msgs/Vector3
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data 
-# in a particular coordinate frame.
-# 
-# sequence ID: consecutively increasing ID 
-uint32 seq
-#Two-integer timestamp that is expressed as:
-# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
-# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
-# time-handling sugar is provided by the client library
-time stamp
-#Frame this data is associated with
-string frame_id"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xbe, 0x07, 0x62, 0x73, 0xdb, 0xb8, 0xe5, 0xac, 0x72, 0x23, 0x2e, 0x91, 0xe5, 0x9b,
                 0xc3, 0x95, 0x63, 0xca, 0x1d, 0xb7, 0x7a, 0xd2, 0x0c, 0x2f, 0xa0, 0x3f, 0x1d, 0xf0,
                 0x61, 0xdb, 0x7d, 0xe9,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::InertiaStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#inertia)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -549,13 +522,16 @@ string frame_id"####;
             const DEFINITION: &'static str = r####"# This contains the position of a point in free space
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x69, 0x63, 0x08, 0x48, 0x42, 0xa9, 0xb0, 0x44, 0x94, 0xd6, 0xb2, 0x94, 0x1d, 0x11,
                 0x44, 0x47, 0x08, 0xd8, 0x92, 0xda, 0x2f, 0x4b, 0x09, 0x84, 0x3b, 0x9c, 0x43, 0xf4,
                 0x2a, 0x7f, 0x68, 0x81,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Point_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(24usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -586,13 +562,16 @@ float64 z"####;
 
 float32 x
 float32 y
-float32 z"####;
+float32 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x2f, 0xc4, 0xdb, 0x7c, 0xae, 0x16, 0xa4, 0x58, 0x2c, 0x79, 0xa5, 0x6b, 0x66, 0x17,
                 0x3a, 0x8d, 0x48, 0xd5, 0x2c, 0x7d, 0xc5, 0x20, 0xdd, 0xc5, 0x5a, 0x0d, 0x4b, 0xcf,
                 0x2a, 0x4b, 0xfd, 0xbc,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Point32_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(12usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -615,12 +594,7 @@ float32 z"####;
             const DEFINITION: &'static str = r####"# This represents a Point with reference coordinate frame and timestamp
 Header header
 Point point
-================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
+
 ================================================================================
 MSG: std_msgs/Header
 # Standard metadata for higher-level stamped data types.
@@ -635,13 +609,27 @@ uint32 seq
 # time-handling sugar is provided by the client library
 time stamp
 #Frame this data is associated with
-string frame_id"####;
+string frame_id
+
+================================================================================
+MSG: geometry_msgs/Point
+# This contains the position of a point in free space
+float64 x
+float64 y
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x16, 0x48, 0x46, 0x59, 0xa6, 0x38, 0x7c, 0xf8, 0xf1, 0xda, 0x65, 0x26, 0xb9, 0x4b,
                 0x8d, 0xa0, 0x6a, 0xc4, 0x8d, 0x0c, 0x03, 0xc9, 0x5e, 0x5b, 0xf7, 0xec, 0x4c, 0x08,
                 0xfe, 0xbb, 0x77, 0x29,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::PointStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#point)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -662,6 +650,7 @@ string frame_id"####;
             const MD5SUM: &'static str = "cd60a26494a087f577976f0329fa120e";
             const DEFINITION: &'static str = r####"#A specification of a polygon where the first and last points are assumed to be connected
 Point32[] points
+
 ================================================================================
 MSG: geometry_msgs/Point32
 # This contains the position of a point in free space(with 32 bits of precision).
@@ -674,13 +663,23 @@ MSG: geometry_msgs/Point32
 
 float32 x
 float32 y
-float32 z"####;
+float32 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x37, 0x82, 0xf9, 0xf0, 0xbf, 0x04, 0x49, 0x64, 0xd6, 0x92, 0xd6, 0xc0, 0x17, 0xd7,
                 0x05, 0xe3, 0x76, 0x11, 0xaf, 0xb1, 0xf0, 0xbf, 0x6a, 0x9d, 0xee, 0x24, 0x8a, 0x7d,
                 0xda, 0x0f, 0x78, 0x4a,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Polygon_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = 4 + self
+                    .r#points
+                    .iter()
+                    .map(::roslibrust::RosMessageType::encoded_len)
+                    .sum::<::std::option::Option<usize>>()?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -703,23 +702,28 @@ float32 z"####;
             const DEFINITION: &'static str = r####"# This represents a Polygon with reference coordinate frame and timestamp
 Header header
 Polygon polygon
+
 ================================================================================
-MSG: geometry_msgs/Point32
-# This contains the position of a point in free space(with 32 bits of precision).
-# It is recommeded to use Point wherever possible instead of Point32.  
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data 
+# in a particular coordinate frame.
 # 
-# This recommendation is to promote interoperability.  
-#
-# This message is designed to take up less space when sending
-# lots of points at once, as in the case of a PointCloud.  
+# sequence ID: consecutively increasing ID 
+uint32 seq
+#Two-integer timestamp that is expressed as:
+# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
+# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
+# time-handling sugar is provided by the client library
+time stamp
+#Frame this data is associated with
+string frame_id
 
-float32 x
-float32 y
-float32 z
 ================================================================================
 MSG: geometry_msgs/Polygon
 #A specification of a polygon where the first and last points are assumed to be connected
 Point32[] points
+
 ================================================================================
 MSG: geometry_msgs/Point32
 # This contains the position of a point in free space(with 32 bits of precision).
@@ -733,27 +737,19 @@ MSG: geometry_msgs/Point32
 float32 x
 float32 y
 float32 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data 
-# in a particular coordinate frame.
-# 
-# sequence ID: consecutively increasing ID 
-uint32 seq
-#Two-integer timestamp that is expressed as:
-# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
-# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
-# time-handling sugar is provided by the client library
-time stamp
-#Frame this data is associated with
-string frame_id"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xff, 0xfe, 0xb2, 0xb4, 0xa8, 0xd7, 0x52, 0xeb, 0x71, 0xf4, 0x2f, 0x5e, 0xe5, 0xbf,
                 0x2e, 0x0a, 0x78, 0x7d, 0x18, 0xd1, 0xcc, 0x94, 0x03, 0xea, 0x8d, 0x87, 0x8d, 0x1e,
                 0x8f, 0x88, 0x07, 0xaf,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::PolygonStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#polygon)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -776,12 +772,14 @@ string frame_id"####;
             const DEFINITION: &'static str = r####"# A representation of pose in free space, composed of position and orientation. 
 Point position
 Quaternion orientation
+
 ================================================================================
 MSG: geometry_msgs/Point
 # This contains the position of a point in free space
 float64 x
 float64 y
 float64 z
+
 ================================================================================
 MSG: geometry_msgs/Quaternion
 # This represents an orientation in free space in quaternion form.
@@ -789,13 +787,16 @@ MSG: geometry_msgs/Quaternion
 float64 x
 float64 y
 float64 z
-float64 w"####;
+float64 w
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xd5, 0x01, 0x95, 0x4e, 0x94, 0x76, 0xce, 0xa2, 0x99, 0x69, 0x84, 0xe8, 0x12, 0x05,
                 0x4b, 0x68, 0x02, 0x6a, 0xe0, 0xbf, 0xae, 0x78, 0x9d, 0x9a, 0x10, 0xb2, 0x3d, 0xaf,
                 0x35, 0xcc, 0x90, 0xfa,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Pose_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(56usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -828,13 +829,16 @@ float64 w"####;
 
 float64 x
 float64 y
-float64 theta"####;
+float64 theta
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xd6, 0x8e, 0xfa, 0x5b, 0x46, 0xe7, 0x0f, 0x7b, 0x16, 0xca, 0x23, 0x08, 0x54, 0x74,
                 0xfd, 0xac, 0x5a, 0x44, 0xb6, 0x38, 0x78, 0x3e, 0xc4, 0x2f, 0x66, 0x1d, 0xa6, 0x4d,
                 0xa4, 0x72, 0x4c, 0xcc,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Pose2D_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(24usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -859,23 +863,36 @@ float64 theta"####;
 Header header
 
 Pose[] poses
+
 ================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data 
+# in a particular coordinate frame.
+# 
+# sequence ID: consecutively increasing ID 
+uint32 seq
+#Two-integer timestamp that is expressed as:
+# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
+# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
+# time-handling sugar is provided by the client library
+time stamp
+#Frame this data is associated with
+string frame_id
+
 ================================================================================
 MSG: geometry_msgs/Pose
 # A representation of pose in free space, composed of position and orientation. 
 Point position
 Quaternion orientation
+
 ================================================================================
 MSG: geometry_msgs/Point
 # This contains the position of a point in free space
 float64 x
 float64 y
 float64 z
+
 ================================================================================
 MSG: geometry_msgs/Quaternion
 # This represents an orientation in free space in quaternion form.
@@ -884,35 +901,24 @@ float64 x
 float64 y
 float64 z
 float64 w
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
-
-float64 x
-float64 y
-float64 z
-float64 w
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data 
-# in a particular coordinate frame.
-# 
-# sequence ID: consecutively increasing ID 
-uint32 seq
-#Two-integer timestamp that is expressed as:
-# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
-# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
-# time-handling sugar is provided by the client library
-time stamp
-#Frame this data is associated with
-string frame_id"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x3d, 0xfa, 0xa2, 0x97, 0xe8, 0xef, 0x84, 0x2d, 0x04, 0xd9, 0xea, 0x4f, 0x53, 0x78,
                 0xa8, 0xe1, 0xd1, 0xff, 0x86, 0x6f, 0x29, 0x8d, 0xe7, 0xec, 0xf4, 0x35, 0x41, 0xf8,
                 0x61, 0x5d, 0x50, 0xed,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::PoseArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + 4
+                    + self
+                        .r#poses
+                        .iter()
+                        .map(::roslibrust::RosMessageType::encoded_len)
+                        .sum::<::std::option::Option<usize>>()?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -935,31 +941,36 @@ string frame_id"####;
             const DEFINITION: &'static str = r####"# A Pose with reference coordinate frame and timestamp
 Header header
 Pose pose
+
 ================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data 
+# in a particular coordinate frame.
+# 
+# sequence ID: consecutively increasing ID 
+uint32 seq
+#Two-integer timestamp that is expressed as:
+# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
+# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
+# time-handling sugar is provided by the client library
+time stamp
+#Frame this data is associated with
+string frame_id
+
 ================================================================================
 MSG: geometry_msgs/Pose
 # A representation of pose in free space, composed of position and orientation. 
 Point position
 Quaternion orientation
+
 ================================================================================
 MSG: geometry_msgs/Point
 # This contains the position of a point in free space
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
 
-float64 x
-float64 y
-float64 z
-float64 w
 ================================================================================
 MSG: geometry_msgs/Quaternion
 # This represents an orientation in free space in quaternion form.
@@ -968,27 +979,19 @@ float64 x
 float64 y
 float64 z
 float64 w
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data 
-# in a particular coordinate frame.
-# 
-# sequence ID: consecutively increasing ID 
-uint32 seq
-#Two-integer timestamp that is expressed as:
-# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
-# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
-# time-handling sugar is provided by the client library
-time stamp
-#Frame this data is associated with
-string frame_id"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x13, 0x7f, 0xcc, 0xf0, 0xc6, 0x2a, 0x8f, 0x26, 0xbf, 0x49, 0x39, 0x74, 0x73, 0x9b,
                 0x58, 0x8f, 0xb8, 0x0d, 0x4b, 0x5c, 0x8d, 0x3a, 0xf6, 0xfe, 0xda, 0x4d, 0x36, 0x45,
                 0x20, 0xec, 0x22, 0x6e,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::PoseStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#pose)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1019,31 +1022,20 @@ Pose pose
 # In order, the parameters are:
 # (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)
 float64[36] covariance
-================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
+
 ================================================================================
 MSG: geometry_msgs/Pose
 # A representation of pose in free space, composed of position and orientation. 
 Point position
 Quaternion orientation
+
 ================================================================================
 MSG: geometry_msgs/Point
 # This contains the position of a point in free space
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
 
-float64 x
-float64 y
-float64 z
-float64 w
 ================================================================================
 MSG: geometry_msgs/Quaternion
 # This represents an orientation in free space in quaternion form.
@@ -1051,13 +1043,16 @@ MSG: geometry_msgs/Quaternion
 float64 x
 float64 y
 float64 z
-float64 w"####;
+float64 w
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x9a, 0x7c, 0x0f, 0xd2, 0x34, 0xb7, 0xf4, 0x5c, 0x60, 0x98, 0x74, 0x5e, 0xcc, 0xcd,
                 0x77, 0x3c, 0xa1, 0x08, 0x56, 0x70, 0xe6, 0x41, 0x07, 0x13, 0x53, 0x97, 0xae, 0xe3,
                 0x1c, 0x02, 0xe1, 0xbb,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::PoseWithCovariance_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(344usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1081,31 +1076,23 @@ float64 w"####;
 
 Header header
 PoseWithCovariance pose
+
 ================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
-================================================================================
-MSG: geometry_msgs/Pose
-# A representation of pose in free space, composed of position and orientation. 
-Point position
-Quaternion orientation
-================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data 
+# in a particular coordinate frame.
+# 
+# sequence ID: consecutively increasing ID 
+uint32 seq
+#Two-integer timestamp that is expressed as:
+# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
+# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
+# time-handling sugar is provided by the client library
+time stamp
+#Frame this data is associated with
+string frame_id
 
-float64 x
-float64 y
-float64 z
-float64 w
 ================================================================================
 MSG: geometry_msgs/PoseWithCovariance
 # This represents a pose in free space with uncertainty.
@@ -1117,39 +1104,20 @@ Pose pose
 # In order, the parameters are:
 # (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)
 float64[36] covariance
-================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
+
 ================================================================================
 MSG: geometry_msgs/Pose
 # A representation of pose in free space, composed of position and orientation. 
 Point position
 Quaternion orientation
+
 ================================================================================
 MSG: geometry_msgs/Point
 # This contains the position of a point in free space
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
-
-float64 x
-float64 y
-float64 z
-float64 w
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
 
-float64 x
-float64 y
-float64 z
-float64 w
 ================================================================================
 MSG: geometry_msgs/Quaternion
 # This represents an orientation in free space in quaternion form.
@@ -1158,21 +1126,7 @@ float64 x
 float64 y
 float64 z
 float64 w
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data 
-# in a particular coordinate frame.
-# 
-# sequence ID: consecutively increasing ID 
-uint32 seq
-#Two-integer timestamp that is expressed as:
-# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
-# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
-# time-handling sugar is provided by the client library
-time stamp
-#Frame this data is associated with
-string frame_id"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xba, 0x09, 0xde, 0x0d, 0xa1, 0x18, 0xc9, 0xd7, 0x17, 0x53, 0x10, 0xf7, 0x06, 0xfd,
                 0x1b, 0x93, 0x10, 0x5e, 0xe7, 0xf7, 0x12, 0x88, 0xc7, 0x1c, 0x23, 0xa0, 0xd1, 0xe9,
@@ -1180,6 +1134,12 @@ string frame_id"####;
             ];
             const ROS2_TYPE_NAME: &'static str =
                 "geometry_msgs::msg::dds_::PoseWithCovarianceStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#pose)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1206,13 +1166,16 @@ string frame_id"####;
 float64 x
 float64 y
 float64 z
-float64 w"####;
+float64 w
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x8a, 0x76, 0x5f, 0x66, 0x77, 0x8c, 0x8f, 0xf7, 0xc8, 0xab, 0x94, 0xaf, 0xcc, 0x59,
                 0x0a, 0x2e, 0xd5, 0x32, 0x5a, 0x1d, 0x9a, 0x07, 0x6f, 0xff, 0xf3, 0x8f, 0xbc, 0xe3,
                 0x6f, 0x45, 0x86, 0x84,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Quaternion_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(32usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1236,14 +1199,7 @@ float64 w"####;
 
 Header header
 Quaternion quaternion
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
 
-float64 x
-float64 y
-float64 z
-float64 w
 ================================================================================
 MSG: std_msgs/Header
 # Standard metadata for higher-level stamped data types.
@@ -1258,13 +1214,29 @@ uint32 seq
 # time-handling sugar is provided by the client library
 time stamp
 #Frame this data is associated with
-string frame_id"####;
+string frame_id
+
+================================================================================
+MSG: geometry_msgs/Quaternion
+# This represents an orientation in free space in quaternion form.
+
+float64 x
+float64 y
+float64 z
+float64 w
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x65, 0x47, 0x55, 0x14, 0xfe, 0x6b, 0xf5, 0x8e, 0x6b, 0x0e, 0xcb, 0x47, 0xde, 0xc0,
                 0x6e, 0xef, 0xd4, 0x06, 0x79, 0xf6, 0x02, 0xa8, 0xc4, 0xcc, 0x6f, 0x1e, 0x49, 0xa0,
                 0xff, 0x45, 0xc8, 0x5d,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::QuaternionStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#quaternion)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1288,14 +1260,7 @@ string frame_id"####;
 
 Vector3 translation
 Quaternion rotation
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
 
-float64 x
-float64 y
-float64 z
-float64 w
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space. 
@@ -1307,13 +1272,25 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+
+================================================================================
+MSG: geometry_msgs/Quaternion
+# This represents an orientation in free space in quaternion form.
+
+float64 x
+float64 y
+float64 z
+float64 w
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xbe, 0xb8, 0x3f, 0xbe, 0x69, 0x86, 0x36, 0x35, 0x14, 0x61, 0xf6, 0xf3, 0x5d, 0x1a,
                 0xbb, 0x20, 0x01, 0x0c, 0x43, 0xd5, 0x53, 0x74, 0xd8, 0x1b, 0xd0, 0x41, 0xf1, 0xba,
                 0x25, 0x81, 0xfd, 0xdc,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Transform_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(56usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1344,28 +1321,30 @@ float64 z"####;
 Header header
 string child_frame_id # the frame id of the child frame
 Transform transform
+
 ================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data 
+# in a particular coordinate frame.
+# 
+# sequence ID: consecutively increasing ID 
+uint32 seq
+#Two-integer timestamp that is expressed as:
+# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
+# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
+# time-handling sugar is provided by the client library
+time stamp
+#Frame this data is associated with
+string frame_id
 
-float64 x
-float64 y
-float64 z
-float64 w
 ================================================================================
 MSG: geometry_msgs/Transform
 # This represents the transform between two coordinate frames in free space.
 
 Vector3 translation
 Quaternion rotation
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
 
-float64 x
-float64 y
-float64 z
-float64 w
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space. 
@@ -1378,39 +1357,30 @@ MSG: geometry_msgs/Vector3
 float64 x
 float64 y
 float64 z
+
 ================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
+MSG: geometry_msgs/Quaternion
+# This represents an orientation in free space in quaternion form.
 
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data 
-# in a particular coordinate frame.
-# 
-# sequence ID: consecutively increasing ID 
-uint32 seq
-#Two-integer timestamp that is expressed as:
-# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
-# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
-# time-handling sugar is provided by the client library
-time stamp
-#Frame this data is associated with
-string frame_id"####;
+float64 w
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x76, 0xc2, 0xd4, 0xb2, 0x4e, 0x42, 0x44, 0xae, 0x82, 0x0d, 0x8c, 0x98, 0x20, 0x90,
                 0x50, 0xcf, 0x29, 0x3b, 0xa0, 0x43, 0x15, 0xaa, 0xde, 0xf5, 0x59, 0xb3, 0xe5, 0xd4,
                 0xb9, 0xb8, 0x9d, 0x7c,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::TransformStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + 4
+                    + self.r#child_frame_id.len()
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#transform)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1433,6 +1403,7 @@ string frame_id"####;
             const DEFINITION: &'static str = r####"# This expresses velocity in free space broken into its linear and angular parts.
 Vector3  linear
 Vector3  angular
+
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space. 
@@ -1444,13 +1415,16 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x9c, 0x45, 0xbf, 0x16, 0xfe, 0x09, 0x83, 0xd8, 0x0e, 0x3c, 0xfe, 0x75, 0x0d, 0x68,
                 0x35, 0x84, 0x3d, 0x26, 0x5a, 0x9a, 0x6c, 0x46, 0xbd, 0x2e, 0x60, 0x9f, 0xcd, 0xdd,
                 0xe6, 0xfb, 0x8d, 0x2a,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Twist_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(48usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1473,35 +1447,7 @@ float64 z"####;
             const DEFINITION: &'static str = r####"# A twist with reference coordinate frame and timestamp
 Header header
 Twist twist
-================================================================================
-MSG: geometry_msgs/Twist
-# This expresses velocity in free space broken into its linear and angular parts.
-Vector3  linear
-Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
-
-float64 x
-float64 y
-float64 z
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
-
-float64 x
-float64 y
-float64 z
+
 ================================================================================
 MSG: std_msgs/Header
 # Standard metadata for higher-level stamped data types.
@@ -1516,13 +1462,39 @@ uint32 seq
 # time-handling sugar is provided by the client library
 time stamp
 #Frame this data is associated with
-string frame_id"####;
+string frame_id
+
+================================================================================
+MSG: geometry_msgs/Twist
+# This expresses velocity in free space broken into its linear and angular parts.
+Vector3  linear
+Vector3  angular
+
+================================================================================
+MSG: geometry_msgs/Vector3
+# This represents a vector in free space. 
+# It is only meant to represent a direction. Therefore, it does not
+# make sense to apply a translation to it (e.g., when applying a 
+# generic rigid transformation to a Vector3, tf2 will only apply the
+# rotation). If you want your data to be translatable too, use the
+# geometry_msgs/Point message instead.
+
+float64 x
+float64 y
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x6a, 0xdd, 0x90, 0x5e, 0x39, 0x19, 0x47, 0x17, 0x56, 0xa5, 0xd5, 0x69, 0x0a, 0x0a,
                 0x3b, 0x16, 0xe4, 0x69, 0x5c, 0x8c, 0xa4, 0xb7, 0x6b, 0xf7, 0x3d, 0xe7, 0x8a, 0x7c,
                 0xa1, 0xd9, 0x73, 0xf4,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::TwistStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#twist)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1553,23 +1525,13 @@ Twist twist
 # In order, the parameters are:
 # (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)
 float64[36] covariance
+
 ================================================================================
 MSG: geometry_msgs/Twist
 # This expresses velocity in free space broken into its linear and angular parts.
 Vector3  linear
 Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space. 
@@ -1581,13 +1543,16 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x49, 0xf5, 0x74, 0xf0, 0x33, 0xf0, 0x95, 0xd8, 0xb6, 0xcd, 0x1b, 0xea, 0xca, 0x5c,
                 0xa7, 0x92, 0x5e, 0x29, 0x6e, 0x84, 0xaf, 0x17, 0x16, 0xd1, 0x6c, 0x89, 0xd3, 0x8b,
                 0x05, 0x9c, 0x8c, 0x18,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::TwistWithCovariance_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(336usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1610,23 +1575,23 @@ float64 z"####;
             const DEFINITION: &'static str = r####"# This represents an estimated twist with reference coordinate frame and timestamp.
 Header header
 TwistWithCovariance twist
+
 ================================================================================
-MSG: geometry_msgs/Twist
-# This expresses velocity in free space broken into its linear and angular parts.
-Vector3  linear
-Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data 
+# in a particular coordinate frame.
+# 
+# sequence ID: consecutively increasing ID 
+uint32 seq
+#Two-integer timestamp that is expressed as:
+# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
+# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
+# time-handling sugar is provided by the client library
+time stamp
+#Frame this data is associated with
+string frame_id
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/TwistWithCovariance
 # This expresses velocity in free space with uncertainty.
@@ -1638,35 +1603,13 @@ Twist twist
 # In order, the parameters are:
 # (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)
 float64[36] covariance
+
 ================================================================================
 MSG: geometry_msgs/Twist
 # This expresses velocity in free space broken into its linear and angular parts.
 Vector3  linear
 Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
-
-float64 x
-float64 y
-float64 z
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space. 
@@ -1679,21 +1622,7 @@ MSG: geometry_msgs/Vector3
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data 
-# in a particular coordinate frame.
-# 
-# sequence ID: consecutively increasing ID 
-uint32 seq
-#Two-integer timestamp that is expressed as:
-# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
-# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
-# time-handling sugar is provided by the client library
-time stamp
-#Frame this data is associated with
-string frame_id"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x5d, 0x9c, 0xa5, 0x12, 0xb1, 0xd6, 0x4f, 0x68, 0x82, 0xc0, 0xe1, 0xed, 0x3f, 0x3e,
                 0x58, 0x85, 0x13, 0x56, 0x94, 0x27, 0xa0, 0x9f, 0x0d, 0x54, 0x95, 0xdb, 0xf1, 0xb0,
@@ -1701,6 +1630,12 @@ string frame_id"####;
             ];
             const ROS2_TYPE_NAME: &'static str =
                 "geometry_msgs::msg::dds_::TwistWithCovarianceStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#twist)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1730,13 +1665,16 @@ string frame_id"####;
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xcc, 0x12, 0xfe, 0x83, 0xe4, 0xc0, 0x27, 0x19, 0xf1, 0xce, 0x80, 0x70, 0xbf, 0xd1,
                 0x4a, 0xec, 0xd4, 0x0f, 0x75, 0xa9, 0x66, 0x96, 0xa6, 0x7a, 0x2a, 0x1f, 0x37, 0xf7,
                 0xdb, 0xb0, 0x76, 0x5d,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Vector3_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(24usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1759,18 +1697,7 @@ float64 z"####;
             const DEFINITION: &'static str = r####"# This represents a Vector3 with reference coordinate frame and timestamp
 Header header
 Vector3 vector
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: std_msgs/Header
 # Standard metadata for higher-level stamped data types.
@@ -1785,13 +1712,33 @@ uint32 seq
 # time-handling sugar is provided by the client library
 time stamp
 #Frame this data is associated with
-string frame_id"####;
+string frame_id
+
+================================================================================
+MSG: geometry_msgs/Vector3
+# This represents a vector in free space. 
+# It is only meant to represent a direction. Therefore, it does not
+# make sense to apply a translation to it (e.g., when applying a 
+# generic rigid transformation to a Vector3, tf2 will only apply the
+# rotation). If you want your data to be translatable too, use the
+# geometry_msgs/Point message instead.
+
+float64 x
+float64 y
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xcc, 0x30, 0xa6, 0x24, 0x13, 0xb4, 0x62, 0x5e, 0x67, 0xec, 0xe5, 0x7a, 0x3f, 0x06,
                 0x5e, 0x1b, 0x5d, 0x51, 0xd8, 0xcf, 0x6a, 0xa4, 0x08, 0x70, 0x61, 0x99, 0xc4, 0xe5,
                 0xe8, 0x26, 0x11, 0xfa,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Vector3Stamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#vector)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1815,6 +1762,7 @@ string frame_id"####;
 # its linear and angular parts.
 Vector3  force
 Vector3  torque
+
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space. 
@@ -1826,13 +1774,16 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x01, 0x8e, 0x85, 0x19, 0xd5, 0x7c, 0x16, 0xad, 0xbe, 0x97, 0xc9, 0xfe, 0x14, 0x60,
                 0xef, 0x21, 0xfe, 0xc7, 0xe3, 0x1b, 0xc5, 0x41, 0xde, 0x3d, 0x65, 0x3a, 0x35, 0x89,
                 0x56, 0x77, 0xce, 0x52,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Wrench_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(48usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -1855,24 +1806,30 @@ float64 z"####;
             const DEFINITION: &'static str = r####"# A wrench with reference coordinate frame and timestamp
 Header header
 Wrench wrench
+
 ================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space. 
-# It is only meant to represent a direction. Therefore, it does not
-# make sense to apply a translation to it (e.g., when applying a 
-# generic rigid transformation to a Vector3, tf2 will only apply the
-# rotation). If you want your data to be translatable too, use the
-# geometry_msgs/Point message instead.
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data 
+# in a particular coordinate frame.
+# 
+# sequence ID: consecutively increasing ID 
+uint32 seq
+#Two-integer timestamp that is expressed as:
+# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
+# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
+# time-handling sugar is provided by the client library
+time stamp
+#Frame this data is associated with
+string frame_id
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Wrench
 # This represents force in free space, separated into
 # its linear and angular parts.
 Vector3  force
 Vector3  torque
+
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space. 
@@ -1885,27 +1842,19 @@ MSG: geometry_msgs/Vector3
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data 
-# in a particular coordinate frame.
-# 
-# sequence ID: consecutively increasing ID 
-uint32 seq
-#Two-integer timestamp that is expressed as:
-# * stamp.sec: seconds (stamp_secs) since epoch (in Python the variable is called 'secs')
-# * stamp.nsec: nanoseconds since stamp_secs (in Python the variable is called 'nsecs')
-# time-handling sugar is provided by the client library
-time stamp
-#Frame this data is associated with
-string frame_id"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x91, 0x34, 0xc4, 0x7a, 0x80, 0xba, 0x54, 0xcd, 0x3d, 0x73, 0x1c, 0xde, 0x2b, 0x39,
                 0x5c, 0x0b, 0xf2, 0x71, 0x09, 0x07, 0x68, 0x47, 0x46, 0xb3, 0x97, 0xc2, 0x56, 0xea,
                 0x58, 0x63, 0xde, 0x17,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::WrenchStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#wrench)?;
+                ::std::option::Option::Some(len)
+            }
         }
     }
     #[allow(unused_imports)]
@@ -1954,6 +1903,7 @@ char[16] client_gid
 # Sequence number for the request
 # Combined with the client ID, this creates a unique ID for the service transaction
 int64 sequence_number
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -1966,13 +1916,16 @@ int32 sec
 # e.g.
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+uint32 nanosec
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x41, 0xbc, 0xbb, 0xe0, 0x7a, 0x75, 0xc9, 0xb5, 0x2b, 0xc9, 0x6b, 0xfd, 0x5c, 0x24,
                 0xd7, 0xf0, 0xfc, 0x0a, 0x08, 0xc0, 0xcb, 0x79, 0x21, 0xb3, 0x37, 0x3c, 0x57, 0x32,
                 0x34, 0x5a, 0x6f, 0x45,
             ];
             const ROS2_TYPE_NAME: &'static str = "service_msgs::msg::dds_::ServiceEventInfo_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(33usize);
         }
         #[allow(unused)]
         impl ServiceEventInfo {
@@ -2004,13 +1957,16 @@ uint32 nanosec"####;
         impl ::roslibrust::RosMessageType for Bool {
             const ROS_TYPE_NAME: &'static str = "std_msgs/Bool";
             const MD5SUM: &'static str = "8b94c1b53db61fb6aed406028ad6332a";
-            const DEFINITION: &'static str = r####"bool data"####;
+            const DEFINITION: &'static str = r####"bool data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xfe, 0xb9, 0x1e, 0x99, 0x5f, 0xf9, 0xeb, 0xd0, 0x9c, 0x0c, 0xb3, 0xd2, 0xae, 0xd1,
                 0x8b, 0x11, 0x07, 0x75, 0x85, 0x83, 0x9f, 0xb5, 0xdb, 0x80, 0x19, 0x3b, 0x62, 0xd7,
                 0x45, 0x28, 0xf6, 0xc9,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Bool_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(1usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2029,13 +1985,16 @@ uint32 nanosec"####;
         impl ::roslibrust::RosMessageType for Byte {
             const ROS_TYPE_NAME: &'static str = "std_msgs/Byte";
             const MD5SUM: &'static str = "ad736a2e8818154c487bb80fe42ce43b";
-            const DEFINITION: &'static str = r####"byte data"####;
+            const DEFINITION: &'static str = r####"byte data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xe2, 0x8c, 0xa2, 0xc6, 0x2f, 0x3f, 0xb1, 0x0c, 0x20, 0x78, 0x90, 0x75, 0x5a, 0xa7,
                 0xa5, 0xa7, 0x70, 0xcc, 0xde, 0x56, 0x46, 0xfd, 0x66, 0xb4, 0x52, 0xf4, 0x85, 0xc4,
                 0x80, 0x92, 0xf3, 0x27,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Byte_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(1usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2061,11 +2020,7 @@ uint32 nanosec"####;
 
 MultiArrayLayout  layout        # specification of data layout
 byte[]            data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
+
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # The multiarray declares a generic multi-dimensional array of a
@@ -2094,17 +2049,26 @@ uint32 data_offset        # padding elements at front of data
 # dim[2].stride = 3
 #
 # multiarray(i,j,k) refers to the ith row, jth column, and kth channel.
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x69, 0x2e, 0xff, 0x26, 0xdd, 0x8c, 0xa7, 0x62, 0x3e, 0x4e, 0x90, 0xa0, 0x82, 0xf7,
                 0xd8, 0x3f, 0x1c, 0xf5, 0xde, 0xb0, 0xb7, 0xba, 0x74, 0x8a, 0x2d, 0x4d, 0x5f, 0xbc,
                 0xa7, 0x91, 0xdb, 0x7d,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::ByteMultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len();
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2123,13 +2087,16 @@ uint32 stride  # stride of given dimension"####;
         impl ::roslibrust::RosMessageType for Char {
             const ROS_TYPE_NAME: &'static str = "std_msgs/Char";
             const MD5SUM: &'static str = "1bf77f25acecdedba0e224b162199717";
-            const DEFINITION: &'static str = r####"char data"####;
+            const DEFINITION: &'static str = r####"char data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x3a, 0xd2, 0xd0, 0x4d, 0xd2, 0x9b, 0xa1, 0x9d, 0x04, 0xb1, 0x66, 0x59, 0xaf, 0xa3,
                 0xcc, 0xae, 0xdd, 0x69, 0x19, 0x14, 0xb0, 0x2a, 0x64, 0xe8, 0x2e, 0x25, 0x2f, 0x2f,
                 0xa6, 0xa5, 0x86, 0xa9,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Char_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(1usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2154,13 +2121,16 @@ uint32 stride  # stride of given dimension"####;
             const DEFINITION: &'static str = r####"float32 r
 float32 g
 float32 b
-float32 a"####;
+float32 a
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x77, 0xa7, 0xa5, 0xb9, 0xae, 0x47, 0x73, 0x06, 0x09, 0x76, 0x65, 0x10, 0x6e, 0x04,
                 0x13, 0xba, 0x74, 0x44, 0x02, 0x45, 0xb1, 0xf3, 0xd0, 0xc6, 0xd6, 0x40, 0x5f, 0xe5,
                 0xc7, 0x81, 0x3f, 0xe8,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::ColorRGBA_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(16usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2179,13 +2149,16 @@ float32 a"####;
         impl ::roslibrust::RosMessageType for Duration {
             const ROS_TYPE_NAME: &'static str = "std_msgs/Duration";
             const MD5SUM: &'static str = "3e286caf4241d664e55f3ad380e2ae46";
-            const DEFINITION: &'static str = r####"duration data"####;
+            const DEFINITION: &'static str = r####"duration data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x64, 0x12, 0xd7, 0xf2, 0x6b, 0x75, 0xc8, 0xbd, 0xcd, 0x9b, 0x9f, 0xea, 0xe3, 0xa8,
                 0xa1, 0x15, 0x7f, 0x39, 0x50, 0x68, 0xa4, 0x0e, 0x97, 0xc0, 0x43, 0x7f, 0x1a, 0x34,
                 0xfe, 0x1a, 0x37, 0x94,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Duration_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(8usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2209,6 +2182,8 @@ float32 a"####;
                 0x26, 0xa9, 0x63, 0x12,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Empty_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(0usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2227,13 +2202,16 @@ float32 a"####;
         impl ::roslibrust::RosMessageType for Float32 {
             const ROS_TYPE_NAME: &'static str = "std_msgs/Float32";
             const MD5SUM: &'static str = "73fcbf46b49191e672908e50842a83d4";
-            const DEFINITION: &'static str = r####"float32 data"####;
+            const DEFINITION: &'static str = r####"float32 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x71, 0x70, 0xd3, 0xd8, 0xf8, 0x41, 0xf7, 0xbe, 0x31, 0x72, 0xce, 0x5f, 0x4f, 0x59,
                 0xf3, 0xa4, 0xd7, 0xf6, 0x3b, 0x04, 0x47, 0xe8, 0xb3, 0x33, 0x27, 0x60, 0x1a, 0xd6,
                 0x4d, 0x83, 0xd6, 0xe2,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Float32_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(4usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2258,11 +2236,7 @@ float32 a"####;
 
 MultiArrayLayout  layout        # specification of data layout
 float32[]         data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
+
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # The multiarray declares a generic multi-dimensional array of a
@@ -2291,17 +2265,26 @@ uint32 data_offset        # padding elements at front of data
 # dim[2].stride = 3
 #
 # multiarray(i,j,k) refers to the ith row, jth column, and kth channel.
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x05, 0x99, 0xf6, 0xf8, 0x5b, 0x4b, 0xfc, 0xa3, 0x79, 0x87, 0x3a, 0x0b, 0x43, 0x75,
                 0xa0, 0xac, 0xa0, 0x22, 0x15, 0x6b, 0xd2, 0xd7, 0x02, 0x12, 0x75, 0xd1, 0x16, 0xed,
                 0x1f, 0xa8, 0xbf, 0xe0,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Float32MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 4usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2320,13 +2303,16 @@ uint32 stride  # stride of given dimension"####;
         impl ::roslibrust::RosMessageType for Float64 {
             const ROS_TYPE_NAME: &'static str = "std_msgs/Float64";
             const MD5SUM: &'static str = "fdb28210bfa9d7c91146260178d9a584";
-            const DEFINITION: &'static str = r####"float64 data"####;
+            const DEFINITION: &'static str = r####"float64 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x70, 0x5b, 0xa9, 0xc3, 0xd1, 0xa0, 0x9d, 0xf4, 0x37, 0x37, 0xeb, 0x67, 0x09, 0x55,
                 0x34, 0xde, 0x36, 0xfd, 0x42, 0x6c, 0x05, 0x87, 0x77, 0x9b, 0xda, 0x2b, 0xc5, 0x1f,
                 0xe7, 0x90, 0x18, 0x2a,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Float64_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(8usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2351,11 +2337,7 @@ uint32 stride  # stride of given dimension"####;
 
 MultiArrayLayout  layout        # specification of data layout
 float64[]         data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
+
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # The multiarray declares a generic multi-dimensional array of a
@@ -2384,17 +2366,26 @@ uint32 data_offset        # padding elements at front of data
 # dim[2].stride = 3
 #
 # multiarray(i,j,k) refers to the ith row, jth column, and kth channel.
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x10, 0x25, 0xdd, 0xc6, 0xb9, 0x55, 0x2d, 0x19, 0x1f, 0x89, 0xef, 0x1a, 0x8d, 0x2f,
                 0x60, 0xf3, 0xd3, 0x73, 0xe2, 0x8b, 0x28, 0x3d, 0x88, 0x91, 0xdd, 0xcc, 0x97, 0x4e,
                 0x8c, 0x55, 0x39, 0x7f,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Float64MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 8usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2427,13 +2418,37 @@ uint32 seq
 # time-handling sugar is provided by the client library
 time stamp
 #Frame this data is associated with
-string frame_id"####;
+string frame_id
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xfc, 0xd1, 0x24, 0x61, 0x88, 0xef, 0xb1, 0x45, 0x97, 0x36, 0x58, 0x89, 0xd5, 0xad,
                 0xcd, 0x52, 0xac, 0xb6, 0x89, 0x29, 0x29, 0xa1, 0xd2, 0x77, 0x05, 0xef, 0xcd, 0x34,
                 0x3d, 0xc7, 0x96, 0x0f,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Header_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = 4usize + 8usize + 4 + self.r#frame_id.len();
+                ::std::option::Option::Some(len)
+            }
+        }
+        impl ::std::convert::From<::roslibrust::codegen::HeaderCompat> for Header {
+            fn from(header: ::roslibrust::codegen::HeaderCompat) -> Self {
+                Self {
+                    r#seq: header.seq,
+                    r#stamp: header.stamp,
+                    r#frame_id: header.frame_id,
+                }
+            }
+        }
+        impl ::std::convert::From<Header> for ::roslibrust::codegen::HeaderCompat {
+            fn from(header: Header) -> Self {
+                Self {
+                    seq: header.r#seq,
+                    stamp: header.r#stamp,
+                    frame_id: header.r#frame_id,
+                }
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2452,13 +2467,16 @@ string frame_id"####;
         impl ::roslibrust::RosMessageType for Int16 {
             const ROS_TYPE_NAME: &'static str = "std_msgs/Int16";
             const MD5SUM: &'static str = "8524586e34fbd7cb1c08c5f5f1ca0e57";
-            const DEFINITION: &'static str = r####"int16 data"####;
+            const DEFINITION: &'static str = r####"int16 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x1d, 0xcc, 0x34, 0x64, 0xe4, 0x7c, 0x28, 0x8a, 0x55, 0xf9, 0x43, 0xa3, 0x89, 0xd3,
                 0x37, 0xcd, 0xb0, 0x68, 0x04, 0xde, 0x3f, 0x5c, 0xd7, 0xa2, 0x66, 0xb0, 0xde, 0x71,
                 0x8e, 0xee, 0x17, 0xe5,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int16_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(2usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2483,11 +2501,7 @@ string frame_id"####;
 
 MultiArrayLayout  layout        # specification of data layout
 int16[]           data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
+
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # The multiarray declares a generic multi-dimensional array of a
@@ -2516,17 +2530,26 @@ uint32 data_offset        # padding elements at front of data
 # dim[2].stride = 3
 #
 # multiarray(i,j,k) refers to the ith row, jth column, and kth channel.
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xb5, 0x88, 0x10, 0xe8, 0xe5, 0xb9, 0x0f, 0xb1, 0x9a, 0x50, 0x62, 0x46, 0x9e, 0xb8,
                 0x40, 0x9f, 0x5a, 0xb1, 0x1a, 0x44, 0x6d, 0x60, 0xde, 0x71, 0x57, 0xa1, 0x45, 0x7e,
                 0x52, 0xa0, 0x76, 0xce,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int16MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 2usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2545,13 +2568,16 @@ uint32 stride  # stride of given dimension"####;
         impl ::roslibrust::RosMessageType for Int32 {
             const ROS_TYPE_NAME: &'static str = "std_msgs/Int32";
             const MD5SUM: &'static str = "da5909fbe378aeaf85e547e830cc1bb7";
-            const DEFINITION: &'static str = r####"int32 data"####;
+            const DEFINITION: &'static str = r####"int32 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xb6, 0x57, 0x8d, 0xed, 0x3c, 0x58, 0xc6, 0x26, 0xcf, 0xe8, 0xd1, 0xa6, 0xfb, 0x6e,
                 0x04, 0xf7, 0x06, 0xf9, 0x7e, 0x9f, 0x03, 0xd2, 0x72, 0x7c, 0x9f, 0xf4, 0xe7, 0x4b,
                 0x1c, 0xef, 0x0d, 0xeb,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int32_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(4usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2576,11 +2602,7 @@ uint32 stride  # stride of given dimension"####;
 
 MultiArrayLayout  layout        # specification of data layout
 int32[]           data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
+
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # The multiarray declares a generic multi-dimensional array of a
@@ -2609,17 +2631,26 @@ uint32 data_offset        # padding elements at front of data
 # dim[2].stride = 3
 #
 # multiarray(i,j,k) refers to the ith row, jth column, and kth channel.
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x84, 0xa7, 0x34, 0x63, 0x23, 0x52, 0x5d, 0x1b, 0x4d, 0xfc, 0xa8, 0x99, 0xdf, 0x38,
                 0x20, 0xf2, 0x45, 0xe5, 0x40, 0x09, 0xda, 0xc5, 0xa6, 0xb6, 0x92, 0x17, 0xd1, 0x4f,
                 0xde, 0xfd, 0x17, 0x01,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int32MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 4usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2638,13 +2669,16 @@ uint32 stride  # stride of given dimension"####;
         impl ::roslibrust::RosMessageType for Int64 {
             const ROS_TYPE_NAME: &'static str = "std_msgs/Int64";
             const MD5SUM: &'static str = "34add168574510e6e17f5d23ecc077ef";
-            const DEFINITION: &'static str = r####"int64 data"####;
+            const DEFINITION: &'static str = r####"int64 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x8c, 0xd1, 0x04, 0x8c, 0x2f, 0x18, 0x6b, 0x6b, 0xd9, 0xa9, 0x24, 0x72, 0xdc, 0x1c,
                 0xe5, 0x17, 0x23, 0xc0, 0x83, 0x3a, 0x22, 0x1e, 0x2b, 0x7a, 0xec, 0xff, 0xf1, 0x11,
                 0x77, 0x4f, 0x4b, 0x49,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int64_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(8usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2669,11 +2703,7 @@ uint32 stride  # stride of given dimension"####;
 
 MultiArrayLayout  layout        # specification of data layout
 int64[]           data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
+
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # The multiarray declares a generic multi-dimensional array of a
@@ -2702,17 +2732,26 @@ uint32 data_offset        # padding elements at front of data
 # dim[2].stride = 3
 #
 # multiarray(i,j,k) refers to the ith row, jth column, and kth channel.
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xe6, 0x0f, 0x9f, 0xe3, 0x4d, 0x69, 0x7f, 0x09, 0x39, 0xad, 0x49, 0xd3, 0x31, 0x58,
                 0x69, 0x3c, 0x12, 0x77, 0xfb, 0xac, 0x0e, 0x2f, 0x04, 0xb7, 0xc2, 0x99, 0x5d, 0xc2,
                 0x1c, 0x89, 0xb4, 0x22,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int64MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 8usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2731,13 +2770,16 @@ uint32 stride  # stride of given dimension"####;
         impl ::roslibrust::RosMessageType for Int8 {
             const ROS_TYPE_NAME: &'static str = "std_msgs/Int8";
             const MD5SUM: &'static str = "27ffa0c9c4b8fb8492252bcad9e5c57b";
-            const DEFINITION: &'static str = r####"int8 data"####;
+            const DEFINITION: &'static str = r####"int8 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x26, 0x52, 0x50, 0x65, 0xa4, 0x03, 0xd9, 0x72, 0xcb, 0x67, 0x2f, 0x07, 0x77, 0xe3,
                 0x33, 0xf0, 0xc7, 0x99, 0xad, 0x44, 0x4a, 0xe5, 0xfc, 0xd7, 0x9e, 0x43, 0xd1, 0xe7,
                 0x3b, 0xd0, 0xf4, 0x40,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int8_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(1usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2762,11 +2804,7 @@ uint32 stride  # stride of given dimension"####;
 
 MultiArrayLayout  layout        # specification of data layout
 int8[]            data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
+
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # The multiarray declares a generic multi-dimensional array of a
@@ -2795,17 +2833,26 @@ uint32 data_offset        # padding elements at front of data
 # dim[2].stride = 3
 #
 # multiarray(i,j,k) refers to the ith row, jth column, and kth channel.
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xf2, 0x19, 0x98, 0xd4, 0xb4, 0x92, 0xab, 0xd6, 0x33, 0x30, 0x76, 0x5d, 0x75, 0xd5,
                 0x83, 0x12, 0x38, 0xd4, 0x00, 0x74, 0x03, 0x86, 0xf6, 0x51, 0xf1, 0x3a, 0x87, 0x2a,
                 0x4d, 0x21, 0x88, 0xdb,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int8MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len();
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2828,13 +2875,19 @@ uint32 stride  # stride of given dimension"####;
             const MD5SUM: &'static str = "4cd0c83a8683deae40ecdac60e53bfa8";
             const DEFINITION: &'static str = r####"string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x5e, 0x77, 0x3a, 0x60, 0xa4, 0xc7, 0xfc, 0x8a, 0x54, 0x98, 0x5f, 0x30, 0x7c, 0x78,
                 0x37, 0xaa, 0x29, 0x94, 0x25, 0x2a, 0x12, 0x6c, 0x30, 0x19, 0x57, 0xa2, 0x4e, 0x31,
                 0x28, 0x2c, 0x9c, 0xbe,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::MultiArrayDimension_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = 4 + self.r#label.len() + 4usize + 4usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2880,17 +2933,30 @@ uint32 data_offset        # padding elements at front of data
 # dim[2].stride = 3
 #
 # multiarray(i,j,k) refers to the ith row, jth column, and kth channel.
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x4c, 0x66, 0xe6, 0xf7, 0x8e, 0x74, 0x0a, 0xc1, 0x03, 0xa9, 0x4c, 0xf6, 0x32, 0x59,
                 0xf9, 0x68, 0xe4, 0x8c, 0x61, 0x7e, 0x76, 0x99, 0xe8, 0x29, 0xb6, 0x3c, 0x21, 0xa5,
                 0xcb, 0x50, 0xda, 0xc6,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::MultiArrayLayout_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = 4
+                    + self
+                        .r#dim
+                        .iter()
+                        .map(::roslibrust::RosMessageType::encoded_len)
+                        .sum::<::std::option::Option<usize>>()?
+                    + 4usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2909,13 +2975,19 @@ uint32 stride  # stride of given dimension"####;
         impl ::roslibrust::RosMessageType for String {
             const ROS_TYPE_NAME: &'static str = "std_msgs/String";
             const MD5SUM: &'static str = "992ce8a1687cec8c8bd883ec73ca41d1";
-            const DEFINITION: &'static str = r####"string data"####;
+            const DEFINITION: &'static str = r####"string data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xdf, 0x66, 0x8c, 0x74, 0x04, 0x82, 0xbb, 0xd4, 0x8f, 0xb3, 0x9d, 0x76, 0xa7, 0x0d,
                 0xfd, 0x4b, 0xd5, 0x9d, 0xb1, 0x28, 0x80, 0x21, 0x74, 0x35, 0x03, 0x25, 0x9e, 0x94,
                 0x8f, 0x6b, 0x1a, 0x18,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::String_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = 4 + self.r#data.len();
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2934,13 +3006,16 @@ uint32 stride  # stride of given dimension"####;
         impl ::roslibrust::RosMessageType for Time {
             const ROS_TYPE_NAME: &'static str = "std_msgs/Time";
             const MD5SUM: &'static str = "cd7166c74c552c311fbcc2fe5a7bc289";
-            const DEFINITION: &'static str = r####"time data"####;
+            const DEFINITION: &'static str = r####"time data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xe5, 0x77, 0xc4, 0x61, 0x30, 0x6c, 0xfd, 0x1c, 0xbf, 0x5b, 0xbf, 0x5c, 0x87, 0x4b,
                 0x03, 0x8b, 0x7f, 0x3d, 0xcd, 0x6f, 0x16, 0xa1, 0x9b, 0x7f, 0xe6, 0x69, 0x63, 0xc2,
                 0x3d, 0x47, 0x84, 0xa0,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Time_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(8usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2959,13 +3034,16 @@ uint32 stride  # stride of given dimension"####;
         impl ::roslibrust::RosMessageType for UInt16 {
             const ROS_TYPE_NAME: &'static str = "std_msgs/UInt16";
             const MD5SUM: &'static str = "1df79edf208b629fe6b81923a544552d";
-            const DEFINITION: &'static str = r####"uint16 data"####;
+            const DEFINITION: &'static str = r####"uint16 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x08, 0xa4, 0x06, 0xe4, 0xb0, 0x22, 0xbc, 0x22, 0xe9, 0x07, 0xf9, 0x85, 0xd6, 0xa9,
                 0xe9, 0xdd, 0x1d, 0x4f, 0xbe, 0xca, 0xe5, 0x73, 0x54, 0x9c, 0xf4, 0x93, 0x50, 0x11,
                 0x3e, 0x77, 0x57, 0xb1,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt16_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(2usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -2990,11 +3068,7 @@ uint32 stride  # stride of given dimension"####;
 
 MultiArrayLayout  layout        # specification of data layout
 uint16[]            data        # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
+
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # The multiarray declares a generic multi-dimensional array of a
@@ -3023,17 +3097,26 @@ uint32 data_offset        # padding elements at front of data
 # dim[2].stride = 3
 #
 # multiarray(i,j,k) refers to the ith row, jth column, and kth channel.
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x94, 0xfe, 0x73, 0x42, 0x8e, 0xc6, 0x3b, 0xae, 0xcc, 0x77, 0x4f, 0x8f, 0xb8, 0x24,
                 0x06, 0x12, 0x3e, 0x92, 0x91, 0xcf, 0x72, 0x8f, 0x1b, 0x7c, 0x91, 0xca, 0xf5, 0x33,
                 0x51, 0x29, 0x49, 0x2b,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt16MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 2usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3052,13 +3135,16 @@ uint32 stride  # stride of given dimension"####;
         impl ::roslibrust::RosMessageType for UInt32 {
             const ROS_TYPE_NAME: &'static str = "std_msgs/UInt32";
             const MD5SUM: &'static str = "304a39449588c7f8ce2df6e8001c5fce";
-            const DEFINITION: &'static str = r####"uint32 data"####;
+            const DEFINITION: &'static str = r####"uint32 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xa5, 0xc8, 0x74, 0x82, 0x9b, 0x75, 0x2b, 0xc5, 0xfa, 0x19, 0x00, 0x24, 0xb0, 0xad,
                 0x76, 0xf5, 0x78, 0xcc, 0x27, 0x82, 0x71, 0xe8, 0x55, 0xc7, 0xd0, 0x2a, 0x81, 0x8b,
                 0x35, 0x16, 0xfb, 0x4a,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt32_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(4usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3083,11 +3169,7 @@ uint32 stride  # stride of given dimension"####;
 
 MultiArrayLayout  layout        # specification of data layout
 uint32[]          data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
+
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # The multiarray declares a generic multi-dimensional array of a
@@ -3116,17 +3198,26 @@ uint32 data_offset        # padding elements at front of data
 # dim[2].stride = 3
 #
 # multiarray(i,j,k) refers to the ith row, jth column, and kth channel.
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x6c, 0x25, 0x77, 0xc7, 0xad, 0x3c, 0xbd, 0xcc, 0x21, 0x64, 0xa4, 0x1c, 0x12, 0xf1,
                 0xd5, 0xad, 0x31, 0x4e, 0xa3, 0x20, 0xf3, 0xfb, 0x1e, 0xe4, 0x7e, 0x78, 0x01, 0x9f,
                 0xe1, 0x6b, 0xb5, 0xb0,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt32MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 4usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3145,13 +3236,16 @@ uint32 stride  # stride of given dimension"####;
         impl ::roslibrust::RosMessageType for UInt64 {
             const ROS_TYPE_NAME: &'static str = "std_msgs/UInt64";
             const MD5SUM: &'static str = "1b2a79973e8bf53d7b53acb71299cb57";
-            const DEFINITION: &'static str = r####"uint64 data"####;
+            const DEFINITION: &'static str = r####"uint64 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xfb, 0xdc, 0x52, 0x01, 0x8f, 0xc1, 0x37, 0x55, 0xdc, 0xe1, 0x80, 0x24, 0xd1, 0xa6,
                 0x71, 0xc8, 0x56, 0xaa, 0x8b, 0x4a, 0xaf, 0x63, 0xad, 0xfb, 0x09, 0x5b, 0x60, 0x8f,
                 0x98, 0xe8, 0xc9, 0x43,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt64_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(8usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3176,11 +3270,7 @@ uint32 stride  # stride of given dimension"####;
 
 MultiArrayLayout  layout        # specification of data layout
 uint64[]          data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
+
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # The multiarray declares a generic multi-dimensional array of a
@@ -3209,17 +3299,26 @@ uint32 data_offset        # padding elements at front of data
 # dim[2].stride = 3
 #
 # multiarray(i,j,k) refers to the ith row, jth column, and kth channel.
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xfc, 0x1c, 0x68, 0x5c, 0x2f, 0x76, 0xbd, 0xc6, 0x98, 0x3d, 0xa0, 0x25, 0xcb, 0x25,
                 0xd2, 0xdb, 0x5f, 0xb5, 0x15, 0x7b, 0x05, 0x9e, 0x30, 0x0f, 0x6d, 0x95, 0x7d, 0x86,
                 0xf9, 0x81, 0xb3, 0x66,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt64MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 8usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3238,13 +3337,16 @@ uint32 stride  # stride of given dimension"####;
         impl ::roslibrust::RosMessageType for UInt8 {
             const ROS_TYPE_NAME: &'static str = "std_msgs/UInt8";
             const MD5SUM: &'static str = "7c8164229e7d2c17eb95e9231617fdee";
-            const DEFINITION: &'static str = r####"uint8 data"####;
+            const DEFINITION: &'static str = r####"uint8 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x61, 0x38, 0xbd, 0x83, 0xd8, 0xc3, 0x56, 0x9c, 0xb8, 0x0a, 0x66, 0x7d, 0xb0, 0x3c,
                 0xfc, 0x16, 0x29, 0xf5, 0x29, 0xfe, 0xe7, 0x9d, 0x94, 0x4c, 0x39, 0xc3, 0x4e, 0x35,
                 0x2e, 0x72, 0xf0, 0x10,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt8_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(1usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3270,11 +3372,7 @@ uint32 stride  # stride of given dimension"####;
 
 MultiArrayLayout  layout        # specification of data layout
 uint8[]           data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
+
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # The multiarray declares a generic multi-dimensional array of a
@@ -3303,17 +3401,26 @@ uint32 data_offset        # padding elements at front of data
 # dim[2].stride = 3
 #
 # multiarray(i,j,k) refers to the ith row, jth column, and kth channel.
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x56, 0x87, 0xe8, 0x61, 0xb8, 0xd3, 0x07, 0xa5, 0xe4, 0x8b, 0x75, 0x15, 0x46, 0x7a,
                 0xe7, 0xa5, 0xfc, 0x2d, 0xaf, 0x80, 0x5b, 0xd0, 0xce, 0x6d, 0x8e, 0x9e, 0x60, 0x4b,
                 0xad, 0xe9, 0xf3, 0x85,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt8MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len();
+                ::std::option::Option::Some(len)
+            }
         }
     }
 
@@ -3382,13 +3489,16 @@ int32 sec
 # e.g.
 # The duration -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The duration 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+uint32 nanosec
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xe8, 0xd0, 0x09, 0xf6, 0x59, 0x81, 0x6f, 0x75, 0x8b, 0x75, 0x33, 0x4e, 0xe1, 0xa9,
                 0xca, 0x5b, 0x5c, 0x0b, 0x85, 0x98, 0x43, 0x26, 0x1f, 0x14, 0xc7, 0xf9, 0x37, 0x34,
                 0x95, 0x99, 0xd9, 0x3b,
             ];
             const ROS2_TYPE_NAME: &'static str = "builtin_interfaces::msg::dds_::Duration_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(8usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3418,13 +3528,16 @@ int32 sec
 # e.g.
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+uint32 nanosec
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xb1, 0x06, 0x23, 0x5e, 0x25, 0xa4, 0xc5, 0xed, 0x35, 0x09, 0x8a, 0xa0, 0xa6, 0x1a,
                 0x3e, 0xe9, 0xc9, 0xb1, 0x8d, 0x19, 0x7f, 0x39, 0x8b, 0x0e, 0x42, 0x06, 0xce, 0xa9,
                 0xac, 0xf9, 0xc1, 0x97,
             ];
             const ROS2_TYPE_NAME: &'static str = "builtin_interfaces::msg::dds_::Time_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(8usize);
         }
     }
     #[allow(unused_imports)]
@@ -3453,6 +3566,7 @@ uint32 nanosec"####;
             const DEFINITION: &'static str = r####"# This expresses acceleration in free space broken into its linear and angular parts.
 Vector3  linear
 Vector3  angular
+
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space.
@@ -3463,13 +3577,16 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xdc, 0x44, 0x82, 0x43, 0xde, 0xd9, 0xb1, 0xfc, 0xbc, 0xca, 0x24, 0xab, 0xa0, 0xc2,
                 0x2f, 0x01, 0x3d, 0xae, 0x06, 0xc3, 0x54, 0xba, 0x2d, 0x84, 0x95, 0x71, 0xc0, 0xa2,
                 0xa3, 0xf5, 0x7c, 0xa0,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Accel_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(48usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3492,6 +3609,19 @@ float64 z"####;
             const DEFINITION: &'static str = r####"# An accel with reference coordinate frame and timestamp
 std_msgs/Header header
 Accel accel
+
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data
+# in a particular coordinate frame.
+
+# Two-integer timestamp that is expressed as seconds and nanoseconds.
+builtin_interfaces/Time stamp
+
+# Transform frame with which this data is associated.
+string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -3505,22 +3635,13 @@ int32 sec
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
 uint32 nanosec
+
 ================================================================================
 MSG: geometry_msgs/Accel
 # This expresses acceleration in free space broken into its linear and angular parts.
 Vector3  linear
 Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
-
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space.
@@ -3532,36 +3653,19 @@ MSG: geometry_msgs/Vector3
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data
-# in a particular coordinate frame.
-
-# Two-integer timestamp that is expressed as seconds and nanoseconds.
-builtin_interfaces/Time stamp
-
-# Transform frame with which this data is associated.
-string frame_id
-================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
-
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xef, 0x1d, 0xf9, 0xea, 0xba, 0xe0, 0xa7, 0x08, 0xcc, 0x04, 0x9a, 0x06, 0x1e, 0xbc,
                 0xdd, 0xc4, 0xe2, 0xa5, 0xf7, 0x45, 0x73, 0x01, 0x00, 0xba, 0x68, 0x0e, 0x08, 0x6a,
                 0x96, 0x98, 0xb1, 0x65,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::AccelStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#accel)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3592,22 +3696,13 @@ Accel accel
 # In order, the parameters are:
 # (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)
 float64[36] covariance
+
 ================================================================================
 MSG: geometry_msgs/Accel
 # This expresses acceleration in free space broken into its linear and angular parts.
 Vector3  linear
 Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
-
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space.
@@ -3618,13 +3713,16 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x23, 0x0d, 0x51, 0xbd, 0x53, 0xbc, 0x36, 0xf2, 0x60, 0x57, 0x4e, 0x73, 0xb4, 0x29,
                 0x41, 0xce, 0xfe, 0x44, 0x68, 0x47, 0x53, 0x48, 0x0b, 0x6f, 0xc3, 0x30, 0xc0, 0x32,
                 0xc5, 0xdb, 0x59, 0x97,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::AccelWithCovariance_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(336usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3647,6 +3745,19 @@ float64 z"####;
             const DEFINITION: &'static str = r####"# This represents an estimated accel with reference coordinate frame and timestamp.
 std_msgs/Header header
 AccelWithCovariance accel
+
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data
+# in a particular coordinate frame.
+
+# Two-integer timestamp that is expressed as seconds and nanoseconds.
+builtin_interfaces/Time stamp
+
+# Transform frame with which this data is associated.
+string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -3660,22 +3771,7 @@ int32 sec
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
 uint32 nanosec
-================================================================================
-MSG: geometry_msgs/Accel
-# This expresses acceleration in free space broken into its linear and angular parts.
-Vector3  linear
-Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
-
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/AccelWithCovariance
 # This expresses acceleration in free space with uncertainty.
@@ -3687,33 +3783,13 @@ Accel accel
 # In order, the parameters are:
 # (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)
 float64[36] covariance
+
 ================================================================================
 MSG: geometry_msgs/Accel
 # This expresses acceleration in free space broken into its linear and angular parts.
 Vector3  linear
 Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
-
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
-
-float64 x
-float64 y
-float64 z
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
-
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space.
@@ -3725,30 +3801,7 @@ MSG: geometry_msgs/Vector3
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data
-# in a particular coordinate frame.
-
-# Two-integer timestamp that is expressed as seconds and nanoseconds.
-builtin_interfaces/Time stamp
-
-# Transform frame with which this data is associated.
-string frame_id
-================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
-
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x61, 0xc9, 0xad, 0x89, 0x28, 0xe7, 0x1d, 0xd9, 0x5c, 0xe7, 0x91, 0xb2, 0xf0, 0x28,
                 0x09, 0xee, 0x2a, 0x0b, 0xbc, 0xc4, 0x2c, 0xd0, 0xe4, 0x04, 0x7f, 0xd0, 0x0a, 0x82,
@@ -3756,6 +3809,12 @@ uint32 nanosec"####;
             ];
             const ROS2_TYPE_NAME: &'static str =
                 "geometry_msgs::msg::dds_::AccelWithCovarianceStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#accel)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3797,6 +3856,7 @@ float64 ixz
 float64 iyy
 float64 iyz
 float64 izz
+
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space.
@@ -3807,13 +3867,16 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x2d, 0xdd, 0x5d, 0xab, 0x5c, 0x34, 0x78, 0x25, 0xba, 0x2e, 0x56, 0xc8, 0x95, 0xdd,
                 0xcc, 0xfd, 0x0b, 0x8e, 0xfe, 0x53, 0xae, 0x93, 0x1b, 0xf6, 0x7f, 0x90, 0x55, 0x29,
                 0x93, 0x0b, 0x4b, 0xd7,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Inertia_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(80usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3837,6 +3900,19 @@ float64 z"####;
 
 std_msgs/Header header
 Inertia inertia
+
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data
+# in a particular coordinate frame.
+
+# Two-integer timestamp that is expressed as seconds and nanoseconds.
+builtin_interfaces/Time stamp
+
+# Transform frame with which this data is associated.
+string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -3850,6 +3926,7 @@ int32 sec
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
 uint32 nanosec
+
 ================================================================================
 MSG: geometry_msgs/Inertia
 # Mass [kg]
@@ -3868,17 +3945,7 @@ float64 ixz
 float64 iyy
 float64 iyz
 float64 izz
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
-
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space.
@@ -3890,36 +3957,19 @@ MSG: geometry_msgs/Vector3
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data
-# in a particular coordinate frame.
-
-# Two-integer timestamp that is expressed as seconds and nanoseconds.
-builtin_interfaces/Time stamp
-
-# Transform frame with which this data is associated.
-string frame_id
-================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
-
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x76, 0x6b, 0xe4, 0x59, 0x76, 0x25, 0x2b, 0xab, 0xf7, 0xf9, 0xd8, 0xac, 0x4a, 0xe7,
                 0xc9, 0x12, 0xa7, 0xce, 0xcc, 0xf7, 0x10, 0x35, 0x62, 0x25, 0x29, 0xf2, 0x75, 0x18,
                 0xb6, 0x95, 0xaa, 0x09,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::InertiaStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#inertia)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3943,13 +3993,16 @@ uint32 nanosec"####;
             const DEFINITION: &'static str = r####"# This contains the position of a point in free space
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x69, 0x63, 0x08, 0x48, 0x42, 0xa9, 0xb0, 0x44, 0x94, 0xd6, 0xb2, 0x94, 0x1d, 0x11,
                 0x44, 0x47, 0x08, 0xd8, 0x92, 0xda, 0x2f, 0x4b, 0x09, 0x84, 0x3b, 0x9c, 0x43, 0xf4,
                 0x2a, 0x7f, 0x68, 0x81,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Point_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(24usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -3980,13 +4033,16 @@ float64 z"####;
 
 float32 x
 float32 y
-float32 z"####;
+float32 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x2f, 0xc4, 0xdb, 0x7c, 0xae, 0x16, 0xa4, 0x58, 0x2c, 0x79, 0xa5, 0x6b, 0x66, 0x17,
                 0x3a, 0x8d, 0x48, 0xd5, 0x2c, 0x7d, 0xc5, 0x20, 0xdd, 0xc5, 0x5a, 0x0d, 0x4b, 0xcf,
                 0x2a, 0x4b, 0xfd, 0xbc,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Point32_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(12usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -4010,25 +4066,7 @@ float32 z"####;
 
 std_msgs/Header header
 Point point
-================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
 
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec
-================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: std_msgs/Header
 # Standard metadata for higher-level stamped data types.
@@ -4040,6 +4078,7 @@ builtin_interfaces/Time stamp
 
 # Transform frame with which this data is associated.
 string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -4052,13 +4091,27 @@ int32 sec
 # e.g.
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+uint32 nanosec
+
+================================================================================
+MSG: geometry_msgs/Point
+# This contains the position of a point in free space
+float64 x
+float64 y
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x4c, 0x02, 0x96, 0xaf, 0x86, 0xe0, 0x1e, 0x56, 0x2e, 0x9e, 0x04, 0x05, 0xd1, 0x38,
                 0xa0, 0x15, 0x37, 0x24, 0x75, 0x80, 0x07, 0x6c, 0x58, 0xea, 0x38, 0xd7, 0x92, 0x3a,
                 0xc1, 0x04, 0x58, 0x97,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::PointStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#point)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -4080,6 +4133,7 @@ uint32 nanosec"####;
             const DEFINITION: &'static str = r####"# A specification of a polygon where the first and last points are assumed to be connected
 
 Point32[] points
+
 ================================================================================
 MSG: geometry_msgs/Point32
 # This contains the position of a point in free space(with 32 bits of precision).
@@ -4092,13 +4146,23 @@ MSG: geometry_msgs/Point32
 
 float32 x
 float32 y
-float32 z"####;
+float32 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x37, 0x82, 0xf9, 0xf0, 0xbf, 0x04, 0x49, 0x64, 0xd6, 0x92, 0xd6, 0xc0, 0x17, 0xd7,
                 0x05, 0xe3, 0x76, 0x11, 0xaf, 0xb1, 0xf0, 0xbf, 0x6a, 0x9d, 0xee, 0x24, 0x8a, 0x7d,
                 0xda, 0x0f, 0x78, 0x4a,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Polygon_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = 4 + self
+                    .r#points
+                    .iter()
+                    .map(::roslibrust::RosMessageType::encoded_len)
+                    .sum::<::std::option::Option<usize>>()?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -4122,6 +4186,19 @@ float32 z"####;
 
 std_msgs/Header header
 Polygon polygon
+
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data
+# in a particular coordinate frame.
+
+# Two-integer timestamp that is expressed as seconds and nanoseconds.
+builtin_interfaces/Time stamp
+
+# Transform frame with which this data is associated.
+string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -4135,24 +4212,13 @@ int32 sec
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
 uint32 nanosec
-================================================================================
-MSG: geometry_msgs/Point32
-# This contains the position of a point in free space(with 32 bits of precision).
-# It is recommended to use Point wherever possible instead of Point32.
-#
-# This recommendation is to promote interoperability.
-#
-# This message is designed to take up less space when sending
-# lots of points at once, as in the case of a PointCloud.
-
-float32 x
-float32 y
-float32 z
+
 ================================================================================
 MSG: geometry_msgs/Polygon
 # A specification of a polygon where the first and last points are assumed to be connected
 
 Point32[] points
+
 ================================================================================
 MSG: geometry_msgs/Point32
 # This contains the position of a point in free space(with 32 bits of precision).
@@ -4166,36 +4232,19 @@ MSG: geometry_msgs/Point32
 float32 x
 float32 y
 float32 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data
-# in a particular coordinate frame.
-
-# Two-integer timestamp that is expressed as seconds and nanoseconds.
-builtin_interfaces/Time stamp
-
-# Transform frame with which this data is associated.
-string frame_id
-================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
-
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xb7, 0xcf, 0x07, 0x93, 0x2f, 0x15, 0x23, 0xd4, 0xb4, 0x08, 0x80, 0x75, 0x94, 0x5c,
                 0x1a, 0x01, 0x41, 0xf7, 0xcd, 0x21, 0xda, 0x87, 0xcc, 0x94, 0x0f, 0xc6, 0x16, 0x52,
                 0xe9, 0x13, 0x8b, 0x46,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::PolygonStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#polygon)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -4219,12 +4268,14 @@ uint32 nanosec"####;
 
 Point position
 Quaternion orientation
+
 ================================================================================
 MSG: geometry_msgs/Point
 # This contains the position of a point in free space
 float64 x
 float64 y
 float64 z
+
 ================================================================================
 MSG: geometry_msgs/Quaternion
 # This represents an orientation in free space in quaternion form.
@@ -4232,13 +4283,16 @@ MSG: geometry_msgs/Quaternion
 float64 x 0
 float64 y 0
 float64 z 0
-float64 w 1"####;
+float64 w 1
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xd5, 0x01, 0x95, 0x4e, 0x94, 0x76, 0xce, 0xa2, 0x99, 0x69, 0x84, 0xe8, 0x12, 0x05,
                 0x4b, 0x68, 0x02, 0x6a, 0xe0, 0xbf, 0xae, 0x78, 0x9d, 0x9a, 0x10, 0xb2, 0x3d, 0xaf,
                 0x35, 0xcc, 0x90, 0xfa,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Pose_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(56usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -4268,13 +4322,16 @@ float64 w 1"####;
 
 float64 x
 float64 y
-float64 theta"####;
+float64 theta
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xd6, 0x8e, 0xfa, 0x5b, 0x46, 0xe7, 0x0f, 0x7b, 0x16, 0xca, 0x23, 0x08, 0x54, 0x74,
                 0xfd, 0xac, 0x5a, 0x44, 0xb6, 0x38, 0x78, 0x3e, 0xc4, 0x2f, 0x66, 0x1d, 0xa6, 0x4d,
                 0xa4, 0x72, 0x4c, 0xcc,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Pose2D_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(24usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -4299,6 +4356,19 @@ float64 theta"####;
 std_msgs/Header header
 
 Pose[] poses
+
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data
+# in a particular coordinate frame.
+
+# Two-integer timestamp that is expressed as seconds and nanoseconds.
+builtin_interfaces/Time stamp
+
+# Transform frame with which this data is associated.
+string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -4312,32 +4382,21 @@ int32 sec
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
 uint32 nanosec
-================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
+
 ================================================================================
 MSG: geometry_msgs/Pose
 # A representation of pose in free space, composed of position and orientation.
 
 Point position
 Quaternion orientation
+
 ================================================================================
 MSG: geometry_msgs/Point
 # This contains the position of a point in free space
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
 
-float64 x 0
-float64 y 0
-float64 z 0
-float64 w 1
 ================================================================================
 MSG: geometry_msgs/Quaternion
 # This represents an orientation in free space in quaternion form.
@@ -4346,36 +4405,24 @@ float64 x 0
 float64 y 0
 float64 z 0
 float64 w 1
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data
-# in a particular coordinate frame.
-
-# Two-integer timestamp that is expressed as seconds and nanoseconds.
-builtin_interfaces/Time stamp
-
-# Transform frame with which this data is associated.
-string frame_id
-================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
-
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xaf, 0x0c, 0xc3, 0x6d, 0x19, 0x0e, 0x10, 0x4d, 0x54, 0x6d, 0x16, 0x8d, 0x6b, 0x39,
                 0xdf, 0x04, 0xfa, 0x4b, 0x4c, 0xce, 0xcf, 0x59, 0xcb, 0x4c, 0x9e, 0xd3, 0x28, 0xd3,
                 0xd5, 0x00, 0x4a, 0xa0,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::PoseArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + 4
+                    + self
+                        .r#poses
+                        .iter()
+                        .map(::roslibrust::RosMessageType::encoded_len)
+                        .sum::<::std::option::Option<usize>>()?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -4399,6 +4446,19 @@ uint32 nanosec"####;
 
 std_msgs/Header header
 Pose pose
+
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data
+# in a particular coordinate frame.
+
+# Two-integer timestamp that is expressed as seconds and nanoseconds.
+builtin_interfaces/Time stamp
+
+# Transform frame with which this data is associated.
+string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -4412,32 +4472,21 @@ int32 sec
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
 uint32 nanosec
-================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
+
 ================================================================================
 MSG: geometry_msgs/Pose
 # A representation of pose in free space, composed of position and orientation.
 
 Point position
 Quaternion orientation
+
 ================================================================================
 MSG: geometry_msgs/Point
 # This contains the position of a point in free space
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
 
-float64 x 0
-float64 y 0
-float64 z 0
-float64 w 1
 ================================================================================
 MSG: geometry_msgs/Quaternion
 # This represents an orientation in free space in quaternion form.
@@ -4446,36 +4495,19 @@ float64 x 0
 float64 y 0
 float64 z 0
 float64 w 1
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data
-# in a particular coordinate frame.
-
-# Two-integer timestamp that is expressed as seconds and nanoseconds.
-builtin_interfaces/Time stamp
-
-# Transform frame with which this data is associated.
-string frame_id
-================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
-
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x10, 0xf3, 0x78, 0x6d, 0x7d, 0x40, 0xfd, 0x2b, 0x54, 0x36, 0x78, 0x35, 0x61, 0x4b,
                 0xff, 0x85, 0xd4, 0xad, 0x3b, 0x5d, 0xab, 0x62, 0xbf, 0x8b, 0xca, 0x0c, 0xc2, 0x32,
                 0xd7, 0x3b, 0x4c, 0xd8,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::PoseStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#pose)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -4506,32 +4538,21 @@ Pose pose
 # In order, the parameters are:
 # (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)
 float64[36] covariance
-================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
+
 ================================================================================
 MSG: geometry_msgs/Pose
 # A representation of pose in free space, composed of position and orientation.
 
 Point position
 Quaternion orientation
+
 ================================================================================
 MSG: geometry_msgs/Point
 # This contains the position of a point in free space
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
 
-float64 x 0
-float64 y 0
-float64 z 0
-float64 w 1
 ================================================================================
 MSG: geometry_msgs/Quaternion
 # This represents an orientation in free space in quaternion form.
@@ -4539,13 +4560,16 @@ MSG: geometry_msgs/Quaternion
 float64 x 0
 float64 y 0
 float64 z 0
-float64 w 1"####;
+float64 w 1
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x9a, 0x7c, 0x0f, 0xd2, 0x34, 0xb7, 0xf4, 0x5c, 0x60, 0x98, 0x74, 0x5e, 0xcc, 0xcd,
                 0x77, 0x3c, 0xa1, 0x08, 0x56, 0x70, 0xe6, 0x41, 0x07, 0x13, 0x53, 0x97, 0xae, 0xe3,
                 0x1c, 0x02, 0xe1, 0xbb,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::PoseWithCovariance_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(344usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -4569,6 +4593,19 @@ float64 w 1"####;
 
 std_msgs/Header header
 PoseWithCovariance pose
+
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data
+# in a particular coordinate frame.
+
+# Two-integer timestamp that is expressed as seconds and nanoseconds.
+builtin_interfaces/Time stamp
+
+# Transform frame with which this data is associated.
+string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -4582,32 +4619,7 @@ int32 sec
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
 uint32 nanosec
-================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
-================================================================================
-MSG: geometry_msgs/Pose
-# A representation of pose in free space, composed of position and orientation.
 
-Point position
-Quaternion orientation
-================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
-
-float64 x 0
-float64 y 0
-float64 z 0
-float64 w 1
 ================================================================================
 MSG: geometry_msgs/PoseWithCovariance
 # This represents a pose in free space with uncertainty.
@@ -4619,32 +4631,21 @@ Pose pose
 # In order, the parameters are:
 # (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)
 float64[36] covariance
-================================================================================
-MSG: geometry_msgs/Point
-# This contains the position of a point in free space
-float64 x
-float64 y
-float64 z
+
 ================================================================================
 MSG: geometry_msgs/Pose
 # A representation of pose in free space, composed of position and orientation.
 
 Point position
 Quaternion orientation
+
 ================================================================================
 MSG: geometry_msgs/Point
 # This contains the position of a point in free space
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
 
-float64 x 0
-float64 y 0
-float64 z 0
-float64 w 1
 ================================================================================
 MSG: geometry_msgs/Quaternion
 # This represents an orientation in free space in quaternion form.
@@ -4653,38 +4654,7 @@ float64 x 0
 float64 y 0
 float64 z 0
 float64 w 1
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
-
-float64 x 0
-float64 y 0
-float64 z 0
-float64 w 1
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data
-# in a particular coordinate frame.
-
-# Two-integer timestamp that is expressed as seconds and nanoseconds.
-builtin_interfaces/Time stamp
-
-# Transform frame with which this data is associated.
-string frame_id
-================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
-
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x26, 0x43, 0x2f, 0x98, 0x03, 0xe4, 0x37, 0x27, 0xd3, 0xc8, 0xf6, 0x68, 0xd1, 0xfd,
                 0xb3, 0xc6, 0x30, 0xf5, 0x48, 0xaf, 0x63, 0x1e, 0x2f, 0x4e, 0x31, 0x38, 0x23, 0x71,
@@ -4692,6 +4662,12 @@ uint32 nanosec"####;
             ];
             const ROS2_TYPE_NAME: &'static str =
                 "geometry_msgs::msg::dds_::PoseWithCovarianceStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#pose)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -4722,13 +4698,16 @@ uint32 nanosec"####;
 float64 x 0
 float64 y 0
 float64 z 0
-float64 w 1"####;
+float64 w 1
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x8a, 0x76, 0x5f, 0x66, 0x77, 0x8c, 0x8f, 0xf7, 0xc8, 0xab, 0x94, 0xaf, 0xcc, 0x59,
                 0x0a, 0x2e, 0xd5, 0x32, 0x5a, 0x1d, 0x9a, 0x07, 0x6f, 0xff, 0xf3, 0x8f, 0xbc, 0xe3,
                 0x6f, 0x45, 0x86, 0x84,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Quaternion_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(32usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -4752,27 +4731,7 @@ float64 w 1"####;
 
 std_msgs/Header header
 Quaternion quaternion
-================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
 
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
-
-float64 x 0
-float64 y 0
-float64 z 0
-float64 w 1
 ================================================================================
 MSG: std_msgs/Header
 # Standard metadata for higher-level stamped data types.
@@ -4784,6 +4743,7 @@ builtin_interfaces/Time stamp
 
 # Transform frame with which this data is associated.
 string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -4796,13 +4756,29 @@ int32 sec
 # e.g.
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+uint32 nanosec
+
+================================================================================
+MSG: geometry_msgs/Quaternion
+# This represents an orientation in free space in quaternion form.
+
+float64 x 0
+float64 y 0
+float64 z 0
+float64 w 1
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x38, 0x1a, 0xdd, 0x86, 0xc6, 0xc3, 0x16, 0x06, 0x44, 0xd2, 0x28, 0xca, 0x34, 0x21,
                 0x82, 0xc7, 0xfd, 0x6c, 0x7f, 0xab, 0x11, 0xc7, 0xa8, 0x5a, 0xd8, 0x17, 0xa9, 0xcc,
                 0x22, 0xdb, 0xac, 0x6e,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::QuaternionStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#quaternion)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -4826,14 +4802,7 @@ uint32 nanosec"####;
 
 Vector3 translation
 Quaternion rotation
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
 
-float64 x 0
-float64 y 0
-float64 z 0
-float64 w 1
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space.
@@ -4844,13 +4813,25 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+
+================================================================================
+MSG: geometry_msgs/Quaternion
+# This represents an orientation in free space in quaternion form.
+
+float64 x 0
+float64 y 0
+float64 z 0
+float64 w 1
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xbe, 0xb8, 0x3f, 0xbe, 0x69, 0x86, 0x36, 0x35, 0x14, 0x61, 0xf6, 0xf3, 0x5d, 0x1a,
                 0xbb, 0x20, 0x01, 0x0c, 0x43, 0xd5, 0x53, 0x74, 0xd8, 0x1b, 0xd0, 0x41, 0xf1, 0xba,
                 0x25, 0x81, 0xfd, 0xdc,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Transform_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(56usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -4890,6 +4871,19 @@ string child_frame_id
 
 # Translation and rotation in 3-dimensions of child_frame_id from header.frame_id.
 Transform transform
+
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data
+# in a particular coordinate frame.
+
+# Two-integer timestamp that is expressed as seconds and nanoseconds.
+builtin_interfaces/Time stamp
+
+# Transform frame with which this data is associated.
+string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -4903,28 +4897,14 @@ int32 sec
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
 uint32 nanosec
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
 
-float64 x 0
-float64 y 0
-float64 z 0
-float64 w 1
 ================================================================================
 MSG: geometry_msgs/Transform
 # This represents the transform between two coordinate frames in free space.
 
 Vector3 translation
 Quaternion rotation
-================================================================================
-MSG: geometry_msgs/Quaternion
-# This represents an orientation in free space in quaternion form.
 
-float64 x 0
-float64 y 0
-float64 z 0
-float64 w 1
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space.
@@ -4936,47 +4916,30 @@ MSG: geometry_msgs/Vector3
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
 
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
-
-float64 x
-float64 y
-float64 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data
-# in a particular coordinate frame.
-
-# Two-integer timestamp that is expressed as seconds and nanoseconds.
-builtin_interfaces/Time stamp
-
-# Transform frame with which this data is associated.
-string frame_id
 ================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
+MSG: geometry_msgs/Quaternion
+# This represents an orientation in free space in quaternion form.
 
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+float64 x 0
+float64 y 0
+float64 z 0
+float64 w 1
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x0a, 0x24, 0x1f, 0x87, 0xd0, 0x46, 0x68, 0xd9, 0x40, 0x99, 0xcb, 0xb5, 0xba, 0x11,
                 0x69, 0x1d, 0x5a, 0xd3, 0x2c, 0x2f, 0x29, 0x68, 0x2e, 0x4e, 0xb5, 0x65, 0x34, 0x24,
                 0xbd, 0x27, 0x52, 0x06,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::TransformStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + 4
+                    + self.r#child_frame_id.len()
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#transform)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5000,6 +4963,7 @@ uint32 nanosec"####;
 
 Vector3  linear
 Vector3  angular
+
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space.
@@ -5010,13 +4974,16 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x9c, 0x45, 0xbf, 0x16, 0xfe, 0x09, 0x83, 0xd8, 0x0e, 0x3c, 0xfe, 0x75, 0x0d, 0x68,
                 0x35, 0x84, 0x3d, 0x26, 0x5a, 0x9a, 0x6c, 0x46, 0xbd, 0x2e, 0x60, 0x9f, 0xcd, 0xdd,
                 0xe6, 0xfb, 0x8d, 0x2a,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Twist_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(48usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5040,6 +5007,19 @@ float64 z"####;
 
 std_msgs/Header header
 Twist twist
+
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data
+# in a particular coordinate frame.
+
+# Two-integer timestamp that is expressed as seconds and nanoseconds.
+builtin_interfaces/Time stamp
+
+# Transform frame with which this data is associated.
+string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -5053,23 +5033,14 @@ int32 sec
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
 uint32 nanosec
+
 ================================================================================
 MSG: geometry_msgs/Twist
 # This expresses velocity in free space broken into its linear and angular parts.
 
 Vector3  linear
 Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
-
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space.
@@ -5081,36 +5052,19 @@ MSG: geometry_msgs/Vector3
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data
-# in a particular coordinate frame.
-
-# Two-integer timestamp that is expressed as seconds and nanoseconds.
-builtin_interfaces/Time stamp
-
-# Transform frame with which this data is associated.
-string frame_id
-================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
-
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x5f, 0x0f, 0xcd, 0x4f, 0x81, 0xd5, 0xd0, 0x6a, 0xd9, 0xb4, 0xc4, 0xc6, 0x3e, 0x3e,
                 0xa5, 0x1b, 0x82, 0xd6, 0xae, 0x4d, 0x05, 0x58, 0xf1, 0xd4, 0x75, 0x22, 0x9b, 0x11,
                 0x21, 0xdb, 0x6f, 0x64,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::TwistStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#twist)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5141,23 +5095,14 @@ Twist twist
 # In order, the parameters are:
 # (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)
 float64[36] covariance
+
 ================================================================================
 MSG: geometry_msgs/Twist
 # This expresses velocity in free space broken into its linear and angular parts.
 
 Vector3  linear
 Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
 
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
-
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space.
@@ -5168,13 +5113,16 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x49, 0xf5, 0x74, 0xf0, 0x33, 0xf0, 0x95, 0xd8, 0xb6, 0xcd, 0x1b, 0xea, 0xca, 0x5c,
                 0xa7, 0x92, 0x5e, 0x29, 0x6e, 0x84, 0xaf, 0x17, 0x16, 0xd1, 0x6c, 0x89, 0xd3, 0x8b,
                 0x05, 0x9c, 0x8c, 0x18,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::TwistWithCovariance_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(336usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5196,88 +5144,9 @@ float64 z"####;
             const MD5SUM: &'static str = "38d53b26c78ee3db59b20ff3e8127275";
             const DEFINITION: &'static str = r####"# This represents an estimated twist with reference coordinate frame and timestamp.
 
-std_msgs/Header header
-TwistWithCovariance twist
-================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
-
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec
-================================================================================
-MSG: geometry_msgs/Twist
-# This expresses velocity in free space broken into its linear and angular parts.
-
-Vector3  linear
-Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
-
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
-
-float64 x
-float64 y
-float64 z
-================================================================================
-MSG: geometry_msgs/TwistWithCovariance
-# This expresses velocity in free space with uncertainty.
-
-Twist twist
-
-# Row-major representation of the 6x6 covariance matrix
-# The orientation parameters use a fixed-axis representation.
-# In order, the parameters are:
-# (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)
-float64[36] covariance
-================================================================================
-MSG: geometry_msgs/Twist
-# This expresses velocity in free space broken into its linear and angular parts.
-
-Vector3  linear
-Vector3  angular
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
-
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
-
-float64 x
-float64 y
-float64 z
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
-
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
-
-float64 x
-float64 y
-float64 z
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
-
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
+std_msgs/Header header
+TwistWithCovariance twist
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: std_msgs/Header
 # Standard metadata for higher-level stamped data types.
@@ -5289,6 +5158,7 @@ builtin_interfaces/Time stamp
 
 # Transform frame with which this data is associated.
 string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -5301,7 +5171,39 @@ int32 sec
 # e.g.
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+uint32 nanosec
+
+================================================================================
+MSG: geometry_msgs/TwistWithCovariance
+# This expresses velocity in free space with uncertainty.
+
+Twist twist
+
+# Row-major representation of the 6x6 covariance matrix
+# The orientation parameters use a fixed-axis representation.
+# In order, the parameters are:
+# (x, y, z, rotation about X axis, rotation about Y axis, rotation about Z axis)
+float64[36] covariance
+
+================================================================================
+MSG: geometry_msgs/Twist
+# This expresses velocity in free space broken into its linear and angular parts.
+
+Vector3  linear
+Vector3  angular
+
+================================================================================
+MSG: geometry_msgs/Vector3
+# This represents a vector in free space.
+
+# This is semantically different than a point.
+# A vector is always anchored at the origin.
+# When a transform is applied to a vector, only the rotational component is applied.
+
+float64 x
+float64 y
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x77, 0xb6, 0x74, 0x34, 0x53, 0x1e, 0x65, 0x29, 0xb7, 0xa0, 0x09, 0x13, 0x57, 0xb1,
                 0x86, 0xb6, 0xeb, 0xdb, 0x17, 0xfd, 0x9f, 0xfd, 0x3e, 0x0c, 0x7c, 0xe9, 0xd3, 0xfb,
@@ -5309,6 +5211,12 @@ uint32 nanosec"####;
             ];
             const ROS2_TYPE_NAME: &'static str =
                 "geometry_msgs::msg::dds_::TwistWithCovarianceStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#twist)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5337,13 +5245,16 @@ uint32 nanosec"####;
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xcc, 0x12, 0xfe, 0x83, 0xe4, 0xc0, 0x27, 0x19, 0xf1, 0xce, 0x80, 0x70, 0xbf, 0xd1,
                 0x4a, 0xec, 0xd4, 0x0f, 0x75, 0xa9, 0x66, 0x96, 0xa6, 0x7a, 0x2a, 0x1f, 0x37, 0xf7,
                 0xdb, 0xb0, 0x76, 0x5d,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Vector3_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(24usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5370,30 +5281,7 @@ float64 z"####;
 
 std_msgs/Header header
 Vector3 vector
-================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
-
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
-
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: std_msgs/Header
 # Standard metadata for higher-level stamped data types.
@@ -5405,6 +5293,7 @@ builtin_interfaces/Time stamp
 
 # Transform frame with which this data is associated.
 string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -5417,13 +5306,32 @@ int32 sec
 # e.g.
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+uint32 nanosec
+
+================================================================================
+MSG: geometry_msgs/Vector3
+# This represents a vector in free space.
+
+# This is semantically different than a point.
+# A vector is always anchored at the origin.
+# When a transform is applied to a vector, only the rotational component is applied.
+
+float64 x
+float64 y
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xd4, 0x82, 0x96, 0x22, 0x28, 0x8c, 0xbb, 0x44, 0x38, 0x86, 0xe7, 0xea, 0x94, 0xea,
                 0x56, 0x71, 0xa3, 0xb1, 0xbe, 0x6b, 0xab, 0x4a, 0xd0, 0x42, 0x24, 0x43, 0x2a, 0x65,
                 0xf7, 0xd7, 0x88, 0x7a,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Vector3Stamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#vector)?;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5447,6 +5355,7 @@ uint32 nanosec"####;
 
 Vector3  force
 Vector3  torque
+
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space.
@@ -5457,13 +5366,16 @@ MSG: geometry_msgs/Vector3
 
 float64 x
 float64 y
-float64 z"####;
+float64 z
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x01, 0x8e, 0x85, 0x19, 0xd5, 0x7c, 0x16, 0xad, 0xbe, 0x97, 0xc9, 0xfe, 0x14, 0x60,
                 0xef, 0x21, 0xfe, 0xc7, 0xe3, 0x1b, 0xc5, 0x41, 0xde, 0x3d, 0x65, 0x3a, 0x35, 0x89,
                 0x56, 0x77, 0xce, 0x52,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::Wrench_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(48usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5487,6 +5399,19 @@ float64 z"####;
 
 std_msgs/Header header
 Wrench wrench
+
+================================================================================
+MSG: std_msgs/Header
+# Standard metadata for higher-level stamped data types.
+# This is generally used to communicate timestamped data
+# in a particular coordinate frame.
+
+# Two-integer timestamp that is expressed as seconds and nanoseconds.
+builtin_interfaces/Time stamp
+
+# Transform frame with which this data is associated.
+string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -5500,23 +5425,14 @@ int32 sec
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
 uint32 nanosec
-================================================================================
-MSG: geometry_msgs/Vector3
-# This represents a vector in free space.
-
-# This is semantically different than a point.
-# A vector is always anchored at the origin.
-# When a transform is applied to a vector, only the rotational component is applied.
 
-float64 x
-float64 y
-float64 z
 ================================================================================
 MSG: geometry_msgs/Wrench
 # This represents force in free space, separated into its linear and angular parts.
 
 Vector3  force
 Vector3  torque
+
 ================================================================================
 MSG: geometry_msgs/Vector3
 # This represents a vector in free space.
@@ -5528,36 +5444,19 @@ MSG: geometry_msgs/Vector3
 float64 x
 float64 y
 float64 z
-================================================================================
-MSG: std_msgs/Header
-# Standard metadata for higher-level stamped data types.
-# This is generally used to communicate timestamped data
-# in a particular coordinate frame.
-
-# Two-integer timestamp that is expressed as seconds and nanoseconds.
-builtin_interfaces/Time stamp
-
-# Transform frame with which this data is associated.
-string frame_id
-================================================================================
-MSG: builtin_interfaces/Time
-# This message communicates ROS Time defined here:
-# https://design.ros2.org/articles/clock_and_time.html
-
-# The seconds component, valid over all int32 values.
-int32 sec
-
-# The nanoseconds component, valid in the range [0, 1e9), to be added to the seconds component. 
-# e.g.
-# The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
-# The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x8d, 0xc3, 0xde, 0xaf, 0x06, 0xb2, 0xab, 0x28, 0x1f, 0x9f, 0x9a, 0x74, 0x2a, 0x89,
                 0x61, 0xc3, 0x28, 0xca, 0x7c, 0xec, 0x16, 0xe3, 0xfd, 0x65, 0x86, 0xd3, 0xa5, 0xc8,
                 0x3f, 0xa7, 0x8f, 0x77,
             ];
             const ROS2_TYPE_NAME: &'static str = "geometry_msgs::msg::dds_::WrenchStamped_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#header)?
+                    + ::roslibrust::RosMessageType::encoded_len(&self.r#wrench)?;
+                ::std::option::Option::Some(len)
+            }
         }
     }
     #[allow(unused_imports)]
@@ -5606,6 +5505,7 @@ char[16] client_gid
 # Sequence number for the request
 # Combined with the client ID, this creates a unique ID for the service transaction
 int64 sequence_number
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -5618,13 +5518,16 @@ int32 sec
 # e.g.
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+uint32 nanosec
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x41, 0xbc, 0xbb, 0xe0, 0x7a, 0x75, 0xc9, 0xb5, 0x2b, 0xc9, 0x6b, 0xfd, 0x5c, 0x24,
                 0xd7, 0xf0, 0xfc, 0x0a, 0x08, 0xc0, 0xcb, 0x79, 0x21, 0xb3, 0x37, 0x3c, 0x57, 0x32,
                 0x34, 0x5a, 0x6f, 0x45,
             ];
             const ROS2_TYPE_NAME: &'static str = "service_msgs::msg::dds_::ServiceEventInfo_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(33usize);
         }
         #[allow(unused)]
         impl ServiceEventInfo {
@@ -5661,13 +5564,16 @@ uint32 nanosec"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-bool data"####;
+bool data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xfe, 0xb9, 0x1e, 0x99, 0x5f, 0xf9, 0xeb, 0xd0, 0x9c, 0x0c, 0xb3, 0xd2, 0xae, 0xd1,
                 0x8b, 0x11, 0x07, 0x75, 0x85, 0x83, 0x9f, 0xb5, 0xdb, 0x80, 0x19, 0x3b, 0x62, 0xd7,
                 0x45, 0x28, 0xf6, 0xc9,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Bool_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(1usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5691,13 +5597,16 @@ bool data"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-byte data"####;
+byte data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xe2, 0x8c, 0xa2, 0xc6, 0x2f, 0x3f, 0xb1, 0x0c, 0x20, 0x78, 0x90, 0x75, 0x5a, 0xa7,
                 0xa5, 0xa7, 0x70, 0xcc, 0xde, 0x56, 0x46, 0xfd, 0x66, 0xb4, 0x52, 0xf4, 0x85, 0xc4,
                 0x80, 0x92, 0xf3, 0x27,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Byte_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(1usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5728,16 +5637,7 @@ byte data"####;
 
 MultiArrayLayout  layout        # specification of data layout
 byte[]            data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-# This was originally provided as an example message.
-# It is deprecated as of Foxy
-# It is recommended to create your own semantically meaningful message.
-# However if you would like to continue using this please use the equivalent in example_msgs.
 
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # This was originally provided as an example message.
@@ -5771,6 +5671,7 @@ MSG: std_msgs/MultiArrayLayout
 
 MultiArrayDimension[] dim # Array of dimension properties
 uint32 data_offset        # padding bytes at front of data
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 # This was originally provided as an example message.
@@ -5780,13 +5681,21 @@ MSG: std_msgs/MultiArrayDimension
 
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x69, 0x2e, 0xff, 0x26, 0xdd, 0x8c, 0xa7, 0x62, 0x3e, 0x4e, 0x90, 0xa0, 0x82, 0xf7,
                 0xd8, 0x3f, 0x1c, 0xf5, 0xde, 0xb0, 0xb7, 0xba, 0x74, 0x8a, 0x2d, 0x4d, 0x5f, 0xbc,
                 0xa7, 0x91, 0xdb, 0x7d,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::ByteMultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len();
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5810,13 +5719,16 @@ uint32 stride  # stride of given dimension"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-char data"####;
+char data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x3a, 0xd2, 0xd0, 0x4d, 0xd2, 0x9b, 0xa1, 0x9d, 0x04, 0xb1, 0x66, 0x59, 0xaf, 0xa3,
                 0xcc, 0xae, 0xdd, 0x69, 0x19, 0x14, 0xb0, 0x2a, 0x64, 0xe8, 0x2e, 0x25, 0x2f, 0x2f,
                 0xa6, 0xa5, 0x86, 0xa9,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Char_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(1usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5841,13 +5753,16 @@ char data"####;
             const DEFINITION: &'static str = r####"float32 r
 float32 g
 float32 b
-float32 a"####;
+float32 a
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x77, 0xa7, 0xa5, 0xb9, 0xae, 0x47, 0x73, 0x06, 0x09, 0x76, 0x65, 0x10, 0x6e, 0x04,
                 0x13, 0xba, 0x74, 0x44, 0x02, 0x45, 0xb1, 0xf3, 0xd0, 0xc6, 0xd6, 0x40, 0x5f, 0xe5,
                 0xc7, 0x81, 0x3f, 0xe8,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::ColorRGBA_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(16usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5871,6 +5786,8 @@ float32 a"####;
                 0x26, 0xa9, 0x63, 0x12,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Empty_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(0usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5894,13 +5811,16 @@ float32 a"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-float32 data"####;
+float32 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x71, 0x70, 0xd3, 0xd8, 0xf8, 0x41, 0xf7, 0xbe, 0x31, 0x72, 0xce, 0x5f, 0x4f, 0x59,
                 0xf3, 0xa4, 0xd7, 0xf6, 0x3b, 0x04, 0x47, 0xe8, 0xb3, 0x33, 0x27, 0x60, 0x1a, 0xd6,
                 0x4d, 0x83, 0xd6, 0xe2,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Float32_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(4usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -5930,16 +5850,7 @@ float32 data"####;
 
 MultiArrayLayout  layout        # specification of data layout
 float32[]         data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-# This was originally provided as an example message.
-# It is deprecated as of Foxy
-# It is recommended to create your own semantically meaningful message.
-# However if you would like to continue using this please use the equivalent in example_msgs.
 
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # This was originally provided as an example message.
@@ -5973,6 +5884,7 @@ MSG: std_msgs/MultiArrayLayout
 
 MultiArrayDimension[] dim # Array of dimension properties
 uint32 data_offset        # padding bytes at front of data
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 # This was originally provided as an example message.
@@ -5982,13 +5894,21 @@ MSG: std_msgs/MultiArrayDimension
 
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x05, 0x99, 0xf6, 0xf8, 0x5b, 0x4b, 0xfc, 0xa3, 0x79, 0x87, 0x3a, 0x0b, 0x43, 0x75,
                 0xa0, 0xac, 0xa0, 0x22, 0x15, 0x6b, 0xd2, 0xd7, 0x02, 0x12, 0x75, 0xd1, 0x16, 0xed,
                 0x1f, 0xa8, 0xbf, 0xe0,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Float32MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 4usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6012,13 +5932,16 @@ uint32 stride  # stride of given dimension"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-float64 data"####;
+float64 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x70, 0x5b, 0xa9, 0xc3, 0xd1, 0xa0, 0x9d, 0xf4, 0x37, 0x37, 0xeb, 0x67, 0x09, 0x55,
                 0x34, 0xde, 0x36, 0xfd, 0x42, 0x6c, 0x05, 0x87, 0x77, 0x9b, 0xda, 0x2b, 0xc5, 0x1f,
                 0xe7, 0x90, 0x18, 0x2a,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Float64_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(8usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6048,16 +5971,7 @@ float64 data"####;
 
 MultiArrayLayout  layout        # specification of data layout
 float64[]         data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-# This was originally provided as an example message.
-# It is deprecated as of Foxy
-# It is recommended to create your own semantically meaningful message.
-# However if you would like to continue using this please use the equivalent in example_msgs.
 
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # This was originally provided as an example message.
@@ -6091,6 +6005,7 @@ MSG: std_msgs/MultiArrayLayout
 
 MultiArrayDimension[] dim # Array of dimension properties
 uint32 data_offset        # padding bytes at front of data
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 # This was originally provided as an example message.
@@ -6100,13 +6015,21 @@ MSG: std_msgs/MultiArrayDimension
 
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x10, 0x25, 0xdd, 0xc6, 0xb9, 0x55, 0x2d, 0x19, 0x1f, 0x89, 0xef, 0x1a, 0x8d, 0x2f,
                 0x60, 0xf3, 0xd3, 0x73, 0xe2, 0x8b, 0x28, 0x3d, 0x88, 0x91, 0xdd, 0xcc, 0x97, 0x4e,
                 0x8c, 0x55, 0x39, 0x7f,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Float64MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 8usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6135,6 +6058,7 @@ builtin_interfaces/Time stamp
 
 # Transform frame with which this data is associated.
 string frame_id
+
 ================================================================================
 MSG: builtin_interfaces/Time
 # This message communicates ROS Time defined here:
@@ -6147,13 +6071,44 @@ int32 sec
 # e.g.
 # The time -1.7 seconds is represented as {sec: -2, nanosec: 3e8}
 # The time 1.7 seconds is represented as {sec: 1, nanosec: 7e8}
-uint32 nanosec"####;
+uint32 nanosec
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xf4, 0x9f, 0xb3, 0xae, 0x2c, 0xf0, 0x70, 0xf7, 0x93, 0x64, 0x5f, 0xf7, 0x49, 0x68,
                 0x3a, 0xc6, 0xb0, 0x62, 0x03, 0xe4, 0x1c, 0x89, 0x1e, 0x17, 0x70, 0x1b, 0x1c, 0xb5,
                 0x97, 0xce, 0x6a, 0x01,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Header_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#stamp)?
+                    + 4
+                    + self.r#frame_id.len();
+                ::std::option::Option::Some(len)
+            }
+        }
+        impl ::std::convert::From<::roslibrust::codegen::HeaderCompat> for Header {
+            fn from(header: ::roslibrust::codegen::HeaderCompat) -> Self {
+                Self {
+                    r#stamp: {
+                        let (sec, nanosec) = header.stamp.to_sec_nanosec();
+                        builtin_interfaces::Time { sec, nanosec }
+                    },
+                    r#frame_id: header.frame_id,
+                }
+            }
+        }
+        impl ::std::convert::From<Header> for ::roslibrust::codegen::HeaderCompat {
+            fn from(header: Header) -> Self {
+                Self {
+                    seq: 0,
+                    stamp: ::roslibrust::codegen::integral_types::Time::from_sec_nanosec(
+                        header.r#stamp.r#sec,
+                        header.r#stamp.r#nanosec,
+                    ),
+                    frame_id: header.r#frame_id,
+                }
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6177,13 +6132,16 @@ uint32 nanosec"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-int16 data"####;
+int16 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x1d, 0xcc, 0x34, 0x64, 0xe4, 0x7c, 0x28, 0x8a, 0x55, 0xf9, 0x43, 0xa3, 0x89, 0xd3,
                 0x37, 0xcd, 0xb0, 0x68, 0x04, 0xde, 0x3f, 0x5c, 0xd7, 0xa2, 0x66, 0xb0, 0xde, 0x71,
                 0x8e, 0xee, 0x17, 0xe5,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int16_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(2usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6213,16 +6171,7 @@ int16 data"####;
 
 MultiArrayLayout  layout        # specification of data layout
 int16[]           data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-# This was originally provided as an example message.
-# It is deprecated as of Foxy
-# It is recommended to create your own semantically meaningful message.
-# However if you would like to continue using this please use the equivalent in example_msgs.
 
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # This was originally provided as an example message.
@@ -6256,6 +6205,7 @@ MSG: std_msgs/MultiArrayLayout
 
 MultiArrayDimension[] dim # Array of dimension properties
 uint32 data_offset        # padding bytes at front of data
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 # This was originally provided as an example message.
@@ -6265,13 +6215,21 @@ MSG: std_msgs/MultiArrayDimension
 
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xb5, 0x88, 0x10, 0xe8, 0xe5, 0xb9, 0x0f, 0xb1, 0x9a, 0x50, 0x62, 0x46, 0x9e, 0xb8,
                 0x40, 0x9f, 0x5a, 0xb1, 0x1a, 0x44, 0x6d, 0x60, 0xde, 0x71, 0x57, 0xa1, 0x45, 0x7e,
                 0x52, 0xa0, 0x76, 0xce,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int16MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 2usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6295,13 +6253,16 @@ uint32 stride  # stride of given dimension"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-int32 data"####;
+int32 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xb6, 0x57, 0x8d, 0xed, 0x3c, 0x58, 0xc6, 0x26, 0xcf, 0xe8, 0xd1, 0xa6, 0xfb, 0x6e,
                 0x04, 0xf7, 0x06, 0xf9, 0x7e, 0x9f, 0x03, 0xd2, 0x72, 0x7c, 0x9f, 0xf4, 0xe7, 0x4b,
                 0x1c, 0xef, 0x0d, 0xeb,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int32_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(4usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6331,16 +6292,7 @@ int32 data"####;
 
 MultiArrayLayout  layout        # specification of data layout
 int32[]           data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-# This was originally provided as an example message.
-# It is deprecated as of Foxy
-# It is recommended to create your own semantically meaningful message.
-# However if you would like to continue using this please use the equivalent in example_msgs.
 
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # This was originally provided as an example message.
@@ -6374,6 +6326,7 @@ MSG: std_msgs/MultiArrayLayout
 
 MultiArrayDimension[] dim # Array of dimension properties
 uint32 data_offset        # padding bytes at front of data
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 # This was originally provided as an example message.
@@ -6383,13 +6336,21 @@ MSG: std_msgs/MultiArrayDimension
 
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x84, 0xa7, 0x34, 0x63, 0x23, 0x52, 0x5d, 0x1b, 0x4d, 0xfc, 0xa8, 0x99, 0xdf, 0x38,
                 0x20, 0xf2, 0x45, 0xe5, 0x40, 0x09, 0xda, 0xc5, 0xa6, 0xb6, 0x92, 0x17, 0xd1, 0x4f,
                 0xde, 0xfd, 0x17, 0x01,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int32MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 4usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6413,13 +6374,16 @@ uint32 stride  # stride of given dimension"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-int64 data"####;
+int64 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x8c, 0xd1, 0x04, 0x8c, 0x2f, 0x18, 0x6b, 0x6b, 0xd9, 0xa9, 0x24, 0x72, 0xdc, 0x1c,
                 0xe5, 0x17, 0x23, 0xc0, 0x83, 0x3a, 0x22, 0x1e, 0x2b, 0x7a, 0xec, 0xff, 0xf1, 0x11,
                 0x77, 0x4f, 0x4b, 0x49,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int64_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(8usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6449,16 +6413,7 @@ int64 data"####;
 
 MultiArrayLayout  layout        # specification of data layout
 int64[]           data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-# This was originally provided as an example message.
-# It is deprecated as of Foxy
-# It is recommended to create your own semantically meaningful message.
-# However if you would like to continue using this please use the equivalent in example_msgs.
 
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # This was originally provided as an example message.
@@ -6492,6 +6447,7 @@ MSG: std_msgs/MultiArrayLayout
 
 MultiArrayDimension[] dim # Array of dimension properties
 uint32 data_offset        # padding bytes at front of data
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 # This was originally provided as an example message.
@@ -6501,13 +6457,21 @@ MSG: std_msgs/MultiArrayDimension
 
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xe6, 0x0f, 0x9f, 0xe3, 0x4d, 0x69, 0x7f, 0x09, 0x39, 0xad, 0x49, 0xd3, 0x31, 0x58,
                 0x69, 0x3c, 0x12, 0x77, 0xfb, 0xac, 0x0e, 0x2f, 0x04, 0xb7, 0xc2, 0x99, 0x5d, 0xc2,
                 0x1c, 0x89, 0xb4, 0x22,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int64MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 8usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6531,13 +6495,16 @@ uint32 stride  # stride of given dimension"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-int8 data"####;
+int8 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x26, 0x52, 0x50, 0x65, 0xa4, 0x03, 0xd9, 0x72, 0xcb, 0x67, 0x2f, 0x07, 0x77, 0xe3,
                 0x33, 0xf0, 0xc7, 0x99, 0xad, 0x44, 0x4a, 0xe5, 0xfc, 0xd7, 0x9e, 0x43, 0xd1, 0xe7,
                 0x3b, 0xd0, 0xf4, 0x40,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int8_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(1usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6567,16 +6534,7 @@ int8 data"####;
 
 MultiArrayLayout  layout        # specification of data layout
 int8[]            data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-# This was originally provided as an example message.
-# It is deprecated as of Foxy
-# It is recommended to create your own semantically meaningful message.
-# However if you would like to continue using this please use the equivalent in example_msgs.
 
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # This was originally provided as an example message.
@@ -6610,6 +6568,7 @@ MSG: std_msgs/MultiArrayLayout
 
 MultiArrayDimension[] dim # Array of dimension properties
 uint32 data_offset        # padding bytes at front of data
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 # This was originally provided as an example message.
@@ -6619,13 +6578,21 @@ MSG: std_msgs/MultiArrayDimension
 
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xf2, 0x19, 0x98, 0xd4, 0xb4, 0x92, 0xab, 0xd6, 0x33, 0x30, 0x76, 0x5d, 0x75, 0xd5,
                 0x83, 0x12, 0x38, 0xd4, 0x00, 0x74, 0x03, 0x86, 0xf6, 0x51, 0xf1, 0x3a, 0x87, 0x2a,
                 0x4d, 0x21, 0x88, 0xdb,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::Int8MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len();
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6653,13 +6620,19 @@ uint32 stride  # stride of given dimension"####;
 
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x5e, 0x77, 0x3a, 0x60, 0xa4, 0xc7, 0xfc, 0x8a, 0x54, 0x98, 0x5f, 0x30, 0x7c, 0x78,
                 0x37, 0xaa, 0x29, 0x94, 0x25, 0x2a, 0x12, 0x6c, 0x30, 0x19, 0x57, 0xa2, 0x4e, 0x31,
                 0x28, 0x2c, 0x9c, 0xbe,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::MultiArrayDimension_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = 4 + self.r#label.len() + 4usize + 4usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6710,6 +6683,7 @@ uint32 stride  # stride of given dimension"####;
 
 MultiArrayDimension[] dim # Array of dimension properties
 uint32 data_offset        # padding bytes at front of data
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 # This was originally provided as an example message.
@@ -6719,13 +6693,25 @@ MSG: std_msgs/MultiArrayDimension
 
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x4c, 0x66, 0xe6, 0xf7, 0x8e, 0x74, 0x0a, 0xc1, 0x03, 0xa9, 0x4c, 0xf6, 0x32, 0x59,
                 0xf9, 0x68, 0xe4, 0x8c, 0x61, 0x7e, 0x76, 0x99, 0xe8, 0x29, 0xb6, 0x3c, 0x21, 0xa5,
                 0xcb, 0x50, 0xda, 0xc6,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::MultiArrayLayout_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = 4
+                    + self
+                        .r#dim
+                        .iter()
+                        .map(::roslibrust::RosMessageType::encoded_len)
+                        .sum::<::std::option::Option<usize>>()?
+                    + 4usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6749,13 +6735,19 @@ uint32 stride  # stride of given dimension"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-string data"####;
+string data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xdf, 0x66, 0x8c, 0x74, 0x04, 0x82, 0xbb, 0xd4, 0x8f, 0xb3, 0x9d, 0x76, 0xa7, 0x0d,
                 0xfd, 0x4b, 0xd5, 0x9d, 0xb1, 0x28, 0x80, 0x21, 0x74, 0x35, 0x03, 0x25, 0x9e, 0x94,
                 0x8f, 0x6b, 0x1a, 0x18,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::String_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = 4 + self.r#data.len();
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6779,13 +6771,16 @@ string data"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-uint16 data"####;
+uint16 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x08, 0xa4, 0x06, 0xe4, 0xb0, 0x22, 0xbc, 0x22, 0xe9, 0x07, 0xf9, 0x85, 0xd6, 0xa9,
                 0xe9, 0xdd, 0x1d, 0x4f, 0xbe, 0xca, 0xe5, 0x73, 0x54, 0x9c, 0xf4, 0x93, 0x50, 0x11,
                 0x3e, 0x77, 0x57, 0xb1,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt16_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(2usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6815,16 +6810,7 @@ uint16 data"####;
 
 MultiArrayLayout  layout        # specification of data layout
 uint16[]            data        # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-# This was originally provided as an example message.
-# It is deprecated as of Foxy
-# It is recommended to create your own semantically meaningful message.
-# However if you would like to continue using this please use the equivalent in example_msgs.
 
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # This was originally provided as an example message.
@@ -6858,6 +6844,7 @@ MSG: std_msgs/MultiArrayLayout
 
 MultiArrayDimension[] dim # Array of dimension properties
 uint32 data_offset        # padding bytes at front of data
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 # This was originally provided as an example message.
@@ -6867,13 +6854,21 @@ MSG: std_msgs/MultiArrayDimension
 
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x94, 0xfe, 0x73, 0x42, 0x8e, 0xc6, 0x3b, 0xae, 0xcc, 0x77, 0x4f, 0x8f, 0xb8, 0x24,
                 0x06, 0x12, 0x3e, 0x92, 0x91, 0xcf, 0x72, 0x8f, 0x1b, 0x7c, 0x91, 0xca, 0xf5, 0x33,
                 0x51, 0x29, 0x49, 0x2b,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt16MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 2usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6897,13 +6892,16 @@ uint32 stride  # stride of given dimension"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-uint32 data"####;
+uint32 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xa5, 0xc8, 0x74, 0x82, 0x9b, 0x75, 0x2b, 0xc5, 0xfa, 0x19, 0x00, 0x24, 0xb0, 0xad,
                 0x76, 0xf5, 0x78, 0xcc, 0x27, 0x82, 0x71, 0xe8, 0x55, 0xc7, 0xd0, 0x2a, 0x81, 0x8b,
                 0x35, 0x16, 0xfb, 0x4a,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt32_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(4usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -6933,16 +6931,7 @@ uint32 data"####;
 
 MultiArrayLayout  layout        # specification of data layout
 uint32[]          data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-# This was originally provided as an example message.
-# It is deprecated as of Foxy
-# It is recommended to create your own semantically meaningful message.
-# However if you would like to continue using this please use the equivalent in example_msgs.
 
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # This was originally provided as an example message.
@@ -6976,6 +6965,7 @@ MSG: std_msgs/MultiArrayLayout
 
 MultiArrayDimension[] dim # Array of dimension properties
 uint32 data_offset        # padding bytes at front of data
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 # This was originally provided as an example message.
@@ -6985,13 +6975,21 @@ MSG: std_msgs/MultiArrayDimension
 
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x6c, 0x25, 0x77, 0xc7, 0xad, 0x3c, 0xbd, 0xcc, 0x21, 0x64, 0xa4, 0x1c, 0x12, 0xf1,
                 0xd5, 0xad, 0x31, 0x4e, 0xa3, 0x20, 0xf3, 0xfb, 0x1e, 0xe4, 0x7e, 0x78, 0x01, 0x9f,
                 0xe1, 0x6b, 0xb5, 0xb0,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt32MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 4usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -7015,13 +7013,16 @@ uint32 stride  # stride of given dimension"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-uint64 data"####;
+uint64 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xfb, 0xdc, 0x52, 0x01, 0x8f, 0xc1, 0x37, 0x55, 0xdc, 0xe1, 0x80, 0x24, 0xd1, 0xa6,
                 0x71, 0xc8, 0x56, 0xaa, 0x8b, 0x4a, 0xaf, 0x63, 0xad, 0xfb, 0x09, 0x5b, 0x60, 0x8f,
                 0x98, 0xe8, 0xc9, 0x43,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt64_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(8usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -7051,16 +7052,7 @@ uint64 data"####;
 
 MultiArrayLayout  layout        # specification of data layout
 uint64[]          data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-# This was originally provided as an example message.
-# It is deprecated as of Foxy
-# It is recommended to create your own semantically meaningful message.
-# However if you would like to continue using this please use the equivalent in example_msgs.
 
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # This was originally provided as an example message.
@@ -7094,6 +7086,7 @@ MSG: std_msgs/MultiArrayLayout
 
 MultiArrayDimension[] dim # Array of dimension properties
 uint32 data_offset        # padding bytes at front of data
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 # This was originally provided as an example message.
@@ -7103,13 +7096,21 @@ MSG: std_msgs/MultiArrayDimension
 
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0xfc, 0x1c, 0x68, 0x5c, 0x2f, 0x76, 0xbd, 0xc6, 0x98, 0x3d, 0xa0, 0x25, 0xcb, 0x25,
                 0xd2, 0xdb, 0x5f, 0xb5, 0x15, 0x7b, 0x05, 0x9e, 0x30, 0x0f, 0x6d, 0x95, 0x7d, 0x86,
                 0xf9, 0x81, 0xb3, 0x66,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt64MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len() * 8usize;
+                ::std::option::Option::Some(len)
+            }
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -7133,13 +7134,16 @@ uint32 stride  # stride of given dimension"####;
 # It is recommended to create your own semantically meaningful message.
 # However if you would like to continue using this please use the equivalent in example_msgs.
 
-uint8 data"####;
+uint8 data
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x61, 0x38, 0xbd, 0x83, 0xd8, 0xc3, 0x56, 0x9c, 0xb8, 0x0a, 0x66, 0x7d, 0xb0, 0x3c,
                 0xfc, 0x16, 0x29, 0xf5, 0x29, 0xfe, 0xe7, 0x9d, 0x94, 0x4c, 0x39, 0xc3, 0x4e, 0x35,
                 0x2e, 0x72, 0xf0, 0x10,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt8_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> =
+                ::std::option::Option::Some(1usize);
         }
         #[allow(non_snake_case)]
         #[allow(dead_code)]
@@ -7170,16 +7174,7 @@ uint8 data"####;
 
 MultiArrayLayout  layout        # specification of data layout
 uint8[]           data          # array of data
-================================================================================
-MSG: std_msgs/MultiArrayDimension
-# This was originally provided as an example message.
-# It is deprecated as of Foxy
-# It is recommended to create your own semantically meaningful message.
-# However if you would like to continue using this please use the equivalent in example_msgs.
 
-string label   # label of given dimension
-uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension
 ================================================================================
 MSG: std_msgs/MultiArrayLayout
 # This was originally provided as an example message.
@@ -7213,6 +7208,7 @@ MSG: std_msgs/MultiArrayLayout
 
 MultiArrayDimension[] dim # Array of dimension properties
 uint32 data_offset        # padding bytes at front of data
+
 ================================================================================
 MSG: std_msgs/MultiArrayDimension
 # This was originally provided as an example message.
@@ -7222,13 +7218,21 @@ MSG: std_msgs/MultiArrayDimension
 
 string label   # label of given dimension
 uint32 size    # size of given dimension (in type units)
-uint32 stride  # stride of given dimension"####;
+uint32 stride  # stride of given dimension
+"####;
             const ROS2_HASH: &'static [u8; 32] = &[
                 0x56, 0x87, 0xe8, 0x61, 0xb8, 0xd3, 0x07, 0xa5, 0xe4, 0x8b, 0x75, 0x15, 0x46, 0x7a,
                 0xe7, 0xa5, 0xfc, 0x2d, 0xaf, 0x80, 0x5b, 0xd0, 0xce, 0x6d, 0x8e, 0x9e, 0x60, 0x4b,
                 0xad, 0xe9, 0xf3, 0x85,
             ];
             const ROS2_TYPE_NAME: &'static str = "std_msgs::msg::dds_::UInt8MultiArray_";
+            const SERIALIZED_SIZE: ::std::option::Option<usize> = ::std::option::Option::None;
+            fn encoded_len(&self) -> ::std::option::Option<usize> {
+                let len = ::roslibrust::RosMessageType::encoded_len(&self.r#layout)?
+                    + 4
+                    + self.r#data.len();
+                ::std::option::Option::Some(len)
+            }
         }
     }
 
@@ -70,15 +70,21 @@ fn generate_messages() -> String {
         ROS2_BUILTIN_INTERFACES.into(),
     ];
 
+    // Doc comments are left out to keep the committed messages.rs compact
+    let options = roslibrust::codegen::CodegenOptions {
+        generate_docs: false,
+        ..Default::default()
+    };
+
     // Generate ROS1 messages
     let (ros1_source, _) =
-        roslibrust::codegen::find_and_generate_ros_messages_without_ros_package_path(ros1_paths)
+        roslibrust::codegen::find_and_generate_ros_messages_with_options(ros1_paths, &options)
             .expect("Failed to generate ROS1 messages");
     let ros1_source = format_rust_source(&ros1_source.to_string()).to_string();
 
     // Generate ROS2 messages
     let (ros2_source, _) =
-        roslibrust::codegen::find_and_generate_ros_messages_without_ros_package_path(ros2_paths)
+        roslibrust::codegen::find_and_generate_ros_messages_with_options(ros2_paths, &options)
             .expect("Failed to generate ROS2 messages");
     let ros2_source = format_rust_source(&ros2_source.to_string()).to_string();
 
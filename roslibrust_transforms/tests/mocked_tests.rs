@@ -401,3 +401,43 @@ async fn test_wait_for_transform_default_timeout() {
         "Should not have waited much longer than the buffer duration"
     );
 }
+
+#[tokio::test(flavor = "multi_thread")]
+async fn test_wait_for_transform_timeout_on_clock() {
+    use roslibrust_transforms::TransformManagerError;
+
+    let mock_ros = MockRos::new();
+
+    // Timeouts are measured on the mock's clock, which only moves when told to
+    let manager =
+        TransformManager::<Ros1TFMessage, _>::new(&mock_ros, std::time::Duration::from_secs(10))
+            .await
+            .expect("Failed to create TransformManager")
+            .with_clock(mock_ros.clock());
+    assert_eq!(manager.now(), Timestamp::zero());
+
+    let waiter = tokio::spawn(async move {
+        manager
+            .wait_for_transform(
+                "nonexistent_parent",
+                "nonexistent_child",
+                Timestamp::zero(),
+                Some(Duration::from_secs(5)),
+            )
+            .await
+    });
+
+    // Far longer than the timeout in wall time, but no time has passed on the clock
+    tokio::time::sleep(Duration::from_millis(200)).await;
+    assert!(!waiter.is_finished(), "Should wait for the clock to move");
+
+    mock_ros
+        .advance_time(Duration::from_secs(5))
+        .await
+        .expect("Failed to advance time");
+    let result = tokio::time::timeout(Duration::from_secs(1), waiter)
+        .await
+        .expect("Should time out once the clock passes the timeout")
+        .unwrap();
+    assert!(matches!(result, Err(TransformManagerError::Timeout(..))));
+}